@@ -0,0 +1,146 @@
+//! Ключи и конфиг Solana CLI.
+
+use std::path::{Path, PathBuf};
+
+use anchor_lang::prelude::Pubkey;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha512};
+
+/// Ed25519-ключ в формате `solana-keygen`: JSON-массив из 64 байт
+/// (32 байта seed + 32 байта открытого ключа).
+pub struct Keypair {
+    seed: [u8; 32],
+    pubkey: Pubkey,
+}
+
+impl Keypair {
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let pubkey = Pubkey::new_from_array(
+            EdwardsPoint::mul_base_clamped(Self::expand(&seed).0)
+                .compress()
+                .to_bytes(),
+        );
+        Self { seed, pubkey }
+    }
+
+    /// Новый случайный ключ (для аккаунта игры).
+    pub fn generate() -> Result<Self, String> {
+        use std::io::Read;
+        let mut seed = [0u8; 32];
+        std::fs::File::open("/dev/urandom")
+            .and_then(|mut f| f.read_exact(&mut seed))
+            .map_err(|err| format!("cannot read /dev/urandom: {}", err))?;
+        Ok(Self::from_seed(seed))
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("cannot read keypair {}: {}", path.display(), err))?;
+        let bytes: Vec<u8> = serde_json::from_str(&text)
+            .map_err(|err| format!("invalid keypair file {}: {}", path.display(), err))?;
+        if bytes.len() != 64 {
+            return Err(format!(
+                "invalid keypair file {}: expected 64 bytes",
+                path.display()
+            ));
+        }
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&bytes[..32]);
+        let keypair = Self::from_seed(seed);
+        if keypair.pubkey.as_ref() != &bytes[32..] {
+            return Err(format!(
+                "invalid keypair file {}: public key does not match secret",
+                path.display()
+            ));
+        }
+        Ok(keypair)
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    /// Подпись Ed25519 (RFC 8032).
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        let (scalar_bytes, prefix) = Self::expand(&self.seed);
+        let a = Scalar::from_bytes_mod_order(scalar_bytes);
+        let r = Scalar::from_bytes_mod_order_wide(
+            &Sha512::new()
+                .chain_update(prefix)
+                .chain_update(message)
+                .finalize()
+                .into(),
+        );
+        let big_r = EdwardsPoint::mul_base(&r).compress();
+        let k = Scalar::from_bytes_mod_order_wide(
+            &Sha512::new()
+                .chain_update(big_r.as_bytes())
+                .chain_update(self.pubkey.as_ref())
+                .chain_update(message)
+                .finalize()
+                .into(),
+        );
+        let s = r + k * a;
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(big_r.as_bytes());
+        signature[32..].copy_from_slice(s.as_bytes());
+        signature
+    }
+
+    /// Зажатый скаляр и префикс из sha512(seed).
+    fn expand(seed: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+        let hash = Sha512::digest(seed);
+        let mut scalar = [0u8; 32];
+        let mut prefix = [0u8; 32];
+        scalar.copy_from_slice(&hash[..32]);
+        prefix.copy_from_slice(&hash[32..]);
+        scalar[0] &= 248;
+        scalar[31] &= 127;
+        scalar[31] |= 64;
+        (scalar, prefix)
+    }
+}
+
+/// Настройки из конфига Solana CLI (`~/.config/solana/cli/config.yml`).
+pub struct SolanaConfig {
+    pub json_rpc_url: String,
+    pub keypair_path: PathBuf,
+}
+
+impl SolanaConfig {
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".config/solana/cli/config.yml"))
+    }
+
+    /// Читает `json_rpc_url` и `keypair_path`. Без файла — значения
+    /// по умолчанию Solana CLI (localhost и `~/.config/solana/id.json`).
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let home = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        let mut config = Self {
+            json_rpc_url: "http://127.0.0.1:8899".to_string(),
+            keypair_path: home.join(".config/solana/id.json"),
+        };
+        let path = match path.map(Path::to_path_buf).or_else(Self::default_path) {
+            Some(path) if path.exists() => path,
+            _ => return Ok(config),
+        };
+        let text = std::fs::read_to_string(&path)
+            .map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            match key.trim() {
+                "json_rpc_url" => config.json_rpc_url = value.to_string(),
+                "keypair_path" => config.keypair_path = PathBuf::from(value),
+                _ => {}
+            }
+        }
+        Ok(config)
+    }
+}
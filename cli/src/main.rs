@@ -0,0 +1,434 @@
+//! `backgammon-cli` — полный цикл игры из командной строки.
+//!
+//! RPC URL и ключ берутся из конфига Solana CLI, их можно переопределить
+//! флагами `--url`, `--keypair`, `--config`. Ключ из конфига — плательщик и
+//! «свой» игрок; второй подписант (соперник) для finish и refund передаётся
+//! `--opponent-keypair`, ход подписывает только ходящий.
+//! При ошибке программа печатает имя ошибки Anchor и выходит с кодом 1.
+
+mod keys;
+mod rpc;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::task::{Context, Poll, Waker};
+
+use anchor_lang::prelude::Pubkey;
+use backgammon_client::{
+    fetch, history, ix, FeeRoute, GameOptions, GameState, GameStatus, Instruction,
+};
+
+use crate::keys::{Keypair, SolanaConfig};
+use crate::rpc::{RpcClient, Simulation};
+
+const USAGE: &str = "\
+usage: backgammon-cli [--url <http-url>] [--keypair <path>] [--config <path>] <command>
+
+commands:
+  create --stake <lamports> --opponent <pubkey> [--move-fee <lamports> | --move-fee-bps <bps> | --decay-bps <bps>] [--game-id <n>]
+  join <game> [--password <text>]
+  move <game> --board <hex|file> --dice <d1,d2>
+  finish <game> --winner <pubkey> [--multiplier <n>] [--opponent-keypair <path>]
+  refund <game> [--force] [--opponent-keypair <path>]
+  show <game>
+  summary <game>
+  export <game> [--out <path>]";
+
+/// Флаги без значения.
+const SWITCHES: &[&str] = &["--force"];
+
+struct Args {
+    command: String,
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    fn parse(raw: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut raw = raw.peekable();
+        while let Some(arg) = raw.next() {
+            if !arg.starts_with("--") {
+                positional.push(arg);
+            } else if SWITCHES.contains(&arg.as_str()) {
+                options.insert(arg, String::new());
+            } else {
+                let value = raw
+                    .next()
+                    .ok_or_else(|| format!("option {} requires a value", arg))?;
+                options.insert(arg, value);
+            }
+        }
+        if positional.is_empty() {
+            return Err(USAGE.to_string());
+        }
+        let command = positional.remove(0);
+        Ok(Self {
+            command,
+            positional,
+            options,
+        })
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn required(&self, name: &str) -> Result<&str, String> {
+        self.get(name)
+            .ok_or_else(|| format!("{} requires {}", self.command, name))
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    fn game(&self) -> Result<Pubkey, String> {
+        let game = self
+            .positional
+            .first()
+            .ok_or_else(|| format!("{} requires a game address", self.command))?;
+        parse_pubkey(game)
+    }
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(value).map_err(|_| format!("invalid public key {}", value))
+}
+
+fn parse_number<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", name, value))
+}
+
+/// Доска из 48 hex-символов (24 байта i8) или 24 чисел через запятую/пробел;
+/// аргумент может быть путём к файлу с одним из этих форматов.
+fn parse_board(value: &str) -> Result<[i8; 24], String> {
+    let text = if Path::new(value).is_file() {
+        std::fs::read_to_string(value).map_err(|err| format!("cannot read {}: {}", value, err))?
+    } else {
+        value.to_string()
+    };
+    let text = text.trim();
+    let points: Vec<i8> = if text.len() == 48 && text.chars().all(|c| c.is_ascii_hexdigit()) {
+        (0..24)
+            .map(|i| u8::from_str_radix(&text[2 * i..2 * i + 2], 16).map(|b| b as i8))
+            .collect::<Result<_, _>>()
+            .map_err(|_| "invalid board hex".to_string())?
+    } else {
+        text.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(|s| parse_number("--board", s))
+            .collect::<Result<_, _>>()?
+    };
+    points
+        .try_into()
+        .map_err(|points: Vec<i8>| format!("board must have 24 points, got {}", points.len()))
+}
+
+fn parse_dice(value: &str) -> Result<[u8; 2], String> {
+    let dice: Vec<u8> = value
+        .split(',')
+        .map(|d| parse_number("--dice", d.trim()))
+        .collect::<Result<_, _>>()?;
+    dice.try_into()
+        .map_err(|_| format!("--dice expects two values, got {}", value))
+}
+
+/// Фьючерсы SDK поверх блокирующего RPC готовы сразу, исполнитель не нужен.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+struct Cli {
+    rpc: RpcClient,
+    payer: Keypair,
+    args: Args,
+}
+
+impl Cli {
+    fn fetch_game(&self, game: &Pubkey) -> Result<GameState, String> {
+        block_on(fetch::fetch_game(&self.rpc, game)).map_err(|err| err.to_string())
+    }
+
+    fn opponent(&self) -> Result<Option<Keypair>, String> {
+        self.args
+            .get("--opponent-keypair")
+            .map(|path| Keypair::read(Path::new(path)))
+            .transpose()
+    }
+
+    fn send(&self, instruction: Instruction, extra: &[&Keypair]) -> Result<(), String> {
+        let signature = self.rpc.send(&[instruction], &self.payer, extra)?;
+        println!("signature: {}", signature);
+        Ok(())
+    }
+
+    fn create(&self) -> Result<(), String> {
+        let stake = parse_number("--stake", self.args.required("--stake")?)?;
+        let opponent = parse_pubkey(self.args.required("--opponent")?)?;
+        let move_fee = self
+            .args
+            .get("--move-fee")
+            .map(|v| parse_number("--move-fee", v))
+            .transpose()?
+            .unwrap_or(0);
+        let move_fee_bps = self
+            .args
+            .get("--move-fee-bps")
+            .map(|v| parse_number("--move-fee-bps", v))
+            .transpose()?
+            .unwrap_or(0);
+        let decay_bps = self
+            .args
+            .get("--decay-bps")
+            .map(|v| parse_number("--decay-bps", v))
+            .transpose()?
+            .unwrap_or(0);
+        let game_id = match self.args.get("--game-id") {
+            Some(v) => parse_number("--game-id", v)?,
+            None => std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        };
+        let game = Keypair::generate()?;
+        println!("game: {}", game.pubkey());
+        self.send(
+            ix::init_game_ix(
+                &game.pubkey(),
+                &self.payer.pubkey(),
+                game_id,
+                stake,
+                move_fee,
+                &opponent,
+                GameOptions {
+                    move_fee_bps,
+                    decay_bps,
+                    ..GameOptions::default()
+                },
+            ),
+            &[&game],
+        )
+    }
+
+    fn join(&self) -> Result<(), String> {
+        let game = self.args.game()?;
+        let state = self.fetch_game(&game)?;
+        let password = self.args.get("--password").unwrap_or_default();
+        self.send(
+            ix::join_game_ix(
+                &game,
+                &state,
+                &self.payer.pubkey(),
+                password.as_bytes().to_vec(),
+                None,
+            ),
+            &[],
+        )
+    }
+
+    fn make_move(&self) -> Result<(), String> {
+        let game = self.args.game()?;
+        let board = parse_board(self.args.required("--board")?)?;
+        let dice = parse_dice(self.args.required("--dice")?)?;
+        let state = self.fetch_game(&game)?;
+        let config = block_on(fetch::fetch_config(&self.rpc)).map_err(|err| err.to_string())?;
+        let fee_recipient = if state.fee_routed {
+            let route: FeeRoute = block_on(fetch::fetch_account(
+                &self.rpc,
+                &backgammon_client::pda::fee_route(&game),
+            ))
+            .map_err(|err| err.to_string())?;
+            Some(route.fee_recipient)
+        } else {
+            None
+        };
+        self.send(
+            ix::make_move_ix(&game, &state, &config, board, dice, [0; 32], fee_recipient),
+            &[],
+        )
+    }
+
+    fn finish(&self) -> Result<(), String> {
+        let game = self.args.game()?;
+        let winner = parse_pubkey(self.args.required("--winner")?)?;
+        let multiplier = self
+            .args
+            .get("--multiplier")
+            .map(|v| parse_number("--multiplier", v))
+            .transpose()?
+            .unwrap_or(1);
+        let state = self.fetch_game(&game)?;
+        let config = block_on(fetch::fetch_config(&self.rpc)).map_err(|err| err.to_string())?;
+        let opponent = self.opponent()?;
+        self.send(
+            ix::finish_game_ix(&game, &state, &config, &winner, multiplier),
+            &opponent.iter().collect::<Vec<_>>(),
+        )
+    }
+
+    /// До входа соперника — cancel_before_join, с `--force` — force_refund
+    /// по тайм-ауту, иначе — своя половина взаимного manual_refund.
+    fn refund(&self) -> Result<(), String> {
+        let game = self.args.game()?;
+        let state = self.fetch_game(&game)?;
+        let opponent = self.opponent()?;
+        let instruction = if state.status == GameStatus::WaitingForPlayer2 {
+            ix::cancel_before_join_ix(&game, &state)
+        } else {
+            let config = block_on(fetch::fetch_config(&self.rpc)).map_err(|err| err.to_string())?;
+            if self.args.has("--force") {
+                ix::force_refund_ix(&game, &state, &config)
+            } else {
+                ix::manual_refund_ix(&game, &state, &config, &self.payer.pubkey())
+            }
+        };
+        self.send(instruction, &opponent.iter().collect::<Vec<_>>())
+    }
+
+    fn show(&self) -> Result<(), String> {
+        let game = self.args.game()?;
+        let g = self.fetch_game(&game)?;
+        println!("game:            {}", game);
+        println!("game_id:         {}", g.game_id);
+        println!("status:          {:?}", g.status);
+        println!("player1:         {}", g.player1);
+        println!("player2:         {}", g.player2);
+        println!("stake:           {} lamports", g.stake_lamports);
+        if g.move_fee_bps > 0 {
+            println!("move_fee:        {} bps of the pot", g.move_fee_bps);
+        } else {
+            println!("move_fee:        {} lamports", g.move_fee_lamports);
+        }
+        println!("pot:             {} lamports", g.pot_lamports);
+        if g.decay_bps > 0 {
+            println!(
+                "decay:           {} bps of the pot per move ({} lamports so far)",
+                g.decay_bps, g.decayed_lamports
+            );
+        }
+        println!(
+            "deposits:        {} / {} (fees {} / {})",
+            g.player1_deposit, g.player2_deposit, g.player1_fees_paid, g.player2_fees_paid
+        );
+        println!("move_index:      {}", g.move_index);
+        println!("current_turn:    player{}", g.current_turn);
+        println!("dice:            {} {}", g.dice[0], g.dice[1]);
+        println!("cube:            {} (owner {})", g.cube_value, g.cube_owner);
+        println!("preset:          {:?}", g.preset);
+        println!("last_activity:   slot {}", g.last_activity_slot);
+        if g.status == GameStatus::Finished {
+            println!("winner:          {}", g.winner);
+        }
+        let board = g
+            .board_points()
+            .map_err(|err| format!("cannot decode board: {}", err))?;
+        println!();
+        print!("{}", backgammon_core::render_board(&board));
+        Ok(())
+    }
+
+    /// Сводка игры одной симуляцией get_game_summary.
+    fn summary(&self) -> Result<(), String> {
+        let game = self.args.game()?;
+        let simulation = Simulation {
+            rpc: &self.rpc,
+            payer: self.payer.pubkey(),
+        };
+        let s = block_on(fetch::simulate_game_summary(&simulation, &game))
+            .map_err(|err| err.to_string())?;
+        println!("status:          {:?}", s.status);
+        println!("current_turn:    player{}", s.current_turn);
+        println!("move_index:      {}", s.move_index);
+        println!("pot:             {} lamports", s.pot_lamports);
+        println!(
+            "deposits:        {} / {} (fees {} / {})",
+            s.player1_deposit, s.player2_deposit, s.player1_fees_paid, s.player2_fees_paid
+        );
+        println!("idle:            {} slots", s.slots_since_activity);
+        println!(
+            "force_refund:    {}",
+            if s.force_refund_available {
+                "available"
+            } else {
+                "not yet"
+            }
+        );
+        Ok(())
+    }
+
+    /// Запись партии в формате `.mat` по логам её транзакций.
+    fn export(&self) -> Result<(), String> {
+        let game = self.args.game()?;
+        let g = self.fetch_game(&game)?;
+        let mut logs = Vec::new();
+        for signature in self.rpc.signatures_for_address(&game)? {
+            logs.extend(self.rpc.transaction_logs(&signature)?);
+        }
+        let transcript = history::transcript(&game, &g, &logs).map_err(|err| err.to_string())?;
+        let text = transcript
+            .to_mat()
+            .map_err(|err| format!("cannot export game {}: {:?}", game, err))?;
+        match self.args.get("--out") {
+            Some(path) => {
+                std::fs::write(path, text).map_err(|err| format!("cannot write {}: {}", path, err))
+            }
+            None => {
+                print!("{}", text);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args = Args::parse(std::env::args().skip(1))?;
+    let config = SolanaConfig::load(args.get("--config").map(Path::new))?;
+    let url = args
+        .get("--url")
+        .map(str::to_string)
+        .unwrap_or(config.json_rpc_url);
+    let keypair_path = args
+        .get("--keypair")
+        .map(PathBuf::from)
+        .unwrap_or(config.keypair_path);
+    let cli = Cli {
+        rpc: RpcClient::new(&url)?,
+        payer: Keypair::read(&keypair_path)?,
+        args,
+    };
+    match cli.args.command.as_str() {
+        "create" => cli.create(),
+        "join" => cli.join(),
+        "move" => cli.make_move(),
+        "finish" => cli.finish(),
+        "refund" => cli.refund(),
+        "show" => cli.show(),
+        "summary" => cli.summary(),
+        "export" => cli.export(),
+        other => Err(format!("unknown command {}\n{}", other, USAGE)),
+    }
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,338 @@
+//! Минимальный JSON-RPC клиент Solana поверх HTTP/1.0.
+//!
+//! Поддерживаются только `http://` адреса (локальный валидатор, RPC в
+//! закрытой сети); для публичных `https://` RPC нужен полноценный клиент.
+
+use std::future::Future;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use backgammon_client::fetch::{AccountSource, Simulator};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_hash::Hash;
+use solana_message::Message;
+
+use crate::keys::Keypair;
+
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct RpcClient {
+    host: String,
+    path: String,
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> Result<Self, String> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| format!("unsupported RPC URL {}: only http:// is supported", url))?;
+        let (host, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        let host = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:80", host)
+        };
+        Ok(Self {
+            host,
+            path: path.to_string(),
+        })
+    }
+
+    /// Вызов метода; ошибка RPC возвращается как объект `error` ответа.
+    fn call(&self, method: &str, params: Value) -> Result<Value, Value> {
+        let body =
+            json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params}).to_string();
+        let request = format!(
+            "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.path,
+            self.host,
+            body.len(),
+            body
+        );
+        let mut stream = TcpStream::connect(&self.host).map_err(
+            |err| json!({"message": format!("cannot connect to {}: {}", self.host, err)}),
+        )?;
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|err| json!({"message": format!("RPC request failed: {}", err)}))?;
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .map_err(|err| json!({"message": format!("RPC response failed: {}", err)}))?;
+        let payload = response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body)
+            .ok_or_else(|| json!({"message": "malformed HTTP response"}))?;
+        let mut reply: Value = serde_json::from_str(payload)
+            .map_err(|err| json!({"message": format!("malformed RPC response: {}", err)}))?;
+        match reply.get_mut("error") {
+            Some(error) => Err(error.take()),
+            None => Ok(reply["result"].take()),
+        }
+    }
+
+    pub fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, String> {
+        let result = self
+            .call(
+                "getAccountInfo",
+                json!([address.to_string(), {"encoding": "base64", "commitment": "confirmed"}]),
+            )
+            .map_err(|err| error_name(&err))?;
+        let Some(data) = result["value"]["data"][0].as_str() else {
+            return Ok(None);
+        };
+        BASE64
+            .decode(data)
+            .map(Some)
+            .map_err(|err| format!("invalid account data: {}", err))
+    }
+
+    fn latest_blockhash(&self) -> Result<Hash, String> {
+        let result = self
+            .call("getLatestBlockhash", json!([{"commitment": "confirmed"}]))
+            .map_err(|err| error_name(&err))?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or("malformed getLatestBlockhash response")?;
+        Hash::from_str(blockhash).map_err(|err| format!("invalid blockhash: {}", err))
+    }
+
+    /// Подписывает, отправляет и дожидается подтверждения транзакции.
+    /// Возвращает подпись; при ошибке программы — имя ошибки Anchor.
+    pub fn send(
+        &self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<String, String> {
+        let blockhash = self.latest_blockhash()?;
+        let message = Message::new_with_blockhash(instructions, Some(&payer.pubkey()), &blockhash);
+        let message_bytes = message.serialize();
+        let required = message.header.num_required_signatures as usize;
+        let mut wire = short_vec_len(required);
+        let mut signature = String::new();
+        for key in &message.account_keys[..required] {
+            let signer = std::iter::once(payer)
+                .chain(signers.iter().copied())
+                .find(|kp| kp.pubkey() == *key)
+                .ok_or_else(|| format!("missing signer {}", key))?;
+            let sig = signer.sign(&message_bytes);
+            if signature.is_empty() {
+                signature = bs58::encode(sig).into_string();
+            }
+            wire.extend_from_slice(&sig);
+        }
+        wire.extend_from_slice(&message_bytes);
+
+        self.call(
+            "sendTransaction",
+            json!([BASE64.encode(&wire), {"encoding": "base64", "preflightCommitment": "confirmed"}]),
+        )
+        .map_err(|err| error_name(&err))?;
+
+        let started = Instant::now();
+        while started.elapsed() < CONFIRM_TIMEOUT {
+            let result = self
+                .call("getSignatureStatuses", json!([[signature]]))
+                .map_err(|err| error_name(&err))?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    return Err(format!(
+                        "transaction {} failed: {}",
+                        signature, status["err"]
+                    ));
+                }
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed" | "finalized")
+                ) {
+                    return Ok(signature);
+                }
+            }
+            std::thread::sleep(Duration::from_millis(500));
+        }
+        Err(format!(
+            "transaction {} was not confirmed in time",
+            signature
+        ))
+    }
+}
+
+impl RpcClient {
+    /// Симуляция без подписей (sigVerify выключен, blockhash подставляет
+    /// RPC); возвращает return data транзакции.
+    pub fn simulate(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+    ) -> Result<Option<(Pubkey, Vec<u8>)>, String> {
+        let message = Message::new(instructions, Some(payer));
+        let required = message.header.num_required_signatures as usize;
+        let mut wire = short_vec_len(required);
+        wire.resize(wire.len() + 64 * required, 0);
+        wire.extend_from_slice(&message.serialize());
+
+        let result = self
+            .call(
+                "simulateTransaction",
+                json!([BASE64.encode(&wire), {
+                    "encoding": "base64",
+                    "sigVerify": false,
+                    "replaceRecentBlockhash": true,
+                    "commitment": "confirmed"
+                }]),
+            )
+            .map_err(|err| error_name(&err))?;
+        let value = &result["value"];
+        if !value["err"].is_null() {
+            return Err(error_name(&json!({ "data": value })));
+        }
+        let return_data = &value["returnData"];
+        if return_data.is_null() {
+            return Ok(None);
+        }
+        let program_id = return_data["programId"]
+            .as_str()
+            .and_then(|id| Pubkey::from_str(id).ok())
+            .ok_or("malformed simulateTransaction response")?;
+        let data = BASE64
+            .decode(return_data["data"][0].as_str().unwrap_or_default())
+            .map_err(|err| format!("invalid return data: {}", err))?;
+        Ok(Some((program_id, data)))
+    }
+}
+
+/// [`Simulator`] поверх RPC: плательщиком в симуляции должен быть
+/// существующий аккаунт, поэтому он задаётся здесь.
+pub struct Simulation<'a> {
+    pub rpc: &'a RpcClient,
+    pub payer: Pubkey,
+}
+
+impl Simulator for Simulation<'_> {
+    type Error = String;
+
+    fn return_data(
+        &self,
+        instruction: &Instruction,
+    ) -> impl Future<Output = Result<Option<(Pubkey, Vec<u8>)>, String>> {
+        let result = self
+            .rpc
+            .simulate(std::slice::from_ref(instruction), &self.payer);
+        async move { result }
+    }
+}
+
+/// Сколько подписей getSignaturesForAddress отдаёт за один запрос.
+const SIGNATURES_PAGE: usize = 1000;
+
+impl RpcClient {
+    /// Подписи успешных транзакций с адресом, от старых к новым.
+    pub fn signatures_for_address(&self, address: &Pubkey) -> Result<Vec<String>, String> {
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+        loop {
+            let mut options = json!({"limit": SIGNATURES_PAGE, "commitment": "confirmed"});
+            if let Some(before) = &before {
+                options["before"] = json!(before);
+            }
+            let result = self
+                .call(
+                    "getSignaturesForAddress",
+                    json!([address.to_string(), options]),
+                )
+                .map_err(|err| error_name(&err))?;
+            let page = result
+                .as_array()
+                .ok_or("malformed getSignaturesForAddress response")?;
+            for entry in page {
+                let signature = entry["signature"]
+                    .as_str()
+                    .ok_or("malformed getSignaturesForAddress response")?;
+                if entry["err"].is_null() {
+                    signatures.push(signature.to_string());
+                }
+                before = Some(signature.to_string());
+            }
+            if page.len() < SIGNATURES_PAGE {
+                break;
+            }
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    /// Логи подтверждённой транзакции.
+    pub fn transaction_logs(&self, signature: &str) -> Result<Vec<String>, String> {
+        let result = self
+            .call(
+                "getTransaction",
+                json!([signature, {
+                    "encoding": "json",
+                    "commitment": "confirmed",
+                    "maxSupportedTransactionVersion": 0
+                }]),
+            )
+            .map_err(|err| error_name(&err))?;
+        let logs = result["meta"]["logMessages"]
+            .as_array()
+            .ok_or_else(|| format!("no logs for transaction {}", signature))?;
+        Ok(logs
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+impl AccountSource for RpcClient {
+    type Error = String;
+
+    fn account_data(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = Result<Option<Vec<u8>>, String>> {
+        let result = self.get_account_data(address);
+        async move { result }
+    }
+}
+
+/// Длина в формате short_vec (compact-u16) сериализации транзакций.
+fn short_vec_len(mut len: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            out.push(byte);
+            return out;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Имя ошибки Anchor из логов симуляции («Error Code: GameNotActive»),
+/// иначе — ошибка транзакции или текст ошибки RPC.
+fn error_name(error: &Value) -> String {
+    let data = &error["data"];
+    let from_logs = data["logs"].as_array().and_then(|logs| {
+        logs.iter().filter_map(Value::as_str).find_map(|line| {
+            let (_, rest) = line.split_once("Error Code: ")?;
+            rest.split('.').next().map(str::to_string)
+        })
+    });
+    from_logs
+        .or_else(|| (!data["err"].is_null()).then(|| data["err"].to_string()))
+        .or_else(|| error["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| error.to_string())
+}
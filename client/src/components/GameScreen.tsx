@@ -1,5 +1,5 @@
 import { useState, useEffect, useCallback } from "react";
-import { getGameState } from "../solana/gameService";
+import { createMakeMoveInstruction, getGameState } from "../solana/gameService";
 import { getGame, logMove } from "../server/apiClient";
 import { wsClient } from "../server/wsClient";
 import { getCurrentKeypair, getProvider } from "../solana/anchorClient";
@@ -39,12 +39,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
   const [isProcessingFinish, setIsProcessingFinish] = useState(false);
   const [isProcessingManual, setIsProcessingManual] = useState(false);
   const [selectedCell, setSelectedCell] = useState<number | null>(null);
-  const [pendingMove, setPendingMove] = useState<{
-    boardPoints: number[];
-    dice: [number, number];
-    moveIndex: number;
-    transactionData?: number[];
-  } | null>(null);
   const [pendingFinish, setPendingFinish] = useState<{
     winnerPubkey: string;
     transactionData: number[];
@@ -163,7 +157,7 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
 
       try {
         logger.info("Sending transaction to Solana...");
-        // Транзакция уже полностью подписана обоими игроками
+        // Ход подписывает только ходящий игрок
         // Проверяем подписи перед отправкой
         logger.debug("Transaction signatures before serialization", {
           signaturesCount: signedTx.signatures.length,
@@ -190,15 +184,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
           })),
         });
 
-        // Проверяем, что все подписи присутствуют
-        if (signedTx.signatures.length < 2) {
-          const error = new Error(`Transaction missing signatures: expected 2, got ${signedTx.signatures.length}`);
-          logger.error("Transaction validation failed", error, {
-            signaturesCount: signedTx.signatures.length,
-          });
-          throw error;
-        }
-
         // Используем maxRetries для надежности
         const signature = await connection.sendRawTransaction(serializedTx, {
           skipPreflight: false,
@@ -216,7 +201,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
         // Обновляем локальное состояние доски и кубиков
         setBoardPoints(finalBoardPoints);
         setDice(finalDice);
-        setPendingMove(null);
         setSelectedCell(null);
 
         // Определяем новый ход (1 -> 2, 2 -> 1)
@@ -237,7 +221,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
         logger.error("Error submitting transaction to blockchain", error as Error, {
           gamePubkey,
         });
-        alert("Failed to submit move to blockchain. Please try again.");
         throw error;
       }
     },
@@ -285,37 +268,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
     [gamePubkey, onBack]
   );
 
-  // Отправка хода в блокчейн (после получения подписи от второго игрока) - fallback метод
-  const submitMoveToBlockchain = useCallback(
-    async (finalBoardPoints: number[], finalDice: [number, number]) => {
-      logger.info("Submitting move to blockchain (fallback method)", {
-        gamePubkey,
-        boardPoints: finalBoardPoints,
-        dice: finalDice,
-      });
-
-      try {
-        // Используем submitSignedTransactionToBlockchain если есть транзакция
-        // Иначе просто обновляем локальное состояние
-        setBoardPoints(finalBoardPoints);
-        setDice(finalDice);
-        setCurrentTurn((prev) => (prev === 1 ? 2 : 1));
-        setIsMyTurn((prev) => !prev);
-        setPendingMove(null);
-        setSelectedCell(null);
-
-        // Обновляем состояние из блокчейна
-        await refreshGameState();
-      } catch (error) {
-        logger.error("Error submitting move to blockchain", error as Error, {
-          gamePubkey,
-        });
-        alert("Failed to submit move to blockchain. Please try again.");
-      }
-    },
-    [gamePubkey, refreshGameState]
-  );
-
   // Подписываемся на WebSocket сообщения
   useEffect(() => {
     logger.info("Setting up WebSocket handlers", { gamePubkey, myPubkey });
@@ -362,7 +314,7 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
         });
 
         // Это оффчейн логирование хода, НЕ обрабатываем его как реальный ход
-        // Реальный ход будет обработан через move_signed после подписания транзакции
+        // Реальный ход ходящий игрок отправляет в блокчейн сам
         // Обновляем доску для отображения хода противника
         // НО НЕ обновляем кубики - они должны быть сброшены при turn_changed
         // Кубики противника не нужны новому игроку, он должен бросить свои
@@ -374,115 +326,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
 
         // НЕ переключаем ход здесь - это только оффчейн логирование
         // Ход будет переключен после успешной отправки в блокчейн через turn_changed
-      } else if (message.type === "move_request") {
-        // Запрос на подпись хода от другого игрока
-        logger.info("Received move request for signature", {
-          moveIndex: message.moveIndex,
-          boardPoints: message.boardPoints,
-          dice: message.dice,
-          hasTransactionData: !!message.transactionData,
-        });
-
-        // Сохраняем как pending move
-        if (message.boardPoints && message.dice && message.transactionData) {
-          logger.info("Setting pending move for signature", {
-            moveIndex: message.moveIndex,
-            hasTransactionData: !!message.transactionData,
-            transactionDataLength: message.transactionData.length,
-          });
-          setPendingMove({
-            boardPoints: message.boardPoints,
-            dice: [message.dice[0], message.dice[1]],
-            moveIndex: message.moveIndex || 0,
-            transactionData: message.transactionData, // Сохраняем сериализованную транзакцию
-          });
-          logger.info("Pending move set successfully", {
-            moveIndex: message.moveIndex,
-            boardPoints: message.boardPoints,
-            dice: message.dice,
-          });
-        } else {
-          logger.warn("Move request missing required data", {
-            hasBoardPoints: !!message.boardPoints,
-            hasDice: !!message.dice,
-            hasTransactionData: !!message.transactionData,
-          });
-        }
-      } else if (message.type === "move_signed") {
-        // Ход подписан вторым игроком, можно отправлять в блокчейн
-        // НО: только первый игрок (тот, кто запросил подпись) должен отправлять в блокчейн
-        // Второй игрок (тот, кто подписал) не должен обрабатывать это сообщение
-        
-        // Проверяем, что это сообщение для нас (первого игрока, который запросил подпись)
-        // Если playerPubkey в сообщении - это мы, значит мы второй игрок и не должны обрабатывать
-        if (message.playerPubkey === myPubkey) {
-          logger.debug("Ignoring move_signed message - we are the signer, not the requester", {
-            moveIndex: message.moveIndex,
-            myPubkey,
-            messagePlayerPubkey: message.playerPubkey,
-          });
-          return; // Второй игрок не должен обрабатывать move_signed
-        }
-
-        logger.info("Received signed move, ready to submit to blockchain", {
-          moveIndex: message.moveIndex,
-          hasTransactionData: !!message.transactionData,
-          messagePlayerPubkey: message.playerPubkey,
-          myPubkey,
-        });
-
-        if (message.transactionData && message.boardPoints && message.dice) {
-          // Десериализуем транзакцию
-          const txBuffer = Buffer.from(message.transactionData);
-          const tx = Transaction.from(txBuffer);
-
-          logger.info("Transaction deserialized", {
-            signaturesCount: tx.signatures.length,
-            signatures: tx.signatures.map((sig, idx) => ({
-              index: idx,
-              publicKey: sig.publicKey.toBase58(),
-              signature: sig.signature ? Buffer.from(sig.signature).toString("base64").substring(0, 16) + "..." : "null",
-            })),
-            hasRecentBlockhash: !!tx.recentBlockhash,
-            recentBlockhash: tx.recentBlockhash ? tx.recentBlockhash.substring(0, 8) + "..." : "missing",
-            feePayer: tx.feePayer?.toBase58() || "not set",
-          });
-
-          // Проверяем, что все подписи присутствуют
-          if (tx.signatures.length < 2) {
-            const error = new Error(`Transaction missing signatures: expected 2, got ${tx.signatures.length}`);
-            logger.error("Transaction validation failed", error, {
-              signaturesCount: tx.signatures.length,
-              signatures: tx.signatures.map((sig, idx) => ({
-                index: idx,
-                publicKey: sig.publicKey.toBase58(),
-                signature: sig.signature ? "present" : "missing",
-              })),
-            });
-            alert("Transaction is missing signatures. Please try again.");
-            return;
-          }
-
-          // Проверяем, что feePayer установлен
-          if (!tx.feePayer) {
-            logger.warn("Transaction missing feePayer, setting from game state");
-            const gameState = await getGameState(gamePubkey);
-            if (gameState) {
-              tx.feePayer = new PublicKey(gameState.player1);
-              logger.info("Fee payer set", { feePayer: tx.feePayer.toBase58() });
-            }
-          }
-
-          // Отправляем в блокчейн (только первый игрок доходит до этого места)
-          submitSignedTransactionToBlockchain(tx, message.boardPoints, [message.dice[0], message.dice[1]]).catch((error: unknown) => {
-            logger.error("Error submitting signed transaction", error instanceof Error ? error : new Error(String(error)));
-          });
-        } else if (pendingMove) {
-          // Fallback: если нет транзакции, используем старый метод
-          submitMoveToBlockchain(pendingMove.boardPoints, pendingMove.dice).catch((error: unknown) => {
-            logger.error("Error submitting move (fallback)", error instanceof Error ? error : new Error(String(error)));
-          });
-        }
       } else if (message.type === "turn_changed") {
         // Сервер уведомил о смене хода - обновляем состояние обоих игроков
         logger.info("Turn changed notification received", {
@@ -519,7 +362,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
           // Новый игрок должен бросить новые кубики
           setDice(null);
           setSelectedCell(null);
-          setPendingMove(null);
           setIsRolling(false);
           setIsProcessingMove(false);
 
@@ -532,7 +374,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
             newTurn,
             diceCleared: true,
             selectedCellCleared: true,
-            pendingMoveCleared: true,
           });
         }
       } else if (message.type === "finish_request" && message.gamePubkey === gamePubkey) {
@@ -605,10 +446,8 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
       logger.debug("Cleaning up WebSocket handlers");
       wsClient.offMessage(messageHandler);
     };
-    // НЕ включаем pendingMove в зависимости, чтобы не пересоздавать handlers при его изменении
-    // pendingMove используется внутри handler через замыкание
     // eslint-disable-next-line react-hooks/exhaustive-deps
-  }, [gamePubkey, myPubkey, submitMoveToBlockchain, submitSignedTransactionToBlockchain, submitFinishTransaction, refreshGameState, gameInfo]);
+  }, [gamePubkey, myPubkey, submitFinishTransaction, refreshGameState, gameInfo]);
 
   // Бросок кубиков
   const rollDice = useCallback(async () => {
@@ -671,51 +510,11 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
 
       const connection = provider.connection ?? (await import("../solana/anchorClient")).getConnection();
 
-      const gameState = await getGameState(gamePubkey);
-      if (!gameState) {
-        throw new Error("Game state not found");
-      }
-
-      const player1Pubkey = new PublicKey(gameState.player1);
-      const player2Pubkey = new PublicKey(gameState.player2);
       const gamePubkeyObj = new PublicKey(gamePubkey);
+      const moverPubkey = new PublicKey(myPubkey);
 
-      // Формируем data для инструкции make_move
-      const makeMoveIdl = (idlJson.instructions as { name: string; discriminator: number[] }[]).find(
-        (ix) => ix.name === "make_move"
-      );
-      if (!makeMoveIdl) {
-        throw new Error("make_move instruction not found in IDL");
-      }
-
-      const discriminator = Buffer.from(makeMoveIdl.discriminator);
-
-      // Конвертируем boardPoints в i8 массив
-      const boardPointsBuffer = Buffer.alloc(24);
-      for (let i = 0; i < 24; i++) {
-        const value = Math.max(-128, Math.min(127, newBoardPoints[i]));
-        boardPointsBuffer.writeInt8(value, i);
-      }
-
-      const diceBuffer = Buffer.from(newDice);
-      const data = Buffer.concat([discriminator, boardPointsBuffer, diceBuffer]);
-
-      logger.debug("Instruction data prepared", {
-        dataLength: data.length,
-        boardPoints: newBoardPoints,
-        dice: newDice,
-      });
-
-      const ix = new TransactionInstruction({
-        programId: PROGRAM_ID,
-        keys: [
-          { pubkey: gamePubkeyObj, isSigner: false, isWritable: true },
-          { pubkey: player1Pubkey, isSigner: true, isWritable: true },
-          { pubkey: player2Pubkey, isSigner: true, isWritable: true },
-          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-        ],
-        data,
-      });
+      // make_move подписывает только ходящий; соперник передаётся без подписи
+      const ix = await createMakeMoveInstruction(gamePubkeyObj, moverPubkey, newBoardPoints, newDice);
 
       const tx = new Transaction().add(ix);
 
@@ -723,18 +522,17 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
       logger.debug("Fetching recent blockhash for transaction");
       const { blockhash } = await connection.getLatestBlockhash("finalized");
       tx.recentBlockhash = blockhash;
-      tx.feePayer = player1Pubkey; // Устанавливаем fee payer (может быть любой из игроков)
+      tx.feePayer = moverPubkey; // Ходящий платит комиссию
 
       logger.info("Transaction created with recentBlockhash", {
         gamePubkey: gamePubkeyObj.toBase58(),
-        player1Pubkey: player1Pubkey.toBase58(),
-        player2Pubkey: player2Pubkey.toBase58(),
+        moverPubkey: moverPubkey.toBase58(),
         blockhash: blockhash.substring(0, 8) + "...",
       });
 
       return tx;
     },
-    [gamePubkey]
+    [gamePubkey, myPubkey]
   );
 
   // Формирование транзакции finish_game
@@ -841,60 +639,15 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
       // Формируем транзакцию
       const tx = await createMoveTransaction(newBoardPoints, dice);
 
-      // Подписываем транзакцию своей подписью (partialSign, чтобы не затирать чужие подписи)
-      tx.partialSign(myKeypair);
+      // Подписываем транзакцию своей подписью (подпись соперника не нужна)
+      tx.sign(myKeypair);
       logger.info("Transaction signed by me", {
         myPubkey: myKeypair.publicKey.toBase58(),
         signaturesCount: tx.signatures.length,
-        signatures: tx.signatures.map((sig, idx) => ({
-          index: idx,
-          publicKey: sig.publicKey.toBase58(),
-          signature: sig.signature ? Buffer.from(sig.signature).toString("base64").substring(0, 16) + "..." : "null",
-        })),
       });
 
-      // Сериализуем транзакцию для отправки через WS
-      // Используем requireAllSignatures: false, так как второй игрок еще не подписал
-      const serializedTx = tx.serialize({
-        requireAllSignatures: false,
-        verifySignatures: false,
-      });
-
-      logger.debug("Transaction serialized for WebSocket (first player signature only)", {
-        serializedLength: serializedTx.length,
-        signaturesCount: tx.signatures.length,
-      });
-
-      logger.info("Transaction serialized", {
-        serializedLength: serializedTx.length,
-      });
-
-      // Отправляем запрос на подпись второму игроку через WS
-      const moveRequest: WSMessage = {
-        type: "move_request",
-        gamePubkey,
-        playerPubkey: myPubkey,
-        moveIndex,
-        boardPoints: newBoardPoints,
-        dice,
-        transactionData: Array.from(serializedTx), // Сериализованная транзакция
-      };
-
-      logger.info("Sending move request to opponent via WebSocket", {
-        moveRequest: {
-          ...moveRequest,
-          transactionData: `[${serializedTx.length} bytes]`,
-        },
-      });
-
-      wsClient.sendMoveRequest(gamePubkey, moveRequest);
-
-      // Сохраняем как pending move
-      setPendingMove({
-        boardPoints: newBoardPoints,
-        dice,
-        moveIndex,
-      });
+      // Отправляем ход в блокчейн сами
+      await submitSignedTransactionToBlockchain(tx, newBoardPoints, dice);
 
       // Логируем ход на сервере (оффчейн)
       await logMove(gamePubkey, {
@@ -916,7 +669,7 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
       setIsProcessingMove(false);
       setSelectedCell(null);
     }
-  }, [isMyTurn, dice, selectedCell, boardPoints, gamePubkey, myPubkey, isProcessingMove, createMoveTransaction]);
+  }, [isMyTurn, dice, selectedCell, boardPoints, gamePubkey, myPubkey, isProcessingMove, createMoveTransaction, submitSignedTransactionToBlockchain]);
 
   // Завершение игры (кнопка "Я выиграл")
   const confirmWin = useCallback(async () => {
@@ -1035,110 +788,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
     }
   }, [gamePubkey, isProcessingManual, myPubkey, onBack]);
 
-  // Обработка pending move (когда пришёл запрос на подпись)
-  const handlePendingMoveSignature = useCallback(async () => {
-    if (!pendingMove || !pendingMove.transactionData) return;
-
-    logger.info("Signing pending move", {
-      gamePubkey,
-      pendingMove: {
-        ...pendingMove,
-        transactionData: `[${pendingMove.transactionData.length} bytes]`,
-      },
-      myPubkey,
-    });
-
-    try {
-      const myKeypair = getCurrentKeypair();
-      if (!myKeypair) {
-        throw new Error("Keypair not available");
-      }
-
-      // Десериализуем транзакцию
-      const txBuffer = Buffer.from(pendingMove.transactionData);
-      const tx = Transaction.from(txBuffer);
-
-      logger.info("Transaction deserialized for signing", {
-        signaturesCount: tx.signatures.length,
-        hasRecentBlockhash: !!tx.recentBlockhash,
-        recentBlockhash: tx.recentBlockhash ? tx.recentBlockhash.substring(0, 8) + "..." : "missing",
-        signatures: tx.signatures.map((sig, idx) => ({
-          index: idx,
-          publicKey: sig.publicKey.toBase58(),
-          signature: sig.signature ? Buffer.from(sig.signature).toString("base64").substring(0, 16) + "..." : "null",
-        })),
-      });
-
-      // Проверяем, что recentBlockhash установлен (он должен быть уже установлен отправителем)
-      if (!tx.recentBlockhash) {
-        logger.warn("Transaction missing recentBlockhash, fetching new one");
-        const provider = getProvider();
-        if (!provider) {
-          throw new Error("Provider not initialized");
-        }
-        const connection = provider.connection ?? (await import("../solana/anchorClient")).getConnection();
-        const { blockhash } = await connection.getLatestBlockhash("finalized");
-        tx.recentBlockhash = blockhash;
-        logger.info("Recent blockhash set", { blockhash: blockhash.substring(0, 8) + "..." });
-      }
-
-      // Подписываем транзакцию своей подписью (partialSign, чтобы не затирать подпись первого игрока)
-      // ВАЖНО: подпись добавляется к существующим подписям, не заменяет их
-      tx.partialSign(myKeypair);
-
-      logger.info("Transaction signed by me", {
-        myPubkey: myKeypair.publicKey.toBase58(),
-        signaturesCount: tx.signatures.length,
-        signatures: tx.signatures.map((sig, idx) => ({
-          index: idx,
-          publicKey: sig.publicKey.toBase58(),
-          signature: sig.signature ? Buffer.from(sig.signature).toString("base64").substring(0, 16) + "..." : "null",
-        })),
-      });
-
-      // Сериализуем подписанную транзакцию
-      const serializedTx = tx.serialize({
-        requireAllSignatures: true,
-        verifySignatures: false,
-      });
-
-      logger.info("Signed transaction serialized", {
-        serializedLength: serializedTx.length,
-      });
-
-      // Отправляем обратно через WS
-      const signedMove: WSMessage = {
-        type: "move_signed",
-        gamePubkey,
-        playerPubkey: myPubkey,
-        moveIndex: pendingMove.moveIndex,
-        boardPoints: pendingMove.boardPoints,
-        dice: pendingMove.dice,
-        transactionData: Array.from(serializedTx),
-      };
-
-      logger.info("Sending signed move back to requester", {
-        signedMove: {
-          ...signedMove,
-          transactionData: `[${serializedTx.length} bytes]`,
-        },
-      });
-
-      wsClient.sendMoveSigned(gamePubkey, signedMove);
-
-      setPendingMove(null);
-    } catch (error) {
-      logger.error("Error signing pending move", error as Error, {
-        gamePubkey,
-        pendingMove: pendingMove ? {
-          ...pendingMove,
-          transactionData: pendingMove.transactionData ? `[${pendingMove.transactionData.length} bytes]` : undefined,
-        } : null,
-      });
-      alert("Failed to sign move. Please try again.");
-    }
-  }, [pendingMove, gamePubkey, myPubkey]);
-
   // Обработка pending finish (подписание победы вторым игроком)
   const handlePendingFinishSignature = useCallback(async () => {
     if (!pendingFinish || !pendingFinish.transactionData) return;
@@ -1397,40 +1046,6 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
 
       </div>
 
-      {/* Pending move для подписи */}
-      {pendingMove && !isMyTurn && (
-        <div
-          style={{
-            padding: "20px",
-            backgroundColor: "#fff3cd",
-            border: "2px solid #ffc107",
-            borderRadius: "4px",
-            marginBottom: "20px",
-          }}
-        >
-          <h3 style={{ color: "#000", marginBottom: "10px" }}>Pending Move to Sign</h3>
-          <p style={{ color: "#000", marginBottom: "10px" }}>
-            Opponent wants to make a move. Please review and sign.
-          </p>
-          <div style={{ marginBottom: "10px", color: "#000" }}>
-            <strong>Dice:</strong> {pendingMove.dice[0]}, {pendingMove.dice[1]}
-          </div>
-          <button
-            onClick={handlePendingMoveSignature}
-            style={{
-              padding: "10px 20px",
-              backgroundColor: "#28a745",
-              color: "white",
-              border: "none",
-              borderRadius: "4px",
-              cursor: "pointer",
-            }}
-          >
-            Sign & Approve Move
-          </button>
-        </div>
-      )}
-
       {pendingFinish && !isMyTurn && (
         <div
           style={{
@@ -1464,7 +1079,7 @@ export default function GameScreen({ gamePubkey, onBack }: GameScreenProps) {
         </div>
       )}
 
-      {!isMyTurn && !pendingMove && (
+      {!isMyTurn && (
         <div style={{ color: "#666", fontStyle: "italic" }}>
           Waiting for opponent's move...
         </div>
@@ -7,6 +7,85 @@ const logger = new Logger("AnchorClient");
 export const PROGRAM_ID = new PublicKey("DmEwwQX5n6mt2Hgv923xmVLDQpWWcvYmTcm3yJbZ5xRr");
 export const RPC_URL = "http://127.0.0.1:8899";
 
+/**
+ * Аккаунты event-cpi, которые Anchor дописывает в конец контекстов с
+ * #[event_cpi] (расчётные инструкции: finish_game, возвраты, отмена):
+ * PDA [b"__event_authority"] и сама программа. События GameFinished,
+ * GameRefunded и GameCancelled приходят как inner instruction и не теряются
+ * при обрезке логов RPC.
+ */
+export function eventCpiAccounts(): { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[] {
+  const [eventAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority")],
+    PROGRAM_ID
+  );
+  return [
+    { pubkey: eventAuthority, isSigner: false, isWritable: false },
+    { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+  ];
+}
+
+/**
+ * PDA [b"payout_splits", игрок]. Контексты выплат и возвратов сверяют его по
+ * seeds и не дают пропустить; если игрок сплиты не регистрировал, аккаунт
+ * просто не инициализирован и программа платит всё самому игроку.
+ */
+export function payoutSplitsPda(player: PublicKey): PublicKey {
+  const [splits] = PublicKey.findProgramAddressSync(
+    [Buffer.from("payout_splits"), player.toBuffer()],
+    PROGRAM_ID
+  );
+  return splits;
+}
+
+/** PDA [b"config"] — глобальный конфиг программы. */
+export function configPda(): PublicKey {
+  const [config] = PublicKey.findProgramAddressSync([Buffer.from("config")], PROGRAM_ID);
+  return config;
+}
+
+/** PDA [b"open_games", игрок] — счётчик открытых лобби создателя. */
+export function openGamesPda(player: PublicKey): PublicKey {
+  const [openGames] = PublicKey.findProgramAddressSync(
+    [Buffer.from("open_games"), player.toBuffer()],
+    PROGRAM_ID
+  );
+  return openGames;
+}
+
+/** PDA [b"create_cooldown", игрок] — слот прошлого init_game создателя. */
+export function createCooldownPda(player: PublicKey): PublicKey {
+  const [cooldown] = PublicKey.findProgramAddressSync(
+    [Buffer.from("create_cooldown"), player.toBuffer()],
+    PROGRAM_ID
+  );
+  return cooldown;
+}
+
+/**
+ * PDA [b"ban", игрок]. Нужен init_game и join_game при config.bans_enforced;
+ * передавать его можно всегда: неинициализированный аккаунт = бана нет.
+ */
+export function banPda(player: PublicKey): PublicKey {
+  const [ban] = PublicKey.findProgramAddressSync(
+    [Buffer.from("ban"), player.toBuffer()],
+    PROGRAM_ID
+  );
+  return ban;
+}
+
+/** PDA [b"stats", игрок]; может быть не инициализирован (рейтинг по умолчанию). */
+export function playerStatsPda(player: PublicKey): PublicKey {
+  const [stats] = PublicKey.findProgramAddressSync(
+    [Buffer.from("stats"), player.toBuffer()],
+    PROGRAM_ID
+  );
+  return stats;
+}
+
+/** Заглушка для необязательного аккаунта Anchor (Option<...> = None). */
+export const NONE_ACCOUNT = { pubkey: PROGRAM_ID, isSigner: false, isWritable: false };
+
 // Интерфейс для wallet-объекта, совместимого с AnchorProvider
 type AnchorWallet = Wallet & {
   publicKey: PublicKey;
@@ -7,7 +7,21 @@ import {
   Transaction,
   TransactionInstruction,
 } from "@solana/web3.js";
-import { getConnection, getCurrentKeypair, PROGRAM_ID, getProvider } from "./anchorClient";
+import {
+  getConnection,
+  getCurrentKeypair,
+  PROGRAM_ID,
+  getProvider,
+  eventCpiAccounts,
+  configPda,
+  payoutSplitsPda,
+  openGamesPda,
+  createCooldownPda,
+  banPda,
+  playerStatsPda,
+  NONE_ACCOUNT,
+} from "./anchorClient";
+import { defaultValue, encodeInstruction } from "./idlCoder";
 import idlJson from "../idl/backgammon.json";
 import { Logger } from "../utils/logger";
 
@@ -121,32 +135,33 @@ export async function initGame(
       moveFeeLamports: moveFeeLamports.toString(),
     });
 
-    // Формируем data для инструкции init_game вручную по IDL.
-    const initGameIdl = (idlJson.instructions as { name: string; discriminator: number[] }[]).find(
-      (ix) => ix.name === "init_game"
-    );
-    if (!initGameIdl) {
-      const error = new Error("init_game instruction not found in IDL");
-      logger.error("IDL lookup failed", error);
-      throw error;
-    }
-
-    const discriminator: number[] = initGameIdl.discriminator;
-    const discBuffer = Buffer.from(discriminator);
-
-    const gameIdBuf = gameId.toArrayLike(Buffer, "le", 8);
-    const stakeBuf = stakeLamports.toArrayLike(Buffer, "le", 8);
-    const moveFeeBuf = moveFeeLamports.toArrayLike(Buffer, "le", 8);
-    const player2Buf = player2PubkeyObj.toBuffer();
-
-    const data = Buffer.concat([discBuffer, gameIdBuf, stakeBuf, moveFeeBuf, player2Buf]);
+    // data по IDL; options — GameOptions по умолчанию (Casual, без доп. правил)
+    const data = encodeInstruction("init_game", {
+      game_id: gameId,
+      stake_lamports: stakeLamports,
+      move_fee_lamports: moveFeeLamports,
+      player2_pubkey: player2PubkeyObj,
+      options: defaultValue({ defined: { name: "GameOptions" } }),
+    });
 
+    // Для Casual-игры без FeeRoute, арбитра, истории и с game_id != 0
+    // необязательные аккаунты не нужны
     const ix = new TransactionInstruction({
       programId: PROGRAM_ID,
       keys: [
         { pubkey: gamePubkey, isSigner: true, isWritable: true },
         { pubkey: player1Pubkey, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: configPda(), isSigner: false, isWritable: false },
+        { pubkey: banPda(player1Pubkey), isSigner: false, isWritable: false },
+        { pubkey: openGamesPda(player1Pubkey), isSigner: false, isWritable: true },
+        NONE_ACCOUNT, // time_control
+        { pubkey: createCooldownPda(player1Pubkey), isSigner: false, isWritable: true },
+        NONE_ACCOUNT, // fee_route
+        NONE_ACCOUNT, // whitelisted_arbiter
+        NONE_ACCOUNT, // game_counter
+        NONE_ACCOUNT, // game_history
+        NONE_ACCOUNT, // history_commitment
       ],
       data,
     });
@@ -221,6 +236,10 @@ export async function cancelGameBeforeJoin(gamePubkey: string): Promise<string>
       { pubkey: gamePubkeyObj, isSigner: false, isWritable: true },
       { pubkey: myKeypair.publicKey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: openGamesPda(myKeypair.publicKey), isSigner: false, isWritable: true },
+      { pubkey: payoutSplitsPda(myKeypair.publicKey), isSigner: false, isWritable: false },
+      NONE_ACCOUNT, // event_buffer
+      ...eventCpiAccounts(),
     ],
     data,
   });
@@ -343,6 +362,28 @@ export async function getGameState(gamePubkey: string): Promise<{
   }
 }
 
+// Смещение Config.treasury: discriminator (8) + admin (32) + current_season (4) +
+// bump (1) + bans_enforced (1) + max_open_games (2) + bot_wallet (32) +
+// bot_max_stake (8) + paused (1) + locked (1) + arbiter (32) +
+// min_create_gap_slots (8) + stake_pool (32) = 162.
+const CONFIG_TREASURY_OFFSET = 162;
+
+/**
+ * Казна из Config (null, если не задана): возвраты отправляют ей остаток от
+ * деления банка, и тогда аккаунт treasury обязателен.
+ */
+async function getConfigTreasury(): Promise<PublicKey | null> {
+  const connection = getConnection();
+  const accountInfo = await connection.getAccountInfo(configPda());
+  if (!accountInfo) {
+    throw new Error("Config account not found");
+  }
+  const treasury = new PublicKey(
+    accountInfo.data.slice(CONFIG_TREASURY_OFFSET, CONFIG_TREASURY_OFFSET + 32)
+  );
+  return treasury.equals(PublicKey.default) ? null : treasury;
+}
+
 /**
  * Присоединяется к существующей игре.
  * @param gamePubkey - Публичный ключ аккаунта игры
@@ -391,25 +432,43 @@ export async function joinGame(gamePubkey: string): Promise<void> {
   });
 
   try {
-    // Формируем data для инструкции join_game вручную по IDL.
-    const joinGameIdl = (idlJson.instructions as { name: string; discriminator: number[] }[]).find(
-      (ix) => ix.name === "join_game"
-    );
-    if (!joinGameIdl) {
-      const error = new Error("join_game instruction not found in IDL");
-      logger.error("IDL lookup failed", error);
-      throw error;
+    const gameState = await getGameState(gamePubkey);
+    if (!gameState) {
+      throw new Error("Game state not found");
     }
+    const player1Pubkey = new PublicKey(gameState.player1);
 
-    const discriminator: number[] = joinGameIdl.discriminator;
-    const data = Buffer.from(discriminator);
+    // data по IDL: игры этого клиента без пароля, client = None
+    const data = encodeInstruction("join_game", {
+      password: Buffer.alloc(0),
+      client: null,
+    });
 
+    // Игра учтена в счётчике лобби создателя, поэтому player1_open_games
+    // обязателен; размещения банка (stake_pot) у таких игр нет
     const ix = new TransactionInstruction({
       programId: PROGRAM_ID,
       keys: [
         { pubkey: gamePubkeyObj, isSigner: false, isWritable: true },
         { pubkey: player2Pubkey, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: configPda(), isSigner: false, isWritable: false },
+        { pubkey: openGamesPda(player1Pubkey), isSigner: false, isWritable: true },
+        { pubkey: banPda(player2Pubkey), isSigner: false, isWritable: false },
+        { pubkey: playerStatsPda(player2Pubkey), isSigner: false, isWritable: false },
+        NONE_ACCOUNT, // client_telemetry
+        NONE_ACCOUNT, // event_buffer
+        NONE_ACCOUNT, // lobby
+        NONE_ACCOUNT, // pot_stake
+        NONE_ACCOUNT, // pot_vault
+        NONE_ACCOUNT, // pool_tokens
+        NONE_ACCOUNT, // pool_mint
+        NONE_ACCOUNT, // stake_pool
+        NONE_ACCOUNT, // stake_pool_withdraw_authority
+        NONE_ACCOUNT, // reserve_stake
+        NONE_ACCOUNT, // manager_fee_account
+        NONE_ACCOUNT, // stake_pool_program
+        NONE_ACCOUNT, // token_program
       ],
       data,
     });
@@ -432,18 +491,115 @@ export async function joinGame(gamePubkey: string): Promise<void> {
 }
 
 /**
- * Делает ход в игре (make_move).
- * Требует подписи ОБОИХ игроков - эта функция вызывается после того,
- * как оба игрока подписали транзакцию через WebSocket.
+ * Инструкция make_move для стороны current_turn. Подписывает только ходящий
+ * (mover); соперник — ключ другой стороны (game.player_key(3 - current_turn)),
+ * его подпись не нужна. Необязательные аккаунты рассчитаны на игры,
+ * созданные этим клиентом (Casual, без команд, бота, FeeRoute, истории и
+ * тающего банка); страховой пул передаётся, если он создан.
  * @param gamePubkey - Публичный ключ аккаунта игры
+ * @param mover - Ключ ходящего игрока
  * @param newBoardPoints - Новое состояние доски [i8; 24]
  * @param newDice - Новые значения кубиков [u8; 2]
+ * @param annotationHash - Хэш оффчейн-аннотации хода [u8; 32] (нули = без аннотации)
+ */
+export async function createMakeMoveInstruction(
+  gamePubkey: PublicKey,
+  mover: PublicKey,
+  newBoardPoints: number[],
+  newDice: [number, number],
+  annotationHash: Uint8Array = new Uint8Array(32)
+): Promise<TransactionInstruction> {
+  const gameState = await getGameState(gamePubkey.toBase58());
+  if (!gameState) {
+    throw new Error("Game state not found");
+  }
+  if (gameState.currentTurn !== 1 && gameState.currentTurn !== 2) {
+    throw new Error(`Invalid current_turn: ${gameState.currentTurn}`);
+  }
+  const opponent = new PublicKey(
+    gameState.currentTurn === 1 ? gameState.player2 : gameState.player1
+  );
+
+  const makeMoveIdl = (idlJson.instructions as { name: string; discriminator: number[] }[]).find(
+    (ix) => ix.name === "make_move"
+  );
+  if (!makeMoveIdl) {
+    throw new Error("make_move instruction not found in IDL");
+  }
+
+  if (newBoardPoints.length !== 24) {
+    throw new Error(`Invalid boardPoints length: expected 24, got ${newBoardPoints.length}`);
+  }
+  if (newDice.length !== 2) {
+    throw new Error(`Invalid dice length: expected 2, got ${newDice.length}`);
+  }
+  if (annotationHash.length !== 32) {
+    throw new Error(`Invalid annotationHash length: expected 32, got ${annotationHash.length}`);
+  }
+
+  // Конвертируем boardPoints в i8 массив (значения от -128 до 127)
+  const boardPointsBuffer = Buffer.alloc(24);
+  for (let i = 0; i < 24; i++) {
+    const value = Math.max(-128, Math.min(127, newBoardPoints[i]));
+    boardPointsBuffer.writeInt8(value, i);
+  }
+
+  // data: discriminator + new_board_points [i8; 24] + new_dice [u8; 2] +
+  // annotation_hash [u8; 32] + client: Option<ClientInfo> (0 = None)
+  const data = Buffer.concat([
+    Buffer.from(makeMoveIdl.discriminator),
+    boardPointsBuffer,
+    Buffer.from(newDice),
+    Buffer.from(annotationHash),
+    Buffer.from([0]),
+  ]);
+
+  const [insurancePoolPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("insurance_pool")],
+    PROGRAM_ID
+  );
+  const insurancePool = await getConnection().getAccountInfo(insurancePoolPda);
+
+  return new TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys: [
+      { pubkey: gamePubkey, isSigner: false, isWritable: true },
+      { pubkey: mover, isSigner: true, isWritable: true },
+      { pubkey: opponent, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      NONE_ACCOUNT, // bot_vault
+      { pubkey: configPda(), isSigner: false, isWritable: false },
+      NONE_ACCOUNT, // team
+      NONE_ACCOUNT, // time_control
+      NONE_ACCOUNT, // fee_route
+      NONE_ACCOUNT, // fee_recipient
+      insurancePool
+        ? { pubkey: insurancePoolPda, isSigner: false, isWritable: true }
+        : NONE_ACCOUNT, // insurance_pool
+      NONE_ACCOUNT, // client_telemetry
+      NONE_ACCOUNT, // event_buffer
+      NONE_ACCOUNT, // game_history
+      NONE_ACCOUNT, // history_commitment
+      NONE_ACCOUNT, // decay_sink
+    ],
+    data,
+  });
+}
+
+/**
+ * Делает ход в игре (make_move) от имени текущего кошелька.
+ * Подписывает только ходящий игрок, он же платит комиссию за ход.
+ * @param gamePubkey - Публичный ключ аккаунта игры
+ * @param newBoardPoints - Новое состояние доски [i8; 24]
+ * @param newDice - Новые значения кубиков [u8; 2]
+ * @param annotationHash - Хэш оффчейн-аннотации хода [u8; 32] (по умолчанию нули)
  * @returns transaction signature
  */
 export async function makeMove(
   gamePubkey: string,
   newBoardPoints: number[],
-  newDice: [number, number]
+  newDice: [number, number],
+  annotationHash?: Uint8Array
 ): Promise<string> {
   logger.info("makeMove called", {
     gamePubkey,
@@ -490,118 +646,20 @@ export async function makeMove(
   logger.debug("Keypair retrieved for makeMove", { pubkey: myPubkey.toBase58() });
 
   try {
-    // Получаем состояние игры из блокчейна, чтобы узнать второго игрока
-    const gameState = await getGameState(gamePubkey);
-    if (!gameState) {
-      const error = new Error("Game state not found");
-      logger.error("Game state check failed", error, { gamePubkey });
-      throw error;
-    }
-
-    logger.info("Game state retrieved", {
-      gamePubkey,
-      player1: gameState.player1,
-      player2: gameState.player2,
-      status: gameState.status,
-    });
-
-    // Определяем, кто мы (player1 или player2)
-    const isPlayer1 = myPubkey.toBase58() === gameState.player1;
-    const player1Pubkey = new PublicKey(gameState.player1);
-    const player2Pubkey = new PublicKey(gameState.player2);
-
-    logger.info("Player identification", {
-      myPubkey: myPubkey.toBase58(),
-      isPlayer1,
-      player1Pubkey: player1Pubkey.toBase58(),
-      player2Pubkey: player2Pubkey.toBase58(),
-    });
-
-    // Формируем data для инструкции make_move
-    const makeMoveIdl = (idlJson.instructions as { name: string; discriminator: number[] }[]).find(
-      (ix) => ix.name === "make_move"
+    const ix = await createMakeMoveInstruction(
+      gamePubkeyObj,
+      myPubkey,
+      newBoardPoints,
+      newDice,
+      annotationHash
     );
-    if (!makeMoveIdl) {
-      const error = new Error("make_move instruction not found in IDL");
-      logger.error("IDL lookup failed", error);
-      throw error;
-    }
-
-    const discriminator: number[] = makeMoveIdl.discriminator;
-    const discBuffer = Buffer.from(discriminator);
-
-    // Проверяем, что newBoardPoints имеет длину 24
-    if (newBoardPoints.length !== 24) {
-      const error = new Error(`Invalid boardPoints length: expected 24, got ${newBoardPoints.length}`);
-      logger.error("Validation failed", error, { boardPointsLength: newBoardPoints.length });
-      throw error;
-    }
-
-    // Конвертируем boardPoints в i8 массив (значения от -128 до 127)
-    const boardPointsBuffer = Buffer.alloc(24);
-    for (let i = 0; i < 24; i++) {
-      const value = Math.max(-128, Math.min(127, newBoardPoints[i]));
-      boardPointsBuffer.writeInt8(value, i);
-    }
-
-    logger.debug("Board points converted", {
-      original: newBoardPoints,
-      converted: Array.from({ length: 24 }, (_, i) => boardPointsBuffer.readInt8(i)),
-    });
-
-    // Проверяем dice
-    if (newDice.length !== 2) {
-      const error = new Error(`Invalid dice length: expected 2, got ${newDice.length}`);
-      logger.error("Validation failed", error, { diceLength: newDice.length });
-      throw error;
-    }
-
-    const diceBuffer = Buffer.from(newDice);
-
-    // Собираем data: discriminator + boardPoints (24 bytes) + dice (2 bytes)
-    const data = Buffer.concat([discBuffer, boardPointsBuffer, diceBuffer]);
-
-    logger.debug("Instruction data prepared", {
-      discriminatorLength: discBuffer.length,
-      boardPointsLength: boardPointsBuffer.length,
-      diceLength: diceBuffer.length,
-      totalDataLength: data.length,
-    });
-
-    // Формируем инструкцию
-    const ix = new TransactionInstruction({
-      programId: PROGRAM_ID,
-      keys: [
-        { pubkey: gamePubkeyObj, isSigner: false, isWritable: true },
-        { pubkey: player1Pubkey, isSigner: true, isWritable: true },
-        { pubkey: player2Pubkey, isSigner: true, isWritable: true },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      ],
-      data,
-    });
-
     const tx = new Transaction().add(ix);
 
-    logger.info("Transaction prepared, signing with my keypair", {
+    logger.info("Sending makeMove transaction to Solana...", {
       gamePubkey: gamePubkeyObj.toBase58(),
-      player1Pubkey: player1Pubkey.toBase58(),
-      player2Pubkey: player2Pubkey.toBase58(),
-      myPubkey: myPubkey.toBase58(),
-      isPlayer1,
-    });
-
-    // Подписываем транзакцию своим keypair
-    tx.sign(myKeypair);
-
-    logger.info("Transaction signed by me", {
-      myPubkey: myPubkey.toBase58(),
-      signaturesCount: tx.signatures.length,
+      mover: myPubkey.toBase58(),
     });
-
-    // Отправляем транзакцию
-    logger.info("Sending makeMove transaction to Solana...");
-    // Транзакция уже полностью подписана обоими игроками, signers не нужны
-    const signature = await connection.sendTransaction(tx, [], {
+    const signature = await connection.sendTransaction(tx, [myKeypair], {
       skipPreflight: false,
     });
     await connection.confirmTransaction(signature, "confirmed");
@@ -711,6 +769,7 @@ export async function createManualRefundTransaction(gamePubkey: string): Promise
 
   const discriminator = Buffer.from(manualRefundIdl.discriminator);
   const data = discriminator;
+  const treasury = await getConfigTreasury();
 
   logger.debug("Instruction data prepared", {
     discriminatorLength: discriminator.length,
@@ -726,6 +785,15 @@ export async function createManualRefundTransaction(gamePubkey: string): Promise
       { pubkey: player2Pubkey, isSigner: false, isWritable: true },
       { pubkey: requesterPubkey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      NONE_ACCOUNT, // bot_vault
+      NONE_ACCOUNT, // escrow_agent
+      NONE_ACCOUNT, // team
+      { pubkey: payoutSplitsPda(player1Pubkey), isSigner: false, isWritable: false },
+      { pubkey: payoutSplitsPda(player2Pubkey), isSigner: false, isWritable: false },
+      NONE_ACCOUNT, // event_buffer
+      { pubkey: configPda(), isSigner: false, isWritable: false },
+      treasury ? { pubkey: treasury, isSigner: false, isWritable: true } : NONE_ACCOUNT, // treasury
+      ...eventCpiAccounts(),
     ],
     data,
   });
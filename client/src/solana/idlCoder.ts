@@ -0,0 +1,86 @@
+import { BN, BorshInstructionCoder, type Idl } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import idlJson from "../idl/backgammon.json";
+
+// Типы IDL в том объёме, который нужен defaultValue (формат Anchor 0.30+).
+type IdlTypeJson =
+  | string
+  | { option: IdlTypeJson }
+  | { coption: IdlTypeJson }
+  | { vec: IdlTypeJson }
+  | { array: [IdlTypeJson, number] }
+  | { defined: { name: string } };
+
+type IdlFieldJson = { name: string; type: IdlTypeJson };
+
+type IdlTypeDefJson = {
+  name: string;
+  type:
+    | { kind: "struct"; fields?: IdlFieldJson[] }
+    | { kind: "enum"; variants: { name: string }[] };
+};
+
+const idl = idlJson as unknown as Idl;
+const idlTypes = (idlJson as unknown as { types: IdlTypeDefJson[] }).types;
+
+const instructionCoder = new BorshInstructionCoder(idl);
+
+/**
+ * Данные инструкции по IDL: дискриминатор + аргументы в borsh.
+ * Имена инструкции и полей — как в IDL (snake_case), u64/i64 передаются как BN,
+ * pubkey — как PublicKey, enum — как { Вариант: {} }, Option::None — null.
+ */
+export function encodeInstruction(name: string, args: Record<string, unknown>): Buffer {
+  return instructionCoder.encode(name, args);
+}
+
+/**
+ * Значение по умолчанию для типа из IDL, как у #[derive(Default)] в программе:
+ * нули, false, Pubkey::default(), None и первый вариант enum. Для GameOptions
+ * это обычная Casual-игра без дополнительных правил.
+ */
+export function defaultValue(type: IdlTypeJson): unknown {
+  if (typeof type === "string") {
+    switch (type) {
+      case "bool":
+        return false;
+      case "u64":
+      case "i64":
+      case "u128":
+      case "i128":
+        return new BN(0);
+      case "pubkey":
+        return PublicKey.default;
+      case "bytes":
+        return Buffer.alloc(0);
+      case "string":
+        return "";
+      default:
+        return 0;
+    }
+  }
+  if ("option" in type || "coption" in type) {
+    return null;
+  }
+  if ("vec" in type) {
+    return [];
+  }
+  if ("array" in type) {
+    const [inner, len] = type.array;
+    return Array.from({ length: len }, () => defaultValue(inner));
+  }
+
+  const typeName = type.defined.name;
+  const typeDef = idlTypes.find((t) => t.name === typeName);
+  if (!typeDef) {
+    throw new Error(`Type ${typeName} not found in IDL`);
+  }
+  if (typeDef.type.kind === "enum") {
+    return { [typeDef.type.variants[0].name]: {} };
+  }
+  const value: Record<string, unknown> = {};
+  for (const field of typeDef.type.fields ?? []) {
+    value[field.name] = defaultValue(field.type);
+  }
+  return value;
+}
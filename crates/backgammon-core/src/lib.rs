@@ -0,0 +1,451 @@
+//! Правила нард без зависимостей от Anchor и Solana: стартовые позиции,
+//! упаковка доски, проверка и перебор ходов, подсчёт пипсов, конец партии,
+//! ASCII-отрисовка доски, запись партии в формате `.mat`, Merkle-коммитмент
+//! истории ходов.
+//!
+//! Крейт `no_std` (`alloc` нужен для [`legal_moves`], отрисовки и записи
+//! партии), поэтому один и тот же код работает в программе, в SDK/CLI и в
+//! тестах.
+//!
+//! Доска `[i8; 24]` — с точки зрения player1: индекс i = пункт i + 1, шашки
+//! player1 положительные, player2 — отрицательные. Сторона (`side`) — 1 или 2.
+
+#![no_std]
+
+extern crate alloc;
+
+mod merkle;
+mod render;
+mod transcript;
+
+use alloc::vec::Vec;
+
+pub use merkle::{
+    merkle_proof, merkle_root, verify_proof, Hash, HistoryFrontier, MerkleError, MerkleProof,
+    MoveLeaf, Sha256, HISTORY_TREE_DEPTH, MAX_HISTORY_LEAVES,
+};
+pub use render::{render, render_board};
+pub use transcript::{Action, Outcome, Transcript, TranscriptError};
+
+/// Доска: 24 пункта, знак = владелец.
+pub type Board = [i8; 24];
+
+/// Упакованная доска: по 5 бит на пункт (4 бита — число шашек 0..15, старший
+/// бит — владелец: 0 = player1, 1 = player2), пункты подряд от младшего бита.
+pub type PackedBoard = [u8; 15];
+
+/// Наибольшая фора (шашек на баре у несущего её игрока).
+pub const MAX_HANDICAP: u8 = 3;
+
+/// Номер «пункта» бара в координатах ходящего.
+const BAR: usize = 24;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoardError {
+    /// Доска не кодируется: больше 15 шашек на пункте или пустой пункт
+    /// с битом владельца.
+    InvalidBoard,
+    /// Фора больше MAX_HANDICAP или фора в хайпергаммоне.
+    InvalidHandicap,
+}
+
+/// Вариант правил: стартовая позиция и число шашек у каждой стороны.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Variant {
+    /// Классический бэкгаммон, 15 шашек.
+    #[default]
+    Standard,
+    /// Накгаммон, 15 шашек: задние шашки разбиты 2 + 2 на 24-м и 23-м пунктах.
+    Nackgammon,
+    /// Хайпергаммон, 3 шашки.
+    Hypergammon,
+}
+
+impl Variant {
+    /// Число шашек у каждой стороны.
+    pub fn checkers(&self) -> u8 {
+        match self {
+            Variant::Hypergammon => 3,
+            Variant::Standard | Variant::Nackgammon => 15,
+        }
+    }
+
+    /// Стартовая расстановка player1: (индекс пункта, число шашек).
+    pub fn layout(&self) -> &'static [(usize, i8)] {
+        match self {
+            // 2 на 24-м, 5 на 13-м, 3 на 8-м, 5 на 6-м.
+            Variant::Standard => &[(23, 2), (12, 5), (7, 3), (5, 5)],
+            // 2 на 24-м, 2 на 23-м, 4 на 13-м, 3 на 8-м, 4 на 6-м.
+            Variant::Nackgammon => &[(23, 2), (22, 2), (12, 4), (7, 3), (5, 4)],
+            // по одной на 24-м, 23-м и 22-м.
+            Variant::Hypergammon => &[(23, 1), (22, 1), (21, 1)],
+        }
+    }
+
+    /// Структурная проверка доски: у каждой стороны на доске не больше
+    /// checkers() шашек (остальные на баре или сняты).
+    pub fn board_fits(&self, board: &Board) -> bool {
+        let max = self.checkers() as u32;
+        let p1: u32 = board.iter().filter(|&&v| v > 0).map(|&v| v as u32).sum();
+        let p2: u32 = board
+            .iter()
+            .filter(|&&v| v < 0)
+            .map(|&v| v.unsigned_abs() as u32)
+            .sum();
+        p1 <= max && p2 <= max
+    }
+}
+
+/// Стартовая позиция варианта с форой.
+///
+/// Шашки player1 расставлены по Variant::layout, шашки player2 — зеркально
+/// (индекс 23 - i).
+///
+/// Таблица фор: при |handicap| = n (1..=MAX_HANDICAP) у несущего фору игрока
+/// n шашек снимаются с его 6-го пункта (пункт 6 у player1, пункт 19 у player2)
+/// и стартуют на баре, т.е. на доске их нет. handicap > 0 — фору несёт player1
+/// (более сильный игрок), handicap < 0 — player2. В хайпергаммоне форы нет.
+pub fn start_board(variant: Variant, handicap: i8) -> Result<Board, BoardError> {
+    let level = handicap.unsigned_abs();
+    if level > MAX_HANDICAP || (level > 0 && variant == Variant::Hypergammon) {
+        return Err(BoardError::InvalidHandicap);
+    }
+
+    let mut board = [0i8; 24];
+    for &(point, count) in variant.layout() {
+        board[point] = count;
+        board[23 - point] = -count;
+    }
+
+    if handicap > 0 {
+        board[5] -= level as i8;
+    } else if handicap < 0 {
+        board[18] += level as i8;
+    }
+    Ok(board)
+}
+
+/// Пакует доску в PackedBoard; на пункте не больше 15 шашек.
+pub fn pack(board: &Board) -> Result<PackedBoard, BoardError> {
+    let mut packed = [0u8; 15];
+    for (i, &v) in board.iter().enumerate() {
+        let count = v.unsigned_abs();
+        if count > 15 {
+            return Err(BoardError::InvalidBoard);
+        }
+        let code = count | if v < 0 { 0x10 } else { 0 };
+        let bit = i * 5;
+        let word = (code as u16) << (bit % 8);
+        packed[bit / 8] |= word as u8;
+        if let Some(next) = packed.get_mut(bit / 8 + 1) {
+            *next |= (word >> 8) as u8;
+        }
+    }
+    Ok(packed)
+}
+
+/// Распаковывает PackedBoard; «пустой пункт player2» (бит владельца без
+/// шашек) считается ошибкой, чтобы у каждой доски была одна упаковка.
+pub fn unpack(packed: &PackedBoard) -> Result<Board, BoardError> {
+    let mut board = [0i8; 24];
+    for (i, point) in board.iter_mut().enumerate() {
+        let bit = i * 5;
+        let low = packed[bit / 8] as u16;
+        let high = packed.get(bit / 8 + 1).copied().unwrap_or(0) as u16;
+        let code = (((high << 8) | low) >> (bit % 8)) as u8 & 0x1f;
+        let count = (code & 0x0f) as i8;
+        if code & 0x10 != 0 && count == 0 {
+            return Err(BoardError::InvalidBoard);
+        }
+        *point = if code & 0x10 != 0 { -count } else { count };
+    }
+    Ok(board)
+}
+
+/// Конец партии по доске: победитель (1 или 2) — сторона, у которой на доске
+/// не осталось шашек, пока у соперника они ещё есть.
+pub fn is_terminal(board: &Board) -> Option<u8> {
+    let p1_on_board = board.iter().any(|&v| v > 0);
+    let p2_on_board = board.iter().any(|&v| v < 0);
+    match (p1_on_board, p2_on_board) {
+        (false, true) => Some(1),
+        (true, false) => Some(2),
+        _ => None,
+    }
+}
+
+/// Позиция: доска плюс шашки на баре и снятые, которых доска сама не
+/// различает.
+///
+/// Внутри ходы считаются в координатах ходящего: пункт p = 0..23 (0 — его
+/// 1-й пункт), бар — пункт 24, дом — пункты 0..5.
+///
+/// `Display` рисует ASCII-доску (см. [`render`]), `Debug` — поля и доску.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub board: Board,
+    pub bar: [u8; 2],
+    pub off: [u8; 2],
+}
+
+/// Результаты перебора расстановок одного хода к целевой доске.
+#[derive(Default)]
+struct Search {
+    /// Наибольшее число сыгранных кубиков.
+    max_steps: u8,
+    /// Позиция с целевой доской для каждого числа сыгранных кубиков.
+    matches: [Option<Position>; 5],
+    /// Для разных кубиков: можно ли сыграть кубик i первым.
+    playable: [bool; 2],
+    /// Для разных кубиков: целевая доска одним кубиком i.
+    single: [Option<Position>; 2],
+}
+
+impl Position {
+    /// Стартовая позиция: шашки форы стоят на баре несущего её игрока.
+    pub fn start(variant: Variant, handicap: i8) -> Result<Self, BoardError> {
+        let level = handicap.unsigned_abs();
+        let bar = match handicap.signum() {
+            1 => [level, 0],
+            -1 => [0, level],
+            _ => [0, 0],
+        };
+        Ok(Self {
+            board: start_board(variant, handicap)?,
+            bar,
+            off: [0; 2],
+        })
+    }
+
+    /// Индекс доски для пункта p стороны side.
+    fn index(side: u8, p: usize) -> usize {
+        if side == 1 {
+            p
+        } else {
+            23 - p
+        }
+    }
+
+    /// Шашки стороны side на индексе доски idx.
+    fn count(&self, side: u8, idx: usize) -> u8 {
+        let v = self.board[idx];
+        if side == 1 {
+            v.max(0) as u8
+        } else {
+            v.min(0).unsigned_abs()
+        }
+    }
+
+    /// Все шашки стороны в доме и на баре никого.
+    fn all_home(&self, side: u8) -> bool {
+        self.bar[(side - 1) as usize] == 0
+            && (6..24).all(|p| self.count(side, Self::index(side, p)) == 0)
+    }
+
+    /// Один шаг на кубик die с пункта from (None — с бара), если он законен.
+    fn step(&self, side: u8, from: Option<usize>, die: u8) -> Option<Self> {
+        let me = (side - 1) as usize;
+        let sign: i8 = if side == 1 { 1 } else { -1 };
+        let mut next = *self;
+        let start = match from {
+            None => {
+                if self.bar[me] == 0 {
+                    return None;
+                }
+                next.bar[me] -= 1;
+                BAR
+            }
+            Some(p) => {
+                if self.bar[me] > 0 || self.count(side, Self::index(side, p)) == 0 {
+                    return None;
+                }
+                next.board[Self::index(side, p)] -= sign;
+                p
+            }
+        };
+
+        let dest = start as i32 - die as i32;
+        if dest >= 0 {
+            let idx = Self::index(side, dest as usize);
+            match self.count(3 - side, idx) {
+                0 => {}
+                1 => {
+                    next.board[idx] = 0;
+                    next.bar[1 - me] += 1;
+                }
+                _ => return None,
+            }
+            next.board[idx] += sign;
+        } else {
+            // Снятие: все шашки дома; кубиком больше нужного — только с
+            // самого дальнего занятого пункта.
+            if !self.all_home(side) {
+                return None;
+            }
+            if dest < -1 && (start + 1..6).any(|q| self.count(side, Self::index(side, q)) > 0) {
+                return None;
+            }
+            next.off[me] += 1;
+        }
+        Some(next)
+    }
+
+    /// Все законные одиночные шаги на кубик die.
+    fn steps(&self, side: u8, die: u8) -> impl Iterator<Item = Self> + '_ {
+        core::iter::once(None)
+            .chain((0..24).map(Some))
+            .filter_map(move |from| self.step(side, from, die))
+    }
+
+    /// Перебор расстановок: dice — оставшиеся кубики, first — индекс кубика,
+    /// сыгранного первым. Возвращает true, если целевая доска достигнута
+    /// всеми кубиками (дальше искать незачем).
+    fn search(
+        &self,
+        side: u8,
+        dice: &[u8],
+        depth: usize,
+        first: usize,
+        target: &Board,
+        out: &mut Search,
+    ) -> bool {
+        if depth > 0 {
+            out.max_steps = out.max_steps.max(depth as u8);
+            if self.board == *target {
+                out.matches[depth].get_or_insert(*self);
+                if depth == 1 {
+                    out.single[first].get_or_insert(*self);
+                }
+                if depth == dice.len() {
+                    return true;
+                }
+            }
+        }
+        let Some(&die) = dice.get(depth) else {
+            return false;
+        };
+        for next in self.steps(side, die) {
+            if depth == 0 {
+                out.playable[first] = true;
+            }
+            if next.search(side, dice, depth + 1, first, target, out) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Все позиции после хода: (число сыгранных кубиков, индекс кубика,
+    /// сыгранного первым, позиция).
+    fn collect(
+        &self,
+        side: u8,
+        dice: &[u8],
+        depth: usize,
+        first: usize,
+        out: &mut Vec<(usize, usize, Position)>,
+    ) {
+        let mut moved = false;
+        if let Some(&die) = dice.get(depth) {
+            for next in self.steps(side, die) {
+                moved = true;
+                next.collect(side, dice, depth + 1, first, out);
+            }
+        }
+        if !moved {
+            out.push((depth, first, *self));
+        }
+    }
+
+    /// Сумма пипсов стороны: расстояние каждой шашки до снятия (бар = 25).
+    pub fn pip_count(&self, side: u8) -> u32 {
+        let on_board: u32 = (0..24)
+            .map(|p| self.count(side, Self::index(side, p)) as u32 * (p as u32 + 1))
+            .sum();
+        on_board + self.bar[(side - 1) as usize] as u32 * (BAR as u32 + 1)
+    }
+
+    /// Сторона сняла все шашки варианта.
+    pub fn borne_off(&self, side: u8, variant: Variant) -> bool {
+        self.off[(side - 1) as usize] >= variant.checkers()
+    }
+}
+
+fn valid_dice(dice: [u8; 2]) -> bool {
+    dice.iter().all(|d| (1..=6).contains(d))
+}
+
+/// Законный ход стороны side с кубиками dice, приводящий к доске target.
+///
+/// Правила: сыграть нужно наибольшее возможное число кубиков, а если
+/// можно сыграть только один из двух разных — по возможности больший.
+pub fn apply_move(
+    position: &Position,
+    side: u8,
+    dice: [u8; 2],
+    target: &Board,
+) -> Option<Position> {
+    if !valid_dice(dice) {
+        return None;
+    }
+    let mut out = Search::default();
+    if dice[0] == dice[1] {
+        position.search(side, &[dice[0]; 4], 0, 0, target, &mut out);
+    } else {
+        position.search(side, &dice, 0, 0, target, &mut out);
+        position.search(side, &[dice[1], dice[0]], 0, 1, target, &mut out);
+    }
+
+    match out.max_steps {
+        0 => (position.board == *target).then_some(*position),
+        1 if dice[0] != dice[1] => {
+            let larger = if dice[0] > dice[1] { 0 } else { 1 };
+            if out.playable[larger] {
+                out.single[larger]
+            } else {
+                out.single[1 - larger]
+            }
+        }
+        steps => out.matches[steps as usize],
+    }
+}
+
+/// Все различные позиции после законного хода стороны side с кубиками dice
+/// (по тем же правилам, что и apply_move). Если сыграть нечего — одна
+/// исходная позиция; при недопустимых кубиках — пусто.
+pub fn legal_moves(position: &Position, side: u8, dice: [u8; 2]) -> Vec<Position> {
+    if !valid_dice(dice) {
+        return Vec::new();
+    }
+    let mut all = Vec::new();
+    if dice[0] == dice[1] {
+        position.collect(side, &[dice[0]; 4], 0, 0, &mut all);
+    } else {
+        position.collect(side, &dice, 0, 0, &mut all);
+        position.collect(side, &[dice[1], dice[0]], 0, 1, &mut all);
+    }
+
+    let max_steps = all.iter().map(|&(steps, _, _)| steps).max().unwrap_or(0);
+    let larger = if dice[0] > dice[1] { 0 } else { 1 };
+    let larger_playable = all
+        .iter()
+        .any(|&(steps, first, _)| steps > 0 && first == larger);
+    let mut moves: Vec<Position> = Vec::new();
+    for (steps, first, next) in all {
+        if steps != max_steps {
+            continue;
+        }
+        if max_steps == 1 && dice[0] != dice[1] && larger_playable && first != larger {
+            continue;
+        }
+        if !moves.contains(&next) {
+            moves.push(next);
+        }
+    }
+    moves
+}
+
+/// Сумма пипсов стороны side на позиции.
+pub fn pip_count(position: &Position, side: u8) -> u32 {
+    position.pip_count(side)
+}
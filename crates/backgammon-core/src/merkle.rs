@@ -0,0 +1,183 @@
+//! Merkle-коммитмент истории ходов: листья — хэши записей ходов по порядку,
+//! дерево фиксированной глубины [`HISTORY_TREE_DEPTH`] дополнено нулевыми
+//! листьями справа.
+//!
+//! Хэши: лист — `sha256(0x00 || move_index || mover || board || dice ||
+//! slot || fee_lamports)` (числа little-endian), узел — `sha256(0x01 ||
+//! left || right)`, пустой лист — 32 нулевых байта. Префиксы не дают выдать
+//! внутренний узел за лист.
+//!
+//! Программа хранит только фронтир ([`HistoryFrontier`]: по одному узлу на
+//! уровень) и дописывает лист за O(глубины) хэшей; оффчейн-код строит корень
+//! и доказательства по всем листьям ([`merkle_root`], [`merkle_proof`]).
+//!
+//! sha256 передаётся через [`Sha256`], чтобы крейт не зависел от Solana:
+//! программа подставляет системный вызов, оффчейн-код — любую реализацию.
+
+use alloc::vec::Vec;
+
+use crate::PackedBoard;
+
+/// Хэш sha256.
+pub type Hash = [u8; 32];
+
+/// Глубина дерева истории: до 2^16 - 1 листьев покрывает любой u16-лимит
+/// ходов партии.
+pub const HISTORY_TREE_DEPTH: usize = 16;
+
+/// Сколько листьев помещается в дерево.
+pub const MAX_HISTORY_LEAVES: u64 = (1 << HISTORY_TREE_DEPTH) - 1;
+
+/// Доказательство листа: соседние узлы от листа к корню.
+pub type MerkleProof = [Hash; HISTORY_TREE_DEPTH];
+
+/// sha256 от конкатенации частей.
+pub trait Sha256 {
+    fn hashv(parts: &[&[u8]]) -> Hash;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MerkleError {
+    /// В дереве уже MAX_HISTORY_LEAVES листьев.
+    TreeFull,
+}
+
+/// Запись хода, из которой строится лист.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MoveLeaf {
+    /// Номер хода с 1; лист лежит на позиции move_index - 1.
+    pub move_index: u64,
+    pub mover: [u8; 32],
+    pub board: PackedBoard,
+    pub dice: [u8; 2],
+    pub slot: u64,
+    pub fee_lamports: u64,
+}
+
+impl MoveLeaf {
+    pub fn hash<H: Sha256>(&self) -> Hash {
+        H::hashv(&[
+            &[0x00],
+            &self.move_index.to_le_bytes(),
+            &self.mover,
+            &self.board,
+            &self.dice,
+            &self.slot.to_le_bytes(),
+            &self.fee_lamports.to_le_bytes(),
+        ])
+    }
+}
+
+fn node_hash<H: Sha256>(left: &Hash, right: &Hash) -> Hash {
+    H::hashv(&[&[0x01], left, right])
+}
+
+/// Фронтир дописываемого дерева: branch[h] — левый узел уровня h, ещё
+/// ждущий правого соседа (значим, только если бит h в count установлен).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct HistoryFrontier {
+    pub count: u64,
+    pub branch: [Hash; HISTORY_TREE_DEPTH],
+}
+
+impl HistoryFrontier {
+    /// Дописывает лист; хэшей не больше глубины дерева.
+    pub fn append<H: Sha256>(&mut self, leaf: Hash) -> Result<(), MerkleError> {
+        if self.count >= MAX_HISTORY_LEAVES {
+            return Err(MerkleError::TreeFull);
+        }
+        self.count += 1;
+        let mut size = self.count;
+        let mut node = leaf;
+        for branch in self.branch.iter_mut() {
+            if size & 1 == 1 {
+                *branch = node;
+                break;
+            }
+            node = node_hash::<H>(branch, &node);
+            size >>= 1;
+        }
+        Ok(())
+    }
+
+    /// Корень дерева из count листьев, дополненного нулевыми.
+    pub fn root<H: Sha256>(&self) -> Hash {
+        let mut node = [0u8; 32];
+        let mut zero = [0u8; 32];
+        let mut size = self.count;
+        for branch in &self.branch {
+            node = if size & 1 == 1 {
+                node_hash::<H>(branch, &node)
+            } else {
+                node_hash::<H>(&node, &zero)
+            };
+            zero = node_hash::<H>(&zero, &zero);
+            size >>= 1;
+        }
+        node
+    }
+}
+
+/// Уровни дерева над листьями: levels[h] — непустые узлы уровня h (правее
+/// них одни нулевые поддеревья), zeros[h] — хэш нулевого поддерева.
+fn levels<H: Sha256>(leaves: &[Hash]) -> (Vec<Vec<Hash>>, [Hash; HISTORY_TREE_DEPTH + 1]) {
+    let mut zeros = [[0u8; 32]; HISTORY_TREE_DEPTH + 1];
+    for h in 0..HISTORY_TREE_DEPTH {
+        zeros[h + 1] = node_hash::<H>(&zeros[h], &zeros[h]);
+    }
+    let mut levels = Vec::with_capacity(HISTORY_TREE_DEPTH + 1);
+    levels.push(leaves.to_vec());
+    for h in 0..HISTORY_TREE_DEPTH {
+        let next = levels[h]
+            .chunks(2)
+            .map(|pair| node_hash::<H>(&pair[0], pair.get(1).unwrap_or(&zeros[h])))
+            .collect();
+        levels.push(next);
+    }
+    (levels, zeros)
+}
+
+/// Корень дерева по всем листьям (то же, что HistoryFrontier::root после
+/// дописывания их по порядку). None — листьев больше MAX_HISTORY_LEAVES.
+pub fn merkle_root<H: Sha256>(leaves: &[Hash]) -> Option<Hash> {
+    if leaves.len() as u64 > MAX_HISTORY_LEAVES {
+        return None;
+    }
+    let (levels, zeros) = levels::<H>(leaves);
+    Some(
+        levels[HISTORY_TREE_DEPTH]
+            .first()
+            .copied()
+            .unwrap_or(zeros[HISTORY_TREE_DEPTH]),
+    )
+}
+
+/// Доказательство листа index; None — такого листа нет.
+pub fn merkle_proof<H: Sha256>(leaves: &[Hash], index: usize) -> Option<MerkleProof> {
+    if index >= leaves.len() || leaves.len() as u64 > MAX_HISTORY_LEAVES {
+        return None;
+    }
+    let (levels, zeros) = levels::<H>(leaves);
+    let mut proof = [[0u8; 32]; HISTORY_TREE_DEPTH];
+    for (h, sibling) in proof.iter_mut().enumerate() {
+        let position = (index >> h) ^ 1;
+        *sibling = levels[h].get(position).copied().unwrap_or(zeros[h]);
+    }
+    Some(proof)
+}
+
+/// Проверяет, что leaf лежит в дереве с корнем root на позиции index.
+pub fn verify_proof<H: Sha256>(root: &Hash, leaf: &Hash, index: u64, proof: &MerkleProof) -> bool {
+    if index >= MAX_HISTORY_LEAVES {
+        return false;
+    }
+    let mut node = *leaf;
+    for (h, sibling) in proof.iter().enumerate() {
+        node = if (index >> h) & 1 == 0 {
+            node_hash::<H>(&node, sibling)
+        } else {
+            node_hash::<H>(sibling, &node)
+        };
+    }
+    node == *root
+}
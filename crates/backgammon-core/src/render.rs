@@ -0,0 +1,156 @@
+//! ASCII-доска для отладки: `Display` и `Debug` у Position, `show` в CLI.
+//!
+//! Рисует и структурно неверные доски (больше 15 шашек на пункте или у
+//! стороны) — как раз тогда она и нужна; такие места помечаются строками
+//! с `!!` под доской.
+
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::{Board, Position};
+
+/// Сколько шашек рисуется в столбике; дальше — число.
+const STACK: usize = 5;
+/// Больше шашек у стороны не бывает ни в одном варианте.
+const MAX_CHECKERS: u32 = 15;
+
+/// Клетка пункта в строке row (0 — у края доски).
+fn cell(value: i8, row: usize) -> String {
+    let count = value.unsigned_abs() as usize;
+    let symbol = if value > 0 { 'X' } else { 'O' };
+    if count <= row {
+        String::new()
+    } else if row == STACK - 1 && count > STACK {
+        format!("{}", count)
+    } else {
+        format!("{}", symbol)
+    }
+}
+
+/// Клетка бара: число шашек стороны на баре в первой строке половины.
+fn bar_cell(symbol: char, count: u8, row: usize) -> String {
+    if row == 0 && count > 0 {
+        format!("{}{}", symbol, count)
+    } else {
+        String::new()
+    }
+}
+
+fn push_row(out: &mut String, left: [String; 6], bar: String, right: [String; 6]) {
+    let mut line = String::new();
+    for c in &left {
+        let _ = write!(line, "{:>3}", c);
+    }
+    let _ = write!(line, " |{:^3}|", bar);
+    for c in &right {
+        let _ = write!(line, "{:>3}", c);
+    }
+    out.push_str(line.trim_end());
+    out.push('\n');
+}
+
+fn push_numbers(
+    out: &mut String,
+    left: impl Iterator<Item = usize>,
+    right: impl Iterator<Item = usize>,
+) {
+    let mut line = String::new();
+    for p in left {
+        let _ = write!(line, "{:>3}", p);
+    }
+    line.push_str(" |BAR|");
+    for p in right {
+        let _ = write!(line, "{:>3}", p);
+    }
+    out.push_str(&line);
+    out.push('\n');
+}
+
+const BORDER: &str = "-------------------+---+------------------";
+
+/// Доска позиции: сверху пункты 13..24, снизу 12..1 (с точки зрения
+/// player1), X — player1, O — player2; бар в середине, снятые — под доской.
+pub fn render(position: &Position) -> String {
+    let b = &position.board;
+    let mut out = String::new();
+
+    push_numbers(&mut out, 13..=18, 19..=24);
+    out.push_str(BORDER);
+    out.push('\n');
+    for row in 0..STACK {
+        push_row(
+            &mut out,
+            core::array::from_fn(|i| cell(b[12 + i], row)),
+            bar_cell('O', position.bar[1], row),
+            core::array::from_fn(|i| cell(b[18 + i], row)),
+        );
+    }
+    push_row(
+        &mut out,
+        Default::default(),
+        String::new(),
+        Default::default(),
+    );
+    for row in (0..STACK).rev() {
+        push_row(
+            &mut out,
+            core::array::from_fn(|i| cell(b[11 - i], row)),
+            bar_cell('X', position.bar[0], row),
+            core::array::from_fn(|i| cell(b[5 - i], row)),
+        );
+    }
+    out.push_str(BORDER);
+    out.push('\n');
+    push_numbers(&mut out, (7..=12).rev(), (1..=6).rev());
+    let _ = writeln!(
+        out,
+        "bar: X {} O {}  off: X {} O {}",
+        position.bar[0], position.bar[1], position.off[0], position.off[1]
+    );
+
+    for (i, &v) in b.iter().enumerate() {
+        if v.unsigned_abs() > 15 {
+            let _ = writeln!(out, "!! point {}: {} checkers", i + 1, v.unsigned_abs());
+        }
+    }
+    for (side, symbol) in [(0usize, 'X'), (1, 'O')] {
+        let on_board: u32 = b
+            .iter()
+            .filter(|&&v| if side == 0 { v > 0 } else { v < 0 })
+            .map(|&v| v.unsigned_abs() as u32)
+            .sum();
+        let total = on_board + position.bar[side] as u32 + position.off[side] as u32;
+        if total > MAX_CHECKERS {
+            let _ = writeln!(out, "!! {}: {} checkers", symbol, total);
+        }
+    }
+    out
+}
+
+/// Доска без бара и снятых шашек.
+pub fn render_board(board: &Board) -> String {
+    render(&Position {
+        board: *board,
+        bar: [0; 2],
+        off: [0; 2],
+    })
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&render(self))
+    }
+}
+
+/// Поля и доска целиком: в упавшем assert_eq видно, что на доске.
+impl fmt::Debug for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Position {{ board: {:?}, bar: {:?}, off: {:?} }}",
+            self.board, self.bar, self.off
+        )?;
+        f.write_str(&render(self))
+    }
+}
@@ -0,0 +1,615 @@
+//! Запись партии в текстовом формате `.mat` (как у GNU Backgammon) и разбор
+//! обратно.
+//!
+//! Ходы пишутся в обычной нотации `31: 8/5 6/5` — пункты в координатах
+//! ходящего, `bar` и `off` для бара и снятия, `*` для боя. Вариант, фора и
+//! итоговая доска лежат в тегах-комментариях `; [Имя "значение"]`, которые
+//! GNU Backgammon пропускает. Разбор переигрывает каждый ход по правилам и
+//! сверяет итог с тегом `Final`.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::{apply_move, Board, BoardError, Position, Variant};
+
+/// Действие партии; очередь сторон следует из порядка (первым ходит player1).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    /// Ход стороны на очереди: кубики и доска после хода.
+    Move { dice: [u8; 2], board: Board },
+    /// Сторона на очереди удваивает до своего хода; cube — новое значение.
+    Double { cube: u32 },
+    /// Соперник принимает удвоение и забирает куб.
+    Take,
+    /// Соперник отказывается; партия окончена.
+    Drop,
+}
+
+/// Итог партии: победитель (1 или 2) и число очков.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Outcome {
+    pub winner: u8,
+    pub points: u32,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Transcript {
+    pub variant: Variant,
+    pub handicap: i8,
+    /// Имена игроков (без пробелов — так их читает GNU Backgammon).
+    pub players: [String; 2],
+    pub actions: Vec<Action>,
+    pub outcome: Option<Outcome>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TranscriptError {
+    Board(BoardError),
+    /// Строка line (с 1) не разобрана.
+    Syntax {
+        line: usize,
+    },
+    /// Действие action (с 0) не по правилам: ход не получается из позиции,
+    /// удвоение или ответ не в свою очередь, действие после конца партии,
+    /// итог с другим победителем.
+    IllegalAction {
+        action: usize,
+    },
+    /// Переигранная доска не совпала с тегом Final.
+    FinalMismatch,
+}
+
+impl From<BoardError> for TranscriptError {
+    fn from(err: BoardError) -> Self {
+        TranscriptError::Board(err)
+    }
+}
+
+/// Ширина левой колонки (ходы player1) после номера строки.
+const COLUMN: usize = 30;
+/// Отступ, с которого запись без левой колонки считается правой.
+const RIGHT_INDENT: usize = 10;
+
+/// Состояние переигрывания: позиция, очередь и куб.
+struct Replay {
+    variant: Variant,
+    position: Position,
+    /// Сторона на очереди.
+    turn: u8,
+    cube: u32,
+    /// Владелец куба (0 — в центре).
+    owner: u8,
+    pending_double: bool,
+    /// Победитель, если партия кончилась на доске или отказом.
+    winner: Option<u8>,
+}
+
+impl Replay {
+    fn new(variant: Variant, handicap: i8) -> Result<Self, TranscriptError> {
+        Ok(Self {
+            variant,
+            position: Position::start(variant, handicap)?,
+            turn: 1,
+            cube: 1,
+            owner: 0,
+            pending_double: false,
+            winner: None,
+        })
+    }
+
+    /// Кто сделал бы действие: ход и удвоение — сторона на очереди, ответ — соперник.
+    fn actor(&self, action: &Action) -> u8 {
+        match action {
+            Action::Move { .. } | Action::Double { .. } => self.turn,
+            Action::Take | Action::Drop => 3 - self.turn,
+        }
+    }
+
+    /// Применяет действие; ход — уже проверенной позицией next.
+    fn play(&mut self, action: &Action, next: Option<Position>) -> bool {
+        if self.winner.is_some() {
+            return false;
+        }
+        match *action {
+            Action::Move { dice, board } => {
+                if self.pending_double {
+                    return false;
+                }
+                let Some(next) =
+                    next.or_else(|| apply_move(&self.position, self.turn, dice, &board))
+                else {
+                    return false;
+                };
+                self.position = next;
+                if next.borne_off(self.turn, self.variant) {
+                    self.winner = Some(self.turn);
+                }
+                self.turn = 3 - self.turn;
+            }
+            Action::Double { cube } => {
+                if self.pending_double
+                    || cube != self.cube * 2
+                    || (self.owner != 0 && self.owner != self.turn)
+                {
+                    return false;
+                }
+                self.pending_double = true;
+            }
+            Action::Take => {
+                if !self.pending_double {
+                    return false;
+                }
+                self.pending_double = false;
+                self.cube *= 2;
+                self.owner = 3 - self.turn;
+            }
+            Action::Drop => {
+                if !self.pending_double {
+                    return false;
+                }
+                self.pending_double = false;
+                self.winner = Some(self.turn);
+            }
+        }
+        true
+    }
+
+    fn check_outcome(&self, outcome: &Outcome) -> bool {
+        (1..=2).contains(&outcome.winner) && self.winner.is_none_or(|w| w == outcome.winner)
+    }
+}
+
+impl Transcript {
+    /// Позиции партии: стартовая и после каждого хода.
+    pub fn positions(&self) -> Result<Vec<Position>, TranscriptError> {
+        let mut replay = Replay::new(self.variant, self.handicap)?;
+        let mut positions = Vec::with_capacity(self.actions.len() + 1);
+        positions.push(replay.position);
+        for (i, action) in self.actions.iter().enumerate() {
+            if !replay.play(action, None) {
+                return Err(TranscriptError::IllegalAction { action: i });
+            }
+            if matches!(action, Action::Move { .. }) {
+                positions.push(replay.position);
+            }
+        }
+        if let Some(outcome) = &self.outcome {
+            if !replay.check_outcome(outcome) {
+                return Err(TranscriptError::IllegalAction {
+                    action: self.actions.len(),
+                });
+            }
+        }
+        Ok(positions)
+    }
+
+    /// Текст `.mat`; каждый ход должен быть законным (см. [`Self::positions`]).
+    pub fn to_mat(&self) -> Result<String, TranscriptError> {
+        let mut replay = Replay::new(self.variant, self.handicap)?;
+        let mut entries: Vec<(u8, String)> = Vec::new();
+        for (i, action) in self.actions.iter().enumerate() {
+            let side = replay.actor(action);
+            let before = replay.position;
+            if !replay.play(action, None) {
+                return Err(TranscriptError::IllegalAction { action: i });
+            }
+            let text = match *action {
+                Action::Move { dice, .. } => {
+                    let mut text = format!("{}{}:", dice[0], dice[1]);
+                    let checkers = path(&before, side, dice, &replay.position)
+                        .ok_or(TranscriptError::IllegalAction { action: i })?;
+                    for c in checkers {
+                        text.push(' ');
+                        text.push_str(&c.notation());
+                    }
+                    text
+                }
+                Action::Double { cube } => format!("Doubles => {}", cube),
+                Action::Take => "Takes".to_string(),
+                Action::Drop => "Drops".to_string(),
+            };
+            entries.push((side, text));
+        }
+        if let Some(outcome) = &self.outcome {
+            if !replay.check_outcome(outcome) {
+                return Err(TranscriptError::IllegalAction {
+                    action: self.actions.len(),
+                });
+            }
+            let plural = if outcome.points == 1 { "" } else { "s" };
+            entries.push((
+                outcome.winner,
+                format!("Wins {} point{}", outcome.points, plural),
+            ));
+        }
+
+        let mut out = String::new();
+        let _ = writeln!(out, "; [Variation \"{}\"]", variant_name(self.variant));
+        if self.handicap != 0 {
+            let _ = writeln!(out, "; [Handicap \"{}\"]", self.handicap);
+        }
+        let final_board: Vec<String> = replay
+            .position
+            .board
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        let _ = writeln!(out, "; [Final \"{}\"]", final_board.join(" "));
+        out.push_str("\n 0 point match\n\n Game 1\n");
+        let _ = writeln!(
+            out,
+            " {} : 0{}{} : 0",
+            self.players[0],
+            pad(self.players[0].len() + 4),
+            self.players[1]
+        );
+
+        let mut line = 0;
+        let mut row: [Option<String>; 2] = [None, None];
+        for (side, text) in entries {
+            let column = (side - 1) as usize;
+            if row[column].is_some() || (column == 0 && row[1].is_some()) {
+                line += 1;
+                push_row(&mut out, line, &mut row);
+            }
+            row[column] = Some(text);
+        }
+        if row.iter().any(Option::is_some) {
+            push_row(&mut out, line + 1, &mut row);
+        }
+        Ok(out)
+    }
+
+    /// Разбор `.mat` с одной партией: ходы переигрываются по правилам, итог
+    /// сверяется с тегом Final (если он есть).
+    pub fn parse(text: &str) -> Result<Self, TranscriptError> {
+        let mut variant = Variant::Standard;
+        let mut handicap = 0i8;
+        let mut final_board: Option<Board> = None;
+        let mut players: Option<[String; 2]> = None;
+        let mut game_seen = false;
+        let mut entries: Vec<(usize, u8, &str)> = Vec::new();
+
+        for (i, raw) in text.lines().enumerate() {
+            let line = i + 1;
+            let syntax = TranscriptError::Syntax { line };
+            let trimmed = raw.trim();
+            if trimmed.is_empty() || trimmed.ends_with("point match") {
+                continue;
+            }
+            if let Some(tag) = trimmed.strip_prefix(';') {
+                let Some((name, value)) = parse_tag(tag) else {
+                    continue;
+                };
+                match name {
+                    "Variation" => variant = parse_variant(value).ok_or(syntax)?,
+                    "Handicap" => handicap = value.parse().map_err(|_| syntax)?,
+                    "Final" => final_board = Some(parse_board(value).ok_or(syntax)?),
+                    _ => {}
+                }
+                continue;
+            }
+            if trimmed.starts_with("Game ") {
+                if game_seen {
+                    return Err(syntax);
+                }
+                game_seen = true;
+                continue;
+            }
+            if let Some((number, _)) = trimmed.split_once(')') {
+                if !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) {
+                    // Колонки различаются по отступу: левая запись идёт сразу
+                    // за номером, правая — после пробелов.
+                    let rest = &raw[raw.find(')').unwrap_or(0) + 1..];
+                    let indent = rest.len() - rest.trim_start().len();
+                    if indent >= RIGHT_INDENT {
+                        entries.push((line, 2, rest.trim()));
+                    } else {
+                        let rest = rest.trim();
+                        let (left, right) = match rest.find("  ") {
+                            Some(at) => (rest[..at].trim(), rest[at..].trim()),
+                            None => (rest, ""),
+                        };
+                        entries.push((line, 1, left));
+                        if !right.is_empty() {
+                            entries.push((line, 2, right));
+                        }
+                    }
+                    continue;
+                }
+            }
+            if game_seen && players.is_none() {
+                let tokens: Vec<&str> = trimmed.split_whitespace().collect();
+                match tokens.as_slice() {
+                    [p1, ":", _, p2, ":", _] => {
+                        players = Some([p1.to_string(), p2.to_string()]);
+                        continue;
+                    }
+                    _ => return Err(syntax),
+                }
+            }
+            return Err(syntax);
+        }
+
+        let mut replay = Replay::new(variant, handicap)?;
+        let mut actions = Vec::new();
+        let mut outcome = None;
+        for (line, side, text) in entries {
+            let syntax = TranscriptError::Syntax { line };
+            let illegal = TranscriptError::IllegalAction {
+                action: actions.len(),
+            };
+            if outcome.is_some() {
+                return Err(illegal);
+            }
+            if let Some(points) = text.strip_prefix("Wins ") {
+                let points = points
+                    .trim_end_matches("points")
+                    .trim_end_matches("point")
+                    .trim()
+                    .parse()
+                    .map_err(|_| syntax)?;
+                let result = Outcome {
+                    winner: side,
+                    points,
+                };
+                if !replay.check_outcome(&result) {
+                    return Err(illegal);
+                }
+                outcome = Some(result);
+                continue;
+            }
+            let (action, next) = if let Some(cube) = text.strip_prefix("Doubles =>") {
+                let cube = cube.trim().parse().map_err(|_| syntax)?;
+                (Action::Double { cube }, None)
+            } else if text == "Takes" {
+                (Action::Take, None)
+            } else if text == "Drops" || text == "Passes" {
+                (Action::Drop, None)
+            } else {
+                let (dice, moves) = parse_move(text).ok_or(syntax)?;
+                if side != replay.turn || replay.winner.is_some() {
+                    return Err(illegal);
+                }
+                let next = play_notation(&replay.position, side, dice, &moves).ok_or(illegal)?;
+                (
+                    Action::Move {
+                        dice,
+                        board: next.board,
+                    },
+                    Some(next),
+                )
+            };
+            if side != replay.actor(&action) || !replay.play(&action, next) {
+                return Err(illegal);
+            }
+            actions.push(action);
+        }
+
+        if final_board.is_some_and(|board| board != replay.position.board) {
+            return Err(TranscriptError::FinalMismatch);
+        }
+        Ok(Self {
+            variant,
+            handicap,
+            players: players.unwrap_or_default(),
+            actions,
+            outcome,
+        })
+    }
+}
+
+fn variant_name(variant: Variant) -> &'static str {
+    match variant {
+        Variant::Standard => "Backgammon",
+        Variant::Nackgammon => "Nackgammon",
+        Variant::Hypergammon => "Hypergammon",
+    }
+}
+
+fn parse_variant(name: &str) -> Option<Variant> {
+    [Variant::Standard, Variant::Nackgammon, Variant::Hypergammon]
+        .into_iter()
+        .find(|&v| variant_name(v) == name)
+}
+
+/// `[Имя "значение"]` → (имя, значение).
+fn parse_tag(tag: &str) -> Option<(&str, &str)> {
+    let inner = tag.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let (name, value) = inner.split_once(' ')?;
+    Some((name, value.trim().strip_prefix('"')?.strip_suffix('"')?))
+}
+
+fn parse_board(value: &str) -> Option<Board> {
+    let mut board = [0i8; 24];
+    let mut points = value.split_whitespace();
+    for point in board.iter_mut() {
+        *point = points.next()?.parse().ok()?;
+    }
+    points.next().is_none().then_some(board)
+}
+
+fn pad(used: usize) -> String {
+    " ".repeat(COLUMN.saturating_sub(used).max(2))
+}
+
+fn push_row(out: &mut String, line: usize, row: &mut [Option<String>; 2]) {
+    let left = row[0].take().unwrap_or_default();
+    let right = row[1].take().unwrap_or_default();
+    let mut text = format!("{:>3}) {}", line, left);
+    if !right.is_empty() {
+        text.push_str(&pad(left.len()));
+        text.push_str(&right);
+    }
+    out.push_str(text.trim_end());
+    out.push('\n');
+}
+
+/// Перемещение шашки: пункты ходящего 1..24, 25 — бар, 0 — снятие.
+#[derive(Clone, Copy)]
+struct Checker {
+    from: u8,
+    to: u8,
+    hit: bool,
+}
+
+impl Checker {
+    fn notation(&self) -> String {
+        let point = |p: u8, edge: &str| match p {
+            0 | 25 => edge.to_string(),
+            p => p.to_string(),
+        };
+        format!(
+            "{}/{}{}",
+            point(self.from, "bar"),
+            point(self.to, "off"),
+            if self.hit { "*" } else { "" }
+        )
+    }
+}
+
+/// Один шаг на кубик die с пункта from (1..25) и его запись.
+fn step(position: &Position, side: u8, from: u8, die: u8) -> Option<(Position, Checker)> {
+    let start = if from == 25 {
+        None
+    } else {
+        Some(from as usize - 1)
+    };
+    let next = position.step(side, start, die)?;
+    let opponent = (2 - side) as usize;
+    Some((
+        next,
+        Checker {
+            from,
+            to: from.saturating_sub(die),
+            hit: next.bar[opponent] > position.bar[opponent],
+        },
+    ))
+}
+
+/// Последовательность шагов, переводящая позицию в target (target уже
+/// проверен apply_move).
+fn path(position: &Position, side: u8, dice: [u8; 2], target: &Position) -> Option<Vec<Checker>> {
+    fn search(
+        position: &Position,
+        side: u8,
+        dice: &[u8],
+        target: &Position,
+        out: &mut Vec<Checker>,
+    ) -> bool {
+        if position == target {
+            return true;
+        }
+        let Some((&die, rest)) = dice.split_first() else {
+            return false;
+        };
+        for from in (1..=25).rev() {
+            if let Some((next, checker)) = step(position, side, from, die) {
+                out.push(checker);
+                if search(&next, side, rest, target, out) {
+                    return true;
+                }
+                out.pop();
+            }
+        }
+        false
+    }
+
+    let orders: Vec<Vec<u8>> = if dice[0] == dice[1] {
+        alloc::vec![alloc::vec![dice[0]; 4]]
+    } else {
+        alloc::vec![alloc::vec![dice[0], dice[1]], alloc::vec![dice[1], dice[0]]]
+    };
+    let mut out = Vec::new();
+    orders
+        .iter()
+        .any(|order| search(position, side, order, target, &mut out))
+        .then_some(out)
+}
+
+/// Перемещение из записи: (from, to) в пунктах ходящего, как у [`Checker`].
+type Hop = (u8, u8);
+
+/// `31: 8/5 6/5*` → кубики и перемещения (from, to); поддерживаются цепочки
+/// `24/18/13` и повторы `13/7(2)`.
+fn parse_move(text: &str) -> Option<([u8; 2], Vec<Hop>)> {
+    let (dice, moves) = text.split_once(':')?;
+    let digits: Vec<u8> = dice.trim().bytes().map(|b| b.wrapping_sub(b'0')).collect();
+    let dice = match digits.as_slice() {
+        &[a, b] if (1..=6).contains(&a) && (1..=6).contains(&b) => [a, b],
+        _ => return None,
+    };
+    let point = |p: &str| -> Option<u8> {
+        match p.trim_end_matches('*') {
+            "bar" => Some(25),
+            "off" => Some(0),
+            n => n.parse().ok().filter(|n| (1..=24).contains(n)),
+        }
+    };
+    let mut hops = Vec::new();
+    for token in moves.split_whitespace() {
+        let (token, repeat) = match token.split_once('(') {
+            Some((token, count)) => (token, count.strip_suffix(')')?.parse().ok()?),
+            None => (token, 1usize),
+        };
+        let points: Vec<u8> = token.split('/').map(point).collect::<Option<_>>()?;
+        if points.len() < 2 {
+            return None;
+        }
+        for _ in 0..repeat {
+            for pair in points.windows(2) {
+                hops.push((pair[0], pair[1]));
+            }
+        }
+    }
+    Some((dice, hops))
+}
+
+/// Переигрывает перемещения из записи и проверяет ход целиком по правилам.
+/// Перемещение может использовать несколько кубиков одной шашкой (`24/13`).
+fn play_notation(position: &Position, side: u8, dice: [u8; 2], hops: &[Hop]) -> Option<Position> {
+    fn hop(
+        position: &Position,
+        side: u8,
+        from: u8,
+        to: u8,
+        dice: &mut Vec<u8>,
+    ) -> Option<Position> {
+        for i in 0..dice.len() {
+            let die = dice[i];
+            if dice[..i].contains(&die) || (to > 0 && from < to + die) {
+                continue;
+            }
+            let Some((next, checker)) = step(position, side, from, die) else {
+                continue;
+            };
+            dice.remove(i);
+            if checker.to == to {
+                return Some(next);
+            }
+            if checker.to > 0 {
+                if let Some(done) = hop(&next, side, checker.to, to, dice) {
+                    return Some(done);
+                }
+            }
+            dice.insert(i, die);
+        }
+        None
+    }
+
+    let mut remaining: Vec<u8> = if dice[0] == dice[1] {
+        alloc::vec![dice[0]; 4]
+    } else {
+        alloc::vec![dice[0], dice[1]]
+    };
+    let mut current = *position;
+    for &(from, to) in hops {
+        if from <= to {
+            return None;
+        }
+        current = hop(&current, side, from, to, &mut remaining)?;
+    }
+    apply_move(position, side, dice, &current.board).filter(|p| *p == current)
+}
@@ -0,0 +1,77 @@
+//! Общий с backgammon-wasm набор примеров (tests/fixtures/rules.json): тот же
+//! файл проверяет JS-харнесс, так что браузер и программа видят одни правила.
+
+use backgammon_core::{apply_move, legal_moves, pack, unpack, Board, PackedBoard, Position};
+use serde_json::Value;
+
+fn fixture() -> Value {
+    serde_json::from_str(include_str!("fixtures/rules.json")).unwrap()
+}
+
+fn array<const N: usize, T: TryFrom<i64>>(value: &Value) -> [T; N] {
+    let items: Vec<T> = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| T::try_from(v.as_i64().unwrap()).ok().unwrap())
+        .collect();
+    items.try_into().ok().unwrap()
+}
+
+fn position(value: &Value) -> Position {
+    Position {
+        board: array(&value["board"]),
+        bar: array(&value["bar"]),
+        off: array(&value["off"]),
+    }
+}
+
+fn cases<'a>(fixture: &'a Value, key: &str) -> &'a Vec<Value> {
+    fixture[key].as_array().unwrap()
+}
+
+#[test]
+fn legal_moves_match_fixture() {
+    let fixture = fixture();
+    for case in cases(&fixture, "legal_moves") {
+        let side = case["side"].as_u64().unwrap() as u8;
+        let dice: [u8; 2] = array(&case["dice"]);
+        let mut moves = legal_moves(&position(&case["position"]), side, dice);
+        moves.sort_by_key(|m| (m.board, m.bar, m.off));
+        let expected: Vec<Position> = case["moves"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(position)
+            .collect();
+        assert_eq!(moves, expected, "{}", case["name"]);
+    }
+}
+
+#[test]
+fn transitions_match_fixture() {
+    let fixture = fixture();
+    for case in cases(&fixture, "transitions") {
+        let side = case["side"].as_u64().unwrap() as u8;
+        let dice: [u8; 2] = array(&case["dice"]);
+        let target: Board = array(&case["target"]);
+        let result = apply_move(&position(&case["position"]), side, dice, &target);
+        let expected = (!case["result"].is_null()).then(|| position(&case["result"]));
+        assert_eq!(result, expected, "{}", case["name"]);
+    }
+}
+
+#[test]
+fn packing_matches_fixture() {
+    let fixture = fixture();
+    for case in cases(&fixture, "packing") {
+        let board: Board = array(&case["board"]);
+        let packed: PackedBoard = array(&case["packed"]);
+        assert_eq!(pack(&board).unwrap(), packed);
+        assert_eq!(unpack(&packed).unwrap(), board);
+    }
+    for case in cases(&fixture, "invalid_packed") {
+        let packed: PackedBoard = array(&case["packed"]);
+        assert!(unpack(&packed).is_err(), "{}", case["name"]);
+    }
+}
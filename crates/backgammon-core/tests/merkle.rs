@@ -0,0 +1,143 @@
+use backgammon_core::{
+    merkle_proof, merkle_root, pack, start_board, verify_proof, Hash, HistoryFrontier, MerkleError,
+    MoveLeaf, Variant, MAX_HISTORY_LEAVES,
+};
+use sha2::Digest;
+
+struct Sha256;
+
+impl backgammon_core::Sha256 for Sha256 {
+    fn hashv(parts: &[&[u8]]) -> Hash {
+        let mut hasher = sha2::Sha256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+}
+
+fn leaf(move_index: u64) -> MoveLeaf {
+    MoveLeaf {
+        move_index,
+        mover: [move_index as u8 % 2 + 1; 32],
+        board: pack(&start_board(Variant::Standard, 0).unwrap()).unwrap(),
+        dice: [3, 1],
+        slot: 1_000 + move_index,
+        fee_lamports: 5_000,
+    }
+}
+
+fn leaves(count: u64) -> Vec<Hash> {
+    (1..=count).map(|i| leaf(i).hash::<Sha256>()).collect()
+}
+
+#[test]
+fn frontier_root_matches_the_full_tree() {
+    let mut frontier = HistoryFrontier::default();
+    assert_eq!(
+        frontier.root::<Sha256>(),
+        merkle_root::<Sha256>(&[]).unwrap()
+    );
+    let all = leaves(70);
+    for (i, &leaf) in all.iter().enumerate() {
+        frontier.append::<Sha256>(leaf).unwrap();
+        assert_eq!(frontier.count, i as u64 + 1);
+        assert_eq!(
+            frontier.root::<Sha256>(),
+            merkle_root::<Sha256>(&all[..=i]).unwrap(),
+            "{} leaves",
+            i + 1
+        );
+    }
+}
+
+#[test]
+fn every_leaf_proves_against_the_root() {
+    for count in [1, 2, 3, 8, 33] {
+        let all = leaves(count);
+        let root = merkle_root::<Sha256>(&all).unwrap();
+        for (i, leaf) in all.iter().enumerate() {
+            let proof = merkle_proof::<Sha256>(&all, i).unwrap();
+            assert!(verify_proof::<Sha256>(&root, leaf, i as u64, &proof));
+            // Тот же лист на чужой позиции не проходит.
+            assert!(!verify_proof::<Sha256>(
+                &root,
+                leaf,
+                (i as u64 + 1) % count.max(2),
+                &proof
+            ));
+        }
+        assert_eq!(merkle_proof::<Sha256>(&all, all.len()), None);
+    }
+}
+
+#[test]
+fn falsified_leaf_fails_verification() {
+    let all = leaves(20);
+    let root = merkle_root::<Sha256>(&all).unwrap();
+    let proof = merkle_proof::<Sha256>(&all, 6).unwrap();
+    let honest = leaf(7);
+    assert!(verify_proof::<Sha256>(
+        &root,
+        &honest.hash::<Sha256>(),
+        6,
+        &proof
+    ));
+
+    // Другая доска, кубики, слот или комиссия при том же доказательстве.
+    let mut falsified = honest;
+    falsified.dice = [6, 6];
+    assert!(!verify_proof::<Sha256>(
+        &root,
+        &falsified.hash::<Sha256>(),
+        6,
+        &proof
+    ));
+    let mut falsified = honest;
+    falsified.board[0] ^= 1;
+    assert!(!verify_proof::<Sha256>(
+        &root,
+        &falsified.hash::<Sha256>(),
+        6,
+        &proof
+    ));
+    let mut falsified = honest;
+    falsified.fee_lamports = 0;
+    assert!(!verify_proof::<Sha256>(
+        &root,
+        &falsified.hash::<Sha256>(),
+        6,
+        &proof
+    ));
+
+    // Подменённый сосед в доказательстве.
+    let mut forged = proof;
+    forged[3][0] ^= 1;
+    assert!(!verify_proof::<Sha256>(
+        &root,
+        &honest.hash::<Sha256>(),
+        6,
+        &forged
+    ));
+
+    // Внутренний узел за лист не выдать.
+    let (left, right) = (all[6], all[7]);
+    let parent = <Sha256 as backgammon_core::Sha256>::hashv(&[&[0x01], &left, &right]);
+    let mut shortened = [[0u8; 32]; 16];
+    shortened[..15].copy_from_slice(&proof[1..]);
+    assert!(!verify_proof::<Sha256>(&root, &parent, 3, &shortened));
+}
+
+#[test]
+fn full_tree_rejects_more_leaves() {
+    let mut frontier = HistoryFrontier {
+        count: MAX_HISTORY_LEAVES - 1,
+        ..HistoryFrontier::default()
+    };
+    frontier.append::<Sha256>([1; 32]).unwrap();
+    assert_eq!(
+        frontier.append::<Sha256>([2; 32]),
+        Err(MerkleError::TreeFull)
+    );
+    assert_eq!(frontier.count, MAX_HISTORY_LEAVES);
+}
@@ -0,0 +1,112 @@
+use backgammon_core::{render_board, start_board, Board, Position, Variant};
+
+/// Снимки пишутся с переноса строки после кавычки, чтобы колонки совпадали.
+fn snapshot(text: &str) -> &str {
+    text.strip_prefix('\n').unwrap()
+}
+
+#[test]
+fn start_position_snapshot() {
+    let position = Position::start(Variant::Standard, 0).unwrap();
+    assert_eq!(
+        position.to_string(),
+        snapshot(
+            "
+ 13 14 15 16 17 18 |BAR| 19 20 21 22 23 24
+-------------------+---+------------------
+  X           O    |   |  O              X
+  X           O    |   |  O              X
+  X           O    |   |  O
+  X                |   |  O
+  X                |   |  O
+                   |   |
+  O                |   |  X
+  O                |   |  X
+  O           X    |   |  X
+  O           X    |   |  X              O
+  O           X    |   |  X              O
+-------------------+---+------------------
+ 12 11 10  9  8  7 |BAR|  6  5  4  3  2  1
+bar: X 0 O 0  off: X 0 O 0
+"
+        )
+    );
+    assert_eq!(
+        render_board(&start_board(Variant::Standard, 0).unwrap()),
+        position.to_string()
+    );
+}
+
+#[test]
+fn mid_game_snapshot() {
+    let mut board: Board = [0; 24];
+    board[0] = 7;
+    board[1] = 3;
+    board[4] = -1;
+    board[5] = 2;
+    board[7] = -2;
+    board[17] = -4;
+    board[22] = -6;
+    let position = Position {
+        board,
+        bar: [1, 0],
+        off: [2, 2],
+    };
+    assert_eq!(
+        position.to_string(),
+        snapshot(
+            "
+ 13 14 15 16 17 18 |BAR| 19 20 21 22 23 24
+-------------------+---+------------------
+                 O |   |              O
+                 O |   |              O
+                 O |   |              O
+                 O |   |              O
+                   |   |              6
+                   |   |
+                   |   |                 7
+                   |   |                 X
+                   |   |              X  X
+              O    |   |  X           X  X
+              O    |X1 |  X  O        X  X
+-------------------+---+------------------
+ 12 11 10  9  8  7 |BAR|  6  5  4  3  2  1
+bar: X 1 O 0  off: X 2 O 2
+"
+        )
+    );
+}
+
+#[test]
+fn tall_stacks_show_their_count() {
+    let mut board: Board = [0; 24];
+    board[23] = -12;
+    let text = render_board(&board);
+    assert!(text.contains("|   |                12\n"), "{}", text);
+}
+
+#[test]
+fn invalid_boards_are_drawn_with_warnings() {
+    let mut board = start_board(Variant::Standard, 0).unwrap();
+    board[0] = 20;
+    let position = Position {
+        board,
+        bar: [0, 3],
+        off: [0, 0],
+    };
+    let text = position.to_string();
+    assert!(text.starts_with(" 13 14 15"), "{}", text);
+    assert!(
+        text.ends_with("!! point 1: 20 checkers\n!! X: 35 checkers\n!! O: 16 checkers\n"),
+        "{}",
+        text
+    );
+}
+
+#[test]
+fn debug_shows_fields_and_board() {
+    let position = Position::start(Variant::Standard, 0).unwrap();
+    let debug = format!("{:?}", position);
+    assert!(debug.starts_with("Position { board: [-2, 0,"), "{}", debug);
+    assert!(debug.ends_with(&position.to_string()), "{}", debug);
+}
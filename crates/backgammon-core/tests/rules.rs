@@ -0,0 +1,396 @@
+use backgammon_core::{
+    apply_move, is_terminal, legal_moves, pack, pip_count, start_board, unpack, Board, BoardError,
+    Position, Variant, MAX_HANDICAP,
+};
+
+const VARIANTS: [Variant; 3] = [Variant::Standard, Variant::Nackgammon, Variant::Hypergammon];
+
+/// Все 21 различный бросок.
+fn rolls() -> impl Iterator<Item = [u8; 2]> {
+    (1..=6u8).flat_map(|a| (a..=6).map(move |b| [b, a]))
+}
+
+fn position(board: Board, bar: [u8; 2]) -> Position {
+    Position {
+        board,
+        bar,
+        off: [0; 2],
+    }
+}
+
+fn board(points: &[(usize, i8)]) -> Board {
+    let mut board = [0i8; 24];
+    for &(i, v) in points {
+        board[i] = v;
+    }
+    board
+}
+
+#[test]
+fn start_boards_are_mirrored_and_complete() {
+    for variant in VARIANTS {
+        let board = start_board(variant, 0).unwrap();
+        for i in 0..24 {
+            assert_eq!(board[i], -board[23 - i], "{:?} point {}", variant, i);
+        }
+        let p1: i32 = board.iter().filter(|&&v| v > 0).map(|&v| v as i32).sum();
+        assert_eq!(p1, variant.checkers() as i32);
+        assert!(variant.board_fits(&board));
+        assert_eq!(is_terminal(&board), None);
+    }
+}
+
+#[test]
+fn handicap_moves_checkers_from_six_point_to_bar() {
+    let plain = start_board(Variant::Standard, 0).unwrap();
+    for level in 1..=MAX_HANDICAP as i8 {
+        let p1 = start_board(Variant::Standard, level).unwrap();
+        assert_eq!(p1[5], plain[5] - level);
+        let p2 = start_board(Variant::Standard, -level).unwrap();
+        assert_eq!(p2[18], plain[18] + level);
+
+        let pos = Position::start(Variant::Standard, level).unwrap();
+        assert_eq!(pos.bar, [level as u8, 0]);
+        let pos = Position::start(Variant::Standard, -level).unwrap();
+        assert_eq!(pos.bar, [0, level as u8]);
+    }
+}
+
+#[test]
+fn invalid_handicaps_are_rejected() {
+    let over = MAX_HANDICAP as i8 + 1;
+    assert_eq!(
+        start_board(Variant::Standard, over),
+        Err(BoardError::InvalidHandicap)
+    );
+    assert_eq!(
+        start_board(Variant::Standard, -over),
+        Err(BoardError::InvalidHandicap)
+    );
+    assert_eq!(
+        start_board(Variant::Hypergammon, 1),
+        Err(BoardError::InvalidHandicap)
+    );
+    assert!(Position::start(Variant::Nackgammon, -over).is_err());
+}
+
+#[test]
+fn board_fits_counts_each_side() {
+    let mut board = start_board(Variant::Hypergammon, 0).unwrap();
+    assert!(Variant::Hypergammon.board_fits(&board));
+    board[10] = 1;
+    assert!(!Variant::Hypergammon.board_fits(&board));
+    assert!(Variant::Standard.board_fits(&board));
+}
+
+#[test]
+fn pack_roundtrips_every_point_value() {
+    for i in 0..24 {
+        for v in -15..=15i8 {
+            let mut board = [0i8; 24];
+            board[i] = v;
+            assert_eq!(unpack(&pack(&board).unwrap()).unwrap(), board);
+        }
+    }
+}
+
+/// Пункт занимает 5 бит и может переходить через границу байта только к
+/// соседу, поэтому перебор всех значений каждой пары соседних пунктов
+/// покрывает все сочетания битов упаковки.
+#[test]
+fn pack_roundtrips_every_adjacent_pair() {
+    for i in 0..23 {
+        for a in -15..=15i8 {
+            for b in -15..=15i8 {
+                let mut board = [0i8; 24];
+                board[i] = a;
+                board[i + 1] = b;
+                let packed = pack(&board).unwrap();
+                assert_eq!(unpack(&packed).unwrap(), board, "points {i}, {}", i + 1);
+            }
+        }
+    }
+}
+
+/// Обратное направление: каждая пара 5-битных кодов соседних пунктов либо
+/// распаковывается и упаковывается обратно в те же байты, либо отклоняется
+/// (бит владельца без шашек) — у доски ровно одна упаковка.
+#[test]
+fn unpack_roundtrips_every_adjacent_code_pair() {
+    for i in 0..23 {
+        for a in 0..32u16 {
+            for b in 0..32u16 {
+                let mut packed = [0u8; 15];
+                for (point, code) in [(i, a), (i + 1, b)] {
+                    let bit = point * 5;
+                    let word = code << (bit % 8);
+                    packed[bit / 8] |= word as u8;
+                    if let Some(next) = packed.get_mut(bit / 8 + 1) {
+                        *next |= (word >> 8) as u8;
+                    }
+                }
+                let owner_without_checkers = |code: u16| code == 0x10;
+                match unpack(&packed) {
+                    Ok(board) => assert_eq!(pack(&board).unwrap(), packed),
+                    Err(err) => {
+                        assert_eq!(err, BoardError::InvalidBoard);
+                        assert!(owner_without_checkers(a) || owner_without_checkers(b));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn pack_roundtrips_pseudo_random_boards() {
+    let mut seed = 0x2545_f491_4f6c_dd1du64;
+    for _ in 0..10_000 {
+        let mut board = [0i8; 24];
+        for point in board.iter_mut() {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            *point = (seed % 31) as i8 - 15;
+        }
+        assert_eq!(unpack(&pack(&board).unwrap()).unwrap(), board);
+    }
+}
+
+#[test]
+fn pack_rejects_unencodable_boards() {
+    let mut board = [0i8; 24];
+    board[3] = 16;
+    assert_eq!(pack(&board), Err(BoardError::InvalidBoard));
+    board[3] = -16;
+    assert_eq!(pack(&board), Err(BoardError::InvalidBoard));
+
+    // Бит владельца player2 без шашек.
+    let mut packed = [0u8; 15];
+    packed[0] = 0x10;
+    assert_eq!(unpack(&packed), Err(BoardError::InvalidBoard));
+}
+
+#[test]
+fn terminal_when_one_side_has_left_the_board() {
+    assert_eq!(is_terminal(&board(&[(3, -2)])), Some(1));
+    assert_eq!(is_terminal(&board(&[(20, 1)])), Some(2));
+    assert_eq!(is_terminal(&board(&[(3, -2), (20, 1)])), None);
+    assert_eq!(is_terminal(&[0; 24]), None);
+}
+
+#[test]
+fn pip_count_of_start_positions() {
+    let pos = Position::start(Variant::Standard, 0).unwrap();
+    assert_eq!(pip_count(&pos, 1), 167);
+    assert_eq!(pip_count(&pos, 2), 167);
+
+    // Шашка с 6-го пункта на баре: -6 + 25.
+    let pos = Position::start(Variant::Standard, 1).unwrap();
+    assert_eq!(pip_count(&pos, 1), 186);
+    assert_eq!(pip_count(&pos, 2), 167);
+
+    let pos = Position::start(Variant::Hypergammon, 0).unwrap();
+    assert_eq!(pip_count(&pos, 1), 24 + 23 + 22);
+}
+
+#[test]
+fn opening_three_one_makes_the_five_point() {
+    let start = Position::start(Variant::Standard, 0).unwrap();
+    let mut target = start.board;
+    target[7] -= 1;
+    target[5] -= 1;
+    target[4] += 2;
+    let next = apply_move(&start, 1, [3, 1], &target).unwrap();
+    assert_eq!(next.board, target);
+    assert!(legal_moves(&start, 1, [3, 1])
+        .iter()
+        .any(|m| m.board == target));
+
+    // Только 8/5 — кубик 1 не сыгран, хотя можно.
+    let mut partial = start.board;
+    partial[7] -= 1;
+    partial[4] += 1;
+    assert_eq!(apply_move(&start, 1, [3, 1], &partial), None);
+
+    // Стоять на месте нельзя.
+    assert_eq!(apply_move(&start, 1, [3, 1], &start.board), None);
+}
+
+#[test]
+fn opening_double_sixes() {
+    let start = Position::start(Variant::Standard, 0).unwrap();
+    let mut target = start.board;
+    target[23] = 0;
+    target[17] = 2;
+    target[12] -= 2;
+    target[6] = 2;
+    assert!(apply_move(&start, 1, [6, 6], &target).is_some());
+    assert!(legal_moves(&start, 1, [6, 6])
+        .iter()
+        .any(|m| m.board == target));
+}
+
+#[test]
+fn invalid_dice_are_rejected() {
+    let start = Position::start(Variant::Standard, 0).unwrap();
+    for dice in [[0, 3], [7, 1], [0, 0]] {
+        assert_eq!(apply_move(&start, 1, dice, &start.board), None);
+        assert!(legal_moves(&start, 1, dice).is_empty());
+    }
+}
+
+#[test]
+fn larger_die_must_be_played_when_only_one_fits() {
+    // Одна шашка на 13-м пункте; 13 - 6 - 5 = 2-й пункт закрыт, 7-й и 8-й
+    // свободны: сыграть можно только один кубик, и это должна быть шестёрка.
+    let pos = position(board(&[(12, 1), (1, -2)]), [0; 2]);
+    let with_six = board(&[(6, 1), (1, -2)]);
+    let with_five = board(&[(7, 1), (1, -2)]);
+    assert!(apply_move(&pos, 1, [6, 5], &with_six).is_some());
+    assert_eq!(apply_move(&pos, 1, [6, 5], &with_five), None);
+    assert_eq!(
+        legal_moves(&pos, 1, [5, 6]),
+        vec![position(with_six, [0; 2])]
+    );
+}
+
+#[test]
+fn checker_on_bar_must_enter_first() {
+    // player1 на баре, входы 6 (индекс 18) и 5 (индекс 19) закрыты.
+    let blocked = board(&[(18, -2), (19, -2), (3, 1)]);
+    let pos = position(blocked, [1, 0]);
+    assert_eq!(legal_moves(&pos, 1, [6, 5]), vec![pos]);
+    assert!(apply_move(&pos, 1, [6, 5], &blocked).is_some());
+    // Ход другой шашкой при занятом баре незаконен.
+    let other = board(&[(18, -2), (19, -2), (0, 1)]);
+    assert_eq!(apply_move(&pos, 1, [3, 3], &other), None);
+
+    // Вход на открытый пункт: пятёркой на индекс 19, единица — другой
+    // шашкой (19 -> 18 закрыт). Вход единицей оставляет пятёрку несыгранной.
+    let open = position(board(&[(18, -2), (3, 1)]), [1, 0]);
+    let entered = board(&[(18, -2), (19, 1), (2, 1)]);
+    let next = apply_move(&open, 1, [5, 1], &entered).unwrap();
+    assert_eq!(next.bar, [0, 0]);
+    assert_eq!(legal_moves(&open, 1, [5, 1]), vec![next]);
+    assert_eq!(
+        apply_move(&open, 1, [5, 1], &board(&[(18, -2), (23, 1), (3, 1)])),
+        None
+    );
+}
+
+#[test]
+fn hitting_a_blot_sends_it_to_the_bar() {
+    let pos = position(board(&[(10, 1), (7, -1), (0, -3)]), [0; 2]);
+    let target = board(&[(6, 1), (0, -3)]);
+    let next = apply_move(&pos, 1, [3, 1], &target).unwrap();
+    assert_eq!(next.bar, [0, 1]);
+    // Шашки player2 на индексе 0 — его 24-й пункт.
+    assert_eq!(pip_count(&next, 2), 3 * 24 + 25);
+}
+
+#[test]
+fn bearing_off_from_the_farthest_point_only() {
+    let pos = position(board(&[(4, 1), (1, 1), (20, -2)]), [0; 2]);
+    // Шестёркой снимается только самая дальняя шашка.
+    assert_eq!(
+        apply_move(&pos, 1, [6, 1], &board(&[(3, 1), (20, -2)])),
+        None
+    );
+    let next = apply_move(&pos, 1, [6, 1], &board(&[(1, 1), (20, -2)])).unwrap();
+    assert_eq!(next.off, [1, 0]);
+    let next = apply_move(&pos, 1, [6, 1], &board(&[(0, 1), (20, -2)])).unwrap();
+    assert_eq!(next.off, [1, 0]);
+
+    let done = apply_move(&pos, 1, [6, 5], &board(&[(20, -2)])).unwrap();
+    assert_eq!(done.off, [2, 0]);
+    assert_eq!(is_terminal(&done.board), Some(1));
+}
+
+#[test]
+fn no_bearing_off_with_checkers_outside_home() {
+    let pos = position(board(&[(6, 1), (0, 1), (20, -2)]), [0; 2]);
+    // Снять с 1-го пункта нельзя, пока шашка на 7-м.
+    assert_eq!(
+        apply_move(&pos, 1, [1, 1], &board(&[(5, 1), (20, -2)])),
+        None
+    );
+}
+
+#[test]
+fn player2_moves_the_other_way() {
+    let start = Position::start(Variant::Standard, 0).unwrap();
+    // Зеркало 3-1 у player2: 8/5 6/5 = индексы 16, 18 -> 19.
+    let mut target = start.board;
+    target[16] += 1;
+    target[18] += 1;
+    target[19] -= 2;
+    assert!(apply_move(&start, 2, [3, 1], &target).is_some());
+}
+
+/// Каждый ход из legal_moves принимается apply_move и целиком разыгрывает
+/// кубики (на старте снятия нет); соперник теряет пипсы только при взятии.
+#[test]
+fn legal_moves_agree_with_apply_move_from_every_start() {
+    for variant in VARIANTS {
+        let start = Position::start(variant, 0).unwrap();
+        for side in [1u8, 2] {
+            for dice in rolls() {
+                let moves = legal_moves(&start, side, dice);
+                assert!(!moves.is_empty(), "{:?} side {} {:?}", variant, side, dice);
+                let total = if dice[0] == dice[1] {
+                    4 * dice[0] as u32
+                } else {
+                    dice[0] as u32 + dice[1] as u32
+                };
+                for m in &moves {
+                    let played = apply_move(&start, side, dice, &m.board)
+                        .unwrap_or_else(|| panic!("{:?} {:?} {:?}", variant, dice, m.board));
+                    assert_eq!(played.board, m.board);
+                    assert_eq!(pip_count(&start, side) - pip_count(m, side), total);
+                    if m.bar == start.bar {
+                        assert_eq!(pip_count(m, 3 - side), pip_count(&start, 3 - side));
+                    } else {
+                        // Бить можно только в хайпергаммоне, где на старте все шашки — блоты.
+                        assert_eq!(variant, Variant::Hypergammon);
+                        assert!(pip_count(m, 3 - side) > pip_count(&start, 3 - side));
+                    }
+                    assert!(variant.board_fits(&m.board));
+                }
+                for (i, a) in moves.iter().enumerate() {
+                    assert!(moves[i + 1..].iter().all(|b| b != a));
+                }
+            }
+        }
+    }
+}
+
+/// Доски, не входящие в legal_moves, apply_move отвергает.
+#[test]
+fn apply_move_rejects_boards_outside_legal_moves() {
+    let start = Position::start(Variant::Standard, 0).unwrap();
+    for dice in rolls() {
+        let legal: Vec<Board> = legal_moves(&start, 1, dice)
+            .iter()
+            .map(|m| m.board)
+            .collect();
+        for from in 0..24 {
+            for to in 0..24 {
+                let mut target = start.board;
+                if target[from] <= 0 || target[to] < 0 {
+                    continue;
+                }
+                target[from] -= 1;
+                target[to] += 1;
+                assert_eq!(
+                    apply_move(&start, 1, dice, &target).is_some(),
+                    legal.contains(&target),
+                    "{:?} {} -> {}",
+                    dice,
+                    from,
+                    to
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use backgammon_core::{
+    legal_moves, Action, Outcome, Position, Transcript, TranscriptError, Variant,
+};
+
+/// Записанная партия: 39 ходов, удвоение с приёмом, player1 снимает все шашки.
+const GAME: &str = include_str!("fixtures/game.mat");
+
+fn moves(transcript: &Transcript) -> usize {
+    transcript
+        .actions
+        .iter()
+        .filter(|a| matches!(a, Action::Move { .. }))
+        .count()
+}
+
+#[test]
+fn recorded_game_replays_to_final_board() {
+    let game = Transcript::parse(GAME).unwrap();
+    assert_eq!(game.players, ["alice".to_string(), "bob".to_string()]);
+    assert_eq!(moves(&game), 39);
+    assert_eq!(
+        game.outcome,
+        Some(Outcome {
+            winner: 1,
+            points: 2
+        })
+    );
+    assert!(game.actions.contains(&Action::Double { cube: 2 }));
+
+    let positions = game.positions().unwrap();
+    assert_eq!(positions.len(), 40);
+    let last = positions.last().unwrap();
+    assert!(last.borne_off(1, Variant::Standard));
+    assert_eq!(last.board[22], -4);
+    assert_eq!(last.board[23], -1);
+}
+
+#[test]
+fn recorded_game_roundtrips_byte_for_byte() {
+    let game = Transcript::parse(GAME).unwrap();
+    assert_eq!(game.to_mat().unwrap(), GAME);
+}
+
+#[test]
+fn built_transcript_roundtrips_through_text() {
+    let mut position = Position::start(Variant::Nackgammon, 2).unwrap();
+    let mut actions = Vec::new();
+    let rolls = [[3, 1], [6, 6], [5, 2], [4, 4], [2, 1], [6, 3]];
+    for (i, dice) in rolls.into_iter().enumerate() {
+        let side = 1 + (i % 2) as u8;
+        if i == 3 {
+            actions.push(Action::Double { cube: 2 });
+            actions.push(Action::Take);
+        }
+        position = *legal_moves(&position, side, dice).last().unwrap();
+        actions.push(Action::Move {
+            dice,
+            board: position.board,
+        });
+    }
+    let transcript = Transcript {
+        variant: Variant::Nackgammon,
+        handicap: 2,
+        players: ["p1".to_string(), "p2".to_string()],
+        actions,
+        outcome: None,
+    };
+
+    let text = transcript.to_mat().unwrap();
+    assert!(text.contains("; [Variation \"Nackgammon\"]"), "{}", text);
+    assert!(text.contains("; [Handicap \"2\"]"), "{}", text);
+    let parsed = Transcript::parse(&text).unwrap();
+    assert_eq!(parsed, transcript);
+    assert_eq!(parsed.positions().unwrap().last(), Some(&position));
+}
+
+#[test]
+fn final_tag_must_match_replayed_board() {
+    let tampered = GAME.replace("-4 -1\"]", "-3 -2\"]");
+    assert_ne!(tampered, GAME);
+    assert_eq!(
+        Transcript::parse(&tampered),
+        Err(TranscriptError::FinalMismatch)
+    );
+}
+
+#[test]
+fn illegal_moves_are_rejected() {
+    // 7/3 — четвёрка, а выпали 6 и 3.
+    let tampered = GAME.replacen("63: 13/7 7/4", "63: 13/7 7/3", 1);
+    assert_eq!(
+        Transcript::parse(&tampered),
+        Err(TranscriptError::IllegalAction { action: 0 })
+    );
+    // Сыгран только один кубик, хотя можно оба.
+    let tampered = GAME.replacen("63: 13/7 7/4", "63: 13/7", 1);
+    assert_eq!(
+        Transcript::parse(&tampered),
+        Err(TranscriptError::IllegalAction { action: 0 })
+    );
+
+    let mut game = Transcript::parse(GAME).unwrap();
+    if let Action::Move { board, .. } = &mut game.actions[1] {
+        board[0] += 1;
+    }
+    assert_eq!(
+        game.to_mat(),
+        Err(TranscriptError::IllegalAction { action: 1 })
+    );
+}
+
+#[test]
+fn combined_and_repeated_notation_is_accepted() {
+    let split = Transcript::parse(GAME).unwrap();
+    let combined = GAME.replacen("63: 13/7 7/4", "63: 13/4", 1).replacen(
+        "66: 24/18 24/18 13/7 13/7",
+        "66: 24/18(2) 13/7(2)",
+        1,
+    );
+    assert_eq!(Transcript::parse(&combined).unwrap(), split);
+}
+
+#[test]
+fn cube_actions_follow_the_turn() {
+    let mut position = Position::start(Variant::Standard, 0).unwrap();
+    position = *legal_moves(&position, 1, [3, 1]).last().unwrap();
+    let opening = Action::Move {
+        dice: [3, 1],
+        board: position.board,
+    };
+    let game = |actions: Vec<Action>, outcome| Transcript {
+        variant: Variant::Standard,
+        handicap: 0,
+        players: ["a".to_string(), "b".to_string()],
+        actions,
+        outcome,
+    };
+
+    // player2 удваивает, player1 отказывается: партия player2 за 1 очко.
+    let dropped = game(
+        vec![opening, Action::Double { cube: 2 }, Action::Drop],
+        Some(Outcome {
+            winner: 2,
+            points: 1,
+        }),
+    );
+    let text = dropped.to_mat().unwrap();
+    assert_eq!(Transcript::parse(&text).unwrap(), dropped);
+
+    let wrong_winner = game(
+        dropped.actions.clone(),
+        Some(Outcome {
+            winner: 1,
+            points: 1,
+        }),
+    );
+    assert_eq!(
+        wrong_winner.positions(),
+        Err(TranscriptError::IllegalAction { action: 3 })
+    );
+
+    let after_drop = game(
+        vec![opening, Action::Double { cube: 2 }, Action::Drop, opening],
+        None,
+    );
+    assert_eq!(
+        after_drop.positions(),
+        Err(TranscriptError::IllegalAction { action: 3 })
+    );
+
+    let skipped_cube_value = game(vec![Action::Double { cube: 4 }], None);
+    assert_eq!(
+        skipped_cube_value.positions(),
+        Err(TranscriptError::IllegalAction { action: 0 })
+    );
+
+    // Ответ на удвоение в колонке удвоившего.
+    let (head, _) = text.split_once("  2) Drops").unwrap();
+    let text = format!("{}  2)                  Drops\n", head);
+    assert_eq!(
+        Transcript::parse(&text),
+        Err(TranscriptError::IllegalAction { action: 2 })
+    );
+}
@@ -0,0 +1,133 @@
+//! Привязки wasm-bindgen к движку правил backgammon-core для браузерного
+//! клиента: те же функции, которыми программа проверяет ходы.
+//!
+//! Позиция в JS — `{ board: number[24], bar: [p1, p2], off: [p1, p2] }`,
+//! доска — массив из 24 чисел (как `boardPoints` в IDL), упакованная доска —
+//! `Uint8Array` из 15 байт (аргумент `make_move_packed`).
+
+use backgammon_core::{Board, PackedBoard, Position, Variant};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+#[derive(Serialize, Deserialize)]
+struct PositionJson {
+    board: Board,
+    bar: [u8; 2],
+    off: [u8; 2],
+}
+
+impl From<Position> for PositionJson {
+    fn from(p: Position) -> Self {
+        Self {
+            board: p.board,
+            bar: p.bar,
+            off: p.off,
+        }
+    }
+}
+
+impl From<PositionJson> for Position {
+    fn from(p: PositionJson) -> Self {
+        Self {
+            board: p.board,
+            bar: p.bar,
+            off: p.off,
+        }
+    }
+}
+
+fn position(value: JsValue) -> Result<Position, JsError> {
+    let json: PositionJson = serde_wasm_bindgen::from_value(value)
+        .map_err(|err| JsError::new(&format!("invalid position: {}", err)))?;
+    Ok(json.into())
+}
+
+fn board(value: JsValue) -> Result<Board, JsError> {
+    serde_wasm_bindgen::from_value(value)
+        .map_err(|err| JsError::new(&format!("invalid board: {}", err)))
+}
+
+fn dice(values: &[u8]) -> Result<[u8; 2], JsError> {
+    values
+        .try_into()
+        .map_err(|_| JsError::new("dice must have exactly two values"))
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value).map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Стартовая позиция варианта ("standard", "nackgammon", "hypergammon") с форой.
+#[wasm_bindgen(js_name = startPosition)]
+pub fn start_position(variant: &str, handicap: i8) -> Result<JsValue, JsError> {
+    let variant = match variant {
+        "standard" => Variant::Standard,
+        "nackgammon" => Variant::Nackgammon,
+        "hypergammon" => Variant::Hypergammon,
+        other => return Err(JsError::new(&format!("unknown variant {}", other))),
+    };
+    let start = Position::start(variant, handicap)
+        .map_err(|err| JsError::new(&format!("{:?}", err)))?;
+    to_js(&PositionJson::from(start))
+}
+
+/// Все различные позиции после законного хода стороны side (1 или 2).
+#[wasm_bindgen(js_name = legalMoves)]
+pub fn legal_moves(position_js: JsValue, side: u8, dice_js: &[u8]) -> Result<JsValue, JsError> {
+    let moves: Vec<PositionJson> =
+        backgammon_core::legal_moves(&position(position_js)?, side, dice(dice_js)?)
+            .into_iter()
+            .map(Into::into)
+            .collect();
+    to_js(&moves)
+}
+
+/// Проверка перехода к доске target: позиция после хода или `null`, если
+/// ход незаконен. Та же проверка, что в программе (submit_game_log).
+#[wasm_bindgen(js_name = validateMove)]
+pub fn validate_move(
+    position_js: JsValue,
+    side: u8,
+    dice_js: &[u8],
+    target: JsValue,
+) -> Result<JsValue, JsError> {
+    let next = backgammon_core::apply_move(
+        &position(position_js)?,
+        side,
+        dice(dice_js)?,
+        &board(target)?,
+    );
+    match next {
+        Some(next) => to_js(&PositionJson::from(next)),
+        None => Ok(JsValue::NULL),
+    }
+}
+
+/// Победитель (1 или 2) по доске или `undefined`.
+#[wasm_bindgen(js_name = isTerminal)]
+pub fn is_terminal(board_js: JsValue) -> Result<Option<u8>, JsError> {
+    Ok(backgammon_core::is_terminal(&board(board_js)?))
+}
+
+#[wasm_bindgen(js_name = pipCount)]
+pub fn pip_count(position_js: JsValue, side: u8) -> Result<u32, JsError> {
+    Ok(backgammon_core::pip_count(&position(position_js)?, side))
+}
+
+/// Доска (24 числа) -> 15 байт упакованной доски.
+#[wasm_bindgen(js_name = packBoard)]
+pub fn pack_board(board_js: JsValue) -> Result<Vec<u8>, JsError> {
+    backgammon_core::pack(&board(board_js)?)
+        .map(|packed| packed.to_vec())
+        .map_err(|err| JsError::new(&format!("{:?}", err)))
+}
+
+/// 15 байт упакованной доски -> доска (24 числа).
+#[wasm_bindgen(js_name = unpackBoard)]
+pub fn unpack_board(packed: &[u8]) -> Result<JsValue, JsError> {
+    let packed: PackedBoard = packed
+        .try_into()
+        .map_err(|_| JsError::new("packed board must be 15 bytes"))?;
+    let board = backgammon_core::unpack(&packed).map_err(|err| JsError::new(&format!("{:?}", err)))?;
+    to_js(&board)
+}
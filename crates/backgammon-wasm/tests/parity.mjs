@@ -0,0 +1,44 @@
+// Паритет wasm-привязок с Rust: прогоняет общий набор примеров
+// crates/backgammon-core/tests/fixtures/rules.json (его же проверяет
+// `cargo test -p backgammon-core`). Запуск: npm test (нужен wasm-pack).
+import assert from "node:assert/strict";
+import { readFileSync } from "node:fs";
+import { createRequire } from "node:module";
+
+const require = createRequire(import.meta.url);
+const wasm = require("../pkg-node/backgammon_wasm.js");
+const fixture = JSON.parse(
+  readFileSync(new URL("../../backgammon-core/tests/fixtures/rules.json", import.meta.url), "utf8")
+);
+
+const key = (position) => JSON.stringify([position.board, position.bar, position.off]);
+const sorted = (positions) => [...positions].sort((a, b) => key(a).localeCompare(key(b)));
+const plain = (position) =>
+  position && { board: [...position.board], bar: [...position.bar], off: [...position.off] };
+
+let checks = 0;
+
+for (const c of fixture.legal_moves) {
+  const moves = wasm.legalMoves(c.position, c.side, Uint8Array.from(c.dice)).map(plain);
+  assert.deepEqual(sorted(moves), sorted(c.moves), c.name);
+  checks++;
+}
+
+for (const c of fixture.transitions) {
+  const result = wasm.validateMove(c.position, c.side, Uint8Array.from(c.dice), c.target);
+  assert.deepEqual(plain(result), c.result, c.name);
+  checks++;
+}
+
+for (const c of fixture.packing) {
+  assert.deepEqual([...wasm.packBoard(c.board)], c.packed);
+  assert.deepEqual([...wasm.unpackBoard(Uint8Array.from(c.packed))], c.board);
+  checks++;
+}
+
+for (const c of fixture.invalid_packed) {
+  assert.throws(() => wasm.unpackBoard(Uint8Array.from(c.packed)), c.name);
+  checks++;
+}
+
+console.log(`wasm parity OK (${checks} cases)`);
@@ -0,0 +1,449 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use pooler::cpi::accounts::{AcceptAdmin, FinishGame, InitGame, MakeMove, UpdateConfig};
+use pooler::program::Backgammon;
+use pooler::{ConfigUpdate, GameOptions, GameState, MoveOutcome};
+
+declare_id!("GLeHkBMVVY48PUw1uhVqyGahehyuuvZitqe8vXtwLnhT");
+
+/// Минимальная программа-посредник: создаёт игры и ходит в них от имени
+/// своего PDA («места» оператора) через CPI в backgammon.
+///
+/// Место — system-owned PDA [b"seat", authority] без данных: так оно может
+/// платить ставку и комиссии системными переводами, а подпись за него
+/// программа ставит через invoke_signed. Для backgammon такой игрок ничем не
+/// отличается от обычного ключа.
+///
+/// Выплаты банка (finish_game, force_refund, manual_refund, claim_*)
+/// backgammon через CPI не принимает (CpiNotAllowed, см. pooler::CPI_MATRIX),
+/// их подписывают игроки в собственной транзакции; try_finish_game показывает
+/// этот отказ.
+///
+/// Заодно программа играет роль макета управления (мультисиг, SPL
+/// Governance): PDA [b"governance", authority] может стать администратором
+/// backgammon и подписывать админские инструкции через invoke_signed.
+#[program]
+pub mod cpi_caller {
+    use super::*;
+
+    /// Пополнение места оператора (ставки, комиссии, рента аккаунтов игры).
+    pub fn fund_seat(ctx: Context<FundSeat>, amount: u64) -> Result<()> {
+        msg!(
+            "fund_seat: seat={}, amount={}",
+            ctx.accounts.seat.key(),
+            amount
+        );
+
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.seat.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, amount)
+    }
+
+    /// Создание игры, в которой player1 — место оператора.
+    pub fn create_game(
+        ctx: Context<CreateGame>,
+        game_id: u64,
+        stake_lamports: u64,
+        move_fee_lamports: u64,
+        player2_pubkey: Pubkey,
+        options: GameOptions,
+    ) -> Result<()> {
+        msg!(
+            "create_game: game={}, seat={}",
+            ctx.accounts.game.key(),
+            ctx.accounts.seat.key()
+        );
+
+        let authority = ctx.accounts.authority.key();
+        let bump = [ctx.bumps.seat];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"seat", authority.as_ref(), &bump]];
+        let cpi_accounts = InitGame {
+            game: ctx.accounts.game.to_account_info(),
+            player1: ctx.accounts.seat.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            config: ctx.accounts.config.to_account_info(),
+            player1_ban: None,
+            open_games: ctx.accounts.open_games.to_account_info(),
+            time_control: ctx
+                .accounts
+                .time_control
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            create_cooldown: ctx.accounts.create_cooldown.to_account_info(),
+            fee_route: ctx.accounts.fee_route.as_ref().map(|a| a.to_account_info()),
+            whitelisted_arbiter: ctx
+                .accounts
+                .whitelisted_arbiter
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            game_counter: None,
+            game_history: None,
+            history_commitment: None,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.backgammon_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        pooler::cpi::init_game(
+            cpi_ctx,
+            game_id,
+            stake_lamports,
+            move_fee_lamports,
+            player2_pubkey,
+            options,
+        )
+    }
+
+    /// Ход места оператора: место подписывает как ходящий (mover), подпись
+    /// соперника не нужна. Итог хода backgammon возвращает через return
+    /// data, и он же возвращается отсюда.
+    pub fn play_move(
+        ctx: Context<PlayMove>,
+        new_board_points: [i8; 24],
+        new_dice: [u8; 2],
+        annotation_hash: [u8; 32],
+    ) -> Result<MoveOutcome> {
+        let game = &ctx.accounts.game;
+        let seat = ctx.accounts.seat.to_account_info();
+        require!(
+            game.player1 == seat.key() || game.player2 == seat.key(),
+            CallerError::SeatNotInGame
+        );
+
+        let authority = ctx.accounts.authority.key();
+        let bump = [ctx.bumps.seat];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"seat", authority.as_ref(), &bump]];
+        let cpi_accounts = MakeMove {
+            game: game.to_account_info(),
+            mover: seat,
+            opponent: ctx.accounts.opponent.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            bot_vault: None,
+            config: ctx.accounts.config.to_account_info(),
+            team: None,
+            time_control: ctx
+                .accounts
+                .time_control
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            fee_route: ctx.accounts.fee_route.as_ref().map(|a| a.to_account_info()),
+            fee_recipient: ctx
+                .accounts
+                .fee_recipient
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            insurance_pool: ctx
+                .accounts
+                .insurance_pool
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            client_telemetry: None,
+            event_buffer: None,
+            game_history: ctx
+                .accounts
+                .game_history
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            history_commitment: ctx
+                .accounts
+                .history_commitment
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            decay_sink: ctx
+                .accounts
+                .decay_sink
+                .as_ref()
+                .map(|a| a.to_account_info()),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.backgammon_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        let outcome =
+            pooler::cpi::make_move(cpi_ctx, new_board_points, new_dice, annotation_hash, None)?
+                .get();
+
+        msg!(
+            "play_move: move_index={}, current_turn={}, turn_open={}",
+            outcome.move_index,
+            outcome.current_turn,
+            outcome.turn_open
+        );
+        Ok(outcome)
+    }
+
+    /// Попытка завершить игру места через CPI в finish_game. Соперник
+    /// подписывает внешнюю транзакцию, место — через invoke_signed, но
+    /// backgammon всё равно отвечает CpiNotAllowed: выплаты только на верхнем
+    /// уровне транзакции. Оставлена для проверки матрицы CPI на localnet.
+    pub fn try_finish_game(
+        ctx: Context<TryFinishGame>,
+        winner: Pubkey,
+        result_multiplier: u8,
+    ) -> Result<()> {
+        let game = &ctx.accounts.game;
+        let seat = ctx.accounts.seat.to_account_info();
+        let opponent = ctx.accounts.opponent.to_account_info();
+        let (player1, player2) = if game.player1 == seat.key() {
+            (seat, opponent)
+        } else {
+            require_keys_eq!(game.player2, seat.key(), CallerError::SeatNotInGame);
+            (opponent, seat)
+        };
+
+        let authority = ctx.accounts.authority.key();
+        let bump = [ctx.bumps.seat];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"seat", authority.as_ref(), &bump]];
+        let cpi_accounts = FinishGame {
+            game: game.to_account_info(),
+            player1,
+            player2,
+            system_program: ctx.accounts.system_program.to_account_info(),
+            config: ctx.accounts.config.to_account_info(),
+            season: None,
+            player1_stats: None,
+            player2_stats: None,
+            bot_vault: None,
+            escrow_agent: None,
+            team: None,
+            player1_splits: ctx.accounts.player1_splits.to_account_info(),
+            player2_splits: ctx.accounts.player2_splits.to_account_info(),
+            event_buffer: None,
+            fee_vault: None,
+            incinerator: None,
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.backgammon_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.backgammon_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        pooler::cpi::finish_game(cpi_ctx, winner, result_multiplier)
+    }
+
+    /// Принятие прав администратора backgammon PDA управления (после
+    /// propose_admin от текущего администратора).
+    pub fn govern_accept_admin(ctx: Context<Govern>) -> Result<()> {
+        msg!(
+            "govern_accept_admin: governance={}",
+            ctx.accounts.governance.key()
+        );
+
+        let authority = ctx.accounts.authority.key();
+        let bump = [ctx.bumps.governance];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"governance", authority.as_ref(), &bump]];
+        let cpi_accounts = AcceptAdmin {
+            config: ctx.accounts.config.to_account_info(),
+            new_admin: ctx.accounts.governance.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.backgammon_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        pooler::cpi::accept_admin(cpi_ctx)
+    }
+
+    /// Изменение конфига backgammon от имени PDA управления — админское
+    /// действие, которое в настоящей программе выполняется после голосования.
+    pub fn govern_update_config(ctx: Context<Govern>, update: ConfigUpdate) -> Result<()> {
+        msg!(
+            "govern_update_config: governance={}",
+            ctx.accounts.governance.key()
+        );
+
+        let authority = ctx.accounts.authority.key();
+        let bump = [ctx.bumps.governance];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"governance", authority.as_ref(), &bump]];
+        let cpi_accounts = UpdateConfig {
+            config: ctx.accounts.config.to_account_info(),
+            admin: ctx.accounts.governance.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.backgammon_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        pooler::cpi::update_config(cpi_ctx, update)
+    }
+}
+
+/// Контекст для пополнения места.
+#[derive(Accounts)]
+pub struct FundSeat<'info> {
+    /// Место оператора.
+    #[account(mut, seeds = [b"seat", authority.key().as_ref()], bump)]
+    pub seat: SystemAccount<'info>,
+
+    /// Оператор, владелец места.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+/// Контекст для создания игры от имени места.
+#[derive(Accounts)]
+pub struct CreateGame<'info> {
+    /// Новый аккаунт игры (keypair, подписывает внешнюю транзакцию).
+    #[account(mut)]
+    pub game: Signer<'info>,
+
+    /// Место оператора, станет player1.
+    #[account(mut, seeds = [b"seat", authority.key().as_ref()], bump)]
+    pub seat: SystemAccount<'info>,
+
+    /// Оператор, владелец места.
+    pub authority: Signer<'info>,
+
+    /// CHECK: validated by backgammon (config PDA)
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: validated by backgammon (open_games PDA of the seat)
+    #[account(mut)]
+    pub open_games: UncheckedAccount<'info>,
+
+    /// CHECK: validated by backgammon (create_cooldown PDA of the seat)
+    #[account(mut)]
+    pub create_cooldown: UncheckedAccount<'info>,
+
+    /// CHECK: validated by backgammon (time_control PDA, presets other than Casual)
+    #[account(mut)]
+    pub time_control: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by backgammon (fee_route PDA, only with options.fee_recipient)
+    #[account(mut)]
+    pub fee_route: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by backgammon (arbiter whitelist PDA, only with options.arbiter)
+    pub whitelisted_arbiter: Option<UncheckedAccount<'info>>,
+
+    /// Программа backgammon.
+    pub backgammon_program: Program<'info, Backgammon>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+/// Контекст для хода места.
+#[derive(Accounts)]
+pub struct PlayMove<'info> {
+    /// Игра, в которой место — один из игроков.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Место оператора.
+    #[account(mut, seeds = [b"seat", authority.key().as_ref()], bump)]
+    pub seat: SystemAccount<'info>,
+
+    /// Оператор, владелец места.
+    pub authority: Signer<'info>,
+
+    /// CHECK: the other player of the game, validated by backgammon
+    pub opponent: UncheckedAccount<'info>,
+
+    /// CHECK: validated by backgammon (config PDA)
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: validated by backgammon (time_control PDA)
+    #[account(mut)]
+    pub time_control: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by backgammon (fee_route PDA)
+    #[account(mut)]
+    pub fee_route: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by backgammon against fee_route
+    #[account(mut)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by backgammon (insurance_pool PDA)
+    #[account(mut)]
+    pub insurance_pool: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by backgammon (game_history of this game)
+    #[account(mut)]
+    pub game_history: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by backgammon (history_commitment of this game)
+    #[account(mut)]
+    pub history_commitment: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by backgammon against config.decay_destination()
+    #[account(mut)]
+    pub decay_sink: Option<UncheckedAccount<'info>>,
+
+    /// Программа backgammon.
+    pub backgammon_program: Program<'info, Backgammon>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+/// Контекст для попытки завершить игру через CPI.
+#[derive(Accounts)]
+pub struct TryFinishGame<'info> {
+    /// Игра, в которой место — один из игроков.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Место оператора.
+    #[account(mut, seeds = [b"seat", authority.key().as_ref()], bump)]
+    pub seat: SystemAccount<'info>,
+
+    /// Оператор, владелец места.
+    pub authority: Signer<'info>,
+
+    /// Второй игрок; подписывает внешнюю транзакцию.
+    #[account(mut)]
+    pub opponent: Signer<'info>,
+
+    /// CHECK: validated by backgammon (config PDA)
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: validated by backgammon (event authority PDA)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: validated by backgammon (payout splits PDA of game.player1)
+    pub player1_splits: UncheckedAccount<'info>,
+
+    /// CHECK: validated by backgammon (payout splits PDA of game.player2)
+    pub player2_splits: UncheckedAccount<'info>,
+
+    /// Программа backgammon.
+    pub backgammon_program: Program<'info, Backgammon>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+/// Контекст для админских действий от имени PDA управления.
+#[derive(Accounts)]
+pub struct Govern<'info> {
+    /// PDA управления; администратор backgammon.
+    #[account(seeds = [b"governance", authority.key().as_ref()], bump)]
+    pub governance: SystemAccount<'info>,
+
+    /// Владелец PDA управления (в макете вместо голосования).
+    pub authority: Signer<'info>,
+
+    /// CHECK: validated by backgammon (config PDA)
+    #[account(mut)]
+    pub config: UncheckedAccount<'info>,
+
+    /// Программа backgammon.
+    pub backgammon_program: Program<'info, Backgammon>,
+}
+
+#[error_code]
+pub enum CallerError {
+    #[msg("Seat is not a player of this game")]
+    SeatNotInGame,
+}
@@ -0,0 +1,101 @@
+//! Какие инструкции можно вызывать через CPI.
+
+use crate::*;
+
+/// Можно ли вызвать инструкцию через CPI из другой программы.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpiPolicy {
+    /// Открыта для CPI: на ней строится композиция с другими программами.
+    Allowed,
+    /// Только инструкция верхнего уровня транзакции, иначе CpiNotAllowed.
+    TopLevelOnly,
+}
+
+/// Матрица CPI по дискриминаторам инструкций — единственное место, где
+/// решается, что можно вызывать через CPI.
+///
+/// Выплаты и заявки на банк закрыты: программа-«помощник», которую игрока
+/// уговорили вызвать, могла бы подставить в выплату свои аккаунты от его
+/// имени. Создание игры, вход и ход открыты явно. Админские инструкции тоже
+/// открыты явно: администратор может быть PDA мультисига или программы
+/// управления, который подписывает только через CPI. Инструкций, которых
+/// здесь нет, матрица не ограничивает.
+pub const CPI_MATRIX: &[(&[u8], CpiPolicy)] = &[
+    (instruction::InitGame::DISCRIMINATOR, CpiPolicy::Allowed),
+    (instruction::JoinGame::DISCRIMINATOR, CpiPolicy::Allowed),
+    (instruction::MakeMove::DISCRIMINATOR, CpiPolicy::Allowed),
+    (instruction::UpdateConfig::DISCRIMINATOR, CpiPolicy::Allowed),
+    (instruction::ProposeAdmin::DISCRIMINATOR, CpiPolicy::Allowed),
+    (instruction::AcceptAdmin::DISCRIMINATOR, CpiPolicy::Allowed),
+    (
+        instruction::RenounceAdmin::DISCRIMINATOR,
+        CpiPolicy::Allowed,
+    ),
+    (instruction::AddBan::DISCRIMINATOR, CpiPolicy::Allowed),
+    (instruction::RemoveBan::DISCRIMINATOR, CpiPolicy::Allowed),
+    (instruction::AddArbiter::DISCRIMINATOR, CpiPolicy::Allowed),
+    (
+        instruction::RemoveArbiter::DISCRIMINATOR,
+        CpiPolicy::Allowed,
+    ),
+    (instruction::CloseSeason::DISCRIMINATOR, CpiPolicy::Allowed),
+    (
+        instruction::WithdrawBotVault::DISCRIMINATOR,
+        CpiPolicy::Allowed,
+    ),
+    (instruction::Escheat::DISCRIMINATOR, CpiPolicy::Allowed),
+    (
+        instruction::InitInsurancePool::DISCRIMINATOR,
+        CpiPolicy::Allowed,
+    ),
+    (instruction::Compensate::DISCRIMINATOR, CpiPolicy::Allowed),
+    (instruction::InitFeeVault::DISCRIMINATOR, CpiPolicy::Allowed),
+    (
+        instruction::WithdrawProtocolFees::DISCRIMINATOR,
+        CpiPolicy::Allowed,
+    ),
+    (
+        instruction::FinishGame::DISCRIMINATOR,
+        CpiPolicy::TopLevelOnly,
+    ),
+    (
+        instruction::ForceRefund::DISCRIMINATOR,
+        CpiPolicy::TopLevelOnly,
+    ),
+    (
+        instruction::ManualRefund::DISCRIMINATOR,
+        CpiPolicy::TopLevelOnly,
+    ),
+    (
+        instruction::ClaimWin::DISCRIMINATOR,
+        CpiPolicy::TopLevelOnly,
+    ),
+    (
+        instruction::ClaimPotUnstake::DISCRIMINATOR,
+        CpiPolicy::TopLevelOnly,
+    ),
+    (
+        instruction::ClaimTeamShare::DISCRIMINATOR,
+        CpiPolicy::TopLevelOnly,
+    ),
+];
+
+/// Политика CPI инструкции по её дискриминатору (вне матрицы — Allowed).
+pub fn cpi_policy(discriminator: &[u8]) -> CpiPolicy {
+    CPI_MATRIX
+        .iter()
+        .find(|(d, _)| *d == discriminator)
+        .map_or(CpiPolicy::Allowed, |(_, policy)| *policy)
+}
+
+/// Проверяет текущий вызов по CPI_MATRIX: инструкция TopLevelOnly должна
+/// выполняться на верхнем уровне транзакции (высота стека вызовов 1).
+pub fn require_cpi_policy(discriminator: &[u8]) -> Result<()> {
+    if cpi_policy(discriminator) == CpiPolicy::TopLevelOnly {
+        require!(
+            get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+            ErrorCode::CpiNotAllowed
+        );
+    }
+    Ok(())
+}
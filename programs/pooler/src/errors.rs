@@ -0,0 +1,526 @@
+//! Коды ошибок программы.
+
+use crate::*;
+
+/// Коды ошибок для удобной диагностики.
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Game is not waiting for player 2")]
+    GameNotWaitingForPlayer2,
+
+    #[msg("Invalid player 2")]
+    InvalidPlayer2,
+
+    #[msg("Game is not active")]
+    GameNotActive,
+
+    #[msg("It's not this player's turn")]
+    NotPlayersTurn,
+
+    #[msg("Invalid current_turn value")]
+    InvalidCurrentTurn,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Invalid winner")]
+    InvalidWinner,
+
+    #[msg("Invalid player 1")]
+    InvalidPlayer1,
+
+    #[msg("Invalid player")]
+    InvalidPlayer,
+
+    #[msg("Not enough balance to pay move fee")]
+    NotEnoughBalanceForMove,
+
+    #[msg("Force refund timeout not reached yet")]
+    TimeoutNotReached,
+
+    #[msg("Inconsistent pot and recorded contributions")]
+    InconsistentPot,
+
+    #[msg("A double is pending and must be taken or dropped first")]
+    DoublePending,
+
+    #[msg("No double is pending")]
+    NoDoublePending,
+
+    #[msg("Player has no access to the cube")]
+    NoCubeAccess,
+
+    #[msg("Cube is already at its maximum value")]
+    CubeAtMaximum,
+
+    #[msg("Beavers are not allowed in this game")]
+    BeaversNotAllowed,
+
+    #[msg("No raccoon window is open")]
+    NoRaccoonWindow,
+
+    #[msg("Result multiplier must be 1 (single), 2 (gammon) or 3 (backgammon)")]
+    InvalidResultMultiplier,
+
+    #[msg("Automatic doubles are disabled for this game")]
+    AutoDoublesDisabled,
+
+    #[msg("Opening roll is already over")]
+    OpeningRollOver,
+
+    #[msg("Chouette is already settled")]
+    ChouetteSettled,
+
+    #[msg("Chouette has no free member slots")]
+    ChouetteFull,
+
+    #[msg("Player is already a chouette member")]
+    ChouetteMemberExists,
+
+    #[msg("Invalid chouette member")]
+    InvalidChouetteMember,
+
+    #[msg("Game is not finished")]
+    GameNotFinished,
+
+    #[msg("Invalid tournament size")]
+    InvalidTournamentSize,
+
+    #[msg("Tournament is not in registration")]
+    TournamentNotInRegistration,
+
+    #[msg("Tournament is full")]
+    TournamentFull,
+
+    #[msg("Tournament is not full yet")]
+    TournamentNotFull,
+
+    #[msg("Player already joined this tournament")]
+    AlreadyInTournament,
+
+    #[msg("Tournament is not running")]
+    TournamentNotRunning,
+
+    #[msg("Tournament is not finished")]
+    TournamentNotFinished,
+
+    #[msg("Game does not belong to this tournament round")]
+    GameNotInTournament,
+
+    #[msg("Result for this match is already reported")]
+    ResultAlreadyReported,
+
+    #[msg("Not all matches of the round are reported")]
+    RoundNotComplete,
+
+    #[msg("Invalid number of Swiss rounds")]
+    InvalidSwissRounds,
+
+    #[msg("Instruction is not available in this tournament mode")]
+    WrongTournamentMode,
+
+    #[msg("All Swiss rounds are already paired")]
+    SwissRoundsOver,
+
+    #[msg("Season is closed or is not the current season")]
+    SeasonClosed,
+
+    #[msg("Player stats account is required for this game")]
+    MissingPlayerStats,
+
+    #[msg("Player rating is below the game's minimum")]
+    RatingTooLow,
+
+    #[msg("Player is banned")]
+    PlayerBanned,
+
+    #[msg("Ban account is required while the ban list is enforced")]
+    MissingBanAccount,
+
+    #[msg("Wrong game password")]
+    WrongPassword,
+
+    #[msg("Password is too long")]
+    PasswordTooLong,
+
+    #[msg("Join deadline has passed")]
+    JoinDeadlinePassed,
+
+    #[msg("Join deadline has not passed yet")]
+    JoinDeadlineNotPassed,
+
+    #[msg("Join deadline must be in the future")]
+    InvalidJoinDeadline,
+
+    #[msg("Too many open games for this creator")]
+    TooManyOpenGames,
+
+    #[msg("Side bet amount must be positive")]
+    InvalidSideBetAmount,
+
+    #[msg("Side bet is not open")]
+    SideBetNotOpen,
+
+    #[msg("Side bet is not matched")]
+    SideBetNotMatched,
+
+    #[msg("Tip amount must be positive")]
+    InvalidTipAmount,
+
+    #[msg("House bot is not configured")]
+    BotDisabled,
+
+    #[msg("Bot cannot join a game it created")]
+    BotCannotJoinOwnGame,
+
+    #[msg("Game is not open for the house bot")]
+    GameNotOpenForBot,
+
+    #[msg("Stake exceeds the bot's maximum")]
+    BotStakeTooHigh,
+
+    #[msg("Bot vault has insufficient funds")]
+    BotVaultInsufficient,
+
+    #[msg("Bot vault account is required for bot games")]
+    MissingBotVault,
+
+    #[msg("Doubling cube is disabled in bot games")]
+    CubeDisabledInBotGame,
+
+    #[msg("Player signature is missing")]
+    MissingPlayerSignature,
+
+    #[msg("Session key is invalid or expired")]
+    InvalidSessionKey,
+
+    #[msg("Key rotation is not allowed for this game")]
+    KeyRotationNotAllowed,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Config is locked, admin has renounced")]
+    ConfigLocked,
+
+    #[msg("Escrow agent signature is required")]
+    MissingEscrowAgent,
+
+    #[msg("Invalid escrow agent")]
+    InvalidEscrowAgent,
+
+    #[msg("A move is awaiting acknowledgement")]
+    MovePending,
+
+    #[msg("No move is awaiting acknowledgement")]
+    NoMovePending,
+
+    #[msg("Game is not disputed")]
+    GameNotDisputed,
+
+    #[msg("Evidence pointer must not be empty")]
+    InvalidEvidence,
+
+    #[msg("Evidence window is still open")]
+    EvidenceWindowOpen,
+
+    #[msg("Auto-take threshold must be 0 or a power of two up to the maximum cube")]
+    InvalidAutoTakeThreshold,
+
+    #[msg("Double is above the taker's auto-take threshold")]
+    AutoTakeNotAllowed,
+
+    #[msg("Games do not form a valid mirror pair")]
+    InvalidMirrorPair,
+
+    #[msg("Mirror pair is already settled")]
+    MirrorPairSettled,
+
+    #[msg("Team account is required for team games")]
+    MissingTeamAccount,
+
+    #[msg("Invalid teammate")]
+    InvalidTeammate,
+
+    #[msg("Teammate share must be positive and not exceed the player's contribution")]
+    InvalidTeamShare,
+
+    #[msg("Doubling cube is disabled in team games")]
+    CubeDisabledInTeamGame,
+
+    #[msg("Team share is already claimed")]
+    TeamShareClaimed,
+
+    #[msg("Handicap is out of range")]
+    InvalidHandicap,
+
+    #[msg("Initial board does not match the declared rule variant and handicap")]
+    InitialBoardMismatch,
+
+    #[msg("Board has more checkers than the rule variant allows")]
+    InvalidBoard,
+
+    #[msg("Invalid time control parameters")]
+    InvalidTimeControl,
+
+    #[msg("Explicit time control value conflicts with the preset")]
+    PresetOverrideConflict,
+
+    #[msg("Time control account is required for this preset")]
+    MissingTimeControl,
+
+    #[msg("Settlement amounts must sum exactly to the pot")]
+    InvalidSettlement,
+
+    #[msg("Practice games must have zero stake, move fee and auto-take")]
+    InvalidPracticeGame,
+
+    #[msg("Not allowed in practice games")]
+    NotAllowedInPracticeGame,
+
+    #[msg("Game creation is rate limited, try again later")]
+    CreateRateLimited,
+
+    #[msg("A win claim is pending")]
+    WinClaimPending,
+
+    #[msg("No win claim is pending")]
+    NoWinClaim,
+
+    #[msg("Win claim contest window is still open")]
+    ClaimWindowOpen,
+
+    #[msg("Win claim contest window has closed")]
+    ClaimWindowClosed,
+
+    #[msg("Board does not show a finished game")]
+    BoardNotTerminal,
+
+    #[msg("Move cap reached, settle with settle_by_move_cap")]
+    MoveCapReached,
+
+    #[msg("Move cap not reached yet")]
+    MoveCapNotReached,
+
+    #[msg("player1 and player2 must be different accounts")]
+    DuplicatePlayerAccounts,
+
+    #[msg("Payout account must not be the game account")]
+    PayoutAliasesGame,
+
+    #[msg("Fee route account is required for this game")]
+    MissingFeeRoute,
+
+    #[msg("Fee recipient does not match the game's fee route")]
+    InvalidFeeRecipient,
+
+    #[msg("Signed message is for another program or game")]
+    InvalidSignedMessage,
+
+    #[msg("Relayed move payload is for another move index")]
+    RelayPayloadStale,
+
+    #[msg("Signed message has expired")]
+    SignedMessageExpired,
+
+    #[msg("Relayed moves are not supported for this game or side")]
+    RelayNotSupported,
+
+    #[msg("Relay budget account of the moving player is required")]
+    MissingRelayBudget,
+
+    #[msg("Expected a valid Ed25519 signature instruction before this one")]
+    InvalidEd25519Instruction,
+
+    #[msg("Game log replay needs a game tracked from the starting position")]
+    LogReplayUnavailable,
+
+    #[msg("Game log replay has already finished")]
+    LogReplayFinished,
+
+    #[msg("Game log chunk is too large")]
+    LogChunkTooLarge,
+
+    #[msg("Pot staking is disabled in config")]
+    StakePoolDisabled,
+
+    #[msg("Stake pool deposit returned no pool tokens")]
+    InvalidStakePool,
+
+    #[msg("Pot is not staked or unstake was already requested")]
+    PotNotStaked,
+
+    #[msg("Pot unstake can be claimed only in a later epoch")]
+    UnstakeEpochNotReached,
+
+    #[msg("Pot is empty")]
+    EmptyPot,
+
+    #[msg("Treasury is not configured")]
+    TreasuryNotSet,
+
+    #[msg("Game can still be settled by its players and cannot be escheated")]
+    GameNotEscheatable,
+
+    #[msg("Game has not been idle long enough to escheat")]
+    EscheatTooEarly,
+
+    #[msg("Escheat threshold is below the minimum")]
+    EscheatThresholdTooLow,
+
+    #[msg("Invalid payout splits or split recipient accounts")]
+    InvalidPayoutSplits,
+
+    #[msg("Insurance fee exceeds the maximum")]
+    InvalidInsuranceFee,
+
+    #[msg("Insurance pool account is required")]
+    MissingInsurancePool,
+
+    #[msg("Game is not stuck")]
+    GameNotStuck,
+
+    #[msg("Compensation exceeds the player's recorded contributions")]
+    CompensationCapExceeded,
+
+    #[msg("Insurance pool balance is insufficient")]
+    InsufficientInsurance,
+
+    #[msg("Invalid lobby shard")]
+    InvalidLobbyShard,
+
+    #[msg("Lobby shard is full")]
+    LobbyFull,
+
+    #[msg("Game is already listed in the lobby")]
+    GameAlreadyListed,
+
+    #[msg("Game is not listed in this lobby shard")]
+    GameNotListed,
+
+    #[msg("This instruction cannot be invoked via CPI")]
+    CpiNotAllowed,
+
+    #[msg("Expected move index does not match the game")]
+    MoveIndexMismatch,
+
+    #[msg("Account balance is insufficient for the payout")]
+    InsufficientAccountBalance,
+
+    #[msg("Payout would leave the account below its rent-exempt minimum")]
+    RentExemptViolation,
+
+    #[msg("A player cannot play against themselves")]
+    SelfPlayNotAllowed,
+
+    #[msg("Game id must be non-zero")]
+    InvalidGameId,
+
+    #[msg("Stake must be greater than zero outside practice games")]
+    ZeroStake,
+
+    #[msg("Move fee cannot exceed the stake")]
+    MoveFeeExceedsStake,
+
+    #[msg("Player2 must be set unless the game is an open lobby")]
+    MissingPlayer2,
+
+    #[msg("Initial board has more checkers than the rule variant allows")]
+    InvalidInitialBoard,
+    #[msg("Account is not a game state of a previous version")]
+    NotLegacyGameState,
+    #[msg("Signed message nonce does not match the game's action nonce")]
+    SignedMessageNonceMismatch,
+    #[msg("Protocol fee exceeds the maximum")]
+    InvalidProtocolFee,
+    #[msg("Fee vault account is required")]
+    MissingFeeVault,
+    #[msg("Withdrawal exceeds the protocol fees left in the vault")]
+    InsufficientProtocolFees,
+    #[msg("Treasury cannot be the fee vault")]
+    TreasuryIsFeeVault,
+    #[msg("Prune retention window is below the minimum")]
+    PruneThresholdTooLow,
+    #[msg("Prune reward exceeds the maximum")]
+    InvalidPruneReward,
+    #[msg("prune_games expects up to MAX_PRUNE_BATCH game and rent payer pairs")]
+    InvalidPruneBatch,
+    #[msg("No admin handover has been proposed")]
+    NoPendingAdmin,
+    #[msg("Signer is not the proposed admin")]
+    NotPendingAdmin,
+    #[msg("Arbiter is not on the whitelist")]
+    ArbiterNotWhitelisted,
+    #[msg("Signer is not the arbiter of this game")]
+    InvalidArbiter,
+    #[msg("Appeal window is below the minimum")]
+    AppealWindowTooShort,
+    #[msg("Arbiter resolution is awaiting appeal or execution")]
+    ResolutionPending,
+    #[msg("No arbiter resolution is pending")]
+    NoPendingResolution,
+    #[msg("Appeal window is still open")]
+    AppealWindowOpen,
+    #[msg("Appeal window has closed")]
+    AppealWindowClosed,
+    #[msg("Resolution has already been appealed")]
+    AlreadyAppealed,
+    #[msg("Burn share exceeds 100% of the protocol fee")]
+    InvalidBurnShare,
+    #[msg("Incinerator account is required")]
+    MissingIncinerator,
+    #[msg("A counter-offer is already pending")]
+    CounterOfferPending,
+    #[msg("No counter-offer is pending")]
+    NoCounterOffer,
+    #[msg("Counter-offers are not available for practice or tournament games")]
+    CounterOfferNotAllowed,
+    #[msg("Sides can only be swapped before the first move")]
+    SwapWindowClosed,
+    #[msg("Sides cannot be swapped in bot, team, tournament or chouette games")]
+    SwapNotAllowed,
+    #[msg("Game counter account is required when game_id is 0")]
+    MissingGameCounter,
+    #[msg("Explicit game_id must not have the high bit set")]
+    ReservedGameId,
+    #[msg("Game history account is required for this game")]
+    MissingGameHistory,
+    #[msg("Game history chunk is not the current chunk of this game")]
+    GameHistoryMismatch,
+    #[msg("Game history chunk is full; call rotate_history_chunk first")]
+    HistoryFull,
+    #[msg("Game history chunk is not full yet")]
+    HistoryNotFull,
+    #[msg("History commitment account is required for this game")]
+    MissingHistoryCommitment,
+    #[msg("History commitment does not belong to this game")]
+    HistoryCommitmentMismatch,
+    #[msg("Move record is not in the committed move history")]
+    HistoryProofInvalid,
+    #[msg("move_fee_lamports and move_fee_bps are mutually exclusive")]
+    ConflictingMoveFee,
+    #[msg("move_fee_bps cannot exceed 10000")]
+    InvalidMoveFeeBps,
+    #[msg("decay_bps exceeds MAX_DECAY_BPS")]
+    InvalidDecayBps,
+    #[msg("Decay sink account is required and must match the config destination")]
+    InvalidDecaySink,
+    #[msg("Pot of a decaying game cannot be staked")]
+    DecayingPotNotStakeable,
+    #[msg("Winner account is required to receive the forfeited claim bond")]
+    MissingWinnerAccount,
+    #[msg("Current season account is required while rating is enforced")]
+    MissingSeason,
+    #[msg("Open games counter is required for games counted in it")]
+    MissingOpenGames,
+    #[msg("Pot is staked; call claim_pot_unstake before paying it out")]
+    PotStaked,
+    #[msg("Game is already part of a mirror pair")]
+    GameAlreadyPaired,
+    #[msg("Mirror pair games must share a non-zero dice seed")]
+    MirrorDiceSeedMismatch,
+    #[msg("Stake pool accounts are required to join a game with a staked pot")]
+    MissingStakePoolAccounts,
+    #[msg("Treasury account is required to receive the refund split remainder")]
+    MissingTreasury,
+}
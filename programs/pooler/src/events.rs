@@ -0,0 +1,376 @@
+//! События программы (emit! и emit_cpi!).
+
+use crate::*;
+
+/// Игра создана (init_game).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameInitialized {
+    pub game: Pubkey,
+    pub game_id: u64,
+    pub player1: Pubkey,
+    /// Ожидаемый второй игрок (Pubkey::default() = открытое лобби).
+    pub player2: Pubkey,
+    pub stake_lamports: u64,
+    pub move_fee_lamports: u64,
+    pub practice: bool,
+}
+
+/// Игра создана с тающим банком (init_game, options.decay_bps): каждый ход
+/// срезает decay_bps банка на incinerator или config.treasury вместо
+/// комиссии с ходящего. Идёт сразу за GameInitialized.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PotDecayEnabled {
+    pub game: Pubkey,
+    pub decay_bps: u16,
+}
+
+/// Второй игрок (или бот) вошёл в игру (join_game, bot_join).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerJoined {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub stake_lamports: u64,
+    pub pot_lamports: u64,
+    pub bot: bool,
+}
+
+/// Игра завершена с выплатой банка.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameFinished {
+    pub game: Pubkey,
+    /// Pubkey::default() для договорного раздела.
+    pub winner: Pubkey,
+    /// Выплачено из банка (в finish_game — вместе с надбавкой за марс/кокс).
+    pub pot_paid: u64,
+    /// Комиссии за ходы, вошедшие в банк.
+    pub fee_lamports: u64,
+    pub reason: FinishReason,
+}
+
+/// Лобби отменено создателем (cancel_before_join).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameCancelled {
+    pub game: Pubkey,
+    pub player1: Pubkey,
+    pub refunded: u64,
+}
+
+/// Вклады возвращены игрокам.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameRefunded {
+    pub game: Pubkey,
+    pub player1_amount: u64,
+    pub player2_amount: u64,
+    pub reason: RefundReason,
+    /// То же, что GameState::finish_reason после возврата.
+    pub finish_reason: FinishReason,
+}
+
+/// Игра помечена как застрявшая (integrity_check).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameFlagged {
+    pub game: Pubkey,
+    pub reason: StuckReason,
+    pub pot_lamports: u64,
+    /// Сумма вкладов по refund_totals (u64::MAX при переполнении).
+    pub refund_total: u64,
+    pub lamports: u64,
+    pub flagged_by: Pubkey,
+}
+
+/// Выплата из страхового пула (compensate).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InsuranceCompensated {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub admin: Pubkey,
+    pub reason: StuckReason,
+    pub amount: u64,
+    pub compensated_total: u64,
+    pub cap: u64,
+    pub pool_remaining: u64,
+}
+
+/// Средства заброшенной игры переданы в казну (escheat).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameEscheated {
+    pub game: Pubkey,
+    pub game_id: u64,
+    pub player1: Pubkey,
+    pub player2: Pubkey,
+    pub treasury: Pubkey,
+    pub idle_slots: u64,
+    pub pot_lamports: u64,
+    pub rent_lamports: u64,
+    pub total_lamports: u64,
+}
+
+/// Комиссия протокола удержана с выигрыша (finish_game и другие выплаты
+/// победителю, settle_game — с обеих договорных долей).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtocolFeeCollected {
+    pub game: Pubkey,
+    /// Вся удержанная комиссия, включая сожжённую.
+    pub fee: u64,
+    /// Часть fee, отправленная на incinerator (config.burn_bps).
+    pub burned: u64,
+}
+
+/// Комиссия протокола выведена в казну (withdraw_protocol_fees).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtocolFeesWithdrawn {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    /// Счётчики FeeVault после вывода.
+    pub collected: u64,
+    pub withdrawn: u64,
+    pub burned: u64,
+}
+
+/// Предложена передача прав администратора (propose_admin).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    /// Pubkey::default() — предложение отозвано.
+    pub pending_admin: Pubkey,
+}
+
+/// Права администратора приняты новым ключом (accept_admin).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdminChanged {
+    pub previous: Pubkey,
+    pub admin: Pubkey,
+}
+
+/// Старые завершённые игры закрыты (prune_games).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GamesPruned {
+    pub caller: Pubkey,
+    pub pruned: u8,
+    pub skipped: u8,
+    /// Сумма наград вызвавшему и сумма, вернувшаяся плательщикам ренты.
+    pub caller_reward: u64,
+    pub payer_refund: u64,
+}
+
+/// Банк размещён в пуле (join_game с options.stake_pot).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PotStaked {
+    pub game: Pubkey,
+    pub lamports: u64,
+    pub pool_tokens: u64,
+}
+
+/// Банк возвращён из пула (claim_pot_unstake).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PotUnstaked {
+    pub game: Pubkey,
+    pub principal: u64,
+    pub returned: u64,
+}
+
+/// Переигрывание лога партии завершено (submit_game_log).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameLogReplayed {
+    pub game: Pubkey,
+    pub submitter: Pubkey,
+    pub verified: bool,
+    pub failed_at: Option<u64>,
+}
+
+/// Игра упёрлась в предел ходов и рассчитана по вкладам (settle_by_move_cap).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoveCapSettled {
+    pub game: Pubkey,
+    pub move_index: u64,
+    pub player1_amount: u64,
+    pub player2_amount: u64,
+}
+
+/// Игрок заявил о победе (claim_win), открыто окно оспаривания.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WinClaimed {
+    pub game: Pubkey,
+    pub claimant: Pubkey,
+    pub final_board: [i8; 24],
+    pub deadline_slot: u64,
+}
+
+/// Игра завершена договорным разделом банка (settle_game). Доли указаны до
+/// удержания комиссии протокола.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegotiatedSettlement {
+    pub game: Pubkey,
+    pub player1_amount: u64,
+    pub player2_amount: u64,
+}
+
+/// Игрок подал ссылку на доказательства в споре.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvidenceSubmitted {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub cid: [u8; EVIDENCE_CID_LEN],
+}
+
+/// Арбитр (или админ после апелляции) решил спор; выплата — execute_resolution
+/// после appeal_deadline_slot.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolutionProposed {
+    pub game: Pubkey,
+    pub resolver: Pubkey,
+    pub winner: Pubkey,
+    pub appeal_deadline_slot: u64,
+}
+
+/// Игрок обжаловал решение арбитра; спор переходит к админу.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolutionAppealed {
+    pub game: Pubkey,
+    pub appellant: Pubkey,
+    pub winner: Pubkey,
+}
+
+/// Приглашённый player2 предложил другие ставку и комиссию за ход.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CounterOffered {
+    pub game: Pubkey,
+    pub player2: Pubkey,
+    pub stake_lamports: u64,
+    pub move_fee_lamports: u64,
+}
+
+/// player1 принял встречное предложение; депозит приведён к новой ставке.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CounterAccepted {
+    pub game: Pubkey,
+    pub stake_lamports: u64,
+    pub move_fee_lamports: u64,
+    pub player1_deposit: u64,
+}
+
+/// Встречное предложение снято автором или отклонено player1.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CounterWithdrawn {
+    pub game: Pubkey,
+    pub proposer: Pubkey,
+    pub by: Pubkey,
+}
+
+/// Игроки обменялись сторонами до первого хода (ключи — после обмена).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SidesSwapped {
+    pub game: Pubkey,
+    pub player1: Pubkey,
+    pub player2: Pubkey,
+}
+
+/// Запись хода доказана по Merkle-корню истории (verify_history_entry);
+/// на неё можно сослаться в споре.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEntryVerified {
+    pub game: Pubkey,
+    pub history_root: [u8; 32],
+    pub entry: MoveRecord,
+}
+
+/// Совершён ход (move_index — номер после хода).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoveMade {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub move_index: u64,
+    pub board_points: [i8; 24],
+    pub board_hash: [u8; 32],
+    pub dice: [u8; 2],
+    /// Комиссия за ход (вместе со страховым отчислением).
+    pub fee_lamports: u64,
+    pub annotation_hash: [u8; 32],
+    pub annotation_chain: [u8; 32],
+    /// Телеметрия клиента, собравшего ход (если передана).
+    pub client: Option<ClientInfo>,
+}
+
+/// Чаевые зрителя в банк игры (для оверлеев стримов).
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PotTipped {
+    pub game: Pubkey,
+    pub tipper: Pubkey,
+    pub amount: u64,
+    pub tips_lamports: u64,
+}
+
+/// Ничья на стартовом броске осталась простым переброском
+/// (record_opening_tie): автоудвоение не сделано, куб и банк не менялись.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AutoDoubleSkipped {
+    pub game: Pubkey,
+    pub reason: AutoDoubleSkipReason,
+    pub cube_value: u8,
+    /// Доплата с каждого игрока, которую пришлось бы внести (0 при Limit).
+    pub extra: u64,
+}
+
+/// Почему автоудвоение пропущено.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoDoubleSkipReason {
+    /// Исчерпан auto_double_limit или куб уже на MAX_CUBE_VALUE.
+    Limit,
+    /// Кому-то из игроков не хватает lamports на доплату.
+    InsufficientFunds,
+}
+
+/// Значение в ErrorContext. Ключей здесь нет: при несовпадении ключа
+/// (require_keys_eq!, ограничение address) Anchor сам пишет в лог оба
+/// значения строками Left/Right.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContextValue {
+    Lamports(u64),
+    /// Для тайм-аутов expected — первый слот, с которого действие доступно.
+    Slot(u64),
+}
+
+/// Ожидаемое и фактическое значение при отказе с диагностической ошибкой
+/// (require_ctx!). Пишется в лог через emit! до возврата ошибки, поэтому
+/// остаётся в логах упавшей транзакции: что было в аккаунте, видно без
+/// повторного чтения состояния, которое могло с тех пор измениться.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// Код ошибки, как в InstructionError::Custom.
+    pub error_code: u32,
+    pub expected: ContextValue,
+    pub actual: ContextValue,
+}
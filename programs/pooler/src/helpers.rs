@@ -0,0 +1,733 @@
+//! Общие шаги инструкций над аккаунтами: переводы lamports, комиссии за
+//! ход, выплаты, проверки банка, Ed25519-подписи и рейтинг.
+
+use crate::*;
+
+/// Размер Ed25519SignatureOffsets в данных инструкции Ed25519-программы.
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Проверяет, что инструкция перед текущей — вызов Ed25519-программы, в
+/// которой каждый из signers подписал message, и возвращает их подписи в
+/// том же порядке.
+///
+/// Смещения подписи, ключа и сообщения обязаны указывать внутрь самой
+/// Ed25519-инструкции (instruction_index = u16::MAX), иначе проверенные
+/// рантаймом байты могли бы отличаться от прочитанных здесь.
+pub fn verify_ed25519_signatures(
+    instructions: &AccountInfo,
+    message: &[u8],
+    signers: &[Pubkey],
+) -> Result<Vec<[u8; 64]>> {
+    let ix = get_instruction_relative(-1, instructions)
+        .map_err(|_| error!(ErrorCode::InvalidEd25519Instruction))?;
+    require!(
+        ix.program_id == ed25519_program::ID && ix.accounts.is_empty(),
+        ErrorCode::InvalidEd25519Instruction
+    );
+
+    let data = &ix.data;
+    let slice = |at: usize, len: usize| -> Result<&[u8]> {
+        data.get(at..at.saturating_add(len))
+            .ok_or(error!(ErrorCode::InvalidEd25519Instruction))
+    };
+    let read_u16 = |at: usize| -> Result<usize> {
+        let bytes = slice(at, 2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+    };
+
+    let count = slice(0, 1)?[0] as usize;
+    let mut found: Vec<Option<[u8; 64]>> = vec![None; signers.len()];
+    for i in 0..count {
+        let base = 2 + i * ED25519_OFFSETS_SIZE;
+        let signature_offset = read_u16(base)?;
+        let public_key_offset = read_u16(base + 4)?;
+        let message_offset = read_u16(base + 8)?;
+        let message_size = read_u16(base + 10)?;
+        require!(
+            read_u16(base + 2)? == u16::MAX as usize
+                && read_u16(base + 6)? == u16::MAX as usize
+                && read_u16(base + 12)? == u16::MAX as usize,
+            ErrorCode::InvalidEd25519Instruction
+        );
+        if slice(message_offset, message_size)? != message {
+            continue;
+        }
+        let key = Pubkey::new_from_array(
+            slice(public_key_offset, 32)?
+                .try_into()
+                .map_err(|_| error!(ErrorCode::InvalidEd25519Instruction))?,
+        );
+        if let Some(pos) = signers.iter().position(|signer| *signer == key) {
+            found[pos] = Some(
+                slice(signature_offset, 64)?
+                    .try_into()
+                    .map_err(|_| error!(ErrorCode::InvalidEd25519Instruction))?,
+            );
+        }
+    }
+
+    found
+        .into_iter()
+        .map(|signature| signature.ok_or(error!(ErrorCode::MissingPlayerSignature)))
+        .collect()
+}
+
+/// Проверка бана по PDA [b"ban", player] (seeds проверяются в контексте).
+///
+/// Если в конфиге бан-лист не включён, всегда false. Если включён, аккаунт
+/// обязателен: забанен тот, чей PDA инициализирован нашей программой.
+pub fn is_banned(config: &Config, ban: Option<&AccountInfo>) -> Result<bool> {
+    if !config.bans_enforced {
+        return Ok(false);
+    }
+    let ban = ban.ok_or(ErrorCode::MissingBanAccount)?;
+    Ok(ban.owner == &crate::ID && !ban.data_is_empty())
+}
+
+/// Проверка арбитра игры по белому списку (PDA [b"arbiter", arbiter], seeds
+/// проверяются в контексте).
+///
+/// Если флаг config.require_whitelisted_arbiters выключен, проверки нет.
+/// Арбитр конфига (в игре — Pubkey::default()) допущен всегда. Иначе
+/// аккаунт обязателен: допущен тот, чей PDA инициализирован нашей программой.
+pub fn check_arbiter_whitelisted(
+    config: &Config,
+    arbiter: &Pubkey,
+    whitelisted: Option<&AccountInfo>,
+) -> Result<()> {
+    if !config.require_whitelisted_arbiters
+        || *arbiter == Pubkey::default()
+        || *arbiter == config.arbiter
+    {
+        return Ok(());
+    }
+    require!(
+        whitelisted.is_some_and(|info| info.owner == &crate::ID && !info.data_is_empty()),
+        ErrorCode::ArbiterNotWhitelisted
+    );
+    Ok(())
+}
+
+/// Обновляет рейтинг по итогам игры, если переданы текущий сезон и
+/// статистика обоих игроков; при config.rating_enforced они обязательны.
+/// Игра засчитывается в сезон, в котором она завершилась. Тренировочные игры в рейтинг и статистику не идут; вес
+/// результата задаёт game.finish_reason.
+pub fn record_game_rating<'info>(
+    game: &GameState,
+    winner: Pubkey,
+    config: &Config,
+    season: Option<&mut Account<'info, Season>>,
+    player1_stats: Option<&mut Account<'info, PlayerStats>>,
+    player2_stats: Option<&mut Account<'info, PlayerStats>>,
+) -> Result<()> {
+    if game.practice {
+        verbose_msg!("record_game_rating: practice game, ratings unchanged");
+        return Ok(());
+    }
+    let weight_pct = game.finish_reason.rating_weight_pct();
+    if weight_pct == 0 {
+        verbose_msg!(
+            "record_game_rating: {:?} is not rated, ratings unchanged",
+            game.finish_reason
+        );
+        return Ok(());
+    }
+    // Без аккаунтов рейтинг пропускается, только пока конфиг его не требует:
+    // иначе проигравший просто не передал бы свою статистику.
+    if config.rating_enforced {
+        require!(season.is_some(), ErrorCode::MissingSeason);
+        require!(
+            player1_stats.is_some() && player2_stats.is_some(),
+            ErrorCode::MissingPlayerStats
+        );
+    }
+    let (Some(season), Some(p1_stats), Some(p2_stats)) = (season, player1_stats, player2_stats)
+    else {
+        return Ok(());
+    };
+    require!(
+        season.index == config.current_season && !season.closed,
+        ErrorCode::SeasonClosed
+    );
+    let (winner_stats, loser_stats, winner_handicap) = if winner == game.player1 {
+        (p1_stats, p2_stats, game.handicap)
+    } else {
+        (p2_stats, p1_stats, -game.handicap)
+    };
+    apply_rating_result(
+        winner_stats,
+        loser_stats,
+        config.current_season,
+        winner_handicap,
+        weight_pct,
+    )?;
+    season.record_standing(winner_stats);
+    season.record_standing(loser_stats);
+
+    verbose_msg!(
+        "record_game_rating: season={}, winner_rating={}, loser_rating={}",
+        season.index,
+        winner_stats.rating,
+        loser_stats.rating
+    );
+    Ok(())
+}
+
+/// Параметры контроля времени и комиссия текущего хода; запас времени
+/// ходящего (mover_side) пополняется в TimeControl.
+///
+/// Первые free_moves ходов (GameState::free_moves) бесплатны; эскалация
+/// комиссии отсчитывается от конца бесплатного окна, то есть первый платный
+/// ход стоит базовую комиссию. Если игра не задала своё окно, оно читается
+/// из конфига на каждом ходе, поэтому изменение config.free_moves действует
+/// и на идущие игры.
+///
+/// В игре с move_fee_bps базовая комиссия считается от банка перед ходом,
+/// поэтому растёт вместе с ним; fees_paid и банк получают ровно списанную
+/// сумму, и возвраты сходятся при любом росте комиссии.
+pub fn charge_time_control(
+    game: &GameState,
+    time_control: Option<&mut Account<TimeControl>>,
+    mover_side: u8,
+    free_moves: u16,
+) -> Result<(TimeControlParams, u64)> {
+    let params = game.time_control(time_control.as_deref().map(|tc| &**tc))?;
+    let elapsed = Clock::get()?
+        .slot
+        .checked_sub(game.last_activity_slot)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if let Some(tc) = time_control {
+        tc.record_move(mover_side, elapsed);
+    }
+    let free_moves = free_moves as u64;
+    let move_fee = if game.move_index < free_moves {
+        0
+    } else {
+        params.move_fee(game.base_move_fee(), game.move_index - free_moves)?
+    };
+    Ok((params, move_fee))
+}
+
+/// Куда идёт комиссия за ход: в банк (аккаунт игры) или, если организатор
+/// задал FeeRoute, мимо банка на fee_recipient.
+pub fn fee_destination<'info>(
+    game: &Account<'info, GameState>,
+    fee_route: Option<&FeeRoute>,
+    fee_recipient: Option<&UncheckedAccount<'info>>,
+) -> Result<AccountInfo<'info>> {
+    if !game.fee_routed {
+        return Ok(game.to_account_info());
+    }
+    let route = fee_route.ok_or(ErrorCode::MissingFeeRoute)?;
+    let recipient = fee_recipient.ok_or(ErrorCode::MissingFeeRoute)?;
+    require_keys_eq!(
+        recipient.key(),
+        route.fee_recipient,
+        ErrorCode::InvalidFeeRecipient
+    );
+    Ok(recipient.to_account_info())
+}
+
+/// Срез тающего банка (game.decay_bps) за ход: pot_decay от банка перед
+/// ходом уходит с аккаунта игры на decay_sink (config.decay_destination()) и
+/// учитывается в decayed_lamports, так что deposits + fees - decayed == pot.
+/// Первые free_moves ходов банк не тает, как и комиссия не берётся.
+/// Возвращает срезанную сумму; decay_sink нужен, только если она ненулевая.
+pub fn decay_pot<'info>(
+    game: &mut Account<'info, GameState>,
+    config: &Config,
+    decay_sink: Option<&UncheckedAccount<'info>>,
+) -> Result<u64> {
+    if game.move_index < game.free_moves(config) as u64 {
+        return Ok(0);
+    }
+    let decay = pot_decay(game.pot_lamports, game.decay_bps);
+    if decay == 0 {
+        return Ok(0);
+    }
+    let sink = decay_sink.ok_or(ErrorCode::InvalidDecaySink)?;
+    require_keys_eq!(
+        sink.key(),
+        config.decay_destination(),
+        ErrorCode::InvalidDecaySink
+    );
+    debit(&game.to_account_info(), decay)?;
+    credit(&sink.to_account_info(), decay)?;
+    game.pot_lamports = game
+        .pot_lamports
+        .checked_sub(decay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    game.decayed_lamports = game
+        .decayed_lamports
+        .checked_add(decay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(decay)
+}
+
+/// Отчисление config.insurance_fee_bps от комиссии, поступившей на аккаунт
+/// игры, в InsurancePool. Отведённые организатору (FeeRoute) комиссии не
+/// облагаются. Возвращает отчисленную сумму; в банк и вклад ходящего идёт
+/// только остаток.
+pub fn take_insurance_cut<'info>(
+    game: &Account<'info, GameState>,
+    pool: Option<&mut Account<'info, InsurancePool>>,
+    insurance_fee_bps: u16,
+    move_fee: u64,
+) -> Result<u64> {
+    if insurance_fee_bps == 0 || move_fee == 0 || game.practice || game.fee_routed {
+        return Ok(0);
+    }
+    let pool = pool.ok_or(ErrorCode::MissingInsurancePool)?;
+    let cut = insurance_cut(move_fee, insurance_fee_bps);
+    if cut == 0 {
+        return Ok(0);
+    }
+    debit(&game.to_account_info(), cut)?;
+    credit(&pool.to_account_info(), cut)?;
+    pool.total_collected = pool
+        .total_collected
+        .checked_add(cut)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(cut)
+}
+
+/// Переводит комиссию протокола fee с аккаунта игры: доля burn_bps уходит
+/// на incinerator, остаток — в FeeVault. Вся комиссия учитывается в
+/// collected, сожжённая — ещё и в burned. Хранилище нужно, только если
+/// комиссия ненулевая, incinerator — только если сжигается что-то.
+/// Возвращает сожжённую сумму.
+pub fn collect_protocol_fee<'info>(
+    game: &Account<'info, GameState>,
+    vault: Option<&mut Account<'info, FeeVault>>,
+    incinerator: Option<&AccountInfo<'info>>,
+    fee: u64,
+    burn_bps: u16,
+) -> Result<u64> {
+    if fee == 0 {
+        return Ok(0);
+    }
+    let vault = vault.ok_or(ErrorCode::MissingFeeVault)?;
+    let burned = burn_share(fee, burn_bps);
+    let incinerator = match burned {
+        0 => None,
+        _ => Some(incinerator.ok_or(ErrorCode::MissingIncinerator)?),
+    };
+    debit(&game.to_account_info(), fee)?;
+    if let Some(incinerator) = incinerator {
+        credit(incinerator, burned)?;
+    }
+    credit(&vault.to_account_info(), fee - burned)?;
+    vault.collected = vault
+        .collected
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    vault.burned = vault
+        .burned
+        .checked_add(burned)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(burned)
+}
+
+/// Учитывает уже переведённую комиссию за ход: в банке и вкладе ходящего
+/// или, при FeeRoute, в fees_routed_out.
+pub fn book_move_fee(
+    game: &mut GameState,
+    fee_route: Option<&mut Account<FeeRoute>>,
+    team: Option<&mut Account<TeamState>>,
+    fee_member: usize,
+    move_fee: u64,
+) -> Result<()> {
+    // Отведённая комиссия не входит ни в банк, ни во вклады игроков,
+    // поэтому проверки согласованности банка её не касаются.
+    if game.fee_routed {
+        let route = fee_route.ok_or(ErrorCode::MissingFeeRoute)?;
+        route.fees_routed_out = route
+            .fees_routed_out
+            .checked_add(move_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        game.pot_lamports = game
+            .pot_lamports
+            .checked_add(move_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Обновляем, кто сколько заплатил комиссий за ходы.
+        match game.current_turn {
+            1 => {
+                game.player1_fees_paid = game
+                    .player1_fees_paid
+                    .checked_add(move_fee)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+            2 => {
+                game.player2_fees_paid = game
+                    .player2_fees_paid
+                    .checked_add(move_fee)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+            _ => {}
+        }
+        if let Some(team) = team {
+            team.add_contribution(fee_member, move_fee)?;
+        }
+    }
+    Ok(())
+}
+
+/// Событие GameFinished по уже завершённой игре (winner записан);
+/// эмитится через emit_cpi!.
+pub fn game_finished_event(game: &Account<GameState>, pot_paid: u64) -> GameFinished {
+    GameFinished {
+        game: game.key(),
+        winner: game.winner,
+        pot_paid,
+        fee_lamports: game
+            .player1_fees_paid
+            .saturating_add(game.player2_fees_paid),
+        reason: game.finish_reason,
+    }
+}
+
+/// Записывает оплаченный ход: цепочка аннотаций, доска (сразу или через
+/// pending в двухфазном режиме), событие MoveMade и итог для return data.
+pub fn commit_move(
+    game: &mut Account<GameState>,
+    new_board_points: [i8; 24],
+    new_dice: [u8; 2],
+    annotation_hash: [u8; 32],
+    move_timeout_slots: u64,
+    move_fee: u64,
+    client: Option<ClientInfo>,
+) -> Result<MoveOutcome> {
+    // Любой ход закрывает окно для енота (raccoon).
+    game.raccoon_pending = false;
+
+    // Цепочка хэшей аннотаций: chain = sha256(chain || move_index || annotation_hash).
+    if annotation_hash != [0u8; 32] {
+        game.annotation_chain = hashv(&[
+            &game.annotation_chain,
+            &game.move_index.to_le_bytes(),
+            &annotation_hash,
+        ])
+        .to_bytes();
+    }
+    let mover = game.player_key(game.current_turn);
+    let current_slot = Clock::get()?.slot;
+
+    if game.two_phase_moves {
+        // Двухфазный режим: доска становится официальной только после
+        // ack_move соперника (или finalize_pending после срока).
+        game.set_pending_board_points(&new_board_points)?;
+        game.pending_dice = new_dice;
+        game.move_pending = true;
+        game.pending_deadline_slot = current_slot
+            .checked_add(move_timeout_slots)
+            .ok_or(ErrorCode::MathOverflow)?;
+        game.last_activity_slot = current_slot;
+
+        verbose_msg!(
+            "make_move: pending acknowledgement, deadline_slot={}, pot_lamports={}",
+            game.pending_deadline_slot,
+            game.pot_lamports
+        );
+    } else {
+        // Обновляем доску, счётчик ходов и очередь (валидация оффчейн)
+        game.apply_move(new_board_points, new_dice, current_slot)?;
+
+        verbose_msg!(
+            "make_move: completed, new_move_index={}, new_current_turn={}, pot_lamports={}",
+            game.move_index,
+            game.current_turn,
+            game.pot_lamports
+        );
+    }
+
+    emit!(MoveMade {
+        game: game.key(),
+        player: mover,
+        move_index: if game.move_pending {
+            game.move_index
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?
+        } else {
+            game.move_index
+        },
+        board_points: new_board_points,
+        board_hash: board_hash(&new_board_points),
+        dice: new_dice,
+        fee_lamports: move_fee,
+        annotation_hash,
+        annotation_chain: game.annotation_chain,
+        client,
+    });
+
+    // Итог хода уходит в return data: ход делается целиком, поэтому
+    // неиспользованных кубиков нет; в двухфазном режиме очередь остаётся
+    // у ходившего до ack_move.
+    Ok(MoveOutcome {
+        move_index: game.move_index,
+        current_turn: game.current_turn,
+        turn_open: game.move_pending,
+        remaining_dice: [0; 2],
+        board_hash: board_hash(&new_board_points),
+    })
+}
+
+/// Аккаунт второго игрока для его выплат и возвратов: в игре с ботом это bot_vault.
+pub fn player2_funds<'info>(
+    game: &GameState,
+    player2: AccountInfo<'info>,
+    bot_vault: Option<&UncheckedAccount<'info>>,
+) -> Result<AccountInfo<'info>> {
+    if game.bot_game {
+        Ok(bot_vault
+            .ok_or(ErrorCode::MissingBotVault)?
+            .to_account_info())
+    } else {
+        Ok(player2)
+    }
+}
+
+/// Перевод из bot_vault (system-owned PDA [b"bot_vault"]) с подписью программы.
+pub fn transfer_from_bot_vault<'info>(
+    system_program_info: AccountInfo<'info>,
+    bot_vault: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let bump_seed = [bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"bot_vault", &bump_seed]];
+    let cpi_accounts = system_program::Transfer {
+        from: bot_vault,
+        to,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(system_program_info, cpi_accounts, signer_seeds);
+    system_program::transfer(cpi_ctx, amount)
+}
+
+/// Получатель выплаты стороне side (1 или 2).
+///
+/// В командной игре выплата стороне с напарником зачисляется на TeamState и
+/// делится через claim_team_share; в остальных случаях это default_info.
+pub fn team_payee<'info>(
+    game: &GameState,
+    side: u8,
+    default_info: AccountInfo<'info>,
+    team: Option<&mut Account<'info, TeamState>>,
+    amount: u64,
+) -> Result<AccountInfo<'info>> {
+    if !game.team_game {
+        return Ok(default_info);
+    }
+    let team = team.ok_or(ErrorCode::MissingTeamAccount)?;
+    if !team.has_teammate(side) {
+        return Ok(default_info);
+    }
+    let idx = (side - 1) as usize;
+    team.received[idx] = team.received[idx]
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(team.to_account_info())
+}
+
+/// Списывает lamports с аккаунта программы (прямое изменение баланса).
+///
+/// Не даёт балансу уйти в минус (InsufficientAccountBalance) и ниже
+/// минимума освобождения от ренты при текущем размере данных
+/// (RentExemptViolation; остаток и минимум — в compared values ошибки).
+/// Проверки идут до изменения баланса, при ошибке он не меняется.
+pub fn debit(account: &AccountInfo, lamports: u64) -> Result<()> {
+    let rent_min = Rent::get()?.minimum_balance(account.data_len());
+    let mut balance = account.try_borrow_mut_lamports()?;
+    let remaining = balance
+        .checked_sub(lamports)
+        .ok_or(ErrorCode::InsufficientAccountBalance)?;
+    require_gte!(remaining, rent_min, ErrorCode::RentExemptViolation);
+    **balance = remaining;
+    Ok(())
+}
+
+/// Зачисляет lamports на аккаунт; переполнение — MathOverflow.
+pub fn credit(account: &AccountInfo, lamports: u64) -> Result<()> {
+    let mut balance = account.try_borrow_mut_lamports()?;
+    **balance = balance
+        .checked_add(lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+/// Инвариант банка в конце инструкции: на аккаунте игры не меньше lamports,
+/// чем рента + pot_lamports + резервы автовзятий, за вычетом банка,
+/// размещённого в пуле (game.staked_lamports). Излишек допустим — перевести lamports на
+/// аккаунт может кто угодно; недостача — InconsistentPot.
+///
+/// Баланс читается через AccountInfo и после CPI уже актуален; reload()
+/// здесь не нужен (и отбросил бы ещё не записанные изменения GameState).
+pub fn assert_pot_invariant(game: &Account<GameState>) -> Result<()> {
+    let info = game.to_account_info();
+    let rent_min = Rent::get()?.minimum_balance(info.data_len());
+    let required = game
+        .pot_lamports
+        .checked_sub(game.staked_lamports)
+        .and_then(|pot| pot.checked_add(rent_min))
+        .and_then(|total| total.checked_add(game.p1_take_reserve))
+        .and_then(|total| total.checked_add(game.p2_take_reserve))
+        .ok_or(ErrorCode::InconsistentPot)?;
+    require_ctx!(
+        info.lamports() >= required,
+        ErrorCode::InconsistentPot,
+        Lamports {
+            expected: required,
+            actual: info.lamports(),
+        }
+    );
+    Ok(())
+}
+
+/// Проверяет, что аккаунт — GameState прошлой версии: дискриминатор
+/// GameState и 8 + GameState::BASELINE_MAX_SIZE (версия 0),
+/// 8 + GameState::LEGACY_MAX_SIZE (версия 1), 8 + GameState::V2_MAX_SIZE
+/// (версия 2) или 8 + GameState::MAX_SIZE с version меньше текущей (версия 3)
+/// байт данных. Возвращает версию.
+pub fn check_legacy_game_state(info: &AccountInfo) -> Result<u8> {
+    let data = info.try_borrow_data()?;
+    require!(
+        data.starts_with(GameState::DISCRIMINATOR),
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    match data.len() - 8 {
+        GameState::BASELINE_MAX_SIZE => Ok(0),
+        GameState::LEGACY_MAX_SIZE => Ok(1),
+        GameState::V2_MAX_SIZE => Ok(2),
+        // Версия 3 того же размера, что текущая: различаются байтом version.
+        GameState::MAX_SIZE => {
+            let version = GameState::try_deserialize(&mut &data[..])?.version;
+            require!(version < GAME_STATE_VERSION, ErrorCode::NotLegacyGameState);
+            Ok(version)
+        }
+        _ => Err(ErrorCode::NotLegacyGameState.into()),
+    }
+}
+
+/// Снимает игру, покидающую WaitingForPlayer2, со счётчика открытых лобби
+/// создателя. Игры, созданные до OpenGames, в счётчике не учтены: им аккаунт
+/// не нужен, и чужой счётчик не уменьшается. Флаг сбрасывается, чтобы игра
+/// не снималась дважды.
+pub fn release_open_game(
+    game: &mut GameState,
+    open_games: Option<&mut Account<OpenGames>>,
+) -> Result<()> {
+    if !game.open_games_counted {
+        return Ok(());
+    }
+    open_games.ok_or(ErrorCode::MissingOpenGames)?.release();
+    game.open_games_counted = false;
+    Ok(())
+}
+
+/// Доплата ренты за расширение аккаунта с old_len до new_len байт данных.
+///
+/// Считается разницей минимумов, а не от баланса: на аккаунте игры лежит
+/// ещё и банк, и если засчитать его в ренту, то после переноса полная
+/// выплата банка упрётся в RentExemptViolation.
+pub fn migration_rent(rent: &Rent, old_len: usize, new_len: usize) -> u64 {
+    rent.minimum_balance(new_len)
+        .saturating_sub(rent.minimum_balance(old_len))
+}
+
+/// Перенос данных GameState версии old_version, уже расширенных до
+/// 8 + MAX_SIZE (новые байты нулевые): поля, включая reserved, остаются
+/// как были, меняются только версия, доски (переупаковываются в
+/// StoredBoard текущей версии), для версий 0 и 1 — finish_reason и, для
+/// версии 0, cube_value.
+///
+/// Раскладка версии 0 — префикс текущей (player1..bump), поэтому поля,
+/// появившиеся позже, читаются из новых байтов как нули.
+pub fn migrate_game_state_data(data: &mut [u8], old_version: u8) -> Result<()> {
+    let mut game = GameState::try_deserialize(&mut &data[..])?;
+    let board = load_board(&game.board, old_version)?;
+    let pending_board = load_board(&game.pending_board, old_version)?;
+    game.version = GAME_STATE_VERSION;
+    game.set_board_points(&board)?;
+    game.set_pending_board_points(&pending_board)?;
+    // У завершённых игр версий 0 и 1 причина неизвестна; версия 2 её уже вела.
+    if old_version <= 1 {
+        game.finish_reason = FinishReason::NotFinished;
+    }
+    // Куба в версии 0 не было: игра идёт при кубе 1 в центре.
+    if old_version == 0 {
+        game.cube_value = 1;
+    }
+    game.try_serialize(&mut &mut data[..])
+}
+
+/// Выплата amount с аккаунта игры.
+///
+/// splits — PDA [b"payout_splits", игрок]; контекст проверяет его адрес через
+/// seeds и не даёт его пропустить, поэтому обойти зарегистрированные сплиты
+/// нельзя. Неинициализированный аккаунт означает, что сплитов нет. Если payee —
+/// кошелёк этого игрока и сплиты есть, сумма делится между получателями из
+/// remaining (по порядку записей, ключи сверяются), остаток от округления —
+/// первому. Иначе (нет сплитов, выплата на TeamState или bot_vault) всё
+/// уходит на payee.
+pub fn pay_out<'info>(
+    game: &AccountInfo<'info>,
+    payee: AccountInfo<'info>,
+    splits: &AccountInfo<'info>,
+    remaining: &mut std::slice::Iter<'_, AccountInfo<'info>>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let splits = match registered_payout_splits(splits)? {
+        Some(splits) if splits.player == payee.key() => splits,
+        _ => {
+            debit(game, amount)?;
+            credit(&payee, amount)?;
+            return Ok(());
+        }
+    };
+
+    let shares = payout_shares(amount, &splits.splits);
+
+    debit(game, amount)?;
+    for (split, share) in splits.splits.iter().zip(shares) {
+        let recipient = remaining.next().ok_or(ErrorCode::InvalidPayoutSplits)?;
+        require_keys_eq!(
+            recipient.key(),
+            split.recipient,
+            ErrorCode::InvalidPayoutSplits
+        );
+        require_keys_neq!(recipient.key(), game.key(), ErrorCode::PayoutAliasesGame);
+        verbose_msg!("pay_out: recipient={}, amount={}", split.recipient, share);
+        credit(recipient, share)?;
+    }
+    Ok(())
+}
+
+/// Остаток от деления при возврате банка (refund_totals с
+/// Config::split_remainder) уходит с аккаунта игры на config.treasury;
+/// адрес treasury сверяет контекст, нужен он только при ненулевом остатке.
+pub fn pay_split_remainder<'info>(
+    game: &AccountInfo<'info>,
+    treasury: Option<&UncheckedAccount<'info>>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let treasury = treasury.ok_or(ErrorCode::MissingTreasury)?;
+    debit(game, amount)?;
+    credit(&treasury.to_account_info(), amount)
+}
+
+/// PayoutSplits из аккаунта по адресу PDA сплитов; None, если игрок их не
+/// регистрировал (или закрыл) и аккаунт не принадлежит программе.
+fn registered_payout_splits(info: &AccountInfo) -> Result<Option<PayoutSplits>> {
+    if info.owner != &crate::ID {
+        return Ok(None);
+    }
+    PayoutSplits::try_deserialize(&mut &info.try_borrow_data()?[..]).map(Some)
+}
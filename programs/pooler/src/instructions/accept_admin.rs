@@ -0,0 +1,38 @@
+use crate::*;
+
+/// Контекст для принятия прав администратора.
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// Глобальный конфиг.
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.locked @ ErrorCode::ConfigLocked,
+        constraint = config.pending_admin != Pubkey::default() @ ErrorCode::NoPendingAdmin,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Предложенный администратор (config.pending_admin).
+    #[account(address = config.pending_admin @ ErrorCode::NotPendingAdmin)]
+    pub new_admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous = config.admin;
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+
+    verbose_msg!(
+        "accept_admin: previous={}, admin={}",
+        previous,
+        config.admin
+    );
+    emit!(AdminChanged {
+        previous,
+        admin: config.admin,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::*;
+
+/// Контекст для принятия встречного предложения ставки.
+#[derive(Accounts)]
+pub struct AcceptCounter<'info> {
+    /// Игра, ожидающая второго игрока.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Встречные условия; закрываются, рента возвращается автору.
+    #[account(
+        mut,
+        seeds = [b"counter_proposal", game.key().as_ref()],
+        bump = proposal.bump,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, CounterProposal>,
+
+    /// CHECK: address constraint ensures this is proposal.proposer; balance only credited
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Первый игрок: доплачивает или получает разницу депозита.
+    #[account(mut, address = game.player1)]
+    pub player1: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Шард лобби, в котором опубликована игра (необязательно).
+    #[account(mut, seeds = [b"lobby".as_ref(), &[lobby.shard]], bump = lobby.bump)]
+    pub lobby: Option<Box<Account<'info, Lobby>>>,
+}
+
+pub fn handler(ctx: Context<AcceptCounter>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+    let proposal = &ctx.accounts.proposal;
+
+    verbose_msg!(
+        "accept_counter: game_id={}, stake={}->{}, move_fee={}->{}",
+        game.game_id,
+        game.stake_lamports,
+        proposal.stake_lamports,
+        game.move_fee_lamports,
+        proposal.move_fee_lamports
+    );
+
+    require!(
+        game.status == GameStatus::WaitingForPlayer2,
+        ErrorCode::GameNotWaitingForPlayer2
+    );
+    require!(game.counter_pending, ErrorCode::NoCounterOffer);
+    require_ctx!(
+        game.pot_lamports == game.player1_deposit,
+        ErrorCode::InconsistentPot,
+        Lamports {
+            expected: game.player1_deposit,
+            actual: game.pot_lamports,
+        }
+    );
+
+    // Депозит player1 и резерв автовзятий (он зависит от ставки) приводятся
+    // к новой ставке одним переводом в нужную сторону.
+    let old_total = game
+        .player1_deposit
+        .checked_add(game.p1_take_reserve)
+        .ok_or(ErrorCode::MathOverflow)?;
+    game.stake_lamports = proposal.stake_lamports;
+    game.move_fee_lamports = proposal.move_fee_lamports;
+    let reserve = game.auto_take_reserve_needed(game.p1_auto_take_up_to)?;
+    let new_total = game
+        .stake_lamports
+        .checked_add(reserve)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if new_total > old_total {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.player1.to_account_info(),
+            to: game.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, new_total - old_total)?;
+    } else if new_total < old_total {
+        let refund = old_total - new_total;
+        debit(&game.to_account_info(), refund)?;
+        credit(&ctx.accounts.player1.to_account_info(), refund)?;
+    }
+
+    game.pot_lamports = game.stake_lamports;
+    game.player1_deposit = game.stake_lamports;
+    game.p1_take_reserve = reserve;
+    game.counter_pending = false;
+    game.last_activity_slot = Clock::get()?.slot;
+
+    if let Some(lobby) = ctx.accounts.lobby.as_deref_mut() {
+        for entry in lobby.entries.iter_mut().filter(|e| e.game == game.key()) {
+            entry.stake_lamports = game.stake_lamports;
+        }
+    }
+
+    emit!(CounterAccepted {
+        game: game.key(),
+        stake_lamports: game.stake_lamports,
+        move_fee_lamports: game.move_fee_lamports,
+        player1_deposit: game.player1_deposit,
+    });
+
+    verbose_msg!(
+        "accept_counter: completed, pot_lamports={}, take_reserve={}",
+        game.pot_lamports,
+        game.p1_take_reserve
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::*;
+
+/// Контекст для подтверждения или отклонения хода соперником.
+#[derive(Accounts)]
+pub struct RespondMove<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Соперник ходившего игрока.
+    pub player: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RespondMove>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "ack_move: game_id={}, move_index={}, player={}",
+        game.game_id,
+        game.move_index,
+        ctx.accounts.player.key()
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.move_pending, ErrorCode::NoMovePending);
+    require_keys_eq!(
+        ctx.accounts.player.key(),
+        game.player_key(3 - game.current_turn),
+        ErrorCode::InvalidPlayer
+    );
+
+    game.finalize_pending_move(Clock::get()?.slot)?;
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
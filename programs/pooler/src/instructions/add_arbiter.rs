@@ -0,0 +1,42 @@
+use crate::*;
+
+/// Контекст для добавления арбитра в белый список.
+#[derive(Accounts)]
+#[instruction(arbiter: Pubkey)]
+pub struct AddArbiter<'info> {
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// PDA арбитра в белом списке.
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + WhitelistedArbiter::MAX_SIZE,
+        seeds = [b"arbiter", arbiter.as_ref()],
+        bump,
+    )]
+    pub whitelisted_arbiter: Account<'info, WhitelistedArbiter>,
+
+    /// Администратор, платит за создание аккаунта.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddArbiter>, arbiter: Pubkey) -> Result<()> {
+    let entry = &mut ctx.accounts.whitelisted_arbiter;
+    entry.arbiter = arbiter;
+    entry.added_at_slot = Clock::get()?.slot;
+    entry.bump = ctx.bumps.whitelisted_arbiter;
+
+    verbose_msg!(
+        "add_arbiter: arbiter={}, slot={}",
+        entry.arbiter,
+        entry.added_at_slot
+    );
+
+    Ok(())
+}
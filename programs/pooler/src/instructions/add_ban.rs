@@ -0,0 +1,42 @@
+use crate::*;
+
+/// Контекст для бана игрока.
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct AddBan<'info> {
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// PDA бана.
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Ban::MAX_SIZE,
+        seeds = [b"ban", player.as_ref()],
+        bump,
+    )]
+    pub ban: Account<'info, Ban>,
+
+    /// Администратор, платит за создание аккаунта.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddBan>, player: Pubkey) -> Result<()> {
+    let ban = &mut ctx.accounts.ban;
+    ban.player = player;
+    ban.banned_at_slot = Clock::get()?.slot;
+    ban.bump = ctx.bumps.ban;
+
+    verbose_msg!(
+        "add_ban: player={}, slot={}",
+        ban.player,
+        ban.banned_at_slot
+    );
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use crate::*;
+
+/// Контекст для добавления напарника.
+#[derive(Accounts)]
+pub struct AddTeammate<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Составы команд, создаётся при первом напарнике.
+    #[account(
+        init_if_needed,
+        payer = teammate,
+        space = 8 + TeamState::MAX_SIZE,
+        seeds = [b"team", game.key().as_ref()],
+        bump,
+    )]
+    pub team: Account<'info, TeamState>,
+
+    /// Основной игрок стороны, получает долю напарника.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Напарник, платит свою долю и за аккаунт команд.
+    #[account(mut)]
+    pub teammate: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddTeammate>, share_lamports: u64) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let player = ctx.accounts.player.key();
+    let teammate = ctx.accounts.teammate.key();
+
+    verbose_msg!(
+        "add_teammate: game_id={}, player={}, teammate={}, share_lamports={}",
+        game.game_id,
+        player,
+        teammate,
+        share_lamports
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.move_index == 0, ErrorCode::OpeningRollOver);
+    require!(!game.bot_game, ErrorCode::InvalidTeammate);
+    let side: u8 = if player == game.player1 {
+        1
+    } else if player == game.player2 {
+        2
+    } else {
+        return Err(ErrorCode::InvalidPlayer.into());
+    };
+    require!(
+        teammate != game.player1 && teammate != game.player2,
+        ErrorCode::InvalidTeammate
+    );
+    // Резерв автовзятий вносил основной игрок один.
+    let (contribution, reserve) = if side == 1 {
+        (
+            game.player1_deposit.checked_add(game.player1_fees_paid),
+            game.p1_take_reserve,
+        )
+    } else {
+        (
+            game.player2_deposit.checked_add(game.player2_fees_paid),
+            game.p2_take_reserve,
+        )
+    };
+    let contribution = contribution.ok_or(ErrorCode::MathOverflow)?;
+    require!(reserve == 0, ErrorCode::InvalidTeammate);
+    require!(
+        share_lamports > 0 && share_lamports <= contribution,
+        ErrorCode::InvalidTeamShare
+    );
+
+    let team = &mut ctx.accounts.team;
+    if team.game == Pubkey::default() {
+        team.game = game.key();
+        team.bump = ctx.bumps.team;
+    }
+    require!(!team.has_teammate(side), ErrorCode::InvalidTeammate);
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.teammate.to_account_info(),
+        to: ctx.accounts.player.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, share_lamports)?;
+
+    let main_member = ((side - 1) * 2) as usize;
+    team.teammates[(side - 1) as usize] = teammate;
+    team.contributions[main_member] = contribution - share_lamports;
+    team.contributions[main_member + 1] = share_lamports;
+    game.team_game = true;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::*;
+
+pub fn handler(ctx: Context<TournamentOrganizer>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+
+    require!(
+        tournament.status == TournamentStatus::Running,
+        ErrorCode::TournamentNotRunning
+    );
+    require!(
+        tournament.mode == TournamentMode::Knockout,
+        ErrorCode::WrongTournamentMode
+    );
+
+    let pairs = (tournament.bracket_size / 2) as usize;
+    require!(
+        tournament.round_winners[..pairs]
+            .iter()
+            .all(|w| *w != Pubkey::default()),
+        ErrorCode::RoundNotComplete
+    );
+
+    let mut bracket = [Pubkey::default(); TOURNAMENT_MAX_PLAYERS];
+    bracket[..pairs].copy_from_slice(&tournament.round_winners[..pairs]);
+    tournament.bracket = bracket;
+    tournament.bracket_size = pairs as u8;
+    tournament.round_winners = [Pubkey::default(); TOURNAMENT_MAX_PLAYERS / 2];
+
+    if pairs == 1 {
+        tournament.champion = tournament.bracket[0];
+        tournament.status = TournamentStatus::Finished;
+        verbose_msg!(
+            "advance_round: tournament finished, champion={}",
+            tournament.champion
+        );
+    } else {
+        tournament.round = tournament
+            .round
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        verbose_msg!(
+            "advance_round: round={}, players_left={}",
+            tournament.round,
+            tournament.bracket_size
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::*;
+
+/// Контекст для апелляции на решение арбитра.
+#[derive(Accounts)]
+pub struct Appeal<'info> {
+    /// Спорная игра с решением арбитра.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Игрок, обжалующий решение.
+    pub player: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<Appeal>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let player = ctx.accounts.player.key();
+    let current_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "appeal: game_id={}, player={}, appeal_deadline_slot={}, current_slot={}",
+        game.game_id,
+        player,
+        game.appeal_deadline_slot,
+        current_slot
+    );
+
+    require!(
+        player == game.player1 || player == game.player2,
+        ErrorCode::InvalidPlayer
+    );
+    require!(game.resolution_pending(), ErrorCode::NoPendingResolution);
+    require!(!game.resolution_appealed, ErrorCode::AlreadyAppealed);
+    require_ctx!(
+        current_slot <= game.appeal_deadline_slot,
+        ErrorCode::AppealWindowClosed,
+        Slot {
+            expected: game.appeal_deadline_slot,
+            actual: current_slot,
+        }
+    );
+
+    let winner = game.player_key(game.resolution_winner);
+    // Решение арбитра отменяется; игра остаётся в споре, и дальше его
+    // решает админ через arbiter_resolve (или возвраты).
+    game.resolution_winner = 0;
+    game.appeal_deadline_slot = 0;
+    game.resolution_appealed = true;
+
+    emit!(ResolutionAppealed {
+        game: game.key(),
+        appellant: player,
+        winner,
+    });
+
+    Ok(())
+}
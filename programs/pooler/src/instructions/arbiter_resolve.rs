@@ -0,0 +1,109 @@
+use crate::*;
+
+/// Контекст для решения арбитра.
+#[derive(Accounts)]
+pub struct ArbiterResolve<'info> {
+    /// Спорная игра.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Арбитр игры (game.arbiter или, если он не задан, config.arbiter);
+    /// после апелляции — админ.
+    pub arbiter: Signer<'info>,
+
+    /// Доказательства сторон, если подавались.
+    #[account(seeds = [b"evidence", game.key().as_ref()], bump = evidence.bump)]
+    pub evidence: Option<Account<'info, Evidence>>,
+
+    /// CHECK: PDA [b"arbiter", game.arbiter] enforced by seeds; initialized =
+    /// whitelisted. Required only for a game arbiter when
+    /// config.require_whitelisted_arbiters.
+    #[account(seeds = [b"arbiter", game.arbiter.as_ref()], bump)]
+    pub whitelisted_arbiter: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<ArbiterResolve>, winner: Pubkey) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(!config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+    let resolver = ctx.accounts.arbiter.key();
+    let current_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "arbiter_resolve: game_id={}, resolver={}, winner={}, appealed={}",
+        game.game_id,
+        resolver,
+        winner,
+        game.resolution_appealed
+    );
+
+    require!(
+        game.status == GameStatus::Disputed,
+        ErrorCode::GameNotDisputed
+    );
+    require!(!game.resolution_pending(), ErrorCode::ResolutionPending);
+
+    let appeal_deadline_slot = if game.resolution_appealed {
+        // Обжалованный спор решает админ; его решение не обжалуется и
+        // исполняется со следующего слота.
+        require!(!config.locked, ErrorCode::ConfigLocked);
+        require_keys_eq!(resolver, config.admin, ErrorCode::InvalidArbiter);
+        current_slot
+    } else {
+        require_keys_eq!(
+            resolver,
+            game.arbiter_key(config),
+            ErrorCode::InvalidArbiter
+        );
+        // Белый список проверяется и при решении: арбитр, исключённый из него
+        // после init_game, теряет власть над игрой, и спор разрешается
+        // возвратами (force_refund / manual_refund).
+        check_arbiter_whitelisted(
+            config,
+            &game.arbiter,
+            ctx.accounts
+                .whitelisted_arbiter
+                .as_ref()
+                .map(|a| a.as_ref()),
+        )?;
+        current_slot
+            .checked_add(config.appeal_window())
+            .ok_or(ErrorCode::MathOverflow)?
+    };
+
+    let winner_index = if winner == game.player1 {
+        1
+    } else if winner == game.player2 {
+        2
+    } else {
+        return Err(ErrorCode::InvalidWinner.into());
+    };
+
+    let has_evidence = ctx.accounts.evidence.as_ref().is_some_and(|e| {
+        e.player1_cid != [0u8; EVIDENCE_CID_LEN] || e.player2_cid != [0u8; EVIDENCE_CID_LEN]
+    });
+    // last_activity_slot в споре не меняется и равен слоту reject_move.
+    let window_passed = current_slot
+        .checked_sub(game.last_activity_slot)
+        .ok_or(ErrorCode::MathOverflow)?
+        >= EVIDENCE_WINDOW_SLOTS;
+    require!(has_evidence || window_passed, ErrorCode::EvidenceWindowOpen);
+
+    // Банк не трогается: выплату делает execute_resolution после окна
+    // апелляции, а до неё возвраты заблокированы.
+    game.resolution_winner = winner_index;
+    game.appeal_deadline_slot = appeal_deadline_slot;
+
+    emit!(ResolutionProposed {
+        game: game.key(),
+        resolver,
+        winner,
+        appeal_deadline_slot,
+    });
+
+    Ok(())
+}
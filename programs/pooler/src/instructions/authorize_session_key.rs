@@ -0,0 +1,47 @@
+use crate::*;
+
+/// Контекст для выдачи и отзыва сессионного ключа.
+#[derive(Accounts)]
+pub struct SessionKey<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Игрок (основной ключ), выдающий или отзывающий сессионный ключ.
+    pub player: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SessionKey>, key: Pubkey, expiry_slot: u64) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let player = ctx.accounts.player.key();
+
+    verbose_msg!(
+        "authorize_session_key: game_id={}, player={}, key={}, expiry_slot={}",
+        game.game_id,
+        player,
+        key,
+        expiry_slot
+    );
+
+    require!(!game.status.is_over(), ErrorCode::GameNotActive);
+    require!(
+        expiry_slot > Clock::get()?.slot,
+        ErrorCode::InvalidSessionKey
+    );
+    require!(
+        key != Pubkey::default() && key != game.player1 && key != game.player2,
+        ErrorCode::InvalidSessionKey
+    );
+
+    if player == game.player1 {
+        game.player1_session_key = key;
+        game.player1_session_expiry = expiry_slot;
+    } else if player == game.player2 {
+        game.player2_session_key = key;
+        game.player2_session_expiry = expiry_slot;
+    } else {
+        return Err(ErrorCode::InvalidPlayer.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::*;
+
+/// Контекст для автоматического взятия удвоения.
+#[derive(Accounts)]
+pub struct AutoTake<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Любой плательщик комиссии транзакции.
+    pub payer: Signer<'info>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<AutoTake>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "auto_take: game_id={}, cube_value={}, current_turn={}",
+        game.game_id,
+        game.cube_value,
+        game.current_turn
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.double_pending, ErrorCode::NoDoublePending);
+
+    let taker_turn = match game.current_turn {
+        1 => 2u8,
+        2 => 1u8,
+        _ => return Err(ErrorCode::InvalidCurrentTurn.into()),
+    };
+    let (up_to, reserve) = if taker_turn == 1 {
+        (game.p1_auto_take_up_to, game.p1_take_reserve)
+    } else {
+        (game.p2_auto_take_up_to, game.p2_take_reserve)
+    };
+    let new_cube = game
+        .cube_value
+        .checked_mul(2)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(new_cube <= up_to, ErrorCode::AutoTakeNotAllowed);
+
+    let extra = game
+        .stake_lamports
+        .checked_mul(game.cube_value as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let remaining = reserve
+        .checked_sub(extra)
+        .ok_or(ErrorCode::AutoTakeNotAllowed)?;
+
+    verbose_msg!(
+        "auto_take: moving extra={} from reserve of player{}, reserve_left={}",
+        extra,
+        taker_turn,
+        remaining
+    );
+
+    // Лампорты резерва уже лежат на аккаунте игры, переносим их в банк.
+    if taker_turn == 1 {
+        game.p1_take_reserve = remaining;
+    } else {
+        game.p2_take_reserve = remaining;
+    }
+    game.add_deposit(taker_turn, extra)?;
+
+    game.cube_value = new_cube;
+    game.cube_owner = taker_turn;
+    game.double_pending = false;
+    game.last_activity_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "auto_take: completed, cube_value={}, pot_lamports={}",
+        game.cube_value,
+        game.pot_lamports
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::*;
+
+/// Контекст для бобра: доплаты вносят оба игрока.
+#[derive(Accounts)]
+pub struct BeaverDouble<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок, должен совпадать с game.player1.
+    #[account(mut, address = game.player1)]
+    pub player1: Signer<'info>,
+
+    /// Второй игрок, должен совпадать с game.player2.
+    #[account(
+        mut,
+        address = game.player2,
+        constraint = player2.key() != player1.key() @ ErrorCode::DuplicatePlayerAccounts,
+    )]
+    pub player2: Signer<'info>,
+
+    /// Системная программа Solana, нужна для transfer через CPI.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<BeaverDouble>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "beaver_double: game_id={}, cube_value={}, current_turn={}",
+        game.game_id,
+        game.cube_value,
+        game.current_turn
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.beavers_allowed, ErrorCode::BeaversNotAllowed);
+    require!(game.double_pending, ErrorCode::NoDoublePending);
+
+    // Считаем в u16: при кубе 64 произведение не помещается в u8, а ответить
+    // нужно CubeAtMaximum, а не MathOverflow.
+    let new_cube = game.cube_value as u16 * 4;
+    require!(new_cube <= MAX_CUBE_VALUE as u16, ErrorCode::CubeAtMaximum);
+    let new_cube = new_cube as u8;
+
+    let doubler_turn = game.current_turn;
+    let taker_turn = match doubler_turn {
+        1 => 2u8,
+        2 => 1u8,
+        _ => return Err(ErrorCode::InvalidCurrentTurn.into()),
+    };
+
+    // Предложивший уже внёс доплату за 2v, ему остаётся довнести до 4v.
+    // Принимающий вносит доплату за 2v и за 4v сразу.
+    let step = game
+        .stake_lamports
+        .checked_mul(game.cube_value as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let doubler_extra = step.checked_mul(2).ok_or(ErrorCode::MathOverflow)?;
+    let taker_extra = step.checked_mul(3).ok_or(ErrorCode::MathOverflow)?;
+
+    let (p1_extra, p2_extra) = if doubler_turn == 1 {
+        (doubler_extra, taker_extra)
+    } else {
+        (taker_extra, doubler_extra)
+    };
+
+    verbose_msg!(
+        "beaver_double: escrowing p1_extra={}, p2_extra={}, new_cube={}",
+        p1_extra,
+        p2_extra,
+        new_cube
+    );
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player1.to_account_info(),
+        to: game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, p1_extra)?;
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player2.to_account_info(),
+        to: game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, p2_extra)?;
+
+    game.add_deposit(1, p1_extra)?;
+    game.add_deposit(2, p2_extra)?;
+
+    game.cube_value = new_cube;
+    game.cube_owner = taker_turn;
+    game.double_pending = false;
+    game.raccoon_pending = true;
+    game.last_activity_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "beaver_double: completed, cube_value={}, cube_owner={}, pot_lamports={}",
+        game.cube_value,
+        game.cube_owner,
+        game.pot_lamports
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
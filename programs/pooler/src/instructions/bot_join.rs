@@ -0,0 +1,135 @@
+use crate::*;
+
+/// Контекст для входа бота в открытое лобби.
+#[derive(Accounts)]
+pub struct BotJoin<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Глобальный конфиг программы.
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; system-owned, holds the bot's funds.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: UncheckedAccount<'info>,
+
+    /// Кранк оператора бота, должен совпадать с config.bot_wallet.
+    #[account(address = config.bot_wallet)]
+    pub bot_operator: Signer<'info>,
+
+    /// Счётчик открытых лобби создателя игры; обязателен, если игра в нём
+    /// учтена (game.open_games_counted), у игр до OpenGames не нужен.
+    #[account(mut, seeds = [b"open_games", game.player1.as_ref()], bump = player1_open_games.bump)]
+    pub player1_open_games: Option<Account<'info, OpenGames>>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Шард лобби, в котором опубликована игра (необязательно).
+    #[account(mut, seeds = [b"lobby".as_ref(), &[lobby.shard]], bump = lobby.bump)]
+    pub lobby: Option<Box<Account<'info, Lobby>>>,
+}
+
+pub fn handler(ctx: Context<BotJoin>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let config = &ctx.accounts.config;
+    let game = &mut ctx.accounts.game;
+    let bot_wallet = config.bot_wallet;
+
+    verbose_msg!(
+        "bot_join: game_id={}, player1={}, player2={}, stake_lamports={}, bot_wallet={}",
+        game.game_id,
+        game.player1,
+        game.player2,
+        game.stake_lamports,
+        bot_wallet
+    );
+
+    require!(bot_wallet != Pubkey::default(), ErrorCode::BotDisabled);
+    // Бот не передаёт аккаунты пула: банк бот-игры не размещается.
+    require!(!game.stake_pot, ErrorCode::MissingStakePoolAccounts);
+    require!(
+        game.status == GameStatus::WaitingForPlayer2,
+        ErrorCode::GameNotWaitingForPlayer2
+    );
+    require!(
+        game.join_deadline_slot == 0 || Clock::get()?.slot <= game.join_deadline_slot,
+        ErrorCode::JoinDeadlinePassed
+    );
+    require!(game.player1 != bot_wallet, ErrorCode::BotCannotJoinOwnGame);
+    require!(
+        game.player2 == Pubkey::default() || game.player2 == bot_wallet,
+        ErrorCode::GameNotOpenForBot
+    );
+    require!(
+        game.password_hash == [0u8; 32]
+            && game.tournament == Pubkey::default()
+            && game.min_opponent_rating <= DEFAULT_RATING,
+        ErrorCode::GameNotOpenForBot
+    );
+    require!(
+        game.stake_lamports <= config.bot_max_stake,
+        ErrorCode::BotStakeTooHigh
+    );
+
+    // bot_vault — system-owned PDA без данных, поэтому после списания на нём
+    // должна остаться сумма не меньше rent-exempt минимума.
+    let stake = game.stake_lamports;
+    let min_balance = Rent::get()?.minimum_balance(0);
+    require!(
+        ctx.accounts.bot_vault.lamports()
+            >= stake
+                .checked_add(min_balance)
+                .ok_or(ErrorCode::MathOverflow)?,
+        ErrorCode::BotVaultInsufficient
+    );
+
+    if !game.practice {
+        transfer_from_bot_vault(
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.bot_vault.to_account_info(),
+            game.to_account_info(),
+            ctx.bumps.bot_vault,
+            stake,
+        )?;
+    }
+    game.add_deposit(2, stake)?;
+
+    game.player2 = bot_wallet;
+    game.bot_game = true;
+    game.last_activity_slot = Clock::get()?.slot;
+    game.status = GameStatus::Active;
+    release_open_game(game, ctx.accounts.player1_open_games.as_mut())?;
+    if let Some(lobby) = ctx.accounts.lobby.as_deref_mut() {
+        lobby.unlist(game.key());
+    }
+
+    emit!(PlayerJoined {
+        game: game.key(),
+        player: game.player2,
+        stake_lamports: stake,
+        pot_lamports: game.pot_lamports,
+        bot: true,
+    });
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Joined,
+        game.player2,
+        stake,
+    )?;
+
+    verbose_msg!(
+        "bot_join: completed, pot_lamports={}, status={:?}",
+        game.pot_lamports,
+        game.status
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::*;
+
+/// Отмена игры до присоединения второго игрока.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelBeforeJoin<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок, который создавал игру и может её отменить.
+    #[account(mut, address = game.player1)]
+    pub player1: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Счётчик открытых лобби создателя игры; обязателен, если игра в нём
+    /// учтена (game.open_games_counted), у игр до OpenGames не нужен.
+    #[account(mut, seeds = [b"open_games", game.player1.as_ref()], bump = player1_open_games.bump)]
+    pub player1_open_games: Option<Account<'info, OpenGames>>,
+
+    /// CHECK: PDA [b"payout_splits", game.player1] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player1.as_ref()], bump)]
+    pub player1_splits: UncheckedAccount<'info>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelBeforeJoin<'info>>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+
+    require!(
+        game.status == GameStatus::WaitingForPlayer2,
+        ErrorCode::GameNotWaitingForPlayer2
+    );
+
+    let amount = game.pot_lamports;
+    verbose_msg!(
+        "cancel_before_join: refunding {} lamports to player1={}",
+        amount,
+        game.player1
+    );
+
+    // Переводим банк обратно игроку напрямую (аккаунт игры принадлежит нашей программе).
+    pay_out(
+        &game.to_account_info(),
+        ctx.accounts.player1.to_account_info(),
+        ctx.accounts.player1_splits.as_ref(),
+        &mut ctx.remaining_accounts.iter(),
+        amount,
+    )?;
+
+    game.cancel(Clock::get()?.slot);
+    release_open_game(game, ctx.accounts.player1_open_games.as_mut())?;
+
+    emit_cpi!(GameCancelled {
+        game: game.key(),
+        player1: game.player1,
+        refunded: amount,
+    });
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Cancelled,
+        game.player1,
+        amount,
+    )?;
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::*;
+
+/// Контекст для отказа члена команды от удвоения.
+#[derive(Accounts)]
+pub struct ChouetteDrop<'info> {
+    /// Аккаунт шуэта.
+    #[account(mut)]
+    pub chouette: Account<'info, ChouetteState>,
+
+    /// Член команды, которому предложено удвоение.
+    pub member: Signer<'info>,
+
+    /// CHECK: address constraint ensures this is chouette.box_player; balance only credited
+    #[account(mut, address = chouette.box_player)]
+    pub box_player: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<ChouetteDrop>, member_index: u8) -> Result<()> {
+    let chouette = &mut ctx.accounts.chouette;
+    let idx = member_index as usize;
+
+    require!(!chouette.settled, ErrorCode::ChouetteSettled);
+    require!(idx < CHOUETTE_MAX_MEMBERS, ErrorCode::InvalidChouetteMember);
+    let entry = chouette.members[idx];
+    require!(
+        entry.status == ChouetteMemberStatus::Active,
+        ErrorCode::InvalidChouetteMember
+    );
+    require_keys_eq!(
+        ctx.accounts.member.key(),
+        entry.member,
+        ErrorCode::InvalidChouetteMember
+    );
+    require!(entry.double_pending, ErrorCode::NoDoublePending);
+
+    let amount = entry
+        .member_deposit
+        .checked_add(entry.box_deposit)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!(
+        "chouette_drop: member={}, paying {} lamports to box={}",
+        entry.member,
+        amount,
+        chouette.box_player
+    );
+
+    debit(&chouette.to_account_info(), amount)?;
+    credit(&ctx.accounts.box_player.to_account_info(), amount)?;
+
+    let entry = &mut chouette.members[idx];
+    entry.member_deposit = 0;
+    entry.box_deposit = 0;
+    entry.double_pending = false;
+    entry.status = ChouetteMemberStatus::Dropped;
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::*;
+
+/// Контекст для удвоения коробкой против члена команды.
+#[derive(Accounts)]
+pub struct ChouetteOfferDouble<'info> {
+    /// Аккаунт шуэта.
+    #[account(mut)]
+    pub chouette: Account<'info, ChouetteState>,
+
+    /// Игрок в коробке.
+    #[account(mut, address = chouette.box_player)]
+    pub box_player: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ChouetteOfferDouble>, member_index: u8) -> Result<()> {
+    let chouette = &mut ctx.accounts.chouette;
+    let idx = member_index as usize;
+
+    require!(!chouette.settled, ErrorCode::ChouetteSettled);
+    require!(idx < CHOUETTE_MAX_MEMBERS, ErrorCode::InvalidChouetteMember);
+    let entry = chouette.members[idx];
+    require!(
+        entry.status == ChouetteMemberStatus::Active,
+        ErrorCode::InvalidChouetteMember
+    );
+    require!(!entry.double_pending, ErrorCode::DoublePending);
+    require!(entry.cube_value < MAX_CUBE_VALUE, ErrorCode::CubeAtMaximum);
+
+    let extra = chouette
+        .stake_lamports
+        .checked_mul(entry.cube_value as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!(
+        "chouette_offer_double: member={}, cube_value={}, box_extra={}",
+        entry.member,
+        entry.cube_value,
+        extra
+    );
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.box_player.to_account_info(),
+        to: chouette.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, extra)?;
+
+    let entry = &mut chouette.members[idx];
+    entry.box_deposit = entry
+        .box_deposit
+        .checked_add(extra)
+        .ok_or(ErrorCode::MathOverflow)?;
+    entry.double_pending = true;
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::*;
+
+/// Контекст для принятия удвоения членом команды.
+#[derive(Accounts)]
+pub struct ChouetteRespond<'info> {
+    /// Аккаунт шуэта.
+    #[account(mut)]
+    pub chouette: Account<'info, ChouetteState>,
+
+    /// Член команды, которому предложено удвоение.
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ChouetteRespond>, member_index: u8) -> Result<()> {
+    let chouette = &mut ctx.accounts.chouette;
+    let idx = member_index as usize;
+
+    require!(!chouette.settled, ErrorCode::ChouetteSettled);
+    require!(idx < CHOUETTE_MAX_MEMBERS, ErrorCode::InvalidChouetteMember);
+    let entry = chouette.members[idx];
+    require!(
+        entry.status == ChouetteMemberStatus::Active,
+        ErrorCode::InvalidChouetteMember
+    );
+    require_keys_eq!(
+        ctx.accounts.member.key(),
+        entry.member,
+        ErrorCode::InvalidChouetteMember
+    );
+    require!(entry.double_pending, ErrorCode::NoDoublePending);
+
+    let extra = chouette
+        .stake_lamports
+        .checked_mul(entry.cube_value as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!(
+        "chouette_take: member={}, cube_value={}, member_extra={}",
+        entry.member,
+        entry.cube_value,
+        extra
+    );
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.member.to_account_info(),
+        to: chouette.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, extra)?;
+
+    let entry = &mut chouette.members[idx];
+    entry.member_deposit = entry
+        .member_deposit
+        .checked_add(extra)
+        .ok_or(ErrorCode::MathOverflow)?;
+    entry.cube_value = entry
+        .cube_value
+        .checked_mul(2)
+        .ok_or(ErrorCode::MathOverflow)?;
+    entry.double_pending = false;
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use crate::*;
+
+/// Контекст для вывода банка из пула.
+#[derive(Accounts)]
+pub struct ClaimPotUnstake<'info> {
+    /// Аккаунт игры, получает банк обратно.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Учёт размещения.
+    #[account(mut, seeds = [b"pot_stake", game.key().as_ref()], bump = pot_stake.bump)]
+    pub pot_stake: Account<'info, PotStake>,
+
+    /// Промежуточный system-owned счёт, принимает вывод пула.
+    #[account(mut, seeds = [b"pot_vault", game.key().as_ref()], bump)]
+    pub pot_vault: SystemAccount<'info>,
+
+    /// Счёт токенов пула; закрывается после вывода.
+    #[account(mut, seeds = [b"pot_stake_tokens", game.key().as_ref()], bump)]
+    pub pool_tokens: Account<'info, TokenAccount>,
+
+    /// Минт токенов пула.
+    #[account(mut, address = pool_tokens.mint)]
+    pub pool_mint: Account<'info, Mint>,
+
+    /// CHECK: address constraint ensures this is the pool the pot was staked in
+    #[account(mut, address = pot_stake.stake_pool)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: address constraint ensures this is pot_stake.funder; only credited
+    #[account(mut, address = pot_stake.funder)]
+    pub funder: UncheckedAccount<'info>,
+
+    /// Sysvar Clock (нужен пулу).
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: address constraint ensures this is the stake history sysvar
+    #[account(address = solana_sdk_ids::sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: address constraint ensures this is the native stake program
+    #[account(address = solana_sdk_ids::stake::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    /// CHECK: address constraint ensures this is the SPL stake pool program
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    /// Программа SPL Token.
+    pub token_program: Program<'info, Token>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimPotUnstake>) -> Result<()> {
+    require_cpi_policy(instruction::ClaimPotUnstake::DISCRIMINATOR)?;
+    let stake = &ctx.accounts.pot_stake;
+    require!(
+        stake.pool_tokens > 0 && stake.unstake_epoch != 0,
+        ErrorCode::PotNotStaked
+    );
+    require!(
+        Clock::get()?.epoch > stake.unstake_epoch,
+        ErrorCode::UnstakeEpochNotReached
+    );
+    let pool_tokens = stake.pool_tokens;
+    let principal = stake.principal;
+
+    let game_key = ctx.accounts.game.key();
+    let stake_bump = [stake.bump];
+    let stake_seeds: &[&[u8]] = &[b"pot_stake", game_key.as_ref(), &stake_bump];
+    let vault_bump = [ctx.bumps.pot_vault];
+    let vault_seeds: &[&[u8]] = &[b"pot_vault", game_key.as_ref(), &vault_bump];
+    let vault_signer: &[&[&[u8]]] = &[vault_seeds];
+
+    let ix = StakePoolAccounts {
+        stake_pool: ctx.accounts.stake_pool.key(),
+        withdraw_authority: ctx.accounts.stake_pool_withdraw_authority.key(),
+        reserve_stake: ctx.accounts.reserve_stake.key(),
+        manager_fee_account: ctx.accounts.manager_fee_account.key(),
+        pool_mint: ctx.accounts.pool_mint.key(),
+    }
+    .withdraw_sol(
+        &ctx.accounts.pot_stake.key(),
+        &ctx.accounts.pool_tokens.key(),
+        &ctx.accounts.pot_vault.key(),
+        pool_tokens,
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.stake_pool.to_account_info(),
+            ctx.accounts.stake_pool_withdraw_authority.to_account_info(),
+            ctx.accounts.pot_stake.to_account_info(),
+            ctx.accounts.pool_tokens.to_account_info(),
+            ctx.accounts.reserve_stake.to_account_info(),
+            ctx.accounts.pot_vault.to_account_info(),
+            ctx.accounts.manager_fee_account.to_account_info(),
+            ctx.accounts.pool_mint.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        ],
+        &[stake_seeds],
+    )?;
+
+    // Всё, что пришло на vault, возвращается на аккаунт игры.
+    let returned = ctx.accounts.pot_vault.lamports();
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.pot_vault.to_account_info(),
+        to: ctx.accounts.game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.system_program.to_account_info(),
+        cpi_accounts,
+        vault_signer,
+    );
+    system_program::transfer(cpi_ctx, returned)?;
+
+    // Пустой счёт токенов пула закрывается, рента — тому, кто её платил.
+    let close_accounts = CloseAccount {
+        account: ctx.accounts.pool_tokens.to_account_info(),
+        destination: ctx.accounts.funder.to_account_info(),
+        authority: ctx.accounts.pot_stake.to_account_info(),
+    };
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_accounts,
+        &[stake_seeds],
+    ))?;
+
+    let game = &mut ctx.accounts.game;
+    game.apply_stake_result(principal, returned)?;
+    game.staked_lamports = 0;
+    let stake = &mut ctx.accounts.pot_stake;
+    stake.pool_tokens = 0;
+    stake.returned = returned;
+
+    verbose_msg!(
+        "claim_pot_unstake: game_id={}, principal={}, returned={}, pot={}",
+        ctx.accounts.game.game_id,
+        principal,
+        returned,
+        ctx.accounts.game.pot_lamports
+    );
+    emit!(PotUnstaked {
+        game: game_key,
+        principal,
+        returned,
+    });
+    Ok(())
+}
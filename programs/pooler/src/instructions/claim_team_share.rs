@@ -0,0 +1,64 @@
+use crate::*;
+
+/// Контекст для выплаты доли участнику командной игры.
+#[derive(Accounts)]
+pub struct ClaimTeamShare<'info> {
+    /// Завершённая игра.
+    #[account(address = team.game)]
+    pub game: Account<'info, GameState>,
+
+    /// Составы команд.
+    #[account(mut, seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Account<'info, TeamState>,
+
+    /// CHECK: must match the claimed member's key, checked in the handler; balance only credited
+    #[account(mut, constraint = recipient.key() != game.key() @ ErrorCode::PayoutAliasesGame)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Любой плательщик комиссии транзакции.
+    pub payer: Signer<'info>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<ClaimTeamShare>, member: u8) -> Result<()> {
+    require_cpi_policy(instruction::ClaimTeamShare::DISCRIMINATOR)?;
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &ctx.accounts.game;
+    let team = &mut ctx.accounts.team;
+    let recipient = ctx.accounts.recipient.key();
+
+    require!(game.status.is_over(), ErrorCode::GameNotFinished);
+    require!(member < 4, ErrorCode::InvalidTeammate);
+    let member = member as usize;
+    let side = (member / 2) as u8 + 1;
+    let expected = if member % 2 == 0 {
+        game.player_key(side)
+    } else {
+        team.teammates[(side - 1) as usize]
+    };
+    require!(
+        expected != Pubkey::default() && recipient == expected,
+        ErrorCode::InvalidTeammate
+    );
+    require!(!team.claimed[member], ErrorCode::TeamShareClaimed);
+
+    let amount = team.share_of(member);
+    verbose_msg!(
+        "claim_team_share: game_id={}, member={}, recipient={}, amount={}",
+        game.game_id,
+        member,
+        recipient,
+        amount
+    );
+
+    if amount > 0 {
+        debit(&team.to_account_info(), amount)?;
+        credit(&ctx.accounts.recipient, amount)?;
+    }
+    team.claimed[member] = true;
+
+    Ok(())
+}
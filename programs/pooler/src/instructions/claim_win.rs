@@ -0,0 +1,80 @@
+use crate::*;
+
+/// Контекст для заявки на победу.
+#[derive(Accounts)]
+pub struct ClaimWin<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Заявка; хранит залог заявителя.
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + WinClaim::MAX_SIZE,
+        seeds = [b"win_claim", game.key().as_ref()],
+        bump,
+    )]
+    pub win_claim: Account<'info, WinClaim>,
+
+    /// Заявитель (один из игроков), вносит залог.
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimWin>, final_board: [i8; 24]) -> Result<()> {
+    require_cpi_policy(instruction::ClaimWin::DISCRIMINATOR)?;
+    let game = &mut ctx.accounts.game;
+    let claimant = ctx.accounts.claimant.key();
+    let current_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "claim_win: game_id={}, claimant={}, current_slot={}",
+        game.game_id,
+        claimant,
+        current_slot
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(!game.win_claim_pending, ErrorCode::WinClaimPending);
+    require!(!game.move_pending, ErrorCode::MovePending);
+    require!(
+        claimant == game.player1 || claimant == game.player2,
+        ErrorCode::InvalidPlayer
+    );
+    require!(
+        game.rule_variant.board_fits(&final_board),
+        ErrorCode::InvalidBoard
+    );
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.claimant.to_account_info(),
+        to: ctx.accounts.win_claim.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, WIN_CLAIM_BOND_LAMPORTS)?;
+
+    let deadline_slot = current_slot
+        .checked_add(CLAIM_CONTEST_WINDOW_SLOTS)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let claim = &mut ctx.accounts.win_claim;
+    claim.game = game.key();
+    claim.claimant = claimant;
+    claim.final_board = final_board;
+    claim.deadline_slot = deadline_slot;
+    claim.bond_lamports = WIN_CLAIM_BOND_LAMPORTS;
+    claim.bump = ctx.bumps.win_claim;
+    game.win_claim_pending = true;
+
+    emit!(WinClaimed {
+        game: game.key(),
+        claimant,
+        final_board,
+        deadline_slot,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::*;
+
+/// Контекст для закрытия буфера событий.
+#[derive(Accounts)]
+pub struct CloseEventBuffer<'info> {
+    /// Завершённая игра.
+    pub game: Account<'info, GameState>,
+
+    /// Буфер событий, закрывается.
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"event_buffer", game.key().as_ref()],
+        bump = event_buffer.bump,
+    )]
+    pub event_buffer: Box<Account<'info, EventBuffer>>,
+
+    /// CHECK: address constraint ensures this is event_buffer.payer; only credited
+    #[account(mut, address = event_buffer.payer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseEventBuffer>) -> Result<()> {
+    require!(
+        ctx.accounts.game.status.is_over(),
+        ErrorCode::GameNotFinished
+    );
+    verbose_msg!("close_event_buffer: game={}", ctx.accounts.game.key());
+    Ok(())
+}
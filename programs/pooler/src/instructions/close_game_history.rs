@@ -0,0 +1,42 @@
+use crate::*;
+
+/// Контекст для закрытия последнего чанка истории ходов.
+#[derive(Accounts)]
+pub struct CloseGameHistory<'info> {
+    /// Завершённая игра; после закрытия всей истории её можно закрыть prune_games.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Последний чанк истории (GameState::current_history_chunk), закрывается.
+    #[account(
+        mut,
+        close = payer,
+        constraint = game_history.game == game.key()
+            && game_history.chunk_index == game.current_history_chunk
+            @ ErrorCode::GameHistoryMismatch,
+    )]
+    pub game_history: Box<Account<'info, GameHistory>>,
+
+    /// CHECK: address constraint ensures this is game_history.payer; only credited
+    #[account(mut, address = game_history.payer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseGameHistory>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    require!(game.status.is_over(), ErrorCode::GameNotFinished);
+
+    // Чанки закрываются от последнего к первому; после чанка 0 истории нет.
+    match game.current_history_chunk {
+        0 => game.move_history = false,
+        chunk => game.current_history_chunk = chunk - 1,
+    }
+
+    verbose_msg!(
+        "close_game_history: game={}, chunk={}, moves={}",
+        game.key(),
+        ctx.accounts.game_history.chunk_index,
+        ctx.accounts.game_history.moves.len()
+    );
+    Ok(())
+}
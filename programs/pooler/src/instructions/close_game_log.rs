@@ -0,0 +1,28 @@
+use crate::*;
+
+/// Контекст для закрытия GameLog.
+#[derive(Accounts)]
+pub struct CloseGameLog<'info> {
+    /// Состояние переигрывания лога.
+    #[account(
+        mut,
+        seeds = [b"game_log", game_log.game.as_ref(), submitter.key().as_ref()],
+        bump = game_log.bump,
+        has_one = submitter,
+        close = submitter,
+    )]
+    pub game_log: Account<'info, GameLog>,
+
+    /// Подававший лог, получает ренту.
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseGameLog>) -> Result<()> {
+    verbose_msg!(
+        "close_game_log: game={}, verified={}",
+        ctx.accounts.game_log.game,
+        ctx.accounts.game_log.verified
+    );
+    Ok(())
+}
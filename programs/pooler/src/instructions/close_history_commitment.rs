@@ -0,0 +1,34 @@
+use crate::*;
+
+/// Контекст для закрытия Merkle-коммитмента ходов.
+#[derive(Accounts)]
+pub struct CloseHistoryCommitment<'info> {
+    /// Завершённая игра; после закрытия коммитмента её можно закрыть prune_games.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Коммитмент игры, закрывается.
+    #[account(
+        mut,
+        close = payer,
+        constraint = history_commitment.game == game.key() @ ErrorCode::HistoryCommitmentMismatch,
+    )]
+    pub history_commitment: Box<Account<'info, HistoryCommitment>>,
+
+    /// CHECK: address constraint ensures this is history_commitment.payer; only credited
+    #[account(mut, address = history_commitment.payer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseHistoryCommitment>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    require!(game.status.is_over(), ErrorCode::GameNotFinished);
+    game.history_commitment = false;
+
+    verbose_msg!(
+        "close_history_commitment: game={}, moves={}",
+        game.key(),
+        ctx.accounts.history_commitment.leaf_count
+    );
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::*;
+
+/// Контекст для удаления сплитов выплат.
+#[derive(Accounts)]
+pub struct ClosePayoutSplits<'info> {
+    /// Сплиты игрока, закрываются.
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"payout_splits", player.key().as_ref()],
+        bump = payout_splits.bump,
+    )]
+    pub payout_splits: Account<'info, PayoutSplits>,
+
+    /// Игрок, получает ренту.
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ClosePayoutSplits>) -> Result<()> {
+    verbose_msg!("close_payout_splits: player={}", ctx.accounts.player.key());
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::*;
+
+/// Контекст для закрытия бюджета relayed_move.
+#[derive(Accounts)]
+pub struct CloseRelayBudget<'info> {
+    /// Бюджет; закрывается, остаток уходит игроку.
+    #[account(
+        mut,
+        seeds = [b"relay_budget", relay_budget.game.as_ref(), player.key().as_ref()],
+        bump = relay_budget.bump,
+        has_one = player,
+        close = player,
+    )]
+    pub relay_budget: Account<'info, RelayBudget>,
+
+    /// Владелец бюджета.
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseRelayBudget>) -> Result<()> {
+    verbose_msg!(
+        "close_relay_budget: game={}, player={}, lamports={}",
+        ctx.accounts.relay_budget.game,
+        ctx.accounts.player.key(),
+        ctx.accounts.relay_budget.to_account_info().lamports()
+    );
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::*;
+
+/// Контекст для закрытия сезона.
+#[derive(Accounts)]
+pub struct CloseSeason<'info> {
+    /// Глобальный конфиг.
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// Текущий сезон.
+    #[account(mut, seeds = [b"season".as_ref(), &config.current_season.to_le_bytes()], bump = season.bump)]
+    pub season: Account<'info, Season>,
+
+    /// Следующий сезон.
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Season::MAX_SIZE,
+        seeds = [b"season".as_ref(), &(config.current_season + 1).to_le_bytes()],
+        bump,
+    )]
+    pub next_season: Account<'info, Season>,
+
+    /// Администратор.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CloseSeason>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let season = &mut ctx.accounts.season;
+    let slot = Clock::get()?.slot;
+
+    require!(!season.closed, ErrorCode::SeasonClosed);
+
+    season.closed = true;
+    season.end_slot = slot;
+
+    config.current_season = config
+        .current_season
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let next = &mut ctx.accounts.next_season;
+    next.index = config.current_season;
+    next.start_slot = slot;
+    next.end_slot = 0;
+    next.closed = false;
+    next.standings = [SeasonStanding::default(); SEASON_STANDINGS_SIZE];
+    next.bump = ctx.bumps.next_season;
+
+    verbose_msg!(
+        "close_season: closed season={}, opened season={}",
+        season.index,
+        next.index
+    );
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use crate::*;
+
+/// Контекст для выплаты из страхового пула.
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct Compensate<'info> {
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// Застрявшая игра.
+    pub game: Account<'info, GameState>,
+
+    /// Пометка игры (integrity_check).
+    #[account(mut, seeds = [b"game_flag", game.key().as_ref()], bump = game_flag.bump)]
+    pub game_flag: Account<'info, GameFlag>,
+
+    /// Страховой пул.
+    #[account(mut, seeds = [b"insurance_pool"], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    /// CHECK: address constraint ensures this is the compensated player; only credited
+    #[account(mut, address = player)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Администратор.
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<Compensate>, player: Pubkey, amount: u64) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let side = if player == game.player1 {
+        0
+    } else if player == game.player2 {
+        1
+    } else {
+        return err!(ErrorCode::InvalidPlayer);
+    };
+    let cap = if side == 0 {
+        game.player1_deposit.checked_add(game.player1_fees_paid)
+    } else {
+        game.player2_deposit.checked_add(game.player2_fees_paid)
+    }
+    .ok_or(ErrorCode::MathOverflow)?;
+
+    let flag = &mut ctx.accounts.game_flag;
+    let compensated = flag.compensated[side]
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(compensated <= cap, ErrorCode::CompensationCapExceeded);
+
+    let pool = &mut ctx.accounts.insurance_pool;
+    let pool_info = pool.to_account_info();
+    let rent_min = Rent::get()?.minimum_balance(pool_info.data_len());
+    require!(
+        pool_info.lamports().saturating_sub(rent_min) >= amount,
+        ErrorCode::InsufficientInsurance
+    );
+
+    debit(&pool_info, amount)?;
+    credit(&ctx.accounts.recipient, amount)?;
+    flag.compensated[side] = compensated;
+    pool.total_compensated = pool
+        .total_compensated
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let pool_remaining = pool_info.lamports().saturating_sub(rent_min);
+    verbose_msg!(
+        "compensate: game_id={}, player={}, amount={}, compensated={}, cap={}, pool_remaining={}",
+        game.game_id,
+        player,
+        amount,
+        compensated,
+        cap,
+        pool_remaining
+    );
+    emit!(InsuranceCompensated {
+        game: game.key(),
+        player,
+        admin: ctx.accounts.admin.key(),
+        reason: flag.reason,
+        amount,
+        compensated_total: compensated,
+        cap,
+        pool_remaining,
+    });
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::*;
+
+/// Контекст для оспаривания заявки на победу.
+#[derive(Accounts)]
+pub struct ContestClaim<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Заявка на победу.
+    #[account(seeds = [b"win_claim", game.key().as_ref()], bump = win_claim.bump)]
+    pub win_claim: Account<'info, WinClaim>,
+
+    /// Соперник заявителя.
+    pub player: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ContestClaim>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let claim = &ctx.accounts.win_claim;
+    let player = ctx.accounts.player.key();
+    let current_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "contest_claim: game_id={}, player={}, claimant={}, deadline_slot={}",
+        game.game_id,
+        player,
+        claim.claimant,
+        claim.deadline_slot
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.win_claim_pending, ErrorCode::NoWinClaim);
+    require!(
+        player != claim.claimant && (player == game.player1 || player == game.player2),
+        ErrorCode::InvalidPlayer
+    );
+    require!(
+        current_slot <= claim.deadline_slot,
+        ErrorCode::ClaimWindowClosed
+    );
+
+    game.status = GameStatus::Disputed;
+    game.last_activity_slot = current_slot;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::*;
+
+/// Контекст для встречного предложения ставки.
+#[derive(Accounts)]
+pub struct CounterOffer<'info> {
+    /// Игра, ожидающая второго игрока.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Встречные условия; рента за счёт player2.
+    #[account(
+        init,
+        payer = player2,
+        space = 8 + CounterProposal::INIT_SPACE,
+        seeds = [b"counter_proposal", game.key().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, CounterProposal>,
+
+    /// Приглашённый второй игрок.
+    #[account(mut, address = game.player2 @ ErrorCode::InvalidPlayer2)]
+    pub player2: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(
+    ctx: Context<CounterOffer>,
+    new_stake_lamports: u64,
+    new_move_fee_lamports: u64,
+) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+    let current_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "counter_offer: game_id={}, player2={}, stake={}->{}, move_fee={}->{}",
+        game.game_id,
+        game.player2,
+        game.stake_lamports,
+        new_stake_lamports,
+        game.move_fee_lamports,
+        new_move_fee_lamports
+    );
+
+    require!(
+        game.status == GameStatus::WaitingForPlayer2,
+        ErrorCode::GameNotWaitingForPlayer2
+    );
+    require!(
+        game.join_deadline_slot == 0 || current_slot <= game.join_deadline_slot,
+        ErrorCode::JoinDeadlinePassed
+    );
+    require!(!game.counter_pending, ErrorCode::CounterOfferPending);
+    // Ставку тренировочной и турнирной игры задают не игроки.
+    require!(
+        !game.practice && game.tournament == Pubkey::default(),
+        ErrorCode::CounterOfferNotAllowed
+    );
+    require!(new_stake_lamports > 0, ErrorCode::ZeroStake);
+    require!(
+        new_move_fee_lamports <= new_stake_lamports,
+        ErrorCode::MoveFeeExceedsStake
+    );
+    // В игре с комиссией от банка или тающим банком фиксированную комиссию
+    // не предложить.
+    require!(
+        new_move_fee_lamports == 0 || (game.move_fee_bps == 0 && game.decay_bps == 0),
+        ErrorCode::ConflictingMoveFee
+    );
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.game = game.key();
+    proposal.proposer = game.player2;
+    proposal.stake_lamports = new_stake_lamports;
+    proposal.move_fee_lamports = new_move_fee_lamports;
+    proposal.bump = ctx.bumps.proposal;
+    game.counter_pending = true;
+    game.last_activity_slot = current_slot;
+
+    emit!(CounterOffered {
+        game: game.key(),
+        player2: game.player2,
+        stake_lamports: new_stake_lamports,
+        move_fee_lamports: new_move_fee_lamports,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::*;
+
+/// Контекст для создания турнира.
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
+    /// Аккаунт турнира, PDA от организатора и tournament_id.
+    #[account(
+        init,
+        payer = organizer,
+        space = 8 + Tournament::MAX_SIZE,
+        seeds = [b"tournament", organizer.key().as_ref(), &tournament_id.to_le_bytes()],
+        bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    /// Организатор, платит за создание аккаунта.
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(
+    ctx: Context<CreateTournament>,
+    tournament_id: u64,
+    entry_fee: u64,
+    max_players: u8,
+    mode: TournamentMode,
+    swiss_rounds: u8,
+) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    verbose_msg!(
+        "create_tournament: organizer={}, tournament_id={}, entry_fee={}, max_players={}, mode={:?}, swiss_rounds={}",
+        ctx.accounts.organizer.key(),
+        tournament_id,
+        entry_fee,
+        max_players,
+        mode,
+        swiss_rounds
+    );
+
+    require!(
+        max_players >= 2 && max_players as usize <= TOURNAMENT_MAX_PLAYERS,
+        ErrorCode::InvalidTournamentSize
+    );
+    match mode {
+        TournamentMode::Knockout => {
+            require!(
+                max_players.is_power_of_two(),
+                ErrorCode::InvalidTournamentSize
+            );
+        }
+        TournamentMode::Swiss => {
+            require!(
+                swiss_rounds >= 1 && swiss_rounds < max_players,
+                ErrorCode::InvalidSwissRounds
+            );
+        }
+    }
+
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.organizer = ctx.accounts.organizer.key();
+    tournament.tournament_id = tournament_id;
+    tournament.entry_fee = entry_fee;
+    tournament.max_players = max_players;
+    tournament.player_count = 0;
+    tournament.players = [Pubkey::default(); TOURNAMENT_MAX_PLAYERS];
+    tournament.status = TournamentStatus::Registration;
+    tournament.round = 0;
+    tournament.bracket = [Pubkey::default(); TOURNAMENT_MAX_PLAYERS];
+    tournament.bracket_size = 0;
+    tournament.round_winners = [Pubkey::default(); TOURNAMENT_MAX_PLAYERS / 2];
+    tournament.prize_pool = 0;
+    tournament.champion = Pubkey::default();
+    tournament.bump = ctx.bumps.tournament;
+    tournament.mode = mode;
+    tournament.swiss_rounds = if mode == TournamentMode::Swiss {
+        swiss_rounds
+    } else {
+        0
+    };
+    tournament.scores = [0; TOURNAMENT_MAX_PLAYERS];
+    tournament.opponents = [0; TOURNAMENT_MAX_PLAYERS];
+    tournament.pairings = [SWISS_BYE; TOURNAMENT_MAX_PLAYERS];
+    tournament.reported = 0;
+    tournament.had_bye = 0;
+    tournament.buchholz = [0; TOURNAMENT_MAX_PLAYERS];
+    tournament.standings = [0; TOURNAMENT_MAX_PLAYERS];
+
+    Ok(())
+}
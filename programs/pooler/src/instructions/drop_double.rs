@@ -0,0 +1,155 @@
+use crate::*;
+
+/// Контекст для отказа от удвоения.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DropDouble<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Игрок, отказывающийся от удвоения.
+    pub player: Signer<'info>,
+
+    /// CHECK: key is validated against the doubler in the handler; balance only credited
+    #[account(mut, constraint = doubler.key() != game.key() @ ErrorCode::PayoutAliasesGame)]
+    pub doubler: AccountInfo<'info>,
+
+    /// Глобальный конфиг (пауза и рейтинг).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Текущий сезон; обязателен при config.rating_enforced.
+    #[account(
+        mut,
+        seeds = [b"season".as_ref(), &config.current_season.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+
+    /// Статистика первого игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player1.as_ref()], bump = player1_stats.bump)]
+    pub player1_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Статистика второго игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player2.as_ref()], bump = player2_stats.bump)]
+    pub player2_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Эскроу-агент игры; обязателен, если game.escrow_agent задан.
+    pub escrow_agent: Option<Signer<'info>>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Хранилище комиссии протокола; обязательно при config.protocol_fee_bps > 0.
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// CHECK: address constraint ensures this is the incinerator; balance only credited.
+    /// Required when config.burn_bps > 0.
+    #[account(mut, address = INCINERATOR)]
+    pub incinerator: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA [b"payout_splits", doubler] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", doubler.key().as_ref()], bump)]
+    pub doubler_splits: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DropDouble<'info>>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    require!(ctx.accounts.game.staked_lamports == 0, ErrorCode::PotStaked);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "drop_double: game_id={}, cube_value={}, current_turn={}",
+        game.game_id,
+        game.cube_value,
+        game.current_turn
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.double_pending, ErrorCode::NoDoublePending);
+    game.check_escrow_agent(ctx.accounts.escrow_agent.as_ref())?;
+
+    let (doubler, taker) = match game.current_turn {
+        1 => (game.player1, game.player2),
+        2 => (game.player2, game.player1),
+        _ => return Err(ErrorCode::InvalidCurrentTurn.into()),
+    };
+    require_keys_eq!(ctx.accounts.player.key(), taker, ErrorCode::InvalidPlayer);
+    require_keys_eq!(
+        ctx.accounts.doubler.key(),
+        doubler,
+        ErrorCode::InvalidWinner
+    );
+
+    let pot = game.pot_lamports;
+    // Комиссия протокола удерживается из банка, как в finish_game.
+    let fee = if game.practice {
+        0
+    } else {
+        protocol_fee(pot, ctx.accounts.config.protocol_fee_bps)
+    };
+    let winnings = pot - fee;
+    verbose_msg!(
+        "drop_double: paying pot={} (fee={}) to doubler={}",
+        pot,
+        fee,
+        doubler
+    );
+
+    let burned = collect_protocol_fee(
+        game,
+        ctx.accounts.fee_vault.as_mut(),
+        ctx.accounts.incinerator.as_ref().map(|a| a.as_ref()),
+        fee,
+        ctx.accounts.config.burn_bps,
+    )?;
+    if fee > 0 {
+        emit_cpi!(ProtocolFeeCollected {
+            game: game.key(),
+            fee,
+            burned,
+        });
+    }
+    pay_out(
+        &game.to_account_info(),
+        ctx.accounts.doubler.to_account_info(),
+        ctx.accounts.doubler_splits.as_ref(),
+        &mut ctx.remaining_accounts.iter(),
+        winnings,
+    )?;
+
+    game.pot_lamports = 0;
+    game.tips_lamports = 0;
+    game.double_pending = false;
+    game.finish(FinishReason::DoubleDropped, Clock::get()?.slot);
+    game.winner = doubler;
+    emit_cpi!(game_finished_event(game, winnings));
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Finished,
+        game.winner,
+        winnings,
+    )?;
+
+    record_game_rating(
+        game,
+        doubler,
+        &ctx.accounts.config,
+        ctx.accounts.season.as_mut(),
+        ctx.accounts.player1_stats.as_mut(),
+        ctx.accounts.player2_stats.as_mut(),
+    )?;
+
+    verbose_msg!(
+        "drop_double: completed, game_id={}, winner={}",
+        game.game_id,
+        game.winner
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
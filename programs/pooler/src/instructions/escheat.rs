@@ -0,0 +1,117 @@
+use crate::*;
+
+/// Контекст для escheat заброшенной игры.
+#[derive(Accounts)]
+pub struct Escheat<'info> {
+    /// Глобальный конфиг (казна и порог).
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// Заброшенная игра, закрывается на treasury.
+    #[account(mut, close = treasury)]
+    pub game: Account<'info, GameState>,
+
+    /// Составы команд (обязателен для игр 2 на 2).
+    #[account(seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Option<Account<'info, TeamState>>,
+
+    /// CHECK: address constraint ensures this is config.treasury; only credited
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: address constraint ensures this is game.player1; receives p1_take_reserve
+    #[account(mut, address = game.player1)]
+    pub player1: UncheckedAccount<'info>,
+
+    /// CHECK: address constraint ensures this is game.player2; receives p2_take_reserve
+    #[account(mut, address = game.player2)]
+    pub player2: UncheckedAccount<'info>,
+
+    /// Администратор.
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<Escheat>) -> Result<()> {
+    require!(ctx.accounts.game.staked_lamports == 0, ErrorCode::PotStaked);
+    let config = &ctx.accounts.config;
+    let game = &ctx.accounts.game;
+
+    require!(
+        config.treasury != Pubkey::default(),
+        ErrorCode::TreasuryNotSet
+    );
+    require!(
+        game.status.is_over()
+            && !game.win_claim_pending
+            && !game.move_history
+            && !game.history_commitment
+            && game.unsettled_side_bets == 0,
+        ErrorCode::GameNotEscheatable
+    );
+    if game.team_game {
+        let team = ctx
+            .accounts
+            .team
+            .as_ref()
+            .ok_or(ErrorCode::MissingTeamAccount)?;
+        require!(!team.has_unclaimed_shares(), ErrorCode::GameNotEscheatable);
+    }
+
+    let current_slot = Clock::get()?.slot;
+    let idle_slots = current_slot
+        .checked_sub(game.last_activity_slot)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let threshold = config.escheat_threshold();
+    require!(idle_slots >= threshold, ErrorCode::EscheatTooEarly);
+
+    // Резервы автовзятий лежат вне банка и принадлежат игрокам: они
+    // возвращаются до закрытия, в казну уходят только банк и рента.
+    let (p1_reserve, p2_reserve) = (game.p1_take_reserve, game.p2_take_reserve);
+    for (reserve, player) in [
+        (p1_reserve, &ctx.accounts.player1),
+        (p2_reserve, &ctx.accounts.player2),
+    ] {
+        if reserve > 0 {
+            debit(&game.to_account_info(), reserve)?;
+            credit(&player.to_account_info(), reserve)?;
+        }
+    }
+    let game = &mut ctx.accounts.game;
+    game.p1_take_reserve = 0;
+    game.p2_take_reserve = 0;
+
+    let total_lamports = game.to_account_info().lamports();
+    let pot_lamports = game.pot_lamports;
+    let rent_lamports = total_lamports
+        .checked_sub(pot_lamports)
+        .ok_or(ErrorCode::InconsistentPot)?;
+
+    verbose_msg!(
+        "escheat: game_id={}, idle_slots={}, threshold={}, pot={}, rent={}, reserves={}/{}, treasury={}",
+        game.game_id,
+        idle_slots,
+        threshold,
+        pot_lamports,
+        rent_lamports,
+        p1_reserve,
+        p2_reserve,
+        config.treasury
+    );
+
+    // Аккаунт игры закрывается на treasury (close = treasury) вместе со всем
+    // остатком: банком и рентой.
+    emit!(GameEscheated {
+        game: game.key(),
+        game_id: game.game_id,
+        player1: game.player1,
+        player2: game.player2,
+        treasury: config.treasury,
+        idle_slots,
+        pot_lamports,
+        rent_lamports,
+        total_lamports,
+    });
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use crate::*;
+
+/// Контекст для исполнения решения арбитра.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteResolution<'info> {
+    /// Спорная игра с решением арбитра.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Глобальный конфиг (пауза и рейтинг).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Текущий сезон; обязателен при config.rating_enforced.
+    #[account(
+        mut,
+        seeds = [b"season".as_ref(), &config.current_season.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+
+    /// Статистика первого игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player1.as_ref()], bump = player1_stats.bump)]
+    pub player1_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Статистика второго игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player2.as_ref()], bump = player2_stats.bump)]
+    pub player2_stats: Option<Account<'info, PlayerStats>>,
+
+    /// CHECK: address constraint ensures this is game.player1; balance only credited
+    #[account(mut, address = game.player1)]
+    pub player1: AccountInfo<'info>,
+
+    /// CHECK: address constraint ensures this is game.player2; balance only credited
+    #[account(
+        mut,
+        address = game.player2,
+        constraint = player2.key() != player1.key() @ ErrorCode::DuplicatePlayerAccounts,
+    )]
+    pub player2: AccountInfo<'info>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; required only for bot games.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: Option<UncheckedAccount<'info>>,
+
+    /// Составы команд; обязателен для командных игр.
+    #[account(mut, seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Option<Account<'info, TeamState>>,
+
+    /// Заявка на победу; обязательна, если спор начат через contest_claim.
+    #[account(mut, seeds = [b"win_claim", game.key().as_ref()], bump = win_claim.bump)]
+    pub win_claim: Option<Account<'info, WinClaim>>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Хранилище комиссии протокола; обязательно при config.protocol_fee_bps > 0.
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// CHECK: address constraint ensures this is the incinerator; balance only credited.
+    /// Required when config.burn_bps > 0.
+    #[account(mut, address = INCINERATOR)]
+    pub incinerator: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA [b"payout_splits", game.player1] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player1.as_ref()], bump)]
+    pub player1_splits: UncheckedAccount<'info>,
+
+    /// CHECK: PDA [b"payout_splits", game.player2] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player2.as_ref()], bump)]
+    pub player2_splits: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteResolution<'info>>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    require!(ctx.accounts.game.staked_lamports == 0, ErrorCode::PotStaked);
+    let game = &mut ctx.accounts.game;
+    let current_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "execute_resolution: game_id={}, resolution_winner={}, appeal_deadline_slot={}, current_slot={}",
+        game.game_id,
+        game.resolution_winner,
+        game.appeal_deadline_slot,
+        current_slot
+    );
+
+    require!(
+        game.status == GameStatus::Disputed,
+        ErrorCode::GameNotDisputed
+    );
+    require!(game.resolution_pending(), ErrorCode::NoPendingResolution);
+    require_ctx!(
+        current_slot > game.appeal_deadline_slot,
+        ErrorCode::AppealWindowOpen,
+        Slot {
+            expected: game.appeal_deadline_slot.saturating_add(1),
+            actual: current_slot,
+        }
+    );
+    let winner = game.player_key(game.resolution_winner);
+
+    let pot = game.pot_lamports;
+    let fee = if game.practice {
+        0
+    } else {
+        protocol_fee(pot, ctx.accounts.config.protocol_fee_bps)
+    };
+    let winnings = pot - fee;
+    let winner_info = if winner == game.player1 {
+        team_payee(
+            game,
+            1,
+            ctx.accounts.player1.to_account_info(),
+            ctx.accounts.team.as_mut(),
+            winnings,
+        )?
+    } else {
+        team_payee(
+            game,
+            2,
+            player2_funds(
+                game,
+                ctx.accounts.player2.to_account_info(),
+                ctx.accounts.bot_vault.as_ref(),
+            )?,
+            ctx.accounts.team.as_mut(),
+            winnings,
+        )?
+    };
+    let burned = collect_protocol_fee(
+        game,
+        ctx.accounts.fee_vault.as_mut(),
+        ctx.accounts.incinerator.as_ref().map(|a| a.as_ref()),
+        fee,
+        ctx.accounts.config.burn_bps,
+    )?;
+    if fee > 0 {
+        emit_cpi!(ProtocolFeeCollected {
+            game: game.key(),
+            fee,
+            burned,
+        });
+    }
+    let winner_splits = if winner == game.player1 {
+        ctx.accounts.player1_splits.as_ref()
+    } else {
+        ctx.accounts.player2_splits.as_ref()
+    };
+    pay_out(
+        &game.to_account_info(),
+        winner_info,
+        winner_splits,
+        &mut ctx.remaining_accounts.iter(),
+        winnings,
+    )?;
+
+    if game.win_claim_pending {
+        let claim = ctx
+            .accounts
+            .win_claim
+            .as_ref()
+            .ok_or(ErrorCode::NoWinClaim)?;
+        let claimant_info = if claim.claimant == game.player1 {
+            ctx.accounts.player1.to_account_info()
+        } else {
+            ctx.accounts.player2.to_account_info()
+        };
+        if winner != claim.claimant {
+            let bond_to = if winner == game.player1 {
+                ctx.accounts.player1.to_account_info()
+            } else {
+                player2_funds(
+                    game,
+                    ctx.accounts.player2.to_account_info(),
+                    ctx.accounts.bot_vault.as_ref(),
+                )?
+            };
+            debit(&claim.to_account_info(), claim.bond_lamports)?;
+            credit(&bond_to, claim.bond_lamports)?;
+        }
+        claim.close(claimant_info)?;
+        game.win_claim_pending = false;
+    }
+
+    game.pot_lamports = 0;
+    game.tips_lamports = 0;
+    game.move_pending = false;
+    game.double_pending = false;
+    game.resolution_winner = 0;
+    game.finish(FinishReason::Arbiter, current_slot);
+    game.winner = winner;
+    emit_cpi!(game_finished_event(game, winnings));
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Finished,
+        game.winner,
+        winnings,
+    )?;
+
+    record_game_rating(
+        game,
+        winner,
+        &ctx.accounts.config,
+        ctx.accounts.season.as_mut(),
+        ctx.accounts.player1_stats.as_mut(),
+        ctx.accounts.player2_stats.as_mut(),
+    )?;
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use crate::*;
+
+/// Контекст для завершения заявки на победу.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeClaim<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Заявка; закрывается, рента уходит заявителю (залог — см. обработчик).
+    #[account(
+        mut,
+        seeds = [b"win_claim", game.key().as_ref()],
+        bump = win_claim.bump,
+        close = claimant,
+    )]
+    pub win_claim: Account<'info, WinClaim>,
+
+    /// CHECK: address constraint ensures this is win_claim.claimant; balance only credited
+    #[account(
+        mut,
+        address = win_claim.claimant,
+        constraint = claimant.key() != game.key() @ ErrorCode::PayoutAliasesGame,
+    )]
+    pub claimant: UncheckedAccount<'info>,
+
+    /// Глобальный конфиг (пауза и рейтинг).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Текущий сезон; обязателен при config.rating_enforced.
+    #[account(
+        mut,
+        seeds = [b"season".as_ref(), &config.current_season.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+
+    /// Статистика первого игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player1.as_ref()], bump = player1_stats.bump)]
+    pub player1_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Статистика второго игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player2.as_ref()], bump = player2_stats.bump)]
+    pub player2_stats: Option<Account<'info, PlayerStats>>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; required only for bot games.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: Option<UncheckedAccount<'info>>,
+
+    /// Составы команд; обязателен для командных игр.
+    #[account(mut, seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Option<Account<'info, TeamState>>,
+
+    /// Любой плательщик комиссии транзакции.
+    pub payer: Signer<'info>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Хранилище комиссии протокола; обязательно при config.protocol_fee_bps > 0.
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// CHECK: address constraint ensures this is the incinerator; balance only credited.
+    /// Required when config.burn_bps > 0.
+    #[account(mut, address = INCINERATOR)]
+    pub incinerator: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: address constraint ensures this is game.winner; balance only credited.
+    /// Required when the game already finished with a winner other than the claimant.
+    #[account(mut, address = game.winner)]
+    pub winner: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA [b"payout_splits", win_claim.claimant] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", win_claim.claimant.as_ref()], bump)]
+    pub claimant_splits: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeClaim<'info>>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    require!(ctx.accounts.game.staked_lamports == 0, ErrorCode::PotStaked);
+    let game = &mut ctx.accounts.game;
+    let claim = &ctx.accounts.win_claim;
+    let current_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "finalize_claim: game_id={}, status={:?}, claimant={}, deadline_slot={}, current_slot={}",
+        game.game_id,
+        game.status,
+        claim.claimant,
+        claim.deadline_slot,
+        current_slot
+    );
+
+    require!(game.win_claim_pending, ErrorCode::NoWinClaim);
+    match game.status {
+        GameStatus::Active => {
+            require!(
+                current_slot > claim.deadline_slot,
+                ErrorCode::ClaimWindowOpen
+            );
+            let pot = game.pot_lamports;
+            let fee = if game.practice {
+                0
+            } else {
+                protocol_fee(pot, ctx.accounts.config.protocol_fee_bps)
+            };
+            let winnings = pot - fee;
+            let claimant_info = if claim.claimant == game.player1 {
+                team_payee(
+                    game,
+                    1,
+                    ctx.accounts.claimant.to_account_info(),
+                    ctx.accounts.team.as_mut(),
+                    winnings,
+                )?
+            } else {
+                team_payee(
+                    game,
+                    2,
+                    player2_funds(
+                        game,
+                        ctx.accounts.claimant.to_account_info(),
+                        ctx.accounts.bot_vault.as_ref(),
+                    )?,
+                    ctx.accounts.team.as_mut(),
+                    winnings,
+                )?
+            };
+            let burned = collect_protocol_fee(
+                game,
+                ctx.accounts.fee_vault.as_mut(),
+                ctx.accounts.incinerator.as_ref().map(|a| a.as_ref()),
+                fee,
+                ctx.accounts.config.burn_bps,
+            )?;
+            if fee > 0 {
+                emit_cpi!(ProtocolFeeCollected {
+                    game: game.key(),
+                    fee,
+                    burned,
+                });
+            }
+            pay_out(
+                &game.to_account_info(),
+                claimant_info,
+                ctx.accounts.claimant_splits.as_ref(),
+                &mut ctx.remaining_accounts.iter(),
+                winnings,
+            )?;
+
+            game.set_board_points(&claim.final_board)?;
+            game.pot_lamports = 0;
+            game.tips_lamports = 0;
+            game.double_pending = false;
+            game.finish(FinishReason::Claim, current_slot);
+            game.winner = claim.claimant;
+            emit_cpi!(game_finished_event(game, winnings));
+            record_event(
+                ctx.accounts.event_buffer.as_deref_mut(),
+                BufferedEventKind::Finished,
+                game.winner,
+                winnings,
+            )?;
+
+            record_game_rating(
+                game,
+                game.winner,
+                &ctx.accounts.config,
+                ctx.accounts.season.as_mut(),
+                ctx.accounts.player1_stats.as_mut(),
+                ctx.accounts.player2_stats.as_mut(),
+            )?;
+        }
+        GameStatus::Finished => {
+            // Залог возвращается, только если заявитель и победил (или
+            // победителя нет — возврат, договорённость); иначе он уходит
+            // победителю, как в execute_resolution.
+            if game.winner == claim.claimant || game.winner == Pubkey::default() {
+                msg!("finalize_claim: game already finished, returning bond");
+            } else {
+                let winner = ctx
+                    .accounts
+                    .winner
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingWinnerAccount)?
+                    .to_account_info();
+                let bond_to = if game.winner == game.player2 {
+                    player2_funds(game, winner, ctx.accounts.bot_vault.as_ref())?
+                } else {
+                    winner
+                };
+                debit(&claim.to_account_info(), claim.bond_lamports)?;
+                credit(&bond_to, claim.bond_lamports)?;
+                msg!("finalize_claim: game already finished, bond forfeited to the winner");
+            }
+        }
+        _ => return Err(ErrorCode::GameNotFinished.into()),
+    }
+
+    // Рента WinClaim и невыплаченный залог возвращаются заявителю (close = claimant).
+    game.win_claim_pending = false;
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use crate::*;
+
+/// Контекст для расчёта по терминальной доске.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeFromBoard<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// CHECK: address constraint ensures this is game.player1; balance only credited
+    #[account(mut, address = game.player1)]
+    pub player1: UncheckedAccount<'info>,
+
+    /// CHECK: address constraint ensures this is game.player2; balance only credited
+    #[account(
+        mut,
+        address = game.player2,
+        constraint = player2.key() != player1.key() @ ErrorCode::DuplicatePlayerAccounts,
+    )]
+    pub player2: UncheckedAccount<'info>,
+
+    /// Глобальный конфиг (пауза и рейтинг).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Текущий сезон; обязателен при config.rating_enforced.
+    #[account(
+        mut,
+        seeds = [b"season".as_ref(), &config.current_season.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+
+    /// Статистика первого игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player1.as_ref()], bump = player1_stats.bump)]
+    pub player1_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Статистика второго игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player2.as_ref()], bump = player2_stats.bump)]
+    pub player2_stats: Option<Account<'info, PlayerStats>>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; required only for bot games.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: Option<UncheckedAccount<'info>>,
+
+    /// Составы команд; обязателен для командных игр.
+    #[account(mut, seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Option<Account<'info, TeamState>>,
+
+    /// Любой плательщик комиссии транзакции.
+    pub payer: Signer<'info>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Хранилище комиссии протокола; обязательно при config.protocol_fee_bps > 0.
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// CHECK: address constraint ensures this is the incinerator; balance only credited.
+    /// Required when config.burn_bps > 0.
+    #[account(mut, address = INCINERATOR)]
+    pub incinerator: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA [b"payout_splits", game.player1] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player1.as_ref()], bump)]
+    pub player1_splits: UncheckedAccount<'info>,
+
+    /// CHECK: PDA [b"payout_splits", game.player2] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player2.as_ref()], bump)]
+    pub player2_splits: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeFromBoard<'info>>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    require!(ctx.accounts.game.staked_lamports == 0, ErrorCode::PotStaked);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "finalize_from_board: game_id={}, status={:?}, move_index={}",
+        game.game_id,
+        game.status,
+        game.move_index
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(!game.move_pending, ErrorCode::MovePending);
+    require!(game.board_from_start, ErrorCode::BoardNotTerminal);
+
+    let winner = match backgammon_core::is_terminal(&game.board_points()?) {
+        Some(1) => game.player1,
+        Some(_) => game.player2,
+        None => return Err(ErrorCode::BoardNotTerminal.into()),
+    };
+
+    let pot = game.pot_lamports;
+    let fee = if game.practice {
+        0
+    } else {
+        protocol_fee(pot, ctx.accounts.config.protocol_fee_bps)
+    };
+    let winnings = pot - fee;
+    let winner_info = if winner == game.player1 {
+        team_payee(
+            game,
+            1,
+            ctx.accounts.player1.to_account_info(),
+            ctx.accounts.team.as_mut(),
+            winnings,
+        )?
+    } else {
+        team_payee(
+            game,
+            2,
+            player2_funds(
+                game,
+                ctx.accounts.player2.to_account_info(),
+                ctx.accounts.bot_vault.as_ref(),
+            )?,
+            ctx.accounts.team.as_mut(),
+            winnings,
+        )?
+    };
+    let burned = collect_protocol_fee(
+        game,
+        ctx.accounts.fee_vault.as_mut(),
+        ctx.accounts.incinerator.as_ref().map(|a| a.as_ref()),
+        fee,
+        ctx.accounts.config.burn_bps,
+    )?;
+    if fee > 0 {
+        emit_cpi!(ProtocolFeeCollected {
+            game: game.key(),
+            fee,
+            burned,
+        });
+    }
+    if !game.practice {
+        let winner_splits = if winner == game.player1 {
+            ctx.accounts.player1_splits.as_ref()
+        } else {
+            ctx.accounts.player2_splits.as_ref()
+        };
+        pay_out(
+            &game.to_account_info(),
+            winner_info,
+            winner_splits,
+            &mut ctx.remaining_accounts.iter(),
+            winnings,
+        )?;
+    }
+
+    game.pot_lamports = 0;
+    game.tips_lamports = 0;
+    game.double_pending = false;
+    game.finish(FinishReason::Board, Clock::get()?.slot);
+    game.winner = winner;
+    emit_cpi!(game_finished_event(game, winnings));
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Finished,
+        game.winner,
+        winnings,
+    )?;
+
+    record_game_rating(
+        game,
+        winner,
+        &ctx.accounts.config,
+        ctx.accounts.season.as_mut(),
+        ctx.accounts.player1_stats.as_mut(),
+        ctx.accounts.player2_stats.as_mut(),
+    )?;
+
+    verbose_msg!(
+        "finalize_from_board: completed, game_id={}, winner={}, pot={}",
+        game.game_id,
+        winner,
+        pot
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::*;
+
+/// Контекст для автоподтверждения хода по сроку.
+#[derive(Accounts)]
+pub struct FinalizePending<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Любой плательщик комиссии транзакции.
+    pub payer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FinalizePending>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let current_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "finalize_pending: game_id={}, current_slot={}, deadline_slot={}",
+        game.game_id,
+        current_slot,
+        game.pending_deadline_slot
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.move_pending, ErrorCode::NoMovePending);
+    require_ctx!(
+        current_slot > game.pending_deadline_slot,
+        ErrorCode::TimeoutNotReached,
+        Slot {
+            expected: game.pending_deadline_slot.saturating_add(1),
+            actual: current_slot,
+        }
+    );
+
+    game.finalize_pending_move(current_slot)
+}
@@ -0,0 +1,48 @@
+use crate::*;
+
+pub fn handler(ctx: Context<TournamentOrganizer>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+
+    require!(
+        tournament.status == TournamentStatus::Running,
+        ErrorCode::TournamentNotRunning
+    );
+    require!(
+        tournament.mode == TournamentMode::Swiss,
+        ErrorCode::WrongTournamentMode
+    );
+    let count = tournament.player_count as usize;
+    let all_mask: u32 = if count == 32 {
+        u32::MAX
+    } else {
+        (1u32 << count) - 1
+    };
+    require!(
+        tournament.round == tournament.swiss_rounds && tournament.reported & all_mask == all_mask,
+        ErrorCode::RoundNotComplete
+    );
+
+    let mut buchholz = [0u16; TOURNAMENT_MAX_PLAYERS];
+    for (i, value) in buchholz.iter_mut().enumerate().take(count) {
+        for j in 0..count {
+            if tournament.opponents[i] & (1 << j) != 0 {
+                *value += tournament.scores[j] as u16;
+            }
+        }
+    }
+
+    let order = swiss_order(&tournament.scores, &buchholz, count);
+    tournament.buchholz = buchholz;
+    tournament.standings = order;
+    tournament.champion = tournament.players[order[0] as usize];
+    tournament.status = TournamentStatus::Finished;
+
+    verbose_msg!(
+        "finalize_swiss: champion={}, score={}, buchholz={}",
+        tournament.champion,
+        tournament.scores[order[0] as usize],
+        buchholz[order[0] as usize]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,282 @@
+use crate::*;
+
+/// Контекст для завершения игры и вывода банка победителю.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinishGame<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок, должен совпадать с game.player1.
+    #[account(mut, address = game.player1 @ ErrorCode::InvalidPlayer1)]
+    pub player1: Signer<'info>,
+
+    /// Второй игрок, должен совпадать с game.player2.
+    #[account(
+        mut,
+        address = game.player2 @ ErrorCode::InvalidPlayer2,
+        constraint = player2.key() != player1.key() @ ErrorCode::DuplicatePlayerAccounts,
+    )]
+    pub player2: Signer<'info>,
+
+    /// Системная программа Solana, нужна для transfer через CPI.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (пауза и рейтинг).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Текущий сезон; обязателен при config.rating_enforced.
+    #[account(
+        mut,
+        seeds = [b"season".as_ref(), &config.current_season.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+
+    /// Статистика первого игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player1.as_ref()], bump = player1_stats.bump)]
+    pub player1_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Статистика второго игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player2.as_ref()], bump = player2_stats.bump)]
+    pub player2_stats: Option<Account<'info, PlayerStats>>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; required only for bot games.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: Option<UncheckedAccount<'info>>,
+
+    /// Эскроу-агент игры; обязателен, если game.escrow_agent задан.
+    pub escrow_agent: Option<Signer<'info>>,
+
+    /// Составы команд; обязателен для командных игр.
+    #[account(mut, seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Option<Account<'info, TeamState>>,
+
+    /// CHECK: PDA [b"payout_splits", game.player1] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player1.as_ref()], bump)]
+    pub player1_splits: UncheckedAccount<'info>,
+
+    /// CHECK: PDA [b"payout_splits", game.player2] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player2.as_ref()], bump)]
+    pub player2_splits: UncheckedAccount<'info>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Хранилище комиссии протокола; обязательно при config.protocol_fee_bps > 0.
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// CHECK: address constraint ensures this is the incinerator; balance only credited.
+    /// Required when config.burn_bps > 0.
+    #[account(mut, address = INCINERATOR)]
+    pub incinerator: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinishGame<'info>>,
+    winner: Pubkey,
+    result_multiplier: u8,
+) -> Result<()> {
+    require_cpi_policy(instruction::FinishGame::DISCRIMINATOR)?;
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    require!(ctx.accounts.game.staked_lamports == 0, ErrorCode::PotStaked);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "finish_game: game_id={}, status={:?}, winner_param={}, result_multiplier={}, game.player1={}, game.player2={}",
+        game.game_id,
+        game.status,
+        winner,
+        result_multiplier,
+        game.player1,
+        game.player2
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(
+        (1..=3).contains(&result_multiplier),
+        ErrorCode::InvalidResultMultiplier
+    );
+    game.check_escrow_agent(ctx.accounts.escrow_agent.as_ref())?;
+
+    // Победителем может быть только один из двух игроков.
+    require!(
+        winner == game.player1 || winner == game.player2,
+        ErrorCode::InvalidWinner
+    );
+
+    let pot = game.pot_lamports;
+
+    let multiplier = game.effective_multiplier(result_multiplier);
+
+    // Надбавка за марс/кокс: проигравший платит победителю сверх банка.
+    let bonus = if multiplier > 1 {
+        game.stake_lamports
+            .checked_mul(game.cube_value as u64)
+            .and_then(|v| v.checked_mul((multiplier - 1) as u64))
+            .ok_or(ErrorCode::MathOverflow)?
+    } else {
+        0
+    };
+    let winnings = pot.checked_add(bonus).ok_or(ErrorCode::MathOverflow)?;
+
+    // Комиссия протокола удерживается из выигрыша; в тренировочной игре
+    // выигрыша нет.
+    let fee = if game.practice {
+        0
+    } else {
+        protocol_fee(winnings, ctx.accounts.config.protocol_fee_bps)
+    };
+    let winnings = winnings - fee;
+
+    // Определяем, чей аккаунт победителя пополнить
+    let (winner_account_info, winner_label) = if winner == game.player1 {
+        verbose_msg!(
+            "finish_game: winner is player1={}, pot_lamports={}",
+            game.player1,
+            pot
+        );
+        (
+            team_payee(
+                game,
+                1,
+                ctx.accounts.player1.to_account_info(),
+                ctx.accounts.team.as_mut(),
+                winnings,
+            )?,
+            "player1",
+        )
+    } else {
+        verbose_msg!(
+            "finish_game: winner is player2={}, pot_lamports={}",
+            game.player2,
+            pot
+        );
+        (
+            team_payee(
+                game,
+                2,
+                player2_funds(
+                    game,
+                    ctx.accounts.player2.to_account_info(),
+                    ctx.accounts.bot_vault.as_ref(),
+                )?,
+                ctx.accounts.team.as_mut(),
+                winnings,
+            )?,
+            "player2",
+        )
+    };
+
+    // Надбавку переводит основной ключ проигравшей стороны на аккаунт игры,
+    // дальше она выплачивается вместе с банком.
+    if bonus > 0 {
+        verbose_msg!(
+            "finish_game: multiplier={}, loser pays bonus={}",
+            multiplier,
+            bonus
+        );
+
+        if winner == game.player1 && game.bot_game {
+            // Проигравший бот платит надбавку из bot_vault.
+            let bot_vault = ctx
+                .accounts
+                .bot_vault
+                .as_ref()
+                .ok_or(ErrorCode::MissingBotVault)?;
+            transfer_from_bot_vault(
+                ctx.accounts.system_program.to_account_info(),
+                bot_vault.to_account_info(),
+                game.to_account_info(),
+                ctx.bumps.bot_vault.ok_or(ErrorCode::MissingBotVault)?,
+                bonus,
+            )?;
+        } else {
+            let loser_account_info = if winner == game.player1 {
+                ctx.accounts.player2.to_account_info()
+            } else {
+                ctx.accounts.player1.to_account_info()
+            };
+
+            let cpi_accounts = system_program::Transfer {
+                from: loser_account_info,
+                to: game.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, bonus)?;
+        }
+    }
+
+    // Комиссию снимаем после надбавки: она считается и с неё, а банка без
+    // надбавки может не хватить.
+    let burned = collect_protocol_fee(
+        game,
+        ctx.accounts.fee_vault.as_mut(),
+        ctx.accounts.incinerator.as_ref().map(|a| a.as_ref()),
+        fee,
+        ctx.accounts.config.burn_bps,
+    )?;
+    if fee > 0 {
+        emit_cpi!(ProtocolFeeCollected {
+            game: game.key(),
+            fee,
+            burned,
+        });
+    }
+
+    // Переводим выигрыш победителю напрямую, т.к. аккаунт игры принадлежит нашей программе.
+    // В тренировочной игре банк пуст, записывается только победитель.
+    if !game.practice {
+        let winner_splits = if winner == game.player1 {
+            ctx.accounts.player1_splits.as_ref()
+        } else {
+            ctx.accounts.player2_splits.as_ref()
+        };
+        pay_out(
+            &game.to_account_info(),
+            winner_account_info,
+            winner_splits,
+            &mut ctx.remaining_accounts.iter(),
+            winnings,
+        )?;
+    }
+
+    game.pot_lamports = 0;
+    game.tips_lamports = 0;
+    game.finish(FinishReason::Win, Clock::get()?.slot);
+    game.winner = winner;
+    emit_cpi!(game_finished_event(game, winnings));
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Finished,
+        game.winner,
+        winnings,
+    )?;
+
+    record_game_rating(
+        game,
+        winner,
+        &ctx.accounts.config,
+        ctx.accounts.season.as_mut(),
+        ctx.accounts.player1_stats.as_mut(),
+        ctx.accounts.player2_stats.as_mut(),
+    )?;
+
+    verbose_msg!(
+        "finish_game: completed, game_id={}, final_status={:?}, winner={} ({})",
+        game.game_id,
+        game.status,
+        game.winner,
+        winner_label
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use crate::*;
+
+/// Аварийный возврат средств обоим игрокам по тайм-ауту.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ForceRefund<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок.
+    #[account(mut, address = game.player1 @ ErrorCode::InvalidPlayer1)]
+    pub player1: Signer<'info>,
+
+    /// Второй игрок.
+    #[account(
+        mut,
+        address = game.player2 @ ErrorCode::InvalidPlayer2,
+        constraint = player2.key() != player1.key() @ ErrorCode::DuplicatePlayerAccounts,
+    )]
+    pub player2: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; required only for bot games.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: Option<UncheckedAccount<'info>>,
+
+    /// Составы команд; обязателен для командных игр.
+    #[account(mut, seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Option<Account<'info, TeamState>>,
+
+    /// Контроль времени; обязателен для пресетов, кроме Casual.
+    #[account(seeds = [b"time_control", game.key().as_ref()], bump = time_control.bump)]
+    pub time_control: Option<Account<'info, TimeControl>>,
+
+    /// CHECK: PDA [b"payout_splits", game.player1] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player1.as_ref()], bump)]
+    pub player1_splits: UncheckedAccount<'info>,
+
+    /// CHECK: PDA [b"payout_splits", game.player2] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player2.as_ref()], bump)]
+    pub player2_splits: UncheckedAccount<'info>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Глобальный конфиг: куда уходит остаток от деления (split_remainder).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: address constraint ensures this is config.treasury; only credited.
+    /// Required only when a treasury is set and the refund leaves a remainder.
+    #[account(mut, address = config.treasury)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ForceRefund<'info>>) -> Result<()> {
+    require_cpi_policy(instruction::ForceRefund::DISCRIMINATOR)?;
+    require!(ctx.accounts.game.staked_lamports == 0, ErrorCode::PotStaked);
+    let game = &mut ctx.accounts.game;
+
+    require!(
+        game.status == GameStatus::Active || game.status == GameStatus::Disputed,
+        ErrorCode::GameNotActive
+    );
+    // Пока решение арбитра ждёт апелляции, банк заблокирован.
+    require!(!game.resolution_pending(), ErrorCode::ResolutionPending);
+
+    let current_slot = Clock::get()?.slot;
+    let last = game.last_activity_slot;
+    let refund_timeout = game
+        .time_control(ctx.accounts.time_control.as_deref())?
+        .refund_timeout_slots;
+
+    verbose_msg!(
+        "force_refund: current_slot={}, last_activity_slot={}, refund_timeout={}",
+        current_slot,
+        last,
+        refund_timeout
+    );
+
+    require_ctx!(
+        current_slot
+            .checked_sub(last)
+            .ok_or(ErrorCode::MathOverflow)?
+            >= refund_timeout,
+        ErrorCode::TimeoutNotReached,
+        Slot {
+            expected: last.saturating_add(refund_timeout),
+            actual: current_slot,
+        }
+    );
+
+    let (total_p1, total_p2, to_treasury) =
+        game.refund_totals(ctx.accounts.config.split_remainder())?;
+
+    let pot = game.pot_lamports;
+    verbose_msg!(
+        "force_refund: pot={}, total_p1={}, total_p2={}, tips={}",
+        pot,
+        total_p1,
+        total_p2,
+        game.tips_lamports
+    );
+
+    let total = total_p1
+        .checked_add(total_p2)
+        .and_then(|v| v.checked_add(to_treasury))
+        .ok_or(ErrorCode::MathOverflow)?;
+    require_ctx!(
+        total == pot,
+        ErrorCode::InconsistentPot,
+        Lamports {
+            expected: pot,
+            actual: total,
+        }
+    );
+
+    // Возвращаем каждому ровно его вклад. Аккаунт игры принадлежит нашей программе,
+    // поэтому можем напрямую изменять его баланс.
+    let mut remaining = ctx.remaining_accounts.iter();
+    if total_p1 > 0 {
+        let player1_info = team_payee(
+            game,
+            1,
+            ctx.accounts.player1.to_account_info(),
+            ctx.accounts.team.as_mut(),
+            total_p1,
+        )?;
+        pay_out(
+            &game.to_account_info(),
+            player1_info,
+            ctx.accounts.player1_splits.as_ref(),
+            &mut remaining,
+            total_p1,
+        )?;
+    }
+
+    if total_p2 > 0 {
+        let player2_info = team_payee(
+            game,
+            2,
+            player2_funds(
+                game,
+                ctx.accounts.player2.to_account_info(),
+                ctx.accounts.bot_vault.as_ref(),
+            )?,
+            ctx.accounts.team.as_mut(),
+            total_p2,
+        )?;
+        pay_out(
+            &game.to_account_info(),
+            player2_info,
+            ctx.accounts.player2_splits.as_ref(),
+            &mut remaining,
+            total_p2,
+        )?;
+    }
+
+    pay_split_remainder(
+        &game.to_account_info(),
+        ctx.accounts.treasury.as_ref(),
+        to_treasury,
+    )?;
+
+    game.pot_lamports = 0;
+    game.player1_deposit = 0;
+    game.player2_deposit = 0;
+    game.player1_fees_paid = 0;
+    game.player2_fees_paid = 0;
+    game.decayed_lamports = 0;
+    game.tips_lamports = 0;
+    game.finish(RefundReason::Timeout.into(), current_slot);
+
+    emit_cpi!(GameRefunded {
+        game: game.key(),
+        player1_amount: total_p1,
+        player2_amount: total_p2,
+        reason: RefundReason::Timeout,
+        finish_reason: game.finish_reason,
+    });
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Refunded,
+        Pubkey::default(),
+        total_p1.saturating_add(total_p2),
+    )?;
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::*;
+
+/// Контекст для пополнения bot_vault.
+#[derive(Accounts)]
+pub struct FundBotVault<'info> {
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; only credited.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: UncheckedAccount<'info>,
+
+    /// Отправитель средств.
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundBotVault>, amount: u64) -> Result<()> {
+    verbose_msg!(
+        "fund_bot_vault: funder={}, amount={}",
+        ctx.accounts.funder.key(),
+        amount
+    );
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.funder.to_account_info(),
+        to: ctx.accounts.bot_vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, amount)
+}
@@ -0,0 +1,53 @@
+use crate::*;
+
+/// Контекст для пополнения бюджета relayed_move.
+#[derive(Accounts)]
+pub struct FundRelayBudget<'info> {
+    /// Игра, для ходов в которой пополняется бюджет.
+    pub game: Account<'info, GameState>,
+
+    /// Бюджет игрока; создаётся при первом пополнении.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + RelayBudget::MAX_SIZE,
+        seeds = [b"relay_budget", game.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub relay_budget: Account<'info, RelayBudget>,
+
+    /// Игрок, пополняющий свой бюджет.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundRelayBudget>, amount: u64) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let player = ctx.accounts.player.key();
+    require!(
+        player == game.player1 || player == game.player2,
+        ErrorCode::InvalidPlayer
+    );
+
+    verbose_msg!(
+        "fund_relay_budget: game_id={}, player={}, amount={}",
+        game.game_id,
+        player,
+        amount
+    );
+
+    let budget = &mut ctx.accounts.relay_budget;
+    budget.game = game.key();
+    budget.player = player;
+    budget.bump = ctx.bumps.relay_budget;
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player.to_account_info(),
+        to: budget.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, amount)
+}
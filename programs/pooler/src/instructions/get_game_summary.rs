@@ -0,0 +1,29 @@
+use crate::*;
+
+/// Сводка игры (get_game_summary): только чтение.
+#[derive(Accounts)]
+pub struct GetGameSummary<'info> {
+    pub game: Account<'info, GameState>,
+
+    /// CHECK: time_control PDA of the game; deserialized only when it exists and is owned by this program
+    #[account(seeds = [b"time_control", game.key().as_ref()], bump)]
+    pub time_control: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<GetGameSummary>) -> Result<GameSummary> {
+    let game = &ctx.accounts.game;
+    let info = ctx.accounts.time_control.to_account_info();
+    let time_control = if info.owner == &crate::ID && !info.data_is_empty() {
+        Some(TimeControl::try_deserialize(
+            &mut &info.try_borrow_data()?[..],
+        )?)
+    } else {
+        None
+    };
+    let refund_timeout = game
+        .time_control(time_control.as_ref())?
+        .refund_timeout_slots;
+
+    verbose_msg!("get_game_summary: game_id={}", game.game_id);
+    Ok(game.summary(Clock::get()?.slot, refund_timeout))
+}
@@ -0,0 +1,56 @@
+use crate::*;
+
+/// Контекст для создания шуэта.
+#[derive(Accounts)]
+pub struct InitChouette<'info> {
+    /// Игра, поверх которой играется шуэт.
+    pub game: Account<'info, GameState>,
+
+    /// Аккаунт шуэта, PDA от игры.
+    #[account(
+        init,
+        payer = box_player,
+        space = 8 + ChouetteState::MAX_SIZE,
+        seeds = [b"chouette", game.key().as_ref()],
+        bump,
+    )]
+    pub chouette: Account<'info, ChouetteState>,
+
+    /// Игрок в коробке (game.player1), платит за создание аккаунта.
+    #[account(mut, address = game.player1)]
+    pub box_player: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<InitChouette>, stake_lamports: u64) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &ctx.accounts.game;
+    let chouette = &mut ctx.accounts.chouette;
+
+    verbose_msg!(
+        "init_chouette: game={}, box={}, captain={}, stake_lamports={}",
+        game.key(),
+        game.player1,
+        game.player2,
+        stake_lamports
+    );
+
+    require!(!game.status.is_over(), ErrorCode::GameNotActive);
+
+    chouette.game = game.key();
+    chouette.box_player = game.player1;
+    chouette.captain = game.player2;
+    chouette.stake_lamports = stake_lamports;
+    chouette.members = [ChouetteMember::default(); CHOUETTE_MAX_MEMBERS];
+    chouette.member_count = 0;
+    chouette.settled = false;
+    chouette.bump = ctx.bumps.chouette;
+
+    Ok(())
+}
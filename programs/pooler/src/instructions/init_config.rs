@@ -0,0 +1,51 @@
+use crate::*;
+
+/// Контекст для создания конфига и первого сезона.
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    /// Глобальный конфиг.
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::MAX_SIZE,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Первый сезон.
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Season::MAX_SIZE,
+        seeds = [b"season".as_ref(), &0u32.to_le_bytes()],
+        bump,
+    )]
+    pub season: Account<'info, Season>,
+
+    /// Администратор, платит за создание аккаунтов.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.current_season = 0;
+    config.bump = ctx.bumps.config;
+
+    let season = &mut ctx.accounts.season;
+    season.index = 0;
+    season.start_slot = Clock::get()?.slot;
+    season.end_slot = 0;
+    season.closed = false;
+    season.standings = [SeasonStanding::default(); SEASON_STANDINGS_SIZE];
+    season.bump = ctx.bumps.season;
+
+    verbose_msg!("init_config: admin={}, season=0", config.admin);
+
+    Ok(())
+}
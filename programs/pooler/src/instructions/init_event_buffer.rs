@@ -0,0 +1,40 @@
+use crate::*;
+
+/// Контекст для создания буфера событий.
+#[derive(Accounts)]
+pub struct InitEventBuffer<'info> {
+    /// Игра, события которой пишутся в буфер.
+    pub game: Account<'info, GameState>,
+
+    /// Буфер событий.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + EventBuffer::INIT_SPACE,
+        seeds = [b"event_buffer", game.key().as_ref()],
+        bump,
+    )]
+    pub event_buffer: Box<Account<'info, EventBuffer>>,
+
+    /// Плательщик ренты.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitEventBuffer>) -> Result<()> {
+    let buffer = &mut ctx.accounts.event_buffer;
+    buffer.game = ctx.accounts.game.key();
+    buffer.payer = ctx.accounts.payer.key();
+    buffer.next_seq = 1;
+    buffer.bump = ctx.bumps.event_buffer;
+
+    verbose_msg!(
+        "init_event_buffer: game={}, payer={}",
+        buffer.game,
+        buffer.payer
+    );
+    Ok(())
+}
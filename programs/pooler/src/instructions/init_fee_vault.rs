@@ -0,0 +1,37 @@
+use crate::*;
+
+/// Контекст для создания хранилища комиссии протокола.
+#[derive(Accounts)]
+pub struct InitFeeVault<'info> {
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// Хранилище комиссии протокола.
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"fee_vault"],
+        bump,
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// Администратор, платит ренту.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitFeeVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.fee_vault;
+    vault.collected = 0;
+    vault.withdrawn = 0;
+    vault.burned = 0;
+    vault.bump = ctx.bumps.fee_vault;
+
+    verbose_msg!("init_fee_vault: admin={}", ctx.accounts.admin.key());
+    Ok(())
+}
@@ -0,0 +1,482 @@
+use crate::*;
+
+/// Контекст для init_game.
+///
+/// #[instruction(...)] говорит Anchor'у:
+/// "эта инструкция принимает такие-то аргументы, их можно использовать в seeds".
+#[derive(Accounts)]
+#[instruction(
+    game_id: u64,
+    stake_lamports: u64,
+    move_fee_lamports: u64,
+    player2_pubkey: Pubkey,
+    options: GameOptions,
+)]
+pub struct InitGame<'info> {
+    /// Аккаунт игры. Создаётся этой инструкцией.
+    /// При CPI может быть PDA вызывающей программы (подписывает create_account).
+    #[account(
+        init,
+        payer = player1,
+        space = 8 + GameState::MAX_SIZE,
+    )]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок, он платит за создание аккаунта и вносит первую ставку.
+    /// При CPI может быть system-owned PDA вызывающей программы.
+    #[account(mut)]
+    pub player1: Signer<'info>,
+
+    /// Стандартная системная программа Solana, нужна для создания аккаунта.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг программы.
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: PDA [b"ban", player1] enforced by seeds; initialized = banned.
+    /// Required only when config.bans_enforced.
+    #[account(seeds = [b"ban", player1.key().as_ref()], bump)]
+    pub player1_ban: Option<UncheckedAccount<'info>>,
+
+    /// Счётчик открытых лобби первого игрока, создаётся при первой игре.
+    #[account(
+        init_if_needed,
+        payer = player1,
+        space = 8 + OpenGames::MAX_SIZE,
+        seeds = [b"open_games", player1.key().as_ref()],
+        bump,
+    )]
+    pub open_games: Account<'info, OpenGames>,
+
+    /// Контроль времени; создаётся для всех пресетов, кроме Casual.
+    #[account(
+        init,
+        payer = player1,
+        space = 8 + TimeControl::MAX_SIZE,
+        seeds = [b"time_control", game.key().as_ref()],
+        bump,
+    )]
+    pub time_control: Option<Account<'info, TimeControl>>,
+
+    /// Слот прошлого init_game создателя, создаётся при первой игре.
+    #[account(
+        init_if_needed,
+        payer = player1,
+        space = 8 + CreateCooldown::MAX_SIZE,
+        seeds = [b"create_cooldown", player1.key().as_ref()],
+        bump,
+    )]
+    pub create_cooldown: Account<'info, CreateCooldown>,
+
+    /// Получатель комиссий организатора; создаётся, если задан options.fee_recipient.
+    #[account(
+        init,
+        payer = player1,
+        space = 8 + FeeRoute::MAX_SIZE,
+        seeds = [b"fee_route", game.key().as_ref()],
+        bump,
+    )]
+    pub fee_route: Option<Account<'info, FeeRoute>>,
+
+    /// CHECK: PDA [b"arbiter", options.arbiter] enforced by seeds; initialized =
+    /// whitelisted. Required only for a game arbiter when
+    /// config.require_whitelisted_arbiters.
+    #[account(seeds = [b"arbiter", options.arbiter.as_ref()], bump)]
+    pub whitelisted_arbiter: Option<UncheckedAccount<'info>>,
+
+    /// Счётчик игр создателя, создаётся при первом обращении. Нужен, только
+    /// если game_id = 0 (id выдаёт программа).
+    #[account(
+        init_if_needed,
+        payer = player1,
+        space = 8 + GameCounter::INIT_SPACE,
+        seeds = [b"counter", player1.key().as_ref()],
+        bump,
+    )]
+    pub game_counter: Option<Account<'info, GameCounter>>,
+
+    /// Чанк 0 истории ходов; создаётся, если задан options.move_history.
+    #[account(
+        init,
+        payer = player1,
+        space = 8 + GameHistory::INIT_SPACE,
+        seeds = [b"game_history", game.key().as_ref(), &[0]],
+        bump,
+    )]
+    pub game_history: Option<Box<Account<'info, GameHistory>>>,
+
+    /// Merkle-коммитмент ходов; создаётся, если задан options.history_commitment.
+    #[account(
+        init,
+        payer = player1,
+        space = 8 + HistoryCommitment::INIT_SPACE,
+        seeds = [b"history_commitment", game.key().as_ref()],
+        bump,
+    )]
+    pub history_commitment: Option<Box<Account<'info, HistoryCommitment>>>,
+}
+
+pub fn handler(
+    ctx: Context<InitGame>,
+    game_id: u64,
+    stake_lamports: u64,
+    move_fee_lamports: u64,
+    player2_pubkey: Pubkey,
+    options: GameOptions,
+) -> Result<()> {
+    verbose_msg!(
+        "init_game: game_id={}, stake_lamports={}, move_fee_lamports={}, player1={}, player2={}, options={:?}",
+        game_id,
+        stake_lamports,
+        move_fee_lamports,
+        ctx.accounts.player1.key(),
+        player2_pubkey,
+        options
+    );
+
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    require!(
+        !options.stake_pot || ctx.accounts.config.stake_pool != Pubkey::default(),
+        ErrorCode::StakePoolDisabled
+    );
+    require!(
+        !is_banned(
+            &ctx.accounts.config,
+            ctx.accounts.player1_ban.as_ref().map(|a| a.as_ref())
+        )?,
+        ErrorCode::PlayerBanned
+    );
+    check_arbiter_whitelisted(
+        &ctx.accounts.config,
+        &options.arbiter,
+        ctx.accounts
+            .whitelisted_arbiter
+            .as_ref()
+            .map(|a| a.as_ref()),
+    )?;
+
+    // Игрок не может пригласить сам себя: иначе один и тот же аккаунт
+    // попал бы в оба слота, дважды учитывался в выплатах и статистике.
+    require!(
+        player2_pubkey != ctx.accounts.player1.key(),
+        ErrorCode::SelfPlayNotAllowed
+    );
+    require!(
+        player2_pubkey != ctx.accounts.game.key(),
+        ErrorCode::PayoutAliasesGame
+    );
+
+    // game_id = 0: id выдаёт счётчик создателя. Явные id живут в нижней
+    // половине диапазона и с выданными не пересекаются.
+    let game_id = if game_id == 0 {
+        let counter = ctx
+            .accounts
+            .game_counter
+            .as_mut()
+            .ok_or(ErrorCode::MissingGameCounter)?;
+        counter.bump = ctx
+            .bumps
+            .game_counter
+            .ok_or(ErrorCode::MissingGameCounter)?;
+        counter.assign()?
+    } else {
+        require!(game_id & AUTO_GAME_ID_FLAG == 0, ErrorCode::ReservedGameId);
+        game_id
+    };
+
+    check_init_params(
+        game_id,
+        stake_lamports,
+        move_fee_lamports,
+        &player2_pubkey,
+        &options,
+    )?;
+
+    // Пресет раскрывается в параметры контроля времени. Для Casual
+    // действуют глобальные константы и TimeControl не создаётся.
+    let time_control = resolve_time_control(options.preset, options.time_control)?;
+    verbose_msg!(
+        "init_game: preset={:?}, time_control={:?}",
+        options.preset,
+        time_control
+    );
+    match ctx.accounts.time_control.as_mut() {
+        Some(tc) => {
+            require!(
+                options.preset != GamePreset::Casual,
+                ErrorCode::InvalidTimeControl
+            );
+            tc.game = ctx.accounts.game.key();
+            tc.params = time_control;
+            tc.p1_bank_slots = 0;
+            tc.p2_bank_slots = 0;
+            tc.bump = ctx
+                .bumps
+                .time_control
+                .ok_or(ErrorCode::MissingTimeControl)?;
+        }
+        None => require!(
+            options.preset == GamePreset::Casual,
+            ErrorCode::MissingTimeControl
+        ),
+    }
+
+    // Получатель комиссий организатора хранится в FeeRoute; без него
+    // комиссии, как и раньше, идут в банк.
+    match ctx.accounts.fee_route.as_mut() {
+        Some(route) => {
+            require!(
+                options.fee_recipient != Pubkey::default(),
+                ErrorCode::MissingFeeRoute
+            );
+            require!(
+                options.fee_recipient != ctx.accounts.game.key(),
+                ErrorCode::PayoutAliasesGame
+            );
+            route.game = ctx.accounts.game.key();
+            route.fee_recipient = options.fee_recipient;
+            route.fees_routed_out = 0;
+            route.bump = ctx.bumps.fee_route.ok_or(ErrorCode::MissingFeeRoute)?;
+        }
+        None => require!(
+            options.fee_recipient == Pubkey::default(),
+            ErrorCode::MissingFeeRoute
+        ),
+    }
+
+    // История ходов ведётся в GameHistory и/или HistoryCommitment, их
+    // дописывают make_move и relayed_move.
+    match ctx.accounts.game_history.as_mut() {
+        Some(history) => {
+            require!(options.move_history, ErrorCode::MissingGameHistory);
+            history.game = ctx.accounts.game.key();
+            history.payer = ctx.accounts.player1.key();
+            history.chunk_index = 0;
+            history.prev_chunk = Pubkey::default();
+            history.moves = Vec::new();
+            history.bump = ctx
+                .bumps
+                .game_history
+                .ok_or(ErrorCode::MissingGameHistory)?;
+        }
+        None => require!(!options.move_history, ErrorCode::MissingGameHistory),
+    }
+    match ctx.accounts.history_commitment.as_mut() {
+        Some(commitment) => {
+            require!(
+                options.history_commitment,
+                ErrorCode::MissingHistoryCommitment
+            );
+            commitment.game = ctx.accounts.game.key();
+            commitment.payer = ctx.accounts.player1.key();
+            commitment.leaf_count = 0;
+            commitment.frontier = [[0; 32]; 16];
+            commitment.history_root =
+                backgammon_core::HistoryFrontier::default().root::<HistoryHasher>();
+            commitment.bump = ctx
+                .bumps
+                .history_commitment
+                .ok_or(ErrorCode::MissingHistoryCommitment)?;
+        }
+        None => require!(
+            !options.history_commitment,
+            ErrorCode::MissingHistoryCommitment
+        ),
+    }
+
+    // Минимальный интервал между играми одного создателя (админ освобождён).
+    let current_slot = Clock::get()?.slot;
+    let config = &ctx.accounts.config;
+    let cooldown = &mut ctx.accounts.create_cooldown;
+    if config.min_create_gap_slots > 0
+        && ctx.accounts.player1.key() != config.admin
+        && cooldown.last_created_slot != 0
+    {
+        require!(
+            current_slot.saturating_sub(cooldown.last_created_slot) >= config.min_create_gap_slots,
+            ErrorCode::CreateRateLimited
+        );
+    }
+    cooldown.last_created_slot = current_slot;
+
+    // Лимит одновременно открытых лобби на создателя (0 = без ограничения).
+    let open_games = &mut ctx.accounts.open_games;
+    let max_open_games = ctx.accounts.config.max_open_games;
+    require!(
+        max_open_games == 0 || open_games.open_games < max_open_games,
+        ErrorCode::TooManyOpenGames
+    );
+    open_games.player = ctx.accounts.player1.key();
+    open_games.open_games = open_games
+        .open_games
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    open_games.bump = ctx.bumps.open_games;
+
+    // ОДНА мут-ссылка на аккаунт игры
+    let game = &mut ctx.accounts.game;
+
+    // Заполняем структуру состояния игры
+    game.player1 = ctx.accounts.player1.key();
+    game.player2 = player2_pubkey;
+    game.game_id = game_id;
+    game.stake_lamports = stake_lamports;
+    game.move_fee_lamports = move_fee_lamports;
+    game.move_fee_bps = options.move_fee_bps;
+    game.decay_bps = options.decay_bps;
+    game.own_free_moves = options.free_moves.is_some();
+    game.free_moves = options.free_moves.unwrap_or(0);
+    game.open_games_counted = true;
+    game.dice_seed = options.dice_seed;
+    game.mirror_paired = false;
+    game.stake_pot = options.stake_pot;
+    game.staked_lamports = 0;
+    game.decayed_lamports = 0;
+    game.pot_lamports = 0;
+    game.player1_deposit = 0;
+    game.player2_deposit = 0;
+    game.player1_fees_paid = 0;
+    game.player2_fees_paid = 0;
+    game.tips_lamports = 0;
+    game.bot_game = false;
+    game.player1_session_key = Pubkey::default();
+    game.player1_session_expiry = 0;
+    game.player2_session_key = Pubkey::default();
+    game.player2_session_expiry = 0;
+    game.version = GAME_STATE_VERSION;
+    game.unsettled_side_bets = 0;
+    game.reserved = [0; 4];
+    game.action_nonce = 0;
+    game.finished_slot = 0;
+    game.finish_reason = FinishReason::NotFinished;
+    game.resolution_winner = 0;
+    game.appeal_deadline_slot = 0;
+    game.resolution_appealed = false;
+    game.counter_pending = false;
+    game.sides_swapped = false;
+    game.move_history = options.move_history;
+    game.current_history_chunk = 0;
+    game.history_commitment = options.history_commitment;
+    game.set_board_points(&options.initial_board)?;
+    game.handicap = options.handicap;
+    game.rule_variant = options.rule_variant;
+    game.preset = options.preset;
+    game.practice = options.practice;
+    game.fee_routed = options.fee_recipient != Pubkey::default();
+    game.board_from_start = options.initial_board != [0; 24];
+    game.dice = [0; 2];
+    game.current_turn = 1;
+    game.status = GameStatus::WaitingForPlayer2;
+    game.winner = Pubkey::default();
+    // Для упрощения в учебном примере не используем PDA seeds для аккаунта игры,
+    // поэтому bump просто ставим в 0.
+    game.bump = 0;
+    game.move_index = 0;
+    game.last_activity_slot = Clock::get()?.slot;
+    game.cube_value = 1;
+    game.cube_owner = 0;
+    game.double_pending = false;
+    game.beavers_allowed = options.beavers_allowed;
+    game.raccoon_pending = false;
+    game.jacoby = options.jacoby;
+    game.auto_doubles = options.auto_doubles;
+    game.auto_double_limit = options.auto_double_limit;
+    game.auto_doubles_done = 0;
+    game.tournament = options.tournament;
+    game.tournament_round = options.tournament_round;
+    game.min_opponent_rating = options.min_opponent_rating;
+    game.password_hash = options.password_hash;
+    game.join_deadline_slot = options.join_deadline_slot;
+    game.escrow_agent = options.escrow_agent;
+    game.arbiter = options.arbiter;
+    game.strike_limit = options.strike_limit;
+    game.max_moves = options.max_moves;
+    game.p1_timeout_strikes = 0;
+    game.p2_timeout_strikes = 0;
+    game.annotation_chain = [0; 32];
+    game.two_phase_moves = options.two_phase_moves;
+    game.move_pending = false;
+    game.set_pending_board_points(&[0; 24])?;
+    game.pending_dice = [0; 2];
+    game.pending_deadline_slot = 0;
+    game.p1_auto_take_up_to = 0;
+    game.p2_auto_take_up_to = 0;
+    game.p1_take_reserve = 0;
+    game.p2_take_reserve = 0;
+
+    verbose_msg!(
+        "init_game: GameState initialized: status={:?}, current_turn={}, pot_lamports={}, bump={}",
+        game.status,
+        game.current_turn,
+        game.pot_lamports,
+        game.bump
+    );
+
+    // Забираем ставку у первого игрока в аккаунт игры через CPI в системную программу.
+    // В тренировочной игре переводить нечего.
+    if !game.practice {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.player1.to_account_info(),
+            to: game.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, stake_lamports)?;
+    }
+
+    game.pot_lamports = game
+        .pot_lamports
+        .checked_add(stake_lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    game.player1_deposit = game
+        .player1_deposit
+        .checked_add(stake_lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!(
+        "init_game: stake transferred from player1={}, stake_lamports={}, pot_lamports={}",
+        game.player1,
+        stake_lamports,
+        game.pot_lamports
+    );
+
+    // Резерв под автоматические взятия куба player1 (вне банка).
+    if options.auto_take_up_to != 0 {
+        let reserve = game.auto_take_reserve_needed(options.auto_take_up_to)?;
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.player1.to_account_info(),
+            to: game.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, reserve)?;
+        game.p1_auto_take_up_to = options.auto_take_up_to;
+        game.p1_take_reserve = reserve;
+
+        verbose_msg!(
+            "init_game: auto_take_up_to={}, take_reserve={}",
+            options.auto_take_up_to,
+            reserve
+        );
+    }
+
+    emit!(GameInitialized {
+        game: game.key(),
+        game_id: game.game_id,
+        player1: game.player1,
+        player2: game.player2,
+        stake_lamports: game.stake_lamports,
+        move_fee_lamports: game.move_fee_lamports,
+        practice: game.practice,
+    });
+    // Тающий банк меняет экономику партии: отдельное событие рядом с
+    // GameInitialized, чтобы второй игрок видел режим до входа.
+    if game.decay_bps > 0 {
+        emit!(PotDecayEnabled {
+            game: game.key(),
+            decay_bps: game.decay_bps,
+        });
+    }
+
+    assert_pot_invariant(&ctx.accounts.game)?;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::*;
+
+/// Контекст для создания страхового пула.
+#[derive(Accounts)]
+pub struct InitInsurancePool<'info> {
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// Страховой пул.
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + InsurancePool::INIT_SPACE,
+        seeds = [b"insurance_pool"],
+        bump,
+    )]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    /// Администратор, платит ренту.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitInsurancePool>) -> Result<()> {
+    let pool = &mut ctx.accounts.insurance_pool;
+    pool.total_collected = 0;
+    pool.total_compensated = 0;
+    pool.bump = ctx.bumps.insurance_pool;
+
+    verbose_msg!("init_insurance_pool: admin={}", ctx.accounts.admin.key());
+    Ok(())
+}
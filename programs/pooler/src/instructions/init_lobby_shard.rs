@@ -0,0 +1,33 @@
+use crate::*;
+
+/// Контекст для создания шарда лобби.
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct InitLobbyShard<'info> {
+    /// Шард лобби.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Lobby::INIT_SPACE,
+        seeds = [b"lobby".as_ref(), &[shard]],
+        bump,
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
+
+    /// Плательщик ренты.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitLobbyShard>, shard: u8) -> Result<()> {
+    require!(shard < LOBBY_SHARDS, ErrorCode::InvalidLobbyShard);
+    let lobby = &mut ctx.accounts.lobby;
+    lobby.shard = shard;
+    lobby.bump = ctx.bumps.lobby;
+
+    verbose_msg!("init_lobby_shard: shard={}", shard);
+    Ok(())
+}
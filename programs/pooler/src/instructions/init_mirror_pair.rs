@@ -0,0 +1,89 @@
+use crate::*;
+
+/// Контекст для создания зеркальной пары.
+#[derive(Accounts)]
+pub struct InitMirrorPair<'info> {
+    /// Игра, где player_a — первый игрок; помечается как игра пары.
+    #[account(mut)]
+    pub game_a: Account<'info, GameState>,
+
+    /// Игра с переставленными цветами; помечается как игра пары.
+    #[account(mut)]
+    pub game_b: Account<'info, GameState>,
+
+    /// Общий эскроу пары.
+    #[account(
+        init,
+        payer = player_a,
+        space = 8 + MirrorPair::MAX_SIZE,
+        seeds = [b"mirror", game_a.key().as_ref(), game_b.key().as_ref()],
+        bump,
+    )]
+    pub pair: Account<'info, MirrorPair>,
+
+    /// Игрок A, платит за аккаунт пары.
+    #[account(mut)]
+    pub player_a: Signer<'info>,
+
+    /// Игрок B.
+    #[account(mut)]
+    pub player_b: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<InitMirrorPair>, stake_lamports: u64) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game_a = &ctx.accounts.game_a;
+    let game_b = &ctx.accounts.game_b;
+    let player_a = ctx.accounts.player_a.key();
+    let player_b = ctx.accounts.player_b.key();
+
+    verbose_msg!(
+        "init_mirror_pair: game_a={}, game_b={}, player_a={}, player_b={}, stake_lamports={}",
+        game_a.key(),
+        game_b.key(),
+        player_a,
+        player_b,
+        stake_lamports
+    );
+
+    MirrorPair::check_games(game_a, game_b, &player_a, &player_b)?;
+
+    // Каждый вносит ставку за обе игры: максимальный проигрыш — обе.
+    let deposit = stake_lamports
+        .checked_mul(2)
+        .ok_or(ErrorCode::MathOverflow)?;
+    for from in [
+        ctx.accounts.player_a.to_account_info(),
+        ctx.accounts.player_b.to_account_info(),
+    ] {
+        let cpi_accounts = system_program::Transfer {
+            from,
+            to: ctx.accounts.pair.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, deposit)?;
+    }
+
+    // Игра входит не больше чем в одну пару: иначе одни и те же результаты
+    // рассчитывались бы несколькими MirrorPair.
+    ctx.accounts.game_a.mirror_paired = true;
+    ctx.accounts.game_b.mirror_paired = true;
+
+    let pair = &mut ctx.accounts.pair;
+    pair.game_a = ctx.accounts.game_a.key();
+    pair.game_b = ctx.accounts.game_b.key();
+    pair.player_a = player_a;
+    pair.player_b = player_b;
+    pair.stake_lamports = stake_lamports;
+    pair.settled = false;
+    pair.bump = ctx.bumps.pair;
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::*;
+
+/// Контекст для создания статистики игрока.
+#[derive(Accounts)]
+pub struct InitPlayerStats<'info> {
+    /// Статистика игрока.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PlayerStats::MAX_SIZE,
+        seeds = [b"stats", player.key().as_ref()],
+        bump,
+    )]
+    pub stats: Account<'info, PlayerStats>,
+
+    /// Глобальный конфиг (текущий сезон).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: only used as a PDA seed
+    pub player: AccountInfo<'info>,
+
+    /// Плательщик за создание аккаунта.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitPlayerStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.stats;
+    stats.player = ctx.accounts.player.key();
+    stats.rating = DEFAULT_RATING;
+    stats.games_played = 0;
+    stats.wins = 0;
+    stats.losses = 0;
+    stats.season = ctx.accounts.config.current_season;
+    stats.season_wins = 0;
+    stats.season_losses = 0;
+    stats.bump = ctx.bumps.stats;
+
+    verbose_msg!(
+        "init_player_stats: player={}, rating={}",
+        stats.player,
+        stats.rating
+    );
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::*;
+
+/// Контекст для проверки целостности игры.
+#[derive(Accounts)]
+pub struct IntegrityCheck<'info> {
+    /// Проверяемая игра.
+    pub game: Account<'info, GameState>,
+
+    /// Пометка застрявшей игры.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GameFlag::INIT_SPACE,
+        seeds = [b"game_flag", game.key().as_ref()],
+        bump,
+    )]
+    pub game_flag: Account<'info, GameFlag>,
+
+    /// Любой плательщик ренты.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<IntegrityCheck>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    require!(!game.status.is_over(), ErrorCode::GameNotStuck);
+
+    // Банк в пуле не на аккаунте игры, но это не застревание.
+    require!(game.staked_lamports == 0, ErrorCode::GameNotStuck);
+
+    let game_info = game.to_account_info();
+    let lamports = game_info.lamports();
+    let rent_min = Rent::get()?.minimum_balance(game_info.data_len());
+    let refund_total = game
+        .refund_totals(Remainder::First)
+        .ok()
+        .and_then(|(p1, p2, _)| p1.checked_add(p2));
+
+    let reason = if refund_total != Some(game.pot_lamports) {
+        StuckReason::PotMismatch
+    } else if lamports < game.pot_lamports.saturating_add(rent_min) {
+        StuckReason::LamportShortfall
+    } else {
+        return err!(ErrorCode::GameNotStuck);
+    };
+
+    msg!(
+        "integrity_check: game_id={}, reason={:?}, pot={}, refund_total={:?}, lamports={}, rent_min={}",
+        game.game_id,
+        reason,
+        game.pot_lamports,
+        refund_total,
+        lamports,
+        rent_min
+    );
+
+    let flag = &mut ctx.accounts.game_flag;
+    flag.game = game.key();
+    flag.reason = reason;
+    flag.flagged_slot = Clock::get()?.slot;
+    flag.compensated = [0; 2];
+    flag.bump = ctx.bumps.game_flag;
+
+    emit!(GameFlagged {
+        game: game.key(),
+        reason,
+        pot_lamports: game.pot_lamports,
+        refund_total: refund_total.unwrap_or(u64::MAX),
+        lamports,
+        flagged_by: ctx.accounts.payer.key(),
+    });
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::*;
+
+/// Контекст для входа члена команды.
+#[derive(Accounts)]
+pub struct JoinChouette<'info> {
+    /// Игра, поверх которой играется шуэт.
+    #[account(address = chouette.game)]
+    pub game: Account<'info, GameState>,
+
+    /// Аккаунт шуэта.
+    #[account(mut, seeds = [b"chouette", game.key().as_ref()], bump = chouette.bump)]
+    pub chouette: Account<'info, ChouetteState>,
+
+    /// Игрок в коробке, вносит встречную ставку.
+    #[account(mut, address = chouette.box_player)]
+    pub box_player: Signer<'info>,
+
+    /// Новый член команды.
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<JoinChouette>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let chouette = &mut ctx.accounts.chouette;
+    let member = ctx.accounts.member.key();
+
+    verbose_msg!(
+        "join_chouette: chouette={}, member={}, member_count={}",
+        chouette.key(),
+        member,
+        chouette.member_count
+    );
+
+    require!(!chouette.settled, ErrorCode::ChouetteSettled);
+    require!(
+        !ctx.accounts.game.status.is_over(),
+        ErrorCode::GameNotActive
+    );
+    require!(
+        member != chouette.box_player && member != chouette.captain,
+        ErrorCode::InvalidPlayer
+    );
+    require!(
+        !chouette
+            .members
+            .iter()
+            .any(|m| m.status != ChouetteMemberStatus::Empty && m.member == member),
+        ErrorCode::ChouetteMemberExists
+    );
+
+    let slot = chouette
+        .members
+        .iter()
+        .position(|m| m.status == ChouetteMemberStatus::Empty)
+        .ok_or(ErrorCode::ChouetteFull)?;
+
+    let stake = chouette.stake_lamports;
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.member.to_account_info(),
+        to: chouette.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, stake)?;
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.box_player.to_account_info(),
+        to: chouette.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, stake)?;
+
+    chouette.members[slot] = ChouetteMember {
+        member,
+        member_deposit: stake,
+        box_deposit: stake,
+        cube_value: 1,
+        double_pending: false,
+        status: ChouetteMemberStatus::Active,
+    };
+    chouette.member_count = chouette
+        .member_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!("join_chouette: completed, slot={}, stake={}", slot, stake);
+
+    Ok(())
+}
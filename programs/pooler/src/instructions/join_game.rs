@@ -0,0 +1,354 @@
+use crate::*;
+
+/// Контекст для присоединения второго игрока.
+#[derive(Accounts)]
+pub struct JoinGame<'info> {
+    /// Аккаунт игры. Уже должен быть инициализирован через init_game.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Второй игрок, указанный при init_game; вносит свою стартовую ставку.
+    /// При CPI может быть system-owned PDA вызывающей программы.
+    #[account(
+        mut,
+        address = game.player2 @ ErrorCode::InvalidPlayer2,
+        constraint = player2.key() != game.player1 @ ErrorCode::SelfPlayNotAllowed,
+    )]
+    pub player2: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг программы.
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Счётчик открытых лобби создателя игры; обязателен, если игра в нём
+    /// учтена (game.open_games_counted), у игр до OpenGames не нужен.
+    #[account(mut, seeds = [b"open_games", game.player1.as_ref()], bump = player1_open_games.bump)]
+    pub player1_open_games: Option<Account<'info, OpenGames>>,
+
+    /// CHECK: PDA [b"ban", player2] enforced by seeds; initialized = banned.
+    /// Required only when config.bans_enforced.
+    #[account(seeds = [b"ban", player2.key().as_ref()], bump)]
+    pub player2_ban: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA [b"stats", player2] enforced by seeds; may be uninitialized (default rating).
+    /// Required only when the game has min_opponent_rating > 0.
+    #[account(seeds = [b"stats", player2.key().as_ref()], bump)]
+    pub player2_stats: Option<UncheckedAccount<'info>>,
+
+    /// Телеметрия клиентов (необязательно).
+    #[account(
+        init_if_needed,
+        payer = player2,
+        space = 8 + ClientTelemetry::INIT_SPACE,
+        seeds = [b"client_telemetry", game.key().as_ref()],
+        bump,
+    )]
+    pub client_telemetry: Option<Account<'info, ClientTelemetry>>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Шард лобби, в котором опубликована игра (необязательно).
+    #[account(mut, seeds = [b"lobby".as_ref(), &[lobby.shard]], bump = lobby.bump)]
+    pub lobby: Option<Box<Account<'info, Lobby>>>,
+
+    /// Учёт размещения банка; обязателен (как и аккаунты пула ниже), если
+    /// игра создана с options.stake_pot.
+    #[account(
+        init,
+        payer = player2,
+        space = 8 + PotStake::MAX_SIZE,
+        seeds = [b"pot_stake", game.key().as_ref()],
+        bump,
+    )]
+    pub pot_stake: Option<Box<Account<'info, PotStake>>>,
+
+    /// Промежуточный system-owned счёт банка.
+    #[account(mut, seeds = [b"pot_vault", game.key().as_ref()], bump)]
+    pub pot_vault: Option<SystemAccount<'info>>,
+
+    /// Счёт токенов пула под управлением PotStake.
+    #[account(
+        init,
+        payer = player2,
+        seeds = [b"pot_stake_tokens", game.key().as_ref()],
+        bump,
+        token::mint = pool_mint,
+        token::authority = pot_stake,
+    )]
+    pub pool_tokens: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Минт токенов пула.
+    #[account(mut)]
+    pub pool_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// CHECK: must be config.stake_pool; the pool program validates the rest
+    #[account(mut, address = config.stake_pool)]
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the stake pool program
+    pub stake_pool_withdraw_authority: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: address constraint ensures this is the SPL stake pool program
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: Option<UncheckedAccount<'info>>,
+
+    /// Программа SPL Token.
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+pub fn handler(
+    ctx: Context<JoinGame>,
+    password: Vec<u8>,
+    client: Option<ClientInfo>,
+) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "join_game: game_id={}, player2_expected={}, player2_actual={}",
+        game.game_id,
+        game.player2,
+        ctx.accounts.player2.key()
+    );
+
+    // Игра должна ожидать второго игрока
+    require!(
+        game.status == GameStatus::WaitingForPlayer2,
+        ErrorCode::GameNotWaitingForPlayer2
+    );
+
+    // Приглашение действует до join_deadline_slot включительно.
+    require!(
+        game.join_deadline_slot == 0 || Clock::get()?.slot <= game.join_deadline_slot,
+        ErrorCode::JoinDeadlinePassed
+    );
+
+    if game.password_hash != [0u8; 32] {
+        require!(
+            password.len() <= MAX_PASSWORD_LEN as usize,
+            ErrorCode::PasswordTooLong
+        );
+        require!(
+            hash(&password).to_bytes() == game.password_hash,
+            ErrorCode::WrongPassword
+        );
+    }
+
+    // Забаненный игрок не может войти в новую игру. Уже идущие игры с ним
+    // по-прежнему можно завершить или вернуть средства.
+    require!(
+        !is_banned(
+            &ctx.accounts.config,
+            ctx.accounts.player2_ban.as_ref().map(|a| a.as_ref())
+        )?,
+        ErrorCode::PlayerBanned
+    );
+
+    // Порог рейтинга: статистика передаётся по PDA самого игрока (seeds
+    // проверяются в контексте), поэтому чужой аккаунт подставить нельзя.
+    // Если PDA ещё не создан, считаем рейтинг равным DEFAULT_RATING.
+    if game.min_opponent_rating > 0 {
+        let stats_info = ctx
+            .accounts
+            .player2_stats
+            .as_ref()
+            .ok_or(ErrorCode::MissingPlayerStats)?;
+        let rating = if stats_info.owner == &crate::ID {
+            let data = stats_info.try_borrow_data()?;
+            PlayerStats::try_deserialize(&mut &data[..])?.rating
+        } else {
+            DEFAULT_RATING
+        };
+
+        verbose_msg!(
+            "join_game: rating gate, rating={}, min_opponent_rating={}",
+            rating,
+            game.min_opponent_rating
+        );
+        require!(rating >= game.min_opponent_rating, ErrorCode::RatingTooLow);
+    }
+
+    // Списываем стартовую ставку со второго игрока в аккаунт игры. Висящее
+    // встречное предложение (counter_pending) её не меняет: новые условия
+    // действуют только после accept_counter.
+    let stake = game.stake_lamports;
+
+    verbose_msg!(
+        "join_game: transferring stake from player2={}, stake_lamports={}",
+        ctx.accounts.player2.key(),
+        stake
+    );
+
+    if !game.practice {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.player2.to_account_info(),
+            to: game.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, stake)?;
+    }
+
+    game.pot_lamports = game
+        .pot_lamports
+        .checked_add(stake)
+        .ok_or(ErrorCode::MathOverflow)?;
+    game.player2_deposit = game
+        .player2_deposit
+        .checked_add(stake)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if game.stake_pot {
+        stake_pot(ctx.accounts, &ctx.bumps)?;
+    }
+    let game = &mut ctx.accounts.game;
+
+    game.last_activity_slot = Clock::get()?.slot;
+    game.status = GameStatus::Active;
+    release_open_game(game, ctx.accounts.player1_open_games.as_mut())?;
+    if let Some(lobby) = ctx.accounts.lobby.as_deref_mut() {
+        lobby.unlist(game.key());
+    }
+
+    if let (Some(telemetry), Some(bump)) = (
+        ctx.accounts.client_telemetry.as_mut(),
+        ctx.bumps.client_telemetry,
+    ) {
+        telemetry.game = game.key();
+        telemetry.bump = bump;
+        telemetry.record(2, client);
+    }
+
+    emit!(PlayerJoined {
+        game: game.key(),
+        player: game.player2,
+        stake_lamports: stake,
+        pot_lamports: game.pot_lamports,
+        bot: false,
+    });
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Joined,
+        game.player2,
+        stake,
+    )?;
+
+    verbose_msg!(
+        "join_game: completed, pot_lamports={}, status={:?}",
+        game.pot_lamports,
+        game.status
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
+
+/// Вносит весь банк в config.stake_pool (DepositSol) и заводит PotStake.
+fn stake_pot(accounts: &mut JoinGame, bumps: &JoinGameBumps) -> Result<()> {
+    let (
+        Some(pot_stake),
+        Some(pot_vault),
+        Some(pool_tokens),
+        Some(pool_mint),
+        Some(stake_pool),
+        Some(withdraw_authority),
+        Some(reserve_stake),
+        Some(manager_fee_account),
+        Some(_),
+        Some(token_program),
+        Some(pot_stake_bump),
+        Some(vault_bump),
+    ) = (
+        accounts.pot_stake.as_mut(),
+        accounts.pot_vault.as_ref(),
+        accounts.pool_tokens.as_mut(),
+        accounts.pool_mint.as_ref(),
+        accounts.stake_pool.as_ref(),
+        accounts.stake_pool_withdraw_authority.as_ref(),
+        accounts.reserve_stake.as_ref(),
+        accounts.manager_fee_account.as_ref(),
+        accounts.stake_pool_program.as_ref(),
+        accounts.token_program.as_ref(),
+        bumps.pot_stake,
+        bumps.pot_vault,
+    )
+    else {
+        return err!(ErrorCode::MissingStakePoolAccounts);
+    };
+    let game = &mut accounts.game;
+    require!(game.pot_lamports > 0, ErrorCode::EmptyPot);
+
+    let amount = game.pot_lamports;
+    verbose_msg!(
+        "join_game: staking pot, amount={}, stake_pool={}",
+        amount,
+        stake_pool.key()
+    );
+
+    // Банк временно уходит с аккаунта игры на vault (system-owned,
+    // иначе пул не примет перевод).
+    debit(&game.to_account_info(), amount)?;
+    credit(pot_vault, amount)?;
+
+    let game_key = game.key();
+    let vault_bump = [vault_bump];
+    let vault_seeds: &[&[&[u8]]] = &[&[b"pot_vault", game_key.as_ref(), &vault_bump]];
+    let pool_tokens_info = pool_tokens.to_account_info();
+    // Реферальная доля возвращается на наш же счёт токенов.
+    let ix = StakePoolAccounts {
+        stake_pool: stake_pool.key(),
+        withdraw_authority: withdraw_authority.key(),
+        reserve_stake: reserve_stake.key(),
+        manager_fee_account: manager_fee_account.key(),
+        pool_mint: pool_mint.key(),
+    }
+    .deposit_sol(&pot_vault.key(), &pool_tokens_info.key(), amount)?;
+    invoke_signed(
+        &ix,
+        &[
+            stake_pool.to_account_info(),
+            withdraw_authority.to_account_info(),
+            reserve_stake.to_account_info(),
+            pot_vault.to_account_info(),
+            pool_tokens_info,
+            manager_fee_account.to_account_info(),
+            pool_mint.to_account_info(),
+            accounts.system_program.to_account_info(),
+            token_program.to_account_info(),
+        ],
+        vault_seeds,
+    )?;
+
+    pool_tokens.reload()?;
+    let minted = pool_tokens.amount;
+    require!(minted > 0, ErrorCode::InvalidStakePool);
+
+    pot_stake.game = game_key;
+    pot_stake.stake_pool = stake_pool.key();
+    pot_stake.funder = accounts.player2.key();
+    pot_stake.principal = amount;
+    pot_stake.pool_tokens = minted;
+    pot_stake.unstake_epoch = 0;
+    pot_stake.returned = 0;
+    pot_stake.bump = pot_stake_bump;
+    game.staked_lamports = amount;
+
+    emit!(PotStaked {
+        game: game_key,
+        lamports: amount,
+        pool_tokens: minted,
+    });
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::*;
+
+/// Контекст для регистрации в турнире.
+#[derive(Accounts)]
+pub struct JoinTournament<'info> {
+    /// Аккаунт турнира.
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+
+    /// Участник, платит взнос.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<JoinTournament>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let tournament = &mut ctx.accounts.tournament;
+    let player = ctx.accounts.player.key();
+
+    verbose_msg!(
+        "join_tournament: tournament={}, player={}, player_count={}",
+        tournament.key(),
+        player,
+        tournament.player_count
+    );
+
+    require!(
+        tournament.status == TournamentStatus::Registration,
+        ErrorCode::TournamentNotInRegistration
+    );
+    require!(
+        tournament.player_count < tournament.max_players,
+        ErrorCode::TournamentFull
+    );
+    let count = tournament.player_count as usize;
+    require!(
+        !tournament.players[..count].contains(&player),
+        ErrorCode::AlreadyInTournament
+    );
+
+    let fee = tournament.entry_fee;
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player.to_account_info(),
+        to: tournament.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, fee)?;
+
+    tournament.players[count] = player;
+    tournament.player_count = tournament
+        .player_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    tournament.prize_pool = tournament
+        .prize_pool
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
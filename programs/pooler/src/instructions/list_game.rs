@@ -0,0 +1,62 @@
+use crate::*;
+
+/// Контекст для публикации игры в лобби.
+#[derive(Accounts)]
+pub struct ListGame<'info> {
+    /// Открытая игра.
+    pub game: Account<'info, GameState>,
+
+    /// Создатель игры.
+    #[account(address = game.player1)]
+    pub player1: Signer<'info>,
+
+    /// Шард лобби.
+    #[account(mut, seeds = [b"lobby".as_ref(), &[lobby.shard]], bump = lobby.bump)]
+    pub lobby: Box<Account<'info, Lobby>>,
+
+    /// CHECK: PDA [b"stats", player1] enforced by seeds; may be uninitialized (default rating).
+    #[account(seeds = [b"stats", player1.key().as_ref()], bump)]
+    pub player1_stats: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ListGame>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    require!(
+        game.status == GameStatus::WaitingForPlayer2,
+        ErrorCode::GameNotWaitingForPlayer2
+    );
+
+    let stats_info = &ctx.accounts.player1_stats;
+    let creator_rating = if stats_info.owner == &crate::ID {
+        let data = stats_info.try_borrow_data()?;
+        PlayerStats::try_deserialize(&mut &data[..])?.rating
+    } else {
+        DEFAULT_RATING
+    };
+
+    let lobby = &mut ctx.accounts.lobby;
+    require!(
+        lobby.entries.iter().all(|e| e.game != game.key()),
+        ErrorCode::GameAlreadyListed
+    );
+    require!(
+        lobby.entries.len() < LOBBY_SHARD_CAPACITY as usize,
+        ErrorCode::LobbyFull
+    );
+    lobby.entries.push(LobbyEntry {
+        game: game.key(),
+        stake_lamports: game.stake_lamports,
+        rule_variant: game.rule_variant,
+        creator_rating,
+    });
+
+    verbose_msg!(
+        "list_game: shard={}, game_id={}, stake={}, creator_rating={}, entries={}",
+        lobby.shard,
+        game.game_id,
+        game.stake_lamports,
+        creator_rating,
+        lobby.entries.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use crate::*;
+
+/// Контекст для совершения хода.
+#[derive(Accounts)]
+pub struct MakeMove<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Ходящий: основной ключ стороны current_turn, её сессионный ключ или
+    /// напарник (проверяется в обработчике, GameState::mover_member); платит комиссию.
+    #[account(mut)]
+    pub mover: Signer<'info>,
+
+    /// CHECK: key of the side not on turn, enforced by the address constraint; never signs
+    #[account(address = game.player_key(3 - game.current_turn) @ ErrorCode::InvalidPlayer)]
+    pub opponent: UncheckedAccount<'info>,
+
+    /// Системная программа Solana, нужна для transfer через CPI.
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; required only for bot games.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: Option<UncheckedAccount<'info>>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Составы команд; обязателен для командных игр.
+    #[account(mut, seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Option<Account<'info, TeamState>>,
+
+    /// Контроль времени; обязателен для пресетов, кроме Casual.
+    #[account(mut, seeds = [b"time_control", game.key().as_ref()], bump = time_control.bump)]
+    pub time_control: Option<Account<'info, TimeControl>>,
+
+    /// Получатель комиссий организатора; обязателен, если game.fee_routed.
+    #[account(mut, seeds = [b"fee_route", game.key().as_ref()], bump = fee_route.bump)]
+    pub fee_route: Option<Account<'info, FeeRoute>>,
+
+    /// CHECK: must equal fee_route.fee_recipient, checked in the handler; only credited
+    #[account(mut, constraint = fee_recipient.key() != game.key() @ ErrorCode::PayoutAliasesGame)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Страховой пул; обязателен при config.insurance_fee_bps > 0.
+    #[account(mut, seeds = [b"insurance_pool"], bump = insurance_pool.bump)]
+    pub insurance_pool: Option<Account<'info, InsurancePool>>,
+
+    /// Телеметрия клиентов (необязательно).
+    #[account(mut, seeds = [b"client_telemetry", game.key().as_ref()], bump = client_telemetry.bump)]
+    pub client_telemetry: Option<Account<'info, ClientTelemetry>>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Текущий чанк истории ходов; обязателен, если game.move_history.
+    #[account(
+        mut,
+        constraint = game_history.game == game.key()
+            && game_history.chunk_index == game.current_history_chunk
+            @ ErrorCode::GameHistoryMismatch,
+    )]
+    pub game_history: Option<Box<Account<'info, GameHistory>>>,
+
+    /// Merkle-коммитмент ходов; обязателен, если game.history_commitment.
+    #[account(
+        mut,
+        constraint = history_commitment.game == game.key() @ ErrorCode::HistoryCommitmentMismatch,
+    )]
+    pub history_commitment: Option<Box<Account<'info, HistoryCommitment>>>,
+
+    /// CHECK: must equal config.decay_destination(), checked in decay_pot; only credited
+    #[account(mut)]
+    pub decay_sink: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(
+    ctx: Context<MakeMove>,
+    new_board_points: [i8; 24],
+    new_dice: [u8; 2],
+    annotation_hash: [u8; 32],
+    client: Option<ClientInfo>,
+) -> Result<MoveOutcome> {
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "make_move: game_id={}, move_index={}, current_turn={}, status={:?}",
+        game.game_id,
+        game.move_index,
+        game.current_turn,
+        game.status
+    );
+
+    game.check_can_move(ctx.accounts.config.paused, &new_board_points)?;
+
+    require!(
+        !game.team_game || ctx.accounts.team.is_some(),
+        ErrorCode::MissingTeamAccount
+    );
+    let mover_side = game.current_turn;
+    let mover = ctx.accounts.mover.to_account_info();
+    // fee_member — участник (индекс TeamState), в чей вклад идёт комиссия.
+    let fee_member = game.mover_member(
+        &mover.key(),
+        ctx.accounts.team.as_deref(),
+        Clock::get()?.slot,
+    )?;
+    verbose_msg!("make_move: mover={}, side={}", mover.key(), mover_side);
+
+    // Списываем комиссию за ход в пользу банка
+    let (time_control, move_fee) = charge_time_control(
+        game,
+        ctx.accounts.time_control.as_mut(),
+        mover_side,
+        game.free_moves(&ctx.accounts.config),
+    )?;
+    verbose_msg!(
+        "make_move: charging move_fee={}, from_player={}",
+        move_fee,
+        mover.key()
+    );
+
+    let fee_to = fee_destination(
+        game,
+        ctx.accounts.fee_route.as_deref(),
+        ctx.accounts.fee_recipient.as_ref(),
+    )?;
+
+    // В тренировочной игре и в бесплатном окне комиссии нет и переводов не делаем.
+    if !game.practice && move_fee > 0 {
+        // В игре с ботом комиссию за ходы бота платит bot_vault, а не ключ оператора.
+        if game.bot_game && game.current_turn == 2 {
+            let bot_vault = ctx
+                .accounts
+                .bot_vault
+                .as_ref()
+                .ok_or(ErrorCode::MissingBotVault)?;
+            require!(
+                bot_vault.lamports() >= move_fee,
+                ErrorCode::NotEnoughBalanceForMove
+            );
+            transfer_from_bot_vault(
+                ctx.accounts.system_program.to_account_info(),
+                bot_vault.to_account_info(),
+                fee_to,
+                ctx.bumps.bot_vault.ok_or(ErrorCode::MissingBotVault)?,
+                move_fee,
+            )?;
+        } else {
+            // Проверяем, что у игрока достаточно средств для оплаты хода.
+            let from_lamports = mover.lamports();
+            require!(
+                from_lamports >= move_fee,
+                ErrorCode::NotEnoughBalanceForMove
+            );
+
+            let cpi_accounts = system_program::Transfer {
+                from: mover,
+                to: fee_to,
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, move_fee)?;
+        }
+    }
+
+    let insured = take_insurance_cut(
+        game,
+        ctx.accounts.insurance_pool.as_mut(),
+        ctx.accounts.config.insurance_fee_bps,
+        move_fee,
+    )?;
+    book_move_fee(
+        game,
+        ctx.accounts.fee_route.as_mut(),
+        ctx.accounts.team.as_mut(),
+        fee_member,
+        move_fee - insured,
+    )?;
+    let decayed = decay_pot(game, &ctx.accounts.config, ctx.accounts.decay_sink.as_ref())?;
+    if decayed > 0 {
+        verbose_msg!("make_move: decayed={}, pot={}", decayed, game.pot_lamports);
+    }
+    if let Some(telemetry) = ctx.accounts.client_telemetry.as_mut() {
+        telemetry.record(mover_side, client);
+    }
+    let mover = game.player_key(game.current_turn);
+    record_move(
+        ctx.accounts.game_history.as_deref_mut(),
+        ctx.accounts.history_commitment.as_deref_mut(),
+        game,
+        &new_board_points,
+        new_dice,
+        move_fee,
+    )?;
+    let outcome = commit_move(
+        game,
+        new_board_points,
+        new_dice,
+        annotation_hash,
+        time_control.move_timeout_slots,
+        move_fee,
+        client,
+    )?;
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Move,
+        mover,
+        outcome.move_index,
+    )?;
+    assert_pot_invariant(game)?;
+    Ok(outcome)
+}
@@ -0,0 +1,12 @@
+use crate::*;
+
+pub fn handler(
+    ctx: Context<MakeMove>,
+    packed_board: PackedBoard,
+    new_dice: [u8; 2],
+    annotation_hash: [u8; 32],
+    client: Option<ClientInfo>,
+) -> Result<MoveOutcome> {
+    let new_board_points = unpack_board(&packed_board)?;
+    super::make_move::handler(ctx, new_board_points, new_dice, annotation_hash, client)
+}
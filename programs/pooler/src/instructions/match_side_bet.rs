@@ -0,0 +1,66 @@
+use crate::*;
+
+/// Контекст для встречной ставки.
+#[derive(Accounts)]
+pub struct MatchSideBet<'info> {
+    /// Игра, на исход которой сделана ставка; считает сведённые пари.
+    #[account(mut, address = side_bet.game)]
+    pub game: Account<'info, GameState>,
+
+    /// Аккаунт пари.
+    #[account(
+        mut,
+        seeds = [b"side_bet", game.key().as_ref(), side_bet.bettor.as_ref(), &side_bet.bet_id.to_le_bytes()],
+        bump = side_bet.bump,
+    )]
+    pub side_bet: Account<'info, SideBet>,
+
+    /// Контрагент, ставит на противоположный исход.
+    #[account(mut)]
+    pub counterparty: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MatchSideBet>) -> Result<()> {
+    let counterparty = ctx.accounts.counterparty.key();
+    let side_bet = &ctx.accounts.side_bet;
+
+    verbose_msg!(
+        "match_side_bet: side_bet={}, counterparty={}, amount={}",
+        side_bet.key(),
+        counterparty,
+        side_bet.amount
+    );
+
+    require!(
+        side_bet.status == SideBetStatus::Open,
+        ErrorCode::SideBetNotOpen
+    );
+    require!(
+        !ctx.accounts.game.status.is_over(),
+        ErrorCode::GameNotActive
+    );
+    require!(counterparty != side_bet.bettor, ErrorCode::InvalidPlayer);
+
+    let amount = side_bet.amount;
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.counterparty.to_account_info(),
+        to: ctx.accounts.side_bet.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, amount)?;
+
+    let side_bet = &mut ctx.accounts.side_bet;
+    side_bet.counterparty = counterparty;
+    side_bet.status = SideBetStatus::Matched;
+    // Пока пари не рассчитано, игру нельзя закрыть (prune_games, escheat).
+    let game = &mut ctx.accounts.game;
+    game.unsettled_side_bets = game
+        .unsettled_side_bets
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
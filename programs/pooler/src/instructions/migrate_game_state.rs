@@ -0,0 +1,43 @@
+use crate::*;
+
+/// Контекст для migrate_game_state.
+#[derive(Accounts)]
+pub struct MigrateGameState<'info> {
+    /// CHECK: owner constraint plus discriminator and length checked in check_legacy_game_state
+    #[account(mut, owner = crate::ID)]
+    pub game: UncheckedAccount<'info>,
+
+    /// Доплачивает ренту за новые байты.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateGameState>) -> Result<()> {
+    let game_info = ctx.accounts.game.to_account_info();
+    let new_len = 8 + GameState::MAX_SIZE;
+    let old_version = check_legacy_game_state(&game_info)?;
+
+    verbose_msg!(
+        "migrate_game_state: game={}, version={}, old_len={}, new_len={}",
+        game_info.key(),
+        old_version,
+        game_info.data_len(),
+        new_len
+    );
+
+    let shortfall = migration_rent(&Rent::get()?, game_info.data_len(), new_len);
+    if shortfall > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: game_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, shortfall)?;
+    }
+    game_info.resize(new_len)?;
+
+    let mut data = game_info.try_borrow_mut_data()?;
+    migrate_game_state_data(&mut data, old_version)
+}
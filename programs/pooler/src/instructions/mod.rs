@@ -0,0 +1,216 @@
+//! Инструкции программы: по файлу на инструкцию, в файле — её контекст
+//! Accounts (если он не общий с инструкцией выше по списку) и `handler`,
+//! которому делегирует одноимённая функция модуля `backgammon`. Обработчики
+//! можно вызывать напрямую: `instructions::<имя>::handler(ctx, ...)`.
+
+#![allow(ambiguous_glob_reexports)]
+
+pub mod accept_admin;
+pub mod accept_counter;
+pub mod ack_move;
+pub mod add_arbiter;
+pub mod add_ban;
+pub mod add_teammate;
+pub mod advance_round;
+pub mod appeal;
+pub mod arbiter_resolve;
+pub mod authorize_session_key;
+pub mod auto_take;
+pub mod beaver_double;
+pub mod bot_join;
+pub mod cancel_before_join;
+pub mod chouette_drop;
+pub mod chouette_offer_double;
+pub mod chouette_take;
+pub mod claim_pot_unstake;
+pub mod claim_team_share;
+pub mod claim_win;
+pub mod close_event_buffer;
+pub mod close_game_history;
+pub mod close_game_log;
+pub mod close_history_commitment;
+pub mod close_payout_splits;
+pub mod close_relay_budget;
+pub mod close_season;
+pub mod compensate;
+pub mod contest_claim;
+pub mod counter_offer;
+pub mod create_tournament;
+pub mod drop_double;
+pub mod escheat;
+pub mod execute_resolution;
+pub mod finalize_claim;
+pub mod finalize_from_board;
+pub mod finalize_pending;
+pub mod finalize_swiss;
+pub mod finish_game;
+pub mod force_refund;
+pub mod fund_bot_vault;
+pub mod fund_relay_budget;
+pub mod get_game_summary;
+pub mod init_chouette;
+pub mod init_config;
+pub mod init_event_buffer;
+pub mod init_fee_vault;
+pub mod init_game;
+pub mod init_insurance_pool;
+pub mod init_lobby_shard;
+pub mod init_mirror_pair;
+pub mod init_player_stats;
+pub mod integrity_check;
+pub mod join_chouette;
+pub mod join_game;
+pub mod join_tournament;
+pub mod list_game;
+pub mod make_move;
+pub mod make_move_packed;
+pub mod manual_refund;
+pub mod match_side_bet;
+pub mod migrate_game_state;
+pub mod offer_double;
+pub mod open_side_bet;
+pub mod pair_round;
+pub mod propose_admin;
+pub mod prune_games;
+pub mod prune_lobby;
+pub mod raccoon_double;
+pub mod reclaim_side_bet;
+pub mod record_opening_tie;
+pub mod record_strike;
+pub mod reject_move;
+pub mod relayed_move;
+pub mod remove_arbiter;
+pub mod remove_ban;
+pub mod renounce_admin;
+pub mod reopen_invite;
+pub mod report_result;
+pub mod report_swiss_result;
+pub mod request_pot_unstake;
+pub mod revoke_session_key;
+pub mod rotate_history_chunk;
+pub mod rotate_player_key;
+pub mod set_auto_take;
+pub mod set_payout_splits;
+pub mod settle_by_move_cap;
+pub mod settle_chouette;
+pub mod settle_game;
+pub mod settle_pair;
+pub mod settle_side_bet;
+pub mod settle_tournament;
+pub mod start_game_log;
+pub mod start_tournament;
+pub mod submit_evidence;
+pub mod submit_game_log;
+pub mod swap_sides;
+pub mod take_double;
+pub mod tip_pot;
+pub mod unlist_game;
+pub mod update_config;
+pub mod validate_move;
+pub mod verify_history_entry;
+pub mod withdraw_bot_vault;
+pub mod withdraw_counter;
+pub mod withdraw_protocol_fees;
+
+// Контексты Accounts (и модули, которые Anchor генерирует рядом с ними)
+// нужны в корне крейта, поэтому модули с контекстами реэкспортируются
+// целиком. handler при этом неоднозначен, но снаружи он вызывается только
+// по полному пути.
+pub use accept_admin::*;
+pub use accept_counter::*;
+pub use ack_move::*;
+pub use add_arbiter::*;
+pub use add_ban::*;
+pub use add_teammate::*;
+pub use appeal::*;
+pub use arbiter_resolve::*;
+pub use authorize_session_key::*;
+pub use auto_take::*;
+pub use beaver_double::*;
+pub use bot_join::*;
+pub use cancel_before_join::*;
+pub use chouette_drop::*;
+pub use chouette_offer_double::*;
+pub use chouette_take::*;
+pub use claim_pot_unstake::*;
+pub use claim_team_share::*;
+pub use claim_win::*;
+pub use close_event_buffer::*;
+pub use close_game_history::*;
+pub use close_game_log::*;
+pub use close_history_commitment::*;
+pub use close_payout_splits::*;
+pub use close_relay_budget::*;
+pub use close_season::*;
+pub use compensate::*;
+pub use contest_claim::*;
+pub use counter_offer::*;
+pub use create_tournament::*;
+pub use drop_double::*;
+pub use escheat::*;
+pub use execute_resolution::*;
+pub use finalize_claim::*;
+pub use finalize_from_board::*;
+pub use finalize_pending::*;
+pub use finish_game::*;
+pub use force_refund::*;
+pub use fund_bot_vault::*;
+pub use fund_relay_budget::*;
+pub use get_game_summary::*;
+pub use init_chouette::*;
+pub use init_config::*;
+pub use init_event_buffer::*;
+pub use init_fee_vault::*;
+pub use init_game::*;
+pub use init_insurance_pool::*;
+pub use init_lobby_shard::*;
+pub use init_mirror_pair::*;
+pub use init_player_stats::*;
+pub use integrity_check::*;
+pub use join_chouette::*;
+pub use join_game::*;
+pub use join_tournament::*;
+pub use list_game::*;
+pub use make_move::*;
+pub use manual_refund::*;
+pub use match_side_bet::*;
+pub use migrate_game_state::*;
+pub use offer_double::*;
+pub use open_side_bet::*;
+pub use propose_admin::*;
+pub use prune_games::*;
+pub use prune_lobby::*;
+pub use raccoon_double::*;
+pub use reclaim_side_bet::*;
+pub use record_opening_tie::*;
+pub use record_strike::*;
+pub use relayed_move::*;
+pub use remove_arbiter::*;
+pub use remove_ban::*;
+pub use reopen_invite::*;
+pub use report_result::*;
+pub use request_pot_unstake::*;
+pub use rotate_history_chunk::*;
+pub use rotate_player_key::*;
+pub use set_auto_take::*;
+pub use set_payout_splits::*;
+pub use settle_by_move_cap::*;
+pub use settle_chouette::*;
+pub use settle_game::*;
+pub use settle_pair::*;
+pub use settle_side_bet::*;
+pub use settle_tournament::*;
+pub use start_game_log::*;
+pub use start_tournament::*;
+pub use submit_evidence::*;
+pub use submit_game_log::*;
+pub use swap_sides::*;
+pub use take_double::*;
+pub use tip_pot::*;
+pub use unlist_game::*;
+pub use update_config::*;
+pub use validate_move::*;
+pub use verify_history_entry::*;
+pub use withdraw_bot_vault::*;
+pub use withdraw_counter::*;
+pub use withdraw_protocol_fees::*;
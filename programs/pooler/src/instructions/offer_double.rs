@@ -0,0 +1,89 @@
+use crate::*;
+
+/// Контекст для предложения удвоения.
+#[derive(Accounts)]
+pub struct OfferDouble<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Игрок на ходу, вносит доплату за удвоение.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Системная программа Solana, нужна для transfer через CPI.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<OfferDouble>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "offer_double: game_id={}, cube_value={}, cube_owner={}, current_turn={}",
+        game.game_id,
+        game.cube_value,
+        game.cube_owner,
+        game.current_turn
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(!game.double_pending, ErrorCode::DoublePending);
+    require!(!game.bot_game, ErrorCode::CubeDisabledInBotGame);
+    require!(!game.team_game, ErrorCode::CubeDisabledInTeamGame);
+    require!(!game.move_pending, ErrorCode::MovePending);
+
+    // Удваивать может только игрок, который сейчас на ходу.
+    let doubler = ctx.accounts.player.key();
+    let expected = match game.current_turn {
+        1 => game.player1,
+        2 => game.player2,
+        _ => return Err(ErrorCode::InvalidCurrentTurn.into()),
+    };
+    require_keys_eq!(doubler, expected, ErrorCode::NotPlayersTurn);
+
+    // Доступ к кубу: куб в центре или у самого игрока.
+    require!(
+        game.cube_owner == 0 || game.cube_owner == game.current_turn,
+        ErrorCode::NoCubeAccess
+    );
+    require!(game.cube_value < MAX_CUBE_VALUE, ErrorCode::CubeAtMaximum);
+
+    // Доплата каждого игрока при удвоении равна его текущей ставке.
+    let extra = game
+        .stake_lamports
+        .checked_mul(game.cube_value as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!(
+        "offer_double: escrowing extra={} from doubler={}",
+        extra,
+        doubler
+    );
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player.to_account_info(),
+        to: game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, extra)?;
+
+    let doubler_turn = game.current_turn;
+    game.add_deposit(doubler_turn, extra)?;
+
+    game.double_pending = true;
+    game.last_activity_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "offer_double: completed, pot_lamports={}, double_pending={}",
+        game.pot_lamports,
+        game.double_pending
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::*;
+
+/// Контекст для открытия зрительского пари.
+#[derive(Accounts)]
+#[instruction(bet_id: u64)]
+pub struct OpenSideBet<'info> {
+    /// Игра, на исход которой делается ставка.
+    pub game: Account<'info, GameState>,
+
+    /// Аккаунт пари, PDA от игры, автора и bet_id.
+    #[account(
+        init,
+        payer = bettor,
+        space = 8 + SideBet::MAX_SIZE,
+        seeds = [b"side_bet", game.key().as_ref(), bettor.key().as_ref(), &bet_id.to_le_bytes()],
+        bump,
+    )]
+    pub side_bet: Account<'info, SideBet>,
+
+    /// Автор пари, вносит ставку и платит за аккаунт.
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(
+    ctx: Context<OpenSideBet>,
+    bet_id: u64,
+    predicted_winner: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &ctx.accounts.game;
+
+    verbose_msg!(
+        "open_side_bet: game={}, bettor={}, bet_id={}, predicted_winner={}, amount={}",
+        game.key(),
+        ctx.accounts.bettor.key(),
+        bet_id,
+        predicted_winner,
+        amount
+    );
+
+    require!(!game.status.is_over(), ErrorCode::GameNotActive);
+    require!(
+        predicted_winner == game.player1 || predicted_winner == game.player2,
+        ErrorCode::InvalidPlayer
+    );
+    require!(amount > 0, ErrorCode::InvalidSideBetAmount);
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.bettor.to_account_info(),
+        to: ctx.accounts.side_bet.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, amount)?;
+
+    let side_bet = &mut ctx.accounts.side_bet;
+    side_bet.game = game.key();
+    side_bet.bettor = ctx.accounts.bettor.key();
+    side_bet.counterparty = Pubkey::default();
+    side_bet.bet_id = bet_id;
+    side_bet.predicted_winner = predicted_winner;
+    side_bet.predicted_player = game.creation_index(if predicted_winner == game.player1 {
+        1
+    } else {
+        2
+    });
+    side_bet.amount = amount;
+    side_bet.status = SideBetStatus::Open;
+    side_bet.bump = ctx.bumps.side_bet;
+
+    Ok(())
+}
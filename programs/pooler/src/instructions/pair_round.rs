@@ -0,0 +1,92 @@
+use crate::*;
+
+pub fn handler(ctx: Context<TournamentOrganizer>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+
+    require!(
+        tournament.status == TournamentStatus::Running,
+        ErrorCode::TournamentNotRunning
+    );
+    require!(
+        tournament.mode == TournamentMode::Swiss,
+        ErrorCode::WrongTournamentMode
+    );
+    require!(
+        tournament.round < tournament.swiss_rounds,
+        ErrorCode::SwissRoundsOver
+    );
+
+    let count = tournament.player_count as usize;
+    let all_mask: u32 = if count == 32 {
+        u32::MAX
+    } else {
+        (1u32 << count) - 1
+    };
+    require!(
+        tournament.round == 0 || tournament.reported & all_mask == all_mask,
+        ErrorCode::RoundNotComplete
+    );
+
+    let order = swiss_order(&tournament.scores, &[0; TOURNAMENT_MAX_PLAYERS], count);
+
+    let mut used: u32 = 0;
+    let mut pairings = [SWISS_BYE; TOURNAMENT_MAX_PLAYERS];
+    let mut reported: u32 = 0;
+
+    if count % 2 == 1 {
+        let bye = order[..count]
+            .iter()
+            .rev()
+            .copied()
+            .find(|&i| tournament.had_bye & (1 << i) == 0)
+            .unwrap_or(order[count - 1]);
+        used |= 1 << bye;
+        reported |= 1 << bye;
+        tournament.had_bye |= 1 << bye;
+        tournament.scores[bye as usize] = tournament.scores[bye as usize]
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        verbose_msg!("pair_round: bye for player_index={}", bye);
+    }
+
+    let mut budget = SWISS_PAIRING_BUDGET;
+    let mut attempt = pairings;
+    if swiss_pair(
+        &order[..count],
+        used,
+        &tournament.opponents,
+        &mut attempt,
+        &mut budget,
+        false,
+    ) {
+        pairings = attempt;
+    } else {
+        msg!("pair_round: rematch-free pairing not found, allowing rematches");
+        let mut budget = u32::MAX;
+        swiss_pair(
+            &order[..count],
+            used,
+            &tournament.opponents,
+            &mut pairings,
+            &mut budget,
+            true,
+        );
+    }
+
+    for (i, &opp) in pairings.iter().enumerate().take(count) {
+        if opp != SWISS_BYE {
+            tournament.opponents[i] |= 1 << opp;
+        }
+    }
+
+    tournament.pairings = pairings;
+    tournament.reported = reported;
+    tournament.round = tournament
+        .round
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!("pair_round: round={} paired", tournament.round);
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::*;
+
+/// Контекст для предложения нового администратора.
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    /// Глобальный конфиг.
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// Текущий администратор.
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_admin = new_admin;
+
+    verbose_msg!(
+        "propose_admin: admin={}, pending_admin={}",
+        config.admin,
+        new_admin
+    );
+    emit!(AdminProposed {
+        admin: config.admin,
+        pending_admin: new_admin,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::*;
+
+/// Контекст для закрытия старых завершённых игр.
+#[derive(Accounts)]
+pub struct PruneGames<'info> {
+    /// Глобальный конфиг (срок хранения и награда).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Вызвавший; получает prune_reward_bps от ренты закрытых игр.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, PruneGames<'info>>) -> Result<()> {
+    let pairs = ctx.remaining_accounts;
+    require!(
+        pairs.len() % 2 == 0 && pairs.len() <= 2 * MAX_PRUNE_BATCH as usize,
+        ErrorCode::InvalidPruneBatch
+    );
+
+    let config = &ctx.accounts.config;
+    let caller = ctx.accounts.caller.to_account_info();
+    let current_slot = Clock::get()?.slot;
+    let retention = config.prune_threshold();
+    let rent_sysvar = Rent::get()?;
+    let (mut pruned, mut skipped) = (0u8, 0u8);
+    let (mut caller_reward, mut payer_refund) = (0u64, 0u64);
+
+    for pair in pairs.chunks(2) {
+        let (game_info, payer_info) = (&pair[0], &pair[1]);
+        // Аккаунт, не прошедший проверки, пропускается: один неподходящий
+        // аккаунт не должен ронять всю пачку. Лишние lamports на аккаунте
+        // игры пруну не мешают: иначе любой мог бы защитить игру от закрытия,
+        // переведя на неё 1 lamport.
+        let prunable = game_info.owner == &crate::ID
+            && game_info.is_writable
+            && payer_info.is_writable
+            && !game_info.data_is_empty()
+            && game_info.lamports() >= rent_sysvar.minimum_balance(game_info.data_len())
+            && GameState::try_deserialize(&mut &game_info.try_borrow_data()?[..]).is_ok_and(|g| {
+                g.player1 == payer_info.key() && g.prunable_at(current_slot, retention)
+            });
+        if !prunable {
+            verbose_msg!("prune_games: skipped game={}", game_info.key());
+            skipped += 1;
+            continue;
+        }
+
+        // Награда вызвавшего считается от ренты, остальной баланс (включая
+        // переведённое сверх ренты) уходит плательщику ренты — создателю
+        // игры (player1).
+        let rent = rent_sysvar.minimum_balance(game_info.data_len());
+        let balance = game_info.lamports();
+        let reward = prune_reward(rent, config.prune_reward_bps);
+        **game_info.try_borrow_mut_lamports()? = 0;
+        credit(&caller, reward)?;
+        credit(payer_info, balance - reward)?;
+        // Обнулённые данные позволяют сменить владельца без resize; аккаунт
+        // без лампортов рантайм удалит в конце транзакции.
+        game_info.try_borrow_mut_data()?.fill(0);
+        game_info.assign(&system_program::ID);
+
+        verbose_msg!(
+            "prune_games: closed game={}, balance={}, reward={}",
+            game_info.key(),
+            balance,
+            reward
+        );
+        pruned += 1;
+        caller_reward = caller_reward
+            .checked_add(reward)
+            .ok_or(ErrorCode::MathOverflow)?;
+        payer_refund = payer_refund
+            .checked_add(balance - reward)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    emit!(GamesPruned {
+        caller: caller.key(),
+        pruned,
+        skipped,
+        caller_reward,
+        payer_refund,
+    });
+    Ok(())
+}
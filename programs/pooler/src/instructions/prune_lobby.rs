@@ -0,0 +1,35 @@
+use crate::*;
+
+/// Контекст для очистки шарда лобби.
+#[derive(Accounts)]
+pub struct PruneLobby<'info> {
+    /// Шард лобби.
+    #[account(mut, seeds = [b"lobby".as_ref(), &[lobby.shard]], bump = lobby.bump)]
+    pub lobby: Box<Account<'info, Lobby>>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, PruneLobby<'info>>) -> Result<()> {
+    let lobby = &mut ctx.accounts.lobby;
+    let mut pruned: u32 = 0;
+
+    for game_info in ctx.remaining_accounts.iter() {
+        if lobby.entries.iter().all(|e| e.game != game_info.key()) {
+            continue;
+        }
+        let waiting = game_info.owner == &crate::ID
+            && !game_info.data_is_empty()
+            && GameState::try_deserialize(&mut &game_info.try_borrow_data()?[..])
+                .is_ok_and(|g| g.status == GameStatus::WaitingForPlayer2);
+        if !waiting && lobby.unlist(game_info.key()) {
+            pruned += 1;
+        }
+    }
+
+    verbose_msg!(
+        "prune_lobby: shard={}, pruned={}, entries={}",
+        lobby.shard,
+        pruned,
+        lobby.entries.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::*;
+
+/// Контекст для енота: доплаты вносят оба игрока.
+#[derive(Accounts)]
+pub struct RaccoonDouble<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок, должен совпадать с game.player1.
+    #[account(mut, address = game.player1)]
+    pub player1: Signer<'info>,
+
+    /// Второй игрок, должен совпадать с game.player2.
+    #[account(
+        mut,
+        address = game.player2,
+        constraint = player2.key() != player1.key() @ ErrorCode::DuplicatePlayerAccounts,
+    )]
+    pub player2: Signer<'info>,
+
+    /// Системная программа Solana, нужна для transfer через CPI.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<RaccoonDouble>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "raccoon_double: game_id={}, cube_value={}, current_turn={}",
+        game.game_id,
+        game.cube_value,
+        game.current_turn
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.beavers_allowed, ErrorCode::BeaversNotAllowed);
+    require!(game.raccoon_pending, ErrorCode::NoRaccoonWindow);
+
+    let new_cube = game
+        .cube_value
+        .checked_mul(2)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(new_cube <= MAX_CUBE_VALUE, ErrorCode::CubeAtMaximum);
+
+    // Каждый игрок доплачивает свою текущую ставку.
+    let extra = game
+        .stake_lamports
+        .checked_mul(game.cube_value as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!(
+        "raccoon_double: escrowing extra={} from both players, new_cube={}",
+        extra,
+        new_cube
+    );
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player1.to_account_info(),
+        to: game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, extra)?;
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player2.to_account_info(),
+        to: game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, extra)?;
+
+    game.add_deposit(1, extra)?;
+    game.add_deposit(2, extra)?;
+
+    game.cube_value = new_cube;
+    game.raccoon_pending = false;
+    game.last_activity_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "raccoon_double: completed, cube_value={}, cube_owner={}, pot_lamports={}",
+        game.cube_value,
+        game.cube_owner,
+        game.pot_lamports
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
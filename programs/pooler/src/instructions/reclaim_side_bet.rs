@@ -0,0 +1,36 @@
+use crate::*;
+
+/// Контекст для возврата несведённого пари.
+#[derive(Accounts)]
+pub struct ReclaimSideBet<'info> {
+    /// Аккаунт пари, закрывается на bettor вместе со ставкой.
+    #[account(
+        mut,
+        seeds = [b"side_bet", side_bet.game.as_ref(), bettor.key().as_ref(), &side_bet.bet_id.to_le_bytes()],
+        bump = side_bet.bump,
+        close = bettor,
+    )]
+    pub side_bet: Account<'info, SideBet>,
+
+    /// Автор пари.
+    #[account(mut, address = side_bet.bettor)]
+    pub bettor: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReclaimSideBet>) -> Result<()> {
+    let side_bet = &ctx.accounts.side_bet;
+
+    verbose_msg!(
+        "reclaim_side_bet: side_bet={}, bettor={}, amount={}",
+        side_bet.key(),
+        side_bet.bettor,
+        side_bet.amount
+    );
+
+    require!(
+        side_bet.status == SideBetStatus::Open,
+        ErrorCode::SideBetNotOpen
+    );
+
+    Ok(())
+}
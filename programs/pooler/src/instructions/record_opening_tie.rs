@@ -0,0 +1,126 @@
+use crate::*;
+
+/// Контекст для фиксации ничьей на стартовом броске.
+#[derive(Accounts)]
+pub struct RecordOpeningTie<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок, должен совпадать с game.player1.
+    #[account(mut, address = game.player1)]
+    pub player1: Signer<'info>,
+
+    /// Второй игрок, должен совпадать с game.player2.
+    #[account(
+        mut,
+        address = game.player2,
+        constraint = player2.key() != player1.key() @ ErrorCode::DuplicatePlayerAccounts,
+    )]
+    pub player2: Signer<'info>,
+
+    /// Системная программа Solana, нужна для transfer через CPI.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<RecordOpeningTie>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "record_opening_tie: game_id={}, cube_value={}, auto_doubles_done={}",
+        game.game_id,
+        game.cube_value,
+        game.auto_doubles_done
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.auto_doubles, ErrorCode::AutoDoublesDisabled);
+    require!(!game.bot_game, ErrorCode::CubeDisabledInBotGame);
+    require!(!game.team_game, ErrorCode::CubeDisabledInTeamGame);
+    require!(!game.move_pending, ErrorCode::MovePending);
+    // Стартовый бросок бывает только до первого хода.
+    require!(game.move_index == 0, ErrorCode::OpeningRollOver);
+    require!(!game.double_pending, ErrorCode::DoublePending);
+
+    let limit_reached =
+        game.auto_double_limit != 0 && game.auto_doubles_done >= game.auto_double_limit;
+    if limit_reached || game.cube_value >= MAX_CUBE_VALUE {
+        verbose_msg!(
+            "record_opening_tie: auto double skipped, limit reached, cube_value={}",
+            game.cube_value
+        );
+        emit!(AutoDoubleSkipped {
+            game: game.key(),
+            reason: AutoDoubleSkipReason::Limit,
+            cube_value: game.cube_value,
+            extra: 0,
+        });
+        return Ok(());
+    }
+
+    let extra = game
+        .stake_lamports
+        .checked_mul(game.cube_value as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let p1_lamports = **ctx.accounts.player1.to_account_info().lamports.borrow();
+    let p2_lamports = **ctx.accounts.player2.to_account_info().lamports.borrow();
+    if p1_lamports < extra || p2_lamports < extra {
+        verbose_msg!(
+            "record_opening_tie: auto double skipped, extra={}, p1_lamports={}, p2_lamports={}",
+            extra,
+            p1_lamports,
+            p2_lamports
+        );
+        emit!(AutoDoubleSkipped {
+            game: game.key(),
+            reason: AutoDoubleSkipReason::InsufficientFunds,
+            cube_value: game.cube_value,
+            extra,
+        });
+        return Ok(());
+    }
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player1.to_account_info(),
+        to: game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, extra)?;
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player2.to_account_info(),
+        to: game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, extra)?;
+
+    game.add_deposit(1, extra)?;
+    game.add_deposit(2, extra)?;
+
+    // Автоудвоение оставляет куб в центре.
+    game.cube_value = game
+        .cube_value
+        .checked_mul(2)
+        .ok_or(ErrorCode::MathOverflow)?;
+    game.auto_doubles_done = game
+        .auto_doubles_done
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    game.last_activity_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "record_opening_tie: completed, cube_value={}, auto_doubles_done={}, pot_lamports={}",
+        game.cube_value,
+        game.auto_doubles_done,
+        game.pot_lamports
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use crate::*;
+
+/// Контекст для страйка за просроченный ход.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RecordStrike<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Игрок, ожидающий хода соперника; получает банк при поражении соперника.
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; required only for bot games.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: Option<UncheckedAccount<'info>>,
+
+    /// Составы команд; обязателен для командных игр.
+    #[account(mut, seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Option<Account<'info, TeamState>>,
+
+    /// Контроль времени; обязателен для пресетов, кроме Casual.
+    #[account(mut, seeds = [b"time_control", game.key().as_ref()], bump = time_control.bump)]
+    pub time_control: Option<Account<'info, TimeControl>>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Текущий сезон; обязателен при config.rating_enforced.
+    #[account(
+        mut,
+        seeds = [b"season".as_ref(), &config.current_season.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+
+    /// Статистика первого игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player1.as_ref()], bump = player1_stats.bump)]
+    pub player1_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Статистика второго игрока; обязательна при config.rating_enforced.
+    #[account(mut, seeds = [b"stats", game.player2.as_ref()], bump = player2_stats.bump)]
+    pub player2_stats: Option<Account<'info, PlayerStats>>,
+
+    /// Хранилище комиссии протокола; обязательно при config.protocol_fee_bps > 0.
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// CHECK: address constraint ensures this is the incinerator; balance only credited.
+    /// Required when config.burn_bps > 0.
+    #[account(mut, address = INCINERATOR)]
+    pub incinerator: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA [b"payout_splits", claimant] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", claimant.key().as_ref()], bump)]
+    pub claimant_splits: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RecordStrike<'info>>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+    let claimant = ctx.accounts.claimant.key();
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    // Неподтверждённый ход закрывается через finalize_pending, а не страйком.
+    require!(!game.move_pending, ErrorCode::MovePending);
+
+    // При непринятом удвоении ход за принимающим, а не за current_turn.
+    let offender_index = match (game.current_turn, game.double_pending) {
+        (1, false) | (2, true) => 1,
+        (2, false) | (1, true) => 2,
+        _ => return Err(ErrorCode::InvalidCurrentTurn.into()),
+    };
+    let claimant_index = 3 - offender_index;
+    require_keys_eq!(
+        claimant,
+        game.player_key(claimant_index),
+        ErrorCode::InvalidPlayer
+    );
+
+    let current_slot = Clock::get()?.slot;
+    verbose_msg!(
+        "record_strike: game_id={}, claimant={}, offender_index={}, current_slot={}, last_activity_slot={}",
+        game.game_id,
+        claimant,
+        offender_index,
+        current_slot,
+        game.last_activity_slot
+    );
+    let deadline =
+        game.move_deadline_slots(offender_index, ctx.accounts.time_control.as_deref())?;
+    require_ctx!(
+        current_slot
+            .checked_sub(game.last_activity_slot)
+            .ok_or(ErrorCode::MathOverflow)?
+            >= deadline,
+        ErrorCode::TimeoutNotReached,
+        Slot {
+            expected: game.last_activity_slot.saturating_add(deadline),
+            actual: current_slot,
+        }
+    );
+
+    let strikes = if offender_index == 1 {
+        &mut game.p1_timeout_strikes
+    } else {
+        &mut game.p2_timeout_strikes
+    };
+    *strikes = strikes.saturating_add(1);
+    let strikes = *strikes;
+    game.last_activity_slot = current_slot;
+
+    // strike_limit = 0 (в том числе у игр, созданных до страйков) — значение по умолчанию.
+    let strike_limit = if game.strike_limit == 0 {
+        DEFAULT_STRIKE_LIMIT
+    } else {
+        game.strike_limit
+    };
+    verbose_msg!(
+        "record_strike: strikes={}, strike_limit={}",
+        strikes,
+        strike_limit
+    );
+
+    if strikes < strike_limit {
+        return Ok(());
+    }
+
+    require!(game.staked_lamports == 0, ErrorCode::PotStaked);
+    // Поражение по страйкам: банк за вычетом комиссии протокола уходит заявителю.
+    let pot = game.pot_lamports;
+    let fee = if game.practice {
+        0
+    } else {
+        protocol_fee(pot, ctx.accounts.config.protocol_fee_bps)
+    };
+    let winnings = pot - fee;
+    let claimant_info = if claimant_index == 1 {
+        ctx.accounts.claimant.to_account_info()
+    } else {
+        player2_funds(
+            game,
+            ctx.accounts.claimant.to_account_info(),
+            ctx.accounts.bot_vault.as_ref(),
+        )?
+    };
+    let claimant_info = team_payee(
+        game,
+        claimant_index,
+        claimant_info,
+        ctx.accounts.team.as_mut(),
+        winnings,
+    )?;
+    let burned = collect_protocol_fee(
+        game,
+        ctx.accounts.fee_vault.as_mut(),
+        ctx.accounts.incinerator.as_ref().map(|a| a.as_ref()),
+        fee,
+        ctx.accounts.config.burn_bps,
+    )?;
+    if fee > 0 {
+        emit_cpi!(ProtocolFeeCollected {
+            game: game.key(),
+            fee,
+            burned,
+        });
+    }
+    pay_out(
+        &game.to_account_info(),
+        claimant_info,
+        ctx.accounts.claimant_splits.as_ref(),
+        &mut ctx.remaining_accounts.iter(),
+        winnings,
+    )?;
+
+    game.pot_lamports = 0;
+    game.tips_lamports = 0;
+    game.double_pending = false;
+    game.finish(FinishReason::Strikes, current_slot);
+    game.winner = claimant;
+    emit_cpi!(game_finished_event(game, winnings));
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Finished,
+        game.winner,
+        winnings,
+    )?;
+
+    record_game_rating(
+        game,
+        claimant,
+        &ctx.accounts.config,
+        ctx.accounts.season.as_mut(),
+        ctx.accounts.player1_stats.as_mut(),
+        ctx.accounts.player2_stats.as_mut(),
+    )?;
+
+    verbose_msg!(
+        "record_strike: forfeit, game_id={}, winner={}, pot={}",
+        game.game_id,
+        game.winner,
+        pot
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use crate::*;
+
+pub fn handler(ctx: Context<RespondMove>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "reject_move: game_id={}, move_index={}, player={}",
+        game.game_id,
+        game.move_index,
+        ctx.accounts.player.key()
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.move_pending, ErrorCode::NoMovePending);
+    require_keys_eq!(
+        ctx.accounts.player.key(),
+        game.player_key(3 - game.current_turn),
+        ErrorCode::InvalidPlayer
+    );
+
+    game.status = GameStatus::Disputed;
+    game.last_activity_slot = Clock::get()?.slot;
+
+    Ok(())
+}
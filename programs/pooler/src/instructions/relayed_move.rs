@@ -0,0 +1,201 @@
+use crate::*;
+
+/// Контекст для хода через ретранслятор.
+#[derive(Accounts)]
+pub struct RelayedMove<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Ретранслятор: единственный подписант, платит сетевую комиссию.
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Бюджет комиссий ходящего; обязателен, если комиссия за ход ненулевая.
+    #[account(
+        mut,
+        seeds = [b"relay_budget", game.key().as_ref(), relay_budget.player.as_ref()],
+        bump = relay_budget.bump,
+    )]
+    pub relay_budget: Option<Account<'info, RelayBudget>>,
+
+    /// Контроль времени; обязателен для пресетов, кроме Casual.
+    #[account(mut, seeds = [b"time_control", game.key().as_ref()], bump = time_control.bump)]
+    pub time_control: Option<Account<'info, TimeControl>>,
+
+    /// Получатель комиссий организатора; обязателен, если game.fee_routed.
+    #[account(mut, seeds = [b"fee_route", game.key().as_ref()], bump = fee_route.bump)]
+    pub fee_route: Option<Account<'info, FeeRoute>>,
+
+    /// CHECK: must equal fee_route.fee_recipient, checked in the handler; only credited
+    #[account(mut, constraint = fee_recipient.key() != game.key() @ ErrorCode::PayoutAliasesGame)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: address constraint ensures this is the instructions sysvar
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Страховой пул; обязателен при config.insurance_fee_bps > 0.
+    #[account(mut, seeds = [b"insurance_pool"], bump = insurance_pool.bump)]
+    pub insurance_pool: Option<Account<'info, InsurancePool>>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Текущий чанк истории ходов; обязателен, если game.move_history.
+    #[account(
+        mut,
+        constraint = game_history.game == game.key()
+            && game_history.chunk_index == game.current_history_chunk
+            @ ErrorCode::GameHistoryMismatch,
+    )]
+    pub game_history: Option<Box<Account<'info, GameHistory>>>,
+
+    /// Merkle-коммитмент ходов; обязателен, если game.history_commitment.
+    #[account(
+        mut,
+        constraint = history_commitment.game == game.key() @ ErrorCode::HistoryCommitmentMismatch,
+    )]
+    pub history_commitment: Option<Box<Account<'info, HistoryCommitment>>>,
+
+    /// CHECK: must equal config.decay_destination(), checked in decay_pot; only credited
+    #[account(mut)]
+    pub decay_sink: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(
+    ctx: Context<RelayedMove>,
+    payload: RelayedMovePayload,
+    nonce: u64,
+    expiry_slot: u64,
+    player_sig: [u8; 64],
+) -> Result<MoveOutcome> {
+    let game_key = ctx.accounts.game.key();
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "relayed_move: game_id={}, move_index={}, current_turn={}, relayer={}",
+        game.game_id,
+        game.move_index,
+        game.current_turn,
+        ctx.accounts.relayer.key()
+    );
+
+    let mover_side = game.current_turn;
+    require!(!game.team_game, ErrorCode::RelayNotSupported);
+    require!(
+        !(game.bot_game && mover_side == 2),
+        ErrorCode::RelayNotSupported
+    );
+
+    // Как и в make_move, подписывает только ходящий.
+    let mover = game.player_key(mover_side);
+    let signers = [mover];
+    let mut data = Vec::new();
+    payload.serialize(&mut data)?;
+    let message = signing::SignedMessage::new(
+        game_key,
+        signing::SignedAction::RelayedMove,
+        nonce,
+        expiry_slot,
+        &data,
+    );
+    let signatures = signing::consume(
+        &ctx.accounts.instructions,
+        game,
+        &game_key,
+        &message,
+        &signers,
+        Clock::get()?.slot,
+    )?;
+    require!(
+        signatures[0] == player_sig,
+        ErrorCode::MissingPlayerSignature
+    );
+    require!(
+        payload.expected_move_index == game.move_index,
+        ErrorCode::RelayPayloadStale
+    );
+    game.check_can_move(ctx.accounts.config.paused, &payload.board_points)?;
+
+    let (time_control, move_fee) = charge_time_control(
+        game,
+        ctx.accounts.time_control.as_mut(),
+        mover_side,
+        game.free_moves(&ctx.accounts.config),
+    )?;
+    let fee_to = fee_destination(
+        game,
+        ctx.accounts.fee_route.as_deref(),
+        ctx.accounts.fee_recipient.as_ref(),
+    )?;
+    verbose_msg!(
+        "relayed_move: charging move_fee={}, player={}",
+        move_fee,
+        mover
+    );
+
+    // Комиссия идёт из RelayBudget (program-owned PDA), сверх ренты.
+    if !game.practice && move_fee > 0 {
+        let budget = ctx
+            .accounts
+            .relay_budget
+            .as_ref()
+            .ok_or(ErrorCode::MissingRelayBudget)?;
+        require_keys_eq!(budget.player, mover, ErrorCode::MissingRelayBudget);
+        let budget_info = budget.to_account_info();
+        let rent_min = Rent::get()?.minimum_balance(budget_info.data_len());
+        require!(
+            budget_info.lamports().saturating_sub(rent_min) >= move_fee,
+            ErrorCode::NotEnoughBalanceForMove
+        );
+        debit(&budget_info, move_fee)?;
+        credit(&fee_to, move_fee)?;
+    }
+
+    let fee_member = ((mover_side - 1) * 2) as usize;
+    let insured = take_insurance_cut(
+        game,
+        ctx.accounts.insurance_pool.as_mut(),
+        ctx.accounts.config.insurance_fee_bps,
+        move_fee,
+    )?;
+    book_move_fee(
+        game,
+        ctx.accounts.fee_route.as_mut(),
+        None,
+        fee_member,
+        move_fee - insured,
+    )?;
+    decay_pot(game, &ctx.accounts.config, ctx.accounts.decay_sink.as_ref())?;
+    record_move(
+        ctx.accounts.game_history.as_deref_mut(),
+        ctx.accounts.history_commitment.as_deref_mut(),
+        game,
+        &payload.board_points,
+        payload.dice,
+        move_fee,
+    )?;
+    let outcome = commit_move(
+        game,
+        payload.board_points,
+        payload.dice,
+        payload.annotation_hash,
+        time_control.move_timeout_slots,
+        move_fee,
+        None,
+    )?;
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Move,
+        mover,
+        outcome.move_index,
+    )?;
+    assert_pot_invariant(game)?;
+    Ok(outcome)
+}
@@ -0,0 +1,30 @@
+use crate::*;
+
+/// Контекст для исключения арбитра из белого списка.
+#[derive(Accounts)]
+pub struct RemoveArbiter<'info> {
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// PDA арбитра, закрывается.
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"arbiter", whitelisted_arbiter.arbiter.as_ref()],
+        bump = whitelisted_arbiter.bump,
+    )]
+    pub whitelisted_arbiter: Account<'info, WhitelistedArbiter>,
+
+    /// Администратор, получает ренту.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveArbiter>) -> Result<()> {
+    verbose_msg!(
+        "remove_arbiter: arbiter={}",
+        ctx.accounts.whitelisted_arbiter.arbiter
+    );
+    Ok(())
+}
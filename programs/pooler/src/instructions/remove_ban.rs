@@ -0,0 +1,22 @@
+use crate::*;
+
+/// Контекст для снятия бана.
+#[derive(Accounts)]
+pub struct RemoveBan<'info> {
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// PDA бана, закрывается.
+    #[account(mut, close = admin, seeds = [b"ban", ban.player.as_ref()], bump = ban.bump)]
+    pub ban: Account<'info, Ban>,
+
+    /// Администратор, получает ренту.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveBan>) -> Result<()> {
+    verbose_msg!("remove_ban: player={}", ctx.accounts.ban.player);
+    Ok(())
+}
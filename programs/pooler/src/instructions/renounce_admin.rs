@@ -0,0 +1,11 @@
+use crate::*;
+
+pub fn handler(ctx: Context<UpdateConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.locked = true;
+    config.pending_admin = Pubkey::default();
+
+    verbose_msg!("renounce_admin: admin={}, config locked", config.admin);
+
+    Ok(())
+}
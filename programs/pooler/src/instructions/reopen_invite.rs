@@ -0,0 +1,66 @@
+use crate::*;
+
+/// Повторное приглашение второго игрока.
+#[derive(Accounts)]
+pub struct ReopenInvite<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок, создатель игры.
+    #[account(address = game.player1)]
+    pub player1: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<ReopenInvite>,
+    new_player2: Pubkey,
+    new_deadline_slot: u64,
+) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let current_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "reopen_invite: game_id={}, old_player2={}, new_player2={}, old_deadline={}, new_deadline={}, current_slot={}",
+        game.game_id,
+        game.player2,
+        new_player2,
+        game.join_deadline_slot,
+        new_deadline_slot,
+        current_slot
+    );
+
+    require!(
+        game.status == GameStatus::WaitingForPlayer2,
+        ErrorCode::GameNotWaitingForPlayer2
+    );
+    require!(
+        game.join_deadline_slot != 0 && current_slot > game.join_deadline_slot,
+        ErrorCode::JoinDeadlineNotPassed
+    );
+    require!(game.player1_deposit > 0, ErrorCode::InconsistentPot);
+    require_ctx!(
+        game.pot_lamports == game.player1_deposit,
+        ErrorCode::InconsistentPot,
+        Lamports {
+            expected: game.player1_deposit,
+            actual: game.pot_lamports,
+        }
+    );
+    require!(
+        new_deadline_slot > current_slot,
+        ErrorCode::InvalidJoinDeadline
+    );
+
+    if new_player2 != Pubkey::default() {
+        // Встречное предложение прежнего player2 сначала снимается через
+        // withdraw_counter.
+        require!(!game.counter_pending, ErrorCode::CounterOfferPending);
+        require_keys_neq!(new_player2, game.player1, ErrorCode::SelfPlayNotAllowed);
+        game.player2 = new_player2;
+    }
+    game.join_deadline_slot = new_deadline_slot;
+    game.last_activity_slot = current_slot;
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::*;
+
+/// Контекст для фиксации результата матча.
+#[derive(Accounts)]
+pub struct ReportResult<'info> {
+    /// Аккаунт турнира.
+    #[account(mut, has_one = organizer)]
+    pub tournament: Account<'info, Tournament>,
+
+    /// Завершённая игра турнира.
+    pub game: Account<'info, GameState>,
+
+    /// Организатор турнира.
+    pub organizer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReportResult>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let tournament = &mut ctx.accounts.tournament;
+
+    verbose_msg!(
+        "report_result: tournament={}, round={}, game={}, winner={}",
+        tournament.key(),
+        tournament.round,
+        game.key(),
+        game.winner
+    );
+
+    require!(
+        tournament.status == TournamentStatus::Running,
+        ErrorCode::TournamentNotRunning
+    );
+    require!(
+        tournament.mode == TournamentMode::Knockout,
+        ErrorCode::WrongTournamentMode
+    );
+    require!(
+        game.status == GameStatus::Finished,
+        ErrorCode::GameNotFinished
+    );
+    require_keys_eq!(
+        game.tournament,
+        tournament.key(),
+        ErrorCode::GameNotInTournament
+    );
+    require!(
+        game.tournament_round == tournament.round,
+        ErrorCode::GameNotInTournament
+    );
+    require!(
+        game.winner == game.player1 || game.winner == game.player2,
+        ErrorCode::InvalidWinner
+    );
+
+    let pairs = (tournament.bracket_size / 2) as usize;
+    let pair = (0..pairs)
+        .find(|&i| {
+            let a = tournament.bracket[2 * i];
+            let b = tournament.bracket[2 * i + 1];
+            (a == game.player1 && b == game.player2) || (a == game.player2 && b == game.player1)
+        })
+        .ok_or(ErrorCode::GameNotInTournament)?;
+
+    require!(
+        tournament.round_winners[pair] == Pubkey::default(),
+        ErrorCode::ResultAlreadyReported
+    );
+    tournament.round_winners[pair] = game.winner;
+
+    verbose_msg!("report_result: pair={}, winner={}", pair, game.winner);
+
+    Ok(())
+}
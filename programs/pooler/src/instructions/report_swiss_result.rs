@@ -0,0 +1,66 @@
+use crate::*;
+
+pub fn handler(ctx: Context<ReportResult>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let tournament = &mut ctx.accounts.tournament;
+
+    verbose_msg!(
+        "report_swiss_result: tournament={}, round={}, game={}, winner={}",
+        tournament.key(),
+        tournament.round,
+        game.key(),
+        game.winner
+    );
+
+    require!(
+        tournament.status == TournamentStatus::Running,
+        ErrorCode::TournamentNotRunning
+    );
+    require!(
+        tournament.mode == TournamentMode::Swiss,
+        ErrorCode::WrongTournamentMode
+    );
+    require!(
+        game.status == GameStatus::Finished,
+        ErrorCode::GameNotFinished
+    );
+    require_keys_eq!(
+        game.tournament,
+        tournament.key(),
+        ErrorCode::GameNotInTournament
+    );
+    require!(
+        game.tournament_round == tournament.round,
+        ErrorCode::GameNotInTournament
+    );
+    require!(
+        game.winner == game.player1 || game.winner == game.player2,
+        ErrorCode::InvalidWinner
+    );
+
+    let count = tournament.player_count as usize;
+    let a = tournament.players[..count]
+        .iter()
+        .position(|p| *p == game.player1)
+        .ok_or(ErrorCode::GameNotInTournament)?;
+    let b = tournament.players[..count]
+        .iter()
+        .position(|p| *p == game.player2)
+        .ok_or(ErrorCode::GameNotInTournament)?;
+    require!(
+        tournament.pairings[a] as usize == b,
+        ErrorCode::GameNotInTournament
+    );
+    require!(
+        tournament.reported & (1 << a) == 0,
+        ErrorCode::ResultAlreadyReported
+    );
+
+    let winner_index = if game.winner == game.player1 { a } else { b };
+    tournament.scores[winner_index] = tournament.scores[winner_index]
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    tournament.reported |= (1 << a) | (1 << b);
+
+    Ok(())
+}
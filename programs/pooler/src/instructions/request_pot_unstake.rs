@@ -0,0 +1,44 @@
+use crate::*;
+
+/// Контекст для запроса вывода банка из пула.
+#[derive(Accounts)]
+pub struct RequestPotUnstake<'info> {
+    /// Аккаунт игры.
+    pub game: Account<'info, GameState>,
+
+    /// Учёт размещения.
+    #[account(mut, seeds = [b"pot_stake", game.key().as_ref()], bump = pot_stake.bump)]
+    pub pot_stake: Account<'info, PotStake>,
+
+    /// Глобальный конфиг (арбитр).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Игрок или арбитр.
+    pub requester: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RequestPotUnstake>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let requester = ctx.accounts.requester.key();
+    require!(
+        requester == game.player1
+            || requester == game.player2
+            || requester == ctx.accounts.config.arbiter,
+        ErrorCode::InvalidPlayer
+    );
+    let stake = &mut ctx.accounts.pot_stake;
+    require!(
+        stake.pool_tokens > 0 && stake.unstake_epoch == 0,
+        ErrorCode::PotNotStaked
+    );
+    stake.unstake_epoch = Clock::get()?.epoch;
+
+    verbose_msg!(
+        "request_pot_unstake: game_id={}, requester={}, epoch={}",
+        game.game_id,
+        requester,
+        stake.unstake_epoch
+    );
+    Ok(())
+}
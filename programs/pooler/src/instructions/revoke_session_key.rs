@@ -0,0 +1,24 @@
+use crate::*;
+
+pub fn handler(ctx: Context<SessionKey>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let player = ctx.accounts.player.key();
+
+    verbose_msg!(
+        "revoke_session_key: game_id={}, player={}",
+        game.game_id,
+        player
+    );
+
+    if player == game.player1 {
+        game.player1_session_key = Pubkey::default();
+        game.player1_session_expiry = 0;
+    } else if player == game.player2 {
+        game.player2_session_key = Pubkey::default();
+        game.player2_session_expiry = 0;
+    } else {
+        return Err(ErrorCode::InvalidPlayer.into());
+    }
+
+    Ok(())
+}
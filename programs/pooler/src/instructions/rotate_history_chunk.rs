@@ -0,0 +1,73 @@
+use crate::*;
+
+/// Контекст для перехода истории ходов на новый чанк.
+#[derive(Accounts)]
+pub struct RotateHistoryChunk<'info> {
+    /// Игра с историей ходов.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Текущий чанк истории; должен быть заполнен.
+    #[account(
+        constraint = current_chunk.game == game.key()
+            && current_chunk.chunk_index == game.current_history_chunk
+            @ ErrorCode::GameHistoryMismatch,
+    )]
+    pub current_chunk: Box<Account<'info, GameHistory>>,
+
+    /// Новый чанк. Чанк 0 существует, пока есть история, поэтому
+    /// переполнение номера упирается в уже занятый адрес.
+    #[account(
+        init,
+        payer = player,
+        space = 8 + GameHistory::INIT_SPACE,
+        seeds = [
+            b"game_history",
+            game.key().as_ref(),
+            &[game.current_history_chunk.wrapping_add(1)],
+        ],
+        bump,
+    )]
+    pub next_chunk: Box<Account<'info, GameHistory>>,
+
+    /// Один из игроков (основной ключ), платит ренту нового чанка.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RotateHistoryChunk>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let player = ctx.accounts.player.key();
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(
+        player == game.player1 || player == game.player2,
+        ErrorCode::InvalidPlayer
+    );
+    require!(
+        ctx.accounts.current_chunk.is_full(),
+        ErrorCode::HistoryNotFull
+    );
+
+    let next = &mut ctx.accounts.next_chunk;
+    next.game = game.key();
+    next.payer = player;
+    next.chunk_index = game
+        .current_history_chunk
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    next.prev_chunk = ctx.accounts.current_chunk.key();
+    next.moves = Vec::new();
+    next.bump = ctx.bumps.next_chunk;
+    game.current_history_chunk = next.chunk_index;
+
+    verbose_msg!(
+        "rotate_history_chunk: game={}, chunk={}, payer={}",
+        game.key(),
+        next.chunk_index,
+        player
+    );
+    Ok(())
+}
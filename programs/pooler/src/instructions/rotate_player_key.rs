@@ -0,0 +1,82 @@
+use crate::*;
+
+/// Контекст для смены ключа игрока.
+#[derive(Accounts)]
+pub struct RotatePlayerKey<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Игрок, меняющий ключ (текущим ключом).
+    pub player: Signer<'info>,
+
+    /// Соперник, подтверждающий смену ключа.
+    pub opponent: Signer<'info>,
+
+    /// CHECK: PDA [b"chouette", game] enforced by seeds; updated only if initialized.
+    #[account(mut, seeds = [b"chouette", game.key().as_ref()], bump)]
+    pub chouette: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<RotatePlayerKey>, new_key: Pubkey) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let old_key = ctx.accounts.player.key();
+
+    verbose_msg!(
+        "rotate_player_key: game_id={}, old_key={}, new_key={}",
+        game.game_id,
+        old_key,
+        new_key
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(
+        game.tournament == Pubkey::default(),
+        ErrorCode::KeyRotationNotAllowed
+    );
+    require!(
+        new_key != Pubkey::default() && new_key != game.player1 && new_key != game.player2,
+        ErrorCode::InvalidPlayer
+    );
+
+    let player_index = if old_key == game.player1 {
+        1
+    } else if old_key == game.player2 {
+        require!(!game.bot_game, ErrorCode::KeyRotationNotAllowed);
+        2
+    } else {
+        return Err(ErrorCode::InvalidPlayer.into());
+    };
+    let opponent = game.player_key(3 - player_index);
+    require_keys_eq!(
+        ctx.accounts.opponent.key(),
+        opponent,
+        ErrorCode::InvalidPlayer
+    );
+
+    if player_index == 1 {
+        game.player1 = new_key;
+        game.player1_session_key = Pubkey::default();
+        game.player1_session_expiry = 0;
+    } else {
+        game.player2 = new_key;
+        game.player2_session_key = Pubkey::default();
+        game.player2_session_expiry = 0;
+    }
+
+    // Шуэт хранит копии ключей коробки и капитана как адреса выплат.
+    let chouette_info = ctx.accounts.chouette.to_account_info();
+    if chouette_info.owner == &crate::ID && !chouette_info.data_is_empty() {
+        let mut data = chouette_info.try_borrow_mut_data()?;
+        let mut chouette = ChouetteState::try_deserialize(&mut &data[..])?;
+        if chouette.box_player == old_key {
+            chouette.box_player = new_key;
+        }
+        if chouette.captain == old_key {
+            chouette.captain = new_key;
+        }
+        chouette.try_serialize(&mut &mut data[..])?;
+    }
+
+    Ok(())
+}
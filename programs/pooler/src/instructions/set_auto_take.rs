@@ -0,0 +1,78 @@
+use crate::*;
+
+/// Контекст для настройки автовзятия куба.
+#[derive(Accounts)]
+pub struct SetAutoTake<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Игрок, настраивающий свой порог и резерв.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetAutoTake>, up_to: u8) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let player = ctx.accounts.player.key();
+
+    verbose_msg!(
+        "set_auto_take: game_id={}, player={}, up_to={}",
+        game.game_id,
+        player,
+        up_to
+    );
+
+    require!(!game.double_pending, ErrorCode::DoublePending);
+    require!(
+        !game.status.is_over() || up_to == 0,
+        ErrorCode::GameNotActive
+    );
+    require!(
+        !game.team_game || up_to == 0,
+        ErrorCode::CubeDisabledInTeamGame
+    );
+    let player_index = if player == game.player1 {
+        1
+    } else if player == game.player2 {
+        require!(!game.bot_game, ErrorCode::CubeDisabledInBotGame);
+        2
+    } else {
+        return Err(ErrorCode::InvalidPlayer.into());
+    };
+
+    let needed = game.auto_take_reserve_needed(up_to)?;
+    let current = if player_index == 1 {
+        game.p1_take_reserve
+    } else {
+        game.p2_take_reserve
+    };
+
+    if needed > current {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.player.to_account_info(),
+            to: game.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, needed - current)?;
+    } else if current > needed {
+        debit(&game.to_account_info(), current - needed)?;
+        credit(&ctx.accounts.player.to_account_info(), current - needed)?;
+    }
+
+    if player_index == 1 {
+        game.p1_auto_take_up_to = up_to;
+        game.p1_take_reserve = needed;
+    } else {
+        game.p2_auto_take_up_to = up_to;
+        game.p2_take_reserve = needed;
+    }
+
+    verbose_msg!("set_auto_take: completed, take_reserve={}", needed);
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::*;
+
+/// Контекст для регистрации сплитов выплат.
+#[derive(Accounts)]
+pub struct SetPayoutSplits<'info> {
+    /// Сплиты игрока.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PayoutSplits::INIT_SPACE,
+        seeds = [b"payout_splits", player.key().as_ref()],
+        bump,
+    )]
+    pub payout_splits: Account<'info, PayoutSplits>,
+
+    /// Игрок, платит ренту.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetPayoutSplits>, splits: Vec<PayoutSplit>) -> Result<()> {
+    validate_payout_splits(&splits)?;
+
+    let payout_splits = &mut ctx.accounts.payout_splits;
+    payout_splits.player = ctx.accounts.player.key();
+    payout_splits.splits = splits;
+    payout_splits.bump = ctx.bumps.payout_splits;
+
+    verbose_msg!(
+        "set_payout_splits: player={}, splits={:?}",
+        payout_splits.player,
+        payout_splits.splits
+    );
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use crate::*;
+
+/// Контекст для расчёта шуэта.
+#[derive(Accounts)]
+pub struct SettleChouette<'info> {
+    /// Завершённая игра.
+    #[account(address = chouette.game)]
+    pub game: Account<'info, GameState>,
+
+    /// Аккаунт шуэта.
+    #[account(mut, seeds = [b"chouette", game.key().as_ref()], bump = chouette.bump)]
+    pub chouette: Account<'info, ChouetteState>,
+
+    /// CHECK: address constraint ensures this is chouette.box_player; balance only credited
+    #[account(mut, address = chouette.box_player)]
+    pub box_player: AccountInfo<'info>,
+
+    /// Любой плательщик комиссии транзакции.
+    pub payer: Signer<'info>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SettleChouette<'info>>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &ctx.accounts.game;
+    let chouette = &mut ctx.accounts.chouette;
+
+    verbose_msg!(
+        "settle_chouette: chouette={}, game_status={:?}, winner={}",
+        chouette.key(),
+        game.status,
+        game.winner
+    );
+
+    require!(!chouette.settled, ErrorCode::ChouetteSettled);
+    require!(game.status.is_over(), ErrorCode::GameNotFinished);
+
+    let box_won = game.winner == chouette.box_player;
+    let team_won = game.winner == chouette.captain;
+
+    let mut remaining = ctx.remaining_accounts.iter();
+    let mut box_total: u64 = 0;
+
+    for idx in 0..CHOUETTE_MAX_MEMBERS {
+        let entry = chouette.members[idx];
+        if entry.status != ChouetteMemberStatus::Active {
+            continue;
+        }
+        let member_info = remaining.next().ok_or(ErrorCode::InvalidChouetteMember)?;
+        require_keys_eq!(
+            member_info.key(),
+            entry.member,
+            ErrorCode::InvalidChouetteMember
+        );
+
+        let total = entry
+            .member_deposit
+            .checked_add(entry.box_deposit)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let (to_member, to_box) = if box_won {
+            (0, total)
+        } else if team_won {
+            (total, 0)
+        } else {
+            (entry.member_deposit, entry.box_deposit)
+        };
+
+        verbose_msg!(
+            "settle_chouette: member={}, cube_value={}, to_member={}, to_box={}",
+            entry.member,
+            entry.cube_value,
+            to_member,
+            to_box
+        );
+
+        if to_member > 0 {
+            debit(&chouette.to_account_info(), to_member)?;
+            credit(member_info, to_member)?;
+        }
+        box_total = box_total
+            .checked_add(to_box)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let entry = &mut chouette.members[idx];
+        entry.member_deposit = 0;
+        entry.box_deposit = 0;
+    }
+
+    if box_total > 0 {
+        debit(&chouette.to_account_info(), box_total)?;
+        credit(&ctx.accounts.box_player.to_account_info(), box_total)?;
+    }
+
+    chouette.settled = true;
+
+    verbose_msg!("settle_chouette: completed, box_total={}", box_total);
+
+    Ok(())
+}
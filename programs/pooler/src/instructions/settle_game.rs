@@ -0,0 +1,175 @@
+use crate::*;
+
+/// Контекст для договорного завершения игры.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleGame<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок.
+    #[account(mut, address = game.player1)]
+    pub player1: Signer<'info>,
+
+    /// Второй игрок.
+    #[account(
+        mut,
+        address = game.player2,
+        constraint = player2.key() != player1.key() @ ErrorCode::DuplicatePlayerAccounts,
+    )]
+    pub player2: Signer<'info>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; required only for bot games.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: Option<UncheckedAccount<'info>>,
+
+    /// Эскроу-агент игры; обязателен, если game.escrow_agent задан.
+    pub escrow_agent: Option<Signer<'info>>,
+
+    /// Составы команд; обязателен для командных игр.
+    #[account(mut, seeds = [b"team", game.key().as_ref()], bump = team.bump)]
+    pub team: Option<Account<'info, TeamState>>,
+
+    /// Кольцевой буфер событий игры (необязательно).
+    #[account(mut, seeds = [b"event_buffer", game.key().as_ref()], bump = event_buffer.bump)]
+    pub event_buffer: Option<Box<Account<'info, EventBuffer>>>,
+
+    /// Хранилище комиссии протокола; обязательно при config.protocol_fee_bps > 0.
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+
+    /// CHECK: address constraint ensures this is the incinerator; balance only credited.
+    /// Required when config.burn_bps > 0.
+    #[account(mut, address = INCINERATOR)]
+    pub incinerator: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA [b"payout_splits", game.player1] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player1.as_ref()], bump)]
+    pub player1_splits: UncheckedAccount<'info>,
+
+    /// CHECK: PDA [b"payout_splits", game.player2] enforced by seeds; uninitialized
+    /// when the player has no splits, otherwise deserialized by pay_out.
+    #[account(seeds = [b"payout_splits", game.player2.as_ref()], bump)]
+    pub player2_splits: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleGame<'info>>,
+    p1_amount: u64,
+    p2_amount: u64,
+) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    require!(ctx.accounts.game.staked_lamports == 0, ErrorCode::PotStaked);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "settle_game: game_id={}, status={:?}, pot={}, p1_amount={}, p2_amount={}",
+        game.game_id,
+        game.status,
+        game.pot_lamports,
+        p1_amount,
+        p2_amount
+    );
+
+    require!(
+        game.status == GameStatus::Active || game.status == GameStatus::Disputed,
+        ErrorCode::GameNotActive
+    );
+    // Пока решение арбитра ждёт апелляции, банк заблокирован.
+    require!(!game.resolution_pending(), ErrorCode::ResolutionPending);
+    game.check_escrow_agent(ctx.accounts.escrow_agent.as_ref())?;
+
+    let pot = game.pot_lamports;
+    check_settlement(pot, p1_amount, p2_amount)?;
+
+    // Комиссия протокола удерживается с каждой договорной доли отдельно.
+    let fee_bps = if game.practice {
+        0
+    } else {
+        ctx.accounts.config.protocol_fee_bps
+    };
+    let p1_paid = p1_amount - protocol_fee(p1_amount, fee_bps);
+    let p2_paid = p2_amount - protocol_fee(p2_amount, fee_bps);
+    let fee = pot - p1_paid - p2_paid;
+    let burned = collect_protocol_fee(
+        game,
+        ctx.accounts.fee_vault.as_mut(),
+        ctx.accounts.incinerator.as_ref().map(|a| a.as_ref()),
+        fee,
+        ctx.accounts.config.burn_bps,
+    )?;
+    if fee > 0 {
+        emit_cpi!(ProtocolFeeCollected {
+            game: game.key(),
+            fee,
+            burned,
+        });
+    }
+
+    let mut remaining = ctx.remaining_accounts.iter();
+    if p1_paid > 0 {
+        let player1_info = team_payee(
+            game,
+            1,
+            ctx.accounts.player1.to_account_info(),
+            ctx.accounts.team.as_mut(),
+            p1_paid,
+        )?;
+        pay_out(
+            &game.to_account_info(),
+            player1_info,
+            ctx.accounts.player1_splits.as_ref(),
+            &mut remaining,
+            p1_paid,
+        )?;
+    }
+
+    if p2_paid > 0 {
+        let player2_info = team_payee(
+            game,
+            2,
+            player2_funds(
+                game,
+                ctx.accounts.player2.to_account_info(),
+                ctx.accounts.bot_vault.as_ref(),
+            )?,
+            ctx.accounts.team.as_mut(),
+            p2_paid,
+        )?;
+        pay_out(
+            &game.to_account_info(),
+            player2_info,
+            ctx.accounts.player2_splits.as_ref(),
+            &mut remaining,
+            p2_paid,
+        )?;
+    }
+
+    game.pot_lamports = 0;
+    game.tips_lamports = 0;
+    game.move_pending = false;
+    game.double_pending = false;
+    game.finish(FinishReason::Negotiated, Clock::get()?.slot);
+
+    emit!(NegotiatedSettlement {
+        game: game.key(),
+        player1_amount: p1_amount,
+        player2_amount: p2_amount,
+    });
+    emit_cpi!(game_finished_event(game, p1_paid + p2_paid));
+    record_event(
+        ctx.accounts.event_buffer.as_deref_mut(),
+        BufferedEventKind::Finished,
+        game.winner,
+        p1_paid + p2_paid,
+    )?;
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
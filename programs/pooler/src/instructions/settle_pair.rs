@@ -0,0 +1,106 @@
+use crate::*;
+
+/// Контекст для расчёта зеркальной пары.
+#[derive(Accounts)]
+pub struct SettlePair<'info> {
+    /// Аккаунт пары.
+    #[account(
+        mut,
+        seeds = [b"mirror", pair.game_a.as_ref(), pair.game_b.as_ref()],
+        bump = pair.bump,
+    )]
+    pub pair: Account<'info, MirrorPair>,
+
+    /// Первая игра пары.
+    #[account(address = pair.game_a)]
+    pub game_a: Account<'info, GameState>,
+
+    /// Вторая игра пары.
+    #[account(address = pair.game_b)]
+    pub game_b: Account<'info, GameState>,
+
+    /// CHECK: address constraint ensures this is pair.player_a; balance only credited
+    #[account(mut, address = pair.player_a)]
+    pub player_a: AccountInfo<'info>,
+
+    /// CHECK: address constraint ensures this is pair.player_b; balance only credited
+    #[account(
+        mut,
+        address = pair.player_b,
+        constraint = player_b.key() != player_a.key() @ ErrorCode::DuplicatePlayerAccounts,
+    )]
+    pub player_b: AccountInfo<'info>,
+
+    /// Любой плательщик комиссии транзакции.
+    pub payer: Signer<'info>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SettlePair>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let pair = &ctx.accounts.pair;
+    let game_a = &ctx.accounts.game_a;
+    let game_b = &ctx.accounts.game_b;
+
+    verbose_msg!(
+        "settle_pair: pair={}, game_a_status={:?}, game_b_status={:?}",
+        pair.key(),
+        game_a.status,
+        game_b.status
+    );
+
+    require!(!pair.settled, ErrorCode::MirrorPairSettled);
+    require!(
+        game_a.status.is_over() && game_b.status.is_over(),
+        ErrorCode::GameNotFinished
+    );
+
+    let mut wins_a: u64 = 0;
+    let mut wins_b: u64 = 0;
+    for game in [game_a, game_b] {
+        if game.winner == pair.player_a {
+            wins_a += 1;
+        } else if game.winner == pair.player_b {
+            wins_b += 1;
+        }
+    }
+
+    let deposit = pair
+        .stake_lamports
+        .checked_mul(2)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let diff = pair
+        .stake_lamports
+        .checked_mul(wins_a.abs_diff(wins_b))
+        .ok_or(ErrorCode::MathOverflow)?;
+    let (to_a, to_b) = if wins_a >= wins_b {
+        (deposit + diff, deposit - diff)
+    } else {
+        (deposit - diff, deposit + diff)
+    };
+
+    verbose_msg!(
+        "settle_pair: wins_a={}, wins_b={}, to_a={}, to_b={}",
+        wins_a,
+        wins_b,
+        to_a,
+        to_b
+    );
+
+    let pair_info = ctx.accounts.pair.to_account_info();
+    if to_a > 0 {
+        debit(&pair_info, to_a)?;
+        credit(&ctx.accounts.player_a, to_a)?;
+    }
+    if to_b > 0 {
+        debit(&pair_info, to_b)?;
+        credit(&ctx.accounts.player_b, to_b)?;
+    }
+
+    ctx.accounts.pair.settled = true;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use crate::*;
+
+/// Контекст для расчёта пари.
+#[derive(Accounts)]
+pub struct SettleSideBet<'info> {
+    /// Завершённая игра, источник победителя.
+    #[account(mut, address = side_bet.game)]
+    pub game: Account<'info, GameState>,
+
+    /// Аккаунт пари, закрывается на bettor.
+    #[account(
+        mut,
+        seeds = [b"side_bet", game.key().as_ref(), side_bet.bettor.as_ref(), &side_bet.bet_id.to_le_bytes()],
+        bump = side_bet.bump,
+        close = bettor,
+    )]
+    pub side_bet: Account<'info, SideBet>,
+
+    /// CHECK: address constraint ensures this is side_bet.bettor; balance only credited
+    #[account(mut, address = side_bet.bettor)]
+    pub bettor: AccountInfo<'info>,
+
+    /// CHECK: address constraint ensures this is side_bet.counterparty; balance only credited
+    #[account(mut, address = side_bet.counterparty)]
+    pub counterparty: AccountInfo<'info>,
+
+    /// Любой плательщик комиссии транзакции.
+    pub payer: Signer<'info>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SettleSideBet>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &ctx.accounts.game;
+    let side_bet = &ctx.accounts.side_bet;
+
+    verbose_msg!(
+        "settle_side_bet: side_bet={}, game_status={:?}, winner={}",
+        side_bet.key(),
+        game.status,
+        game.winner
+    );
+
+    require!(
+        side_bet.status == SideBetStatus::Matched,
+        ErrorCode::SideBetNotMatched
+    );
+    require!(game.status.is_over(), ErrorCode::GameNotFinished);
+
+    let amount = side_bet.amount;
+    let pool = amount.checked_mul(2).ok_or(ErrorCode::MathOverflow)?;
+    let (to_bettor, to_counterparty) = if game.winner == Pubkey::default() {
+        (amount, amount)
+    } else if game.winner == game.player_key(game.creation_index(side_bet.predicted_player)) {
+        (pool, 0)
+    } else {
+        (0, pool)
+    };
+
+    verbose_msg!(
+        "settle_side_bet: to_bettor={}, to_counterparty={}",
+        to_bettor,
+        to_counterparty
+    );
+
+    // Выплату bettor забирает close = bettor вместе с арендой.
+    if to_counterparty > 0 {
+        debit(&ctx.accounts.side_bet.to_account_info(), to_counterparty)?;
+        credit(&ctx.accounts.counterparty, to_counterparty)?;
+    }
+    // Пари, сведённые до появления счётчика, в нём не учтены.
+    let game = &mut ctx.accounts.game;
+    game.unsettled_side_bets = game.unsettled_side_bets.saturating_sub(1);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::*;
+
+/// Контекст для выплаты приза.
+#[derive(Accounts)]
+pub struct SettleTournament<'info> {
+    /// Аккаунт турнира.
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+
+    /// CHECK: address constraint ensures this is tournament.champion; balance only credited
+    #[account(mut, address = tournament.champion)]
+    pub champion: AccountInfo<'info>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SettleTournament>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let tournament = &mut ctx.accounts.tournament;
+
+    require!(
+        tournament.status == TournamentStatus::Finished,
+        ErrorCode::TournamentNotFinished
+    );
+
+    let prize = tournament.prize_pool;
+    verbose_msg!(
+        "settle_tournament: paying prize_pool={} to champion={}",
+        prize,
+        tournament.champion
+    );
+
+    debit(&tournament.to_account_info(), prize)?;
+    credit(&ctx.accounts.champion.to_account_info(), prize)?;
+
+    tournament.prize_pool = 0;
+
+    Ok(())
+}
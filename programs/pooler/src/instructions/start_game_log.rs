@@ -0,0 +1,48 @@
+use crate::*;
+
+/// Контекст для начала проверки лога партии.
+#[derive(Accounts)]
+pub struct StartGameLog<'info> {
+    /// Проверяемая игра.
+    pub game: Account<'info, GameState>,
+
+    /// Состояние переигрывания лога.
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + GameLog::INIT_SPACE,
+        seeds = [b"game_log", game.key().as_ref(), submitter.key().as_ref()],
+        bump,
+    )]
+    pub game_log: Account<'info, GameLog>,
+
+    /// Подающий лог (любой: игрок, арбитр, кранк).
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<StartGameLog>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    require!(game.board_from_start, ErrorCode::LogReplayUnavailable);
+
+    verbose_msg!(
+        "start_game_log: game_id={}, submitter={}, move_index={}",
+        game.game_id,
+        ctx.accounts.submitter.key(),
+        game.move_index
+    );
+
+    let log = &mut ctx.accounts.game_log;
+    log.game = game.key();
+    log.submitter = ctx.accounts.submitter.key();
+    log.position = ReplayPosition::start(game.rule_variant, game.handicap)?;
+    log.cursor = 0;
+    log.last_dice = [0; 2];
+    log.verified = false;
+    log.failed = false;
+    log.bump = ctx.bumps.game_log;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::*;
+
+/// Контекст для действий организатора турнира.
+#[derive(Accounts)]
+pub struct TournamentOrganizer<'info> {
+    /// Аккаунт турнира.
+    #[account(mut, has_one = organizer)]
+    pub tournament: Account<'info, Tournament>,
+
+    /// Организатор турнира.
+    pub organizer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<TournamentOrganizer>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+
+    require!(
+        tournament.status == TournamentStatus::Registration,
+        ErrorCode::TournamentNotInRegistration
+    );
+
+    match tournament.mode {
+        TournamentMode::Knockout => {
+            require!(
+                tournament.player_count == tournament.max_players,
+                ErrorCode::TournamentNotFull
+            );
+            tournament.bracket = tournament.players;
+            tournament.bracket_size = tournament.player_count;
+            tournament.round = 1;
+            tournament.round_winners = [Pubkey::default(); TOURNAMENT_MAX_PLAYERS / 2];
+        }
+        TournamentMode::Swiss => {
+            require!(
+                tournament.player_count > tournament.swiss_rounds,
+                ErrorCode::InvalidSwissRounds
+            );
+            tournament.round = 0;
+        }
+    }
+    tournament.status = TournamentStatus::Running;
+
+    verbose_msg!(
+        "start_tournament: tournament={}, players={}, prize_pool={}",
+        tournament.key(),
+        tournament.player_count,
+        tournament.prize_pool
+    );
+
+    Ok(())
+}
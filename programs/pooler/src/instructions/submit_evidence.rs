@@ -0,0 +1,61 @@
+use crate::*;
+
+/// Контекст для подачи доказательств.
+#[derive(Accounts)]
+pub struct SubmitEvidence<'info> {
+    /// Спорная игра.
+    pub game: Account<'info, GameState>,
+
+    /// Доказательства, создаются при первой подаче.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + Evidence::MAX_SIZE,
+        seeds = [b"evidence", game.key().as_ref()],
+        bump,
+    )]
+    pub evidence: Account<'info, Evidence>,
+
+    /// Игрок, подающий доказательства.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SubmitEvidence>, cid: [u8; EVIDENCE_CID_LEN]) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let player = ctx.accounts.player.key();
+
+    verbose_msg!(
+        "submit_evidence: game_id={}, player={}",
+        game.game_id,
+        player
+    );
+
+    require!(
+        game.status == GameStatus::Disputed,
+        ErrorCode::GameNotDisputed
+    );
+    require!(cid != [0u8; EVIDENCE_CID_LEN], ErrorCode::InvalidEvidence);
+
+    let evidence = &mut ctx.accounts.evidence;
+    evidence.game = game.key();
+    evidence.bump = ctx.bumps.evidence;
+    if player == game.player1 {
+        evidence.player1_cid = cid;
+    } else if player == game.player2 {
+        evidence.player2_cid = cid;
+    } else {
+        return Err(ErrorCode::InvalidPlayer.into());
+    }
+
+    emit!(EvidenceSubmitted {
+        game: game.key(),
+        player,
+        cid,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::*;
+
+/// Контекст для подачи части лога.
+#[derive(Accounts)]
+pub struct SubmitGameLog<'info> {
+    /// Проверяемая игра.
+    #[account(address = game_log.game)]
+    pub game: Account<'info, GameState>,
+
+    /// Состояние переигрывания лога.
+    #[account(
+        mut,
+        seeds = [b"game_log", game.key().as_ref(), submitter.key().as_ref()],
+        bump = game_log.bump,
+        has_one = submitter,
+    )]
+    pub game_log: Account<'info, GameLog>,
+
+    /// Подающий лог.
+    pub submitter: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SubmitGameLog>, moves: Vec<LoggedMove>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let log = &mut ctx.accounts.game_log;
+    require!(!log.verified && !log.failed, ErrorCode::LogReplayFinished);
+    require!(
+        moves.len() <= MAX_GAME_LOG_CHUNK as usize,
+        ErrorCode::LogChunkTooLarge
+    );
+    let end = log
+        .cursor
+        .checked_add(moves.len() as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(end <= game.move_index, ErrorCode::LogChunkTooLarge);
+
+    verbose_msg!(
+        "submit_game_log: game_id={}, cursor={}, moves={}",
+        game.game_id,
+        log.cursor,
+        moves.len()
+    );
+
+    for logged in moves.iter() {
+        // Первым ходит player1, дальше строго по очереди.
+        let side = 1 + (log.cursor % 2) as u8;
+        match log
+            .position
+            .play_move(side, logged.dice, &logged.board_points)
+        {
+            Some(next) => {
+                log.position = next;
+                log.last_dice = logged.dice;
+                log.cursor += 1;
+            }
+            None => {
+                log.failed = true;
+                break;
+            }
+        }
+    }
+
+    if !log.failed && log.cursor == game.move_index {
+        let dice_match = game.move_index == 0 || log.last_dice == game.dice;
+        if log.position.board == game.board_points()? && dice_match {
+            log.verified = true;
+        } else {
+            log.failed = true;
+        }
+    }
+
+    if log.verified || log.failed {
+        verbose_msg!(
+            "submit_game_log: finished, verified={}, cursor={}",
+            log.verified,
+            log.cursor
+        );
+        emit!(GameLogReplayed {
+            game: game.key(),
+            submitter: log.submitter,
+            verified: log.verified,
+            failed_at: if log.failed { Some(log.cursor) } else { None },
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::*;
+
+/// Контекст для обмена сторонами до первого хода.
+#[derive(Accounts)]
+pub struct SwapSides<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Первый игрок.
+    #[account(address = game.player1 @ ErrorCode::InvalidPlayer)]
+    pub player1: Signer<'info>,
+
+    /// Второй игрок.
+    #[account(address = game.player2 @ ErrorCode::InvalidPlayer)]
+    pub player2: Signer<'info>,
+
+    /// CHECK: PDA [b"chouette", game] enforced by seeds; must be uninitialized.
+    #[account(seeds = [b"chouette", game.key().as_ref()], bump)]
+    pub chouette: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SwapSides>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "swap_sides: game_id={}, player1={}, player2={}, move_index={}",
+        game.game_id,
+        game.player1,
+        game.player2,
+        game.move_index
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.move_index == 0, ErrorCode::SwapWindowClosed);
+    require!(
+        game.cube_owner == 0 && !game.double_pending && !game.move_pending,
+        ErrorCode::SwapWindowClosed
+    );
+    // Бот не подписывает, а турнир, команды и шуэт хранят стороны игроков
+    // в своих аккаунтах.
+    let chouette_info = ctx.accounts.chouette.to_account_info();
+    require!(
+        !game.bot_game
+            && !game.team_game
+            && game.tournament == Pubkey::default()
+            && (chouette_info.owner != &crate::ID || chouette_info.data_is_empty()),
+        ErrorCode::SwapNotAllowed
+    );
+
+    game.swap_players();
+    game.last_activity_slot = Clock::get()?.slot;
+
+    emit!(SidesSwapped {
+        game: game.key(),
+        player1: game.player1,
+        player2: game.player2,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::*;
+
+/// Контекст для принятия удвоения.
+#[derive(Accounts)]
+pub struct TakeDouble<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Принимающий игрок, вносит свою доплату.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Системная программа Solana, нужна для transfer через CPI.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<TakeDouble>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+
+    verbose_msg!(
+        "take_double: game_id={}, cube_value={}, current_turn={}",
+        game.game_id,
+        game.cube_value,
+        game.current_turn
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(game.double_pending, ErrorCode::NoDoublePending);
+
+    // Принимает удвоение соперник игрока, который сейчас на ходу.
+    let taker = ctx.accounts.player.key();
+    let (expected, taker_turn) = match game.current_turn {
+        1 => (game.player2, 2u8),
+        2 => (game.player1, 1u8),
+        _ => return Err(ErrorCode::InvalidCurrentTurn.into()),
+    };
+    require_keys_eq!(taker, expected, ErrorCode::InvalidPlayer);
+
+    let extra = game
+        .stake_lamports
+        .checked_mul(game.cube_value as u64)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!(
+        "take_double: escrowing extra={} from taker={}",
+        extra,
+        taker
+    );
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.player.to_account_info(),
+        to: game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, extra)?;
+
+    game.add_deposit(taker_turn, extra)?;
+
+    game.cube_value = game
+        .cube_value
+        .checked_mul(2)
+        .ok_or(ErrorCode::MathOverflow)?;
+    game.cube_owner = taker_turn;
+    game.double_pending = false;
+    game.last_activity_slot = Clock::get()?.slot;
+
+    verbose_msg!(
+        "take_double: completed, cube_value={}, cube_owner={}, pot_lamports={}",
+        game.cube_value,
+        game.cube_owner,
+        game.pot_lamports
+    );
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::*;
+
+/// Контекст для чаевых в банк игры.
+#[derive(Accounts)]
+pub struct TipPot<'info> {
+    /// Аккаунт игры.
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+
+    /// Зритель, отправляющий чаевые.
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<TipPot>, amount: u64) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+    let game = &mut ctx.accounts.game;
+    let tipper = ctx.accounts.tipper.key();
+
+    verbose_msg!(
+        "tip_pot: game_id={}, tipper={}, amount={}",
+        game.game_id,
+        tipper,
+        amount
+    );
+
+    require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+    require!(!game.practice, ErrorCode::NotAllowedInPracticeGame);
+    require!(amount > 0, ErrorCode::InvalidTipAmount);
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.tipper.to_account_info(),
+        to: game.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, amount)?;
+
+    game.pot_lamports = game
+        .pot_lamports
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    game.tips_lamports = game
+        .tips_lamports
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(PotTipped {
+        game: game.key(),
+        tipper,
+        amount,
+        tips_lamports: game.tips_lamports,
+    });
+
+    assert_pot_invariant(game)?;
+    Ok(())
+}
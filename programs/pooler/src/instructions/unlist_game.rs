@@ -0,0 +1,30 @@
+use crate::*;
+
+/// Контекст для снятия игры с лобби.
+#[derive(Accounts)]
+pub struct UnlistGame<'info> {
+    /// Игра.
+    pub game: Account<'info, GameState>,
+
+    /// Создатель игры.
+    #[account(address = game.player1)]
+    pub player1: Signer<'info>,
+
+    /// Шард лобби.
+    #[account(mut, seeds = [b"lobby".as_ref(), &[lobby.shard]], bump = lobby.bump)]
+    pub lobby: Box<Account<'info, Lobby>>,
+}
+
+pub fn handler(ctx: Context<UnlistGame>) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+    require!(
+        ctx.accounts.lobby.unlist(game_key),
+        ErrorCode::GameNotListed
+    );
+    verbose_msg!(
+        "unlist_game: shard={}, game={}",
+        ctx.accounts.lobby.shard,
+        game_key
+    );
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use crate::*;
+
+/// Контекст для изменения конфига.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// Глобальный конфиг.
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// Администратор.
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    verbose_msg!("update_config: admin={}, update={:?}", config.admin, update);
+
+    if let Some(bans_enforced) = update.bans_enforced {
+        config.bans_enforced = bans_enforced;
+    }
+    if let Some(max_open_games) = update.max_open_games {
+        config.max_open_games = max_open_games;
+    }
+    if let Some(bot_wallet) = update.bot_wallet {
+        config.bot_wallet = bot_wallet;
+    }
+    if let Some(bot_max_stake) = update.bot_max_stake {
+        config.bot_max_stake = bot_max_stake;
+    }
+    if let Some(arbiter) = update.arbiter {
+        config.arbiter = arbiter;
+    }
+    if let Some(min_create_gap_slots) = update.min_create_gap_slots {
+        config.min_create_gap_slots = min_create_gap_slots;
+    }
+    if let Some(stake_pool) = update.stake_pool {
+        config.stake_pool = stake_pool;
+    }
+    if let Some(treasury) = update.treasury {
+        config.treasury = treasury;
+    }
+    if let Some(escheat_after_slots) = update.escheat_after_slots {
+        require!(
+            escheat_after_slots == 0 || escheat_after_slots >= MIN_ESCHEAT_AFTER_SLOTS,
+            ErrorCode::EscheatThresholdTooLow
+        );
+        config.escheat_after_slots = escheat_after_slots;
+    }
+    if let Some(free_moves) = update.free_moves {
+        config.free_moves = free_moves;
+    }
+    if let Some(insurance_fee_bps) = update.insurance_fee_bps {
+        require!(
+            insurance_fee_bps <= MAX_INSURANCE_FEE_BPS,
+            ErrorCode::InvalidInsuranceFee
+        );
+        config.insurance_fee_bps = insurance_fee_bps;
+    }
+    if let Some(protocol_fee_bps) = update.protocol_fee_bps {
+        require!(
+            protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            ErrorCode::InvalidProtocolFee
+        );
+        config.protocol_fee_bps = protocol_fee_bps;
+    }
+    if let Some(prune_after_slots) = update.prune_after_slots {
+        require!(
+            prune_after_slots == 0 || prune_after_slots >= MIN_PRUNE_AFTER_SLOTS,
+            ErrorCode::PruneThresholdTooLow
+        );
+        config.prune_after_slots = prune_after_slots;
+    }
+    if let Some(prune_reward_bps) = update.prune_reward_bps {
+        require!(
+            prune_reward_bps <= MAX_PRUNE_REWARD_BPS,
+            ErrorCode::InvalidPruneReward
+        );
+        config.prune_reward_bps = prune_reward_bps;
+    }
+    if let Some(require_whitelisted_arbiters) = update.require_whitelisted_arbiters {
+        config.require_whitelisted_arbiters = require_whitelisted_arbiters;
+    }
+    if let Some(appeal_window_slots) = update.appeal_window_slots {
+        require!(
+            appeal_window_slots == 0 || appeal_window_slots >= MIN_APPEAL_WINDOW_SLOTS,
+            ErrorCode::AppealWindowTooShort
+        );
+        config.appeal_window_slots = appeal_window_slots;
+    }
+    if let Some(burn_bps) = update.burn_bps {
+        require!(
+            burn_bps as u64 <= BPS_DENOMINATOR,
+            ErrorCode::InvalidBurnShare
+        );
+        config.burn_bps = burn_bps;
+    }
+    if let Some(decay_to_treasury) = update.decay_to_treasury {
+        config.decay_to_treasury = decay_to_treasury;
+    }
+    if let Some(rating_enforced) = update.rating_enforced {
+        config.rating_enforced = rating_enforced;
+    }
+    if let Some(paused) = update.paused {
+        config.paused = paused;
+        verbose_msg!("update_config: paused={}", paused);
+    }
+
+    Ok(())
+}
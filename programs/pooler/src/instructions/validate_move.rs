@@ -0,0 +1,32 @@
+use crate::*;
+
+/// Контекст для пробного хода (validate_move): только чтение.
+#[derive(Accounts)]
+pub struct ValidateMove<'info> {
+    pub game: Account<'info, GameState>,
+
+    /// Глобальный конфиг (проверка паузы).
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(
+    ctx: Context<ValidateMove>,
+    new_board_points: [i8; 24],
+    expected_move_index: u64,
+) -> Result<MoveVerdict> {
+    let game = &ctx.accounts.game;
+    let verdict = game.validate_move(
+        ctx.accounts.config.paused,
+        &new_board_points,
+        expected_move_index,
+    );
+    verbose_msg!(
+        "validate_move: game_id={}, move_index={}, accepted={}, reason={}",
+        game.game_id,
+        game.move_index,
+        verdict.accepted,
+        verdict.reason
+    );
+    Ok(verdict)
+}
@@ -0,0 +1,49 @@
+use crate::*;
+
+/// Контекст для проверки записанного хода по Merkle-корню.
+#[derive(Accounts)]
+pub struct VerifyHistoryEntry<'info> {
+    /// Игра, ход которой доказывается.
+    pub game: Account<'info, GameState>,
+
+    /// Коммитмент истории ходов игры.
+    #[account(
+        constraint = history_commitment.game == game.key() @ ErrorCode::HistoryCommitmentMismatch,
+    )]
+    pub history_commitment: Box<Account<'info, HistoryCommitment>>,
+}
+
+pub fn handler(
+    ctx: Context<VerifyHistoryEntry>,
+    entry: MoveRecord,
+    proof: [[u8; 32]; 16],
+) -> Result<()> {
+    let commitment = &ctx.accounts.history_commitment;
+    require!(
+        entry.move_index >= 1 && entry.move_index <= commitment.leaf_count,
+        ErrorCode::HistoryProofInvalid
+    );
+    require!(
+        backgammon_core::verify_proof::<HistoryHasher>(
+            &commitment.history_root,
+            &entry.leaf_hash(),
+            entry.move_index - 1,
+            &proof,
+        ),
+        ErrorCode::HistoryProofInvalid
+    );
+
+    verbose_msg!(
+        "verify_history_entry: game={}, move_index={}, leaves={}",
+        ctx.accounts.game.key(),
+        entry.move_index,
+        commitment.leaf_count
+    );
+
+    emit!(HistoryEntryVerified {
+        game: ctx.accounts.game.key(),
+        history_root: commitment.history_root,
+        entry,
+    });
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::*;
+
+/// Контекст для вывода средств из bot_vault.
+#[derive(Accounts)]
+pub struct WithdrawBotVault<'info> {
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: PDA [b"bot_vault"] enforced by seeds; debited via PDA-signed transfer.
+    #[account(mut, seeds = [b"bot_vault"], bump)]
+    pub bot_vault: UncheckedAccount<'info>,
+
+    /// Администратор, получатель средств.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Системная программа Solana.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<WithdrawBotVault>, amount: u64) -> Result<()> {
+    verbose_msg!(
+        "withdraw_bot_vault: admin={}, amount={}",
+        ctx.accounts.admin.key(),
+        amount
+    );
+
+    transfer_from_bot_vault(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.bot_vault.to_account_info(),
+        ctx.accounts.admin.to_account_info(),
+        ctx.bumps.bot_vault,
+        amount,
+    )
+}
@@ -0,0 +1,53 @@
+use crate::*;
+
+/// Контекст для снятия встречного предложения.
+#[derive(Accounts)]
+pub struct WithdrawCounter<'info> {
+    /// Игра, к которой относится предложение.
+    #[account(mut, address = proposal.game)]
+    pub game: Account<'info, GameState>,
+
+    /// Встречные условия; закрываются, рента возвращается автору.
+    #[account(
+        mut,
+        seeds = [b"counter_proposal", game.key().as_ref()],
+        bump = proposal.bump,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, CounterProposal>,
+
+    /// CHECK: address constraint ensures this is proposal.proposer; balance only credited
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Автор предложения (отзывает) или player1 (отклоняет).
+    #[account(
+        constraint = signer.key() == proposal.proposer
+            || signer.key() == game.player1 @ ErrorCode::InvalidPlayer,
+    )]
+    pub signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<WithdrawCounter>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let proposal = &ctx.accounts.proposal;
+
+    verbose_msg!(
+        "withdraw_counter: game_id={}, proposer={}, signer={}",
+        game.game_id,
+        proposal.proposer,
+        ctx.accounts.signer.key()
+    );
+
+    // Флаг живёт, пока существует PDA: после join_game (по исходным
+    // условиям) или отмены игры предложение тоже закрывается здесь.
+    game.counter_pending = false;
+
+    emit!(CounterWithdrawn {
+        game: game.key(),
+        proposer: proposal.proposer,
+        by: ctx.accounts.signer.key(),
+    });
+
+    Ok(())
+}
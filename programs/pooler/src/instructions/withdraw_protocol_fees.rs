@@ -0,0 +1,62 @@
+use crate::*;
+
+/// Контекст для вывода комиссии протокола в казну.
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    /// Глобальный конфиг.
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin, constraint = !config.locked @ ErrorCode::ConfigLocked)]
+    pub config: Account<'info, Config>,
+
+    /// Хранилище комиссии протокола.
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    /// CHECK: address constraint ensures this is config.treasury; only credited
+    #[account(
+        mut,
+        address = config.treasury,
+        constraint = treasury.key() != fee_vault.key() @ ErrorCode::TreasuryIsFeeVault,
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Администратор.
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(
+        config.treasury != Pubkey::default(),
+        ErrorCode::TreasuryNotSet
+    );
+
+    let vault = &mut ctx.accounts.fee_vault;
+    require!(
+        amount <= vault.available(),
+        ErrorCode::InsufficientProtocolFees
+    );
+
+    debit(&vault.to_account_info(), amount)?;
+    credit(&ctx.accounts.treasury, amount)?;
+    vault.withdrawn = vault
+        .withdrawn
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    verbose_msg!(
+        "withdraw_protocol_fees: amount={}, collected={}, withdrawn={}, treasury={}",
+        amount,
+        vault.collected,
+        vault.withdrawn,
+        config.treasury
+    );
+    emit!(ProtocolFeesWithdrawn {
+        admin: ctx.accounts.admin.key(),
+        treasury: config.treasury,
+        amount,
+        collected: vault.collected,
+        withdrawn: vault.withdrawn,
+        burned: vault.burned,
+    });
+    Ok(())
+}
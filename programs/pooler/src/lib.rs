@@ -1,8 +1,86 @@
+//! Программа `backgammon`: эскроу ставок и расчёт партий в нарды.
+//!
+//! CPI-интерфейс для других программ (фича `cpi`): `pooler::cpi::init_game`,
+//! `pooler::cpi::join_game`, `pooler::cpi::make_move` и т.д. с контекстами из
+//! `pooler::cpi::accounts`, данные инструкций — `pooler::instruction`
+//! (пример — programs/cpi-caller). Игроками и аккаунтом новой игры могут быть
+//! PDA вызывающей программы, подписанные через invoke_signed; такие места
+//! помечены в контекстах. Инструкции, выплачивающие банк (`finish_game`,
+//! `force_refund`, `manual_refund`, `claim_*`), через CPI не вызываются —
+//! ошибка `CpiNotAllowed`: вызывающая программа могла бы подставить в
+//! выплату свои аккаунты от имени подписавшего транзакцию игрока. Что
+//! открыто для CPI, а что нет, задаёт `CPI_MATRIX`. Сводку игры можно
+//! получить и через CPI: `pooler::cpi::get_game_summary(...)?.get()` вернёт
+//! `GameSummary`.
+//!
+//! Код разложен по модулям: `instructions` (файл на инструкцию с её
+//! контекстом и `handler`), `state` (аккаунты и их типы), `events`,
+//! `errors`, `math` (арифметика банка без рантайма), `helpers` (общие шаги
+//! над аккаунтами) и `cpi_guard`. Всё реэкспортируется из корня крейта,
+//! поэтому пути вида `pooler::GameState` и `pooler::split_pot` не меняются.
+
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{
+    get_stack_height, AccountMeta, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT,
+};
+use anchor_lang::solana_program::program::invoke_signed;
 use anchor_lang::system_program;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount};
+use solana_instructions_sysvar::get_instruction_relative;
+use solana_sdk_ids::ed25519_program;
+use solana_sha256_hasher::{hash, hashv};
 
 declare_id!("DmEwwQX5n6mt2Hgv923xmVLDQpWWcvYmTcm3yJbZ5xRr");
 
+/// msg! только при фиче `verbose-logs` (по умолчанию включена, для релиза —
+/// `--no-default-features`). Трассировка параметров и промежуточных сумм:
+/// индексаторам нужны события, а не логи. Без фичи аргументы всё равно
+/// проверяются компилятором, но вызов вырезается целиком и не тратит CU.
+/// Безусловным msg! остаются только сообщения о нештатных ситуациях.
+macro_rules! verbose_msg {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "verbose-logs") {
+            msg!($($arg)*);
+        }
+    };
+}
+
+/// require! с диагностикой: при отказе пишет событие ErrorContext с
+/// ожидаемым и фактическим значением и возвращает ошибку.
+///
+/// `require_ctx!(total == pot, ErrorCode::InconsistentPot, Lamports { expected: pot, actual: total })`
+/// — вид значений задаётся вариантом ContextValue.
+macro_rules! require_ctx {
+    ($cond:expr, $error:expr, $kind:ident { expected: $expected:expr, actual: $actual:expr $(,)? } $(,)?) => {
+        if !$cond {
+            let error: $crate::ErrorCode = $error;
+            ::anchor_lang::prelude::emit!($crate::ErrorContext {
+                error_code: error.into(),
+                expected: $crate::ContextValue::$kind($expected),
+                actual: $crate::ContextValue::$kind($actual),
+            });
+            return Err(::anchor_lang::prelude::error!(error));
+        }
+    };
+}
+
+pub mod cpi_guard;
+pub mod errors;
+pub mod events;
+pub mod helpers;
+pub mod instructions;
+pub mod math;
+pub mod signing;
+pub mod state;
+
+pub use cpi_guard::*;
+pub use errors::ErrorCode;
+pub use events::*;
+pub use helpers::*;
+pub use instructions::*;
+pub use math::*;
+pub use state::*;
+
 /// Основной модуль программы.
 /// В терминах Anchor сюда кладутся инструкции (функции, которые можно вызвать снаружи).
 #[program]
@@ -13,751 +91,1105 @@ pub mod backgammon {
     ///
     /// Аналог C# метода:
     /// public Result InitGame(Context<InitGame> ctx, ulong gameId, ...)
+    ///
+    /// Игроком может быть и PDA другой программы: подписи игроков везде
+    /// проверяются как Signer / is_signer, а их даёт invoke_signed при CPI
+    /// (пример — programs/cpi-caller). Такой PDA должен быть system-owned и
+    /// без данных, чтобы платить ставку и комиссии системными переводами.
+    ///
+    /// game_id = 0 — id выдаёт программа из счётчика создателя (PDA
+    /// GameCounter [b"counter", player1], передаётся в game_counter):
+    /// AUTO_GAME_ID_FLAG | порядковый номер. Явный id должен быть без
+    /// старшего бита (ReservedGameId), поэтому с выданными не совпадёт.
     pub fn init_game(
         ctx: Context<InitGame>,
         game_id: u64,
         stake_lamports: u64,
         move_fee_lamports: u64,
         player2_pubkey: Pubkey,
+        options: GameOptions,
     ) -> Result<()> {
-        msg!(
-            "init_game: game_id={}, stake_lamports={}, move_fee_lamports={}, player1={}, player2={}",
+        instructions::init_game::handler(
+            ctx,
             game_id,
             stake_lamports,
             move_fee_lamports,
-            ctx.accounts.player1.key(),
-            player2_pubkey
-        );
-
-        // ОДНА мут-ссылка на аккаунт игры
-        let game = &mut ctx.accounts.game;
-
-        // Заполняем структуру состояния игры
-        game.player1 = ctx.accounts.player1.key();
-        game.player2 = player2_pubkey;
-        game.game_id = game_id;
-        game.stake_lamports = stake_lamports;
-        game.move_fee_lamports = move_fee_lamports;
-        game.pot_lamports = 0;
-        game.player1_deposit = 0;
-        game.player2_deposit = 0;
-        game.player1_fees_paid = 0;
-        game.player2_fees_paid = 0;
-        game.board_points = [0; 24];
-        game.dice = [0; 2];
-        game.current_turn = 1;
-        game.status = GameStatus::WaitingForPlayer2;
-        game.winner = Pubkey::default();
-        // Для упрощения в учебном примере не используем PDA seeds для аккаунта игры,
-        // поэтому bump просто ставим в 0.
-        game.bump = 0;
-        game.move_index = 0;
-        game.last_activity_slot = Clock::get()?.slot;
-
-        msg!(
-            "init_game: GameState initialized: status={:?}, current_turn={}, pot_lamports={}, bump={}",
-            game.status,
-            game.current_turn,
-            game.pot_lamports,
-            game.bump
-        );
-
-        // Забираем ставку у первого игрока в аккаунт игры через CPI в системную программу.
-        let cpi_accounts = system_program::Transfer {
-            from: ctx.accounts.player1.to_account_info(),
-            to: game.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
-        system_program::transfer(cpi_ctx, stake_lamports)?;
-
-        game.pot_lamports = game
-            .pot_lamports
-            .checked_add(stake_lamports)
-            .ok_or(ErrorCode::MathOverflow)?;
-        game.player1_deposit = game
-            .player1_deposit
-            .checked_add(stake_lamports)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        msg!(
-            "init_game: stake transferred from player1={}, stake_lamports={}, pot_lamports={}",
-            game.player1,
-            stake_lamports,
-            game.pot_lamports
-        );
+            player2_pubkey,
+            options,
+        )
+    }
 
-        Ok(())
+    /// Перенос GameState исходной раскладки (версия 0, 256 байт данных),
+    /// версии 1 (512 байт), 2 (576 байт) или 3 (640 байт) на текущую раскладку.
+    ///
+    /// Аккаунт увеличивается до 8 + GameState::MAX_SIZE, новые байты
+    /// обнуляются, version становится GAME_STATE_VERSION, доски
+    /// переупаковываются в PackedBoard. У аккаунтов версий 0 и 1
+    /// finish_reason становится NotFinished (у завершённых игр причина
+    /// неизвестна), у версии 0 cube_value — 1. Пока аккаунт версий 0–2 не
+    /// перенесён, остальные инструкции его не читают; аккаунты версии 3
+    /// читаются и без переноса (доска в старом формате по байту version).
+    /// Вызвать может кто угодно: payer доплачивает ренту за новые байты
+    /// (migration_rent); банк на аккаунте в ренту не засчитывается.
+    pub fn migrate_game_state(ctx: Context<MigrateGameState>) -> Result<()> {
+        instructions::migrate_game_state::handler(ctx)
     }
 
     /// Присоединение второго игрока к уже созданной игре.
-    pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
-        let game = &mut ctx.accounts.game;
-
-        msg!(
-            "join_game: game_id={}, player2_expected={}, player2_actual={}",
-            game.game_id,
-            game.player2,
-            ctx.accounts.player2.key()
-        );
-
-        // Игра должна ожидать второго игрока
-        require!(
-            game.status == GameStatus::WaitingForPlayer2,
-            ErrorCode::GameNotWaitingForPlayer2
-        );
-
-        // Проверяем, что присоединился именно тот второй игрок,
-        // который был указан при инициализации.
-        require_keys_eq!(
-            ctx.accounts.player2.key(),
-            game.player2,
-            ErrorCode::InvalidPlayer2
-        );
-
-        // Списываем стартовую ставку со второго игрока в аккаунт игры
-        let stake = game.stake_lamports;
-
-        msg!(
-            "join_game: transferring stake from player2={}, stake_lamports={}",
-            ctx.accounts.player2.key(),
-            stake
-        );
-
-        let cpi_accounts = system_program::Transfer {
-            from: ctx.accounts.player2.to_account_info(),
-            to: game.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
-        system_program::transfer(cpi_ctx, stake)?;
-
-        game.pot_lamports = game
-            .pot_lamports
-            .checked_add(stake)
-            .ok_or(ErrorCode::MathOverflow)?;
-        game.player2_deposit = game
-            .player2_deposit
-            .checked_add(stake)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        game.last_activity_slot = Clock::get()?.slot;
-        game.status = GameStatus::Active;
-
-        msg!(
-            "join_game: completed, pot_lamports={}, status={:?}",
-            game.pot_lamports,
-            game.status
-        );
-
-        Ok(())
+    ///
+    /// Для приватных игр (password_hash не нулевой) нужен пароль: проверяется
+    /// sha256(password) == password_hash. Пароль передаётся в транзакции открытым
+    /// текстом и виден всем после отправки, так что это мягкая защита — она
+    /// лишь не даёт случайным игрокам занять место в лобби.
+    ///
+    /// client — необязательная телеметрия клиента (ClientInfo), только для
+    /// диагностики: сохраняется в ClientTelemetry, если он передан.
+    ///
+    /// Если игра создана с options.stake_pot, весь банк вносится в пул SPL
+    /// Stake Pool (config.stake_pool, DepositSol) через system-owned PDA
+    /// pot_vault; токены пула лежат на PDA-счёте pot_stake_tokens под
+    /// управлением PotStake, размещённая сумма — в game.staked_lamports.
+    /// Аккаунты пула тогда обязательны, ренту PotStake и счёта токенов платит
+    /// player2. Ходы продолжаются (комиссии идут на аккаунт игры как
+    /// обычно), а выплаты и возвраты банка отклоняются с PotStaked, пока он
+    /// не возвращён через request_pot_unstake и claim_pot_unstake.
+    pub fn join_game(
+        ctx: Context<JoinGame>,
+        password: Vec<u8>,
+        client: Option<ClientInfo>,
+    ) -> Result<()> {
+        instructions::join_game::handler(ctx, password, client)
+    }
+
+    /// Встречное предложение приглашённого player2, пока игра ждёт его
+    /// входа: другие ставка и комиссия за ход. Хранится в PDA
+    /// CounterProposal (рента за счёт player2), одновременно — не больше
+    /// одного. Не для тренировочных и турнирных игр.
+    pub fn counter_offer(
+        ctx: Context<CounterOffer>,
+        new_stake_lamports: u64,
+        new_move_fee_lamports: u64,
+    ) -> Result<()> {
+        instructions::counter_offer::handler(ctx, new_stake_lamports, new_move_fee_lamports)
+    }
+
+    /// Принятие встречного предложения первым игроком: ставка и комиссия
+    /// меняются, депозит (и резерв автовзятий) player1 доплачивается или
+    /// частично возвращается до новой ставки. Игра по-прежнему ждёт
+    /// join_game, уже по новым условиям. Пока предложение не принято,
+    /// join_game идёт по исходным условиям.
+    pub fn accept_counter(ctx: Context<AcceptCounter>) -> Result<()> {
+        instructions::accept_counter::handler(ctx)
+    }
+
+    /// Снятие встречного предложения автором или отказ player1; рента PDA
+    /// возвращается автору. Доступно в любом статусе игры.
+    pub fn withdraw_counter(ctx: Context<WithdrawCounter>) -> Result<()> {
+        instructions::withdraw_counter::handler(ctx)
+    }
+
+    /// Выдача сессионного ключа для make_move.
+    ///
+    /// Подписывает игрок основным ключом. Сессионный ключ может подписывать
+    /// make_move только в ходы этого игрока и только до expiry_slot включительно;
+    /// finish_game, возвраты и куб по-прежнему требуют основного ключа.
+    /// Повторный вызов заменяет предыдущий ключ.
+    pub fn authorize_session_key(
+        ctx: Context<SessionKey>,
+        key: Pubkey,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::authorize_session_key::handler(ctx, key, expiry_slot)
+    }
+
+    /// Отзыв сессионного ключа игроком (основным ключом).
+    pub fn revoke_session_key(ctx: Context<SessionKey>) -> Result<()> {
+        instructions::revoke_session_key::handler(ctx)
+    }
+
+    /// Смена ключа игрока посреди партии (например, если горячий кошелёк украден).
+    ///
+    /// Подписывают текущий ключ игрока и соперник, поэтому вор не может
+    /// незаметно переписать ключ. Старый ключ сразу теряет все права, сессионный
+    /// ключ игрока сбрасывается, а в шуэте поверх игры обновляется и адрес выплат.
+    /// Недоступно для турнирных игр (ключи зарегистрированы в турнире) и для бота.
+    pub fn rotate_player_key(ctx: Context<RotatePlayerKey>, new_key: Pubkey) -> Result<()> {
+        instructions::rotate_player_key::handler(ctx, new_key)
+    }
+
+    /// Обмен сторонами до первого хода, если создатель сел не за ту сторону.
+    ///
+    /// Подписывают оба игрока; игра должна быть Active с move_index == 0, без
+    /// предложенного удвоения. Меняются ключи игроков, их депозиты и
+    /// комиссии, сессионные ключи, страйки и резервы автовзятий; доска,
+    /// current_turn, куб и фора относятся к стороне и остаются, поэтому
+    /// первым ходит новый player1. Пари зрителей по-прежнему идут на
+    /// выбранного игрока. Недоступно для игр с ботом, команд, турниров и шуэта.
+    pub fn swap_sides(ctx: Context<SwapSides>) -> Result<()> {
+        instructions::swap_sides::handler(ctx)
+    }
+
+    /// Вход «казино»-бота в открытое лобби (кнопка «сыграть с домом»).
+    ///
+    /// Вызывается кранком оператора, подписывающим ключом config.bot_wallet.
+    /// Ставка списывается из bot_vault (PDA [b"bot_vault"]) с подписью программы,
+    /// и все выплаты и возвраты стороны бота также идут в bot_vault.
+    /// Бот входит только в игры без назначенного соперника (player2 =
+    /// Pubkey::default() или bot_wallet), без пароля и вне турниров, со ставкой
+    /// не выше config.bot_max_stake, и никогда — в игры, созданные им самим.
+    /// Куб удвоения в играх с ботом отключён.
+    pub fn bot_join(ctx: Context<BotJoin>) -> Result<()> {
+        instructions::bot_join::handler(ctx)
+    }
+
+    /// Пополнение bot_vault; вызвать может кто угодно.
+    pub fn fund_bot_vault(ctx: Context<FundBotVault>, amount: u64) -> Result<()> {
+        instructions::fund_bot_vault::handler(ctx, amount)
+    }
+
+    /// Вывод средств из bot_vault администратору.
+    pub fn withdraw_bot_vault(ctx: Context<WithdrawBotVault>, amount: u64) -> Result<()> {
+        instructions::withdraw_bot_vault::handler(ctx, amount)
     }
 
     /// Ход одного из игроков.
     ///
     /// Валидация правил нард делается оффчейн, а здесь мы:
     /// - проверяем, что ходит правильный игрок;
-    /// - списываем move_fee_lamports с ходящего игрока в пользу банка;
+    /// - списываем комиссию за ход с ходящего игрока в пользу банка:
+    ///   move_fee_lamports или, при options.move_fee_bps, долю банка перед
+    ///   ходом (pot_move_fee);
     /// - обновляем board_state;
     /// - переключаем очередь хода.
+    ///
+    /// Подписывает только ходящий (mover): основной ключ стороны
+    /// current_turn, её сессионный ключ или, в командной игре, её напарник;
+    /// комиссию платит mover, и она идёт в его вклад. Соперник передаётся
+    /// без подписи; в двухфазном режиме он подтверждает ход через ack_move.
+    ///
+    /// annotation_hash — необязательный (нули = нет) хэш оффчейн-лога чата и
+    /// аннотаций на момент хода. Программа его не интерпретирует, а лишь
+    /// вплетает в annotation_chain и пишет в событие MoveMade, чтобы лог
+    /// можно было сверить при споре.
+    ///
+    /// В игре с историей ходов (options.move_history) ход дописывается в
+    /// текущий чанк GameHistory; в заполненный чанк (MAX_HISTORY_MOVES ходов)
+    /// ход не проходит (HistoryFull), пока rotate_history_chunk не заведёт
+    /// следующий. С options.history_commitment лист хода дописывается в
+    /// Merkle-дерево HistoryCommitment и обновляет history_root.
     pub fn make_move(
         ctx: Context<MakeMove>,
         new_board_points: [i8; 24],
         new_dice: [u8; 2],
+        annotation_hash: [u8; 32],
+        client: Option<ClientInfo>,
+    ) -> Result<MoveOutcome> {
+        instructions::make_move::handler(ctx, new_board_points, new_dice, annotation_hash, client)
+    }
+
+    /// make_move с доской в упакованном виде (PackedBoard, 15 байт вместо 24
+    /// в данных инструкции).
+    ///
+    /// Доска распаковывается на входе, дальше всё как в make_move. GameState
+    /// с версии PACKED_BOARD_VERSION хранит её упакованной в слоте
+    /// StoredBoard, события несут доску как [i8; 24].
+    pub fn make_move_packed(
+        ctx: Context<MakeMove>,
+        packed_board: PackedBoard,
+        new_dice: [u8; 2],
+        annotation_hash: [u8; 32],
+        client: Option<ClientInfo>,
+    ) -> Result<MoveOutcome> {
+        instructions::make_move_packed::handler(
+            ctx,
+            packed_board,
+            new_dice,
+            annotation_hash,
+            client,
+        )
+    }
+
+    /// Пробный ход: примет ли make_move эту доску прямо сейчас.
+    ///
+    /// Делает те же проверки, что make_move до списания комиссии (общая
+    /// check_can_move), и сверяет expected_move_index; подписи и баланс не
+    /// проверяются. Ничего не меняет и не переводит — вызывается через
+    /// simulateTransaction, вердикт (MoveVerdict) приходит через return data.
+    pub fn validate_move(
+        ctx: Context<ValidateMove>,
+        new_board_points: [i8; 24],
+        expected_move_index: u64,
+    ) -> Result<MoveVerdict> {
+        instructions::validate_move::handler(ctx, new_board_points, expected_move_index)
+    }
+
+    /// Ход, присланный ретранслятором (мета-транзакция).
+    ///
+    /// Ходящий игрок подписывает payload оффчейн своим ключом; подпись
+    /// проверяется через Ed25519-инструкцию, стоящую прямо перед этой
+    /// (instructions sysvar). Единственный подписант транзакции — relayer,
+    /// он же платит сетевую комиссию. Подпись соперника, как и в make_move,
+    /// не нужна.
+    ///
+    /// Комиссия за ход списывается с предоплаченного RelayBudget ходящего
+    /// (fund_relay_budget). Подписывается signing::SignedMessage с nonce
+    /// игры и сроком expiry_slot: принятое сообщение продвигает
+    /// action_nonce, поэтому повтор не проходит; expected_move_index должен
+    /// совпадать с move_index. Командные игры и ходы бота не поддерживаются.
+    pub fn relayed_move(
+        ctx: Context<RelayedMove>,
+        payload: RelayedMovePayload,
+        nonce: u64,
+        expiry_slot: u64,
+        player_sig: [u8; 64],
+    ) -> Result<MoveOutcome> {
+        instructions::relayed_move::handler(ctx, payload, nonce, expiry_slot, player_sig)
+    }
+
+    /// Пополнение предоплаченного бюджета комиссий игрока для relayed_move.
+    ///
+    /// Бюджет — PDA [b"relay_budget", game, player]; создаётся при первом
+    /// пополнении.
+    pub fn fund_relay_budget(ctx: Context<FundRelayBudget>, amount: u64) -> Result<()> {
+        instructions::fund_relay_budget::handler(ctx, amount)
+    }
+
+    /// Закрытие бюджета relayed_move: остаток и рента возвращаются игроку.
+    pub fn close_relay_budget(ctx: Context<CloseRelayBudget>) -> Result<()> {
+        instructions::close_relay_budget::handler(ctx)
+    }
+
+    /// Подтверждение хода соперником в двухфазном режиме.
+    ///
+    /// Доска из pending_board становится официальной, очередь переходит к
+    /// подтвердившему.
+    pub fn ack_move(ctx: Context<RespondMove>) -> Result<()> {
+        instructions::ack_move::handler(ctx)
+    }
+
+    /// Отказ соперника принять ход: игра переходит в спор (Disputed).
+    ///
+    /// Спор разрешает арбитр (arbiter_resolve) или возвраты (force_refund / manual_refund).
+    pub fn reject_move(ctx: Context<RespondMove>) -> Result<()> {
+        instructions::reject_move::handler(ctx)
+    }
+
+    /// Автоподтверждение хода, на который соперник не ответил до
+    /// pending_deadline_slot; вызвать может кто угодно.
+    pub fn finalize_pending(ctx: Context<FinalizePending>) -> Result<()> {
+        instructions::finalize_pending::handler(ctx)
+    }
+
+    /// Ссылка на доказательства в споре (например, IPFS CID полного лога ходов).
+    ///
+    /// Каждый игрок пишет только в своё поле аккаунта Evidence и может
+    /// перезаписать его до решения арбитра.
+    pub fn submit_evidence(
+        ctx: Context<SubmitEvidence>,
+        cid: [u8; EVIDENCE_CID_LEN],
     ) -> Result<()> {
-        let game = &mut ctx.accounts.game;
-
-        msg!(
-            "make_move: game_id={}, move_index={}, current_turn={}, status={:?}",
-            game.game_id,
-            game.move_index,
-            game.current_turn,
-            game.status
-        );
-
-        require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
-
-        // Определяем, чей сейчас ход, и берём соответствующего подписанта.
-        let current_player_signer = match game.current_turn {
-            1 => {
-                msg!("make_move: expected current player = player1={}", game.player1);
-                &ctx.accounts.player1
-            }
-            2 => {
-                msg!("make_move: expected current player = player2={}", game.player2);
-                &ctx.accounts.player2
-            }
-            _ => {
-                msg!(
-                    "make_move: invalid current_turn value = {}",
-                    game.current_turn
-                );
-                return Err(ErrorCode::InvalidCurrentTurn.into());
-            }
-        };
-
-        // Списываем комиссию за ход в пользу банка
-        let move_fee = game.move_fee_lamports;
-        msg!(
-            "make_move: charging move_fee={}, from_player={}",
-            move_fee,
-            current_player_signer.key()
-        );
-
-        // Проверяем, что у игрока достаточно средств для оплаты хода.
-        let from_lamports = **current_player_signer.to_account_info().lamports.borrow();
-        require!(
-            from_lamports >= move_fee,
-            ErrorCode::NotEnoughBalanceForMove
-        );
-
-        let cpi_accounts = system_program::Transfer {
-            from: current_player_signer.to_account_info(),
-            to: game.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
-        system_program::transfer(cpi_ctx, move_fee)?;
-        game.pot_lamports = game
-            .pot_lamports
-            .checked_add(move_fee)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        // Обновляем, кто сколько заплатил комиссий за ходы.
-        match game.current_turn {
-            1 => {
-                game.player1_fees_paid = game
-                    .player1_fees_paid
-                    .checked_add(move_fee)
-                    .ok_or(ErrorCode::MathOverflow)?;
-            }
-            2 => {
-                game.player2_fees_paid = game
-                    .player2_fees_paid
-                    .checked_add(move_fee)
-                    .ok_or(ErrorCode::MathOverflow)?;
-            }
-            _ => {}
-        }
+        instructions::submit_evidence::handler(ctx, cid)
+    }
 
-        // Обновляем состояние доски и кубиков (валидация оффчейн)
-        game.board_points = new_board_points;
-        game.dice = new_dice;
+    /// Заявка на победу одним игроком (например, соперник пропал в конце игры).
+    ///
+    /// Заявитель вносит залог WIN_CLAIM_BOND_LAMPORTS и итоговую доску. В течение
+    /// CLAIM_CONTEST_WINDOW_SLOTS соперник может оспорить заявку (contest_claim),
+    /// иначе finalize_claim выплачивает банк заявителю. Пока заявка открыта,
+    /// ходы не принимаются.
+    pub fn claim_win(ctx: Context<ClaimWin>, final_board: [i8; 24]) -> Result<()> {
+        instructions::claim_win::handler(ctx, final_board)
+    }
 
-        // Увеличиваем счётчик ходов
-        game.move_index = game
-            .move_index
-            .checked_add(1)
-            .ok_or(ErrorCode::MathOverflow)?;
+    /// Соперник оспаривает заявку на победу до конца окна: игра переходит в
+    /// Disputed, дальше — arbiter_resolve или возвраты.
+    pub fn contest_claim(ctx: Context<ContestClaim>) -> Result<()> {
+        instructions::contest_claim::handler(ctx)
+    }
 
-        // Переключаем очередь хода
-        game.current_turn = if game.current_turn == 1 { 2 } else { 1 };
+    /// Завершение заявки на победу; вызвать может кто угодно.
+    ///
+    /// Если окно оспаривания прошло без contest_claim, банк уходит заявителю,
+    /// а итоговая доска заявки становится доской игры. Если игра уже завершена
+    /// иначе (возврат, договорённость, finish_game), залог возвращается
+    /// заявителю, только если победил он или победителя нет; иначе залог
+    /// уходит победителю (аккаунт winner). Оспоренная заявка ждёт
+    /// arbiter_resolve и execute_resolution. Рейтинг — как в finish_game.
+    pub fn finalize_claim<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeClaim<'info>>,
+    ) -> Result<()> {
+        instructions::finalize_claim::handler(ctx)
+    }
 
-        // Обновляем время последней активности (используется для force_refund)
-        game.last_activity_slot = Clock::get()?.slot;
+    /// Решение арбитра игры (options.arbiter из init_game, иначе
+    /// config.arbiter) по спорной игре: победителем назначается winner.
+    ///
+    /// Решение не исполняется сразу: открывается окно апелляции
+    /// config.appeal_window_slots, в течение которого банк заблокирован и
+    /// любой игрок может вызвать appeal. Выплату делает execute_resolution.
+    /// Обжалованный спор решает уже админ (тем же arbiter_resolve), и его
+    /// решение не обжалуется.
+    ///
+    /// При config.require_whitelisted_arbiters назначенный арбитр должен
+    /// оставаться в белом списке: исключённый после init_game арбитр спор
+    /// не решает, и остаются возвраты force_refund / manual_refund.
+    ///
+    /// Допускается, только если хотя бы одна сторона подала доказательства или
+    /// с начала спора прошло EVIDENCE_WINDOW_SLOTS.
+    pub fn arbiter_resolve(ctx: Context<ArbiterResolve>, winner: Pubkey) -> Result<()> {
+        instructions::arbiter_resolve::handler(ctx, winner)
+    }
 
-        msg!(
-            "make_move: completed, new_move_index={}, new_current_turn={}, pot_lamports={}",
-            game.move_index,
-            game.current_turn,
-            game.pot_lamports
-        );
+    /// Апелляция игрока на решение арбитра до appeal_deadline_slot: решение
+    /// отменяется без выплаты, игра остаётся в споре, и его решает админ.
+    /// Обжаловать можно один раз.
+    pub fn appeal(ctx: Context<Appeal>) -> Result<()> {
+        instructions::appeal::handler(ctx)
+    }
 
-        Ok(())
+    /// Исполнение решения арбитра после окна апелляции; вызвать может кто
+    /// угодно. Банк уходит победителю. Если спор начат через contest_claim,
+    /// залог заявителя уходит победителю, когда арбитр решил против
+    /// заявителя, и возвращается заявителю в противном случае. Рейтинг — как
+    /// в finish_game.
+    pub fn execute_resolution<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteResolution<'info>>,
+    ) -> Result<()> {
+        instructions::execute_resolution::handler(ctx)
     }
 
     /// Завершение игры и вывод банка победителю.
     ///
     /// Валидация результата (кто на самом деле выиграл) делается оффчейн,
     /// но вывести банк можно только, если транзакцию подписали ОБА игрока.
-    pub fn finish_game(ctx: Context<FinishGame>, winner: Pubkey) -> Result<()> {
-        let game = &mut ctx.accounts.game;
-
-        msg!(
-            "finish_game: game_id={}, status={:?}, winner_param={}, game.player1={}, game.player2={}",
-            game.game_id,
-            game.status,
-            winner,
-            game.player1,
-            game.player2
-        );
-
-        require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
-
-        // Гарантируем, что это действительно те самые игроки
-        require_keys_eq!(
-            ctx.accounts.player1.key(),
-            game.player1,
-            ErrorCode::InvalidPlayer1
-        );
-        require_keys_eq!(
-            ctx.accounts.player2.key(),
-            game.player2,
-            ErrorCode::InvalidPlayer2
-        );
-
-        // Победителем может быть только один из двух игроков.
-        require!(
-            winner == game.player1 || winner == game.player2,
-            ErrorCode::InvalidWinner
-        );
-
-        let pot = game.pot_lamports;
-
-        // Определяем, чей аккаунт победителя пополнить
-        let (winner_account_info, winner_label) = if winner == game.player1 {
-            msg!(
-                "finish_game: winner is player1={}, pot_lamports={}",
-                game.player1,
-                pot
-            );
-            (ctx.accounts.player1.to_account_info(), "player1")
-        } else {
-            msg!(
-                "finish_game: winner is player2={}, pot_lamports={}",
-                game.player2,
-                pot
-            );
-            (ctx.accounts.player2.to_account_info(), "player2")
-        };
-
-        // Переводим весь банк победителю напрямую, т.к. аккаунт игры принадлежит нашей программе.
-        **game.to_account_info().try_borrow_mut_lamports()? -= pot;
-        **winner_account_info.try_borrow_mut_lamports()? += pot;
-
-        game.pot_lamports = 0;
-        game.status = GameStatus::Finished;
-        game.winner = winner;
-
-        msg!(
-            "finish_game: completed, game_id={}, final_status={:?}, winner={} ({})",
-            game.game_id,
-            game.status,
-            game.winner,
-            winner_label
-        );
-
-        Ok(())
+    ///
+    /// result_multiplier: 1 = обычная победа, 2 = марс (gammon), 3 = кокс (backgammon).
+    /// Банк покрывает только одинарную стоимость куба, поэтому надбавка
+    /// stake * cube_value * (multiplier - 1) переводится проигравшим напрямую
+    /// победителю. По правилу Джекоби (jacoby) при неповёрнутом кубе множитель
+    /// ограничивается единицей.
+    ///
+    /// При config.protocol_fee_bps > 0 из выигрыша (банк и надбавка)
+    /// удерживается комиссия протокола в FeeVault; её доля config.burn_bps
+    /// сжигается переводом на incinerator (событие ProtocolFeeCollected).
+    ///
+    /// remaining_accounts: получатели PayoutSplits победителя в порядке записей
+    /// (если сплиты переданы).
+    pub fn finish_game<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinishGame<'info>>,
+        winner: Pubkey,
+        result_multiplier: u8,
+    ) -> Result<()> {
+        instructions::finish_game::handler(ctx, winner, result_multiplier)
     }
 
-    /// Отмена игры до присоединения второго игрока.
+    /// Расчёт по доске без подписей: вызвать может кто угодно (например, кранк).
     ///
-    /// Используется для случая, когда второй игрок так и не зашёл в игру.
-    /// Возвращает весь банк (ставку) первому игроку.
-    pub fn cancel_before_join(ctx: Context<CancelBeforeJoin>) -> Result<()> {
-        let game = &mut ctx.accounts.game;
+    /// Работает для игр, доска которых ведётся со стартовой позиции
+    /// (board_from_start). Если на официальной доске не осталось шашек одной
+    /// стороны, а у другой они есть, первая считается снявшей все шашки и
+    /// выигрывает; банк и рейтинг — как в finish_game с обычной победой.
+    /// Надбавку за марс/кокс без подписи проигравшего не списать, она остаётся
+    /// за finish_game. Доска не различает бар и снятые шашки, поэтому пустая
+    /// сторона всегда трактуется как снявшая.
+    pub fn finalize_from_board<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeFromBoard<'info>>,
+    ) -> Result<()> {
+        instructions::finalize_from_board::handler(ctx)
+    }
+
+    /// Начало проверки полного лога партии (GameLog, PDA [b"game_log", game, submitter]).
+    ///
+    /// Лог переигрывается со стартовой позиции, поэтому нужна игра с
+    /// board_from_start. Сами ходы подаются частями через submit_game_log.
+    pub fn start_game_log(ctx: Context<StartGameLog>) -> Result<()> {
+        instructions::start_game_log::handler(ctx)
+    }
+
+    /// Очередная часть лога: ходы переигрываются по порядку с cursor.
+    ///
+    /// Каждый ход должен получаться из предыдущей позиции законной
+    /// расстановкой по своим кубикам (см. ReplayPosition::play_move). На
+    /// первом несовпадении лог помечается failed, cursor указывает на этот
+    /// ход. Когда cursor доходит до move_index игры, итоговая доска и
+    /// последние кубики сверяются с официальными, и при совпадении ставится
+    /// verified. Промежуточные кубики на чейне не хранятся (они есть в
+    /// событиях MoveMade), поэтому verified означает: существует законная
+    /// партия с кубиками лога, приводящая к текущей доске.
+    pub fn submit_game_log(ctx: Context<SubmitGameLog>, moves: Vec<LoggedMove>) -> Result<()> {
+        instructions::submit_game_log::handler(ctx, moves)
+    }
+
+    /// Создание шарда реестра открытых лобби (PDA [b"lobby", shard]); платит кто угодно.
+    pub fn init_lobby_shard(ctx: Context<InitLobbyShard>, shard: u8) -> Result<()> {
+        instructions::init_lobby_shard::handler(ctx, shard)
+    }
+
+    /// Публикация открытой игры в шарде лобби (обычно в одной транзакции с
+    /// init_game). Подписывает создатель игры; рейтинг создателя берётся из
+    /// его PlayerStats (DEFAULT_RATING, если статистики ещё нет).
+    pub fn list_game(ctx: Context<ListGame>) -> Result<()> {
+        instructions::list_game::handler(ctx)
+    }
+
+    /// Снятие игры с лобби её создателем (без изменения самой игры).
+    pub fn unlist_game(ctx: Context<UnlistGame>) -> Result<()> {
+        instructions::unlist_game::handler(ctx)
+    }
+
+    /// Очистка шарда от устаревших записей; вызвать может кто угодно.
+    ///
+    /// remaining_accounts: аккаунты игр из записей шарда. Запись удаляется,
+    /// если игра уже не в WaitingForPlayer2 или её аккаунт закрыт.
+    pub fn prune_lobby<'info>(ctx: Context<'_, '_, 'info, 'info, PruneLobby<'info>>) -> Result<()> {
+        instructions::prune_lobby::handler(ctx)
+    }
+
+    /// Создание кольцевого буфера событий игры (EventBuffer) для клиентов,
+    /// которые опрашивают аккаунт вместо подписки на логи; платит кто угодно.
+    pub fn init_event_buffer(ctx: Context<InitEventBuffer>) -> Result<()> {
+        instructions::init_event_buffer::handler(ctx)
+    }
 
-        require!(
-            game.status == GameStatus::WaitingForPlayer2,
-            ErrorCode::GameNotWaitingForPlayer2
-        );
+    /// Закрытие буфера событий завершённой игры; рента — создателю буфера.
+    pub fn close_event_buffer(ctx: Context<CloseEventBuffer>) -> Result<()> {
+        instructions::close_event_buffer::handler(ctx)
+    }
 
-        let amount = game.pot_lamports;
-        msg!(
-            "cancel_before_join: refunding {} lamports to player1={}",
-            amount,
-            game.player1
-        );
+    /// Регистрация сплитов выплат игрока: до PAYOUT_SPLITS_MAX получателей,
+    /// доли в б.п. в сумме 10 000.
+    ///
+    /// finish_game и возвраты (force_refund, manual_refund, settle_by_move_cap,
+    /// cancel_before_join) делят выплату на кошелёк игрока между получателями;
+    /// остаток от округления — первому. Повторный вызов заменяет сплиты.
+    pub fn set_payout_splits(
+        ctx: Context<SetPayoutSplits>,
+        splits: Vec<PayoutSplit>,
+    ) -> Result<()> {
+        instructions::set_payout_splits::handler(ctx, splits)
+    }
 
-        // Переводим банк обратно игроку напрямую (аккаунт игры принадлежит нашей программе).
-        **game.to_account_info().try_borrow_mut_lamports()? -= amount;
-        **ctx
-            .accounts
-            .player1
-            .to_account_info()
-            .try_borrow_mut_lamports()? += amount;
+    /// Удаление сплитов: выплаты снова идут на кошелёк игрока, рента возвращается.
+    pub fn close_payout_splits(ctx: Context<ClosePayoutSplits>) -> Result<()> {
+        instructions::close_payout_splits::handler(ctx)
+    }
+
+    /// Первый шаг возврата банка из пула: фиксирует эпоху запроса.
+    ///
+    /// Пул принимает вывод только после обновления в новой эпохе, поэтому
+    /// claim_pot_unstake доступен начиная со следующей эпохи. Запросить может
+    /// любой из игроков или арбитр.
+    pub fn request_pot_unstake(ctx: Context<RequestPotUnstake>) -> Result<()> {
+        instructions::request_pot_unstake::handler(ctx)
+    }
 
-        game.pot_lamports = 0;
-        game.player1_deposit = 0;
-        game.status = GameStatus::Finished;
+    /// Второй шаг: вывод из пула (WithdrawSol) обратно на аккаунт игры.
+    ///
+    /// Вызвать может кто угодно. Доход пула добавляется в банк и к депозитам
+    /// пропорционально вкладам игроков: победителю он уходит вместе с банком,
+    /// при возвратах делится по вкладам, а не пополам.
+    /// Если пул вернул меньше внесённого (комиссии пула), недостача списывается
+    /// с депозитов игроков пропорционально, а сверх депозитов — с комиссий и
+    /// чаевых; вывод поэтому проходит при любой потере.
+    pub fn claim_pot_unstake(ctx: Context<ClaimPotUnstake>) -> Result<()> {
+        instructions::claim_pot_unstake::handler(ctx)
+    }
+
+    /// Новый чанк истории ходов, когда текущий заполнен; подписывает и
+    /// платит ренту любой из игроков. Новый чанк ссылается на предыдущий
+    /// (prev_chunk) и становится GameState::current_history_chunk.
+    pub fn rotate_history_chunk(ctx: Context<RotateHistoryChunk>) -> Result<()> {
+        instructions::rotate_history_chunk::handler(ctx)
+    }
+
+    /// Закрытие последнего чанка истории ходов (GameHistory) завершённой
+    /// игры; рента — тому, кто платил за чанк. Чанки закрываются по одному
+    /// от последнего к первому; пока история не закрыта целиком, игру не
+    /// закрывают prune_games и escheat. Вызвать может кто угодно.
+    pub fn close_game_history(ctx: Context<CloseGameHistory>) -> Result<()> {
+        instructions::close_game_history::handler(ctx)
+    }
+
+    /// Проверка записи хода по Merkle-корню истории (HistoryCommitment):
+    /// proof — соседние узлы от листа entry (позиция move_index - 1) к корню,
+    /// см. backgammon_core::merkle_proof. Доказанная запись пишется в событие
+    /// HistoryEntryVerified, на которое ссылаются в споре (submit_evidence,
+    /// arbiter_resolve); неверная — HistoryProofInvalid. Ничего не меняет.
+    pub fn verify_history_entry(
+        ctx: Context<VerifyHistoryEntry>,
+        entry: MoveRecord,
+        proof: [[u8; 32]; 16],
+    ) -> Result<()> {
+        instructions::verify_history_entry::handler(ctx, entry, proof)
+    }
+
+    /// Закрытие Merkle-коммитмента ходов (HistoryCommitment) завершённой
+    /// игры; рента — тому, кто за него платил. Пока он не закрыт, игру не
+    /// закрывают prune_games и escheat. Вызвать может кто угодно.
+    pub fn close_history_commitment(ctx: Context<CloseHistoryCommitment>) -> Result<()> {
+        instructions::close_history_commitment::handler(ctx)
+    }
+
+    /// Закрытие GameLog; рента возвращается подавшему лог.
+    pub fn close_game_log(ctx: Context<CloseGameLog>) -> Result<()> {
+        instructions::close_game_log::handler(ctx)
+    }
+
+    /// Отмена игры до присоединения второго игрока.
+    ///
+    /// Используется для случая, когда второй игрок так и не зашёл в игру.
+    /// Возвращает весь банк (ставку) первому игроку.
+    ///
+    /// remaining_accounts: получатели PayoutSplits первого игрока (если переданы).
+    pub fn cancel_before_join<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelBeforeJoin<'info>>,
+    ) -> Result<()> {
+        instructions::cancel_before_join::handler(ctx)
+    }
 
-        Ok(())
+    /// Повторное приглашение после истечения срока входа.
+    ///
+    /// Доступно только первому игроку, пока игра ждёт второго игрока, срок
+    /// входа уже истёк, а ставка первого игрока всё ещё в банке. Устанавливает
+    /// новый срок и, если new_player2 не Pubkey::default(), нового приглашённого.
+    pub fn reopen_invite(
+        ctx: Context<ReopenInvite>,
+        new_player2: Pubkey,
+        new_deadline_slot: u64,
+    ) -> Result<()> {
+        instructions::reopen_invite::handler(ctx, new_player2, new_deadline_slot)
     }
 
     /// Аварийный возврат средств обоим игрокам по тайм-ауту.
     ///
     /// Если игра зависла в Active (кто-то не ходит / не подписывает),
     /// и с момента последнего действия прошло достаточно слотов, то
-    /// банк делится между игроками пропорционально их вкладам.
-    pub fn force_refund(ctx: Context<ForceRefund>) -> Result<()> {
-        let game = &mut ctx.accounts.game;
-
-        require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
-
-        let current_slot = Clock::get()?.slot;
-        let last = game.last_activity_slot;
-
-        msg!(
-            "force_refund: current_slot={}, last_activity_slot={}",
-            current_slot,
-            last
-        );
-
-        require!(
-            current_slot
-                .checked_sub(last)
-                .ok_or(ErrorCode::MathOverflow)?
-                >= FORCE_REFUND_TIMEOUT_SLOTS,
-            ErrorCode::TimeoutNotReached
-        );
-
-        let total_p1 = game
-            .player1_deposit
-            .checked_add(game.player1_fees_paid)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let total_p2 = game
-            .player2_deposit
-            .checked_add(game.player2_fees_paid)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        let pot = game.pot_lamports;
-        msg!(
-            "force_refund: pot={}, total_p1={}, total_p2={}",
-            pot,
-            total_p1,
-            total_p2
-        );
-
-        let total = total_p1
-            .checked_add(total_p2)
-            .ok_or(ErrorCode::MathOverflow)?;
-        require!(total == pot, ErrorCode::InconsistentPot);
-
-        // Возвращаем каждому ровно его вклад. Аккаунт игры принадлежит нашей программе,
-        // поэтому можем напрямую изменять его баланс.
-        if total_p1 > 0 {
-            **game.to_account_info().try_borrow_mut_lamports()? -= total_p1;
-            **ctx
-                .accounts
-                .player1
-                .to_account_info()
-                .try_borrow_mut_lamports()? += total_p1;
-        }
+    /// банк делится между игроками пропорционально их вкладам; нечётный
+    /// лампорт чаевых уходит на config.treasury, если казна задана.
+    ///
+    /// remaining_accounts: получатели PayoutSplits (сначала первого игрока,
+    /// затем второго) в порядке записей — только для переданных сплитов.
+    pub fn force_refund<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForceRefund<'info>>,
+    ) -> Result<()> {
+        instructions::force_refund::handler(ctx)
+    }
 
-        if total_p2 > 0 {
-            **game.to_account_info().try_borrow_mut_lamports()? -= total_p2;
-            **ctx
-                .accounts
-                .player2
-                .to_account_info()
-                .try_borrow_mut_lamports()? += total_p2;
-        }
+    /// Ручной возврат средств обоим игрокам одним подписантом.
+    ///
+    /// Требует подписи только одного игрока (requester), который также платит комиссию.
+    /// Возвращает обоим игрокам их депозиты + все уплаченные комиссии за ходы;
+    /// остаток от деления чаевых — на config.treasury, если казна задана.
+    ///
+    /// remaining_accounts: получатели PayoutSplits (сначала первого игрока,
+    /// затем второго) в порядке записей — только для переданных сплитов.
+    pub fn manual_refund<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManualRefundOneSigner<'info>>,
+    ) -> Result<()> {
+        instructions::manual_refund::handler(ctx)
+    }
 
-        game.pot_lamports = 0;
-        game.player1_deposit = 0;
-        game.player2_deposit = 0;
-        game.player1_fees_paid = 0;
-        game.player2_fees_paid = 0;
-        game.status = GameStatus::Finished;
+    /// Сводка игры для лёгких клиентов (GameSummary через return data).
+    ///
+    /// Вызывается через simulateTransaction вместо чтения и разбора всего
+    /// GameState; ничего не меняет и не требует подписей. time_control
+    /// читается, только если PDA создан (пресеты кроме Casual).
+    pub fn get_game_summary(ctx: Context<GetGameSummary>) -> Result<GameSummary> {
+        instructions::get_game_summary::handler(ctx)
+    }
 
-        Ok(())
+    /// Расчёт игры, упёршейся в предел ходов (max_moves).
+    ///
+    /// Вызывает любой из игроков, когда move_index достиг предела: банк
+    /// делится как в manual_refund — каждому его вклад и половина чаевых.
+    /// Режима подсчёта пипсов в программе нет, поэтому других способов
+    /// раздела не предусмотрено. Итог отмечается событием MoveCapSettled.
+    ///
+    /// remaining_accounts: получатели PayoutSplits (сначала первого игрока,
+    /// затем второго) в порядке записей — только для переданных сплитов.
+    pub fn settle_by_move_cap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleByMoveCap<'info>>,
+    ) -> Result<()> {
+        instructions::settle_by_move_cap::handler(ctx)
     }
 
-    /// Ручной возврат средств обоим игрокам одним подписантом.
+    /// Договорное завершение игры: банк делится в согласованных суммах.
     ///
-    /// Требует подписи только одного игрока (requester), который также платит комиссию.
-    /// Возвращает обоим игрокам их депозиты + все уплаченные комиссии за ходы.
-    pub fn manual_refund(ctx: Context<ManualRefundOneSigner>) -> Result<()> {
-        let game = &mut ctx.accounts.game;
-
-        require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
-
-        // Валидация игроков
-        require_keys_eq!(
-            ctx.accounts.player1.key(),
-            game.player1,
-            ErrorCode::InvalidPlayer1
-        );
-        require_keys_eq!(
-            ctx.accounts.player2.key(),
-            game.player2,
-            ErrorCode::InvalidPlayer2
-        );
-
-        // requester должен быть одним из игроков
-        let requester = ctx.accounts.requester.key();
-        require!(
-            requester == game.player1 || requester == game.player2,
-            ErrorCode::InvalidPlayer
-        );
-
-        msg!(
-            "manual_refund: requester={}, game_id={}, pot={}",
-            requester,
-            game.game_id,
-            game.pot_lamports
-        );
-
-        let total_p1 = game
-            .player1_deposit
-            .checked_add(game.player1_fees_paid)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let total_p2 = game
-            .player2_deposit
-            .checked_add(game.player2_fees_paid)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        let pot = game.pot_lamports;
-        msg!(
-            "manual_refund: pot={}, total_p1={}, total_p2={}",
-            pot,
-            total_p1,
-            total_p2
-        );
-
-        let total = total_p1
-            .checked_add(total_p2)
-            .ok_or(ErrorCode::MathOverflow)?;
-        require!(total == pot, ErrorCode::InconsistentPot);
-
-        // Возвращаем каждому ровно его вклад
-        if total_p1 > 0 {
-            **game.to_account_info().try_borrow_mut_lamports()? -= total_p1;
-            **ctx
-                .accounts
-                .player1
-                .to_account_info()
-                .try_borrow_mut_lamports()? += total_p1;
-        }
+    /// Подписывают оба игрока в одной транзакции, p1_amount + p2_amount должно
+    /// в точности равняться pot_lamports. Разрешено и в споре (Disputed) —
+    /// договорённость закрывает спор. Победитель не записывается.
+    pub fn settle_game<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleGame<'info>>,
+        p1_amount: u64,
+        p2_amount: u64,
+    ) -> Result<()> {
+        instructions::settle_game::handler(ctx, p1_amount, p2_amount)
+    }
 
-        if total_p2 > 0 {
-            **game.to_account_info().try_borrow_mut_lamports()? -= total_p2;
-            **ctx
-                .accounts
-                .player2
-                .to_account_info()
-                .try_borrow_mut_lamports()? += total_p2;
-        }
+    /// Страйк за просроченный ход.
+    ///
+    /// Если игрок, от которого ждут действия (ход или ответ на удвоение), не
+    /// успел за срок хода (move_timeout_slots пресета плюс его запас),
+    /// соперник записывает ему страйк: срок хода обновляется, а счётчик
+    /// нарушителя растёт. На strike_limit-м страйке та же инструкция вместо
+    /// этого присуждает победу заявителю и выплачивает ему банк.
+    pub fn record_strike<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordStrike<'info>>,
+    ) -> Result<()> {
+        instructions::record_strike::handler(ctx)
+    }
 
-        game.pot_lamports = 0;
-        game.player1_deposit = 0;
-        game.player2_deposit = 0;
-        game.player1_fees_paid = 0;
-        game.player2_fees_paid = 0;
-        game.status = GameStatus::Finished;
+    /// Чаевые от зрителя в банк активной игры.
+    ///
+    /// Чаевые входят в pot_lamports и учитываются отдельно в tips_lamports:
+    /// при finish_game (и drop_double) они уходят победителю вместе с банком,
+    /// при возврате (force_refund/manual_refund) делятся между игроками пополам.
+    pub fn tip_pot(ctx: Context<TipPot>, amount: u64) -> Result<()> {
+        instructions::tip_pot::handler(ctx, amount)
+    }
 
-        msg!(
-            "manual_refund: completed, game_id={}, requester={}",
-            game.game_id,
-            requester
-        );
+    /// Предложение удвоения (куб).
+    ///
+    /// Удвоить может только игрок, чей сейчас ход, до своего хода и только
+    /// если куб в центре или принадлежит ему. Предлагающий сразу вносит в банк
+    /// свою доплату (stake_lamports * cube_value), поэтому после принятия
+    /// банк всегда покрывает новое значение куба.
+    pub fn offer_double(ctx: Context<OfferDouble>) -> Result<()> {
+        instructions::offer_double::handler(ctx)
+    }
 
-        Ok(())
+    /// Принятие удвоения.
+    ///
+    /// Принимающий (соперник игрока на ходу) вносит свою доплату, значение куба
+    /// удваивается, а владение кубом переходит к принявшему.
+    pub fn take_double(ctx: Context<TakeDouble>) -> Result<()> {
+        instructions::take_double::handler(ctx)
     }
-}
 
+    /// Порог автоматического взятия куба и предоплаченный резерв под него.
+    ///
+    /// Игрок заранее соглашается брать удвоения до куба up_to включительно
+    /// (0 = выключено). Резерв stake * (up_to - 1) покрывает все доплаты
+    /// по пути к up_to и хранится на аккаунте игры вне банка: при повышении
+    /// порога доплачивается, при понижении излишек сразу возвращается. После
+    /// завершения игры set_auto_take(0) забирает остаток резерва.
+    pub fn set_auto_take(ctx: Context<SetAutoTake>, up_to: u8) -> Result<()> {
+        instructions::set_auto_take::handler(ctx, up_to)
+    }
 
-/// Это on-chain аккаунт, который хранит состояние одной игры.
-#[account]
-pub struct GameState {
-    pub player1: Pubkey,          // 32 байта
-    pub player2: Pubkey,          // 32 байта
-    pub game_id: u64,             // 8
-    pub stake_lamports: u64,      // 8
-    pub move_fee_lamports: u64,   // 8
-    pub pot_lamports: u64,        // 8
-    pub player1_deposit: u64,     // 8
-    pub player2_deposit: u64,     // 8
-    pub player1_fees_paid: u64,   // 8
-    pub player2_fees_paid: u64,   // 8
-    pub last_activity_slot: u64,  // 8
-    pub move_index: u64,          // 8
-    pub board_points: [i8; 24],   // 24 пунктов доски, знак = владелец
-    pub dice: [u8; 2],            // последние выпавшие кубики
-    pub current_turn: u8,         // 1
-    pub status: GameStatus,       // ~1
-    pub winner: Pubkey,           // 32
-    pub bump: u8,                 // 1
-}
+    /// Автоматическое взятие удвоения за спящего игрока; вызвать может кто угодно.
+    ///
+    /// Срабатывает, если новое значение куба не выше auto_take_up_to
+    /// принимающего; доплата берётся из его резерва. Выше порога действует
+    /// обычный take/drop с тайм-аутом (record_strike).
+    pub fn auto_take(ctx: Context<AutoTake>) -> Result<()> {
+        instructions::auto_take::handler(ctx)
+    }
 
-// Ассоциированная константа для расчёта размера аккаунта.
-// Мы берём с запасом.
-impl GameState {
-    pub const MAX_SIZE: usize = 256;
-}
+    /// Отказ от удвоения.
+    ///
+    /// Принимающий сдаётся при прежнем значении куба: весь банк (включая уже
+    /// внесённую доплату предложившего) уходит игроку, предложившему удвоение.
+    /// Рейтинг — как в finish_game.
+    pub fn drop_double<'info>(ctx: Context<'_, '_, 'info, 'info, DropDouble<'info>>) -> Result<()> {
+        instructions::drop_double::handler(ctx)
+    }
 
-/// Тайм-аут в слотах для аварийного возврата средств.
-/// Для демо на localnet держим маленьким (например, 5 слотов).
-pub const FORCE_REFUND_TIMEOUT_SLOTS: u64 = 600;
-
-/// Enum тоже хранится on-chain, поэтому нужен Serialize/Deserialize.
-/// Для логирования через `{:?}` добавляем также Debug.
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
-pub enum GameStatus {
-    WaitingForPlayer2,
-    Active,
-    Finished,
-}
+    /// Бобёр (beaver): принимающий берёт удвоение и сразу же переудваивает,
+    /// оставляя куб у себя.
+    ///
+    /// Доступно только при beavers_allowed и только как ответ на предложенное
+    /// удвоение (после take/drop окно закрыто). Куб переходит с v сразу на 4v,
+    /// поэтому доплаты обоих игроков вносятся атомарно и требуются обе подписи.
+    /// После бобра у предложившего открывается окно для енота (raccoon).
+    pub fn beaver_double(ctx: Context<BeaverDouble>) -> Result<()> {
+        instructions::beaver_double::handler(ctx)
+    }
 
-/// Контекст для присоединения второго игрока.
-#[derive(Accounts)]
-pub struct JoinGame<'info> {
-    /// Аккаунт игры. Уже должен быть инициализирован через init_game.
-    #[account(mut)]
-    pub game: Account<'info, GameState>,
+    /// Енот (raccoon): ответ предложившего удвоение на бобра.
+    ///
+    /// Куб ещё раз удваивается, владение кубом остаётся у принявшего (бобра).
+    /// Окно закрывается первым же ходом предложившего.
+    pub fn raccoon_double(ctx: Context<RaccoonDouble>) -> Result<()> {
+        instructions::raccoon_double::handler(ctx)
+    }
 
-    /// Второй игрок, вносит свою стартовую ставку.
-    #[account(mut)]
-    pub player2: Signer<'info>,
+    /// Ничья на стартовом броске (кубики бросаются оффчейн, оба игрока подтверждают).
+    ///
+    /// При включённых auto_doubles куб в центре удваивается, а доплату
+    /// stake_lamports * cube_value вносят оба игрока. Если лимит автоудвоений
+    /// исчерпан, куб на максимуме или кто-то из игроков не может покрыть доплату,
+    /// куб не трогаем — это обычная переброска, о которой сообщает событие
+    /// AutoDoubleSkipped.
+    pub fn record_opening_tie(ctx: Context<RecordOpeningTie>) -> Result<()> {
+        instructions::record_opening_tie::handler(ctx)
+    }
 
-    /// Системная программа Solana.
-    pub system_program: Program<'info, System>,
-}
+    /// Создание шуэта (chouette) поверх существующей игры.
+    ///
+    /// Игрок в «коробке» (box) — это game.player1, капитан команды — game.player2;
+    /// доску играют только они через обычные make_move. Остальные члены команды
+    /// (до CHOUETTE_MAX_MEMBERS) держат с коробкой отдельные ставки и кубы в этом аккаунте.
+    pub fn init_chouette(ctx: Context<InitChouette>, stake_lamports: u64) -> Result<()> {
+        instructions::init_chouette::handler(ctx, stake_lamports)
+    }
 
-/// Отмена игры до присоединения второго игрока.
-#[derive(Accounts)]
-pub struct CancelBeforeJoin<'info> {
-    /// Аккаунт игры.
-    #[account(mut)]
-    pub game: Account<'info, GameState>,
+    /// Вход члена команды в шуэт: член команды и коробка вносят по stake_lamports.
+    pub fn join_chouette(ctx: Context<JoinChouette>) -> Result<()> {
+        instructions::join_chouette::handler(ctx)
+    }
 
-    /// Первый игрок, который создавал игру и может её отменить.
-    #[account(mut, address = game.player1)]
-    pub player1: Signer<'info>,
+    /// Коробка предлагает удвоение отдельному члену команды.
+    ///
+    /// Коробка сразу вносит свою доплату (stake * cube_value члена).
+    /// В MVP удвоения предлагает только коробка.
+    pub fn chouette_offer_double(
+        ctx: Context<ChouetteOfferDouble>,
+        member_index: u8,
+    ) -> Result<()> {
+        instructions::chouette_offer_double::handler(ctx, member_index)
+    }
 
-    /// Системная программа Solana.
-    pub system_program: Program<'info, System>,
-}
+    /// Член команды принимает удвоение: вносит доплату, его куб удваивается.
+    pub fn chouette_take(ctx: Context<ChouetteRespond>, member_index: u8) -> Result<()> {
+        instructions::chouette_take::handler(ctx, member_index)
+    }
 
-/// Аварийный возврат средств обоим игрокам по тайм-ауту.
-#[derive(Accounts)]
-pub struct ForceRefund<'info> {
-    /// Аккаунт игры.
-    #[account(mut)]
-    pub game: Account<'info, GameState>,
+    /// Член команды отказывается от удвоения: его ставка и ставка коробки против
+    /// него сразу уходят коробке, дальше он в расчёте не участвует.
+    pub fn chouette_drop(ctx: Context<ChouetteDrop>, member_index: u8) -> Result<()> {
+        instructions::chouette_drop::handler(ctx, member_index)
+    }
 
-    /// Первый игрок.
-    #[account(mut, address = game.player1)]
-    pub player1: Signer<'info>,
+    /// Расчёт шуэта по результату игры.
+    ///
+    /// Победитель берётся из game.winner. Если выиграла коробка, она забирает
+    /// ставки всех оставшихся членов команды; если капитан — каждый член команды
+    /// забирает свою ставку и ставку коробки против себя. Если игра закончилась
+    /// возвратом (winner не задан), каждому возвращается его вклад.
+    ///
+    /// remaining_accounts: аккаунты активных членов команды в порядке их слотов.
+    pub fn settle_chouette<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleChouette<'info>>,
+    ) -> Result<()> {
+        instructions::settle_chouette::handler(ctx)
+    }
 
-    /// Второй игрок.
-    #[account(mut, address = game.player2)]
-    pub player2: Signer<'info>,
+    /// Открытие зрительского пари на исход игры.
+    ///
+    /// Ставка зрителя хранится в отдельном аккаунте SideBet и никак не
+    /// затрагивает банк игры и её расчёт.
+    pub fn open_side_bet(
+        ctx: Context<OpenSideBet>,
+        bet_id: u64,
+        predicted_winner: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::open_side_bet::handler(ctx, bet_id, predicted_winner, amount)
+    }
 
-    /// Системная программа Solana.
-    pub system_program: Program<'info, System>,
-}
+    /// Встречная ставка на противоположный исход на ту же сумму.
+    pub fn match_side_bet(ctx: Context<MatchSideBet>) -> Result<()> {
+        instructions::match_side_bet::handler(ctx)
+    }
 
-/// Ручной возврат одним подписантом (requester).
-#[derive(Accounts)]
-pub struct ManualRefundOneSigner<'info> {
-    /// Аккаунт игры.
-    #[account(mut)]
-    pub game: Account<'info, GameState>,
+    /// Расчёт сведённого пари по итогу игры; вызвать может кто угодно.
+    ///
+    /// Победитель читается из самого GameState (адрес проверяется по side_bet.game).
+    /// Угадавший забирает обе ставки; если игра завершилась возвратом (winner не
+    /// задан), каждый получает свою ставку обратно. Аренда возвращается bettor.
+    pub fn settle_side_bet(ctx: Context<SettleSideBet>) -> Result<()> {
+        instructions::settle_side_bet::handler(ctx)
+    }
 
-    /// CHECK: address constraint ensures this is game.player1; balance only credited
-    #[account(mut, address = game.player1)]
-    pub player1: AccountInfo<'info>,
+    /// Возврат несведённого пари его автору (в любой момент до match_side_bet).
+    pub fn reclaim_side_bet(ctx: Context<ReclaimSideBet>) -> Result<()> {
+        instructions::reclaim_side_bet::handler(ctx)
+    }
 
-    /// CHECK: address constraint ensures this is game.player2; balance only credited
-    #[account(mut, address = game.player2)]
-    pub player2: AccountInfo<'info>,
+    /// Связывание двух игр в зеркальную пару (duplicate backgammon).
+    ///
+    /// Игры создаются обычными init_game/join_game с нулевой ставкой и
+    /// переставленными цветами: в game_a player1 = A, в game_b player1 = B.
+    /// Ставка пары вносится обоими игроками в общий эскроу MirrorPair
+    /// (по stake_lamports за каждую игру), а settle_pair выплачивает только
+    /// разницу результатов. Обе игры должны быть созданы с одним ненулевым
+    /// options.dice_seed (кубики бросаются оффчейн из этого сида) и ещё не
+    /// входить в пару: init_mirror_pair помечает их mirror_paired, и вторую
+    /// пару из них не собрать (GameAlreadyPaired).
+    pub fn init_mirror_pair(ctx: Context<InitMirrorPair>, stake_lamports: u64) -> Result<()> {
+        instructions::init_mirror_pair::handler(ctx, stake_lamports)
+    }
 
-    /// Инициатор запроса (один из игроков), платит комиссию за транзакцию.
-    #[account(mut)]
-    pub requester: Signer<'info>,
+    /// Взаимозачёт результатов зеркальной пары; вызвать может кто угодно.
+    ///
+    /// Каждая выигранная игра стоит stake_lamports. Если одна игра завершилась
+    /// возвратом (тайм-аут, winner не задан), она не учитывается, и пара
+    /// рассчитывается по второй игре независимо. Выигравший по сумме получает
+    /// свой вклад и разницу, проигравший — остаток своего вклада.
+    pub fn settle_pair(ctx: Context<SettlePair>) -> Result<()> {
+        instructions::settle_pair::handler(ctx)
+    }
 
-    /// Системная программа Solana.
-    pub system_program: Program<'info, System>,
-}
+    /// Напарник для консультационной игры 2 на 2.
+    ///
+    /// Вызывается до первого хода отдельно для каждой стороны: подписывают
+    /// основной игрок стороны и напарник. Напарник возмещает основному игроку
+    /// share_lamports из его взноса (например, половину ставки), и с этого
+    /// момента вклады обоих учитываются раздельно. Куб в командных играх
+    /// отключён, надбавку за марс/кокс платит основной ключ проигравшей стороны.
+    pub fn add_teammate(ctx: Context<AddTeammate>, share_lamports: u64) -> Result<()> {
+        instructions::add_teammate::handler(ctx, share_lamports)
+    }
 
-// Комментарий для содержимого хода.
+    /// Выплата участнику командной игры его доли после завершения.
+    ///
+    /// member: 0 = player1, 1 = напарник player1, 2 = player2, 3 = напарник
+    /// player2. Вызвать может кто угодно, деньги уходят только участнику.
+    pub fn claim_team_share(ctx: Context<ClaimTeamShare>, member: u8) -> Result<()> {
+        instructions::claim_team_share::handler(ctx, member)
+    }
 
-/// Контекст для совершения хода.
-#[derive(Accounts)]
-pub struct MakeMove<'info> {
-    /// Аккаунт игры.
-    #[account(mut)]
-    pub game: Account<'info, GameState>,
+    /// Создание глобального конфига программы (PDA [b"config"]) и первого сезона.
+    ///
+    /// Администратором становится подписант; вызывается один раз при деплое.
+    pub fn init_config(ctx: Context<InitConfig>) -> Result<()> {
+        instructions::init_config::handler(ctx)
+    }
 
-    /// Первый игрок, должен совпадать с game.player1.
-    #[account(mut, address = game.player1)]
-    pub player1: Signer<'info>,
+    /// Создание статистики игрока (PDA [b"stats", player]) со стартовым рейтингом.
+    pub fn init_player_stats(ctx: Context<InitPlayerStats>) -> Result<()> {
+        instructions::init_player_stats::handler(ctx)
+    }
 
-    /// Второй игрок, должен совпадать с game.player2.
-    #[account(mut, address = game.player2)]
-    pub player2: Signer<'info>,
+    /// Закрытие текущего сезона (только админ).
+    ///
+    /// Таблица сезона замораживается, открывается следующий сезон. Живые
+    /// рейтинги сжимаются к DEFAULT_RATING на 50% лениво — при первом
+    /// обновлении статистики игрока в новом сезоне.
+    pub fn close_season(ctx: Context<CloseSeason>) -> Result<()> {
+        instructions::close_season::handler(ctx)
+    }
 
-    /// Системная программа Solana, нужна для transfer через CPI.
-    pub system_program: Program<'info, System>,
-}
+    /// Необратимый отказ от админских прав (immutable mode).
+    ///
+    /// После вызова config.locked = true, и все админские инструкции
+    /// (update_config, add_ban, remove_ban, add_arbiter, remove_arbiter,
+    /// close_season, withdraw_bot_vault, withdraw_protocol_fees, propose_admin,
+    /// accept_admin и сам renounce_admin) навсегда отклоняются:
+    /// значения конфига замораживаются. Инструкции, сбрасывающей locked, нет.
+    pub fn renounce_admin(ctx: Context<UpdateConfig>) -> Result<()> {
+        instructions::renounce_admin::handler(ctx)
+    }
 
-/// Контекст для завершения игры и вывода банка победителю.
-#[derive(Accounts)]
-pub struct FinishGame<'info> {
-    /// Аккаунт игры.
-    #[account(mut)]
-    pub game: Account<'info, GameState>,
+    /// Первый шаг передачи прав администратора (только админ).
+    ///
+    /// Записывает new_admin в config.pending_admin; права переходят только
+    /// после accept_admin, подписанного этим ключом, поэтому опечатка в
+    /// адресе не оставляет программу без администратора. Повторный вызов
+    /// заменяет предложение, Pubkey::default() — отзывает его.
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin::handler(ctx, new_admin)
+    }
 
-    /// Первый игрок, должен совпадать с game.player1.
-    #[account(mut, address = game.player1)]
-    pub player1: Signer<'info>,
+    /// Второй шаг передачи прав: подписывает config.pending_admin.
+    ///
+    /// Администратором может быть любой ключ, в том числе PDA мультисига
+    /// (Squads) или native treasury SPL Governance: такой PDA подписывает
+    /// accept_admin и все админские инструкции через CPI с invoke_signed.
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
 
-    /// Второй игрок, должен совпадать с game.player2.
-    #[account(mut, address = game.player2)]
-    pub player2: Signer<'info>,
+    /// Изменение параметров конфига (только админ). Поля со значением None не меняются.
+    ///
+    /// paused — аварийный выключатель: останавливает init_game, join_game, bot_join,
+    /// make_move, куб (offer_double, take_double, beaver_double, raccoon_double,
+    /// record_opening_tie), встречные условия, чаевые, создание и вход в шуэт,
+    /// турнир, зеркальную пару и пари, а также выплаты победителю (finish_game,
+    /// drop_double, auto_take, settle_pair, settle_chouette, settle_tournament,
+    /// settle_side_bet, claim_team_share), чтобы эксплойт не мог вывести банк.
+    /// cancel_before_join, force_refund и manual_refund работают и на паузе,
+    /// чтобы игроки всегда могли забрать средства.
+    pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+        instructions::update_config::handler(ctx, update)
+    }
 
-    /// Системная программа Solana, нужна для transfer через CPI.
-    pub system_program: Program<'info, System>,
-}
+    /// Бан игрока (только админ): создаёт PDA [b"ban", player].
+    pub fn add_ban(ctx: Context<AddBan>, player: Pubkey) -> Result<()> {
+        instructions::add_ban::handler(ctx, player)
+    }
+
+    /// Передача средств давно заброшенной игры в казну (только админ).
+    ///
+    /// Допускается только для завершённых игр, простаивающих дольше
+    /// config.escheat_threshold(): в Active/Disputed всегда доступен
+    /// force_refund, а лобби может отменить создатель. Нераспределённые доли
+    /// команды, незакрытая история ходов (close_game_history,
+    /// close_history_commitment) и сведённые, но не рассчитанные пари зрителей
+    /// тоже блокируют escheat. Резервы автовзятий — не банк: они
+    /// возвращаются игрокам, а остаток (банк и рента) уходит на
+    /// config.treasury.
+    pub fn escheat(ctx: Context<Escheat>) -> Result<()> {
+        instructions::escheat::handler(ctx)
+    }
+
+    /// Закрытие старых завершённых игр; вызвать может кто угодно.
+    ///
+    /// remaining_accounts: до MAX_PRUNE_BATCH пар (аккаунт игры, её player1 —
+    /// плательщик ренты). Закрывается игра, для которой выполнено
+    /// GameState::prunable_at при сроке хранения config.prune_after_slots;
+    /// остальные пары пропускаются, не роняя вызов. Вызвавшему идёт
+    /// config.prune_reward_bps от ренты закрытой игры, player1 — весь
+    /// остальной баланс, включая переведённое сверх ренты.
+    /// После закрытия рассчитать пари зрителей на игру уже нельзя.
+    pub fn prune_games<'info>(ctx: Context<'_, '_, 'info, 'info, PruneGames<'info>>) -> Result<()> {
+        instructions::prune_games::handler(ctx)
+    }
+
+    /// Создание страхового пула (PDA [b"insurance_pool"]), только админ.
+    pub fn init_insurance_pool(ctx: Context<InitInsurancePool>) -> Result<()> {
+        instructions::init_insurance_pool::handler(ctx)
+    }
 
-/// Коды ошибок для удобной диагностики.
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Game is not waiting for player 2")]
-    GameNotWaitingForPlayer2,
+    /// Проверка целостности игры; вызвать может кто угодно.
+    ///
+    /// Игра считается застрявшей, если её не может развязать ни один путь
+    /// программы: вклады игроков (refund_totals) не сходятся с банком — тогда
+    /// force_refund и manual_refund всегда падают с InconsistentPot, — или на
+    /// аккаунте игры меньше lamports, чем банк плюс рента. Банк, размещённый в
+    /// пуле (game.staked_lamports), застреванием не считается. Для застрявшей
+    /// игры создаётся PDA GameFlag, по которому админ может выплатить
+    /// compensate.
+    pub fn integrity_check(ctx: Context<IntegrityCheck>) -> Result<()> {
+        instructions::integrity_check::handler(ctx)
+    }
 
-    #[msg("Invalid player 2")]
-    InvalidPlayer2,
+    /// Выплата игроку застрявшей игры из страхового пула (только админ).
+    ///
+    /// Игра должна быть помечена integrity_check. Сумма всех выплат игроку по
+    /// игре не превышает его записанный вклад (депозит + комиссии за ходы).
+    pub fn compensate(ctx: Context<Compensate>, player: Pubkey, amount: u64) -> Result<()> {
+        instructions::compensate::handler(ctx, player, amount)
+    }
 
-    #[msg("Game is not active")]
-    GameNotActive,
+    /// Создание хранилища комиссии протокола (PDA [b"fee_vault"]), только админ.
+    ///
+    /// Нужно до включения config.protocol_fee_bps: с ним finish_game
+    /// удерживает комиссию из выигрыша и требует аккаунт хранилища.
+    pub fn init_fee_vault(ctx: Context<InitFeeVault>) -> Result<()> {
+        instructions::init_fee_vault::handler(ctx)
+    }
 
-    #[msg("It's not this player's turn")]
-    NotPlayersTurn,
+    /// Вывод накопленной комиссии протокола на config.treasury (только админ).
+    ///
+    /// Выводить можно частями, но не больше collected - withdrawn - burned;
+    /// выведенное копится в withdrawn.
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_protocol_fees::handler(ctx, amount)
+    }
 
-    #[msg("Invalid current_turn value")]
-    InvalidCurrentTurn,
+    /// Снятие бана (только админ): PDA закрывается, рента возвращается админу.
+    pub fn remove_ban(ctx: Context<RemoveBan>) -> Result<()> {
+        instructions::remove_ban::handler(ctx)
+    }
 
-    #[msg("Math overflow")]
-    MathOverflow,
+    /// Добавление арбитра в белый список (только админ): создаёт PDA
+    /// [b"arbiter", arbiter]. Белый список действует, пока включён
+    /// config.require_whitelisted_arbiters.
+    pub fn add_arbiter(ctx: Context<AddArbiter>, arbiter: Pubkey) -> Result<()> {
+        instructions::add_arbiter::handler(ctx, arbiter)
+    }
 
-    #[msg("Invalid winner")]
-    InvalidWinner,
-    
-    #[msg("Invalid player 1")]
-    InvalidPlayer1,
+    /// Исключение арбитра из белого списка (только админ): PDA закрывается,
+    /// рента возвращается админу. Игры, где он назначен, теряют арбитра.
+    pub fn remove_arbiter(ctx: Context<RemoveArbiter>) -> Result<()> {
+        instructions::remove_arbiter::handler(ctx)
+    }
 
-    #[msg("Invalid player")]
-    InvalidPlayer,
+    /// Создание турнира.
+    ///
+    /// Турнир — PDA от организатора и tournament_id, в нём копятся взносы
+    /// участников (призовой фонд). На выбывание (Knockout) max_players должен
+    /// быть степенью двойки; в швейцарской системе (Swiss) — от 2 до
+    /// TOURNAMENT_MAX_PLAYERS, а swiss_rounds задаёт число туров.
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u64,
+        entry_fee: u64,
+        max_players: u8,
+        mode: TournamentMode,
+        swiss_rounds: u8,
+    ) -> Result<()> {
+        instructions::create_tournament::handler(
+            ctx,
+            tournament_id,
+            entry_fee,
+            max_players,
+            mode,
+            swiss_rounds,
+        )
+    }
 
-    #[msg("Not enough balance to pay move fee")]
-    NotEnoughBalanceForMove,
+    /// Регистрация игрока в турнире со взносом entry_fee в призовой фонд.
+    pub fn join_tournament(ctx: Context<JoinTournament>) -> Result<()> {
+        instructions::join_tournament::handler(ctx)
+    }
 
-    #[msg("Force refund timeout not reached yet")]
-    TimeoutNotReached,
+    /// Старт турнира: регистрация закрывается, сетка фиксируется.
+    ///
+    /// На выбывание: посев = порядок регистрации, в первом раунде играют пары
+    /// (0, 1), (2, 3), ...; турнир стартует только при полном составе.
+    /// В швейцарской системе достаточно двух участников, а пары первого тура
+    /// строит pair_round.
+    pub fn start_tournament(ctx: Context<TournamentOrganizer>) -> Result<()> {
+        instructions::start_tournament::handler(ctx)
+    }
 
-    #[msg("Inconsistent pot and recorded contributions")]
-    InconsistentPot,
-}
+    /// Фиксация результата матча текущего раунда по завершённой игре.
+    ///
+    /// Победитель берётся только из game.winner; игра должна быть создана
+    /// со ссылкой на этот турнир и текущий раунд и сыграна парой из сетки.
+    pub fn report_result(ctx: Context<ReportResult>) -> Result<()> {
+        instructions::report_result::handler(ctx)
+    }
 
-/// Контекст для init_game.
-///
-/// #[instruction(...)] говорит Anchor'у:
-/// "эта инструкция принимает такие-то аргументы, их можно использовать в seeds".
-#[derive(Accounts)]
-#[instruction(game_id: u64, player2_pubkey: Pubkey)]
-pub struct InitGame<'info> {
-    /// Аккаунт игры. Создаётся этой инструкцией.
-    #[account(
-        init,
-        payer = player1,
-        space = 8 + GameState::MAX_SIZE,
-    )]
-    pub game: Account<'info, GameState>,
-
-    /// Первый игрок, он платит за создание аккаунта и вносит первую ставку.
-    #[account(mut)]
-    pub player1: Signer<'info>,
-
-    /// Стандартная системная программа Solana, нужна для создания аккаунта.
-    pub system_program: Program<'info, System>,
-}
+    /// Переход к следующему раунду, когда все матчи текущего раунда сыграны.
+    ///
+    /// Если остался один игрок, он становится чемпионом и турнир завершается.
+    pub fn advance_round(ctx: Context<TournamentOrganizer>) -> Result<()> {
+        instructions::advance_round::handler(ctx)
+    }
 
+    /// Жеребьёвка следующего тура швейцарской системы.
+    ///
+    /// Предыдущий тур должен быть полностью сыгран. Игроки сортируются по
+    /// очкам (по убыванию), при равенстве — по порядку регистрации. При нечётном
+    /// числе участников bye (+1 очко) получает самый низкий в таблице игрок,
+    /// у которого ещё не было bye. Пары подбираются перебором с возвратом
+    /// (ограниченным SWISS_PAIRING_BUDGET шагами) так, чтобы избежать повторных
+    /// встреч; если это невозможно в рамках бюджета, допускаются повторы
+    /// (жадно, сверху вниз по таблице). Результат детерминирован.
+    pub fn pair_round(ctx: Context<TournamentOrganizer>) -> Result<()> {
+        instructions::pair_round::handler(ctx)
+    }
+
+    /// Фиксация результата игры текущего тура швейцарской системы.
+    ///
+    /// Победитель берётся из game.winner и получает одно очко.
+    pub fn report_swiss_result(ctx: Context<ReportResult>) -> Result<()> {
+        instructions::report_swiss_result::handler(ctx)
+    }
+
+    /// Итоговая таблица швейцарской системы: очки, затем Бухгольц
+    /// (сумма очков соперников), затем порядок регистрации.
+    /// Первый в таблице становится чемпионом.
+    pub fn finalize_swiss(ctx: Context<TournamentOrganizer>) -> Result<()> {
+        instructions::finalize_swiss::handler(ctx)
+    }
+
+    /// Выплата призового фонда чемпиону завершённого турнира.
+    pub fn settle_tournament(ctx: Context<SettleTournament>) -> Result<()> {
+        instructions::settle_tournament::handler(ctx)
+    }
+}
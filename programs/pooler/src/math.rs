@@ -0,0 +1,135 @@
+//! Арифметика банка без аккаунтов и рантайма: деление банка, доли
+//! выплат, страховое отчисление, комиссия за ход от банка, срез тающего
+//! банка, комиссия протокола и награда за prune_games, проверка решения
+//! settle_game.
+
+use crate::*;
+
+/// Кому достаётся остаток от деления в split_pot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Remainder {
+    /// Первой части: по соглашению это player1 (основной игрок стороны,
+    /// первый получатель сплита, плательщик доли в б.п.).
+    First,
+    /// В config.treasury: части округляются вниз, остаток возвращается
+    /// отдельно (см. Config::split_remainder).
+    Treasury,
+}
+
+/// Делит total на части пропорционально weights с детерминированным округлением.
+///
+/// Каждая часть — floor(total * weight / sum(weights)); остаток от деления
+/// (меньше числа частей) уходит по remainder: первой части или в казну —
+/// тогда он возвращается вторым значением, иначе там 0. При нулевой сумме
+/// весов весь total получает первая часть. Части вместе с остатком для
+/// казны всегда дают ровно total.
+pub fn split_pot<const N: usize>(
+    total: u64,
+    weights: [u64; N],
+    remainder: Remainder,
+) -> ([u64; N], u64) {
+    let mut parts = [0u64; N];
+    let to_treasury = split_into(total, &weights, &mut parts, remainder);
+    (parts, to_treasury)
+}
+
+/// Ядро split_pot над срезами одной длины; возвращает остаток для казны.
+fn split_into(total: u64, weights: &[u64], parts: &mut [u64], remainder: Remainder) -> u64 {
+    if parts.is_empty() {
+        return 0;
+    }
+    let sum: u128 = weights.iter().map(|&w| w as u128).sum();
+    if sum == 0 {
+        parts[0] = total;
+        return 0;
+    }
+    let mut assigned: u64 = 0;
+    for (part, &weight) in parts.iter_mut().zip(weights) {
+        // total * weight / sum <= total, поэтому результат помещается в u64.
+        *part = (total as u128 * weight as u128 / sum) as u64;
+        assigned += *part;
+    }
+    let rest = total - assigned;
+    match remainder {
+        Remainder::First => {
+            parts[0] += rest;
+            0
+        }
+        Remainder::Treasury => rest,
+    }
+}
+
+/// Доля bps от amount, округлённая вниз: split_pot на (BPS_DENOMINATOR - bps,
+/// bps), остаток от округления — первой части, то есть плательщику доли.
+fn bps_share(amount: u64, bps: u16) -> u64 {
+    let bps = (bps as u64).min(BPS_DENOMINATOR);
+    let ([_, share], _) = split_pot(amount, [BPS_DENOMINATOR - bps, bps], Remainder::First);
+    share
+}
+
+/// Проверка решения settle_game: доли сторон вместе дают ровно банк, ни
+/// одна не превышает его и сумма не переполняется.
+pub fn check_settlement(pot: u64, p1_amount: u64, p2_amount: u64) -> Result<()> {
+    require!(
+        p1_amount <= pot && p2_amount <= pot && p1_amount.checked_add(p2_amount) == Some(pot),
+        ErrorCode::InvalidSettlement
+    );
+    Ok(())
+}
+
+/// Доля insurance_fee_bps от комиссии за ход, округлённая вниз: остаток от
+/// округления остаётся в банке.
+pub fn insurance_cut(move_fee: u64, insurance_fee_bps: u16) -> u64 {
+    bps_share(move_fee, insurance_fee_bps)
+}
+
+/// Комиссия за ход в игре с move_fee_bps: доля bps от банка на момент хода,
+/// округлённая вниз, но не меньше 1 лампорта при bps > 0 (иначе в маленьком
+/// банке ходы были бы бесплатными). При bps = 0 — 0.
+pub fn pot_move_fee(pot: u64, move_fee_bps: u16) -> u64 {
+    if move_fee_bps == 0 {
+        return 0;
+    }
+    // init_game держит move_fee_bps <= BPS_DENOMINATOR, поэтому результат <= pot.
+    bps_share(pot, move_fee_bps).max(1)
+}
+
+/// Срез тающего банка за ход: доля decay_bps от банка перед ходом,
+/// округлённая вниз — остаток от округления остаётся в банке, поэтому
+/// маленький банк перестаёт таять.
+pub fn pot_decay(pot: u64, decay_bps: u16) -> u64 {
+    bps_share(pot, decay_bps)
+}
+
+/// Комиссия протокола: доля protocol_fee_bps от выигрыша, округлённая вниз —
+/// остаток от округления получает победитель.
+pub fn protocol_fee(winnings: u64, protocol_fee_bps: u16) -> u64 {
+    bps_share(winnings, protocol_fee_bps)
+}
+
+/// Сжигаемая часть комиссии протокола: доля burn_bps от fee, округлённая
+/// вниз — остаток от округления остаётся в FeeVault.
+pub fn burn_share(fee: u64, burn_bps: u16) -> u64 {
+    bps_share(fee, burn_bps)
+}
+
+/// Награда вызвавшему prune_games: доля prune_reward_bps от ренты закрытой
+/// игры, округлённая вниз; остальное получает плательщик ренты.
+pub fn prune_reward(rent: u64, prune_reward_bps: u16) -> u64 {
+    bps_share(rent, prune_reward_bps)
+}
+
+/// Доли amount по сплитам: split_pot по весам bps, остаток от округления —
+/// первому получателю. Для сплитов, прошедших validate_payout_splits (сумма
+/// bps = BPS_DENOMINATOR), доля — floor(amount * bps / BPS_DENOMINATOR), а
+/// сумма долей равна amount.
+pub fn payout_shares(amount: u64, splits: &[PayoutSplit]) -> Vec<u64> {
+    let weights: Vec<u64> = splits.iter().map(|split| split.bps as u64).collect();
+    let mut shares = vec![0; splits.len()];
+    split_into(amount, &weights, &mut shares, Remainder::First);
+    shares
+}
+
+/// Знаменатель долей в базисных пунктах (bps): 10_000 = 100%.
+#[constant]
+pub const BPS_DENOMINATOR: u64 = 10_000;
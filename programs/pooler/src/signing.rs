@@ -0,0 +1,100 @@
+//! Единый формат сообщений, которые игроки подписывают оффчейн.
+//!
+//! Каждая инструкция, принимающая Ed25519-подписи, проверяет их над
+//! [`SignedMessage`](signing::SignedMessage): программа, игра, действие,
+//! nonce игры, последний слот и sha256 данных действия. Принятое сообщение
+//! продвигает GameState::action_nonce, поэтому второй раз оно не пройдёт, а
+//! действие в сообщении не даёт выдать подпись под одно действие за другое.
+
+use super::{verify_ed25519_signatures, ErrorCode, GameState};
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hash;
+
+/// Действие, под которым стоит подпись (байт действия в сообщении).
+/// Новые действия добавляются только в конец.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignedAction {
+    /// relayed_move; данные — borsh RelayedMovePayload.
+    RelayedMove,
+}
+
+/// Подписываемое сообщение; его borsh-сериализация (LEN байт) и есть
+/// подписываемые байты.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignedMessage {
+    pub program_id: Pubkey,
+    pub game: Pubkey,
+    pub action: SignedAction,
+    /// GameState::action_nonce, при котором сообщение действительно.
+    pub nonce: u64,
+    /// Последний слот, в котором сообщение можно отправить.
+    pub expiry_slot: u64,
+    /// sha256 данных действия.
+    pub payload_hash: [u8; 32],
+}
+
+impl SignedMessage {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8 + 32;
+
+    /// Сообщение этой программы для игры game над данными payload.
+    pub fn new(
+        game: Pubkey,
+        action: SignedAction,
+        nonce: u64,
+        expiry_slot: u64,
+        payload: &[u8],
+    ) -> Self {
+        SignedMessage {
+            program_id: crate::ID,
+            game,
+            action,
+            nonce,
+            expiry_slot,
+            payload_hash: hash(payload).to_bytes(),
+        }
+    }
+
+    /// Байты для подписи. Размер сериализации постоянный и равен LEN, так
+    /// что ошибка возможна только при рассинхронизации LEN с полями.
+    pub fn to_bytes(&self) -> Result<[u8; Self::LEN]> {
+        let mut bytes = [0u8; Self::LEN];
+        self.serialize(&mut &mut bytes[..])
+            .map_err(|_| error!(ErrorCode::InvalidSignedMessage))?;
+        Ok(bytes)
+    }
+
+    /// Проверки без подписей: программа, игра, nonce и срок.
+    pub fn check(&self, game: &GameState, game_key: &Pubkey, current_slot: u64) -> Result<()> {
+        require_keys_eq!(self.program_id, crate::ID, ErrorCode::InvalidSignedMessage);
+        require_keys_eq!(self.game, *game_key, ErrorCode::InvalidSignedMessage);
+        require!(
+            self.nonce == game.action_nonce,
+            ErrorCode::SignedMessageNonceMismatch
+        );
+        require!(
+            current_slot <= self.expiry_slot,
+            ErrorCode::SignedMessageExpired
+        );
+        Ok(())
+    }
+}
+
+/// Принимает сообщение: проверяет его (SignedMessage::check) и подписи
+/// signers над ним в предыдущей Ed25519-инструкции, затем продвигает
+/// game.action_nonce. Возвращает подписи в порядке signers.
+pub fn consume(
+    instructions: &AccountInfo,
+    game: &mut GameState,
+    game_key: &Pubkey,
+    message: &SignedMessage,
+    signers: &[Pubkey],
+    current_slot: u64,
+) -> Result<Vec<[u8; 64]>> {
+    message.check(game, game_key, current_slot)?;
+    let signatures = verify_ed25519_signatures(instructions, &message.to_bytes()?, signers)?;
+    game.action_nonce = game
+        .action_nonce
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(signatures)
+}
@@ -0,0 +1,115 @@
+//! Доска: варианты правил, стартовые позиции, упаковка и хэш.
+
+use crate::*;
+
+/// Максимальная фора: число шашек, стартующих на баре.
+#[constant]
+pub const MAX_HANDICAP: u8 = backgammon_core::MAX_HANDICAP;
+
+/// Вариант правил: стартовая позиция и число шашек у каждой стороны.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub enum RuleVariant {
+    /// Классический бэкгаммон, 15 шашек.
+    #[default]
+    Standard,
+    /// Накгаммон, 15 шашек: задние шашки разбиты 2 + 2 на 24-м и 23-м пунктах.
+    Nackgammon,
+    /// Хайпергаммон, 3 шашки.
+    Hypergammon,
+}
+
+impl RuleVariant {
+    /// Тот же вариант в движке правил backgammon-core.
+    pub fn core(&self) -> backgammon_core::Variant {
+        match self {
+            RuleVariant::Standard => backgammon_core::Variant::Standard,
+            RuleVariant::Nackgammon => backgammon_core::Variant::Nackgammon,
+            RuleVariant::Hypergammon => backgammon_core::Variant::Hypergammon,
+        }
+    }
+
+    /// Число шашек у каждой стороны.
+    pub fn checkers(&self) -> u8 {
+        self.core().checkers()
+    }
+
+    /// Структурная проверка доски: у каждой стороны на доске не больше
+    /// checkers() шашек (остальные на баре или сняты).
+    pub fn board_fits(&self, board: &[i8; 24]) -> bool {
+        self.core().board_fits(board)
+    }
+}
+
+/// Ошибка движка правил как ошибка программы.
+pub fn board_error(err: backgammon_core::BoardError) -> Error {
+    match err {
+        backgammon_core::BoardError::InvalidBoard => ErrorCode::InvalidBoard.into(),
+        backgammon_core::BoardError::InvalidHandicap => ErrorCode::InvalidHandicap.into(),
+    }
+}
+
+/// Упакованная доска: по 5 бит на пункт (4 бита — число шашек 0..15, старший
+/// бит — владелец: 0 = player1, 1 = player2), пункты подряд от младшего бита.
+///
+/// Используется на входе make_move_packed, в истории ходов (GameHistory,
+/// листья HistoryCommitment) и, начиная с PACKED_BOARD_VERSION, в GameState
+/// (см. StoredBoard). События несут доску как [i8; 24].
+pub type PackedBoard = [u8; 15];
+
+/// Слот доски в GameState (board и pending_board). Размер прежнего поля
+/// [i8; 24] сохранён, чтобы не сдвигать смещения layout: с версии
+/// PACKED_BOARD_VERSION в нём лежит PackedBoard и 9 нулевых байт, у
+/// аккаунтов более ранних версий — пункты как i8. Какой формат в слоте,
+/// говорит GameState::version; читать и писать доску нужно через
+/// board_points / set_board_points.
+///
+/// Аккаунт игры от упаковки не уменьшается: слот, MAX_SIZE и рента те же.
+pub type StoredBoard = [u8; 24];
+
+/// Первая версия GameState с упакованной доской в StoredBoard.
+#[constant]
+pub const PACKED_BOARD_VERSION: u8 = 4;
+
+/// Доска из слота GameState версии version.
+pub fn load_board(stored: &StoredBoard, version: u8) -> Result<[i8; 24]> {
+    if version < PACKED_BOARD_VERSION {
+        return Ok(stored.map(|p| p as i8));
+    }
+    let (packed, padding) = stored.split_at(core::mem::size_of::<PackedBoard>());
+    require!(padding.iter().all(|&b| b == 0), ErrorCode::InvalidBoard);
+    unpack_board(packed.try_into().unwrap())
+}
+
+/// Доска в слот GameState версии version; для упакованного формата на
+/// пункте не больше 15 шашек.
+pub fn store_board(board: &[i8; 24], version: u8) -> Result<StoredBoard> {
+    if version < PACKED_BOARD_VERSION {
+        return Ok(board.map(|p| p as u8));
+    }
+    let mut stored = [0u8; 24];
+    stored[..core::mem::size_of::<PackedBoard>()].copy_from_slice(&pack_board(board)?);
+    Ok(stored)
+}
+
+/// Пакует доску [i8; 24] в PackedBoard; на пункте не больше 15 шашек.
+pub fn pack_board(board: &[i8; 24]) -> Result<PackedBoard> {
+    backgammon_core::pack(board).map_err(board_error)
+}
+
+/// Распаковывает PackedBoard; «пустой пункт player2» (бит владельца без
+/// шашек) считается ошибкой, чтобы у каждой доски была одна упаковка.
+pub fn unpack_board(packed: &PackedBoard) -> Result<[i8; 24]> {
+    backgammon_core::unpack(packed).map_err(board_error)
+}
+
+/// Стартовая позиция варианта с форой (см. backgammon_core::start_board).
+pub fn start_board(variant: RuleVariant, handicap: i8) -> Result<[i8; 24]> {
+    backgammon_core::start_board(variant.core(), handicap).map_err(board_error)
+}
+
+/// sha256 доски (пункты как байты), для сверки клиента с ончейн-состоянием.
+pub fn board_hash(board: &[i8; 24]) -> [u8; 32] {
+    hash(&board.map(|p| p as u8)).to_bytes()
+}
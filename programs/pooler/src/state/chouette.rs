@@ -0,0 +1,45 @@
+//! Шуэт: один «бокс» против команды с капитаном.
+
+use crate::*;
+
+/// Шуэт: один игрок в «коробке» против капитана и команды.
+///
+/// Доску играют коробка (game.player1) и капитан (game.player2), а каждый
+/// член команды держит с коробкой отдельную ставку и свой куб.
+#[account]
+pub struct ChouetteState {
+    pub game: Pubkey,                                    // 32
+    pub box_player: Pubkey,                              // 32
+    pub captain: Pubkey,                                 // 32
+    pub stake_lamports: u64,                             // 8, базовая ставка члена команды
+    pub members: [ChouetteMember; CHOUETTE_MAX_MEMBERS], // 4 * 51
+    pub member_count: u8,                                // 1
+    pub settled: bool,                                   // 1
+    pub bump: u8,                                        // 1
+}
+
+impl ChouetteState {
+    pub const MAX_SIZE: usize = 384;
+}
+
+/// Максимум членов команды в шуэте (без капитана).
+pub const CHOUETTE_MAX_MEMBERS: usize = 4;
+
+/// Ставка одного члена команды против коробки.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ChouetteMember {
+    pub member: Pubkey,               // 32
+    pub member_deposit: u64,          // 8
+    pub box_deposit: u64,             // 8, вклад коробки против этого члена
+    pub cube_value: u8,               // 1
+    pub double_pending: bool,         // 1
+    pub status: ChouetteMemberStatus, // 1
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ChouetteMemberStatus {
+    #[default]
+    Empty,
+    Active,
+    Dropped,
+}
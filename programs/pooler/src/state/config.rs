@@ -0,0 +1,136 @@
+//! Глобальный конфиг программы и его частичное обновление.
+
+use crate::*;
+
+/// Глобальный конфиг программы (PDA [b"config"]).
+#[account]
+pub struct Config {
+    pub admin: Pubkey,                      // 32
+    pub current_season: u32,                // 4
+    pub bump: u8,                           // 1
+    pub bans_enforced: bool,                // 1, проверять бан-лист в init_game/join_game
+    pub max_open_games: u16, // 2, лимит открытых лобби на создателя (0 = без ограничения)
+    pub bot_wallet: Pubkey,  // 32, ключ кранка бота (Pubkey::default() = бот выключен)
+    pub bot_max_stake: u64,  // 8, максимальная ставка, которую принимает бот
+    pub paused: bool,        // 1, аварийная пауза (см. update_config)
+    pub locked: bool,        // 1, админ отказался от прав (renounce_admin), навсегда
+    pub arbiter: Pubkey,     // 32, разрешает споры (arbiter_resolve)
+    pub min_create_gap_slots: u64, // 8, мин. слотов между init_game кошелька (0 = без ограничения)
+    pub stake_pool: Pubkey,  // 32, пул SPL Stake Pool для банка (Pubkey::default() = выкл.)
+    pub treasury: Pubkey,    // 32, казна: escheat и остатки возвратов (Pubkey::default() = нет)
+    pub escheat_after_slots: u64, // 8, простой игры до escheat (0 = DEFAULT_ESCHEAT_AFTER_SLOTS)
+    pub free_moves: u16,     // 2, первые ходы каждой игры без комиссии (промо)
+    pub insurance_fee_bps: u16, // 2, доля комиссии за ход в InsurancePool (б.п.)
+    pub protocol_fee_bps: u16, // 2, комиссия протокола с выигрыша finish_game в FeeVault (б.п.)
+    pub prune_after_slots: u64, // 8, хранение завершённой игры до prune_games (0 = DEFAULT_PRUNE_AFTER_SLOTS)
+    pub prune_reward_bps: u16,  // 2, доля возвращённой ренты вызвавшему prune_games (б.п.)
+    pub pending_admin: Pubkey,  // 32, предложенный админ до accept_admin (Pubkey::default() = нет)
+    pub require_whitelisted_arbiters: bool, // 1, арбитр игры должен быть в белом списке (add_arbiter)
+    pub appeal_window_slots: u64, // 8, окно апелляции на решение арбитра (0 = DEFAULT_APPEAL_WINDOW_SLOTS)
+    pub burn_bps: u16,            // 2, доля комиссии протокола, сжигаемая на incinerator (б.п.)
+    pub decay_to_treasury: bool,  // 1, срез тающих банков — в treasury, а не на incinerator
+    pub rating_enforced: bool, // 1, рейтинговые завершения требуют сезон и статистику обоих игроков
+}
+
+impl Config {
+    pub const MAX_SIZE: usize = 256;
+
+    /// Порог простоя для escheat (0 в аккаунте = DEFAULT_ESCHEAT_AFTER_SLOTS).
+    pub fn escheat_threshold(&self) -> u64 {
+        if self.escheat_after_slots == 0 {
+            DEFAULT_ESCHEAT_AFTER_SLOTS
+        } else {
+            self.escheat_after_slots.max(MIN_ESCHEAT_AFTER_SLOTS)
+        }
+    }
+
+    /// Срок хранения завершённой игры до prune_games (0 в аккаунте =
+    /// DEFAULT_PRUNE_AFTER_SLOTS).
+    pub fn prune_threshold(&self) -> u64 {
+        if self.prune_after_slots == 0 {
+            DEFAULT_PRUNE_AFTER_SLOTS
+        } else {
+            self.prune_after_slots.max(MIN_PRUNE_AFTER_SLOTS)
+        }
+    }
+
+    /// Куда уходит срез тающих банков (GameState::decay_bps): config.treasury
+    /// при decay_to_treasury и заданной казне, иначе incinerator.
+    pub fn decay_destination(&self) -> Pubkey {
+        if self.decay_to_treasury && self.treasury != Pubkey::default() {
+            self.treasury
+        } else {
+            INCINERATOR
+        }
+    }
+
+    /// Кому уходит остаток от деления при возврате банка (split_pot):
+    /// config.treasury, если казна задана, иначе player1.
+    pub fn split_remainder(&self) -> Remainder {
+        if self.treasury != Pubkey::default() {
+            Remainder::Treasury
+        } else {
+            Remainder::First
+        }
+    }
+
+    /// Окно апелляции на решение арбитра (0 в аккаунте =
+    /// DEFAULT_APPEAL_WINDOW_SLOTS).
+    pub fn appeal_window(&self) -> u64 {
+        if self.appeal_window_slots == 0 {
+            DEFAULT_APPEAL_WINDOW_SLOTS
+        } else {
+            self.appeal_window_slots.max(MIN_APPEAL_WINDOW_SLOTS)
+        }
+    }
+}
+
+/// Порог простоя для escheat по умолчанию: ~2 года при 400 мс на слот.
+#[constant]
+pub const DEFAULT_ESCHEAT_AFTER_SLOTS: u64 = 157_680_000;
+
+/// Нижняя граница порога escheat (~1 год); update_config ниже не опустит.
+#[constant]
+pub const MIN_ESCHEAT_AFTER_SLOTS: u64 = 78_840_000;
+
+/// Срок хранения завершённой игры по умолчанию: ~30 дней при 400 мс на слот.
+#[constant]
+pub const DEFAULT_PRUNE_AFTER_SLOTS: u64 = 6_480_000;
+
+/// Нижняя граница срока хранения (~7 дней): за это время успевают
+/// рассчитаться пари зрителей и отчитаться турниры, которым нужен аккаунт игры.
+#[constant]
+pub const MIN_PRUNE_AFTER_SLOTS: u64 = 1_512_000;
+
+/// Максимальная доля ренты вызвавшему prune_games (50%).
+#[constant]
+pub const MAX_PRUNE_REWARD_BPS: u16 = 5_000;
+
+/// Максимум игр в одном вызове prune_games.
+#[constant]
+pub const MAX_PRUNE_BATCH: u8 = 10;
+
+/// Изменения конфига для update_config: None = оставить как есть.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ConfigUpdate {
+    pub bans_enforced: Option<bool>,
+    pub max_open_games: Option<u16>,
+    pub bot_wallet: Option<Pubkey>,
+    pub bot_max_stake: Option<u64>,
+    pub paused: Option<bool>,
+    pub arbiter: Option<Pubkey>,
+    pub min_create_gap_slots: Option<u64>,
+    pub stake_pool: Option<Pubkey>,
+    pub treasury: Option<Pubkey>,
+    pub escheat_after_slots: Option<u64>,
+    pub free_moves: Option<u16>,
+    pub insurance_fee_bps: Option<u16>,
+    pub protocol_fee_bps: Option<u16>,
+    pub prune_after_slots: Option<u64>,
+    pub prune_reward_bps: Option<u16>,
+    pub require_whitelisted_arbiters: Option<bool>,
+    pub appeal_window_slots: Option<u64>,
+    pub burn_bps: Option<u16>,
+    pub decay_to_treasury: Option<bool>,
+    pub rating_enforced: Option<bool>,
+}
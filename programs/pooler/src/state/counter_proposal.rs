@@ -0,0 +1,19 @@
+//! Встречное предложение ставки от приглашённого второго игрока.
+
+use crate::*;
+
+/// Встречные условия player2 до join_game (PDA [b"counter_proposal", game]).
+///
+/// Пока предложение висит, у игры выставлен GameState::counter_pending;
+/// join_game без accept_counter идёт по исходным условиям.
+#[account]
+#[derive(InitSpace)]
+pub struct CounterProposal {
+    pub game: Pubkey,
+    /// Автор предложения (game.player2 на момент counter_offer); ему
+    /// возвращается рента при закрытии.
+    pub proposer: Pubkey,
+    pub stake_lamports: u64,
+    pub move_fee_lamports: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,69 @@
+//! Споры о результате: доказательства и заявки на победу с залогом.
+
+use crate::*;
+
+/// Длина ссылки на доказательства (CIDv0 в base58 — 46 байт).
+pub const EVIDENCE_CID_LEN: usize = 46;
+
+/// Сколько слотов с начала спора арбитр ждёт доказательств.
+#[constant]
+pub const EVIDENCE_WINDOW_SLOTS: u64 = 1_500;
+
+/// Окно апелляции на решение арбитра по умолчанию: ~1 день при 400 мс на слот.
+#[constant]
+pub const DEFAULT_APPEAL_WINDOW_SLOTS: u64 = 216_000;
+
+/// Нижняя граница окна апелляции (~1 час); update_config ниже не опустит.
+#[constant]
+pub const MIN_APPEAL_WINDOW_SLOTS: u64 = 9_000;
+
+/// Доказательства сторон в споре (PDA [b"evidence", game]).
+///
+/// Хранятся отдельно от GameState: в его резерве нет места под две ссылки.
+#[account]
+pub struct Evidence {
+    pub game: Pubkey,                        // 32
+    pub player1_cid: [u8; EVIDENCE_CID_LEN], // 46, нули = не подано
+    pub player2_cid: [u8; EVIDENCE_CID_LEN], // 46
+    pub bump: u8,                            // 1
+}
+
+impl Evidence {
+    pub const MAX_SIZE: usize = 160;
+}
+
+/// Арбитр из белого списка (PDA [b"arbiter", arbiter]). Существование
+/// аккаунта = арбитр допущен (см. config.require_whitelisted_arbiters).
+#[account]
+pub struct WhitelistedArbiter {
+    pub arbiter: Pubkey,    // 32
+    pub added_at_slot: u64, // 8
+    pub bump: u8,           // 1
+}
+
+impl WhitelistedArbiter {
+    pub const MAX_SIZE: usize = 64;
+}
+
+/// Окно для contest_claim после claim_win, в слотах.
+#[constant]
+pub const CLAIM_CONTEST_WINDOW_SLOTS: u64 = 300;
+
+/// Залог за claim_win; теряется, если арбитр решил спор против заявителя.
+#[constant]
+pub const WIN_CLAIM_BOND_LAMPORTS: u64 = 10_000_000;
+
+/// Заявка одного игрока на победу (PDA [b"win_claim", game]).
+#[account]
+pub struct WinClaim {
+    pub game: Pubkey,          // 32
+    pub claimant: Pubkey,      // 32
+    pub final_board: [i8; 24], // 24, итоговая доска по версии заявителя
+    pub deadline_slot: u64,    // 8, последний слот для contest_claim
+    pub bond_lamports: u64,    // 8
+    pub bump: u8,              // 1
+}
+
+impl WinClaim {
+    pub const MAX_SIZE: usize = 128;
+}
@@ -0,0 +1,83 @@
+//! Кольцевой буфер последних событий игры.
+
+use crate::*;
+
+/// Ёмкость кольцевого буфера событий.
+pub const EVENT_BUFFER_LEN: usize = 16;
+
+/// Тип события в EventBuffer.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub enum BufferedEventKind {
+    /// Пустая ячейка.
+    #[default]
+    None,
+    /// Вход второго игрока или бота; value = ставка.
+    Joined,
+    /// Ход; value = move_index после хода.
+    Move,
+    /// Игра завершена с победителем (или договорным разделом); value = выплата.
+    Finished,
+    /// Лобби отменено; value = возвращённая ставка.
+    Cancelled,
+    /// Возврат вкладов; value = сумма возвратов обоим игрокам.
+    Refunded,
+}
+
+/// Компактная запись события.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct BufferedEvent {
+    /// Порядковый номер (с 1); 0 — пустая ячейка.
+    pub seq: u64,
+    pub slot: u64,
+    pub kind: BufferedEventKind,
+    /// Игрок, ходивший / вошедший / победитель (default — нет).
+    pub actor: Pubkey,
+    pub value: u64,
+}
+
+/// Последние EVENT_BUFFER_LEN событий игры (PDA [b"event_buffer", game]).
+///
+/// Событие с номером seq лежит в ячейке (seq - 1) % EVENT_BUFFER_LEN;
+/// клиент запоминает последний прочитанный seq и сравнивает с next_seq.
+#[account]
+#[derive(InitSpace)]
+pub struct EventBuffer {
+    pub game: Pubkey,
+    pub payer: Pubkey,
+    /// Номер следующего события.
+    pub next_seq: u64,
+    pub events: [BufferedEvent; EVENT_BUFFER_LEN],
+    pub bump: u8,
+}
+
+impl EventBuffer {
+    /// Дописывает событие, затирая самое старое.
+    pub fn push(&mut self, slot: u64, kind: BufferedEventKind, actor: Pubkey, value: u64) {
+        let seq = self.next_seq;
+        self.events[((seq - 1) % EVENT_BUFFER_LEN as u64) as usize] = BufferedEvent {
+            seq,
+            slot,
+            kind,
+            actor,
+            value,
+        };
+        self.next_seq = seq + 1;
+    }
+}
+
+/// Запись события в буфер игры, если он передан.
+pub fn record_event(
+    buffer: Option<&mut Account<EventBuffer>>,
+    kind: BufferedEventKind,
+    actor: Pubkey,
+    value: u64,
+) -> Result<()> {
+    if let Some(buffer) = buffer {
+        buffer.push(Clock::get()?.slot, kind, actor, value);
+    }
+    Ok(())
+}
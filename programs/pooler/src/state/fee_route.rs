@@ -0,0 +1,19 @@
+//! Отвод комиссий за ход мимо банка.
+
+use crate::*;
+
+/// Получатель комиссий за ходы, заданный организатором (PDA [b"fee_route", game]).
+///
+/// Комиссии переводятся на fee_recipient напрямую и копятся в fees_routed_out;
+/// в банк и во вклады игроков они не попадают.
+#[account]
+pub struct FeeRoute {
+    pub game: Pubkey,          // 32
+    pub fee_recipient: Pubkey, // 32
+    pub fees_routed_out: u64,  // 8
+    pub bump: u8,              // 1
+}
+
+impl FeeRoute {
+    pub const MAX_SIZE: usize = 96;
+}
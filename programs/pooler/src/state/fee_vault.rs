@@ -0,0 +1,39 @@
+//! Хранилище комиссии протокола.
+
+use crate::*;
+
+/// Максимальная комиссия протокола с выигрыша (5%).
+#[constant]
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 500;
+
+/// Адрес сжигания: лампорты, зачисленные на него, уничтожаются в конце
+/// блока.
+pub const INCINERATOR: Pubkey = solana_sdk_ids::incinerator::ID;
+
+/// Хранилище комиссии протокола (PDA [b"fee_vault"]). Комиссия с выигрыша
+/// (config.protocol_fee_bps) копится здесь, а не уходит сразу на горячий
+/// кошелёк; withdraw_protocol_fees выводит её частями на config.treasury.
+/// Доля config.burn_bps сразу уходит на incinerator и учитывается в burned.
+///
+/// Инвариант для аудита: collected - withdrawn - burned == lamports - рента.
+/// Прямой перевод на PDA увеличивает баланс сверх счётчиков, но вывести
+/// можно только учтённое.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    /// Вся удержанная комиссия, включая сожжённую.
+    pub collected: u64,
+    pub withdrawn: u64,
+    /// Сожжённая часть collected (отправлена на incinerator).
+    pub burned: u64,
+    pub bump: u8,
+}
+
+impl FeeVault {
+    /// Собранная, не сожжённая и ещё не выведенная комиссия.
+    pub fn available(&self) -> u64 {
+        self.collected
+            .saturating_sub(self.withdrawn)
+            .saturating_sub(self.burned)
+    }
+}
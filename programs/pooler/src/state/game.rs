@@ -0,0 +1,961 @@
+//! GameState и всё, что описывает партию: статусы, причины завершения,
+//! параметры создания, итоги хода и сводка.
+
+use crate::*;
+
+/// Это on-chain аккаунт, который хранит состояние одной игры.
+///
+/// Политика раскладки. Пока в `reserved` есть место, новое поле
+/// добавляется перед ним и вырезается из него (reserved уменьшается на
+/// размер поля): смещения существующих полей и размер аккаунта не
+/// меняются, у старых аккаунтов поле читается как нули, и версия остаётся
+/// прежней. Если меняется смысл полей или запаса не хватает, увеличивается
+/// GAME_STATE_VERSION; во втором случае растёт и MAX_SIZE, в хвост
+/// добавляется новый запас, а аккаунты прежнего размера переносит
+/// migrate_game_state (check_legacy_game_state узнаёт версию по размеру).
+/// Без переноса остальные инструкции такой аккаунт не читают.
+///
+/// Версия 4 (PACKED_BOARD_VERSION) меняет только формат слотов доски
+/// (StoredBoard), размер прежний. Это окно миграции: аккаунты версии 3
+/// инструкции читают и пишут в старом формате, выбирая его по байту
+/// version, а migrate_game_state переупаковывает их доски на месте.
+///
+/// История размеров: исходная раскладка (версия 0) — 256 байт; версия 1 —
+/// 512; версия 2 — 576 (запас версии 1 кончился); версии 3 и 4 — 640
+/// (запас версии 2 кончился).
+#[account]
+#[derive(InitSpace)]
+pub struct GameState {
+    pub player1: Pubkey,             // 32 байта
+    pub player2: Pubkey,             // 32 байта
+    pub game_id: u64,                // 8
+    pub stake_lamports: u64,         // 8
+    pub move_fee_lamports: u64,      // 8
+    pub pot_lamports: u64,           // 8
+    pub player1_deposit: u64,        // 8
+    pub player2_deposit: u64,        // 8
+    pub player1_fees_paid: u64,      // 8
+    pub player2_fees_paid: u64,      // 8
+    pub last_activity_slot: u64,     // 8
+    pub move_index: u64,             // 8
+    pub board: [u8; 24],             // 24, доска (StoredBoard, читается через board_points)
+    pub dice: [u8; 2],               // последние выпавшие кубики
+    pub current_turn: u8,            // 1
+    pub status: GameStatus,          // ~1
+    pub winner: Pubkey,              // 32
+    pub bump: u8,                    // 1
+    pub cube_value: u8,              // 1, текущее значение куба (1, 2, 4, ..., 64)
+    pub cube_owner: u8,              // 1, 0 = куб в центре, 1/2 = владелец
+    pub double_pending: bool,        // 1, есть непринятое предложение удвоения
+    pub beavers_allowed: bool,       // 1, разрешены бобры/еноты
+    pub raccoon_pending: bool,       // 1, открыто окно для енота после бобра
+    pub jacoby: bool,                // 1, правило Джекоби
+    pub auto_doubles: bool,          // 1, автоудвоения при ничьей на стартовом броске
+    pub auto_double_limit: u8,       // 1, максимум автоудвоений (0 = без ограничения)
+    pub auto_doubles_done: u8,       // 1, сколько автоудвоений уже было
+    pub tournament: Pubkey,          // 32, турнир (Pubkey::default() = вне турнира)
+    pub tournament_round: u8,        // 1, раунд турнира
+    pub min_opponent_rating: u32,    // 4, минимальный рейтинг второго игрока (0 = без ограничения)
+    pub password_hash: [u8; 32],     // 32, sha256 пароля на вход (нули = без пароля)
+    pub join_deadline_slot: u64,     // 8, последний слот для join_game (0 = без срока)
+    pub tips_lamports: u64,          // 8, чаевые зрителей в составе pot_lamports
+    pub bot_game: bool,              // 1, player2 — бот, его средства в bot_vault
+    pub player1_session_key: Pubkey, // 32, сессионный ключ player1 для make_move
+    pub player1_session_expiry: u64, // 8, последний слот действия ключа
+    pub player2_session_key: Pubkey, // 32
+    pub player2_session_expiry: u64, // 8
+    pub version: u8,                 // 1, версия раскладки (GAME_STATE_VERSION при создании)
+    pub escrow_agent: Pubkey,        // 32, со-подписант расчёта (Pubkey::default() = нет)
+    pub strike_limit: u8,            // 1, страйков до поражения (0 = DEFAULT_STRIKE_LIMIT)
+    pub p1_timeout_strikes: u8,      // 1, просроченные ходы player1
+    pub p2_timeout_strikes: u8,      // 1, просроченные ходы player2
+    pub annotation_chain: [u8; 32],  // 32, цепочка хэшей оффчейн-аннотаций ходов
+    pub two_phase_moves: bool,       // 1, ход ждёт подтверждения соперника
+    pub move_pending: bool,          // 1, есть неподтверждённый ход
+    pub pending_board: [u8; 24],     // 24, доска неподтверждённого хода (StoredBoard)
+    pub pending_dice: [u8; 2],       // 2, кубики неподтверждённого хода
+    pub pending_deadline_slot: u64,  // 8, после этого слота ход подтверждается автоматически
+    pub p1_auto_take_up_to: u8,      // 1, порог автовзятия куба player1 (0 = выкл.)
+    pub p2_auto_take_up_to: u8,      // 1
+    pub p1_take_reserve: u64,        // 8, резерв player1 под автовзятия (вне банка)
+    pub p2_take_reserve: u64,        // 8
+    pub team_game: bool,             // 1, игра 2 на 2, составы в TeamState
+    pub handicap: i8,                // 1, фора (> 0 — у player1, < 0 — у player2)
+    pub rule_variant: RuleVariant,   // 1, вариант правил (у старых игр 0 = Standard)
+    pub preset: GamePreset,          // 1, пресет контроля времени (у старых игр 0 = Casual)
+    pub practice: bool,              // 1, тренировочная игра без переводов и рейтинга
+    pub win_claim_pending: bool,     // 1, есть заявка claim_win (PDA WinClaim)
+    pub board_from_start: bool,      // 1, доска ведётся со стартовой позиции init_game
+    pub max_moves: u16,              // 2, предел ходов (0 = DEFAULT_MAX_MOVES)
+    pub fee_routed: bool,            // 1, комиссии идут на FeeRoute
+    pub finish_reason: FinishReason, // 1, как завершилась игра (NotFinished — идёт или старая)
+    pub action_nonce: u64,           // 8, nonce подписанных оффчейн сообщений (signing)
+    pub finished_slot: u64,          // 8, слот завершения или отмены (0 — идёт или старая игра)
+    pub arbiter: Pubkey,             // 32, арбитр игры (Pubkey::default() = config.arbiter)
+    pub resolution_winner: u8, // 1, победитель по решению арбитра до execute_resolution (0 = нет)
+    pub appeal_deadline_slot: u64, // 8, последний слот для appeal по этому решению
+    pub resolution_appealed: bool, // 1, решение обжаловано: спор решает админ
+    pub counter_pending: bool, // 1, есть PDA CounterProposal (встречное предложение player2)
+    pub sides_swapped: bool,   // 1, игроки обменялись сторонами (нечётное число swap_sides)
+    pub move_history: bool,    // 1, есть PDA GameHistory (история ходов)
+    pub current_history_chunk: u8, // 1, чанк GameHistory, в который пишутся ходы
+    pub history_commitment: bool, // 1, есть PDA HistoryCommitment (Merkle-корень ходов)
+    pub move_fee_bps: u16,     // 2, комиссия за ход в б.п. от банка (0 = move_fee_lamports)
+    pub decay_bps: u16,        // 2, срез банка за ход в б.п. (0 = банк не тает)
+    pub decayed_lamports: u64, // 8, всего срезано с банка (вне игры)
+    pub unsettled_side_bets: u16, // 2, сведённые SideBet, ещё не рассчитанные settle_side_bet
+    pub own_free_moves: bool,  // 1, у игры своё бесплатное окно free_moves
+    pub free_moves: u16,       // 2, бесплатные первые ходы при own_free_moves
+    pub open_games_counted: bool, // 1, игра учтена в OpenGames создателя и ещё не снята
+    pub dice_seed: [u8; 32],   // 32, сид оффчейн-бросков кубиков (нули = без сида)
+    pub mirror_paired: bool,   // 1, игра входит в зеркальную пару (MirrorPair)
+    pub stake_pot: bool,       // 1, join_game размещает банк в пуле (options.stake_pot)
+    pub staked_lamports: u64,  // 8, банк в SPL Stake Pool (0 = не размещён или возвращён)
+    pub reserved: [u8; 4],     // 4, запас под будущие поля
+}
+
+/// Текущая версия раскладки GameState.
+#[constant]
+pub const GAME_STATE_VERSION: u8 = PACKED_BOARD_VERSION;
+
+/// GameState::MAX_SIZE для клиентов: место под данные без 8 байт
+/// дискриминатора (размер аккаунта — 8 + GAME_STATE_MAX_SIZE).
+#[constant]
+pub const GAME_STATE_MAX_SIZE: u64 = GameState::MAX_SIZE as u64;
+
+/// Число пунктов доски (длина доски ходов и board_points).
+#[constant]
+pub const BOARD_POINTS: u8 = 24;
+
+/// Максимальный срез тающего банка за ход (10%).
+#[constant]
+pub const MAX_DECAY_BPS: u16 = 1_000;
+
+// Сериализованный GameState обязан помещаться в выделенное место.
+const _: () = assert!(GameState::INIT_SPACE <= GameState::MAX_SIZE);
+
+const _: () = assert!(BOARD_POINTS as usize == core::mem::size_of::<backgammon_core::Board>());
+
+// Ассоциированная константа для расчёта размера аккаунта.
+// Мы берём с запасом.
+impl GameState {
+    pub const MAX_SIZE: usize = 640;
+
+    /// MAX_SIZE исходной раскладки (версия 0: поля player1..bump, без
+    /// version), до migrate_game_state.
+    pub const BASELINE_MAX_SIZE: usize = 256;
+
+    /// MAX_SIZE версии 1, до migrate_game_state.
+    pub const LEGACY_MAX_SIZE: usize = 512;
+
+    /// MAX_SIZE версии 2, до migrate_game_state.
+    pub const V2_MAX_SIZE: usize = 576;
+
+    /// Бесплатное окно игры: options.free_moves, заданное при создании, или
+    /// config.free_moves, если игра своего окна не задала (в том числе у
+    /// игр, созданных до появления поля).
+    pub fn free_moves(&self, config: &Config) -> u16 {
+        if self.own_free_moves {
+            self.free_moves
+        } else {
+            config.free_moves
+        }
+    }
+
+    /// Базовая комиссия хода до эскалации: move_fee_lamports или, в игре с
+    /// move_fee_bps, доля текущего банка (pot_move_fee).
+    pub fn base_move_fee(&self) -> u64 {
+        if self.move_fee_bps > 0 {
+            pot_move_fee(self.pot_lamports, self.move_fee_bps)
+        } else {
+            self.move_fee_lamports
+        }
+    }
+
+    /// Зачисляет взнос игрока (1 или 2) в банк и в его депозит.
+    pub fn add_deposit(&mut self, player_index: u8, amount: u64) -> Result<()> {
+        self.pot_lamports = self
+            .pot_lamports
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        match player_index {
+            1 => {
+                self.player1_deposit = self
+                    .player1_deposit
+                    .checked_add(amount)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+            2 => {
+                self.player2_deposit = self
+                    .player2_deposit
+                    .checked_add(amount)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+            _ => return Err(ErrorCode::InvalidCurrentTurn.into()),
+        }
+        Ok(())
+    }
+
+    /// Отмена лобби в слоте slot после возврата банка создателю: банк и
+    /// депозит обнуляются, статус — Cancelled (победителя нет).
+    pub fn cancel(&mut self, slot: u64) {
+        self.pot_lamports = 0;
+        self.player1_deposit = 0;
+        self.status = GameStatus::Cancelled;
+        self.finish_reason = FinishReason::Cancelled;
+        self.finished_slot = slot;
+    }
+
+    /// Обмен сторонами (swap_sides): меняются игроки и всё, что относится к
+    /// игроку, а не к стороне доски. Доска, current_turn, куб и фора остаются,
+    /// поэтому первым ходит и фору получает новый player1. PayoutSplits
+    /// привязаны к ключам и следуют за игроками сами, запасы времени
+    /// TimeControl до первого хода равны нулю.
+    pub fn swap_players(&mut self) {
+        std::mem::swap(&mut self.player1, &mut self.player2);
+        std::mem::swap(&mut self.player1_deposit, &mut self.player2_deposit);
+        std::mem::swap(&mut self.player1_fees_paid, &mut self.player2_fees_paid);
+        std::mem::swap(&mut self.player1_session_key, &mut self.player2_session_key);
+        std::mem::swap(
+            &mut self.player1_session_expiry,
+            &mut self.player2_session_expiry,
+        );
+        std::mem::swap(&mut self.p1_timeout_strikes, &mut self.p2_timeout_strikes);
+        std::mem::swap(&mut self.p1_auto_take_up_to, &mut self.p2_auto_take_up_to);
+        std::mem::swap(&mut self.p1_take_reserve, &mut self.p2_take_reserve);
+        self.sides_swapped = !self.sides_swapped;
+    }
+
+    /// Можно ли закрыть аккаунт игры через prune_games в слоте slot: игра
+    /// завершена или отменена, банк пуст, заявки claim_win, встречного
+    /// предложения (CounterProposal), истории ходов (GameHistory) и её
+    /// коммитмента (HistoryCommitment) нет и с завершения прошло не меньше
+    /// retention слотов. У игр, завершённых до появления finished_slot, срок
+    /// отсчитывается от last_activity_slot.
+    /// Командные игры не закрываются: доли напарников лежат в TeamState и
+    /// могут быть не забраны. Не закрываются и игры с резервом автовзятий
+    /// (его забирает владелец через set_auto_take) или с нерассчитанными
+    /// пари зрителей: settle_side_bet читает победителя из GameState.
+    pub fn prunable_at(&self, slot: u64, retention: u64) -> bool {
+        let finished = self.finished_slot.max(self.last_activity_slot);
+        self.status.is_over()
+            && self.pot_lamports == 0
+            && self.p1_take_reserve == 0
+            && self.p2_take_reserve == 0
+            && self.unsettled_side_bets == 0
+            && !self.win_claim_pending
+            && !self.counter_pending
+            && !self.move_history
+            && !self.history_commitment
+            && !self.team_game
+            && slot.saturating_sub(finished) >= retention
+    }
+
+    /// Переводит игру в Finished в слоте slot и записывает причину.
+    /// Победителя (если он есть) и расчёт банка задаёт вызывающая инструкция.
+    pub fn finish(&mut self, reason: FinishReason, slot: u64) {
+        self.status = GameStatus::Finished;
+        self.finish_reason = reason;
+        self.finished_slot = slot;
+    }
+
+    /// Проверяет подпись эскроу-агента, если он задан для игры.
+    ///
+    /// Агент только одобряет расчёт, который подписали сами игроки, и не может
+    /// направить средства куда-либо ещё. force_refund его подписи не требует.
+    pub fn check_escrow_agent(&self, escrow_agent: Option<&Signer>) -> Result<()> {
+        if self.escrow_agent != Pubkey::default() {
+            let agent = escrow_agent.ok_or(ErrorCode::MissingEscrowAgent)?;
+            require_keys_eq!(
+                agent.key(),
+                self.escrow_agent,
+                ErrorCode::InvalidEscrowAgent
+            );
+        }
+        Ok(())
+    }
+
+    /// Арбитр, который решает спор по игре: назначенный в init_game или,
+    /// если он не задан, config.arbiter.
+    pub fn arbiter_key(&self, config: &Config) -> Pubkey {
+        if self.arbiter == Pubkey::default() {
+            config.arbiter
+        } else {
+            self.arbiter
+        }
+    }
+
+    /// Решение арбитра записано и ждёт апелляции или execute_resolution;
+    /// банк в это время заблокирован.
+    pub fn resolution_pending(&self) -> bool {
+        self.resolution_winner != 0
+    }
+
+    /// Резерв под автовзятия до куба up_to: stake * (up_to - 1), 0 если выключено.
+    pub fn auto_take_reserve_needed(&self, up_to: u8) -> Result<u64> {
+        if up_to == 0 {
+            return Ok(0);
+        }
+        require!(
+            (2..=MAX_CUBE_VALUE).contains(&up_to) && up_to.is_power_of_two(),
+            ErrorCode::InvalidAutoTakeThreshold
+        );
+        self.stake_lamports
+            .checked_mul(up_to as u64 - 1)
+            .ok_or(ErrorCode::MathOverflow.into())
+    }
+
+    /// Официальная доска, в каком бы формате (по version) она ни хранилась.
+    pub fn board_points(&self) -> Result<[i8; 24]> {
+        load_board(&self.board, self.version)
+    }
+
+    /// Записывает официальную доску в формате версии аккаунта.
+    pub fn set_board_points(&mut self, board_points: &[i8; 24]) -> Result<()> {
+        self.board = store_board(board_points, self.version)?;
+        Ok(())
+    }
+
+    /// Доска неподтверждённого хода двухфазного режима.
+    pub fn pending_board_points(&self) -> Result<[i8; 24]> {
+        load_board(&self.pending_board, self.version)
+    }
+
+    pub fn set_pending_board_points(&mut self, board_points: &[i8; 24]) -> Result<()> {
+        self.pending_board = store_board(board_points, self.version)?;
+        Ok(())
+    }
+
+    /// Делает ход официальным: доска, кубики, счётчик ходов, очередь и время активности.
+    pub fn apply_move(&mut self, board_points: [i8; 24], dice: [u8; 2], slot: u64) -> Result<()> {
+        self.set_board_points(&board_points)?;
+        self.dice = dice;
+        self.move_index = self
+            .move_index
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.current_turn = if self.current_turn == 1 { 2 } else { 1 };
+        self.last_activity_slot = slot;
+        Ok(())
+    }
+
+    /// Параметры контроля времени игры: для Casual — глобальные константы,
+    /// для остальных пресетов — из TimeControl.
+    pub fn time_control(&self, tc: Option<&TimeControl>) -> Result<TimeControlParams> {
+        match self.preset {
+            GamePreset::Casual => Ok(CASUAL_TIME_CONTROL),
+            _ => Ok(tc.ok_or(ErrorCode::MissingTimeControl)?.params),
+        }
+    }
+
+    /// Срок хода игрока (1 или 2) в слотах: move_timeout_slots плюс его запас.
+    pub fn move_deadline_slots(&self, player_index: u8, tc: Option<&TimeControl>) -> Result<u64> {
+        let params = self.time_control(tc)?;
+        let bank = match tc {
+            Some(tc) if self.preset != GamePreset::Casual => {
+                if player_index == 1 {
+                    tc.p1_bank_slots
+                } else {
+                    tc.p2_bank_slots
+                }
+            }
+            _ => 0,
+        };
+        params
+            .move_timeout_slots
+            .checked_add(bank)
+            .ok_or(ErrorCode::MathOverflow.into())
+    }
+
+    /// Применяет неподтверждённый ход двухфазного режима.
+    pub fn finalize_pending_move(&mut self, slot: u64) -> Result<()> {
+        self.apply_move(self.pending_board_points()?, self.pending_dice, slot)?;
+        self.move_pending = false;
+        self.pending_deadline_slot = 0;
+
+        verbose_msg!(
+            "finalize_pending_move: new_move_index={}, new_current_turn={}",
+            self.move_index,
+            self.current_turn
+        );
+
+        Ok(())
+    }
+
+    /// Текущий ключ игрока по индексу (1 или 2).
+    pub fn player_key(&self, player_index: u8) -> Pubkey {
+        if player_index == 1 {
+            self.player1
+        } else {
+            self.player2
+        }
+    }
+
+    /// Переводит индекс игрока (1 или 2) между нумерацией init_game и
+    /// текущей: после swap_sides они различаются. Перевод в обе стороны
+    /// одинаков, так что функция годится и туда, и обратно.
+    pub fn creation_index(&self, player_index: u8) -> u8 {
+        if self.sides_swapped {
+            3 - player_index
+        } else {
+            player_index
+        }
+    }
+
+    /// Действует ли сессионный ключ key игрока player_index (1 или 2) в слоте slot.
+    pub fn session_key_valid(&self, player_index: u8, key: &Pubkey, slot: u64) -> bool {
+        let (session_key, expiry) = match player_index {
+            1 => (self.player1_session_key, self.player1_session_expiry),
+            2 => (self.player2_session_key, self.player2_session_expiry),
+            _ => return false,
+        };
+        session_key != Pubkey::default() && session_key == *key && slot <= expiry
+    }
+
+    /// Кем подписан ход стороны current_turn: возвращает участника TeamState
+    /// (0..=3), в чей вклад идёт комиссия. Основной ключ стороны и её
+    /// действующий сессионный ключ — основной игрок, ключ напарника из team —
+    /// напарник; любой другой ключ — NotPlayersTurn.
+    pub fn mover_member(
+        &self,
+        mover: &Pubkey,
+        team: Option<&TeamState>,
+        slot: u64,
+    ) -> Result<usize> {
+        let side = self.current_turn;
+        require!(side == 1 || side == 2, ErrorCode::InvalidCurrentTurn);
+        let main_member = ((side - 1) * 2) as usize;
+        if *mover == self.player_key(side) || self.session_key_valid(side, mover, slot) {
+            return Ok(main_member);
+        }
+        if let Some(team) = team.filter(|_| self.team_game) {
+            if team.has_teammate(side) && *mover == team.teammates[(side - 1) as usize] {
+                return Ok(main_member + 1);
+            }
+        }
+        err!(ErrorCode::NotPlayersTurn)
+    }
+
+    /// Суммы возврата по вкладам: депозит + уплаченные комиссии + половина
+    /// чаевых за вычетом доли decayed_lamports. Срезанное с тающего банка
+    /// делится пропорционально этим вкладам (split_pot, остаток — player1),
+    /// нечётный лампорт чаевых уходит по remainder (см. split_tips).
+    /// Возвращает (player1, player2, казна); их сумма равна банку.
+    pub fn refund_totals(&self, remainder: Remainder) -> Result<(u64, u64, u64)> {
+        let (tips_p1, tips_p2, tips_treasury) = self.split_tips(remainder);
+        let total_p1 = self
+            .player1_deposit
+            .checked_add(self.player1_fees_paid)
+            .and_then(|v| v.checked_add(tips_p1))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let total_p2 = self
+            .player2_deposit
+            .checked_add(self.player2_fees_paid)
+            .and_then(|v| v.checked_add(tips_p2))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let ([decayed_p1, decayed_p2], _) = split_pot(
+            self.decayed_lamports,
+            [total_p1, total_p2],
+            Remainder::First,
+        );
+        Ok((
+            total_p1
+                .checked_sub(decayed_p1)
+                .ok_or(ErrorCode::MathOverflow)?,
+            total_p2
+                .checked_sub(decayed_p2)
+                .ok_or(ErrorCode::MathOverflow)?,
+            tips_treasury,
+        ))
+    }
+
+    /// Сводка для get_game_summary на слоте current_slot.
+    pub fn summary(&self, current_slot: u64, refund_timeout_slots: u64) -> GameSummary {
+        let idle = current_slot.saturating_sub(self.last_activity_slot);
+        GameSummary {
+            status: self.status,
+            current_turn: self.current_turn,
+            move_index: self.move_index,
+            pot_lamports: self.pot_lamports,
+            player1_deposit: self.player1_deposit,
+            player2_deposit: self.player2_deposit,
+            player1_fees_paid: self.player1_fees_paid,
+            player2_fees_paid: self.player2_fees_paid,
+            slots_since_activity: idle,
+            force_refund_available: matches!(
+                self.status,
+                GameStatus::Active | GameStatus::Disputed
+            ) && !self.resolution_pending()
+                && idle >= refund_timeout_slots,
+        }
+    }
+
+    /// Общие проверки перед ходом (make_move, relayed_move и validate_move):
+    /// пауза, статус, очередь, структура доски и незакрытые предложения.
+    pub fn check_can_move(&self, paused: bool, board_points: &[i8; 24]) -> Result<()> {
+        require!(!paused, ErrorCode::ProgramPaused);
+        require!(self.status == GameStatus::Active, ErrorCode::GameNotActive);
+        require!(
+            self.current_turn == 1 || self.current_turn == 2,
+            ErrorCode::InvalidCurrentTurn
+        );
+        require!(
+            self.rule_variant.board_fits(board_points),
+            ErrorCode::InvalidBoard
+        );
+
+        // Пока предложенное удвоение не принято и не отклонено, ходить нельзя.
+        require!(!self.double_pending, ErrorCode::DoublePending);
+        require!(!self.move_pending, ErrorCode::MovePending);
+        require!(!self.win_claim_pending, ErrorCode::WinClaimPending);
+        require!(self.move_index < self.move_cap(), ErrorCode::MoveCapReached);
+        Ok(())
+    }
+
+    /// Вердикт validate_move: те же проверки, что у make_move
+    /// (check_can_move), плюс совпадение expected_move_index.
+    pub fn validate_move(
+        &self,
+        paused: bool,
+        board_points: &[i8; 24],
+        expected_move_index: u64,
+    ) -> MoveVerdict {
+        let check = self.check_can_move(paused, board_points).and_then(|()| {
+            require!(
+                expected_move_index == self.move_index,
+                ErrorCode::MoveIndexMismatch
+            );
+            Ok(())
+        });
+        MoveVerdict::from(check)
+    }
+
+    /// Учитывает возврат банка из пула: доход (returned > principal) входит
+    /// в банк и прибавляется к депозитам пропорционально вкладам сторон
+    /// (депозит + комиссии), поэтому возврат делит его так же, как банк;
+    /// чаевыми он становится, только если вкладов нет. Недостача (не больше
+    /// банка) списывается сначала с депозитов, затем с уплаченных комиссий —
+    /// пропорционально внутри каждой группы, — и лишь затем с чаевых, поэтому
+    /// даже потеря сверх депозитов не оставляет claim_pot_unstake без выхода.
+    pub fn apply_stake_result(&mut self, principal: u64, returned: u64) -> Result<()> {
+        if returned >= principal {
+            let gain = returned - principal;
+            self.pot_lamports = self
+                .pot_lamports
+                .checked_add(gain)
+                .ok_or(ErrorCode::MathOverflow)?;
+            let weights = [
+                self.player1_deposit.saturating_add(self.player1_fees_paid),
+                self.player2_deposit.saturating_add(self.player2_fees_paid),
+            ];
+            if weights == [0, 0] {
+                self.tips_lamports = self
+                    .tips_lamports
+                    .checked_add(gain)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            } else {
+                let ([to_p1, to_p2], _) = split_pot(gain, weights, Remainder::First);
+                self.player1_deposit = self
+                    .player1_deposit
+                    .checked_add(to_p1)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                self.player2_deposit = self
+                    .player2_deposit
+                    .checked_add(to_p2)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+        } else {
+            let loss = (principal - returned).min(self.pot_lamports);
+            self.pot_lamports -= loss;
+            let rest = take_loss(loss, [&mut self.player1_deposit, &mut self.player2_deposit]);
+            let rest = take_loss(
+                rest,
+                [&mut self.player1_fees_paid, &mut self.player2_fees_paid],
+            );
+            self.tips_lamports = self.tips_lamports.saturating_sub(rest);
+        }
+        Ok(())
+    }
+
+    /// Предел числа ходов (0 в аккаунте = DEFAULT_MAX_MOVES).
+    pub fn move_cap(&self) -> u64 {
+        if self.max_moves == 0 {
+            DEFAULT_MAX_MOVES as u64
+        } else {
+            self.max_moves as u64
+        }
+    }
+
+    /// Делит чаевые пополам для возврата: (player1, player2, казна), лишний
+    /// лампорт — по remainder (player1 или казне).
+    pub fn split_tips(&self, remainder: Remainder) -> (u64, u64, u64) {
+        let ([to_p1, to_p2], to_treasury) = split_pot(self.tips_lamports, [1, 1], remainder);
+        (to_p1, to_p2, to_treasury)
+    }
+
+    /// Множитель результата с учётом правила Джекоби: пока куб не повёрнут,
+    /// марс и кокс считаются обычной победой.
+    pub fn effective_multiplier(&self, result_multiplier: u8) -> u8 {
+        if self.jacoby && self.cube_value == 1 {
+            1
+        } else {
+            result_multiplier
+        }
+    }
+}
+
+/// Списывает с пары сумм не больше их итога, пропорционально (split_pot);
+/// возвращает несписанный остаток.
+fn take_loss(loss: u64, parts: [&mut u64; 2]) -> u64 {
+    let taken = loss.min(parts[0].saturating_add(*parts[1]));
+    let (shares, _) = split_pot(taken, [*parts[0], *parts[1]], Remainder::First);
+    for (part, share) in parts.into_iter().zip(shares) {
+        *part -= share;
+    }
+    loss - taken
+}
+
+/// Дополнительные правила игры, задаются один раз в init_game.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct GameOptions {
+    /// Разрешены ли бобры и еноты (beaver/raccoon) при удвоении.
+    pub beavers_allowed: bool,
+    /// Правило Джекоби: марс/кокс не считаются, пока куб не повёрнут.
+    pub jacoby: bool,
+    /// Автоудвоения: при ничьей на стартовом броске куб поворачивается на 2.
+    pub auto_doubles: bool,
+    /// Максимум автоудвоений за игру (0 = без ограничения, до MAX_CUBE_VALUE).
+    pub auto_double_limit: u8,
+    /// Турнир, к которому относится игра (Pubkey::default() = вне турнира).
+    pub tournament: Pubkey,
+    /// Раунд турнира, в котором играется игра.
+    pub tournament_round: u8,
+    /// Минимальный рейтинг второго игрока (0 = без ограничения).
+    pub min_opponent_rating: u32,
+    /// sha256 пароля на вход в игру (нули = без пароля).
+    pub password_hash: [u8; 32],
+    /// Последний слот, в котором второй игрок может войти (0 = без срока).
+    pub join_deadline_slot: u64,
+    /// Эскроу-агент, со-подписывающий расчёт (Pubkey::default() = без агента).
+    pub escrow_agent: Pubkey,
+    /// Арбитр спора по игре (Pubkey::default() = config.arbiter). При
+    /// config.require_whitelisted_arbiters должен быть в белом списке.
+    pub arbiter: Pubkey,
+    /// Сколько страйков за просроченный ход приводят к поражению (0 = DEFAULT_STRIKE_LIMIT).
+    pub strike_limit: u8,
+    /// Двухфазные ходы: ход становится официальным после ack_move соперника.
+    pub two_phase_moves: bool,
+    /// Порог автоматического взятия куба для player1 (0 = выключено), см. set_auto_take.
+    pub auto_take_up_to: u8,
+    /// Фора: > 0 — её несёт player1, < 0 — player2 (см. start_board).
+    pub handicap: i8,
+    /// Стартовая доска; нули = без чекпоинта, иначе ровно start_board(rule_variant, handicap).
+    pub initial_board: [i8; 24],
+    /// Вариант правил (стартовая позиция и число шашек).
+    pub rule_variant: RuleVariant,
+    /// Пресет контроля времени и комиссий.
+    pub preset: GamePreset,
+    /// Явные параметры: для Custom обязательны, для остальных пресетов нули
+    /// или значения, совпадающие с пресетом.
+    pub time_control: TimeControlParams,
+    /// Тренировочная игра: ставка и комиссия нулевые, переводов нет, рейтинг не меняется.
+    pub practice: bool,
+    /// Предел числа ходов, после которого доступен settle_by_move_cap (0 = DEFAULT_MAX_MOVES).
+    pub max_moves: u16,
+    /// Кошелёк организатора для комиссий за ходы (Pubkey::default() = комиссии в банк).
+    pub fee_recipient: Pubkey,
+    /// Открытое лобби: второй игрок не задан (Pubkey::default()), игру может
+    /// занять бот через bot_join. Без флага player2 обязателен.
+    pub open_lobby: bool,
+    /// Вести историю ходов: init_game создаёт PDA GameHistory.
+    pub move_history: bool,
+    /// Вести Merkle-коммитмент ходов: init_game создаёт PDA HistoryCommitment.
+    pub history_commitment: bool,
+    /// Комиссия за ход в б.п. от банка на момент хода (0 = фиксированная
+    /// move_fee_lamports). Взаимоисключается с move_fee_lamports, см. pot_move_fee.
+    pub move_fee_bps: u16,
+    /// Тающий банк: каждый ход срезает эту долю банка в б.п. на incinerator
+    /// или config.treasury (см. decay_destination) вместо комиссии с ходящего.
+    /// Несовместим с move_fee_lamports и move_fee_bps.
+    pub decay_bps: u16,
+    /// Свои бесплатные первые ходы игры вместо config.free_moves (None =
+    /// окно из конфига на момент хода, Some(0) = бесплатных ходов нет).
+    pub free_moves: Option<u16>,
+    /// Сид, из которого клиенты выводят броски кубиков (нули = без сида).
+    /// Зеркальную пару (init_mirror_pair) составляют только игры с общим
+    /// ненулевым сидом.
+    pub dice_seed: [u8; 32],
+    /// join_game размещает весь банк в config.stake_pool (PDA PotStake);
+    /// выплаты ждут возврата через request_pot_unstake и claim_pot_unstake.
+    /// Несовместимо с тающим банком и тренировочной игрой.
+    pub stake_pot: bool,
+}
+
+/// Проверка параметров init_game, не зависящих от аккаунтов. Каждое правило
+/// отклоняется своей ошибкой, чтобы клиент мог показать, что исправить.
+pub fn check_init_params(
+    game_id: u64,
+    stake_lamports: u64,
+    move_fee_lamports: u64,
+    player2: &Pubkey,
+    options: &GameOptions,
+) -> Result<()> {
+    require!(game_id != 0, ErrorCode::InvalidGameId);
+    require!(stake_lamports > 0 || options.practice, ErrorCode::ZeroStake);
+    require!(
+        move_fee_lamports <= stake_lamports,
+        ErrorCode::MoveFeeExceedsStake
+    );
+    require!(
+        move_fee_lamports == 0 || options.move_fee_bps == 0,
+        ErrorCode::ConflictingMoveFee
+    );
+    require!(
+        options.move_fee_bps as u64 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidMoveFeeBps
+    );
+    require!(
+        options.decay_bps == 0 || (move_fee_lamports == 0 && options.move_fee_bps == 0),
+        ErrorCode::ConflictingMoveFee
+    );
+    require!(
+        options.decay_bps <= MAX_DECAY_BPS,
+        ErrorCode::InvalidDecayBps
+    );
+    require!(
+        *player2 != Pubkey::default() || options.open_lobby,
+        ErrorCode::MissingPlayer2
+    );
+    require!(
+        options.rule_variant.board_fits(&options.initial_board),
+        ErrorCode::InvalidInitialBoard
+    );
+
+    // Стартовая доска должна совпадать с расстановкой варианта и таблицей
+    // фор, чтобы второй игрок мог доверять параметрам. Нулевая доска (без
+    // чекпоинта, как раньше) допустима только для обычной игры без форы.
+    if options.initial_board == [0; 24] {
+        require!(
+            options.handicap == 0 && options.rule_variant == RuleVariant::Standard,
+            ErrorCode::InitialBoardMismatch
+        );
+    } else {
+        require!(
+            options.initial_board == start_board(options.rule_variant, options.handicap)?,
+            ErrorCode::InitialBoardMismatch
+        );
+    }
+
+    require!(
+        !options.practice
+            || (stake_lamports == 0
+                && move_fee_lamports == 0
+                && options.move_fee_bps == 0
+                && options.decay_bps == 0
+                && options.auto_take_up_to == 0),
+        ErrorCode::InvalidPracticeGame
+    );
+    require!(
+        !options.stake_pot || !options.practice,
+        ErrorCode::NotAllowedInPracticeGame
+    );
+    // Срез тающего банка списывается с аккаунта игры на каждом ходе.
+    require!(
+        !options.stake_pot || options.decay_bps == 0,
+        ErrorCode::DecayingPotNotStakeable
+    );
+    Ok(())
+}
+
+/// Итог make_move, возвращаемый через return data (set_return_data).
+///
+/// Доступен и при симуляции, и в подтверждённой транзакции без повторного
+/// чтения GameState.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveOutcome {
+    /// move_index после хода (в двухфазном режиме — до подтверждения).
+    pub move_index: u64,
+    /// Чья очередь после хода (1 или 2).
+    pub current_turn: u8,
+    /// Ход ещё не передан сопернику (ждёт ack_move).
+    pub turn_open: bool,
+    /// Неиспользованные кубики; ход делается целиком, поэтому нули.
+    pub remaining_dice: [u8; 2],
+    /// board_hash доски, записанной этим ходом.
+    pub board_hash: [u8; 32],
+}
+
+/// Вердикт validate_move, возвращаемый через return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveVerdict {
+    /// make_move принял бы ход.
+    pub accepted: bool,
+    /// Номер ошибки Anchor, с которой make_move отклонил бы ход (6000+ для
+    /// ErrorCode программы); 0, если ход принят.
+    pub reason: u32,
+}
+
+impl From<Result<()>> for MoveVerdict {
+    fn from(check: Result<()>) -> Self {
+        let reason = match check {
+            Ok(()) => 0,
+            Err(Error::AnchorError(err)) => err.error_code_number,
+            Err(Error::ProgramError(err)) => match err.program_error {
+                ProgramError::Custom(code) => code,
+                _ => u32::MAX,
+            },
+        };
+        MoveVerdict {
+            accepted: reason == 0,
+            reason,
+        }
+    }
+}
+
+/// Сводка игры, возвращаемая get_game_summary через return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameSummary {
+    pub status: GameStatus,
+    /// Чья очередь (1 или 2).
+    pub current_turn: u8,
+    pub move_index: u64,
+    pub pot_lamports: u64,
+    pub player1_deposit: u64,
+    pub player2_deposit: u64,
+    pub player1_fees_paid: u64,
+    pub player2_fees_paid: u64,
+    /// Слотов с last_activity_slot.
+    pub slots_since_activity: u64,
+    /// force_refund сейчас пройдёт проверки статуса и тайм-аута.
+    pub force_refund_available: bool,
+}
+
+/// Максимальная длина пароля на вход в приватную игру.
+#[constant]
+pub const MAX_PASSWORD_LEN: u8 = 64;
+
+/// Максимальное значение куба удвоения.
+#[constant]
+pub const MAX_CUBE_VALUE: u8 = 64;
+
+/// Срок на один ход (или ответ на удвоение) в слотах для пресета Casual; после
+/// него соперник может записать страйк через record_strike. Меньше
+/// FORCE_REFUND_TIMEOUT_SLOTS.
+#[constant]
+pub const MOVE_TIMEOUT_SLOTS: u64 = 150;
+
+/// Число страйков до поражения по умолчанию.
+#[constant]
+pub const DEFAULT_STRIKE_LIMIT: u8 = 3;
+
+/// Предел числа ходов по умолчанию (GameOptions.max_moves = 0).
+#[constant]
+pub const DEFAULT_MAX_MOVES: u16 = 500;
+
+/// Тайм-аут в слотах для аварийного возврата средств (пресет Casual).
+/// Для демо на localnet держим маленьким (например, 5 слотов).
+#[constant]
+pub const FORCE_REFUND_TIMEOUT_SLOTS: u64 = 600;
+
+/// Enum тоже хранится on-chain, поэтому нужен Serialize/Deserialize.
+/// Для логирования через `{:?}` добавляем также Debug.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum GameStatus {
+    WaitingForPlayer2,
+    Active,
+    Finished,
+    /// Соперник отклонил ход (reject_move); выход — arbiter_resolve или возвраты.
+    Disputed,
+    /// Лобби отменено до входа второго игрока (cancel_before_join); победителя
+    /// нет. Старые отменённые игры остаются Finished с winner = Pubkey::default().
+    Cancelled,
+}
+
+impl GameStatus {
+    /// Игра закончена: сыграна (Finished) или отменена (Cancelled).
+    pub fn is_over(self) -> bool {
+        matches!(self, GameStatus::Finished | GameStatus::Cancelled)
+    }
+}
+
+/// Как завершилась игра: хранится в GameState::finish_reason и попадает в
+/// события GameFinished и GameRefunded.
+///
+/// Индексы вариантов уже записаны в прошлых событиях GameFinished, поэтому
+/// новые варианты (и NotFinished) добавляются только в конец, а нулевой байт
+/// означает Win: init_game и migrate_game_state записывают NotFinished явно.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub enum FinishReason {
+    /// finish_game, подписанный обоими игроками.
+    Win,
+    /// finalize_claim без оспаривания.
+    Claim,
+    /// arbiter_resolve.
+    Arbiter,
+    /// finalize_from_board по доске.
+    Board,
+    /// Поражение по страйкам (record_strike).
+    Strikes,
+    /// Отказ от удвоения (drop_double).
+    DoubleDropped,
+    /// Договорной раздел (settle_game), winner не задан.
+    Negotiated,
+    /// Игра не завершена (или завершена до появления finish_reason).
+    #[default]
+    NotFinished,
+    /// Возврат вкладов по тайм-ауту (force_refund).
+    TimeoutRefund,
+    /// Возврат вкладов по просьбе игрока (manual_refund).
+    ManualRefund,
+    /// Возврат по пределу ходов (settle_by_move_cap).
+    MoveCapRefund,
+    /// Лобби отменено до входа второго игрока (cancel_before_join).
+    Cancelled,
+}
+
+impl FinishReason {
+    /// Доля изменения рейтинга в процентах; 0 — игра в рейтинг не идёт.
+    ///
+    /// Победа на доске или по решению арбитра учитывается полностью, поражение
+    /// по страйкам — наполовину: тайм-аут говорит об игре меньше, чем партия.
+    /// Возвраты, отмена и договорной раздел победителя не определяют.
+    pub fn rating_weight_pct(self) -> u32 {
+        match self {
+            FinishReason::Win
+            | FinishReason::Claim
+            | FinishReason::Arbiter
+            | FinishReason::Board
+            | FinishReason::DoubleDropped => 100,
+            FinishReason::Strikes => 50,
+            FinishReason::NotFinished
+            | FinishReason::Negotiated
+            | FinishReason::TimeoutRefund
+            | FinishReason::ManualRefund
+            | FinishReason::MoveCapRefund
+            | FinishReason::Cancelled => 0,
+        }
+    }
+}
+
+impl From<RefundReason> for FinishReason {
+    fn from(reason: RefundReason) -> Self {
+        match reason {
+            RefundReason::Timeout => FinishReason::TimeoutRefund,
+            RefundReason::Manual => FinishReason::ManualRefund,
+            RefundReason::MoveCap => FinishReason::MoveCapRefund,
+        }
+    }
+}
+
+/// Почему игроки получили вклады обратно.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RefundReason {
+    /// force_refund по тайм-ауту.
+    Timeout,
+    /// manual_refund.
+    Manual,
+    /// settle_by_move_cap.
+    MoveCap,
+}
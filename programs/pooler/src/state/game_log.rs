@@ -0,0 +1,78 @@
+//! Запись партии для проверки ходов на цепочке (start_game_log /
+//! submit_game_log).
+
+use crate::*;
+
+/// Позиция при переигрывании лога: доска с точки зрения player1 плюс шашки
+/// на баре и снятые, которых доска сама не различает. Хранимая копия
+/// backgammon_core::Position.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct ReplayPosition {
+    pub board: [i8; 24],
+    pub bar: [u8; 2],
+    pub off: [u8; 2],
+}
+
+impl From<backgammon_core::Position> for ReplayPosition {
+    fn from(p: backgammon_core::Position) -> Self {
+        Self {
+            board: p.board,
+            bar: p.bar,
+            off: p.off,
+        }
+    }
+}
+
+impl From<ReplayPosition> for backgammon_core::Position {
+    fn from(p: ReplayPosition) -> Self {
+        Self {
+            board: p.board,
+            bar: p.bar,
+            off: p.off,
+        }
+    }
+}
+
+impl ReplayPosition {
+    /// Стартовая позиция: шашки форы стоят на баре несущего её игрока.
+    pub fn start(variant: RuleVariant, handicap: i8) -> Result<Self> {
+        backgammon_core::Position::start(variant.core(), handicap)
+            .map(Into::into)
+            .map_err(board_error)
+    }
+
+    /// Законный ход стороны side с кубиками dice, приводящий к доске target
+    /// (см. backgammon_core::apply_move).
+    pub fn play_move(&self, side: u8, dice: [u8; 2], target: &[i8; 24]) -> Option<Self> {
+        backgammon_core::apply_move(&(*self).into(), side, dice, target).map(Into::into)
+    }
+}
+
+/// Ход в логе для submit_game_log: доска после хода и его кубики.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoggedMove {
+    pub board_points: [i8; 24],
+    pub dice: [u8; 2],
+}
+
+/// Проверка лога партии (PDA [b"game_log", game, submitter]).
+#[account]
+#[derive(InitSpace)]
+pub struct GameLog {
+    pub game: Pubkey,
+    pub submitter: Pubkey,
+    /// Позиция после cursor переигранных ходов.
+    pub position: ReplayPosition,
+    /// Число переигранных ходов; при failed — индекс отвергнутого хода.
+    pub cursor: u64,
+    pub last_dice: [u8; 2],
+    /// Лог воспроизводит текущую доску игры.
+    pub verified: bool,
+    /// Лог отвергнут на ходе cursor (или итог не совпал с доской).
+    pub failed: bool,
+    pub bump: u8,
+}
+
+/// Максимум ходов в одном вызове submit_game_log.
+#[constant]
+pub const MAX_GAME_LOG_CHUNK: u8 = 32;
@@ -0,0 +1,175 @@
+//! История ходов игры для переигрывания и споров: полная (чанки PDA
+//! GameHistory) и Merkle-коммитмент (PDA HistoryCommitment).
+
+use crate::*;
+use backgammon_core::{HistoryFrontier, MoveLeaf, Sha256};
+
+/// Сколько ходов помещается в один чанк GameHistory; дальше нужен
+/// rotate_history_chunk.
+#[constant]
+pub const MAX_HISTORY_MOVES: u8 = 128;
+
+/// Глубина дерева HistoryCommitment: длина доказательства verify_history_entry.
+#[constant]
+pub const HISTORY_TREE_DEPTH: u8 = backgammon_core::HISTORY_TREE_DEPTH as u8;
+
+/// Запись одного хода в GameHistory и лист дерева HistoryCommitment.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct MoveRecord {
+    /// Номер хода с 1 (move_index после него).
+    pub move_index: u64,
+    /// Основной ключ ходившей стороны.
+    pub mover: Pubkey,
+    /// Доска после хода (в двухфазном режиме — предложенная), PackedBoard.
+    pub board: [u8; 15],
+    pub dice: [u8; 2],
+    pub slot: u64,
+    /// Комиссия, списанная за ход.
+    pub fee_lamports: u64,
+}
+
+impl MoveRecord {
+    /// Лист дерева истории (см. backgammon_core::MoveLeaf).
+    pub fn leaf(&self) -> MoveLeaf {
+        MoveLeaf {
+            move_index: self.move_index,
+            mover: self.mover.to_bytes(),
+            board: self.board,
+            dice: self.dice,
+            slot: self.slot,
+            fee_lamports: self.fee_lamports,
+        }
+    }
+
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        self.leaf().hash::<HistoryHasher>()
+    }
+}
+
+/// sha256 дерева истории: системный вызов в программе, sha2 вне её.
+pub struct HistoryHasher;
+
+impl Sha256 for HistoryHasher {
+    fn hashv(parts: &[&[u8]]) -> [u8; 32] {
+        hashv(parts).to_bytes()
+    }
+}
+
+/// Чанк истории ходов игры (PDA [b"game_history", game, [chunk_index]]).
+///
+/// Чанк 0 создаётся в init_game с options.move_history, следующие —
+/// rotate_history_chunk, когда текущий заполнен; чанки связаны через
+/// prev_chunk от последнего к первому. Пока история есть
+/// (GameState::move_history), make_move и relayed_move пишут только в чанк
+/// GameState::current_history_chunk и без него не проходят. Принадлежность
+/// игре проверяется по полям game и chunk_index.
+#[account]
+#[derive(InitSpace)]
+pub struct GameHistory {
+    pub game: Pubkey,
+    /// Плательщик ренты чанка, получает её при close_game_history.
+    pub payer: Pubkey,
+    /// Номер чанка с 0.
+    pub chunk_index: u8,
+    /// Предыдущий чанк (Pubkey::default() у чанка 0).
+    pub prev_chunk: Pubkey,
+    #[max_len(128)]
+    pub moves: Vec<MoveRecord>,
+    pub bump: u8,
+}
+
+impl GameHistory {
+    pub fn is_full(&self) -> bool {
+        self.moves.len() >= MAX_HISTORY_MOVES as usize
+    }
+
+    /// Дописывает ход; в заполненный чанк — HistoryFull.
+    pub fn append(&mut self, record: MoveRecord) -> Result<()> {
+        require!(!self.is_full(), ErrorCode::HistoryFull);
+        self.moves.push(record);
+        Ok(())
+    }
+}
+
+/// Merkle-коммитмент истории ходов (PDA [b"history_commitment", game]):
+/// дешёвая замена GameHistory — вместо записей хранится фронтир дерева
+/// (backgammon_core::HistoryFrontier) и корень после последнего хода.
+///
+/// Создаётся в init_game с options.history_commitment; пока он есть
+/// (GameState::history_commitment), make_move и relayed_move дописывают в
+/// дерево лист каждого хода и без аккаунта не проходят. Записанный ход
+/// доказывается verify_history_entry.
+#[account]
+#[derive(InitSpace)]
+pub struct HistoryCommitment {
+    pub game: Pubkey,
+    /// Плательщик ренты, получает её при close_history_commitment.
+    pub payer: Pubkey,
+    /// Число листьев (записанных ходов).
+    pub leaf_count: u64,
+    /// HistoryFrontier::branch.
+    pub frontier: [[u8; 32]; 16],
+    /// Корень дерева после последнего хода.
+    pub history_root: [u8; 32],
+    pub bump: u8,
+}
+
+const _: () = assert!(HISTORY_TREE_DEPTH as usize == 16);
+
+impl HistoryCommitment {
+    /// Дописывает лист и пересчитывает history_root.
+    pub fn append(&mut self, leaf: [u8; 32]) -> Result<()> {
+        let mut frontier = HistoryFrontier {
+            count: self.leaf_count,
+            branch: self.frontier,
+        };
+        frontier
+            .append::<HistoryHasher>(leaf)
+            .map_err(|_| ErrorCode::MathOverflow)?;
+        self.leaf_count = frontier.count;
+        self.frontier = frontier.branch;
+        self.history_root = frontier.root::<HistoryHasher>();
+        Ok(())
+    }
+}
+
+/// Запись хода в историю и коммитмент игры: вызывается до commit_move,
+/// пока current_turn и move_index относятся к ходящей стороне.
+pub fn record_move(
+    history: Option<&mut Account<GameHistory>>,
+    commitment: Option<&mut Account<HistoryCommitment>>,
+    game: &GameState,
+    board_points: &[i8; 24],
+    dice: [u8; 2],
+    fee_lamports: u64,
+) -> Result<()> {
+    require!(
+        history.is_some() || !game.move_history,
+        ErrorCode::MissingGameHistory
+    );
+    require!(
+        commitment.is_some() || !game.history_commitment,
+        ErrorCode::MissingHistoryCommitment
+    );
+    if history.is_none() && commitment.is_none() {
+        return Ok(());
+    }
+    let record = MoveRecord {
+        move_index: game
+            .move_index
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?,
+        mover: game.player_key(game.current_turn),
+        board: pack_board(board_points)?,
+        dice,
+        slot: Clock::get()?.slot,
+        fee_lamports,
+    };
+    if let Some(history) = history {
+        history.append(record)?;
+    }
+    if let Some(commitment) = commitment {
+        commitment.append(record.leaf_hash())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Страховой пул и флаги зависших игр.
+
+use crate::*;
+
+/// Максимальная доля комиссии за ход, отчисляемая в InsurancePool (10%).
+#[constant]
+pub const MAX_INSURANCE_FEE_BPS: u16 = 1_000;
+
+/// Страховой пул (PDA [b"insurance_pool"]); lamports сверх ренты — резерв
+/// для compensate.
+#[account]
+#[derive(InitSpace)]
+pub struct InsurancePool {
+    pub total_collected: u64,
+    pub total_compensated: u64,
+    pub bump: u8,
+}
+
+/// Почему integrity_check признал игру застрявшей.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum StuckReason {
+    /// Вклады игроков не сходятся с банком.
+    PotMismatch,
+    /// На аккаунте игры меньше lamports, чем банк плюс рента.
+    LamportShortfall,
+}
+
+/// Пометка застрявшей игры (PDA [b"game_flag", game]) и учёт выплат по ней.
+#[account]
+#[derive(InitSpace)]
+pub struct GameFlag {
+    pub game: Pubkey,
+    pub reason: StuckReason,
+    pub flagged_slot: u64,
+    pub compensated: [u64; 2],
+    pub bump: u8,
+}
@@ -0,0 +1,35 @@
+//! Смещения полей сериализованного `GameState` для фильтров memcmp в
+//! `getProgramAccounts`. Индексаторы должны брать их отсюда, а не считать
+//! руками; сами фильтры собирает SDK (`backgammon_client::filters`).
+//! Совпадение смещений с байтами Borsh проверяет `tests/game_layout.rs`.
+
+use super::{GameState, GameStatus, StoredBoard};
+use anchor_lang::prelude::*;
+
+const PUBKEY: usize = 32;
+const U64: usize = 8;
+const BOARD: usize = core::mem::size_of::<StoredBoard>();
+
+/// Дискриминатор аккаунта Anchor.
+pub const DISCRIMINATOR: usize = 0;
+pub const PLAYER1: usize = DISCRIMINATOR + 8;
+pub const PLAYER2: usize = PLAYER1 + PUBKEY;
+pub const GAME_ID: usize = PLAYER2 + PUBKEY;
+/// После game_id: stake, move_fee, pot, два депозита, две суммы комиссий,
+/// last_activity_slot и move_index, затем board, dice и current_turn.
+pub const STATUS: usize = GAME_ID + U64 + 9 * U64 + BOARD + 2 + 1;
+pub const WINNER: usize = STATUS + 1;
+
+/// Байт статуса в сериализованном виде (индекс варианта Borsh).
+#[constant]
+pub const STATUS_WAITING_FOR_PLAYER2: u8 = GameStatus::WaitingForPlayer2 as u8;
+#[constant]
+pub const STATUS_ACTIVE: u8 = GameStatus::Active as u8;
+#[constant]
+pub const STATUS_FINISHED: u8 = GameStatus::Finished as u8;
+#[constant]
+pub const STATUS_DISPUTED: u8 = GameStatus::Disputed as u8;
+#[constant]
+pub const STATUS_CANCELLED: u8 = GameStatus::Cancelled as u8;
+
+const _: () = assert!(WINNER + PUBKEY <= 8 + GameState::INIT_SPACE);
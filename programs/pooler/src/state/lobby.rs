@@ -0,0 +1,43 @@
+//! Шардированное лобби открытых игр.
+
+use crate::*;
+
+/// Число шардов реестра лобби.
+#[constant]
+pub const LOBBY_SHARDS: u8 = 8;
+
+/// Максимум записей в одном шарде лобби.
+#[constant]
+pub const LOBBY_SHARD_CAPACITY: u8 = 32;
+
+/// Запись об открытой игре в лобби.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct LobbyEntry {
+    pub game: Pubkey,
+    pub stake_lamports: u64,
+    pub rule_variant: RuleVariant,
+    pub creator_rating: u32,
+}
+
+/// Шард реестра открытых лобби (PDA [b"lobby", shard]).
+///
+/// Клиенты читают LOBBY_SHARDS аккаунтов вместо сканирования всех игр.
+/// Записи снимаются при join_game/bot_join (если шард передан), через
+/// unlist_game и prune_lobby.
+#[account]
+#[derive(InitSpace)]
+pub struct Lobby {
+    pub shard: u8,
+    #[max_len(32)]
+    pub entries: Vec<LobbyEntry>,
+    pub bump: u8,
+}
+
+impl Lobby {
+    /// Удаляет запись игры; false, если её не было.
+    pub fn unlist(&mut self, game: Pubkey) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.game != game);
+        self.entries.len() != before
+    }
+}
@@ -0,0 +1,50 @@
+//! Аккаунты программы, типы их полей и константы.
+
+pub mod board;
+pub mod chouette;
+pub mod config;
+pub mod counter_proposal;
+pub mod dispute;
+pub mod event_buffer;
+pub mod fee_route;
+pub mod fee_vault;
+pub mod game;
+pub mod game_log;
+pub mod history;
+pub mod insurance;
+pub mod layout;
+pub mod lobby;
+pub mod payout;
+pub mod player;
+pub mod pot_stake;
+pub mod rating;
+pub mod relay;
+pub mod side_bet;
+pub mod team;
+pub mod telemetry;
+pub mod time_control;
+pub mod tournament;
+
+pub use board::*;
+pub use chouette::*;
+pub use config::*;
+pub use counter_proposal::*;
+pub use dispute::*;
+pub use event_buffer::*;
+pub use fee_route::*;
+pub use fee_vault::*;
+pub use game::*;
+pub use game_log::*;
+pub use history::*;
+pub use insurance::*;
+pub use lobby::*;
+pub use payout::*;
+pub use player::*;
+pub use pot_stake::*;
+pub use rating::*;
+pub use relay::*;
+pub use side_bet::*;
+pub use team::*;
+pub use telemetry::*;
+pub use time_control::*;
+pub use tournament::*;
@@ -0,0 +1,45 @@
+//! Сплиты выплат игрока между несколькими получателями.
+
+use crate::*;
+
+/// Максимальное число получателей в PayoutSplits.
+#[constant]
+pub const PAYOUT_SPLITS_MAX: u8 = 4;
+
+/// Доля выплаты одному получателю.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct PayoutSplit {
+    pub recipient: Pubkey,
+    pub bps: u16,
+}
+
+/// Сплиты выплат игрока (PDA [b"payout_splits", player]).
+#[account]
+#[derive(InitSpace)]
+pub struct PayoutSplits {
+    pub player: Pubkey,
+    #[max_len(4)]
+    pub splits: Vec<PayoutSplit>,
+    pub bump: u8,
+}
+
+/// Проверка сплитов: 1..=PAYOUT_SPLITS_MAX разных получателей с ненулевыми
+/// долями, в сумме 10 000 б.п.
+pub fn validate_payout_splits(splits: &[PayoutSplit]) -> Result<()> {
+    require!(
+        !splits.is_empty() && splits.len() <= PAYOUT_SPLITS_MAX as usize,
+        ErrorCode::InvalidPayoutSplits
+    );
+    let mut total: u64 = 0;
+    for (i, split) in splits.iter().enumerate() {
+        require!(
+            split.bps > 0
+                && split.recipient != Pubkey::default()
+                && splits[..i].iter().all(|s| s.recipient != split.recipient),
+            ErrorCode::InvalidPayoutSplits
+        );
+        total += split.bps as u64;
+    }
+    require!(total == BPS_DENOMINATOR, ErrorCode::InvalidPayoutSplits);
+    Ok(())
+}
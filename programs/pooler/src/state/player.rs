@@ -0,0 +1,75 @@
+//! PDA игрока: открытые игры, кулдаун создания, бан.
+
+use crate::*;
+
+/// Счётчик открытых лобби (WaitingForPlayer2) создателя (PDA [b"open_games", player]).
+///
+/// Увеличивается в init_game и уменьшается на каждом пути выхода игры из
+/// WaitingForPlayer2 (join_game, bot_join, cancel_before_join) — только для
+/// игр с GameState::open_games_counted, см. release_open_game.
+#[account]
+pub struct OpenGames {
+    pub player: Pubkey,  // 32
+    pub open_games: u16, // 2
+    pub bump: u8,        // 1
+}
+
+impl OpenGames {
+    pub const MAX_SIZE: usize = 64;
+
+    /// Игра создателя покинула состояние WaitingForPlayer2.
+    pub fn release(&mut self) {
+        self.open_games = self.open_games.saturating_sub(1);
+    }
+}
+
+/// Слот последнего init_game кошелька (PDA [b"create_cooldown", player]).
+///
+/// Создаётся при первой игре и хранит только слот, чтобы стоить минимум ренты.
+#[account]
+pub struct CreateCooldown {
+    pub last_created_slot: u64, // 8
+}
+
+impl CreateCooldown {
+    pub const MAX_SIZE: usize = 8;
+}
+
+/// Старший бит game_id: выставлен у id, выданных GameCounter, и запрещён у
+/// явных id, поэтому одни не совпадут с другими.
+#[constant]
+pub const AUTO_GAME_ID_FLAG: u64 = 1u64 << 63;
+
+/// Счётчик игр создателя (PDA [b"counter", player]).
+///
+/// init_game с game_id = 0 берёт из него очередной id. Выданные id
+/// уникальны в пределах создателя: PDA пишется одной транзакцией за раз.
+#[account]
+#[derive(InitSpace)]
+pub struct GameCounter {
+    /// Сколько id уже выдано; следующий — AUTO_GAME_ID_FLAG | next_id.
+    pub next_id: u64,
+    pub bump: u8,
+}
+
+impl GameCounter {
+    /// Выдаёт очередной id и сдвигает счётчик.
+    pub fn assign(&mut self) -> Result<u64> {
+        require!(self.next_id < AUTO_GAME_ID_FLAG, ErrorCode::MathOverflow);
+        let id = AUTO_GAME_ID_FLAG | self.next_id;
+        self.next_id += 1;
+        Ok(id)
+    }
+}
+
+/// Бан игрока (PDA [b"ban", player]). Существование аккаунта = игрок забанен.
+#[account]
+pub struct Ban {
+    pub player: Pubkey,      // 32
+    pub banned_at_slot: u64, // 8
+    pub bump: u8,            // 1
+}
+
+impl Ban {
+    pub const MAX_SIZE: usize = 64;
+}
@@ -0,0 +1,133 @@
+//! Банк, временно размещённый в SPL stake pool.
+
+use crate::*;
+
+/// Банк игры, размещённый в SPL Stake Pool (PDA [b"pot_stake", game]).
+///
+/// pool_tokens > 0 — банк в пуле; unstake_epoch — эпоха запроса вывода
+/// (0 = не запрошен); returned — сколько lamports вернул пул.
+#[account]
+pub struct PotStake {
+    pub game: Pubkey,       // 32
+    pub stake_pool: Pubkey, // 32
+    pub funder: Pubkey,     // 32, платил ренту счёта токенов
+    pub principal: u64,     // 8
+    pub pool_tokens: u64,   // 8
+    pub unstake_epoch: u64, // 8
+    pub returned: u64,      // 8
+    pub bump: u8,           // 1
+}
+
+impl PotStake {
+    pub const MAX_SIZE: usize = 160;
+}
+
+/// Программа SPL Stake Pool.
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey =
+    pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// Инструкции SPL Stake Pool, которые вызывает программа. Данные — Borsh
+/// StakePoolInstruction пула: тег варианта (его индекс в перечислении пула)
+/// и аргумент u64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StakePoolInstruction {
+    /// Внести lamports в резерв пула, получить токены пула.
+    DepositSol(u64),
+    /// Сжечь токены пула, получить lamports из резерва.
+    WithdrawSol(u64),
+}
+
+impl StakePoolInstruction {
+    /// Индекс варианта в StakePoolInstruction пула.
+    pub fn tag(&self) -> u8 {
+        match self {
+            Self::DepositSol(_) => 14,
+            Self::WithdrawSol(_) => 16,
+        }
+    }
+}
+
+impl AnchorSerialize for StakePoolInstruction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let (Self::DepositSol(amount) | Self::WithdrawSol(amount)) = self;
+        (self.tag(), amount).serialize(writer)
+    }
+}
+
+/// Аккаунты пула, общие для DepositSol и WithdrawSol; собирает инструкции
+/// в том же порядке аккаунтов, что spl_stake_pool::instruction::{deposit_sol,
+/// withdraw_sol}.
+pub struct StakePoolAccounts {
+    pub stake_pool: Pubkey,
+    pub withdraw_authority: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub manager_fee_account: Pubkey,
+    pub pool_mint: Pubkey,
+}
+
+impl StakePoolAccounts {
+    /// DepositSol: lamports с lamports_from (подписант) в резерв, токены пула
+    /// и реферальная доля — на pool_tokens_to.
+    pub fn deposit_sol(
+        &self,
+        lamports_from: &Pubkey,
+        pool_tokens_to: &Pubkey,
+        lamports: u64,
+    ) -> Result<Instruction> {
+        self.instruction(
+            StakePoolInstruction::DepositSol(lamports),
+            vec![
+                AccountMeta::new(self.stake_pool, false),
+                AccountMeta::new_readonly(self.withdraw_authority, false),
+                AccountMeta::new(self.reserve_stake, false),
+                AccountMeta::new(*lamports_from, true),
+                AccountMeta::new(*pool_tokens_to, false),
+                AccountMeta::new(self.manager_fee_account, false),
+                AccountMeta::new(*pool_tokens_to, false),
+                AccountMeta::new(self.pool_mint, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(token::ID, false),
+            ],
+        )
+    }
+
+    /// WithdrawSol: pool_tokens токенов с pool_tokens_from (владелец —
+    /// user_transfer_authority, подписант), lamports — на lamports_to.
+    pub fn withdraw_sol(
+        &self,
+        user_transfer_authority: &Pubkey,
+        pool_tokens_from: &Pubkey,
+        lamports_to: &Pubkey,
+        pool_tokens: u64,
+    ) -> Result<Instruction> {
+        self.instruction(
+            StakePoolInstruction::WithdrawSol(pool_tokens),
+            vec![
+                AccountMeta::new(self.stake_pool, false),
+                AccountMeta::new_readonly(self.withdraw_authority, false),
+                AccountMeta::new_readonly(*user_transfer_authority, true),
+                AccountMeta::new(*pool_tokens_from, false),
+                AccountMeta::new(self.reserve_stake, false),
+                AccountMeta::new(*lamports_to, false),
+                AccountMeta::new(self.manager_fee_account, false),
+                AccountMeta::new(self.pool_mint, false),
+                AccountMeta::new_readonly(solana_sdk_ids::sysvar::clock::ID, false),
+                AccountMeta::new_readonly(solana_sdk_ids::sysvar::stake_history::ID, false),
+                AccountMeta::new_readonly(solana_sdk_ids::stake::ID, false),
+                AccountMeta::new_readonly(token::ID, false),
+            ],
+        )
+    }
+
+    fn instruction(
+        &self,
+        data: StakePoolInstruction,
+        accounts: Vec<AccountMeta>,
+    ) -> Result<Instruction> {
+        Ok(Instruction {
+            program_id: SPL_STAKE_POOL_PROGRAM_ID,
+            accounts,
+            data: data.try_to_vec()?,
+        })
+    }
+}
@@ -0,0 +1,157 @@
+//! Рейтинг Эло, статистика игроков и сезоны.
+
+use crate::*;
+
+/// Статистика и рейтинг игрока (PDA [b"stats", player]).
+#[account]
+pub struct PlayerStats {
+    pub player: Pubkey,     // 32
+    pub rating: u32,        // 4
+    pub games_played: u32,  // 4
+    pub wins: u32,          // 4
+    pub losses: u32,        // 4
+    pub season: u32,        // 4, сезон, к которому относятся season_*
+    pub season_wins: u32,   // 4
+    pub season_losses: u32, // 4
+    pub bump: u8,           // 1
+}
+
+impl PlayerStats {
+    pub const MAX_SIZE: usize = 128;
+
+    /// Переход статистики в текущий сезон: рейтинг сжимается к DEFAULT_RATING
+    /// на 50% за каждый пропущенный сезон, сезонные счётчики обнуляются.
+    pub fn roll_season(&mut self, current_season: u32) {
+        if self.season >= current_season {
+            return;
+        }
+        let elapsed = (current_season - self.season).min(32);
+        for _ in 0..elapsed {
+            self.rating = compress_rating(self.rating);
+        }
+        self.season = current_season;
+        self.season_wins = 0;
+        self.season_losses = 0;
+    }
+}
+
+/// Сезон (PDA [b"season", index]) с замораживаемой таблицей лучших игроков.
+#[account]
+pub struct Season {
+    pub index: u32,                                         // 4
+    pub start_slot: u64,                                    // 8
+    pub end_slot: u64,                                      // 8, 0 пока сезон открыт
+    pub closed: bool,                                       // 1
+    pub standings: [SeasonStanding; SEASON_STANDINGS_SIZE], // 16 * 44, по убыванию рейтинга
+    pub bump: u8,                                           // 1
+}
+
+impl Season {
+    pub const MAX_SIZE: usize = 768;
+
+    /// Обновляет строку игрока в таблице сезона и пересортировывает её.
+    /// Игрок вне таблицы попадает в неё, если есть место или его рейтинг выше последнего.
+    pub fn record_standing(&mut self, stats: &PlayerStats) {
+        let entry = SeasonStanding {
+            player: stats.player,
+            rating: stats.rating,
+            wins: stats.season_wins,
+            losses: stats.season_losses,
+        };
+        if let Some(existing) = self.standings.iter_mut().find(|s| s.player == stats.player) {
+            *existing = entry;
+        } else if let Some(free) = self
+            .standings
+            .iter_mut()
+            .find(|s| s.player == Pubkey::default())
+        {
+            *free = entry;
+        } else {
+            let last = SEASON_STANDINGS_SIZE - 1;
+            if entry.rating > self.standings[last].rating {
+                self.standings[last] = entry;
+            }
+        }
+        self.standings.sort_by(|a, b| {
+            (b.player != Pubkey::default())
+                .cmp(&(a.player != Pubkey::default()))
+                .then(b.rating.cmp(&a.rating))
+        });
+    }
+}
+
+/// Строка таблицы сезона.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SeasonStanding {
+    pub player: Pubkey, // 32
+    pub rating: u32,    // 4
+    pub wins: u32,      // 4
+    pub losses: u32,    // 4
+}
+
+/// Размер таблицы сезона.
+pub const SEASON_STANDINGS_SIZE: usize = 16;
+
+/// Стартовый рейтинг и база, к которой сжимаются рейтинги при смене сезона.
+#[constant]
+pub const DEFAULT_RATING: u32 = 1200;
+
+/// Коэффициент K для обновления рейтинга.
+#[constant]
+pub const RATING_K: u32 = 32;
+
+/// Сжатие рейтинга к DEFAULT_RATING на 50%.
+pub fn compress_rating(rating: u32) -> u32 {
+    let base = DEFAULT_RATING as i64;
+    (base + (rating as i64 - base) / 2) as u32
+}
+
+/// Изменение рейтинга победителя (и проигравшего) по линейному приближению Эло:
+/// K/2 + (R_loser - R_winner) * K / 800, в пределах [1, K - 1].
+pub fn rating_delta(winner_rating: u32, loser_rating: u32) -> u32 {
+    let k = RATING_K as i64;
+    let diff = loser_rating as i64 - winner_rating as i64;
+    (k / 2 + diff * k / 800).clamp(1, k - 1) as u32
+}
+
+/// Поправка за фору: на каждую шашку форы рейтинг несущего её игрока при
+/// расчёте изменения считается ниже на HANDICAP_RATING_STEP.
+#[constant]
+pub const HANDICAP_RATING_STEP: u32 = 100;
+
+/// Применяет результат игры к статистике обоих игроков в текущем сезоне.
+///
+/// winner_handicap — фора с точки зрения победителя: > 0 — её нёс победитель,
+/// < 0 — проигравший. weight_pct — доля изменения рейтинга
+/// (FinishReason::rating_weight_pct), но не меньше одного пункта.
+pub fn apply_rating_result(
+    winner: &mut PlayerStats,
+    loser: &mut PlayerStats,
+    current_season: u32,
+    winner_handicap: i8,
+    weight_pct: u32,
+) -> Result<()> {
+    winner.roll_season(current_season);
+    loser.roll_season(current_season);
+
+    let shift = HANDICAP_RATING_STEP * winner_handicap.unsigned_abs() as u32;
+    let (winner_rating, loser_rating) = if winner_handicap > 0 {
+        (winner.rating.saturating_sub(shift), loser.rating)
+    } else {
+        (winner.rating, loser.rating.saturating_sub(shift))
+    };
+    let delta = (rating_delta(winner_rating, loser_rating) * weight_pct / 100).max(1);
+    winner.rating = winner
+        .rating
+        .checked_add(delta)
+        .ok_or(ErrorCode::MathOverflow)?;
+    loser.rating = loser.rating.saturating_sub(delta);
+
+    winner.games_played = winner.games_played.saturating_add(1);
+    winner.wins = winner.wins.saturating_add(1);
+    winner.season_wins = winner.season_wins.saturating_add(1);
+    loser.games_played = loser.games_played.saturating_add(1);
+    loser.losses = loser.losses.saturating_add(1);
+    loser.season_losses = loser.season_losses.saturating_add(1);
+    Ok(())
+}
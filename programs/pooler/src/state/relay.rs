@@ -0,0 +1,28 @@
+//! Ретранслируемые ходы: подписываемые данные хода и бюджет ретранслятора.
+
+use crate::*;
+
+/// Ход для relayed_move. Подписывается не сам payload, а
+/// signing::SignedMessage с действием RelayedMove и sha256 его
+/// borsh-сериализации.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RelayedMovePayload {
+    pub board_points: [i8; 24],
+    pub dice: [u8; 2],
+    pub annotation_hash: [u8; 32],
+    /// move_index, при котором ход действителен.
+    pub expected_move_index: u64,
+}
+
+/// Предоплаченный бюджет комиссий игрока для relayed_move
+/// (PDA [b"relay_budget", game, player]); бюджет — lamports сверх ренты.
+#[account]
+pub struct RelayBudget {
+    pub game: Pubkey,   // 32
+    pub player: Pubkey, // 32
+    pub bump: u8,       // 1
+}
+
+impl RelayBudget {
+    pub const MAX_SIZE: usize = 80;
+}
@@ -0,0 +1,27 @@
+//! Ставки зрителей на исход игры.
+
+use crate::*;
+
+/// Зрительское пари на исход игры (PDA [b"side_bet", game, bettor, bet_id]).
+#[account]
+pub struct SideBet {
+    pub game: Pubkey,             // 32
+    pub bettor: Pubkey,           // 32
+    pub counterparty: Pubkey,     // 32, Pubkey::default() пока пари не сведено
+    pub bet_id: u64,              // 8
+    pub predicted_winner: Pubkey, // 32, исход, на который ставит bettor
+    pub predicted_player: u8, // 1, индекс игрока (1/2) по init_game, переживает rotate_player_key и swap_sides
+    pub amount: u64,          // 8, ставка каждой стороны
+    pub status: SideBetStatus, // 1
+    pub bump: u8,             // 1
+}
+
+impl SideBet {
+    pub const MAX_SIZE: usize = 192;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SideBetStatus {
+    Open,
+    Matched,
+}
@@ -0,0 +1,103 @@
+//! Командная игра (TeamState) и зеркальные пары игр (MirrorPair).
+
+use crate::*;
+
+/// Зеркальная пара игр с общим эскроу (PDA [b"mirror", game_a, game_b]).
+#[account]
+pub struct MirrorPair {
+    pub game_a: Pubkey,      // 32, player_a играет за player1
+    pub game_b: Pubkey,      // 32, player_b играет за player1
+    pub player_a: Pubkey,    // 32
+    pub player_b: Pubkey,    // 32
+    pub stake_lamports: u64, // 8, цена одной игры пары
+    pub settled: bool,       // 1
+    pub bump: u8,            // 1
+}
+
+impl MirrorPair {
+    pub const MAX_SIZE: usize = 160;
+
+    /// Проверка игр init_mirror_pair: обе активны, без ходов, ставки и бота,
+    /// ещё не в паре, цвета переставлены (в game_a player1 — player_a, в
+    /// game_b — player_b), и сид кубиков общий и ненулевой.
+    pub fn check_games(
+        game_a: &GameState,
+        game_b: &GameState,
+        player_a: &Pubkey,
+        player_b: &Pubkey,
+    ) -> Result<()> {
+        for game in [game_a, game_b] {
+            require!(game.status == GameStatus::Active, ErrorCode::GameNotActive);
+            require!(game.move_index == 0, ErrorCode::InvalidMirrorPair);
+            require!(
+                game.stake_lamports == 0 && !game.bot_game,
+                ErrorCode::InvalidMirrorPair
+            );
+            require!(!game.mirror_paired, ErrorCode::GameAlreadyPaired);
+        }
+        require!(
+            game_a.player1 == *player_a
+                && game_a.player2 == *player_b
+                && game_b.player1 == *player_b
+                && game_b.player2 == *player_a,
+            ErrorCode::InvalidMirrorPair
+        );
+        require!(
+            game_a.dice_seed != [0; 32] && game_a.dice_seed == game_b.dice_seed,
+            ErrorCode::MirrorDiceSeedMismatch
+        );
+        Ok(())
+    }
+}
+
+/// Составы консультационной игры 2 на 2 (PDA [b"team", game]).
+///
+/// Участники нумеруются так: 0 = player1, 1 = напарник player1, 2 = player2,
+/// 3 = напарник player2. Выплаты стороне с напарником копятся в received и
+/// делятся claim_team_share пропорционально contributions.
+#[account]
+pub struct TeamState {
+    pub game: Pubkey,            // 32
+    pub teammates: [Pubkey; 2],  // 64, напарники player1/player2 (default = нет)
+    pub contributions: [u64; 4], // 32, ставка и комиссии каждого участника
+    pub received: [u64; 2],      // 16, выплаты сторонам, ожидающие раздела
+    pub claimed: [bool; 4],      // 4
+    pub bump: u8,                // 1
+}
+
+impl TeamState {
+    pub const MAX_SIZE: usize = 160;
+
+    /// Остались ли невостребованные доли участников.
+    pub fn has_unclaimed_shares(&self) -> bool {
+        (0..4).any(|member| !self.claimed[member] && self.share_of(member) > 0)
+    }
+
+    /// Есть ли у стороны (1 или 2) напарник.
+    pub fn has_teammate(&self, side: u8) -> bool {
+        self.teammates[(side - 1) as usize] != Pubkey::default()
+    }
+
+    /// Зачисляет вклад участнику member (0..=3).
+    pub fn add_contribution(&mut self, member: usize, amount: u64) -> Result<()> {
+        self.contributions[member] = self.contributions[member]
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Доля участника member в выплате его стороне пропорционально вкладам;
+    /// остаток от деления — основному игроку стороны (см. split_pot).
+    pub fn share_of(&self, member: usize) -> u64 {
+        let side = member / 2;
+        let (shares, _) = split_pot(
+            self.received[side],
+            [
+                self.contributions[side * 2],
+                self.contributions[side * 2 + 1],
+            ],
+            Remainder::First,
+        );
+        shares[member % 2]
+    }
+}
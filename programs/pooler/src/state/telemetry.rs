@@ -0,0 +1,34 @@
+//! Телеметрия клиентов, присылающих ходы.
+
+use crate::*;
+
+/// Идентификатор и версия клиента, собравшего ход; программа на них не
+/// опирается, это только диагностика рассинхронов между клиентами.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct ClientInfo {
+    pub client_id: u16,
+    pub client_version: u16,
+}
+
+/// Последняя телеметрия клиентов игроков (PDA [b"client_telemetry", game]).
+///
+/// Создаётся в join_game (по желанию второго игрока), дальше обновляется
+/// make_move, если аккаунт передан.
+#[account]
+#[derive(InitSpace)]
+pub struct ClientTelemetry {
+    pub game: Pubkey,
+    pub last_client: [ClientInfo; 2],
+    pub bump: u8,
+}
+
+impl ClientTelemetry {
+    /// Запоминает клиента стороны side (1 или 2); None ничего не меняет.
+    pub fn record(&mut self, side: u8, client: Option<ClientInfo>) {
+        if let Some(client) = client {
+            self.last_client[(side - 1) as usize] = client;
+        }
+    }
+}
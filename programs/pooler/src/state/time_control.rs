@@ -0,0 +1,151 @@
+//! Контроль времени: пресеты, тайм-ауты и рост комиссии за ход.
+
+use crate::*;
+
+/// Пресет контроля времени и комиссий, задаётся в init_game.
+///
+/// | пресет         | refund_timeout | move_timeout   | increment | рост комиссии        |
+/// |----------------|----------------|----------------|-----------|----------------------|
+/// | Casual         | 600            | 150            | 0         | нет                  |
+/// | Blitz          | 300            | 75             | 5         | +10% каждые 20 ходов |
+/// | Correspondence | 648 000 (~3 д) | 216 000 (~1 д) | 0         | нет                  |
+/// | Custom         | явно           | явно           | явно      | явно                 |
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub enum GamePreset {
+    /// Глобальные константы, как у игр до пресетов.
+    #[default]
+    Casual,
+    Blitz,
+    Correspondence,
+    Custom,
+}
+
+impl GamePreset {
+    /// Параметры пресета; у Custom их нет.
+    pub fn params(&self) -> Option<TimeControlParams> {
+        match self {
+            GamePreset::Casual => Some(CASUAL_TIME_CONTROL),
+            GamePreset::Blitz => Some(TimeControlParams {
+                refund_timeout_slots: 300,
+                move_timeout_slots: 75,
+                increment_slots: 5,
+                fee_escalation_bps: 1_000,
+                fee_escalation_every: 20,
+            }),
+            GamePreset::Correspondence => Some(TimeControlParams {
+                refund_timeout_slots: 648_000,
+                move_timeout_slots: 216_000,
+                increment_slots: 0,
+                fee_escalation_bps: 0,
+                fee_escalation_every: 0,
+            }),
+            GamePreset::Custom => None,
+        }
+    }
+}
+
+/// Параметры контроля времени (в слотах) и роста комиссии за ход.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default, InitSpace,
+)]
+pub struct TimeControlParams {
+    /// Тайм-аут аварийного возврата (force_refund).
+    pub refund_timeout_slots: u64,
+    /// Срок на ход или ответ на удвоение (record_strike, двухфазные ходы).
+    pub move_timeout_slots: u64,
+    /// Добавка к запасу времени игрока за каждый его ход.
+    pub increment_slots: u64,
+    /// Рост комиссии за ход на каждом шаге, в б.п. от move_fee_lamports.
+    pub fee_escalation_bps: u16,
+    /// Длина шага роста комиссии в ходах (0 = без роста).
+    pub fee_escalation_every: u16,
+}
+
+/// Параметры Casual — прежние глобальные константы.
+pub const CASUAL_TIME_CONTROL: TimeControlParams = TimeControlParams {
+    refund_timeout_slots: FORCE_REFUND_TIMEOUT_SLOTS,
+    move_timeout_slots: MOVE_TIMEOUT_SLOTS,
+    increment_slots: 0,
+    fee_escalation_bps: 0,
+    fee_escalation_every: 0,
+};
+
+impl TimeControlParams {
+    /// Комиссия за ход с номером move_index:
+    /// base * (1 + bps / BPS_DENOMINATOR * (move_index / fee_escalation_every)).
+    pub fn move_fee(&self, base: u64, move_index: u64) -> Result<u64> {
+        if self.fee_escalation_bps == 0 || self.fee_escalation_every == 0 {
+            return Ok(base);
+        }
+        let steps = (move_index / self.fee_escalation_every as u64) as u128;
+        let factor = BPS_DENOMINATOR as u128 + self.fee_escalation_bps as u128 * steps;
+        u64::try_from(base as u128 * factor / BPS_DENOMINATOR as u128)
+            .map_err(|_| ErrorCode::MathOverflow.into())
+    }
+}
+
+/// Раскрывает пресет в параметры. Для Custom явные параметры обязательны и
+/// проверяются; для остальных каждое ненулевое явное значение должно
+/// совпадать со значением пресета.
+pub fn resolve_time_control(
+    preset: GamePreset,
+    explicit: TimeControlParams,
+) -> Result<TimeControlParams> {
+    let Some(params) = preset.params() else {
+        require!(
+            explicit.move_timeout_slots > 0
+                && explicit.refund_timeout_slots > explicit.move_timeout_slots
+                && (explicit.fee_escalation_bps == 0 || explicit.fee_escalation_every > 0),
+            ErrorCode::InvalidTimeControl
+        );
+        return Ok(explicit);
+    };
+    let conflicts = |value: u64, expected: u64| value != 0 && value != expected;
+    require!(
+        !conflicts(explicit.refund_timeout_slots, params.refund_timeout_slots)
+            && !conflicts(explicit.move_timeout_slots, params.move_timeout_slots)
+            && !conflicts(explicit.increment_slots, params.increment_slots)
+            && !conflicts(
+                explicit.fee_escalation_bps as u64,
+                params.fee_escalation_bps as u64
+            )
+            && !conflicts(
+                explicit.fee_escalation_every as u64,
+                params.fee_escalation_every as u64
+            ),
+        ErrorCode::PresetOverrideConflict
+    );
+    Ok(params)
+}
+
+/// Контроль времени игры с пресетом, отличным от Casual (PDA [b"time_control", game]).
+///
+/// Запас времени (bank) работает так: срок хода — move_timeout_slots плюс
+/// запас игрока; ход дольше move_timeout_slots расходует запас, каждый ход
+/// добавляет к нему increment_slots.
+#[account]
+pub struct TimeControl {
+    pub game: Pubkey,              // 32
+    pub params: TimeControlParams, // 28
+    pub p1_bank_slots: u64,        // 8
+    pub p2_bank_slots: u64,        // 8
+    pub bump: u8,                  // 1
+}
+
+impl TimeControl {
+    pub const MAX_SIZE: usize = 96;
+
+    /// Учитывает ход игрока (1 или 2), сделанный через elapsed слотов.
+    pub fn record_move(&mut self, player_index: u8, elapsed: u64) {
+        let over = elapsed.saturating_sub(self.params.move_timeout_slots);
+        let increment = self.params.increment_slots;
+        let bank = if player_index == 1 {
+            &mut self.p1_bank_slots
+        } else {
+            &mut self.p2_bank_slots
+        };
+        *bank = bank.saturating_sub(over).saturating_add(increment);
+    }
+}
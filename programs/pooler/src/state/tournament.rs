@@ -0,0 +1,127 @@
+//! Турниры на выбывание и по швейцарской системе.
+
+use crate::*;
+
+/// Турнир: на выбывание или по швейцарской системе.
+#[account]
+pub struct Tournament {
+    pub organizer: Pubkey,                                   // 32
+    pub tournament_id: u64,                                  // 8
+    pub entry_fee: u64,                                      // 8
+    pub max_players: u8,                                     // 1
+    pub player_count: u8,                                    // 1
+    pub players: [Pubkey; TOURNAMENT_MAX_PLAYERS],           // 8 * 32, в порядке регистрации
+    pub status: TournamentStatus,                            // 1
+    pub round: u8,                                           // 1
+    pub bracket: [Pubkey; TOURNAMENT_MAX_PLAYERS],           // 8 * 32, оставшиеся игроки по парам
+    pub bracket_size: u8,                                    // 1
+    pub round_winners: [Pubkey; TOURNAMENT_MAX_PLAYERS / 2], // 4 * 32, победители пар раунда
+    pub prize_pool: u64,                                     // 8
+    pub champion: Pubkey,                                    // 32
+    pub bump: u8,                                            // 1
+    pub mode: TournamentMode,                                // 1
+    pub swiss_rounds: u8,                                    // 1, число туров (Swiss)
+    pub scores: [u8; TOURNAMENT_MAX_PLAYERS],                // 32, очки по индексу игрока
+    pub opponents: [u32; TOURNAMENT_MAX_PLAYERS], // 128, битовые маски прошлых соперников
+    pub pairings: [u8; TOURNAMENT_MAX_PLAYERS],   // 32, соперник в текущем туре (SWISS_BYE = bye)
+    pub reported: u32,                            // 4, маска игроков с результатом тура
+    pub had_bye: u32,                             // 4, маска игроков, уже получивших bye
+    pub buchholz: [u16; TOURNAMENT_MAX_PLAYERS],  // 64, итоговый Бухгольц
+    pub standings: [u8; TOURNAMENT_MAX_PLAYERS],  // 32, итоговая таблица (индексы игроков)
+}
+
+impl Tournament {
+    pub const MAX_SIZE: usize = 3072;
+}
+
+/// Максимум участников турнира.
+pub const TOURNAMENT_MAX_PLAYERS: usize = 32;
+
+/// Маркер bye в pairings швейцарской системы.
+#[constant]
+pub const SWISS_BYE: u8 = u8::MAX;
+
+/// Ограничение на число шагов перебора при жеребьёвке (чтобы уложиться в compute).
+pub const SWISS_PAIRING_BUDGET: u32 = 2_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TournamentMode {
+    Knockout,
+    Swiss,
+}
+
+/// Порядок игроков в таблице: очки по убыванию, затем tiebreak по убыванию,
+/// затем индекс регистрации по возрастанию.
+pub fn swiss_order(
+    scores: &[u8; TOURNAMENT_MAX_PLAYERS],
+    tiebreak: &[u16; TOURNAMENT_MAX_PLAYERS],
+    count: usize,
+) -> [u8; TOURNAMENT_MAX_PLAYERS] {
+    let mut order = [0u8; TOURNAMENT_MAX_PLAYERS];
+    for (i, slot) in order.iter_mut().enumerate() {
+        *slot = i as u8;
+    }
+    order[..count].sort_by(|&a, &b| {
+        let (a, b) = (a as usize, b as usize);
+        scores[b]
+            .cmp(&scores[a])
+            .then(tiebreak[b].cmp(&tiebreak[a]))
+            .then(a.cmp(&b))
+    });
+    order
+}
+
+/// Перебор с возвратом для жеребьёвки: берём старшего свободного игрока и
+/// пробуем соперников сверху вниз по таблице. Каждый пробный шаг расходует
+/// единицу budget; при исчерпании перебор прекращается.
+pub fn swiss_pair(
+    order: &[u8],
+    used: u32,
+    opponents: &[u32; TOURNAMENT_MAX_PLAYERS],
+    pairings: &mut [u8; TOURNAMENT_MAX_PLAYERS],
+    budget: &mut u32,
+    allow_rematch: bool,
+) -> bool {
+    let first = match order.iter().position(|&i| used & (1 << i) == 0) {
+        Some(pos) => pos,
+        None => return true,
+    };
+    let a = order[first];
+    let used = used | (1 << a);
+
+    for &b in &order[first + 1..] {
+        if used & (1 << b) != 0 {
+            continue;
+        }
+        if !allow_rematch && opponents[a as usize] & (1 << b) != 0 {
+            continue;
+        }
+        if *budget == 0 {
+            return false;
+        }
+        *budget -= 1;
+
+        pairings[a as usize] = b;
+        pairings[b as usize] = a;
+        if swiss_pair(
+            order,
+            used | (1 << b),
+            opponents,
+            pairings,
+            budget,
+            allow_rematch,
+        ) {
+            return true;
+        }
+    }
+
+    pairings[a as usize] = SWISS_BYE;
+    false
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TournamentStatus {
+    Registration,
+    Running,
+    Finished,
+}
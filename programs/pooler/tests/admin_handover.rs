@@ -0,0 +1,112 @@
+//! Передача прав администратора в два шага: propose_admin записывает
+//! предложение, права переходят только после accept_admin от предложенного
+//! ключа. Администратор-PDA (мультисиг, SPL Governance) подписывает через
+//! CPI — здесь это моделируется высотой стека, как в cpi_guard.rs.
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey};
+use anchor_lang::solana_program::instruction::TRANSACTION_LEVEL_STACK_HEIGHT;
+use common::{config_with, parse, set_stack_height, Account};
+use pooler::{AcceptAdmin, Config, ConfigUpdate, ErrorCode, ProposeAdmin, UpdateConfig};
+
+fn admin_config(admin: Pubkey, pending_admin: Pubkey) -> Account {
+    config_with(|config| {
+        config.admin = admin;
+        config.pending_admin = pending_admin;
+    })
+}
+
+fn propose(config: Account, admin: Pubkey, new_admin: Pubkey) -> anchor_lang::Result<Config> {
+    let mut accounts = [config, Account::wallet(admin, true)];
+    let (mut parsed, bumps) = parse::<ProposeAdmin>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::propose_admin(ctx, new_admin)?;
+    Ok((*parsed.config).clone())
+}
+
+fn accept(config: Account, signer: Pubkey) -> anchor_lang::Result<Config> {
+    let mut accounts = [config, Account::wallet(signer, true)];
+    let (mut parsed, bumps) = parse::<AcceptAdmin>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::accept_admin(ctx)?;
+    Ok((*parsed.config).clone())
+}
+
+fn account(config: &Config) -> Account {
+    config_with(|c| *c = config.clone())
+}
+
+#[test]
+fn handover_takes_effect_after_accept() {
+    let (admin, new_admin) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let proposed = propose(admin_config(admin, Pubkey::default()), admin, new_admin).unwrap();
+    assert_eq!(proposed.admin, admin);
+    assert_eq!(proposed.pending_admin, new_admin);
+
+    let accepted = accept(account(&proposed), new_admin).unwrap();
+    assert_eq!(accepted.admin, new_admin);
+    assert_eq!(accepted.pending_admin, Pubkey::default());
+
+    // Прежний админ прав больше не имеет.
+    assert_eq!(
+        propose(account(&accepted), admin, admin).err(),
+        Some(anchor_lang::error::ErrorCode::ConstraintHasOne.into())
+    );
+}
+
+#[test]
+fn unaccepted_proposal_changes_nothing() {
+    let (admin, new_admin) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Без предложения принимать нечего.
+    assert_eq!(
+        accept(admin_config(admin, Pubkey::default()), new_admin).err(),
+        Some(ErrorCode::NoPendingAdmin.into())
+    );
+
+    // Предложение ждёт подписи именно предложенного ключа; админ остаётся прежним.
+    let proposed = propose(admin_config(admin, Pubkey::default()), admin, new_admin).unwrap();
+    assert_eq!(proposed.admin, admin);
+    for signer in [admin, Pubkey::new_unique()] {
+        assert_eq!(
+            accept(account(&proposed), signer).err(),
+            Some(ErrorCode::NotPendingAdmin.into())
+        );
+    }
+
+    // Отозванное предложение (Pubkey::default()) принять нельзя.
+    let revoked = propose(account(&proposed), admin, Pubkey::default()).unwrap();
+    assert_eq!(revoked.pending_admin, Pubkey::default());
+    assert_eq!(
+        accept(account(&revoked), new_admin).err(),
+        Some(ErrorCode::NoPendingAdmin.into())
+    );
+
+    // После renounce_admin передача невозможна.
+    let mut locked = proposed.clone();
+    locked.locked = true;
+    assert_eq!(
+        accept(account(&locked), new_admin).err(),
+        Some(ErrorCode::ConfigLocked.into())
+    );
+}
+
+#[test]
+fn pda_admin_updates_config_through_cpi() {
+    common::install_sysvars();
+    let (governance, _) = Pubkey::find_program_address(&[b"governance"], &Pubkey::new_unique());
+    let mut accounts = [
+        admin_config(governance, Pubkey::default()),
+        Account::wallet(governance, true),
+    ];
+    let (mut parsed, bumps) = parse::<UpdateConfig>(&mut accounts).unwrap();
+    set_stack_height(TRANSACTION_LEVEL_STACK_HEIGHT + 1);
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    let update = ConfigUpdate {
+        paused: Some(true),
+        ..Default::default()
+    };
+    pooler::backgammon::update_config(ctx, update).unwrap();
+    assert!(parsed.config.paused);
+}
@@ -0,0 +1,311 @@
+//! Один аккаунт в двух ролях: контексты с двумя игроками отклоняют
+//! одинаковые player1/player2 (DuplicatePlayerAccounts), а аккаунты выплат —
+//! совпадающие с аккаунтом игры (PayoutAliasesGame). Игра, у которой
+//! player1 == player2, проходит проверки address, так что срабатывает
+//! именно новая проверка.
+//!
+//! init_game и join_game с player2 == player1 проверяет self_play.rs.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{system_program, Accounts, Bumps};
+use common::{
+    config, event_cpi, finish_game, force_refund, game, infos, install_sysvars, no_splits, pda,
+    try_accounts, Account,
+};
+use pooler::{
+    pay_out, ErrorCode, GameState, GameStatus, MirrorPair, PayoutSplit, PayoutSplits, TeamState,
+    WinClaim,
+};
+
+/// Активная игра, в которой оба места занимает один кошелёк.
+fn aliased_game() -> GameState {
+    let mut state = game(GameStatus::Active);
+    state.player2 = state.player1;
+    state
+}
+
+fn duplicate_players<T>(accounts: Vec<Account>)
+where
+    T: Bumps + Accounts<'static, T::Bumps>,
+    T::Bumps: Default,
+{
+    assert_eq!(
+        try_accounts::<T>(Vec::leak(accounts)).unwrap_err(),
+        ErrorCode::DuplicatePlayerAccounts.into()
+    );
+}
+
+fn payout_aliases_game<T>(accounts: Vec<Account>)
+where
+    T: Bumps + Accounts<'static, T::Bumps>,
+    T::Bumps: Default,
+{
+    assert_eq!(
+        try_accounts::<T>(Vec::leak(accounts)).unwrap_err(),
+        ErrorCode::PayoutAliasesGame.into()
+    );
+}
+
+#[test]
+fn settlement_contexts_reject_one_wallet_in_both_seats() {
+    let state = aliased_game();
+    let player = state.player1;
+    duplicate_players::<pooler::FinishGame>(finish_game(&state, player, player));
+    duplicate_players::<pooler::ForceRefund>(force_refund(&state, player, player));
+
+    let game_key = Pubkey::new_unique();
+    let game_account = || Account::owned(game_key, &state);
+
+    // game, player1, player2, requester, system_program, bot_vault,
+    // escrow_agent, team, сплиты ×2, event_buffer, config, treasury, event_cpi.
+    let mut accounts = vec![
+        game_account(),
+        Account::wallet(player, false),
+        Account::wallet(player, false),
+        Account::wallet(player, true),
+        Account::program(system_program::ID),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        no_splits(&player),
+        no_splits(&player),
+        Account::none(),
+        config(),
+        Account::none(),
+    ];
+    accounts.extend(event_cpi());
+    duplicate_players::<pooler::ManualRefundOneSigner>(accounts);
+
+    // game, config, season, stats ×2, player1, player2, bot_vault, team,
+    // win_claim, event_buffer, fee_vault, incinerator, сплиты ×2, event_cpi.
+    let mut accounts = vec![game_account(), config()];
+    accounts.extend((0..3).map(|_| Account::none()));
+    accounts.extend([
+        Account::wallet(player, false),
+        Account::wallet(player, false),
+    ]);
+    accounts.extend((0..6).map(|_| Account::none()));
+    accounts.extend([no_splits(&player), no_splits(&player)]);
+    accounts.extend(event_cpi());
+    duplicate_players::<pooler::ExecuteResolution>(accounts);
+
+    // game, player1, player2, config, bot_vault, escrow_agent, team,
+    // event_buffer, fee_vault, incinerator, сплиты ×2, event_cpi.
+    let mut accounts = vec![
+        game_account(),
+        Account::wallet(player, true),
+        Account::wallet(player, true),
+        config(),
+    ];
+    accounts.extend((0..6).map(|_| Account::none()));
+    accounts.extend([no_splits(&player), no_splits(&player)]);
+    accounts.extend(event_cpi());
+    duplicate_players::<pooler::SettleGame>(accounts);
+
+    // game, player1, player2, config, season, stats ×2, bot_vault, team,
+    // payer, event_buffer, fee_vault, incinerator, сплиты ×2, event_cpi.
+    let mut accounts = vec![
+        game_account(),
+        Account::wallet(player, false),
+        Account::wallet(player, false),
+        config(),
+    ];
+    accounts.extend((0..5).map(|_| Account::none()));
+    accounts.push(Account::wallet(Pubkey::new_unique(), true));
+    accounts.extend((0..3).map(|_| Account::none()));
+    accounts.extend([no_splits(&player), no_splits(&player)]);
+    accounts.extend(event_cpi());
+    duplicate_players::<pooler::FinalizeFromBoard>(accounts);
+
+    // game, player1, player2, requester, bot_vault, escrow_agent, team,
+    // сплиты ×2, event_buffer, config, treasury, event_cpi.
+    let mut accounts = vec![
+        game_account(),
+        Account::wallet(player, false),
+        Account::wallet(player, false),
+        Account::wallet(player, true),
+    ];
+    accounts.extend((0..3).map(|_| Account::none()));
+    accounts.extend([no_splits(&player), no_splits(&player), Account::none()]);
+    accounts.extend([config(), Account::none()]);
+    accounts.extend(event_cpi());
+    duplicate_players::<pooler::SettleByMoveCap>(accounts);
+}
+
+#[test]
+fn cube_and_opening_contexts_reject_one_wallet_in_both_seats() {
+    let state = aliased_game();
+    let game_key = Pubkey::new_unique();
+    let accounts = || {
+        vec![
+            Account::owned(game_key, &state),
+            Account::wallet(state.player1, true),
+            Account::wallet(state.player2, true),
+            Account::program(system_program::ID),
+            config(),
+        ]
+    };
+    duplicate_players::<pooler::BeaverDouble>(accounts());
+    duplicate_players::<pooler::RaccoonDouble>(accounts());
+    duplicate_players::<pooler::RecordOpeningTie>(accounts());
+}
+
+#[test]
+fn settle_pair_rejects_one_wallet_on_both_sides() {
+    let player = Pubkey::new_unique();
+    let (game_a, game_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (pair_key, bump) = pda(&[b"mirror", game_a.as_ref(), game_b.as_ref()]);
+    let pair = MirrorPair {
+        game_a,
+        game_b,
+        player_a: player,
+        player_b: player,
+        stake_lamports: 0,
+        settled: false,
+        bump,
+    };
+    let finished = game(GameStatus::Finished);
+    duplicate_players::<pooler::SettlePair>(vec![
+        Account::owned(pair_key, &pair),
+        Account::owned(game_a, &finished),
+        Account::owned(game_b, &finished),
+        Account::wallet(player, false),
+        Account::wallet(player, false),
+        Account::wallet(Pubkey::new_unique(), true),
+        config(),
+    ]);
+}
+
+#[test]
+fn payout_accounts_must_not_be_the_game() {
+    let state = game(GameStatus::Active);
+    let game_key = Pubkey::new_unique();
+    let game_account = || Account::owned(game_key, &state);
+
+    // drop_double: doubler — сам аккаунт игры.
+    let mut accounts = vec![
+        game_account(),
+        Account::wallet(state.player2, true),
+        Account::wallet(game_key, false),
+        config(),
+    ];
+    accounts.extend((0..7).map(|_| Account::none()));
+    accounts.push(no_splits(&game_key));
+    accounts.extend(event_cpi());
+    payout_aliases_game::<pooler::DropDouble>(accounts);
+
+    // finalize_claim: заявитель выигрыша — аккаунт игры.
+    let (claim_key, bump) = pda(&[b"win_claim", game_key.as_ref()]);
+    let claim = WinClaim {
+        game: game_key,
+        claimant: game_key,
+        final_board: [0; 24],
+        deadline_slot: 0,
+        bond_lamports: 0,
+        bump,
+    };
+    let mut accounts = vec![
+        game_account(),
+        Account::owned(claim_key, &claim),
+        Account::wallet(game_key, false),
+        config(),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::wallet(Pubkey::new_unique(), true),
+    ];
+    accounts.extend((0..4).map(|_| Account::none()));
+    accounts.push(no_splits(&game_key));
+    accounts.extend(event_cpi());
+    payout_aliases_game::<pooler::FinalizeClaim>(accounts);
+
+    // claim_team_share: получатель доли — аккаунт игры.
+    let (team_key, bump) = pda(&[b"team", game_key.as_ref()]);
+    let team = TeamState {
+        game: game_key,
+        teammates: [Pubkey::default(); 2],
+        contributions: [0; 4],
+        received: [0; 2],
+        claimed: [false; 4],
+        bump,
+    };
+    payout_aliases_game::<pooler::ClaimTeamShare>(vec![
+        game_account(),
+        Account::owned(team_key, &team),
+        Account::wallet(game_key, false),
+        Account::wallet(Pubkey::new_unique(), true),
+        config(),
+    ]);
+
+    // make_move: получатель комиссии — аккаунт игры.
+    let mut accounts = vec![
+        game_account(),
+        Account::wallet(state.player1, true),
+        Account::wallet(state.player2, false),
+        Account::program(system_program::ID),
+        Account::none(),
+        config(),
+    ];
+    // team, time_control, fee_route.
+    accounts.extend((0..3).map(|_| Account::none()));
+    accounts.push(Account::wallet(game_key, false));
+    // insurance_pool, client_telemetry, event_buffer, game_history,
+    // history_commitment, decay_sink.
+    accounts.extend((0..6).map(|_| Account::none()));
+    payout_aliases_game::<pooler::MakeMove>(accounts);
+
+    // relayed_move: то же для хода через ретранслятор.
+    let mut accounts = vec![
+        game_account(),
+        Account::wallet(Pubkey::new_unique(), true),
+        config(),
+    ];
+    // relay_budget, time_control, fee_route.
+    accounts.extend((0..3).map(|_| Account::none()));
+    accounts.push(Account::wallet(game_key, false));
+    accounts.push(Account::wallet(solana_instructions_sysvar::ID, false));
+    // insurance_pool, event_buffer, game_history, history_commitment,
+    // decay_sink.
+    accounts.extend((0..5).map(|_| Account::none()));
+    payout_aliases_game::<pooler::RelayedMove>(accounts);
+}
+
+#[test]
+fn split_recipient_must_not_be_the_game() {
+    install_sysvars();
+    let state = game(GameStatus::Finished);
+    let game_key = Pubkey::new_unique();
+    let (splits_key, bump) = pda(&[b"payout_splits", state.player1.as_ref()]);
+    let splits = PayoutSplits {
+        player: state.player1,
+        splits: vec![PayoutSplit {
+            recipient: game_key,
+            bps: 10_000,
+        }],
+        bump,
+    };
+    let mut game_account = Account::owned(game_key, &state);
+    game_account.lamports += 1_000;
+    let mut accounts = [
+        game_account,
+        Account::wallet(state.player1, false),
+        Account::owned(splits_key, &splits),
+        Account::wallet(game_key, false),
+    ];
+    let infos = infos(&mut accounts);
+    assert_eq!(
+        pay_out(
+            &infos[0],
+            infos[1].clone(),
+            &infos[2],
+            &mut infos[3..].iter(),
+            1_000
+        )
+        .unwrap_err(),
+        ErrorCode::PayoutAliasesGame.into()
+    );
+}
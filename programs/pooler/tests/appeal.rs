@@ -0,0 +1,196 @@
+//! Окно апелляции: arbiter_resolve только записывает решение, банк до
+//! execute_resolution заблокирован, а appeal передаёт спор админу.
+//!
+//! Обработчики вызываются напрямую; выплата execute_resolution (emit_cpi!)
+//! проверяется в LiteSVM (tests/arbiters.rs).
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey};
+use common::{config_with, event_cpi, force_refund, game, no_splits, parse, set_slot, Account};
+use pooler::{
+    Appeal, ArbiterResolve, ErrorCode, ExecuteResolution, ForceRefund, GameState, GameStatus,
+    DEFAULT_APPEAL_WINDOW_SLOTS, EVIDENCE_WINDOW_SLOTS, MIN_APPEAL_WINDOW_SLOTS,
+};
+
+/// Слот решения: окно доказательств с начала спора (слот 0) уже прошло.
+const NOW: u64 = EVIDENCE_WINDOW_SLOTS;
+const POT: u64 = 1_000_000;
+
+struct Keys {
+    admin: Pubkey,
+    arbiter: Pubkey,
+}
+
+fn keys() -> Keys {
+    Keys {
+        admin: Pubkey::new_unique(),
+        arbiter: Pubkey::new_unique(),
+    }
+}
+
+fn disputed() -> GameState {
+    let mut state = game(GameStatus::Disputed);
+    state.pot_lamports = POT;
+    state
+}
+
+fn config(keys: &Keys, appeal_window_slots: u64) -> Account {
+    config_with(|config| {
+        config.admin = keys.admin;
+        config.arbiter = keys.arbiter;
+        config.appeal_window_slots = appeal_window_slots;
+    })
+}
+
+fn resolve(
+    state: &GameState,
+    config: Account,
+    signer: Pubkey,
+    winner: Pubkey,
+) -> anchor_lang::Result<GameState> {
+    let mut accounts = [
+        Account::owned(Pubkey::new_unique(), state),
+        config,
+        Account::wallet(signer, true),
+        Account::none(),
+        Account::none(),
+    ];
+    let (mut parsed, bumps) = parse::<ArbiterResolve>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::arbiter_resolve(ctx, winner)?;
+    Ok((*parsed.game).clone())
+}
+
+fn appeal(state: &GameState, signer: Pubkey) -> anchor_lang::Result<GameState> {
+    let mut accounts = [
+        Account::owned(Pubkey::new_unique(), state),
+        Account::wallet(signer, true),
+    ];
+    let (mut parsed, bumps) = parse::<Appeal>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::appeal(ctx)?;
+    Ok((*parsed.game).clone())
+}
+
+/// execute_resolution до выплаты: все проверки идут раньше emit_cpi!.
+fn execute(state: &GameState) -> anchor_lang::Result<()> {
+    let game = Pubkey::new_unique();
+    let mut accounts = vec![
+        Account::owned(game, state),
+        config_with(|_| {}),
+        // season, stats ×2.
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::wallet(state.player1, false),
+        Account::wallet(state.player2, false),
+    ];
+    // bot_vault, team, win_claim, event_buffer, fee_vault, incinerator.
+    accounts.extend((0..6).map(|_| Account::none()));
+    accounts.push(no_splits(&state.player1));
+    accounts.push(no_splits(&state.player2));
+    accounts.extend(event_cpi());
+    let (mut parsed, bumps) = parse::<ExecuteResolution>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::execute_resolution(ctx)
+}
+
+#[test]
+fn resolution_waits_for_the_appeal_window() {
+    set_slot(NOW);
+    let keys = keys();
+    let state = disputed();
+
+    let resolved = resolve(&state, config(&keys, 0), keys.arbiter, state.player2).unwrap();
+    assert_eq!(resolved.status, GameStatus::Disputed);
+    assert_eq!(resolved.resolution_winner, 2);
+    assert_eq!(
+        resolved.appeal_deadline_slot,
+        NOW + DEFAULT_APPEAL_WINDOW_SLOTS
+    );
+    assert_eq!(resolved.pot_lamports, POT);
+
+    // Окно короче минимума поднимается до MIN_APPEAL_WINDOW_SLOTS.
+    let short = resolve(&state, config(&keys, 1), keys.arbiter, state.player2).unwrap();
+    assert_eq!(short.appeal_deadline_slot, NOW + MIN_APPEAL_WINDOW_SLOTS);
+
+    // Пока решение ждёт окна, его нельзя переписать, а банк — вернуть.
+    assert_eq!(
+        resolve(&resolved, config(&keys, 0), keys.arbiter, state.player1).err(),
+        Some(ErrorCode::ResolutionPending.into())
+    );
+    let mut accounts = force_refund(&resolved, resolved.player1, resolved.player2);
+    let (mut parsed, bumps) = parse::<ForceRefund>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::force_refund(ctx).unwrap_err(),
+        ErrorCode::ResolutionPending.into()
+    );
+
+    // Досрочное исполнение отклоняется до конца окна включительно.
+    for slot in [NOW, resolved.appeal_deadline_slot] {
+        set_slot(slot);
+        assert_eq!(
+            execute(&resolved).unwrap_err(),
+            ErrorCode::AppealWindowOpen.into()
+        );
+    }
+    assert_eq!(
+        execute(&state).unwrap_err(),
+        ErrorCode::NoPendingResolution.into()
+    );
+}
+
+#[test]
+fn appeal_hands_the_dispute_to_admin() {
+    set_slot(NOW);
+    let keys = keys();
+    let state = disputed();
+    let resolved = resolve(&state, config(&keys, 0), keys.arbiter, state.player1).unwrap();
+
+    assert_eq!(
+        appeal(&resolved, Pubkey::new_unique()).err(),
+        Some(ErrorCode::InvalidPlayer.into())
+    );
+    set_slot(resolved.appeal_deadline_slot + 1);
+    assert_eq!(
+        appeal(&resolved, resolved.player2).err(),
+        Some(ErrorCode::AppealWindowClosed.into())
+    );
+
+    // В последний слот окна апелляция принимается и снимает решение.
+    set_slot(resolved.appeal_deadline_slot);
+    let appealed = appeal(&resolved, resolved.player2).unwrap();
+    assert!(appealed.resolution_appealed);
+    assert_eq!(appealed.resolution_winner, 0);
+    assert_eq!(appealed.status, GameStatus::Disputed);
+    assert_eq!(appealed.pot_lamports, POT);
+    assert_eq!(
+        execute(&appealed).unwrap_err(),
+        ErrorCode::NoPendingResolution.into()
+    );
+
+    // Дальше решает только админ, пока он не отказался от прав.
+    assert_eq!(
+        resolve(&appealed, config(&keys, 0), keys.arbiter, state.player1).err(),
+        Some(ErrorCode::InvalidArbiter.into())
+    );
+    let locked = config_with(|config| {
+        config.admin = keys.admin;
+        config.locked = true;
+    });
+    assert_eq!(
+        resolve(&appealed, locked, keys.admin, state.player2).err(),
+        Some(ErrorCode::ConfigLocked.into())
+    );
+    let decided = resolve(&appealed, config(&keys, 0), keys.admin, state.player2).unwrap();
+    assert_eq!(decided.resolution_winner, 2);
+    assert_eq!(decided.appeal_deadline_slot, resolved.appeal_deadline_slot);
+
+    // Решение админа не обжалуется.
+    assert_eq!(
+        appeal(&decided, decided.player1).err(),
+        Some(ErrorCode::AlreadyAppealed.into())
+    );
+}
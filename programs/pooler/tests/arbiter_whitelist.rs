@@ -0,0 +1,157 @@
+//! Белый список арбитров: при config.require_whitelisted_arbiters init_game
+//! не принимает арбитра без PDA [b"arbiter", arbiter], а arbiter_resolve
+//! проверяет список заново — исключённый арбитр спор не решает.
+//!
+//! Обработчики вызываются напрямую; проверки срабатывают раньше любых CPI,
+//! исполнение решения (emit_cpi!) проверяется в LiteSVM (tests/arbiters.rs).
+
+mod common;
+
+use anchor_lang::prelude::{
+    Account as AnchorAccount, Context, Program, Pubkey, Signer, UncheckedAccount,
+};
+use anchor_lang::system_program;
+use common::{config_with, game, infos, open_games, parse, pda, Account};
+use pooler::{
+    ArbiterResolve, CreateCooldown, ErrorCode, GameOptions, GameStatus, InitGame, InitGameBumps,
+    WhitelistedArbiter,
+};
+
+fn whitelist_config() -> Account {
+    config_with(|config| config.require_whitelisted_arbiters = true)
+}
+
+/// Инициализированный PDA арбитра в белом списке.
+fn whitelisted(arbiter: Pubkey) -> Account {
+    let (key, bump) = pda(&[b"arbiter", arbiter.as_ref()]);
+    Account::owned(
+        key,
+        &WhitelistedArbiter {
+            arbiter,
+            added_at_slot: 0,
+            bump,
+        },
+    )
+}
+
+/// init_game с options.arbiter = arbiter; player2 — сам аккаунт игры, чтобы
+/// обработчик, пройдя проверку арбитра, остановился на PayoutAliasesGame
+/// раньше CPI.
+fn init_game(arbiter: Pubkey, entry: Option<Account>) -> anchor_lang::Result<()> {
+    common::install_sysvars();
+    let (player1, game_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut accounts = vec![
+        Account::owned(game_key, &game(GameStatus::WaitingForPlayer2)),
+        Account::wallet(player1, true),
+        Account::program(system_program::ID),
+        whitelist_config(),
+        open_games(&player1),
+        Account::owned(
+            Pubkey::new_unique(),
+            &CreateCooldown {
+                last_created_slot: 0,
+            },
+        ),
+    ];
+    let has_entry = entry.is_some();
+    accounts.extend(entry);
+    let infos = infos(&mut accounts);
+    let mut ctx_accounts = InitGame {
+        game: AnchorAccount::try_from(&infos[0])?,
+        player1: Signer::try_from(&infos[1])?,
+        system_program: Program::try_from(&infos[2])?,
+        config: AnchorAccount::try_from(&infos[3])?,
+        player1_ban: None,
+        open_games: AnchorAccount::try_from(&infos[4])?,
+        time_control: None,
+        create_cooldown: AnchorAccount::try_from(&infos[5])?,
+        fee_route: None,
+        whitelisted_arbiter: has_entry.then(|| UncheckedAccount::try_from(&infos[6])),
+        game_counter: None,
+        game_history: None,
+        history_commitment: None,
+    };
+    let ctx = Context::new(
+        &pooler::ID,
+        &mut ctx_accounts,
+        &[],
+        InitGameBumps::default(),
+    );
+    let options = GameOptions {
+        arbiter,
+        ..GameOptions::default()
+    };
+    pooler::backgammon::init_game(ctx, 1, 1_000_000, 0, game_key, options)
+}
+
+#[test]
+fn init_game_rejects_arbiter_off_the_whitelist() {
+    let arbiter = Pubkey::new_unique();
+    assert_eq!(
+        init_game(arbiter, None).unwrap_err(),
+        ErrorCode::ArbiterNotWhitelisted.into()
+    );
+    // PDA арбитра, которого нет в списке (не инициализирован).
+    let missing = Account::wallet(pda(&[b"arbiter", arbiter.as_ref()]).0, false);
+    assert_eq!(
+        init_game(arbiter, Some(missing)).unwrap_err(),
+        ErrorCode::ArbiterNotWhitelisted.into()
+    );
+
+    // Арбитр из списка и арбитр конфига (Pubkey::default()) проходят.
+    assert_eq!(
+        init_game(arbiter, Some(whitelisted(arbiter))).unwrap_err(),
+        ErrorCode::PayoutAliasesGame.into()
+    );
+    assert_eq!(
+        init_game(Pubkey::default(), None).unwrap_err(),
+        ErrorCode::PayoutAliasesGame.into()
+    );
+}
+
+/// arbiter_resolve по спорной игре с арбитром arbiter; entry — PDA арбитра
+/// (инициализированный или уже закрытый).
+fn resolve(signer: Pubkey, arbiter: Pubkey, entry: Account) -> anchor_lang::Result<()> {
+    let mut state = game(GameStatus::Disputed);
+    state.arbiter = arbiter;
+    let mut accounts = [
+        Account::owned(Pubkey::new_unique(), &state),
+        whitelist_config(),
+        Account::wallet(signer, true),
+        Account::none(),
+        entry,
+    ];
+    let (mut parsed, bumps) = parse::<ArbiterResolve>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::arbiter_resolve(ctx, state.player1)
+}
+
+#[test]
+fn arbiter_resolve_rechecks_the_whitelist() {
+    let arbiter = Pubkey::new_unique();
+
+    // Арбитр из списка проходит проверки и упирается в окно доказательств
+    // (слот SLOT < EVIDENCE_WINDOW_SLOTS), а не в белый список.
+    assert_eq!(
+        resolve(arbiter, arbiter, whitelisted(arbiter)).unwrap_err(),
+        ErrorCode::EvidenceWindowOpen.into()
+    );
+
+    // Чужой подписант не решает спор, даже с PDA назначенного арбитра.
+    let stranger = Pubkey::new_unique();
+    assert_eq!(
+        resolve(stranger, arbiter, whitelisted(arbiter)).unwrap_err(),
+        ErrorCode::InvalidArbiter.into()
+    );
+
+    // После remove_arbiter PDA закрыт: назначенный арбитр теряет власть.
+    let removed = Account::wallet(pda(&[b"arbiter", arbiter.as_ref()]).0, false);
+    assert_eq!(
+        resolve(arbiter, arbiter, removed).unwrap_err(),
+        ErrorCode::ArbiterNotWhitelisted.into()
+    );
+    assert_eq!(
+        resolve(arbiter, arbiter, Account::none()).unwrap_err(),
+        ErrorCode::ArbiterNotWhitelisted.into()
+    );
+}
@@ -0,0 +1,38 @@
+//! Бобр на пределе куба: при кубе 64 учетверение не помещается в u8, и
+//! beaver_double должен ответить CubeAtMaximum, а не MathOverflow.
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey};
+use common::{config, game, install_sysvars, parse, Account};
+use pooler::{BeaverDouble, ErrorCode, GameStatus, MAX_CUBE_VALUE};
+
+fn beaver(cube_value: u8) -> anchor_lang::Result<()> {
+    install_sysvars();
+    let mut state = game(GameStatus::Active);
+    state.beavers_allowed = true;
+    state.double_pending = true;
+    state.cube_value = cube_value;
+    let game = Pubkey::new_unique();
+    let mut accounts = [
+        Account::owned(game, &state),
+        Account::wallet(state.player1, true),
+        Account::wallet(state.player2, true),
+        Account::program(anchor_lang::system_program::ID),
+        config(),
+    ];
+    let (mut parsed, bumps) = parse::<BeaverDouble>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::beaver_double(ctx)
+}
+
+#[test]
+fn beaver_past_the_cube_cap_is_rejected() {
+    for cube_value in [32, MAX_CUBE_VALUE] {
+        assert_eq!(
+            beaver(cube_value).unwrap_err(),
+            ErrorCode::CubeAtMaximum.into(),
+            "cube {cube_value}"
+        );
+    }
+}
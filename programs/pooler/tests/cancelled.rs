@@ -0,0 +1,75 @@
+//! Отменённое лобби: cancel_before_join ставит Cancelled, и в такую игру
+//! нельзя войти, сходить или завершить её.
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey};
+use common::{finish_game, game, join_game, parse, SLOT};
+use pooler::{ErrorCode, FinishGame, FinishReason, GameState, GameStatus, JoinGame};
+
+const STAKE: u64 = 1_000_000;
+
+fn open_lobby() -> GameState {
+    let mut state = game(GameStatus::WaitingForPlayer2);
+    state.stake_lamports = STAKE;
+    state.pot_lamports = STAKE;
+    state.player1_deposit = STAKE;
+    state
+}
+
+#[test]
+fn cancel_sets_cancelled_status() {
+    // Сам cancel_before_join вне валидатора не выполнить (emit_cpi! — это
+    // CPI), поэтому проверяется переход, который он применяет.
+    let mut state = open_lobby();
+    state.cancel(SLOT);
+    assert_eq!(state.status, GameStatus::Cancelled);
+    assert_eq!(state.finished_slot, SLOT);
+    assert_eq!(state.finish_reason, FinishReason::Cancelled);
+    assert_eq!(state.winner, Pubkey::default());
+    assert_eq!((state.pot_lamports, state.player1_deposit), (0, 0));
+    assert!(state.status.is_over());
+    assert!(GameStatus::Finished.is_over());
+    assert!(!GameStatus::Disputed.is_over());
+}
+
+#[test]
+fn cancelled_game_cannot_be_joined() {
+    let mut state = open_lobby();
+    state.status = GameStatus::Cancelled;
+    let mut accounts = join_game(&state, state.player2);
+    let (mut parsed, bumps) = parse::<JoinGame>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::join_game(ctx, Vec::new(), None).unwrap_err(),
+        ErrorCode::GameNotWaitingForPlayer2.into()
+    );
+}
+
+#[test]
+fn cancelled_game_cannot_be_moved_in() {
+    let mut state = open_lobby();
+    state.status = GameStatus::Cancelled;
+    state
+        .set_board_points(&pooler::start_board(pooler::RuleVariant::Standard, 0).unwrap())
+        .unwrap();
+    assert_eq!(
+        state
+            .check_can_move(false, &state.board_points().unwrap())
+            .unwrap_err(),
+        ErrorCode::GameNotActive.into()
+    );
+}
+
+#[test]
+fn cancelled_game_cannot_be_finished() {
+    let mut state = open_lobby();
+    state.status = GameStatus::Cancelled;
+    let mut accounts = finish_game(&state, state.player1, state.player2);
+    let (mut parsed, bumps) = parse::<FinishGame>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::finish_game(ctx, state.player1, 1).unwrap_err(),
+        ErrorCode::GameNotActive.into()
+    );
+}
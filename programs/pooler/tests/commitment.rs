@@ -0,0 +1,200 @@
+//! Merkle-коммитмент истории ходов: make_move дописывает лист каждого хода в
+//! HistoryCommitment, history_root совпадает с корнем, построенным оффчейн по
+//! записям ходов, а verify_history_entry принимает настоящую запись и
+//! отклоняет подделанную.
+//!
+//! Обработчики вызываются напрямую в бесплатном окне (без CPI); создание и
+//! закрытие коммитмента проверяются в LiteSVM (tests/commitment.rs).
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey};
+use anchor_lang::system_program;
+use common::{config_with, game, install_sysvars, parse, pda, Account, SLOT};
+use pooler::{
+    pack_board, start_board, ErrorCode, GameState, GameStatus, HistoryCommitment, HistoryHasher,
+    MakeMove, MoveRecord, RuleVariant, VerifyHistoryEntry,
+};
+
+const MOVES: u64 = 9;
+
+fn active_game() -> GameState {
+    let mut state = game(GameStatus::Active);
+    state
+        .set_board_points(&start_board(RuleVariant::Standard, 0).unwrap())
+        .unwrap();
+    state.history_commitment = true;
+    state
+}
+
+fn leaves(records: &[MoveRecord]) -> Vec<[u8; 32]> {
+    records.iter().map(MoveRecord::leaf_hash).collect()
+}
+
+fn proof(records: &[MoveRecord], move_index: u64) -> [[u8; 32]; 16] {
+    backgammon_core::merkle_proof::<HistoryHasher>(&leaves(records), move_index as usize - 1)
+        .unwrap()
+}
+
+fn empty_commitment(game: Pubkey) -> HistoryCommitment {
+    HistoryCommitment {
+        game,
+        payer: Pubkey::new_unique(),
+        leaf_count: 0,
+        frontier: [[0; 32]; 16],
+        history_root: backgammon_core::HistoryFrontier::default().root::<HistoryHasher>(),
+        bump: 0,
+    }
+}
+
+/// make_move стороны current_turn; commitment = None — аккаунт не передан.
+fn make_move(
+    game_key: Pubkey,
+    state: &GameState,
+    commitment: Option<&HistoryCommitment>,
+    dice: [u8; 2],
+) -> anchor_lang::Result<(GameState, Option<HistoryCommitment>)> {
+    install_sysvars();
+    let mover = state.player_key(state.current_turn);
+    let opponent = state.player_key(3 - state.current_turn);
+    let mut accounts = vec![
+        Account::owned(game_key, state),
+        Account::wallet(mover, true),
+        Account::wallet(opponent, false),
+        Account::program(system_program::ID),
+        Account::none(),
+        config_with(|config| config.free_moves = 255),
+    ];
+    // team, time_control, fee_route, fee_recipient, insurance_pool,
+    // client_telemetry, event_buffer, game_history.
+    accounts.extend((0..8).map(|_| Account::none()));
+    accounts.push(match commitment {
+        Some(commitment) => Account::owned(
+            pda(&[b"history_commitment", game_key.as_ref()]).0,
+            commitment,
+        ),
+        None => Account::none(),
+    });
+    // decay_sink.
+    accounts.push(Account::none());
+    let (mut parsed, bumps) = parse::<MakeMove>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::make_move(ctx, state.board_points().unwrap(), dice, [0; 32], None)?;
+    Ok((
+        (*parsed.game).clone(),
+        parsed.history_commitment.map(|c| (**c).clone()),
+    ))
+}
+
+fn verify(
+    game_key: Pubkey,
+    state: &GameState,
+    commitment: &HistoryCommitment,
+    entry: MoveRecord,
+    proof: [[u8; 32]; 16],
+) -> anchor_lang::Result<()> {
+    let mut accounts = vec![
+        Account::owned(game_key, state),
+        Account::owned(
+            pda(&[b"history_commitment", game_key.as_ref()]).0,
+            commitment,
+        ),
+    ];
+    let (mut parsed, bumps) = parse::<VerifyHistoryEntry>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::verify_history_entry(ctx, entry, proof)
+}
+
+/// MOVES ходов (доска не меняется, кубики разные); возвращает состояние,
+/// коммитмент и записи ходов, как их восстановил бы клиент.
+fn play(game_key: Pubkey) -> (GameState, HistoryCommitment, Vec<MoveRecord>) {
+    let mut state = active_game();
+    let mut commitment = empty_commitment(game_key);
+    let mut records = Vec::new();
+    for i in 0..MOVES {
+        let dice = [(i % 6) as u8 + 1, ((i + 3) % 6) as u8 + 1];
+        records.push(MoveRecord {
+            move_index: i + 1,
+            mover: state.player_key(state.current_turn),
+            board: pack_board(&state.board_points().unwrap()).unwrap(),
+            dice,
+            slot: SLOT,
+            fee_lamports: 0,
+        });
+        let (next, next_commitment) = make_move(game_key, &state, Some(&commitment), dice).unwrap();
+        (state, commitment) = (next, next_commitment.unwrap());
+    }
+    (state, commitment, records)
+}
+
+#[test]
+fn moves_roll_the_root_forward() {
+    let game_key = Pubkey::new_unique();
+    let (state, commitment, records) = play(game_key);
+    assert_eq!(state.move_index, MOVES);
+    assert_eq!(commitment.leaf_count, MOVES);
+    assert_eq!(
+        Some(commitment.history_root),
+        backgammon_core::merkle_root::<HistoryHasher>(&leaves(&records))
+    );
+    assert_ne!(
+        commitment.history_root,
+        empty_commitment(game_key).history_root
+    );
+}
+
+#[test]
+fn recorded_moves_verify_and_forgeries_do_not() {
+    let game_key = Pubkey::new_unique();
+    let (state, commitment, records) = play(game_key);
+    for record in &records {
+        let proof = proof(&records, record.move_index);
+        verify(game_key, &state, &commitment, *record, proof).unwrap();
+    }
+
+    let proof = proof(&records, 4);
+    let mut forged = records[3];
+    forged.dice = [6, 6];
+    assert_eq!(
+        verify(game_key, &state, &commitment, forged, proof).err(),
+        Some(ErrorCode::HistoryProofInvalid.into())
+    );
+    let mut forged = records[3];
+    forged.mover = Pubkey::new_unique();
+    assert_eq!(
+        verify(game_key, &state, &commitment, forged, proof).err(),
+        Some(ErrorCode::HistoryProofInvalid.into())
+    );
+
+    // Ход, которого ещё не было, не доказывается даже по нулевому листу.
+    let future = MoveRecord {
+        move_index: MOVES + 1,
+        ..records[0]
+    };
+    assert_eq!(
+        verify(game_key, &state, &commitment, future, [[0; 32]; 16]).err(),
+        Some(ErrorCode::HistoryProofInvalid.into())
+    );
+
+    // Коммитмент другой игры не подходит.
+    let foreign = empty_commitment(Pubkey::new_unique());
+    assert_eq!(
+        verify(game_key, &state, &foreign, records[0], proof).err(),
+        Some(ErrorCode::HistoryCommitmentMismatch.into())
+    );
+}
+
+#[test]
+fn commitment_must_belong_to_the_game() {
+    let state = active_game();
+    let game_key = Pubkey::new_unique();
+    assert_eq!(
+        make_move(game_key, &state, None, [3, 1]).err(),
+        Some(ErrorCode::MissingHistoryCommitment.into())
+    );
+    let foreign = empty_commitment(Pubkey::new_unique());
+    assert_eq!(
+        make_move(game_key, &state, Some(&foreign), [3, 1]).err(),
+        Some(ErrorCode::HistoryCommitmentMismatch.into())
+    );
+}
@@ -0,0 +1,341 @@
+//! Общие заготовки тестов: аккаунты для AccountInfo, PDA программы и
+//! заглушки syscall (Rent::default(), Clock — по умолчанию в слоте SLOT,
+//! высота стека вызовов — по умолчанию верхний уровень транзакции).
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::collections::BTreeSet;
+use std::sync::Once;
+
+use anchor_lang::prelude::{AccountInfo, Clock, Pubkey, Rent};
+use anchor_lang::solana_program::instruction::{
+    BorrowedAccountMeta, BorrowedInstruction, TRANSACTION_LEVEL_STACK_HEIGHT,
+};
+use anchor_lang::{system_program, AccountSerialize, Accounts, AnchorDeserialize, Bumps};
+use pooler::signing::SignedMessage;
+use pooler::{Config, FinishReason, GameState, GameStatus, OpenGames};
+use solana_sdk_ids::ed25519_program;
+use solana_sysvar::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+/// Слот, который видит Clock::get в тестах.
+pub const SLOT: u64 = 1_000;
+
+struct Sysvars;
+
+impl SyscallStubs for Sysvars {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        // SAFETY: Rent::get передаёт указатель на место под Rent.
+        unsafe { (var_addr as *mut Rent).write(Rent::default()) };
+        0
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            slot: CLOCK_SLOT.with(Cell::get),
+            ..Clock::default()
+        };
+        // SAFETY: Clock::get передаёт указатель на место под Clock.
+        unsafe { (var_addr as *mut Clock).write(clock) };
+        0
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        STACK_HEIGHT.with(Cell::get) as u64
+    }
+}
+
+thread_local! {
+    static STACK_HEIGHT: Cell<usize> = const { Cell::new(TRANSACTION_LEVEL_STACK_HEIGHT) };
+    static CLOCK_SLOT: Cell<u64> = const { Cell::new(SLOT) };
+}
+
+/// Слот, который видит Clock::get в текущем потоке теста.
+pub fn set_slot(slot: u64) {
+    CLOCK_SLOT.with(|cell| cell.set(slot));
+}
+
+/// Высота стека вызовов, которую видит get_stack_height в текущем потоке
+/// теста: TRANSACTION_LEVEL_STACK_HEIGHT — верхний уровень, больше — CPI.
+pub fn set_stack_height(height: usize) {
+    STACK_HEIGHT.with(|cell| cell.set(height));
+}
+
+/// Ставит заглушки syscall (один раз на процесс).
+pub fn install_sysvars() {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(Sysvars));
+    });
+}
+
+/// Аккаунт транзакции вместе с хранилищем для AccountInfo.
+pub struct Account {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub signer: bool,
+    pub writable: bool,
+    pub executable: bool,
+}
+
+impl Account {
+    pub fn wallet(key: Pubkey, signer: bool) -> Self {
+        Account {
+            key,
+            owner: system_program::ID,
+            lamports: 1_000_000_000,
+            data: Vec::new(),
+            signer,
+            writable: true,
+            executable: false,
+        }
+    }
+
+    pub fn program(key: Pubkey) -> Self {
+        Account {
+            executable: true,
+            writable: false,
+            ..Account::wallet(key, false)
+        }
+    }
+
+    /// Отсутствующий Option-аккаунт: Anchor узнаёт его по ключу программы.
+    pub fn none() -> Self {
+        Account::program(pooler::ID)
+    }
+
+    pub fn owned<T: AccountSerialize>(key: Pubkey, state: &T) -> Self {
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        Account {
+            owner: pooler::ID,
+            data,
+            ..Account::wallet(key, false)
+        }
+    }
+
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            self.signer,
+            self.writable,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            self.executable,
+            0,
+        )
+    }
+}
+
+pub fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &pooler::ID)
+}
+
+pub fn config() -> Account {
+    config_with(|_| ())
+}
+
+/// Конфиг по PDA [b"config"] с правками edit.
+pub fn config_with(edit: impl FnOnce(&mut Config)) -> Account {
+    let (key, bump) = pda(&[b"config"]);
+    let mut config = Config::deserialize(&mut &[0u8; 1024][..]).unwrap();
+    config.bump = bump;
+    edit(&mut config);
+    Account::owned(key, &config)
+}
+
+/// PDA сплитов выплат игрока без зарегистрированных сплитов.
+pub fn no_splits(player: &Pubkey) -> Account {
+    Account {
+        lamports: 0,
+        writable: false,
+        ..Account::wallet(pda(&[b"payout_splits", player.as_ref()]).0, false)
+    }
+}
+
+pub fn open_games(player: &Pubkey) -> Account {
+    let (key, bump) = pda(&[b"open_games", player.as_ref()]);
+    Account::owned(
+        key,
+        &OpenGames {
+            player: *player,
+            open_games: 1,
+            bump,
+        },
+    )
+}
+
+/// Аккаунты, которые #[event_cpi] добавляет в конец контекста.
+pub fn event_cpi() -> [Account; 2] {
+    [
+        Account::wallet(pda(&[b"__event_authority"]).0, false),
+        Account::program(pooler::ID),
+    ]
+}
+
+pub fn game(status: GameStatus) -> GameState {
+    let mut state = GameState::deserialize(&mut &[0u8; 640][..]).unwrap();
+    state.player1 = Pubkey::new_unique();
+    state.player2 = Pubkey::new_unique();
+    state.status = status;
+    state.current_turn = 1;
+    state.finish_reason = FinishReason::NotFinished;
+    state.version = pooler::GAME_STATE_VERSION;
+    state
+}
+
+/// Аккаунты join_game: обязательные, пять отсутствующих Option и
+/// отсутствующие аккаунты размещения банка.
+pub fn join_game(state: &GameState, player2: Pubkey) -> Vec<Account> {
+    let mut accounts = vec![
+        Account::owned(Pubkey::new_unique(), state),
+        Account::wallet(player2, true),
+        Account::program(system_program::ID),
+        config(),
+        open_games(&state.player1),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+    ];
+    // pot_stake, pot_vault, pool_tokens, pool_mint, аккаунты пула и token_program.
+    accounts.extend((0..10).map(|_| Account::none()));
+    accounts
+}
+
+/// Аккаунты finish_game: обязательные, отсутствующие Option, PDA сплитов без
+/// сплитов и event_cpi.
+pub fn finish_game(state: &GameState, player1: Pubkey, player2: Pubkey) -> Vec<Account> {
+    let mut accounts = vec![
+        Account::owned(Pubkey::new_unique(), state),
+        Account::wallet(player1, true),
+        Account::wallet(player2, true),
+        Account::program(system_program::ID),
+        config(),
+    ];
+    // season, stats ×2, bot_vault, escrow_agent, team.
+    accounts.extend((0..6).map(|_| Account::none()));
+    accounts.push(no_splits(&state.player1));
+    accounts.push(no_splits(&state.player2));
+    // event_buffer, fee_vault, incinerator.
+    accounts.extend((0..3).map(|_| Account::none()));
+    accounts.extend(event_cpi());
+    accounts
+}
+
+/// Аккаунты force_refund: оба игрока, отсутствующие Option, PDA сплитов и
+/// event_cpi.
+pub fn force_refund(state: &GameState, player1: Pubkey, player2: Pubkey) -> Vec<Account> {
+    let mut accounts = vec![
+        Account::owned(Pubkey::new_unique(), state),
+        Account::wallet(player1, true),
+        Account::wallet(player2, true),
+        Account::program(system_program::ID),
+    ];
+    // bot_vault, team, time_control.
+    accounts.extend((0..3).map(|_| Account::none()));
+    accounts.push(no_splits(&state.player1));
+    accounts.push(no_splits(&state.player2));
+    // event_buffer, config, treasury.
+    accounts.extend([Account::none(), config(), Account::none()]);
+    accounts.extend(event_cpi());
+    accounts
+}
+
+/// AccountInfo для accounts. Anchor требует &'a AccountInfo<'a>, поэтому
+/// вектор утекает — в тестах это допустимо.
+pub fn infos(accounts: &mut [Account]) -> &[AccountInfo<'_>] {
+    Vec::leak(accounts.iter_mut().map(Account::info).collect())
+}
+
+/// Разбор контекста T из accounts (ix_data контексту не нужен) вместе с
+/// bump'ами — для Context::new при прямом вызове обработчика.
+pub fn parse<'a, T>(accounts: &'a mut [Account]) -> anchor_lang::Result<(T, T::Bumps)>
+where
+    T: Bumps + Accounts<'a, <T as Bumps>::Bumps>,
+    T::Bumps: Default,
+{
+    // init_if_needed (client_telemetry в JoinGame) читает Rent sysvar.
+    install_sysvars();
+    let mut infos = infos(accounts);
+    let mut bumps = T::Bumps::default();
+    let parsed = T::try_accounts(
+        &pooler::ID,
+        &mut infos,
+        &[],
+        &mut bumps,
+        &mut BTreeSet::new(),
+    )?;
+    Ok((parsed, bumps))
+}
+
+/// Разбор контекста T: проверки ограничений без вызова обработчика.
+pub fn try_accounts<'a, T>(accounts: &'a mut [Account]) -> anchor_lang::Result<()>
+where
+    T: Bumps + Accounts<'a, <T as Bumps>::Bumps>,
+    T::Bumps: Default,
+{
+    parse::<T>(accounts).map(drop)
+}
+
+/// Данные Ed25519-инструкции: по записи на подписанта, ключи, нулевые
+/// подписи и сообщение внутри самой инструкции.
+fn ed25519_data(signers: &[Pubkey], message: &[u8]) -> Vec<u8> {
+    const OFFSETS: usize = 14;
+    let header = 2 + signers.len() * OFFSETS;
+    let message_offset = header + signers.len() * (32 + 64);
+    let mut data = vec![signers.len() as u8, 0];
+    for i in 0..signers.len() {
+        let key_offset = header + i * (32 + 64);
+        for value in [
+            key_offset + 32,
+            u16::MAX as usize,
+            key_offset,
+            u16::MAX as usize,
+            message_offset,
+            message.len(),
+            u16::MAX as usize,
+        ] {
+            data.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+    }
+    for signer in signers {
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0; 64]);
+    }
+    data.extend_from_slice(message);
+    data
+}
+
+/// Аккаунт instructions sysvar: Ed25519-инструкция с подписями signers над
+/// message, за ней текущая (нулевые подписи — см. tests/signing.rs).
+pub fn signed_instructions(signers: &[Pubkey], message: &SignedMessage) -> Account {
+    let ed25519 = ed25519_data(signers, &message.to_bytes().unwrap());
+    let sysvar = solana_instructions_sysvar::ID;
+    let mut data = solana_instructions_sysvar::construct_instructions_data(&[
+        BorrowedInstruction {
+            program_id: &ed25519_program::ID,
+            accounts: Vec::new(),
+            data: &ed25519,
+        },
+        BorrowedInstruction {
+            program_id: &pooler::ID,
+            accounts: vec![BorrowedAccountMeta {
+                pubkey: &sysvar,
+                is_signer: false,
+                is_writable: false,
+            }],
+            data: &[],
+        },
+    ]);
+    solana_instructions_sysvar::store_current_index_checked(&mut data, 1).unwrap();
+    Account {
+        owner: anchor_lang::solana_program::sysvar::ID,
+        data,
+        writable: false,
+        ..Account::wallet(sysvar, false)
+    }
+}
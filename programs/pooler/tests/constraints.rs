@@ -0,0 +1,101 @@
+//! Проверка игроков ограничениями контекстов (address = … @ ошибка).
+//!
+//! Контексты разбираются через Accounts::try_accounts на собранных вручную
+//! AccountInfo: подмена аккаунта должна падать ещё до обработчика, с
+//! ошибкой из `@`.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::system_program;
+use common::{config, finish_game, force_refund, game, join_game, try_accounts, Account};
+use pooler::{ErrorCode, GameState, GameStatus};
+
+#[test]
+fn join_game_checks_player2_by_constraint() {
+    let state = game(GameStatus::WaitingForPlayer2);
+    try_accounts::<pooler::JoinGame>(&mut join_game(&state, state.player2)).unwrap();
+
+    let stranger = Pubkey::new_unique();
+    assert_eq!(
+        try_accounts::<pooler::JoinGame>(&mut join_game(&state, stranger)).unwrap_err(),
+        ErrorCode::InvalidPlayer2.into()
+    );
+}
+
+#[test]
+fn finish_game_checks_players_by_constraint() {
+    let state = game(GameStatus::Active);
+    let (p1, p2) = (state.player1, state.player2);
+    try_accounts::<pooler::FinishGame>(&mut finish_game(&state, p1, p2)).unwrap();
+
+    let stranger = Pubkey::new_unique();
+    assert_eq!(
+        try_accounts::<pooler::FinishGame>(&mut finish_game(&state, stranger, p2)).unwrap_err(),
+        ErrorCode::InvalidPlayer1.into()
+    );
+    assert_eq!(
+        try_accounts::<pooler::FinishGame>(&mut finish_game(&state, p1, stranger)).unwrap_err(),
+        ErrorCode::InvalidPlayer2.into()
+    );
+    assert_eq!(
+        try_accounts::<pooler::FinishGame>(&mut finish_game(&state, p2, p1)).unwrap_err(),
+        ErrorCode::InvalidPlayer1.into()
+    );
+}
+
+#[test]
+fn force_refund_checks_players_by_constraint() {
+    let state = game(GameStatus::Active);
+    let (p1, p2) = (state.player1, state.player2);
+    try_accounts::<pooler::ForceRefund>(&mut force_refund(&state, p1, p2)).unwrap();
+
+    let stranger = Pubkey::new_unique();
+    assert_eq!(
+        try_accounts::<pooler::ForceRefund>(&mut force_refund(&state, stranger, p2)).unwrap_err(),
+        ErrorCode::InvalidPlayer1.into()
+    );
+    assert_eq!(
+        try_accounts::<pooler::ForceRefund>(&mut force_refund(&state, p1, stranger)).unwrap_err(),
+        ErrorCode::InvalidPlayer2.into()
+    );
+}
+
+fn make_move(state: &GameState, mover: Pubkey, opponent: Pubkey) -> Vec<Account> {
+    let mut opponent = Account::wallet(opponent, false);
+    opponent.writable = false;
+    let game = Pubkey::new_unique();
+    let mut accounts = vec![
+        Account::owned(game, state),
+        Account::wallet(mover, true),
+        opponent,
+        Account::program(system_program::ID),
+        Account::none(),
+        config(),
+    ];
+    // team, time_control, fee_route, fee_recipient, insurance_pool,
+    // client_telemetry, event_buffer, game_history, history_commitment,
+    // decay_sink.
+    accounts.extend((0..10).map(|_| Account::none()));
+    accounts
+}
+
+#[test]
+fn make_move_checks_opponent_by_constraint() {
+    let mut state = game(GameStatus::Active);
+    let (p1, p2) = (state.player1, state.player2);
+    try_accounts::<pooler::MakeMove>(&mut make_move(&state, p1, p2)).unwrap();
+
+    let stranger = Pubkey::new_unique();
+    assert_eq!(
+        try_accounts::<pooler::MakeMove>(&mut make_move(&state, p1, stranger)).unwrap_err(),
+        ErrorCode::InvalidPlayer.into()
+    );
+    // Соперник — сторона не на ходу: на ходу player2, значит соперник player1.
+    state.current_turn = 2;
+    assert_eq!(
+        try_accounts::<pooler::MakeMove>(&mut make_move(&state, p2, p2)).unwrap_err(),
+        ErrorCode::InvalidPlayer.into()
+    );
+    try_accounts::<pooler::MakeMove>(&mut make_move(&state, p2, p1)).unwrap();
+}
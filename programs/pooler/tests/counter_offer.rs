@@ -0,0 +1,139 @@
+//! Встречное предложение ставки: accept_counter приводит депозит player1
+//! (и резерв автовзятий) к новой ставке.
+//!
+//! Обработчик вызывается напрямую: возврат при снижении ставки идёт прямым
+//! изменением балансов. Доплата (CPI в системную программу), counter_offer
+//! (init) и закрытие PDA проверяются в LiteSVM (tests/counter_offer.rs).
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey, Rent};
+use common::{config, config_with, game, install_sysvars, parse, pda, Account};
+use pooler::{
+    AcceptCounter, CounterProposal, ErrorCode, GameState, GameStatus, Lobby, LobbyEntry,
+    RuleVariant,
+};
+
+const STAKE: u64 = 1_000_000;
+const NEW_STAKE: u64 = 400_000;
+const NEW_MOVE_FEE: u64 = 1_000;
+
+/// Игра в лобби со ставкой STAKE, автовзятием до 2 (резерв = STAKE) и
+/// встречным предложением NEW_STAKE.
+fn waiting() -> GameState {
+    let mut state = game(GameStatus::WaitingForPlayer2);
+    state.stake_lamports = STAKE;
+    state.move_fee_lamports = 10_000;
+    state.pot_lamports = STAKE;
+    state.player1_deposit = STAKE;
+    state.p1_auto_take_up_to = 2;
+    state.p1_take_reserve = STAKE;
+    state.counter_pending = true;
+    state
+}
+
+/// Аккаунты accept_counter; на аккаунте игры — рента, банк и резерв.
+fn accounts(state: &GameState, signer: Pubkey) -> Vec<Account> {
+    let game_key = Pubkey::new_unique();
+    let mut game = Account::owned(game_key, state);
+    game.lamports = Rent::default().minimum_balance(game.data.len())
+        + state.pot_lamports
+        + state.p1_take_reserve;
+    let (proposal, bump) = pda(&[b"counter_proposal", game_key.as_ref()]);
+    let (lobby, lobby_bump) = pda(&[b"lobby", &[0]]);
+    vec![
+        game,
+        Account::owned(
+            proposal,
+            &CounterProposal {
+                game: game_key,
+                proposer: state.player2,
+                stake_lamports: NEW_STAKE,
+                move_fee_lamports: NEW_MOVE_FEE,
+                bump,
+            },
+        ),
+        Account::wallet(state.player2, false),
+        Account::wallet(signer, true),
+        Account::program(anchor_lang::system_program::ID),
+        config(),
+        Account::owned(
+            lobby,
+            &Lobby {
+                shard: 0,
+                entries: vec![LobbyEntry {
+                    game: game_key,
+                    stake_lamports: STAKE,
+                    rule_variant: RuleVariant::Standard,
+                    creator_rating: 0,
+                }],
+                bump: lobby_bump,
+            },
+        ),
+    ]
+}
+
+fn accept(accounts: &mut [Account]) -> anchor_lang::Result<(GameState, Lobby)> {
+    install_sysvars();
+    let (mut parsed, bumps) = parse::<AcceptCounter>(accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::accept_counter(ctx)?;
+    let lobby = (***parsed.lobby.as_ref().unwrap()).clone();
+    Ok(((*parsed.game).clone(), lobby))
+}
+
+#[test]
+fn accepted_decrease_refunds_player1() {
+    let state = waiting();
+    let mut accounts = accounts(&state, state.player1);
+    let before: Vec<u64> = accounts.iter().map(|a| a.lamports).collect();
+
+    let (accepted, lobby) = accept(&mut accounts).unwrap();
+    assert_eq!(accepted.stake_lamports, NEW_STAKE);
+    assert_eq!(accepted.move_fee_lamports, NEW_MOVE_FEE);
+    assert_eq!(accepted.pot_lamports, NEW_STAKE);
+    assert_eq!(accepted.player1_deposit, NEW_STAKE);
+    assert_eq!(accepted.p1_take_reserve, NEW_STAKE);
+    assert!(!accepted.counter_pending);
+    assert_eq!(accepted.status, GameStatus::WaitingForPlayer2);
+    assert_eq!(lobby.entries[0].stake_lamports, NEW_STAKE);
+
+    // Разница ставки и резерва вернулась player1 из аккаунта игры.
+    let refund = 2 * (STAKE - NEW_STAKE);
+    assert_eq!(accounts[0].lamports, before[0] - refund);
+    assert_eq!(accounts[3].lamports, before[3] + refund);
+}
+
+#[test]
+fn accept_needs_pending_counter_and_player1() {
+    let state = waiting();
+    assert_eq!(
+        accept(&mut accounts(&state, Pubkey::new_unique())).err(),
+        Some(anchor_lang::error::ErrorCode::ConstraintAddress.into())
+    );
+
+    let mut idle = waiting();
+    idle.counter_pending = false;
+    assert_eq!(
+        accept(&mut accounts(&idle, idle.player1)).err(),
+        Some(ErrorCode::NoCounterOffer.into())
+    );
+
+    let mut joined = waiting();
+    joined.status = GameStatus::Active;
+    assert_eq!(
+        accept(&mut accounts(&joined, joined.player1)).err(),
+        Some(ErrorCode::GameNotWaitingForPlayer2.into())
+    );
+}
+
+#[test]
+fn accept_is_blocked_while_paused() {
+    let state = waiting();
+    let mut accounts = accounts(&state, state.player1);
+    accounts[5] = config_with(|config| config.paused = true);
+    assert_eq!(
+        accept(&mut accounts).err(),
+        Some(ErrorCode::ProgramPaused.into())
+    );
+}
@@ -0,0 +1,160 @@
+//! Матрица CPI: выплаты и заявки на банк выполняются только на верхнем
+//! уровне транзакции, создание игры, вход, ход и админские инструкции
+//! открыты для CPI.
+//!
+//! Вызов через программу-помощник (cpi_caller::try_finish_game) здесь
+//! моделируется высотой стека: при CPI get_stack_height больше
+//! TRANSACTION_LEVEL_STACK_HEIGHT, и обработчик видит именно это.
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey};
+use anchor_lang::solana_program::instruction::TRANSACTION_LEVEL_STACK_HEIGHT;
+use anchor_lang::{system_program, Discriminator};
+use common::{
+    config, event_cpi, finish_game, force_refund, game, join_game, no_splits, parse,
+    set_stack_height, Account,
+};
+use pooler::{
+    cpi_policy, instruction, require_cpi_policy, CpiPolicy, ErrorCode, FinishGame, ForceRefund,
+    GameStatus, JoinGame, ManualRefundOneSigner, CPI_MATRIX,
+};
+
+/// Высота стека при вызове из другой программы.
+const CPI_STACK_HEIGHT: usize = TRANSACTION_LEVEL_STACK_HEIGHT + 1;
+
+const TOP_LEVEL_ONLY: [&[u8]; 6] = [
+    instruction::FinishGame::DISCRIMINATOR,
+    instruction::ForceRefund::DISCRIMINATOR,
+    instruction::ManualRefund::DISCRIMINATOR,
+    instruction::ClaimWin::DISCRIMINATOR,
+    instruction::ClaimPotUnstake::DISCRIMINATOR,
+    instruction::ClaimTeamShare::DISCRIMINATOR,
+];
+
+const COMPOSABLE: [&[u8]; 3] = [
+    instruction::InitGame::DISCRIMINATOR,
+    instruction::JoinGame::DISCRIMINATOR,
+    instruction::MakeMove::DISCRIMINATOR,
+];
+
+/// Админские инструкции: администратор-PDA подписывает их через CPI.
+const ADMIN: [&[u8]; 15] = [
+    instruction::UpdateConfig::DISCRIMINATOR,
+    instruction::ProposeAdmin::DISCRIMINATOR,
+    instruction::AcceptAdmin::DISCRIMINATOR,
+    instruction::RenounceAdmin::DISCRIMINATOR,
+    instruction::AddBan::DISCRIMINATOR,
+    instruction::RemoveBan::DISCRIMINATOR,
+    instruction::AddArbiter::DISCRIMINATOR,
+    instruction::RemoveArbiter::DISCRIMINATOR,
+    instruction::CloseSeason::DISCRIMINATOR,
+    instruction::WithdrawBotVault::DISCRIMINATOR,
+    instruction::Escheat::DISCRIMINATOR,
+    instruction::InitInsurancePool::DISCRIMINATOR,
+    instruction::Compensate::DISCRIMINATOR,
+    instruction::InitFeeVault::DISCRIMINATOR,
+    instruction::WithdrawProtocolFees::DISCRIMINATOR,
+];
+
+#[test]
+fn matrix_blocks_payouts_and_allows_composable_instructions() {
+    for discriminator in TOP_LEVEL_ONLY {
+        assert_eq!(cpi_policy(discriminator), CpiPolicy::TopLevelOnly);
+    }
+    for discriminator in COMPOSABLE.into_iter().chain(ADMIN) {
+        assert_eq!(cpi_policy(discriminator), CpiPolicy::Allowed);
+    }
+    // Каждая инструкция записана в матрице один раз, лишних нет.
+    assert_eq!(
+        CPI_MATRIX.len(),
+        TOP_LEVEL_ONLY.len() + COMPOSABLE.len() + ADMIN.len()
+    );
+    for (i, (discriminator, _)) in CPI_MATRIX.iter().enumerate() {
+        assert!(CPI_MATRIX[..i].iter().all(|(d, _)| d != discriminator));
+    }
+}
+
+#[test]
+fn blocked_instructions_fail_only_under_cpi() {
+    common::install_sysvars();
+    for discriminator in TOP_LEVEL_ONLY {
+        set_stack_height(TRANSACTION_LEVEL_STACK_HEIGHT);
+        require_cpi_policy(discriminator).unwrap();
+        set_stack_height(CPI_STACK_HEIGHT);
+        assert_eq!(
+            require_cpi_policy(discriminator).unwrap_err(),
+            ErrorCode::CpiNotAllowed.into()
+        );
+    }
+    for discriminator in COMPOSABLE.into_iter().chain(ADMIN) {
+        require_cpi_policy(discriminator).unwrap();
+    }
+}
+
+#[test]
+fn finish_game_rejects_cpi() {
+    let state = game(GameStatus::Active);
+    let mut accounts = finish_game(&state, state.player1, state.player2);
+    let (mut parsed, bumps) = parse::<FinishGame>(&mut accounts).unwrap();
+    set_stack_height(CPI_STACK_HEIGHT);
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::finish_game(ctx, state.player1, 1).unwrap_err(),
+        ErrorCode::CpiNotAllowed.into()
+    );
+}
+
+#[test]
+fn force_refund_rejects_cpi() {
+    let state = game(GameStatus::Active);
+    let mut accounts = force_refund(&state, state.player1, state.player2);
+    let (mut parsed, bumps) = parse::<ForceRefund>(&mut accounts).unwrap();
+    set_stack_height(CPI_STACK_HEIGHT);
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::force_refund(ctx).unwrap_err(),
+        ErrorCode::CpiNotAllowed.into()
+    );
+}
+
+#[test]
+fn manual_refund_rejects_cpi() {
+    let state = game(GameStatus::Active);
+    let game_key = Pubkey::new_unique();
+    let mut accounts = vec![
+        Account::owned(game_key, &state),
+        Account::wallet(state.player1, false),
+        Account::wallet(state.player2, false),
+        Account::wallet(state.player1, true),
+        Account::program(system_program::ID),
+    ];
+    // bot_vault, escrow_agent, team.
+    accounts.extend((0..3).map(|_| Account::none()));
+    accounts.push(no_splits(&state.player1));
+    accounts.push(no_splits(&state.player2));
+    // event_buffer, config, treasury.
+    accounts.extend([Account::none(), config(), Account::none()]);
+    accounts.extend(event_cpi());
+    let (mut parsed, bumps) = parse::<ManualRefundOneSigner>(&mut accounts).unwrap();
+    set_stack_height(CPI_STACK_HEIGHT);
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::manual_refund(ctx).unwrap_err(),
+        ErrorCode::CpiNotAllowed.into()
+    );
+}
+
+#[test]
+fn join_game_runs_under_cpi() {
+    // Вход открыт для CPI: отказ приходит от проверок самой инструкции.
+    let state = game(GameStatus::Active);
+    let mut accounts = join_game(&state, state.player2);
+    let (mut parsed, bumps) = parse::<JoinGame>(&mut accounts).unwrap();
+    set_stack_height(CPI_STACK_HEIGHT);
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::join_game(ctx, Vec::new(), None).unwrap_err(),
+        ErrorCode::GameNotWaitingForPlayer2.into()
+    );
+}
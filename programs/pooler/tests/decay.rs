@@ -0,0 +1,183 @@
+//! Тающий банк: в игре с decay_bps каждый ход после бесплатного окна срезает
+//! долю банка на incinerator (или в treasury по конфигу), банк уменьшается, а
+//! decayed_lamports растёт ровно на срезанное. Возвраты делят остаток:
+//! вклады за вычетом пропорциональной доли среза дают в сумме ровно банк.
+//!
+//! Ходы без комиссии, поэтому обработчик вызывается напрямую (без CPI).
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey, Rent};
+use anchor_lang::system_program;
+use common::{config_with, game, install_sysvars, parse, Account};
+use pooler::{
+    pot_decay, start_board, Config, ErrorCode, GameState, GameStatus, MakeMove, Remainder,
+    RuleVariant, INCINERATOR,
+};
+
+const STAKE: u64 = 123_456_789;
+/// 2,5% банка за ход.
+const DECAY_BPS: u16 = 250;
+const MOVES: usize = 10;
+
+fn decaying_game() -> GameState {
+    let mut state = game(GameStatus::Active);
+    state
+        .set_board_points(&start_board(RuleVariant::Standard, 0).unwrap())
+        .unwrap();
+    state.player1_deposit = STAKE;
+    state.player2_deposit = STAKE;
+    state.pot_lamports = 2 * STAKE;
+    state.decay_bps = DECAY_BPS;
+    state
+}
+
+/// make_move стороны current_turn без бесплатных ходов; sink = None —
+/// decay_sink не передан. Возвращает состояние, баланс игры сверх ренты и
+/// баланс sink.
+fn make_move(
+    state: &GameState,
+    edit: impl FnOnce(&mut Config),
+    sink: Option<(Pubkey, u64)>,
+) -> anchor_lang::Result<(GameState, u64, u64)> {
+    install_sysvars();
+    let game_key = Pubkey::new_unique();
+    let mut game_account = Account::owned(game_key, state);
+    let rent = Rent::default().minimum_balance(game_account.data.len());
+    game_account.lamports = rent + state.pot_lamports;
+    let mut accounts = vec![
+        game_account,
+        Account::wallet(state.player_key(state.current_turn), true),
+        Account::wallet(state.player_key(3 - state.current_turn), false),
+        Account::program(system_program::ID),
+        Account::none(),
+        config_with(|config| {
+            config.free_moves = 0;
+            edit(config);
+        }),
+    ];
+    // team, time_control, fee_route, fee_recipient, insurance_pool,
+    // client_telemetry, event_buffer, game_history, history_commitment.
+    accounts.extend((0..9).map(|_| Account::none()));
+    accounts.push(match sink {
+        Some((key, lamports)) => Account {
+            lamports,
+            ..Account::wallet(key, false)
+        },
+        None => Account::none(),
+    });
+    let (mut parsed, bumps) = parse::<MakeMove>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::make_move(ctx, state.board_points().unwrap(), [3, 1], [0; 32], None)?;
+    let next = (*parsed.game).clone();
+    drop(parsed);
+    Ok((next, accounts[0].lamports - rent, accounts[15].lamports))
+}
+
+#[test]
+fn ten_moves_decay_the_pot_to_the_lamport() {
+    let mut state = decaying_game();
+    let mut incinerator = 0;
+    let mut decays = Vec::new();
+    for _ in 0..MOVES {
+        let pot = state.pot_lamports;
+        let decay = pot * DECAY_BPS as u64 / 10_000;
+        assert_eq!(pot_decay(pot, DECAY_BPS), decay);
+        let (next, held, sink_lamports) =
+            make_move(&state, |_| (), Some((INCINERATOR, incinerator))).unwrap();
+
+        // Срез уходит с аккаунта игры на incinerator, банк уменьшается на него же.
+        assert_eq!(next.pot_lamports, pot - decay);
+        assert_eq!(next.decayed_lamports, state.decayed_lamports + decay);
+        assert_eq!(sink_lamports, incinerator + decay);
+        assert_eq!(held, pot - decay);
+        incinerator = sink_lamports;
+        decays.push(decay);
+        state = next;
+    }
+
+    // Каждый срез округлён вниз от уменьшившегося банка.
+    assert_eq!(
+        decays,
+        [
+            6_172_839, 6_018_518, 5_868_055, 5_721_354, 5_578_320, 5_438_862, 5_302_890, 5_170_318,
+            5_041_060, 4_915_034
+        ]
+    );
+    assert_eq!(state.move_index, MOVES as u64);
+    assert_eq!(state.pot_lamports, 191_686_328);
+    assert_eq!(state.decayed_lamports, 55_227_250);
+    assert_eq!(incinerator, state.decayed_lamports);
+    assert_eq!(
+        state.player1_deposit + state.player2_deposit - state.decayed_lamports,
+        state.pot_lamports
+    );
+
+    // Возврат делит остаток банка: срез поровну на равные вклады.
+    let (p1, p2, _) = state.refund_totals(Remainder::First).unwrap();
+    assert_eq!((p1, p2), (95_843_164, 95_843_164));
+    assert_eq!(p1 + p2, state.pot_lamports);
+}
+
+#[test]
+fn refunds_split_the_decay_by_contribution() {
+    let mut state = decaying_game();
+    state.player2_deposit = STAKE / 3;
+    state.player2_fees_paid = 7;
+    state.pot_lamports = STAKE + STAKE / 3 + 7;
+    for _ in 0..MOVES {
+        let decayed = state.decayed_lamports;
+        state = make_move(&state, |_| (), Some((INCINERATOR, 0))).unwrap().0;
+        assert!(state.decayed_lamports > decayed);
+        let (p1, p2, _) = state.refund_totals(Remainder::First).unwrap();
+        assert_eq!(p1 + p2, state.pot_lamports);
+        assert!(p1 > 2 * p2);
+    }
+}
+
+#[test]
+fn decay_goes_to_the_configured_destination() {
+    let state = decaying_game();
+    let treasury = Pubkey::new_unique();
+    let to_treasury = |config: &mut Config| {
+        config.treasury = treasury;
+        config.decay_to_treasury = true;
+    };
+
+    assert_eq!(
+        make_move(&state, |_| (), None).err(),
+        Some(ErrorCode::InvalidDecaySink.into())
+    );
+    assert_eq!(
+        make_move(&state, |_| (), Some((Pubkey::new_unique(), 0))).err(),
+        Some(ErrorCode::InvalidDecaySink.into())
+    );
+    assert_eq!(
+        make_move(&state, to_treasury, Some((INCINERATOR, 0))).err(),
+        Some(ErrorCode::InvalidDecaySink.into())
+    );
+    let (next, _, credited) = make_move(&state, to_treasury, Some((treasury, 0))).unwrap();
+    assert_eq!(credited, next.decayed_lamports);
+    assert_eq!(credited, pot_decay(2 * STAKE, DECAY_BPS));
+
+    // Без казны флаг ничего не меняет: срез сжигается.
+    let no_treasury = |config: &mut Config| config.decay_to_treasury = true;
+    make_move(&state, no_treasury, Some((INCINERATOR, 0))).unwrap();
+}
+
+#[test]
+fn free_moves_and_dust_pots_do_not_decay() {
+    let state = decaying_game();
+    let (next, _, _) = make_move(&state, |config| config.free_moves = 255, None).unwrap();
+    assert_eq!(next.pot_lamports, state.pot_lamports);
+    assert_eq!(next.decayed_lamports, 0);
+
+    // Срез округляется вниз: банку меньше 10_000 / DECAY_BPS lamports таять нечего.
+    let mut dust = decaying_game();
+    dust.pot_lamports = 39;
+    dust.player1_deposit = 20;
+    dust.player2_deposit = 19;
+    let (next, _, _) = make_move(&dust, |_| (), None).unwrap();
+    assert_eq!(next.pot_lamports, 39);
+    assert_eq!(next.decayed_lamports, 0);
+}
@@ -0,0 +1,71 @@
+//! escheat: резервы автовзятий возвращаются игрокам, а в казну уходят только
+//! банк и рента; игра со сведёнными, но не рассчитанными пари зрителей не
+//! закрывается.
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey, Rent};
+use common::{config_with, game, install_sysvars, parse, set_slot, Account};
+use pooler::{ErrorCode, Escheat, GameState, GameStatus, DEFAULT_ESCHEAT_AFTER_SLOTS};
+
+const POT: u64 = 10_000;
+const P1_RESERVE: u64 = 300;
+const P2_RESERVE: u64 = 500;
+
+fn abandoned_game() -> GameState {
+    let mut state = game(GameStatus::Finished);
+    state.pot_lamports = POT;
+    state.p1_take_reserve = P1_RESERVE;
+    state.p2_take_reserve = P2_RESERVE;
+    state
+}
+
+/// escheat в слоте, где порог простоя пройден; возвращает балансы
+/// (игра, player1, player2) после обработчика и ренту игры. Всё, что осталось
+/// на аккаунте игры, close = treasury переводит в казну.
+fn escheat(state: &GameState) -> anchor_lang::Result<([u64; 3], u64)> {
+    install_sysvars();
+    set_slot(state.last_activity_slot + DEFAULT_ESCHEAT_AFTER_SLOTS);
+    let (admin, treasury) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let game_key = Pubkey::new_unique();
+    let mut game_account = Account::owned(game_key, state);
+    let rent = Rent::default().minimum_balance(game_account.data.len());
+    game_account.lamports = rent + POT + P1_RESERVE + P2_RESERVE;
+    let mut accounts = [
+        config_with(|config| {
+            config.admin = admin;
+            config.treasury = treasury;
+        }),
+        game_account,
+        Account::none(),
+        Account::wallet(treasury, false),
+        Account::wallet(state.player1, false),
+        Account::wallet(state.player2, false),
+        Account::wallet(admin, true),
+    ];
+    let (mut parsed, bumps) = parse::<Escheat>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::escheat(ctx)?;
+    let balances = [1, 4, 5].map(|i| accounts[i].lamports);
+    Ok((balances, rent))
+}
+
+#[test]
+fn take_reserves_go_back_to_the_players() {
+    let wallet = Account::wallet(Pubkey::new_unique(), false).lamports;
+    let ([game, player1, player2], rent) = escheat(&abandoned_game()).unwrap();
+    // В казну уйдут только банк и рента.
+    assert_eq!(game, POT + rent);
+    assert_eq!(player1, wallet + P1_RESERVE);
+    assert_eq!(player2, wallet + P2_RESERVE);
+}
+
+#[test]
+fn unsettled_side_bets_block_escheat() {
+    let mut state = abandoned_game();
+    state.unsettled_side_bets = 1;
+    assert_eq!(
+        escheat(&state).err(),
+        Some(ErrorCode::GameNotEscheatable.into())
+    );
+}
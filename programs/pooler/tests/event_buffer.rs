@@ -0,0 +1,121 @@
+//! Кольцевой буфер событий: после переполнения в нём остаются последние
+//! EVENT_BUFFER_LEN событий, каждое в ячейке (seq - 1) % EVENT_BUFFER_LEN,
+//! и по номерам они восстанавливаются в порядке записи. Без переданного
+//! буфера record_event ничего не делает.
+
+mod common;
+
+use anchor_lang::prelude::{Account as AnchorAccount, Pubkey};
+use common::{infos, install_sysvars, pda, Account, SLOT};
+use pooler::{record_event, BufferedEvent, BufferedEventKind, EventBuffer, EVENT_BUFFER_LEN};
+
+fn empty_buffer(game: Pubkey) -> EventBuffer {
+    EventBuffer {
+        game,
+        payer: Pubkey::new_unique(),
+        next_seq: 1,
+        events: [BufferedEvent::default(); EVENT_BUFFER_LEN],
+        bump: pda(&[b"event_buffer", game.as_ref()]).1,
+    }
+}
+
+/// События буфера в порядке номеров, без пустых ячеек.
+fn in_order(buffer: &EventBuffer) -> Vec<BufferedEvent> {
+    let mut events: Vec<_> = buffer
+        .events
+        .iter()
+        .filter(|e| e.seq != 0)
+        .copied()
+        .collect();
+    events.sort_by_key(|e| e.seq);
+    events
+}
+
+#[test]
+fn partly_filled_buffer_keeps_every_event() {
+    let mut buffer = empty_buffer(Pubkey::new_unique());
+    for value in 1..=5 {
+        buffer.push(
+            value * 10,
+            BufferedEventKind::Move,
+            Pubkey::default(),
+            value,
+        );
+    }
+    assert_eq!(buffer.next_seq, 6);
+    let events = in_order(&buffer);
+    assert_eq!(events.len(), 5);
+    for (event, seq) in events.iter().zip(1..) {
+        assert_eq!(event.seq, seq);
+        assert_eq!(event.value, seq);
+        assert_eq!(event.slot, seq * 10);
+    }
+    assert!(buffer.events[5..]
+        .iter()
+        .all(|e| e.kind == BufferedEventKind::None));
+}
+
+#[test]
+fn wraparound_keeps_the_last_events_in_order() {
+    let mut buffer = empty_buffer(Pubkey::new_unique());
+    let total = 2 * EVENT_BUFFER_LEN as u64 + 5;
+    for value in 1..=total {
+        buffer.push(value, BufferedEventKind::Move, Pubkey::default(), value);
+
+        // После каждой записи в буфере — ровно последние события подряд.
+        let events = in_order(&buffer);
+        let oldest = value.saturating_sub(EVENT_BUFFER_LEN as u64) + 1;
+        assert_eq!(events.len() as u64, value - oldest + 1);
+        for (event, seq) in events.iter().zip(oldest..) {
+            assert_eq!(event.seq, seq);
+            assert_eq!(event.value, seq);
+            assert_eq!(
+                buffer.events[((seq - 1) % EVENT_BUFFER_LEN as u64) as usize],
+                *event
+            );
+        }
+    }
+    assert_eq!(buffer.next_seq, total + 1);
+
+    // Поллер, прочитавший всё до seq = last, получает новые события по
+    // номерам, даже если запись прошла через границу кольца.
+    let last = total - 3;
+    let fresh: Vec<_> = in_order(&buffer)
+        .into_iter()
+        .filter(|e| e.seq > last)
+        .map(|e| e.seq)
+        .collect();
+    assert_eq!(fresh, vec![total - 2, total - 1, total]);
+}
+
+#[test]
+fn record_event_writes_the_current_slot() {
+    install_sysvars();
+    let game = Pubkey::new_unique();
+    let (key, _) = pda(&[b"event_buffer", game.as_ref()]);
+    let mut accounts = [Account::owned(key, &empty_buffer(game))];
+    let infos = infos(&mut accounts);
+    let mut buffer = AnchorAccount::<EventBuffer>::try_from(&infos[0]).unwrap();
+    let actor = Pubkey::new_unique();
+
+    for value in 1..=EVENT_BUFFER_LEN as u64 + 1 {
+        record_event(Some(&mut buffer), BufferedEventKind::Move, actor, value).unwrap();
+    }
+    let events = in_order(&buffer);
+    assert_eq!(events.len(), EVENT_BUFFER_LEN);
+    assert_eq!(events[0].seq, 2);
+    assert_eq!(
+        events[EVENT_BUFFER_LEN - 1].seq,
+        EVENT_BUFFER_LEN as u64 + 1
+    );
+    assert!(events
+        .iter()
+        .all(|e| e.slot == SLOT && e.actor == actor && e.kind == BufferedEventKind::Move));
+    // Самое новое событие затёрло самое старое в первой ячейке.
+    assert_eq!(buffer.events[0].seq, EVENT_BUFFER_LEN as u64 + 1);
+}
+
+#[test]
+fn missing_buffer_is_skipped() {
+    record_event(None, BufferedEventKind::Finished, Pubkey::new_unique(), 1).unwrap();
+}
@@ -0,0 +1,95 @@
+//! finalize_claim по уже завершённой игре: залог заявки возвращается
+//! заявителю, только если победил он (или победителя нет), иначе уходит
+//! победителю — как в execute_resolution.
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey, Rent};
+use common::{config, event_cpi, game, no_splits, parse, pda, Account, SLOT};
+use pooler::{ErrorCode, FinalizeClaim, GameState, GameStatus, WinClaim, WIN_CLAIM_BOND_LAMPORTS};
+
+/// Завершённая игра с заявкой player1 на победу.
+fn finished(winner: impl FnOnce(&GameState) -> Pubkey) -> GameState {
+    let mut state = game(GameStatus::Finished);
+    state.win_claim_pending = true;
+    state.winner = winner(&state);
+    state
+}
+
+/// finalize_claim; winner — передавать ли аккаунт game.winner. Возвращает
+/// остаток заявки сверх ренты и балансы заявителя и победителя после
+/// обработчика; остаток и рента заявки при close уходят заявителю.
+fn finalize(state: &GameState, winner: bool) -> anchor_lang::Result<[u64; 3]> {
+    let game_account = Account::owned(Pubkey::new_unique(), state);
+    let (claim_key, bump) = pda(&[b"win_claim", game_account.key.as_ref()]);
+    let mut claim = Account::owned(
+        claim_key,
+        &WinClaim {
+            game: game_account.key,
+            claimant: state.player1,
+            final_board: [0; 24],
+            deadline_slot: SLOT - 1,
+            bond_lamports: WIN_CLAIM_BOND_LAMPORTS,
+            bump,
+        },
+    );
+    let rent = Rent::default().minimum_balance(claim.data.len());
+    claim.lamports = rent + WIN_CLAIM_BOND_LAMPORTS;
+    let mut accounts = vec![
+        game_account,
+        claim,
+        Account::wallet(state.player1, false),
+        config(),
+        // season, stats ×2, bot_vault, team.
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::wallet(Pubkey::new_unique(), true),
+    ];
+    // event_buffer, fee_vault, incinerator.
+    accounts.extend((0..3).map(|_| Account::none()));
+    accounts.push(if winner {
+        Account::wallet(state.winner, false)
+    } else {
+        Account::none()
+    });
+    accounts.push(no_splits(&state.player1));
+    accounts.extend(event_cpi());
+    let (mut parsed, bumps) = parse::<FinalizeClaim>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::finalize_claim(ctx)?;
+    Ok([
+        accounts[1].lamports - rent,
+        accounts[2].lamports,
+        accounts[13].lamports,
+    ])
+}
+
+#[test]
+fn bond_goes_to_the_winner_when_the_claimant_lost() {
+    let wallet = Account::wallet(Pubkey::new_unique(), false).lamports;
+    let state = finished(|state| state.player2);
+    let [bond, claimant, winner] = finalize(&state, true).unwrap();
+    assert_eq!(winner, wallet + WIN_CLAIM_BOND_LAMPORTS);
+    assert_eq!(claimant, wallet);
+    // На заявке остаётся только рента — её close вернёт заявителю.
+    assert_eq!(bond, 0);
+
+    assert_eq!(
+        finalize(&state, false).err(),
+        Some(ErrorCode::MissingWinnerAccount.into())
+    );
+}
+
+#[test]
+fn bond_stays_with_the_claimant_who_won_or_without_a_winner() {
+    for state in [
+        finished(|state| state.player1),
+        finished(|_| Pubkey::default()),
+    ] {
+        let [bond, _, _] = finalize(&state, false).unwrap();
+        assert_eq!(bond, WIN_CLAIM_BOND_LAMPORTS);
+    }
+}
@@ -0,0 +1,201 @@
+//! Причина завершения (GameState::finish_reason): запись на каждом
+//! завершающем пути, вес в рейтинге и перенос аккаунтов прошлых версий.
+//!
+//! Завершающие инструкции эмитят события через emit_cpi!, а это CPI, которое
+//! вне валидатора не выполнить; поэтому проверяются GameState::finish и
+//! соответствие причин, которые эти инструкции записывают.
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey, Rent};
+use anchor_lang::{system_program, Discriminator};
+use common::{game, install_sysvars, parse, Account, SLOT};
+use pooler::{
+    apply_rating_result, check_legacy_game_state, debit, migration_rent, ErrorCode, FinishReason,
+    GameState, GameStatus, MigrateGameState, PlayerStats, RefundReason, DEFAULT_RATING,
+};
+
+#[test]
+fn finish_records_the_reason_of_each_terminal_path() {
+    // finalize_claim, arbiter_resolve, finish_game, finalize_from_board,
+    // record_strike, drop_double, settle_game и три возврата.
+    let reasons = [
+        FinishReason::Claim,
+        FinishReason::Arbiter,
+        FinishReason::Win,
+        FinishReason::Board,
+        FinishReason::Strikes,
+        FinishReason::DoubleDropped,
+        FinishReason::Negotiated,
+        RefundReason::Timeout.into(),
+        RefundReason::Manual.into(),
+        RefundReason::MoveCap.into(),
+    ];
+    for reason in reasons {
+        let mut state = game(GameStatus::Active);
+        assert_eq!(state.finish_reason, FinishReason::NotFinished);
+        state.finish(reason, SLOT);
+        assert_eq!(state.status, GameStatus::Finished);
+        assert_eq!(state.finish_reason, reason);
+        assert_eq!(state.finished_slot, SLOT);
+    }
+}
+
+#[test]
+fn refund_reasons_map_to_finish_reasons() {
+    assert_eq!(
+        FinishReason::from(RefundReason::Timeout),
+        FinishReason::TimeoutRefund
+    );
+    assert_eq!(
+        FinishReason::from(RefundReason::Manual),
+        FinishReason::ManualRefund
+    );
+    assert_eq!(
+        FinishReason::from(RefundReason::MoveCap),
+        FinishReason::MoveCapRefund
+    );
+}
+
+#[test]
+fn existing_reasons_keep_their_event_indices() {
+    // Прошлые события GameFinished уже записаны с этими индексами.
+    assert_eq!(FinishReason::Win as u8, 0);
+    assert_eq!(FinishReason::Negotiated as u8, 6);
+    assert_eq!(FinishReason::default(), FinishReason::NotFinished);
+}
+
+fn stats() -> PlayerStats {
+    PlayerStats {
+        player: Pubkey::new_unique(),
+        rating: DEFAULT_RATING,
+        games_played: 0,
+        wins: 0,
+        losses: 0,
+        season: 0,
+        season_wins: 0,
+        season_losses: 0,
+        bump: 0,
+    }
+}
+
+fn rating_gain(reason: FinishReason) -> u32 {
+    let (mut winner, mut loser) = (stats(), stats());
+    apply_rating_result(&mut winner, &mut loser, 0, 0, reason.rating_weight_pct()).unwrap();
+    assert_eq!(
+        winner.rating - DEFAULT_RATING,
+        DEFAULT_RATING - loser.rating
+    );
+    assert_eq!((winner.wins, loser.losses), (1, 1));
+    winner.rating - DEFAULT_RATING
+}
+
+#[test]
+fn strikes_forfeit_moves_ratings_half_as_much() {
+    assert_eq!(rating_gain(FinishReason::Win), 16);
+    assert_eq!(rating_gain(FinishReason::Board), 16);
+    assert_eq!(rating_gain(FinishReason::Strikes), 8);
+}
+
+#[test]
+fn refunds_cancels_and_settlements_are_not_rated() {
+    for reason in [
+        FinishReason::NotFinished,
+        FinishReason::Negotiated,
+        FinishReason::TimeoutRefund,
+        FinishReason::ManualRefund,
+        FinishReason::MoveCapRefund,
+        FinishReason::Cancelled,
+    ] {
+        assert_eq!(reason.rating_weight_pct(), 0, "{:?}", reason);
+    }
+}
+
+fn legacy_game(max_size: usize) -> Account {
+    let mut data = GameState::DISCRIMINATOR.to_vec();
+    data.resize(8 + max_size, 0);
+    Account {
+        owner: pooler::ID,
+        data,
+        ..Account::wallet(Pubkey::new_unique(), false)
+    }
+}
+
+#[test]
+fn only_previous_versions_are_migrated() {
+    assert_eq!(
+        check_legacy_game_state(&legacy_game(GameState::BASELINE_MAX_SIZE).info()).unwrap(),
+        0
+    );
+    assert_eq!(
+        check_legacy_game_state(&legacy_game(GameState::LEGACY_MAX_SIZE).info()).unwrap(),
+        1
+    );
+    assert_eq!(
+        check_legacy_game_state(&legacy_game(GameState::V2_MAX_SIZE).info()).unwrap(),
+        2
+    );
+
+    let current = game(GameStatus::Active);
+    let mut current = Account::owned(Pubkey::new_unique(), &current);
+    current.data.resize(8 + GameState::MAX_SIZE, 0);
+    assert_eq!(
+        check_legacy_game_state(&current.info()).unwrap_err(),
+        ErrorCode::NotLegacyGameState.into()
+    );
+
+    let mut foreign = legacy_game(GameState::LEGACY_MAX_SIZE);
+    foreign.data[..8].copy_from_slice(pooler::Config::DISCRIMINATOR);
+    assert_eq!(
+        check_legacy_game_state(&foreign.info()).unwrap_err(),
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into()
+    );
+}
+
+#[test]
+fn migrate_game_state_rejects_migrated_accounts() {
+    let current = game(GameStatus::Active);
+    let mut current = Account::owned(Pubkey::new_unique(), &current);
+    current.data.resize(8 + GameState::MAX_SIZE, 0);
+    let mut accounts = vec![
+        current,
+        Account::wallet(Pubkey::new_unique(), true),
+        Account::program(system_program::ID),
+    ];
+    let (mut parsed, bumps) = parse::<MigrateGameState>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::migrate_game_state(ctx).unwrap_err(),
+        ErrorCode::NotLegacyGameState.into()
+    );
+}
+
+#[test]
+fn migration_charges_rent_for_the_new_bytes_of_a_funded_game() {
+    install_sysvars();
+    let rent = Rent::default();
+    let pot = 3 * rent.minimum_balance(8 + GameState::MAX_SIZE);
+    for old_size in [
+        GameState::BASELINE_MAX_SIZE,
+        GameState::LEGACY_MAX_SIZE,
+        GameState::V2_MAX_SIZE,
+    ] {
+        let (old_len, new_len) = (8 + old_size, 8 + GameState::MAX_SIZE);
+        // Банк больше ренты нового размера: прежний расчёт от баланса не
+        // доплатил бы ничего.
+        let mut funded = legacy_game(old_size);
+        funded.lamports = rent.minimum_balance(old_len) + pot;
+        let charge = migration_rent(&rent, old_len, new_len);
+        assert!(charge > 0);
+        assert_eq!(
+            charge,
+            rent.minimum_balance(new_len) - rent.minimum_balance(old_len)
+        );
+
+        // После переноса банк выплачивается целиком, рента остаётся.
+        funded.lamports += charge;
+        funded.data.resize(new_len, 0);
+        debit(&funded.info(), pot).unwrap();
+        assert_eq!(funded.lamports, rent.minimum_balance(new_len));
+    }
+}
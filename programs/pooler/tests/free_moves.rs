@@ -0,0 +1,89 @@
+//! Бесплатное окно: первые free_moves ходов игры без комиссии, эскалация
+//! отсчитывается от конца окна, так что первый платный ход стоит базовую
+//! комиссию. Окно игры (options.free_moves) заменяет config.free_moves,
+//! игра без своего окна берёт его из конфига.
+
+mod common;
+
+use anchor_lang::prelude::{Account as AnchorAccount, AnchorDeserialize, Pubkey};
+use common::{game, infos, install_sysvars, pda, Account};
+use pooler::{
+    charge_time_control, Config, GamePreset, GameState, GameStatus, TimeControl, TimeControlParams,
+    CASUAL_TIME_CONTROL,
+};
+
+const MOVE_FEE: u64 = 1_000;
+
+/// +10% базовой комиссии каждые 2 хода.
+const ESCALATION: TimeControlParams = TimeControlParams {
+    fee_escalation_bps: 1_000,
+    fee_escalation_every: 2,
+    ..CASUAL_TIME_CONTROL
+};
+
+fn config(free_moves: u16) -> Config {
+    let mut config = Config::deserialize(&mut &[0u8; 1024][..]).unwrap();
+    config.free_moves = free_moves;
+    config
+}
+
+fn custom_game(free_moves: Option<u16>) -> GameState {
+    let mut state = game(GameStatus::Active);
+    state.preset = GamePreset::Custom;
+    state.move_fee_lamports = MOVE_FEE;
+    state.own_free_moves = free_moves.is_some();
+    state.free_moves = free_moves.unwrap_or(0);
+    state
+}
+
+/// Комиссии ходов 0..moves по charge_time_control с окном игры.
+fn fees(state: &GameState, config: &Config, moves: u64) -> Vec<u64> {
+    install_sysvars();
+    let key = Pubkey::new_unique();
+    let mut accounts = [Account::owned(
+        pda(&[b"time_control", key.as_ref()]).0,
+        &TimeControl {
+            game: key,
+            params: ESCALATION,
+            p1_bank_slots: 0,
+            p2_bank_slots: 0,
+            bump: 0,
+        },
+    )];
+    let infos = infos(&mut accounts);
+    let mut time_control = AnchorAccount::<TimeControl>::try_from(&infos[0]).unwrap();
+    let mut state = state.clone();
+    (0..moves)
+        .map(|move_index| {
+            state.move_index = move_index;
+            let free_moves = state.free_moves(config);
+            charge_time_control(&state, Some(&mut time_control), 1, free_moves)
+                .unwrap()
+                .1
+        })
+        .collect()
+}
+
+#[test]
+fn escalation_starts_at_the_end_of_the_free_window() {
+    let fees = fees(&custom_game(Some(4)), &config(0), 10);
+    assert_eq!(fees, [0, 0, 0, 0, 1_000, 1_000, 1_100, 1_100, 1_200, 1_200]);
+}
+
+#[test]
+fn game_window_overrides_the_config() {
+    // Своего окна нет — действует config.free_moves.
+    assert_eq!(
+        fees(&custom_game(None), &config(2), 4),
+        [0, 0, 1_000, 1_000]
+    );
+    // Some(0) отключает бесплатные ходы даже при окне в конфиге.
+    assert_eq!(
+        fees(&custom_game(Some(0)), &config(10), 3),
+        [1_000, 1_000, 1_100]
+    );
+    assert_eq!(
+        fees(&custom_game(Some(3)), &config(10), 4),
+        [0, 0, 0, 1_000]
+    );
+}
@@ -0,0 +1,98 @@
+//! Счётчик игр создателя: GameCounter выдаёт id со старшим битом, а явный
+//! game_id с этим битом init_game отклоняет.
+//!
+//! Обработчик init_game вызывается напрямую и останавливается на проверке
+//! id раньше любых CPI; выдача id в транзакциях проверяется в LiteSVM
+//! (tests/game_ids.rs).
+
+mod common;
+
+use anchor_lang::prelude::{Account as AnchorAccount, Context, Program, Pubkey, Signer};
+use anchor_lang::system_program;
+use common::{config, game, infos, open_games, Account};
+use pooler::{
+    CreateCooldown, ErrorCode, GameCounter, GameOptions, GameStatus, InitGame, InitGameBumps,
+    AUTO_GAME_ID_FLAG,
+};
+
+#[test]
+fn counter_hands_out_flagged_ids_in_order() {
+    let mut counter = GameCounter {
+        next_id: 0,
+        bump: 0,
+    };
+    assert_eq!(counter.assign().unwrap(), AUTO_GAME_ID_FLAG);
+    assert_eq!(counter.assign().unwrap(), AUTO_GAME_ID_FLAG | 1);
+    assert_eq!(counter.next_id, 2);
+
+    // Последний номер нижней половины выдаётся, дальше — переполнение.
+    counter.next_id = AUTO_GAME_ID_FLAG - 1;
+    assert_eq!(counter.assign().unwrap(), u64::MAX);
+    assert_eq!(
+        counter.assign().unwrap_err(),
+        ErrorCode::MathOverflow.into()
+    );
+}
+
+/// init_game с game_id без аккаунта счётчика.
+fn init_game(game_id: u64) -> anchor_lang::Result<()> {
+    common::install_sysvars();
+    let (player1, game_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut accounts = vec![
+        Account::owned(game_key, &game(GameStatus::WaitingForPlayer2)),
+        Account::wallet(player1, true),
+        Account::program(system_program::ID),
+        config(),
+        open_games(&player1),
+        Account::owned(
+            Pubkey::new_unique(),
+            &CreateCooldown {
+                last_created_slot: 0,
+            },
+        ),
+    ];
+    let infos = infos(&mut accounts);
+    let mut ctx_accounts = InitGame {
+        game: AnchorAccount::try_from(&infos[0])?,
+        player1: Signer::try_from(&infos[1])?,
+        system_program: Program::try_from(&infos[2])?,
+        config: AnchorAccount::try_from(&infos[3])?,
+        player1_ban: None,
+        open_games: AnchorAccount::try_from(&infos[4])?,
+        time_control: None,
+        create_cooldown: AnchorAccount::try_from(&infos[5])?,
+        fee_route: None,
+        whitelisted_arbiter: None,
+        game_counter: None,
+        game_history: None,
+        history_commitment: None,
+    };
+    let ctx = Context::new(
+        &pooler::ID,
+        &mut ctx_accounts,
+        &[],
+        InitGameBumps::default(),
+    );
+    pooler::backgammon::init_game(
+        ctx,
+        game_id,
+        1_000_000,
+        0,
+        Pubkey::new_unique(),
+        GameOptions::default(),
+    )
+}
+
+#[test]
+fn explicit_ids_must_not_use_the_counter_range() {
+    for game_id in [AUTO_GAME_ID_FLAG, AUTO_GAME_ID_FLAG | 1, u64::MAX] {
+        assert_eq!(
+            init_game(game_id).unwrap_err(),
+            ErrorCode::ReservedGameId.into()
+        );
+    }
+    assert_eq!(
+        init_game(0).unwrap_err(),
+        ErrorCode::MissingGameCounter.into()
+    );
+}
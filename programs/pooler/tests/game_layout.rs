@@ -0,0 +1,276 @@
+//! Раскладка GameState: смещения из `layout` указывают на свои поля в
+//! сериализованном аккаунте; байты reserved в хвосте переживают сериализацию,
+//! десериализацию и migrate_game_state, поэтому поле, вырезанное из reserved,
+//! прочитает то, что в нём записано, а у старых аккаунтов — нули. Аккаунт
+//! исходной раскладки (256 байт) переносится вместе с банком, доски версии 3
+//! переупаковываются на месте.
+//!
+//! resize вне валидатора не выполнить, поэтому перенос проверяется на
+//! migrate_game_state_data — той же функции, которую вызывает инструкция
+//! после расширения аккаунта.
+
+mod common;
+
+use anchor_lang::prelude::{borsh, AnchorSerialize, Pubkey};
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator, Space};
+use common::{game, Account};
+use pooler::{
+    check_legacy_game_state, layout, migrate_game_state_data, pack_board, start_board, ErrorCode,
+    FinishReason, GameState, GameStatus, RuleVariant, GAME_STATE_VERSION,
+};
+
+/// Смещение reserved в данных аккаунта: это последнее поле GameState.
+fn reserved_offset(state: &GameState) -> usize {
+    8 + GameState::INIT_SPACE - state.reserved.len()
+}
+
+fn populated(version: u8) -> GameState {
+    let mut state = game(GameStatus::Finished);
+    state.version = version;
+    state.stake_lamports = 1_000_000;
+    state.pot_lamports = 2_000_003;
+    let mut board = [0; 24];
+    board[0] = 2;
+    board[23] = -2;
+    state.set_board_points(&board).unwrap();
+    state.winner = state.player2;
+    state.finish_reason = FinishReason::Board;
+    state.decay_bps = 250;
+    state.decayed_lamports = 77;
+    state.unsettled_side_bets = 3;
+    state
+}
+
+/// Данные аккаунта 8 + MAX_SIZE с state и pattern в reserved.
+fn account_data(state: &GameState, pattern: &[u8]) -> Vec<u8> {
+    let mut data = vec![0u8; 8 + GameState::MAX_SIZE];
+    state.try_serialize(&mut &mut data[..]).unwrap();
+    let offset = reserved_offset(state);
+    data[offset..offset + pattern.len()].copy_from_slice(pattern);
+    data
+}
+
+fn serialized(state: &GameState) -> Vec<u8> {
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn reserved_bytes_survive_round_trip_and_migration() {
+    let state = populated(2);
+    // Будущее поле u16 в начале reserved и ещё байт за ним.
+    let carved = 0x0102u16;
+    let mut pattern = carved.to_le_bytes().to_vec();
+    pattern.push(0xAB);
+    let mut data = account_data(&state, &pattern);
+
+    let read = GameState::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(read.reserved[..pattern.len()], pattern[..]);
+    assert!(read.reserved[pattern.len()..].iter().all(|&b| b == 0));
+    assert_eq!(
+        u16::from_le_bytes(read.reserved[..2].try_into().unwrap()),
+        carved
+    );
+    // Кроме reserved, всё как было.
+    let mut without_reserved = read.clone();
+    without_reserved.reserved = state.reserved;
+    assert_eq!(serialized(&without_reserved), serialized(&state));
+
+    migrate_game_state_data(&mut data, 2).unwrap();
+    let migrated = GameState::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(migrated.version, GAME_STATE_VERSION);
+    assert_eq!(migrated.finish_reason, FinishReason::Board);
+    assert_eq!(migrated.reserved, read.reserved);
+    assert_eq!(
+        migrated.board_points().unwrap(),
+        read.board_points().unwrap()
+    );
+    let mut unversioned = migrated.clone();
+    unversioned.version = 2;
+    unversioned.board = read.board;
+    unversioned.pending_board = read.pending_board;
+    assert_eq!(serialized(&unversioned), serialized(&read));
+}
+
+#[test]
+fn version_1_migration_only_clears_the_finish_reason() {
+    let state = populated(1);
+    let pattern = [0x5A; 4];
+    let mut data = account_data(&state, &pattern);
+    migrate_game_state_data(&mut data, 1).unwrap();
+
+    let migrated = GameState::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(migrated.version, GAME_STATE_VERSION);
+    assert_eq!(migrated.finish_reason, FinishReason::NotFinished);
+    assert_eq!(migrated.reserved, pattern);
+    let mut expected = migrated.clone();
+    expected.version = 1;
+    expected.finish_reason = FinishReason::Board;
+    expected.board = state.board;
+    expected.pending_board = state.pending_board;
+    let mut original = state.clone();
+    original.reserved = pattern;
+    assert_eq!(serialized(&expected), serialized(&original));
+}
+
+#[test]
+fn version_3_boards_are_read_raw_until_migration_repacks_them() {
+    let mut state = populated(3);
+    let pending = start_board(RuleVariant::Standard, 0).unwrap();
+    state.set_pending_board_points(&pending).unwrap();
+    // До версии 4 слот — пункты как i8.
+    assert_eq!((state.board[0], state.board[23]), (2, (-2i8) as u8));
+    let board = state.board_points().unwrap();
+
+    let mut account = Account {
+        owner: pooler::ID,
+        data: account_data(&state, &[]),
+        ..Account::wallet(Pubkey::new_unique(), false)
+    };
+    assert_eq!(check_legacy_game_state(&account.info()).unwrap(), 3);
+    migrate_game_state_data(&mut account.data, 3).unwrap();
+    assert_eq!(
+        check_legacy_game_state(&account.info()).unwrap_err(),
+        ErrorCode::NotLegacyGameState.into()
+    );
+
+    let mut migrated = GameState::try_deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(migrated.version, GAME_STATE_VERSION);
+    assert_eq!(migrated.board[..15], pack_board(&board).unwrap());
+    assert!(migrated.board[15..].iter().all(|&b| b == 0));
+    assert_eq!(migrated.board_points().unwrap(), board);
+    assert_eq!(migrated.pending_board_points().unwrap(), pending);
+
+    // Хвост слота за PackedBoard должен быть нулевым.
+    migrated.board[20] = 1;
+    assert_eq!(
+        migrated.board_points().unwrap_err(),
+        ErrorCode::InvalidBoard.into()
+    );
+}
+
+#[test]
+fn zeroed_reserved_reads_as_zero_for_new_fields() {
+    let state = populated(GAME_STATE_VERSION);
+    let data = account_data(&state, &[]);
+    assert!(data[reserved_offset(&state)..].iter().all(|&b| b == 0));
+    let read = GameState::try_deserialize(&mut &data[..]).unwrap();
+    assert!(read.reserved.iter().all(|&b| b == 0));
+}
+
+/// GameState исходной раскладки (версия 0): поля до bump, 256 байт данных.
+#[derive(AnchorSerialize)]
+struct BaselineGameState {
+    player1: Pubkey,
+    player2: Pubkey,
+    game_id: u64,
+    stake_lamports: u64,
+    move_fee_lamports: u64,
+    pot_lamports: u64,
+    player1_deposit: u64,
+    player2_deposit: u64,
+    player1_fees_paid: u64,
+    player2_fees_paid: u64,
+    last_activity_slot: u64,
+    move_index: u64,
+    board_points: [i8; 24],
+    dice: [u8; 2],
+    current_turn: u8,
+    status: GameStatus,
+    winner: Pubkey,
+    bump: u8,
+}
+
+#[test]
+fn baseline_layout_migrates_with_its_pot() {
+    let baseline = BaselineGameState {
+        player1: Pubkey::new_unique(),
+        player2: Pubkey::new_unique(),
+        game_id: 42,
+        stake_lamports: 1_000_000,
+        move_fee_lamports: 1_000,
+        pot_lamports: 2_003_000,
+        player1_deposit: 1_000_000,
+        player2_deposit: 1_000_000,
+        player1_fees_paid: 2_000,
+        player2_fees_paid: 1_000,
+        last_activity_slot: 900,
+        move_index: 3,
+        board_points: [1; 24],
+        dice: [6, 5],
+        current_turn: 2,
+        status: GameStatus::Active,
+        winner: Pubkey::default(),
+        bump: 254,
+    };
+    let mut data = GameState::DISCRIMINATOR.to_vec();
+    baseline.serialize(&mut data).unwrap();
+    data.resize(8 + GameState::BASELINE_MAX_SIZE, 0);
+    let mut account = Account {
+        owner: pooler::ID,
+        data,
+        ..Account::wallet(Pubkey::new_unique(), false)
+    };
+    assert_eq!(check_legacy_game_state(&account.info()).unwrap(), 0);
+
+    account.data.resize(8 + GameState::MAX_SIZE, 0);
+    migrate_game_state_data(&mut account.data, 0).unwrap();
+    let migrated = GameState::try_deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(migrated.version, GAME_STATE_VERSION);
+    assert_eq!(migrated.finish_reason, FinishReason::NotFinished);
+    assert_eq!(migrated.cube_value, 1);
+    assert_eq!(migrated.cube_owner, 0);
+    assert_eq!(
+        (migrated.player1, migrated.player2, migrated.winner),
+        (baseline.player1, baseline.player2, baseline.winner)
+    );
+    assert_eq!(migrated.game_id, baseline.game_id);
+    assert_eq!(migrated.pot_lamports, baseline.pot_lamports);
+    assert_eq!(
+        (migrated.player1_deposit, migrated.player2_deposit),
+        (baseline.player1_deposit, baseline.player2_deposit)
+    );
+    assert_eq!(
+        (migrated.player1_fees_paid, migrated.player2_fees_paid),
+        (baseline.player1_fees_paid, baseline.player2_fees_paid)
+    );
+    assert_eq!(migrated.move_index, baseline.move_index);
+    assert_eq!(migrated.board_points().unwrap(), baseline.board_points);
+    assert_eq!(migrated.dice, baseline.dice);
+    assert_eq!(migrated.current_turn, baseline.current_turn);
+    assert_eq!(migrated.status, GameStatus::Active);
+    assert_eq!(migrated.bump, baseline.bump);
+    // Поля, появившиеся позже, читаются нулями.
+    assert_eq!(migrated.tips_lamports, 0);
+    assert_eq!(migrated.p1_take_reserve + migrated.p2_take_reserve, 0);
+    assert!(migrated.reserved.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn layout_offsets_point_at_their_fields() {
+    let mut state = populated(GAME_STATE_VERSION);
+    state.game_id = 0x1122_3344_5566_7788;
+    state.winner = Pubkey::new_unique();
+    let data = serialized(&state);
+
+    assert_eq!(&data[layout::DISCRIMINATOR..8], GameState::DISCRIMINATOR);
+    assert_eq!(&data[layout::PLAYER1..][..32], state.player1.as_ref());
+    assert_eq!(&data[layout::PLAYER2..][..32], state.player2.as_ref());
+    assert_eq!(&data[layout::GAME_ID..][..8], &state.game_id.to_le_bytes());
+    assert_eq!(&data[layout::WINNER..][..32], state.winner.as_ref());
+
+    for (status, byte) in [
+        (
+            GameStatus::WaitingForPlayer2,
+            layout::STATUS_WAITING_FOR_PLAYER2,
+        ),
+        (GameStatus::Active, layout::STATUS_ACTIVE),
+        (GameStatus::Finished, layout::STATUS_FINISHED),
+        (GameStatus::Disputed, layout::STATUS_DISPUTED),
+        (GameStatus::Cancelled, layout::STATUS_CANCELLED),
+    ] {
+        state.status = status;
+        assert_eq!(serialized(&state)[layout::STATUS], byte, "{status:?}");
+    }
+}
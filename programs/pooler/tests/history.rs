@@ -0,0 +1,235 @@
+//! История ходов: make_move дописывает текущий чанк GameHistory, и по ней
+//! партия переигрывается до текущей доски. В заполненный чанк ход не
+//! проходит, пока игра не перейдёт на следующий.
+//!
+//! Обработчик make_move вызывается напрямую в бесплатном окне (без CPI);
+//! создание чанков (init_game, rotate_history_chunk) и close_game_history
+//! проверяются в LiteSVM (tests/history.rs).
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey};
+use anchor_lang::system_program;
+use common::{config_with, game, install_sysvars, parse, pda, Account};
+use pooler::{
+    board_hash, pack_board, start_board, unpack_board, ErrorCode, GameHistory, GameState,
+    GameStatus, MakeMove, MoveRecord, ReplayPosition, RuleVariant, MAX_HISTORY_MOVES,
+};
+
+const MOVES: usize = 20;
+
+fn active_game() -> GameState {
+    let mut state = game(GameStatus::Active);
+    state
+        .set_board_points(&start_board(RuleVariant::Standard, 0).unwrap())
+        .unwrap();
+    state.board_from_start = true;
+    state.move_history = true;
+    state
+}
+
+fn empty_history(game: Pubkey, chunk_index: u8) -> GameHistory {
+    GameHistory {
+        game,
+        payer: Pubkey::new_unique(),
+        chunk_index,
+        prev_chunk: Pubkey::default(),
+        moves: Vec::new(),
+        bump: 0,
+    }
+}
+
+/// make_move стороны current_turn; history = None — аккаунт не передан.
+fn make_move(
+    game_key: Pubkey,
+    state: &GameState,
+    history: Option<&GameHistory>,
+    board: [i8; 24],
+    dice: [u8; 2],
+) -> anchor_lang::Result<(GameState, Option<GameHistory>)> {
+    install_sysvars();
+    let mover = state.player_key(state.current_turn);
+    let opponent = state.player_key(3 - state.current_turn);
+    let mut accounts = vec![
+        Account::owned(game_key, state),
+        Account::wallet(mover, true),
+        Account::wallet(opponent, false),
+        Account::program(system_program::ID),
+        Account::none(),
+        config_with(|config| config.free_moves = 255),
+    ];
+    // team, time_control, fee_route, fee_recipient, insurance_pool,
+    // client_telemetry, event_buffer.
+    accounts.extend((0..7).map(|_| Account::none()));
+    accounts.push(match history {
+        Some(history) => Account::owned(
+            pda(&[b"game_history", game_key.as_ref(), &[history.chunk_index]]).0,
+            history,
+        ),
+        None => Account::none(),
+    });
+    // history_commitment, decay_sink.
+    accounts.extend((0..2).map(|_| Account::none()));
+    let (mut parsed, bumps) = parse::<MakeMove>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::make_move(ctx, board, dice, [0; 32], None)?;
+    Ok((
+        (*parsed.game).clone(),
+        parsed.game_history.map(|history| (**history).clone()),
+    ))
+}
+
+/// Партия из MOVES законных ходов с кубиками из простого ГПСЧ; возвращает
+/// итоговое состояние игры и историю.
+fn play() -> (GameState, GameHistory) {
+    let game_key = Pubkey::new_unique();
+    let mut state = active_game();
+    let mut history = empty_history(game_key, 0);
+    let mut position: backgammon_core::Position = ReplayPosition::start(RuleVariant::Standard, 0)
+        .unwrap()
+        .into();
+    let mut seed = 7u64;
+    for _ in 0..MOVES {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        let dice = [(seed >> 33) as u8 % 6 + 1, (seed >> 45) as u8 % 6 + 1];
+        let moves = backgammon_core::legal_moves(&position, state.current_turn, dice);
+        position = moves[(seed >> 20) as usize % moves.len()];
+
+        let (next, next_history) =
+            make_move(game_key, &state, Some(&history), position.board, dice).unwrap();
+        (state, history) = (next, next_history.unwrap());
+    }
+    (state, history)
+}
+
+#[test]
+fn history_replays_to_the_final_board() {
+    let (state, history) = play();
+    assert_eq!(state.move_index, MOVES as u64);
+    assert_eq!(history.moves.len(), MOVES);
+    assert_ne!(
+        state.board_points().unwrap(),
+        start_board(RuleVariant::Standard, 0).unwrap()
+    );
+
+    // Переигрывание по правилам со стартовой позиции: каждая запись —
+    // законный ход своей стороны.
+    let mut position = ReplayPosition::start(RuleVariant::Standard, 0).unwrap();
+    for (i, record) in history.moves.iter().enumerate() {
+        let side = 1 + (i % 2) as u8;
+        assert_eq!(record.move_index, i as u64 + 1);
+        assert_eq!(record.mover, state.player_key(side));
+        let board = unpack_board(&record.board).unwrap();
+        position = position
+            .play_move(side, record.dice, &board)
+            .unwrap_or_else(|| panic!("move {} is not legal", record.move_index));
+    }
+    assert_eq!(
+        board_hash(&position.board),
+        board_hash(&state.board_points().unwrap())
+    );
+}
+
+#[test]
+fn full_chunk_rejects_moves_until_rotated() {
+    let record = MoveRecord {
+        move_index: 1,
+        mover: Pubkey::new_unique(),
+        board: pack_board(&start_board(RuleVariant::Standard, 0).unwrap()).unwrap(),
+        dice: [3, 1],
+        slot: 0,
+        fee_lamports: 0,
+    };
+    let game_key = Pubkey::new_unique();
+    let mut full = empty_history(game_key, 0);
+    for _ in 0..MAX_HISTORY_MOVES {
+        full.append(record).unwrap();
+    }
+    assert!(full.is_full());
+    assert_eq!(
+        full.append(record).unwrap_err(),
+        ErrorCode::HistoryFull.into()
+    );
+
+    // Ход в заполненный чанк не проходит, а не теряет запись.
+    let mut state = active_game();
+    assert_eq!(
+        make_move(
+            game_key,
+            &state,
+            Some(&full),
+            state.board_points().unwrap(),
+            [3, 1]
+        )
+        .err(),
+        Some(ErrorCode::HistoryFull.into())
+    );
+
+    // После перехода на чанк 1 старый чанк уже не текущий.
+    state.current_history_chunk = 1;
+    assert_eq!(
+        make_move(
+            game_key,
+            &state,
+            Some(&full),
+            state.board_points().unwrap(),
+            [3, 1]
+        )
+        .err(),
+        Some(ErrorCode::GameHistoryMismatch.into())
+    );
+    let next = empty_history(game_key, 1);
+    let (after, history) = make_move(
+        game_key,
+        &state,
+        Some(&next),
+        state.board_points().unwrap(),
+        [3, 1],
+    )
+    .unwrap();
+    assert_eq!(after.move_index, 1);
+    assert_eq!(history.unwrap().moves.len(), 1);
+}
+
+#[test]
+fn history_must_belong_to_the_game() {
+    let state = active_game();
+    let game_key = Pubkey::new_unique();
+    assert_eq!(
+        make_move(
+            game_key,
+            &state,
+            None,
+            state.board_points().unwrap(),
+            [3, 1]
+        )
+        .err(),
+        Some(ErrorCode::MissingGameHistory.into())
+    );
+    let foreign = empty_history(Pubkey::new_unique(), 0);
+    assert_eq!(
+        make_move(
+            game_key,
+            &state,
+            Some(&foreign),
+            state.board_points().unwrap(),
+            [3, 1]
+        )
+        .err(),
+        Some(ErrorCode::GameHistoryMismatch.into())
+    );
+
+    // Игра без истории ходит, как раньше.
+    let mut plain = active_game();
+    plain.move_history = false;
+    make_move(
+        game_key,
+        &plain,
+        None,
+        plain.board_points().unwrap(),
+        [3, 1],
+    )
+    .unwrap();
+}
@@ -0,0 +1,107 @@
+//! Константы программы в IDL, которым пользуются клиенты.
+//!
+//! IDL в client/src/idl генерируется `anchor build` (target/idl/backgammon.json)
+//! и копируется оттуда. Тест ловит переименование или пропажу `#[constant]`
+//! и расхождение значений до того, как это заметят клиенты.
+
+use serde_json::Value;
+
+const IDL: &str = include_str!("../../../client/src/idl/backgammon.json");
+
+fn constants() -> Vec<(String, String, String)> {
+    let idl: Value = serde_json::from_str(IDL).expect("IDL is valid JSON");
+    idl["constants"]
+        .as_array()
+        .expect("IDL has constants")
+        .iter()
+        .map(|c| {
+            let field = |name: &str| c[name].as_str().unwrap().to_string();
+            (field("name"), field("type"), field("value"))
+        })
+        .collect()
+}
+
+macro_rules! expected {
+    ($($path:path => $name:ident: $ty:ident),* $(,)?) => {
+        vec![$((
+            stringify!($name).to_string(),
+            stringify!($ty).to_string(),
+            {
+                let value: $ty = $path;
+                value.to_string()
+            },
+        )),*]
+    };
+}
+
+#[test]
+fn idl_exports_program_constants() {
+    use pooler::layout;
+
+    let mut expected = expected! {
+        pooler::AUTO_GAME_ID_FLAG => AUTO_GAME_ID_FLAG: u64,
+        pooler::BOARD_POINTS => BOARD_POINTS: u8,
+        pooler::BPS_DENOMINATOR => BPS_DENOMINATOR: u64,
+        pooler::CLAIM_CONTEST_WINDOW_SLOTS => CLAIM_CONTEST_WINDOW_SLOTS: u64,
+        pooler::DEFAULT_APPEAL_WINDOW_SLOTS => DEFAULT_APPEAL_WINDOW_SLOTS: u64,
+        pooler::DEFAULT_ESCHEAT_AFTER_SLOTS => DEFAULT_ESCHEAT_AFTER_SLOTS: u64,
+        pooler::DEFAULT_MAX_MOVES => DEFAULT_MAX_MOVES: u16,
+        pooler::DEFAULT_PRUNE_AFTER_SLOTS => DEFAULT_PRUNE_AFTER_SLOTS: u64,
+        pooler::DEFAULT_RATING => DEFAULT_RATING: u32,
+        pooler::DEFAULT_STRIKE_LIMIT => DEFAULT_STRIKE_LIMIT: u8,
+        pooler::EVIDENCE_WINDOW_SLOTS => EVIDENCE_WINDOW_SLOTS: u64,
+        pooler::FORCE_REFUND_TIMEOUT_SLOTS => FORCE_REFUND_TIMEOUT_SLOTS: u64,
+        pooler::GAME_STATE_MAX_SIZE => GAME_STATE_MAX_SIZE: u64,
+        pooler::GAME_STATE_VERSION => GAME_STATE_VERSION: u8,
+        pooler::HANDICAP_RATING_STEP => HANDICAP_RATING_STEP: u32,
+        pooler::HISTORY_TREE_DEPTH => HISTORY_TREE_DEPTH: u8,
+        pooler::LOBBY_SHARDS => LOBBY_SHARDS: u8,
+        pooler::LOBBY_SHARD_CAPACITY => LOBBY_SHARD_CAPACITY: u8,
+        pooler::MAX_CUBE_VALUE => MAX_CUBE_VALUE: u8,
+        pooler::MAX_DECAY_BPS => MAX_DECAY_BPS: u16,
+        pooler::MAX_GAME_LOG_CHUNK => MAX_GAME_LOG_CHUNK: u8,
+        pooler::MAX_HANDICAP => MAX_HANDICAP: u8,
+        pooler::MAX_HISTORY_MOVES => MAX_HISTORY_MOVES: u8,
+        pooler::MAX_INSURANCE_FEE_BPS => MAX_INSURANCE_FEE_BPS: u16,
+        pooler::MAX_PASSWORD_LEN => MAX_PASSWORD_LEN: u8,
+        pooler::MAX_PROTOCOL_FEE_BPS => MAX_PROTOCOL_FEE_BPS: u16,
+        pooler::MAX_PRUNE_BATCH => MAX_PRUNE_BATCH: u8,
+        pooler::MAX_PRUNE_REWARD_BPS => MAX_PRUNE_REWARD_BPS: u16,
+        pooler::MIN_APPEAL_WINDOW_SLOTS => MIN_APPEAL_WINDOW_SLOTS: u64,
+        pooler::MIN_ESCHEAT_AFTER_SLOTS => MIN_ESCHEAT_AFTER_SLOTS: u64,
+        pooler::MIN_PRUNE_AFTER_SLOTS => MIN_PRUNE_AFTER_SLOTS: u64,
+        pooler::MOVE_TIMEOUT_SLOTS => MOVE_TIMEOUT_SLOTS: u64,
+        pooler::PACKED_BOARD_VERSION => PACKED_BOARD_VERSION: u8,
+        pooler::PAYOUT_SPLITS_MAX => PAYOUT_SPLITS_MAX: u8,
+        pooler::RATING_K => RATING_K: u32,
+        layout::STATUS_ACTIVE => STATUS_ACTIVE: u8,
+        layout::STATUS_CANCELLED => STATUS_CANCELLED: u8,
+        layout::STATUS_DISPUTED => STATUS_DISPUTED: u8,
+        layout::STATUS_FINISHED => STATUS_FINISHED: u8,
+        layout::STATUS_WAITING_FOR_PLAYER2 => STATUS_WAITING_FOR_PLAYER2: u8,
+        pooler::SWISS_BYE => SWISS_BYE: u8,
+        pooler::WIN_CLAIM_BOND_LAMPORTS => WIN_CLAIM_BOND_LAMPORTS: u64,
+    };
+    expected.sort();
+
+    let mut actual = constants();
+    actual.sort();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn idl_values_match_account_layout() {
+    let value = |name: &str| -> u64 {
+        let (_, _, value) = constants()
+            .into_iter()
+            .find(|(n, _, _)| n == name)
+            .unwrap_or_else(|| panic!("{} is missing from the IDL", name));
+        value.parse().unwrap()
+    };
+    assert_eq!(
+        value("GAME_STATE_MAX_SIZE"),
+        pooler::GameState::MAX_SIZE as u64
+    );
+    assert_eq!(value("BOARD_POINTS"), 24);
+    assert_eq!(value("STATUS_ACTIVE"), pooler::GameStatus::Active as u64);
+}
@@ -0,0 +1,227 @@
+//! check_init_params: каждое правило init_game отклоняется своей ошибкой.
+
+use anchor_lang::prelude::Pubkey;
+use pooler::{check_init_params, start_board, ErrorCode, GameOptions, RuleVariant, MAX_DECAY_BPS};
+
+const STAKE: u64 = 1_000_000;
+
+fn check(
+    game_id: u64,
+    stake: u64,
+    move_fee: u64,
+    player2: Pubkey,
+    options: GameOptions,
+) -> anchor_lang::Result<()> {
+    check_init_params(game_id, stake, move_fee, &player2, &options)
+}
+
+fn rejected(result: anchor_lang::Result<()>, code: ErrorCode) {
+    assert_eq!(result.unwrap_err(), code.into());
+}
+
+#[test]
+fn valid_parameters_pass() {
+    let player2 = Pubkey::new_unique();
+    check(1, STAKE, STAKE / 100, player2, GameOptions::default()).unwrap();
+    check(1, STAKE, STAKE, player2, GameOptions::default()).unwrap();
+
+    let checkpoint = GameOptions {
+        rule_variant: RuleVariant::Nackgammon,
+        initial_board: start_board(RuleVariant::Nackgammon, 0).unwrap(),
+        ..GameOptions::default()
+    };
+    check(1, STAKE, 0, player2, checkpoint).unwrap();
+
+    let practice = GameOptions {
+        practice: true,
+        ..GameOptions::default()
+    };
+    check(1, 0, 0, player2, practice).unwrap();
+
+    let open_lobby = GameOptions {
+        open_lobby: true,
+        ..GameOptions::default()
+    };
+    check(1, STAKE, 0, Pubkey::default(), open_lobby).unwrap();
+}
+
+#[test]
+fn each_rule_has_its_own_error() {
+    let player2 = Pubkey::new_unique();
+    let options = GameOptions::default();
+
+    rejected(
+        check(0, STAKE, 0, player2, options),
+        ErrorCode::InvalidGameId,
+    );
+    rejected(check(1, 0, 0, player2, options), ErrorCode::ZeroStake);
+    rejected(check(1, 0, 1, player2, options), ErrorCode::ZeroStake);
+    rejected(
+        check(1, STAKE, STAKE + 1, player2, options),
+        ErrorCode::MoveFeeExceedsStake,
+    );
+    rejected(
+        check(1, STAKE, 0, Pubkey::default(), options),
+        ErrorCode::MissingPlayer2,
+    );
+
+    let mut overfull = start_board(RuleVariant::Standard, 0).unwrap();
+    overfull[23] += 1;
+    let overfull = GameOptions {
+        initial_board: overfull,
+        ..options
+    };
+    rejected(
+        check(1, STAKE, 0, player2, overfull),
+        ErrorCode::InvalidInitialBoard,
+    );
+
+    let mut shuffled = start_board(RuleVariant::Standard, 0).unwrap();
+    shuffled.swap(0, 1);
+    let shuffled = GameOptions {
+        initial_board: shuffled,
+        ..options
+    };
+    rejected(
+        check(1, STAKE, 0, player2, shuffled),
+        ErrorCode::InitialBoardMismatch,
+    );
+
+    let paid_practice = GameOptions {
+        practice: true,
+        ..options
+    };
+    rejected(
+        check(1, STAKE, 0, player2, paid_practice),
+        ErrorCode::InvalidPracticeGame,
+    );
+}
+
+#[test]
+fn move_fee_bps_replaces_the_flat_fee() {
+    let player2 = Pubkey::new_unique();
+    let bps = |move_fee_bps| GameOptions {
+        move_fee_bps,
+        ..GameOptions::default()
+    };
+    check(1, STAKE, 0, player2, bps(50)).unwrap();
+    check(1, STAKE, 0, player2, bps(10_000)).unwrap();
+
+    rejected(
+        check(1, STAKE, 1, player2, bps(50)),
+        ErrorCode::ConflictingMoveFee,
+    );
+    rejected(
+        check(1, STAKE, 0, player2, bps(10_001)),
+        ErrorCode::InvalidMoveFeeBps,
+    );
+    let practice = GameOptions {
+        practice: true,
+        ..bps(50)
+    };
+    rejected(
+        check(1, 0, 0, player2, practice),
+        ErrorCode::InvalidPracticeGame,
+    );
+}
+
+#[test]
+fn decay_excludes_move_fees() {
+    let player2 = Pubkey::new_unique();
+    let decay = |decay_bps| GameOptions {
+        decay_bps,
+        ..GameOptions::default()
+    };
+    check(1, STAKE, 0, player2, decay(250)).unwrap();
+    check(1, STAKE, 0, player2, decay(MAX_DECAY_BPS)).unwrap();
+
+    // Тающий банк заменяет комиссию за ход: ни фиксированной, ни от банка.
+    rejected(
+        check(1, STAKE, 1, player2, decay(250)),
+        ErrorCode::ConflictingMoveFee,
+    );
+    rejected(
+        check(
+            1,
+            STAKE,
+            0,
+            player2,
+            GameOptions {
+                move_fee_bps: 50,
+                ..decay(250)
+            },
+        ),
+        ErrorCode::ConflictingMoveFee,
+    );
+    rejected(
+        check(1, STAKE, 0, player2, decay(MAX_DECAY_BPS + 1)),
+        ErrorCode::InvalidDecayBps,
+    );
+    let practice = GameOptions {
+        practice: true,
+        ..decay(250)
+    };
+    rejected(
+        check(1, 0, 0, player2, practice),
+        ErrorCode::InvalidPracticeGame,
+    );
+}
+
+#[test]
+fn each_variant_starts_from_its_own_board() {
+    let player2 = Pubkey::new_unique();
+    let variant = |rule_variant, board_variant| GameOptions {
+        rule_variant,
+        initial_board: start_board(board_variant, 0).unwrap(),
+        ..GameOptions::default()
+    };
+    for rule_variant in [
+        RuleVariant::Standard,
+        RuleVariant::Nackgammon,
+        RuleVariant::Hypergammon,
+    ] {
+        check(1, STAKE, 0, player2, variant(rule_variant, rule_variant)).unwrap();
+    }
+
+    // 15 шашек не помещаются в хайпергаммон; у накгаммона те же 15, но
+    // другая расстановка.
+    rejected(
+        check(
+            1,
+            STAKE,
+            0,
+            player2,
+            variant(RuleVariant::Hypergammon, RuleVariant::Standard),
+        ),
+        ErrorCode::InvalidInitialBoard,
+    );
+    rejected(
+        check(
+            1,
+            STAKE,
+            0,
+            player2,
+            variant(RuleVariant::Nackgammon, RuleVariant::Standard),
+        ),
+        ErrorCode::InitialBoardMismatch,
+    );
+    rejected(
+        check(
+            1,
+            STAKE,
+            0,
+            player2,
+            variant(RuleVariant::Standard, RuleVariant::Hypergammon),
+        ),
+        ErrorCode::InitialBoardMismatch,
+    );
+    // Пустая доска — только для обычной игры.
+    let empty = GameOptions {
+        rule_variant: RuleVariant::Hypergammon,
+        ..GameOptions::default()
+    };
+    rejected(
+        check(1, STAKE, 0, player2, empty),
+        ErrorCode::InitialBoardMismatch,
+    );
+}
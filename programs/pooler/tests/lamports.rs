@@ -0,0 +1,201 @@
+//! Проверенные списания и зачисления lamports (debit/credit) и выплаты через них.
+//!
+//! debit читает Rent sysvar; вне валидатора его подставляет заглушка
+//! syscall (Rent::default()).
+
+use std::sync::Once;
+
+use anchor_lang::error::{ComparedValues, Error, ERROR_CODE_OFFSET};
+use anchor_lang::prelude::{AccountInfo, Pubkey, Rent};
+use pooler::{credit, debit, pay_out, ErrorCode};
+use solana_sysvar::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+struct RentSysvar;
+
+impl SyscallStubs for RentSysvar {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        // SAFETY: Rent::get передаёт указатель на место под Rent.
+        unsafe { (var_addr as *mut Rent).write(Rent::default()) };
+        0
+    }
+}
+
+fn rent_min(data_len: usize) -> u64 {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(RentSysvar));
+    });
+    Rent::default().minimum_balance(data_len)
+}
+
+fn anchor_error(result: anchor_lang::Result<()>, expected: ErrorCode) -> Option<ComparedValues> {
+    match result.unwrap_err() {
+        Error::AnchorError(err) => {
+            assert_eq!(err.error_code_number, ERROR_CODE_OFFSET + expected as u32);
+            err.compared_values
+        }
+        Error::ProgramError(err) => panic!("unexpected program error {:?}", err),
+    }
+}
+
+#[test]
+fn debit_and_credit_move_exact_amounts() {
+    let (key, owner) = (Pubkey::new_unique(), pooler::ID);
+    let floor = rent_min(0);
+    let (mut lamports, mut data) = (floor + 1_000, []);
+    let account = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+
+    debit(&account, 400).unwrap();
+    assert_eq!(account.lamports(), floor + 600);
+    credit(&account, 50).unwrap();
+    assert_eq!(account.lamports(), floor + 650);
+    debit(&account, 650).unwrap();
+    assert_eq!(account.lamports(), floor);
+}
+
+#[test]
+fn debit_below_zero_fails_without_change() {
+    let (key, owner) = (Pubkey::new_unique(), pooler::ID);
+    rent_min(0);
+    let (mut lamports, mut data) = (100u64, []);
+    let account = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+
+    anchor_error(debit(&account, 101), ErrorCode::InsufficientAccountBalance);
+    assert_eq!(account.lamports(), 100);
+}
+
+#[test]
+fn debit_below_rent_minimum_reports_both_numbers() {
+    let (key, owner) = (Pubkey::new_unique(), pooler::ID);
+    let mut data = [0u8; 600];
+    let floor = rent_min(data.len());
+    let mut lamports = floor + 10;
+    let account = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+
+    let compared = anchor_error(debit(&account, 11), ErrorCode::RentExemptViolation);
+    let Some(ComparedValues::Values((remaining, minimum))) = compared else {
+        panic!("expected compared values, got {:?}", compared);
+    };
+    assert_eq!(remaining, (floor - 1).to_string());
+    assert_eq!(minimum, floor.to_string());
+    assert_eq!(account.lamports(), floor + 10);
+}
+
+#[test]
+fn credit_overflow_fails_without_change() {
+    let (key, owner) = (Pubkey::new_unique(), pooler::ID);
+    let (mut lamports, mut data) = (u64::MAX - 1, []);
+    let account = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+
+    anchor_error(credit(&account, 2), ErrorCode::MathOverflow);
+    assert_eq!(account.lamports(), u64::MAX - 1);
+}
+
+/// Аккаунт игры с банком pot, на котором лежит rent + held lamports.
+fn payout(pot: u64, held: u64) -> (anchor_lang::Result<()>, u64, u64, u64) {
+    let (game_key, winner_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let system = Pubkey::default();
+    let mut game_data = vec![0u8; 8 + pooler::GameState::MAX_SIZE];
+    let floor = rent_min(game_data.len());
+    let mut game_lamports = floor + held;
+    let (mut winner_lamports, mut winner_data) = (5_000u64, []);
+    let game = AccountInfo::new(
+        &game_key,
+        false,
+        true,
+        &mut game_lamports,
+        &mut game_data,
+        &pooler::ID,
+        false,
+        0,
+    );
+    let winner = AccountInfo::new(
+        &winner_key,
+        false,
+        true,
+        &mut winner_lamports,
+        &mut winner_data,
+        &system,
+        false,
+        0,
+    );
+
+    // Сплиты не зарегистрированы: PDA сплитов — пустой системный аккаунт.
+    let (splits_key, mut splits_lamports, mut splits_data) = (Pubkey::new_unique(), 0u64, []);
+    let splits = AccountInfo::new(
+        &splits_key,
+        false,
+        false,
+        &mut splits_lamports,
+        &mut splits_data,
+        &system,
+        false,
+        0,
+    );
+
+    let remaining: Vec<AccountInfo> = Vec::new();
+    let result = pay_out(&game, winner.clone(), &splits, &mut remaining.iter(), pot);
+    (result, floor, game.lamports(), winner.lamports())
+}
+
+#[test]
+fn payout_of_full_pot_keeps_rent() {
+    let pot = 1_000_000_000;
+    let (result, floor, game, winner) = payout(pot, pot);
+    result.unwrap();
+    assert_eq!(game, floor);
+    assert_eq!(winner, 5_000 + pot);
+}
+
+#[test]
+fn payout_from_drifted_game_account_fails_before_moving_lamports() {
+    // Учёт банка разошёлся с балансом: pot на 1 лампорт больше, чем лежит
+    // сверх ренты, — выплата задела бы ренту.
+    let pot = 1_000_000_000;
+    let (result, floor, game, winner) = payout(pot, pot - 1);
+    anchor_error(result, ErrorCode::RentExemptViolation);
+    assert_eq!(game, floor + pot - 1);
+    assert_eq!(winner, 5_000);
+
+    // Банк больше всего баланса аккаунта — та же выплата без паники.
+    let (result, floor, game, winner) = payout(u64::MAX - 1, pot);
+    anchor_error(result, ErrorCode::InsufficientAccountBalance);
+    assert_eq!(game, floor + pot);
+    assert_eq!(winner, 5_000);
+}
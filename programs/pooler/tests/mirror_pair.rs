@@ -0,0 +1,78 @@
+//! init_mirror_pair: игры пары проверяет MirrorPair::check_games — цвета
+//! переставлены, сид кубиков общий и ненулевой, и ни одна игра ещё не
+//! входит в другую пару.
+//!
+//! Сам обработчик создаёт PDA пары через CPI, поэтому пометка mirror_paired
+//! и повторная пара из тех же игр проверяются в LiteSVM (tests/mirror_pair.rs).
+
+mod common;
+
+use common::game;
+use pooler::{ErrorCode, GameState, GameStatus, MirrorPair};
+
+const SEED: [u8; 32] = [7; 32];
+
+/// Две активные игры без ставки с переставленными цветами и общим сидом.
+fn mirrored() -> (GameState, GameState) {
+    let game_a = GameState {
+        dice_seed: SEED,
+        ..game(GameStatus::Active)
+    };
+    let game_b = GameState {
+        player1: game_a.player2,
+        player2: game_a.player1,
+        ..game_a.clone()
+    };
+    (game_a, game_b)
+}
+
+fn check(game_a: &GameState, game_b: &GameState) -> anchor_lang::Result<()> {
+    MirrorPair::check_games(game_a, game_b, &game_a.player1, &game_b.player1)
+}
+
+#[test]
+fn mirrored_games_with_one_seed_form_a_pair() {
+    let (game_a, game_b) = mirrored();
+    check(&game_a, &game_b).unwrap();
+}
+
+#[test]
+fn paired_games_cannot_join_another_pair() {
+    for paired in [0, 1] {
+        let (mut game_a, mut game_b) = mirrored();
+        [&mut game_a, &mut game_b][paired].mirror_paired = true;
+        assert_eq!(
+            check(&game_a, &game_b).unwrap_err(),
+            ErrorCode::GameAlreadyPaired.into()
+        );
+    }
+}
+
+#[test]
+fn pair_requires_one_non_zero_dice_seed() {
+    let (game_a, mut game_b) = mirrored();
+    game_b.dice_seed[0] ^= 1;
+    assert_eq!(
+        check(&game_a, &game_b).unwrap_err(),
+        ErrorCode::MirrorDiceSeedMismatch.into()
+    );
+
+    let (mut game_a, mut game_b) = mirrored();
+    game_a.dice_seed = [0; 32];
+    game_b.dice_seed = [0; 32];
+    assert_eq!(
+        check(&game_a, &game_b).unwrap_err(),
+        ErrorCode::MirrorDiceSeedMismatch.into()
+    );
+}
+
+#[test]
+fn pair_requires_swapped_colours() {
+    let (game_a, _) = mirrored();
+    let same_colours = game_a.clone();
+    assert_eq!(
+        MirrorPair::check_games(&game_a, &same_colours, &game_a.player1, &game_a.player2)
+            .unwrap_err(),
+        ErrorCode::InvalidMirrorPair.into()
+    );
+}
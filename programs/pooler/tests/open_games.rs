@@ -0,0 +1,56 @@
+//! Счётчик открытых лобби: игра, учтённая в OpenGames (open_games_counted),
+//! снимается с него ровно один раз и без счётчика не выходит из ожидания;
+//! игры, созданные до OpenGames, входят и отменяются без него, не трогая
+//! счётчик создателя.
+
+mod common;
+
+use anchor_lang::prelude::Account as AnchorAccount;
+use common::{game, infos, join_game, open_games, try_accounts, Account};
+use pooler::{release_open_game, ErrorCode, GameStatus, JoinGame, OpenGames};
+
+/// Индекс player1_open_games в аккаунтах join_game.
+const OPEN_GAMES: usize = 4;
+
+#[test]
+fn counted_game_is_released_once() {
+    let mut state = game(GameStatus::WaitingForPlayer2);
+    state.open_games_counted = true;
+    assert_eq!(
+        release_open_game(&mut state, None).err(),
+        Some(ErrorCode::MissingOpenGames.into())
+    );
+
+    let mut accounts = [open_games(&state.player1)];
+    let infos = infos(&mut accounts);
+    let mut counter = AnchorAccount::<OpenGames>::try_from(&infos[0]).unwrap();
+    assert_eq!(counter.open_games, 1);
+    release_open_game(&mut state, Some(&mut counter)).unwrap();
+    assert_eq!(counter.open_games, 0);
+    assert!(!state.open_games_counted);
+
+    // Повторное снятие (и снятие без аккаунта) счётчик не трогает.
+    counter.open_games = 1;
+    release_open_game(&mut state, Some(&mut counter)).unwrap();
+    release_open_game(&mut state, None).unwrap();
+    assert_eq!(counter.open_games, 1);
+}
+
+#[test]
+fn legacy_game_needs_no_counter() {
+    let mut state = game(GameStatus::WaitingForPlayer2);
+    assert!(!state.open_games_counted);
+
+    // Счётчик создателя, заведённый уже после этой игры, не уменьшается.
+    let mut accounts = [open_games(&state.player1)];
+    let infos = infos(&mut accounts);
+    let mut counter = AnchorAccount::<OpenGames>::try_from(&infos[0]).unwrap();
+    release_open_game(&mut state, Some(&mut counter)).unwrap();
+    assert_eq!(counter.open_games, 1);
+    release_open_game(&mut state, None).unwrap();
+
+    // join_game принимает такую игру без аккаунта счётчика.
+    let mut accounts = join_game(&state, state.player2);
+    accounts[OPEN_GAMES] = Account::none();
+    try_accounts::<JoinGame>(&mut accounts).unwrap();
+}
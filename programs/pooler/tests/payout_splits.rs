@@ -0,0 +1,104 @@
+//! Сплиты выплат: PDA сплитов получателя обязателен во всех выплатах, его
+//! адрес проверяется seeds, поэтому зарегистрированные сплиты не обойти;
+//! неинициализированный PDA означает выплату целиком получателю.
+
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use common::{force_refund, game, infos, install_sysvars, no_splits, pda, try_accounts, Account};
+use pooler::{
+    pay_out, payout_shares, ErrorCode, ForceRefund, GameStatus, PayoutSplit, PayoutSplits,
+};
+
+const AMOUNT: u64 = 1_000_003;
+
+/// Индекс PDA сплитов первого игрока в аккаунтах force_refund.
+const PLAYER1_SPLITS: usize = 7;
+
+fn splits(player: Pubkey) -> PayoutSplits {
+    PayoutSplits {
+        player,
+        splits: vec![
+            PayoutSplit {
+                recipient: Pubkey::new_unique(),
+                bps: 7_000,
+            },
+            PayoutSplit {
+                recipient: Pubkey::new_unique(),
+                bps: 3_000,
+            },
+        ],
+        bump: pda(&[b"payout_splits", player.as_ref()]).1,
+    }
+}
+
+/// pay_out AMOUNT игроку player с аккаунтом сплитов splits; recipients —
+/// remaining. Возвращает балансы игрока и получателей после выплаты.
+fn pay(player: Pubkey, splits: Account, recipients: &[Pubkey]) -> anchor_lang::Result<Vec<u64>> {
+    install_sysvars();
+    let state = game(GameStatus::Finished);
+    let mut game_account = Account::owned(Pubkey::new_unique(), &state);
+    game_account.lamports = Rent::default().minimum_balance(game_account.data.len()) + AMOUNT;
+    let mut accounts = vec![game_account, Account::wallet(player, false), splits];
+    accounts.extend(recipients.iter().map(|&key| Account {
+        lamports: 0,
+        ..Account::wallet(key, false)
+    }));
+    let infos = infos(&mut accounts);
+    pay_out(
+        &infos[0],
+        infos[1].clone(),
+        &infos[2],
+        &mut infos[3..].iter(),
+        AMOUNT,
+    )?;
+    let mut balances = vec![infos[1].lamports()];
+    balances.extend(infos[3..].iter().map(|info| info.lamports()));
+    Ok(balances)
+}
+
+#[test]
+fn registered_splits_divide_the_payout() {
+    let player = Pubkey::new_unique();
+    let splits = splits(player);
+    let recipients: Vec<Pubkey> = splits.splits.iter().map(|s| s.recipient).collect();
+    let account = Account::owned(pda(&[b"payout_splits", player.as_ref()]).0, &splits);
+    let wallet = Account::wallet(player, false).lamports;
+
+    let balances = pay(player, account, &recipients).unwrap();
+    let shares = payout_shares(AMOUNT, &splits.splits);
+    assert_eq!(balances, [wallet, shares[0], shares[1]]);
+    assert_eq!(shares[0] + shares[1], AMOUNT);
+
+    // Получателей не хватает — выплата не проходит.
+    let account = Account::owned(pda(&[b"payout_splits", player.as_ref()]).0, &splits);
+    assert_eq!(
+        pay(player, account, &recipients[..1]).err(),
+        Some(ErrorCode::InvalidPayoutSplits.into())
+    );
+}
+
+#[test]
+fn uninitialized_splits_pay_the_player_directly() {
+    let player = Pubkey::new_unique();
+    let wallet = Account::wallet(player, false).lamports;
+    let balances = pay(player, no_splits(&player), &[]).unwrap();
+    assert_eq!(balances, [wallet + AMOUNT]);
+}
+
+#[test]
+fn splits_account_cannot_be_omitted_or_swapped() {
+    let state = game(GameStatus::Active);
+    let mut accounts = force_refund(&state, state.player1, state.player2);
+    try_accounts::<ForceRefund>(&mut accounts).unwrap();
+
+    // Вместо PDA — «отсутствующий» Option-аккаунт или PDA другого игрока.
+    for splits in [Account::none(), no_splits(&Pubkey::new_unique())] {
+        let mut accounts = force_refund(&state, state.player1, state.player2);
+        accounts[PLAYER1_SPLITS] = splits;
+        assert_eq!(
+            try_accounts::<ForceRefund>(&mut accounts).err(),
+            Some(anchor_lang::error::ErrorCode::ConstraintSeeds.into())
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Инвариант банка: на аккаунте игры не меньше, чем рента + pot_lamports +
+//! резервы автовзятий (за вычетом банка в пуле). Недостача ловится в конце
+//! ближайшей инструкции, а не при итоговой выплате.
+
+mod common;
+
+use anchor_lang::prelude::{
+    Account as AnchorAccount, Context, Program, Pubkey, Rent, Signer, UncheckedAccount,
+};
+use anchor_lang::{system_program, AnchorSerialize};
+use common::{
+    config_with, game, infos, install_sysvars, parse, signed_instructions, Account, SLOT,
+};
+use pooler::signing::{SignedAction, SignedMessage};
+use pooler::{
+    assert_pot_invariant, ErrorCode, GameState, GameStatus, MakeMove, MakeMoveBumps, RelayedMove,
+    RelayedMovePayload, RespondMove, RuleVariant, SetAutoTake,
+};
+
+const POT: u64 = 2_000_000;
+
+/// Аккаунт игры с банком POT и балансом rent + POT + reserves + drift.
+fn funded_game(state: &GameState, drift: i64) -> Account {
+    let mut account = Account::owned(Pubkey::new_unique(), state);
+    let rent = Rent::default().minimum_balance(account.data.len());
+    let held = rent + state.pot_lamports + state.p1_take_reserve + state.p2_take_reserve;
+    account.lamports = held.checked_add_signed(drift).unwrap();
+    account
+}
+
+fn pot_game() -> GameState {
+    let mut state = game(GameStatus::Active);
+    state.pot_lamports = POT;
+    state.player1_deposit = POT / 2;
+    state.player2_deposit = POT / 2;
+    state
+}
+
+fn check(account: &mut Account) -> anchor_lang::Result<()> {
+    install_sysvars();
+    let info = &infos(std::slice::from_mut(account))[0];
+    assert_pot_invariant(&AnchorAccount::try_from(info)?)
+}
+
+#[test]
+fn balance_covering_pot_and_rent_passes() {
+    check(&mut funded_game(&pot_game(), 0)).unwrap();
+    // Лишние lamports (прямой перевод на аккаунт) инвариант не нарушают.
+    check(&mut funded_game(&pot_game(), 5)).unwrap();
+
+    let mut reserved = pot_game();
+    reserved.p1_take_reserve = 300;
+    check(&mut funded_game(&reserved, 0)).unwrap();
+}
+
+#[test]
+fn shortfall_is_inconsistent_pot() {
+    assert_eq!(
+        check(&mut funded_game(&pot_game(), -1)).unwrap_err(),
+        ErrorCode::InconsistentPot.into()
+    );
+
+    // Резерв автовзятий лежит вне банка, но тоже должен быть на аккаунте.
+    let mut reserved = pot_game();
+    reserved.p1_take_reserve = 300;
+    let mut account = funded_game(&reserved, -300);
+    assert_eq!(
+        check(&mut account).unwrap_err(),
+        ErrorCode::InconsistentPot.into()
+    );
+}
+
+#[test]
+fn staked_principal_is_not_expected_on_the_account() {
+    let mut state = pot_game();
+    let mut account = funded_game(&state, -(POT as i64));
+    assert_eq!(
+        check(&mut account).unwrap_err(),
+        ErrorCode::InconsistentPot.into()
+    );
+
+    state.staked_lamports = POT;
+    check(&mut funded_game(&state, -(POT as i64))).unwrap();
+}
+
+/// Игра на ходу player1 со стартовой доской и доска после хода 3-1.
+fn move_game() -> (GameState, [i8; 24]) {
+    let mut state = pot_game();
+    state.current_turn = 1;
+    state
+        .set_board_points(&pooler::start_board(RuleVariant::Standard, 0).unwrap())
+        .unwrap();
+    let mut board = state.board_points().unwrap();
+    board[7] -= 1;
+    board[5] -= 1;
+    board[4] += 2;
+    (state, board)
+}
+
+/// make_move в бесплатном окне (без CPI) на аккаунте с отклонением drift.
+fn free_move(drift: i64) -> anchor_lang::Result<()> {
+    install_sysvars();
+    let (state, board) = move_game();
+    let game_account = funded_game(&state, drift);
+
+    let config = config_with(|config| config.free_moves = 10);
+
+    let mut accounts = vec![
+        game_account,
+        Account::wallet(state.player1, true),
+        Account::wallet(state.player2, false),
+        Account::program(system_program::ID),
+        config,
+    ];
+    let infos = infos(&mut accounts);
+    let mut ctx_accounts = MakeMove {
+        game: AnchorAccount::try_from(&infos[0])?,
+        mover: Signer::try_from(&infos[1])?,
+        opponent: UncheckedAccount::try_from(&infos[2]),
+        system_program: Program::try_from(&infos[3])?,
+        bot_vault: None,
+        config: AnchorAccount::try_from(&infos[4])?,
+        team: None,
+        time_control: None,
+        fee_route: None,
+        fee_recipient: None,
+        insurance_pool: None,
+        client_telemetry: None,
+        event_buffer: None,
+        game_history: None,
+        history_commitment: None,
+        decay_sink: None,
+    };
+    let ctx = Context::new(
+        &pooler::ID,
+        &mut ctx_accounts,
+        &[],
+        MakeMoveBumps::default(),
+    );
+    pooler::backgammon::make_move(ctx, board, [3, 1], [0; 32], None).map(drop)
+}
+
+/// relayed_move в бесплатном окне на аккаунте с отклонением drift.
+fn relayed_move(drift: i64) -> anchor_lang::Result<()> {
+    let (state, board) = move_game();
+    let game_account = funded_game(&state, drift);
+    let game_key = game_account.key;
+    let payload = RelayedMovePayload {
+        board_points: board,
+        dice: [3, 1],
+        annotation_hash: [0; 32],
+        expected_move_index: state.move_index,
+    };
+    let message = SignedMessage::new(
+        game_key,
+        SignedAction::RelayedMove,
+        0,
+        SLOT,
+        &payload.try_to_vec().unwrap(),
+    );
+    let mut accounts = vec![
+        game_account,
+        Account::wallet(Pubkey::new_unique(), true),
+        config_with(|config| config.free_moves = 10),
+    ];
+    // relay_budget, time_control, fee_route, fee_recipient.
+    accounts.extend((0..4).map(|_| Account::none()));
+    accounts.push(signed_instructions(&[state.player1], &message));
+    // insurance_pool, event_buffer, game_history, history_commitment,
+    // decay_sink.
+    accounts.extend((0..5).map(|_| Account::none()));
+    let (mut parsed, bumps) = parse::<RelayedMove>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::relayed_move(ctx, payload, 0, SLOT, [0; 64]).map(drop)
+}
+
+#[test]
+fn drift_fails_the_next_instruction() {
+    free_move(0).unwrap();
+    assert_eq!(
+        free_move(-1).unwrap_err(),
+        ErrorCode::InconsistentPot.into()
+    );
+}
+
+#[test]
+fn drift_fails_a_relayed_move() {
+    relayed_move(0).unwrap();
+    assert_eq!(
+        relayed_move(-1).unwrap_err(),
+        ErrorCode::InconsistentPot.into()
+    );
+}
+
+/// ack_move отложенного хода player1 на аккаунте с отклонением drift.
+fn ack_move(drift: i64) -> anchor_lang::Result<()> {
+    install_sysvars();
+    let (mut state, board) = move_game();
+    state.move_pending = true;
+    state.set_pending_board_points(&board).unwrap();
+    state.pending_dice = [3, 1];
+    let game_account = funded_game(&state, drift);
+    let mut accounts = [game_account, Account::wallet(state.player2, true)];
+    let (mut parsed, bumps) = parse::<RespondMove>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::ack_move(ctx)
+}
+
+/// set_auto_take(0) без резерва (без переводов) с отклонением drift.
+fn clear_auto_take(drift: i64) -> anchor_lang::Result<()> {
+    install_sysvars();
+    let state = pot_game();
+    let game_account = funded_game(&state, drift);
+    let mut accounts = [
+        game_account,
+        Account::wallet(state.player1, true),
+        Account::program(system_program::ID),
+    ];
+    let (mut parsed, bumps) = parse::<SetAutoTake>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::set_auto_take(ctx, 0)
+}
+
+#[test]
+fn drift_fails_a_move_confirmation() {
+    ack_move(0).unwrap();
+    assert_eq!(ack_move(-1).unwrap_err(), ErrorCode::InconsistentPot.into());
+}
+
+#[test]
+fn drift_fails_an_auto_take_change() {
+    clear_auto_take(0).unwrap();
+    assert_eq!(
+        clear_auto_take(-1).unwrap_err(),
+        ErrorCode::InconsistentPot.into()
+    );
+}
@@ -0,0 +1,272 @@
+//! Банк в SPL Stake Pool: инструкции пула собираются в раскладке
+//! spl_stake_pool (DepositSol / WithdrawSol), а путь stake → доход → вывод →
+//! выплата держит инвариант банка: пока банк в пуле, его principal не
+//! ожидается на аккаунте игры и выплатить его нельзя; после вывода доход
+//! входит в банк и уходит победителю (при возврате — по вкладам игроков),
+//! потеря делится по депозитам, а сверх них — по комиссиям и чаевым. Пока
+//! банк в пуле (game.staked_lamports), выплаты и возвраты отклоняются с
+//! PotStaked.
+
+mod common;
+
+use anchor_lang::prelude::{
+    Account as AnchorAccount, AccountMeta, AnchorSerialize, Context, Pubkey, Rent,
+};
+use anchor_lang::system_program;
+use common::{finish_game, force_refund, game, infos, install_sysvars, no_splits, parse, Account};
+use pooler::{
+    assert_pot_invariant, credit, debit, pay_out, ErrorCode, FinishGame, ForceRefund, GameState,
+    GameStatus, Remainder, StakePoolAccounts, StakePoolInstruction, SPL_STAKE_POOL_PROGRAM_ID,
+};
+
+const POT: u64 = 2_000_000;
+const POOL_TOKENS: u64 = 1_900_000;
+const YIELD: u64 = 12_345;
+
+fn pool() -> StakePoolAccounts {
+    StakePoolAccounts {
+        stake_pool: Pubkey::new_unique(),
+        withdraw_authority: Pubkey::new_unique(),
+        reserve_stake: Pubkey::new_unique(),
+        manager_fee_account: Pubkey::new_unique(),
+        pool_mint: Pubkey::new_unique(),
+    }
+}
+
+#[test]
+fn instructions_use_the_stake_pool_layout() {
+    assert_eq!(
+        StakePoolInstruction::DepositSol(POT).try_to_vec().unwrap(),
+        [[14].as_slice(), &POT.to_le_bytes()].concat()
+    );
+    assert_eq!(
+        StakePoolInstruction::WithdrawSol(POOL_TOKENS)
+            .try_to_vec()
+            .unwrap(),
+        [[16].as_slice(), &POOL_TOKENS.to_le_bytes()].concat()
+    );
+
+    let pool = pool();
+    let (vault, tokens) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let deposit = pool.deposit_sol(&vault, &tokens, POT).unwrap();
+    assert_eq!(deposit.program_id, SPL_STAKE_POOL_PROGRAM_ID);
+    assert_eq!(deposit.data[0], 14);
+    assert_eq!(
+        deposit.accounts,
+        [
+            AccountMeta::new(pool.stake_pool, false),
+            AccountMeta::new_readonly(pool.withdraw_authority, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new(vault, true),
+            AccountMeta::new(tokens, false),
+            AccountMeta::new(pool.manager_fee_account, false),
+            AccountMeta::new(tokens, false),
+            AccountMeta::new(pool.pool_mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ]
+    );
+
+    let stake = Pubkey::new_unique();
+    let withdraw = pool
+        .withdraw_sol(&stake, &tokens, &vault, POOL_TOKENS)
+        .unwrap();
+    assert_eq!(withdraw.program_id, SPL_STAKE_POOL_PROGRAM_ID);
+    assert_eq!(withdraw.data[0], 16);
+    assert_eq!(
+        withdraw.accounts,
+        [
+            AccountMeta::new(pool.stake_pool, false),
+            AccountMeta::new_readonly(pool.withdraw_authority, false),
+            AccountMeta::new_readonly(stake, true),
+            AccountMeta::new(tokens, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(pool.manager_fee_account, false),
+            AccountMeta::new(pool.pool_mint, false),
+            AccountMeta::new_readonly(solana_sdk_ids::sysvar::clock::ID, false),
+            AccountMeta::new_readonly(solana_sdk_ids::sysvar::stake_history::ID, false),
+            AccountMeta::new_readonly(solana_sdk_ids::stake::ID, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ]
+    );
+}
+
+fn pot_game() -> GameState {
+    let mut state = game(GameStatus::Active);
+    state.pot_lamports = POT;
+    state.player1_deposit = POT / 2;
+    state.player2_deposit = POT / 2;
+    state
+}
+
+/// Аккаунты пути: игра с балансом rent + POT и победитель без сплитов.
+fn accounts(state: &GameState) -> Vec<Account> {
+    let mut game_account = Account::owned(Pubkey::new_unique(), state);
+    game_account.lamports = Rent::default().minimum_balance(game_account.data.len()) + POT;
+    vec![
+        game_account,
+        Account {
+            lamports: 0,
+            ..Account::wallet(state.player1, false)
+        },
+        no_splits(&state.player1),
+    ]
+}
+
+/// Проходит stake → вывод с возвратом returned → выплату банка победителю;
+/// возвращает игру после вывода и сумму, полученную победителем.
+fn settle_after_unstake(state: GameState, returned: u64) -> (GameState, u64) {
+    install_sysvars();
+    let mut accounts = accounts(&state);
+    let infos = infos(&mut accounts);
+    let mut game = AnchorAccount::<GameState>::try_from(&infos[0]).unwrap();
+
+    // join_game с options.stake_pot: банк уходит с аккаунта игры в пул.
+    debit(&infos[0], POT).unwrap();
+    assert_eq!(
+        assert_pot_invariant(&game).unwrap_err(),
+        ErrorCode::InconsistentPot.into()
+    );
+    game.staked_lamports = POT;
+    assert_pot_invariant(&game).unwrap();
+    // Пока банк в пуле, выплатить его не из чего.
+    assert_eq!(
+        pay_out(
+            &infos[0],
+            infos[1].clone(),
+            &infos[2],
+            &mut [].iter(),
+            game.pot_lamports
+        )
+        .unwrap_err(),
+        ErrorCode::RentExemptViolation.into()
+    );
+
+    // claim_pot_unstake: пул возвращает returned, банк пересчитывается.
+    credit(&infos[0], returned).unwrap();
+    game.apply_stake_result(POT, returned).unwrap();
+    game.staked_lamports = 0;
+    assert_pot_invariant(&game).unwrap();
+
+    // Расчёт: весь банк победителю.
+    game.status = GameStatus::Finished;
+    pay_out(
+        &infos[0],
+        infos[1].clone(),
+        &infos[2],
+        &mut [].iter(),
+        game.pot_lamports,
+    )
+    .unwrap();
+    assert_eq!(
+        infos[0].lamports(),
+        Rent::default().minimum_balance(infos[0].data_len())
+    );
+    (game.into_inner(), infos[1].lamports())
+}
+
+#[test]
+fn accrued_yield_goes_to_the_winner() {
+    let (state, paid) = settle_after_unstake(pot_game(), POT + YIELD);
+    assert_eq!(state.pot_lamports, POT + YIELD);
+    assert_eq!(state.tips_lamports, 0);
+    assert_eq!(paid, POT + YIELD);
+}
+
+#[test]
+fn refunded_yield_follows_the_contributions() {
+    // Вклады 3:1 (депозит + комиссии), чаевые делятся пополам отдельно.
+    let mut state = pot_game();
+    state.player1_deposit = 1_000_000;
+    state.player1_fees_paid = 200_000;
+    state.player2_deposit = 300_000;
+    state.player2_fees_paid = 100_000;
+    state.tips_lamports = 400_000;
+
+    let (state, _) = settle_after_unstake(state, POT + YIELD);
+    assert_eq!(state.tips_lamports, 400_000);
+    // 12_345 * 3/4 = 9_258, остаток от деления — player1.
+    assert_eq!(state.player1_deposit, 1_000_000 + 9_259);
+    assert_eq!(state.player2_deposit, 300_000 + 3_086);
+    let (refund_p1, refund_p2, _) = state.refund_totals(Remainder::First).unwrap();
+    assert_eq!(refund_p1, 1_200_000 + 9_259 + 200_000);
+    assert_eq!(refund_p2, 400_000 + 3_086 + 200_000);
+    assert_eq!(refund_p1 + refund_p2, state.pot_lamports);
+
+    // Без вкладов (только чаевые) доход остаётся чаевыми.
+    let mut state = pot_game();
+    state.player1_deposit = 0;
+    state.player2_deposit = 0;
+    state.tips_lamports = POT;
+    let (state, _) = settle_after_unstake(state, POT + YIELD);
+    assert_eq!(state.tips_lamports, POT + YIELD);
+}
+
+#[test]
+fn payouts_and_refunds_wait_for_the_unstake() {
+    install_sysvars();
+    let mut state = pot_game();
+    state.staked_lamports = POT;
+    let (p1, p2) = (state.player1, state.player2);
+
+    let mut accounts = finish_game(&state, p1, p2);
+    let (mut parsed, bumps) = parse::<FinishGame>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::finish_game(ctx, p1, 1).unwrap_err(),
+        ErrorCode::PotStaked.into()
+    );
+
+    let mut accounts = force_refund(&state, p1, p2);
+    let (mut parsed, bumps) = parse::<ForceRefund>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::force_refund(ctx).unwrap_err(),
+        ErrorCode::PotStaked.into()
+    );
+}
+
+#[test]
+fn pool_loss_is_split_across_deposits() {
+    let loss = 1_001;
+    let (state, paid) = settle_after_unstake(pot_game(), POT - loss);
+    assert_eq!(state.pot_lamports, POT - loss);
+    assert_eq!(paid, POT - loss);
+    // Остаток от деления — player1.
+    assert_eq!(state.player1_deposit, POT / 2 - 501);
+    assert_eq!(state.player2_deposit, POT / 2 - 500);
+    let (refund_p1, refund_p2, _) = state.refund_totals(Remainder::First).unwrap();
+    assert_eq!(refund_p1 + refund_p2, state.pot_lamports);
+}
+
+#[test]
+fn pool_loss_beyond_deposits_reaches_fees_and_tips() {
+    // Банк: депозиты 1_000_000, комиссии 700_000, чаевые 300_000.
+    let mut state = pot_game();
+    state.player1_deposit = 500_000;
+    state.player2_deposit = 500_000;
+    state.player1_fees_paid = 400_000;
+    state.player2_fees_paid = 300_000;
+    state.tips_lamports = 300_000;
+
+    // Потеря 1_500_000 больше депозитов: вывод всё равно проходит.
+    let (state, paid) = settle_after_unstake(state, 500_000);
+    assert_eq!(state.pot_lamports, 500_000);
+    assert_eq!(paid, 500_000);
+    assert_eq!((state.player1_deposit, state.player2_deposit), (0, 0));
+    // Остальные 500_000 — с комиссий пропорционально, остаток — player1.
+    assert_eq!(state.player1_fees_paid, 400_000 - 285_715);
+    assert_eq!(state.player2_fees_paid, 300_000 - 214_285);
+    assert_eq!(state.tips_lamports, 300_000);
+    let (refund_p1, refund_p2, _) = state.refund_totals(Remainder::First).unwrap();
+    assert_eq!(refund_p1 + refund_p2, state.pot_lamports);
+
+    // Пул не вернул ничего: банк обнуляется вместе с чаевыми.
+    let mut state = pot_game();
+    state.player1_fees_paid = 1;
+    state.player1_deposit -= 1;
+    let (state, paid) = settle_after_unstake(state, 0);
+    assert_eq!((state.pot_lamports, paid), (0, 0));
+    assert_eq!(state.refund_totals(Remainder::First).unwrap(), (0, 0, 0));
+}
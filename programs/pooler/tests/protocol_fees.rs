@@ -0,0 +1,305 @@
+//! Комиссия протокола: копится в FeeVault с выигрышей нескольких игр,
+//! выводится частями на config.treasury, доля config.burn_bps сжигается, а
+//! collected - withdrawn - burned всегда равно балансу хранилища сверх ренты.
+//! Удерживается на каждом пути выплаты победителю, а не только в finish_game.
+
+mod common;
+
+use anchor_lang::prelude::{
+    Account as AnchorAccount, Context, Pubkey, Rent, Signer, ToAccountInfo, UncheckedAccount,
+};
+use anchor_lang::Space;
+use common::{
+    config_with, event_cpi, game, infos, install_sysvars, no_splits, parse, pda, Account, SLOT,
+};
+use pooler::{
+    burn_share, collect_protocol_fee, protocol_fee, DropDouble, ErrorCode, FeeVault, FinalizeClaim,
+    GameState, GameStatus, UpdateConfig, UpdateConfigBumps, WinClaim, WithdrawProtocolFees,
+    WithdrawProtocolFeesBumps, BPS_DENOMINATOR, INCINERATOR, MAX_PROTOCOL_FEE_BPS,
+};
+
+const FEE_BPS: u16 = 250;
+/// Выигрыши двух игр; второй не делится на 10 000 без остатка.
+const WINNINGS: [u64; 2] = [212_000_000, 57_000_003];
+const BURN_BPS: u16 = 3_000;
+
+fn vault_rent() -> u64 {
+    Rent::default().minimum_balance(8 + FeeVault::INIT_SPACE)
+}
+
+/// Аккаунт игры, на котором лежит выигрыш winnings сверх ренты.
+fn settled_game(winnings: u64) -> Account {
+    let mut state = game(GameStatus::Active);
+    state.pot_lamports = winnings;
+    let mut account = Account::owned(Pubkey::new_unique(), &state);
+    account.lamports = Rent::default().minimum_balance(account.data.len()) + winnings;
+    account
+}
+
+/// Пустое хранилище с рентой.
+fn empty_vault() -> Account {
+    let (key, bump) = pda(&[b"fee_vault"]);
+    let mut vault = Account::owned(
+        key,
+        &FeeVault {
+            collected: 0,
+            withdrawn: 0,
+            burned: 0,
+            bump,
+        },
+    );
+    vault.lamports = vault_rent();
+    vault
+}
+
+/// Счётчики хранилища сходятся с его балансом.
+fn assert_invariant(vault: &AnchorAccount<FeeVault>) {
+    assert_eq!(
+        vault.collected - vault.withdrawn - vault.burned,
+        vault.to_account_info().lamports() - vault_rent()
+    );
+}
+
+#[test]
+fn fees_from_two_games_are_withdrawn_in_parts() {
+    install_sysvars();
+    let (admin, treasury) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut accounts = vec![
+        config_with(|config| {
+            config.admin = admin;
+            config.treasury = treasury;
+            config.protocol_fee_bps = FEE_BPS;
+        }),
+        empty_vault(),
+        Account::wallet(treasury, false),
+        Account::wallet(admin, true),
+        settled_game(WINNINGS[0]),
+        settled_game(WINNINGS[1]),
+    ];
+    let infos = infos(&mut accounts);
+    let mut ctx_accounts = WithdrawProtocolFees {
+        config: AnchorAccount::try_from(&infos[0]).unwrap(),
+        fee_vault: AnchorAccount::try_from(&infos[1]).unwrap(),
+        treasury: UncheckedAccount::try_from(&infos[2]),
+        admin: Signer::try_from(&infos[3]).unwrap(),
+    };
+
+    let mut collected = 0;
+    for (info, winnings) in infos[4..].iter().zip(WINNINGS) {
+        let game = AnchorAccount::try_from(info).unwrap();
+        let before = info.lamports();
+        let fee = protocol_fee(winnings, FEE_BPS);
+        assert_eq!(fee, winnings * FEE_BPS as u64 / 10_000);
+        collect_protocol_fee(&game, Some(&mut ctx_accounts.fee_vault), None, fee, 0).unwrap();
+        assert_eq!(info.lamports(), before - fee);
+        collected += fee;
+        assert_invariant(&ctx_accounts.fee_vault);
+    }
+    assert_eq!(ctx_accounts.fee_vault.collected, collected);
+
+    let treasury_before = infos[2].lamports();
+    let mut withdraw = |amount| {
+        let ctx = Context::new(
+            &pooler::ID,
+            &mut ctx_accounts,
+            &[],
+            WithdrawProtocolFeesBumps::default(),
+        );
+        let result = pooler::backgammon::withdraw_protocol_fees(ctx, amount);
+        assert_invariant(&ctx_accounts.fee_vault);
+        result
+    };
+
+    let part = collected / 3;
+    withdraw(part).unwrap();
+    assert_eq!(infos[2].lamports(), treasury_before + part);
+
+    // Больше остатка вывести нельзя; счётчики и балансы не меняются.
+    assert_eq!(
+        withdraw(collected - part + 1).unwrap_err(),
+        ErrorCode::InsufficientProtocolFees.into()
+    );
+    assert_eq!(infos[2].lamports(), treasury_before + part);
+
+    withdraw(collected - part).unwrap();
+    assert_eq!(infos[2].lamports(), treasury_before + collected);
+    assert_eq!(infos[1].lamports(), vault_rent());
+    assert_eq!(
+        withdraw(1).unwrap_err(),
+        ErrorCode::InsufficientProtocolFees.into()
+    );
+}
+
+#[test]
+fn burned_share_goes_to_incinerator() {
+    install_sysvars();
+    let mut accounts = [
+        empty_vault(),
+        Account::wallet(INCINERATOR, false),
+        settled_game(WINNINGS[1]),
+    ];
+    let infos = infos(&mut accounts);
+    let mut vault = AnchorAccount::try_from(&infos[0]).unwrap();
+    let game = AnchorAccount::try_from(&infos[2]).unwrap();
+
+    // 57 000 003 * 2,5% = 1 425 000 (округление вниз), 30% из них сжигается.
+    let fee = protocol_fee(WINNINGS[1], FEE_BPS);
+    assert_eq!(fee, 1_425_000);
+    assert_eq!(burn_share(fee, BURN_BPS), 427_500);
+
+    // Без incinerator сжечь нечем.
+    assert_eq!(
+        collect_protocol_fee(&game, Some(&mut vault), None, fee, BURN_BPS).unwrap_err(),
+        ErrorCode::MissingIncinerator.into()
+    );
+
+    let (before, incinerated) = (infos[2].lamports(), infos[1].lamports());
+    let burned =
+        collect_protocol_fee(&game, Some(&mut vault), Some(&infos[1]), fee, BURN_BPS).unwrap();
+    assert_eq!(burned, 427_500);
+    assert_eq!(infos[2].lamports(), before - fee);
+    assert_eq!(infos[1].lamports(), incinerated + burned);
+    assert_eq!(infos[0].lamports(), vault_rent() + fee - burned);
+    assert_eq!((vault.collected, vault.burned), (fee, burned));
+    assert_eq!(vault.available(), 997_500);
+    assert_invariant(&vault);
+
+    // При burn_bps = 100% в хранилище остаётся только учёт.
+    let burned = collect_protocol_fee(
+        &game,
+        Some(&mut vault),
+        Some(&infos[1]),
+        fee,
+        BPS_DENOMINATOR as u16,
+    )
+    .unwrap();
+    assert_eq!(burned, fee);
+    assert_eq!(infos[1].lamports(), incinerated + 427_500 + fee);
+    assert_eq!(vault.available(), 997_500);
+    assert_invariant(&vault);
+}
+
+#[test]
+fn protocol_fee_and_burn_are_capped() {
+    let admin = Pubkey::new_unique();
+    let mut accounts = [
+        config_with(|config| config.admin = admin),
+        Account::wallet(admin, true),
+    ];
+    let infos = infos(&mut accounts);
+    let mut ctx_accounts = UpdateConfig {
+        config: AnchorAccount::try_from(&infos[0]).unwrap(),
+        admin: Signer::try_from(&infos[1]).unwrap(),
+    };
+    let mut update = |update| {
+        let ctx = Context::new(
+            &pooler::ID,
+            &mut ctx_accounts,
+            &[],
+            UpdateConfigBumps::default(),
+        );
+        pooler::backgammon::update_config(ctx, update)
+    };
+    let fee = |bps| pooler::ConfigUpdate {
+        protocol_fee_bps: Some(bps),
+        ..Default::default()
+    };
+    let burn = |bps| pooler::ConfigUpdate {
+        burn_bps: Some(bps),
+        ..Default::default()
+    };
+
+    update(fee(MAX_PROTOCOL_FEE_BPS)).unwrap();
+    assert_eq!(
+        update(fee(MAX_PROTOCOL_FEE_BPS + 1)).unwrap_err(),
+        ErrorCode::InvalidProtocolFee.into()
+    );
+
+    // Сжечь можно не больше всей комиссии.
+    update(burn(BPS_DENOMINATOR as u16)).unwrap();
+    assert_eq!(
+        update(burn(BPS_DENOMINATOR as u16 + 1)).unwrap_err(),
+        ErrorCode::InvalidBurnShare.into()
+    );
+    assert_eq!(ctx_accounts.config.protocol_fee_bps, MAX_PROTOCOL_FEE_BPS);
+    assert_eq!(ctx_accounts.config.burn_bps, BPS_DENOMINATOR as u16);
+}
+
+/// Аккаунт игры, на котором лежит банк state сверх ренты.
+fn game_account(state: &GameState) -> Account {
+    let mut account = Account::owned(Pubkey::new_unique(), state);
+    account.lamports = Rent::default().minimum_balance(account.data.len()) + state.pot_lamports;
+    account
+}
+
+fn fee_config() -> Account {
+    config_with(|config| config.protocol_fee_bps = FEE_BPS)
+}
+
+// Сами выплаты эмитят события через emit_cpi!, а это CPI, которое вне
+// валидатора не выполнить (их проверяет tests/lifecycle.rs в LiteSVM). Здесь —
+// что без FeeVault банк не уходит победителю в обход комиссии: комиссия
+// снимается раньше выплаты и событий.
+
+#[test]
+fn drop_double_requires_the_fee_vault() {
+    let mut state = game(GameStatus::Active);
+    state.pot_lamports = WINNINGS[1];
+    state.double_pending = true;
+    let mut accounts = vec![
+        game_account(&state),
+        Account::wallet(state.player2, true),
+        Account::wallet(state.player1, false),
+        fee_config(),
+    ];
+    // season, stats ×2, escrow_agent, event_buffer, fee_vault, incinerator.
+    accounts.extend((0..7).map(|_| Account::none()));
+    accounts.push(no_splits(&state.player1));
+    accounts.extend(event_cpi());
+    let (mut parsed, bumps) = parse::<DropDouble>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::drop_double(ctx).unwrap_err(),
+        ErrorCode::MissingFeeVault.into()
+    );
+}
+
+#[test]
+fn finalize_claim_requires_the_fee_vault() {
+    let mut state = game(GameStatus::Active);
+    state.pot_lamports = WINNINGS[0];
+    state.win_claim_pending = true;
+    let game_account = game_account(&state);
+    let (claim_key, bump) = pda(&[b"win_claim", game_account.key.as_ref()]);
+    let claim = WinClaim {
+        game: game_account.key,
+        claimant: state.player2,
+        final_board: [0; 24],
+        deadline_slot: SLOT - 1,
+        bond_lamports: 0,
+        bump,
+    };
+    let mut accounts = vec![
+        game_account,
+        Account::owned(claim_key, &claim),
+        Account::wallet(state.player2, false),
+        fee_config(),
+        // season, stats ×2, bot_vault, team.
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::none(),
+        Account::wallet(Pubkey::new_unique(), true),
+    ];
+    // event_buffer, fee_vault, incinerator, winner.
+    accounts.extend((0..4).map(|_| Account::none()));
+    accounts.push(no_splits(&state.player2));
+    accounts.extend(event_cpi());
+    let (mut parsed, bumps) = parse::<FinalizeClaim>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    assert_eq!(
+        pooler::backgammon::finalize_claim(ctx).unwrap_err(),
+        ErrorCode::MissingFeeVault.into()
+    );
+}
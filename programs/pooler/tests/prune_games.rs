@@ -0,0 +1,299 @@
+//! prune_games: из смешанной пачки закрываются только старые завершённые
+//! игры с пустым банком, без резервов автовзятий и нерассчитанных пари;
+//! вызвавший получает долю ренты, player1 — весь остальной баланс.
+
+mod common;
+
+use anchor_lang::prelude::{Account as AnchorAccount, Context, Pubkey, Rent, Signer};
+use anchor_lang::AccountDeserialize;
+use common::{config, config_with, game, infos, install_sysvars, parse, pda, set_slot, Account};
+use pooler::{
+    prune_reward, ErrorCode, GameState, GameStatus, PruneGames, PruneGamesBumps, SettleSideBet,
+    SideBet, SideBetStatus, DEFAULT_PRUNE_AFTER_SLOTS, MAX_PRUNE_BATCH, MIN_PRUNE_AFTER_SLOTS,
+};
+
+const REWARD_BPS: u16 = 1_000;
+const FINISHED: u64 = 10_000;
+/// Текущий слот: для игры, завершённой в FINISHED, срок хранения истёк.
+const NOW: u64 = FINISHED + DEFAULT_PRUNE_AFTER_SLOTS;
+
+fn finished_game() -> GameState {
+    let mut state = game(GameStatus::Finished);
+    state.finished_slot = FINISHED;
+    state.last_activity_slot = FINISHED;
+    state
+}
+
+/// Пара (игра, плательщик ренты) с рентой на аккаунте игры.
+fn pair(state: &GameState) -> [Account; 2] {
+    let mut account = Account::owned(Pubkey::new_unique(), state);
+    account.lamports = Rent::default().minimum_balance(account.data.len());
+    [account, Account::wallet(state.player1, false)]
+}
+
+fn prune(accounts: &mut [Account]) -> anchor_lang::Result<()> {
+    let infos = infos(accounts);
+    let mut ctx_accounts = PruneGames {
+        config: AnchorAccount::try_from(&infos[0]).unwrap(),
+        caller: Signer::try_from(&infos[1]).unwrap(),
+    };
+    let ctx = Context::new(
+        &pooler::ID,
+        &mut ctx_accounts,
+        &infos[2..],
+        PruneGamesBumps::default(),
+    );
+    pooler::backgammon::prune_games(ctx)
+}
+
+#[test]
+fn mixed_batch_closes_only_old_finished_games() {
+    install_sysvars();
+    set_slot(NOW);
+
+    let cancelled = {
+        let mut state = finished_game();
+        state.status = GameStatus::Cancelled;
+        state
+    };
+    let active = {
+        let mut state = finished_game();
+        state.status = GameStatus::Active;
+        state
+    };
+    let with_pot = {
+        let mut state = finished_game();
+        state.pot_lamports = 1;
+        state
+    };
+    let recent = {
+        let mut state = finished_game();
+        state.finished_slot = FINISHED + 1;
+        state
+    };
+    let claim_pending = {
+        let mut state = finished_game();
+        state.win_claim_pending = true;
+        state
+    };
+    let team = {
+        let mut state = finished_game();
+        state.team_game = true;
+        state
+    };
+
+    let mut wrong_payer = pair(&finished_game());
+    wrong_payer[1] = Account::wallet(Pubkey::new_unique(), false);
+    let mut foreign = pair(&finished_game());
+    foreign[0].owner = Pubkey::new_unique();
+
+    let caller = Pubkey::new_unique();
+    let mut accounts = vec![
+        config_with(|config| config.prune_reward_bps = REWARD_BPS),
+        Account::wallet(caller, true),
+    ];
+    accounts.extend(pair(&finished_game()));
+    accounts.extend(pair(&active));
+    accounts.extend(pair(&cancelled));
+    accounts.extend(pair(&with_pot));
+    accounts.extend(pair(&recent));
+    accounts.extend(pair(&claim_pending));
+    accounts.extend(pair(&team));
+    accounts.extend(wrong_payer);
+    accounts.extend(foreign);
+    let before: Vec<u64> = accounts.iter().map(|a| a.lamports).collect();
+
+    prune(&mut accounts).unwrap();
+
+    // Закрыты первая (Finished) и третья (Cancelled) пары.
+    let closed = [2, 6];
+    let mut reward = 0;
+    for &i in &closed {
+        let rent = before[i];
+        let share = prune_reward(rent, REWARD_BPS);
+        assert_eq!(share, rent / 10);
+        assert_eq!(accounts[i].lamports, 0);
+        assert_eq!(accounts[i].owner, anchor_lang::system_program::ID);
+        assert!(accounts[i].data.iter().all(|&b| b == 0));
+        assert_eq!(accounts[i + 1].lamports, before[i + 1] + rent - share);
+        reward += share;
+    }
+    assert_eq!(accounts[1].lamports, before[1] + reward);
+
+    // Остальные пары не тронуты.
+    for i in (2..accounts.len()).filter(|i| !closed.contains(i) && !closed.contains(&(i - 1))) {
+        assert_eq!(accounts[i].lamports, before[i], "account {i}");
+    }
+    for i in (4..accounts.len())
+        .step_by(2)
+        .filter(|i| !closed.contains(i))
+    {
+        assert_eq!(
+            accounts[i].owner != pooler::ID,
+            i == accounts.len() - 2,
+            "game {i}"
+        );
+        GameState::try_deserialize(&mut &accounts[i].data[..]).unwrap();
+    }
+}
+
+#[test]
+fn retention_follows_config() {
+    install_sysvars();
+    let run = |prune_after_slots, now| {
+        set_slot(now);
+        let mut accounts = vec![
+            config_with(|config| config.prune_after_slots = prune_after_slots),
+            Account::wallet(Pubkey::new_unique(), true),
+        ];
+        accounts.extend(pair(&finished_game()));
+        prune(&mut accounts).unwrap();
+        accounts[2].lamports == 0
+    };
+
+    // Срок короче минимального поднимается до MIN_PRUNE_AFTER_SLOTS.
+    assert!(!run(1, FINISHED + MIN_PRUNE_AFTER_SLOTS - 1));
+    assert!(run(1, FINISHED + MIN_PRUNE_AFTER_SLOTS));
+    assert!(!run(0, NOW - 1));
+    assert!(run(0, NOW));
+}
+
+#[test]
+fn oversized_batch_is_rejected() {
+    install_sysvars();
+    set_slot(NOW);
+    let mut accounts = vec![
+        config_with(|_| {}),
+        Account::wallet(Pubkey::new_unique(), true),
+    ];
+    for _ in 0..=MAX_PRUNE_BATCH {
+        accounts.extend(pair(&finished_game()));
+    }
+    assert_eq!(
+        prune(&mut accounts).unwrap_err(),
+        ErrorCode::InvalidPruneBatch.into()
+    );
+
+    // Непарный хвост тоже отклоняется.
+    let mut accounts = vec![
+        config_with(|_| {}),
+        Account::wallet(Pubkey::new_unique(), true),
+    ];
+    accounts.extend(pair(&finished_game()));
+    accounts.push(Account::wallet(Pubkey::new_unique(), false));
+    assert_eq!(
+        prune(&mut accounts).unwrap_err(),
+        ErrorCode::InvalidPruneBatch.into()
+    );
+}
+
+#[test]
+fn reserves_and_side_bets_block_pruning() {
+    install_sysvars();
+    set_slot(NOW);
+    let reserve = |player: u8| {
+        let mut state = finished_game();
+        if player == 1 {
+            state.p1_take_reserve = 500;
+        } else {
+            state.p2_take_reserve = 500;
+        }
+        state
+    };
+    let side_bets = {
+        let mut state = finished_game();
+        state.unsettled_side_bets = 1;
+        state
+    };
+    let mut accounts = vec![
+        config_with(|config| config.prune_reward_bps = REWARD_BPS),
+        Account::wallet(Pubkey::new_unique(), true),
+    ];
+    accounts.extend(pair(&reserve(1)));
+    accounts.extend(pair(&reserve(2)));
+    accounts.extend(pair(&side_bets));
+    let before: Vec<u64> = accounts.iter().map(|a| a.lamports).collect();
+
+    prune(&mut accounts).unwrap();
+    for (i, account) in accounts.iter().enumerate() {
+        assert_eq!(account.lamports, before[i], "account {i}");
+    }
+    for i in (2..accounts.len()).step_by(2) {
+        assert_eq!(accounts[i].owner, pooler::ID, "game {i}");
+    }
+}
+
+#[test]
+fn extra_lamports_go_to_the_rent_payer() {
+    install_sysvars();
+    set_slot(NOW);
+    // Перевод 1 lamport не защищает игру от закрытия.
+    let mut extra = pair(&finished_game());
+    extra[0].lamports += 1;
+    let rent = extra[0].lamports - 1;
+
+    let mut accounts = vec![
+        config_with(|config| config.prune_reward_bps = REWARD_BPS),
+        Account::wallet(Pubkey::new_unique(), true),
+    ];
+    accounts.extend(extra);
+    let before: Vec<u64> = accounts.iter().map(|a| a.lamports).collect();
+
+    prune(&mut accounts).unwrap();
+    let reward = prune_reward(rent, REWARD_BPS);
+    assert_eq!(accounts[2].lamports, 0);
+    assert_eq!(accounts[2].owner, anchor_lang::system_program::ID);
+    assert_eq!(accounts[1].lamports, before[1] + reward);
+    assert_eq!(accounts[3].lamports, before[3] + rent + 1 - reward);
+}
+
+/// settle_side_bet по завершённой игре; возвращает её unsettled_side_bets.
+fn settle_side_bet(unsettled_side_bets: u16) -> u16 {
+    install_sysvars();
+    let mut state = finished_game();
+    state.winner = state.player1;
+    state.unsettled_side_bets = unsettled_side_bets;
+    let game_key = Pubkey::new_unique();
+    let (bettor, counterparty) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (side_bet, bump) = pda(&[
+        b"side_bet",
+        game_key.as_ref(),
+        bettor.as_ref(),
+        &7u64.to_le_bytes(),
+    ]);
+    let mut side_bet = Account::owned(
+        side_bet,
+        &SideBet {
+            game: game_key,
+            bettor,
+            counterparty,
+            bet_id: 7,
+            predicted_winner: state.player1,
+            predicted_player: 1,
+            amount: 1_000,
+            status: SideBetStatus::Matched,
+            bump,
+        },
+    );
+    side_bet.lamports += 2_000;
+    let mut accounts = [
+        Account::owned(game_key, &state),
+        side_bet,
+        Account::wallet(bettor, false),
+        Account::wallet(counterparty, false),
+        Account::wallet(Pubkey::new_unique(), true),
+        config(),
+    ];
+    let (mut parsed, bumps) = parse::<SettleSideBet>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::settle_side_bet(ctx).unwrap();
+    parsed.game.unsettled_side_bets
+}
+
+#[test]
+fn settled_side_bets_release_the_game() {
+    assert_eq!(settle_side_bet(2), 1);
+    assert_eq!(settle_side_bet(1), 0);
+    // Пари, сведённое до появления счётчика.
+    assert_eq!(settle_side_bet(0), 0);
+}
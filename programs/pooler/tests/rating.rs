@@ -0,0 +1,165 @@
+//! Рейтинг при завершении: пока config.rating_enforced выключен, без сезона и
+//! статистики рейтинг просто не обновляется; при включённом их отсутствие —
+//! ошибка, иначе проигравший избегал бы потери рейтинга, не передав их.
+//! Сезон принимается только текущий (seeds по config.current_season).
+
+mod common;
+
+use anchor_lang::prelude::{Account, AnchorDeserialize, Pubkey};
+use common::{config_with, event_cpi, finish_game, game, infos, no_splits, pda, try_accounts};
+use pooler::{
+    record_game_rating, Config, DropDouble, ErrorCode, FinishGame, FinishReason, GameState,
+    GameStatus, PlayerStats, Season, DEFAULT_RATING,
+};
+
+/// Индекс сезона в аккаунтах finish_game.
+const SEASON: usize = 5;
+
+fn finished() -> GameState {
+    let mut state = game(GameStatus::Finished);
+    state.finish_reason = FinishReason::Win;
+    state.winner = state.player1;
+    state
+}
+
+fn rating_config(enforced: bool) -> Config {
+    let mut config = Config::deserialize(&mut &[0u8; 1024][..]).unwrap();
+    config.rating_enforced = enforced;
+    config
+}
+
+fn stats(player: Pubkey) -> common::Account {
+    let (key, bump) = pda(&[b"stats", player.as_ref()]);
+    common::Account::owned(
+        key,
+        &PlayerStats {
+            player,
+            rating: DEFAULT_RATING,
+            games_played: 0,
+            wins: 0,
+            losses: 0,
+            season: 0,
+            season_wins: 0,
+            season_losses: 0,
+            bump,
+        },
+    )
+}
+
+fn season(index: u32) -> common::Account {
+    let (key, bump) = pda(&[b"season", &index.to_le_bytes()]);
+    let mut season = Season::deserialize(&mut &[0u8; 1024][..]).unwrap();
+    season.index = index;
+    season.bump = bump;
+    common::Account::owned(key, &season)
+}
+
+#[test]
+fn missing_rating_accounts_fail_only_when_enforced() {
+    let state = finished();
+    record_game_rating(
+        &state,
+        state.winner,
+        &rating_config(false),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        record_game_rating(&state, state.winner, &rating_config(true), None, None, None).err(),
+        Some(ErrorCode::MissingSeason.into())
+    );
+
+    let mut accounts = [season(0), stats(state.player1)];
+    let infos = infos(&mut accounts);
+    let mut season = Account::<Season>::try_from(&infos[0]).unwrap();
+    let mut winner = Account::<PlayerStats>::try_from(&infos[1]).unwrap();
+    assert_eq!(
+        record_game_rating(
+            &state,
+            state.winner,
+            &rating_config(true),
+            Some(&mut season),
+            Some(&mut winner),
+            None,
+        )
+        .err(),
+        Some(ErrorCode::MissingPlayerStats.into())
+    );
+
+    // Тренировочные и нерейтинговые завершения аккаунтов не требуют.
+    let mut practice = finished();
+    practice.practice = true;
+    let mut refunded = finished();
+    refunded.finish_reason = FinishReason::ManualRefund;
+    for state in [practice, refunded] {
+        record_game_rating(&state, state.winner, &rating_config(true), None, None, None).unwrap();
+    }
+}
+
+#[test]
+fn enforced_rating_updates_both_players() {
+    let state = finished();
+    let mut accounts = [season(0), stats(state.player1), stats(state.player2)];
+    let infos = infos(&mut accounts);
+    let mut season = Account::<Season>::try_from(&infos[0]).unwrap();
+    let mut player1 = Account::<PlayerStats>::try_from(&infos[1]).unwrap();
+    let mut player2 = Account::<PlayerStats>::try_from(&infos[2]).unwrap();
+    record_game_rating(
+        &state,
+        state.winner,
+        &rating_config(true),
+        Some(&mut season),
+        Some(&mut player1),
+        Some(&mut player2),
+    )
+    .unwrap();
+    assert!(player1.rating > DEFAULT_RATING);
+    assert!(player2.rating < DEFAULT_RATING);
+    assert_eq!((player1.wins, player2.losses), (1, 1));
+    assert_eq!(season.standings[0].player, state.player1);
+}
+
+#[test]
+fn only_the_current_season_is_accepted() {
+    let state = game(GameStatus::Active);
+    let with_season = |index: u32, current: u32| {
+        let mut accounts = finish_game(&state, state.player1, state.player2);
+        accounts[4] = config_with(|config| config.current_season = current);
+        accounts[SEASON] = season(index);
+        accounts
+    };
+    try_accounts::<FinishGame>(&mut with_season(1, 1)).unwrap();
+    assert_eq!(
+        try_accounts::<FinishGame>(&mut with_season(0, 1)).err(),
+        Some(anchor_lang::error::ErrorCode::ConstraintSeeds.into())
+    );
+}
+
+#[test]
+fn drop_double_takes_the_current_season() {
+    let mut state = game(GameStatus::Active);
+    state.double_pending = true;
+    let with_season = |index: u32| {
+        let mut accounts = vec![
+            common::Account::owned(Pubkey::new_unique(), &state),
+            common::Account::wallet(state.player2, true),
+            common::Account::wallet(state.player1, false),
+            config_with(|config| config.current_season = 1),
+            season(index),
+            stats(state.player1),
+            stats(state.player2),
+        ];
+        // escrow_agent, event_buffer, fee_vault, incinerator.
+        accounts.extend((0..4).map(|_| common::Account::none()));
+        accounts.push(no_splits(&state.player1));
+        accounts.extend(event_cpi());
+        accounts
+    };
+    try_accounts::<DropDouble>(&mut with_season(1)).unwrap();
+    assert_eq!(
+        try_accounts::<DropDouble>(&mut with_season(0)).err(),
+        Some(anchor_lang::error::ErrorCode::ConstraintSeeds.into())
+    );
+}
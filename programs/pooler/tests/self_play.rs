@@ -0,0 +1,123 @@
+//! Игра против самого себя: init_game, join_game и reopen_invite
+//! отклоняют player2 == player1 ошибкой SelfPlayNotAllowed.
+//!
+//! Обработчики вызываются напрямую с Context, собранным из AccountInfo;
+//! проверка срабатывает раньше любых CPI.
+
+mod common;
+
+use anchor_lang::prelude::{Account as AnchorAccount, Context, Program, Pubkey, Signer};
+use anchor_lang::system_program;
+use common::{config, game, infos, join_game, open_games, try_accounts, Account, SLOT};
+use pooler::{
+    CreateCooldown, ErrorCode, GameOptions, GameState, GameStatus, InitGame, InitGameBumps,
+    ReopenInvite, ReopenInviteBumps,
+};
+
+/// init_game с player2 = invite(player1, game).
+fn init_game(invite: impl Fn(Pubkey, Pubkey) -> Pubkey) -> anchor_lang::Result<()> {
+    common::install_sysvars();
+    let (player1, game_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut accounts = vec![
+        Account::owned(game_key, &game(GameStatus::WaitingForPlayer2)),
+        Account::wallet(player1, true),
+        Account::program(system_program::ID),
+        config(),
+        open_games(&player1),
+        Account::owned(
+            Pubkey::new_unique(),
+            &CreateCooldown {
+                last_created_slot: 0,
+            },
+        ),
+    ];
+    let infos = infos(&mut accounts);
+    let mut ctx_accounts = InitGame {
+        game: AnchorAccount::try_from(&infos[0])?,
+        player1: Signer::try_from(&infos[1])?,
+        system_program: Program::try_from(&infos[2])?,
+        config: AnchorAccount::try_from(&infos[3])?,
+        player1_ban: None,
+        open_games: AnchorAccount::try_from(&infos[4])?,
+        time_control: None,
+        create_cooldown: AnchorAccount::try_from(&infos[5])?,
+        fee_route: None,
+        whitelisted_arbiter: None,
+        game_counter: None,
+        game_history: None,
+        history_commitment: None,
+    };
+    let ctx = Context::new(
+        &pooler::ID,
+        &mut ctx_accounts,
+        &[],
+        InitGameBumps::default(),
+    );
+    pooler::backgammon::init_game(
+        ctx,
+        1,
+        1_000_000,
+        0,
+        invite(player1, game_key),
+        GameOptions::default(),
+    )
+}
+
+#[test]
+fn init_game_rejects_inviting_yourself() {
+    assert_eq!(
+        init_game(|player1, _| player1).unwrap_err(),
+        ErrorCode::SelfPlayNotAllowed.into()
+    );
+    // Другой ключ проходит проверку и упирается в следующую (до CPI
+    // system program, которого вне валидатора нет).
+    assert_eq!(
+        init_game(|_, game| game).unwrap_err(),
+        ErrorCode::PayoutAliasesGame.into()
+    );
+}
+
+#[test]
+fn join_game_rejects_joining_own_game() {
+    let mut state = game(GameStatus::WaitingForPlayer2);
+    state.player2 = state.player1;
+    assert_eq!(
+        try_accounts::<pooler::JoinGame>(&mut join_game(&state, state.player1)).unwrap_err(),
+        ErrorCode::SelfPlayNotAllowed.into()
+    );
+}
+
+fn reopen_invite(state: &GameState, new_player2: Pubkey) -> anchor_lang::Result<()> {
+    common::install_sysvars();
+    let mut accounts = vec![
+        Account::owned(Pubkey::new_unique(), state),
+        Account::wallet(state.player1, true),
+    ];
+    let infos = infos(&mut accounts);
+    let mut ctx_accounts = ReopenInvite {
+        game: AnchorAccount::try_from(&infos[0])?,
+        player1: Signer::try_from(&infos[1])?,
+    };
+    let ctx = Context::new(
+        &pooler::ID,
+        &mut ctx_accounts,
+        &[],
+        ReopenInviteBumps::default(),
+    );
+    pooler::backgammon::reopen_invite(ctx, new_player2, SLOT + 100)
+}
+
+#[test]
+fn reopen_invite_rejects_inviting_yourself() {
+    let mut state = game(GameStatus::WaitingForPlayer2);
+    state.join_deadline_slot = SLOT - 1;
+    state.player1_deposit = 1_000_000;
+    state.pot_lamports = 1_000_000;
+
+    assert_eq!(
+        reopen_invite(&state, state.player1).unwrap_err(),
+        ErrorCode::SelfPlayNotAllowed.into()
+    );
+    reopen_invite(&state, Pubkey::new_unique()).unwrap();
+    reopen_invite(&state, Pubkey::default()).unwrap();
+}
@@ -0,0 +1,295 @@
+//! Свойства арифметики банка: вклады складываются в банк, возврат не больше
+//! вклада игрока с его половиной чаевых, выплаты не превышают банк, остатки
+//! от округления уходят туда, куда сказано в документации (первой части или
+//! казне, player1, первому получателю сплита).
+//!
+//! Игра моделируется теми же функциями, что и инструкции: add_deposit,
+//! book_move_fee за вычетом insurance_cut, apply_stake_result; комиссия от
+//! банка (move_fee_bps) — base_move_fee.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::game;
+use pooler::{
+    book_move_fee, check_settlement, insurance_cut, payout_shares, pot_move_fee, split_pot,
+    validate_payout_splits, ErrorCode, GameState, GameStatus, PayoutSplit, Remainder, TeamState,
+    TimeControlParams, BPS_DENOMINATOR, MAX_INSURANCE_FEE_BPS,
+};
+use proptest::prelude::*;
+
+/// Ставки и базовые комиссии: до 10^12 lamports, чтобы сотни ходов с ростом
+/// комиссии не переполняли u64.
+const MAX_AMOUNT: u64 = 1_000_000_000_000;
+
+#[derive(Debug, Clone)]
+struct Play {
+    stakes: [u64; 2],
+    move_fee: u64,
+    moves: u64,
+    escalation: TimeControlParams,
+    insurance_fee_bps: u16,
+}
+
+fn play() -> impl Strategy<Value = Play> {
+    (
+        [0..=MAX_AMOUNT, 0..=MAX_AMOUNT],
+        0..=MAX_AMOUNT / 1_000,
+        0..200u64,
+        0..=5_000u16,
+        0..=50u16,
+        0..=MAX_INSURANCE_FEE_BPS,
+    )
+        .prop_map(
+            |(stakes, move_fee, moves, bps, every, insurance_fee_bps)| Play {
+                stakes,
+                move_fee,
+                moves,
+                escalation: TimeControlParams {
+                    fee_escalation_bps: bps,
+                    fee_escalation_every: every,
+                    ..TimeControlParams::default()
+                },
+                insurance_fee_bps,
+            },
+        )
+}
+
+/// Активная игра после депозитов и play.moves ходов по очереди; вторым
+/// значением — сколько каждый игрок внёс в банк.
+fn played(play: &Play) -> (GameState, [u64; 2]) {
+    let mut state = game(GameStatus::Active);
+    let mut paid = play.stakes;
+    state.add_deposit(1, play.stakes[0]).unwrap();
+    state.add_deposit(2, play.stakes[1]).unwrap();
+    for move_index in 0..play.moves {
+        let fee = play.escalation.move_fee(play.move_fee, move_index).unwrap();
+        let net = fee - insurance_cut(fee, play.insurance_fee_bps);
+        book_move_fee(&mut state, None, None, 0, net).unwrap();
+        paid[state.current_turn as usize - 1] += net;
+        state.current_turn = 3 - state.current_turn;
+    }
+    (state, paid)
+}
+
+fn refunds_sum_to_pot(state: &GameState) -> Result<(), TestCaseError> {
+    let (p1, p2, to_treasury) = state.refund_totals(Remainder::First).unwrap();
+    prop_assert_eq!(to_treasury, 0);
+    check_settlement(state.pot_lamports, p1, p2).unwrap();
+    // С казной нечётный лампорт чаевых уходит ей, а не player1.
+    let (t1, t2, to_treasury) = state.refund_totals(Remainder::Treasury).unwrap();
+    prop_assert_eq!(to_treasury, state.tips_lamports % 2);
+    prop_assert_eq!((t1 + to_treasury, t2), (p1, p2));
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn contributions_sum_to_pot(play in play()) {
+        let (state, paid) = played(&play);
+        prop_assert_eq!(state.pot_lamports, paid[0] + paid[1]);
+        prop_assert_eq!(state.player1_deposit + state.player1_fees_paid, paid[0]);
+        prop_assert_eq!(state.player2_deposit + state.player2_fees_paid, paid[1]);
+        refunds_sum_to_pot(&state)?;
+    }
+
+    #[test]
+    fn refund_never_exceeds_contribution(play in play(), gain in 0..=MAX_AMOUNT) {
+        let (mut state, paid) = played(&play);
+        // Доход пула делится пропорционально вкладам, остаток — player1.
+        state.apply_stake_result(state.pot_lamports, state.pot_lamports + gain).unwrap();
+        prop_assert_eq!(state.tips_lamports, 0);
+        let ([gain_p1, gain_p2], _) = split_pot(gain, paid, Remainder::First);
+
+        let (p1, p2, _) = state.refund_totals(Remainder::First).unwrap();
+        prop_assert_eq!(p1, paid[0] + gain_p1);
+        prop_assert_eq!(p2, paid[1] + gain_p2);
+        refunds_sum_to_pot(&state)?;
+    }
+
+    #[test]
+    fn stake_loss_comes_out_of_deposits(play in play(), lost_pct in 0..=100u64) {
+        let (mut state, paid) = played(&play);
+        let deposits = state.player1_deposit + state.player2_deposit;
+        let loss = (deposits as u128 * lost_pct as u128 / 100) as u64;
+        state.apply_stake_result(state.pot_lamports, state.pot_lamports - loss).unwrap();
+
+        let (p1, p2, _) = state.refund_totals(Remainder::First).unwrap();
+        prop_assert!(p1 <= paid[0] && p2 <= paid[1]);
+        prop_assert_eq!(paid[0] - p1 + paid[1] - p2, loss);
+        // Доли потерь пропорциональны депозитам, остаток — player1.
+        let ([loss_p1, loss_p2], _) =
+            split_pot(loss, [play.stakes[0], play.stakes[1]], Remainder::First);
+        prop_assert_eq!(paid[1] - p2, loss_p2);
+        prop_assert_eq!(paid[0] - p1, loss_p1);
+        refunds_sum_to_pot(&state)?;
+    }
+
+    #[test]
+    fn any_stake_loss_keeps_the_claim_solvent(
+        play in play(),
+        gain in 0..=MAX_AMOUNT,
+        lost_pct in 0..=150u64,
+    ) {
+        let (mut state, _) = played(&play);
+        state.apply_stake_result(state.pot_lamports, state.pot_lamports + gain).unwrap();
+        // Потеря может превысить депозиты и даже весь банк.
+        let pot = state.pot_lamports;
+        let loss = (pot as u128 * lost_pct as u128 / 100) as u64;
+        state.apply_stake_result(loss, 0).unwrap();
+
+        prop_assert_eq!(state.pot_lamports, pot - loss.min(pot));
+        refunds_sum_to_pot(&state)?;
+    }
+
+    #[test]
+    fn settlement_never_exceeds_pot(pot in any::<u64>(), p1 in any::<u64>(), p2 in any::<u64>()) {
+        let result = check_settlement(pot, p1, p2);
+        if p1 as u128 + p2 as u128 == pot as u128 {
+            prop_assert!(result.is_ok());
+        } else {
+            prop_assert_eq!(result.unwrap_err(), ErrorCode::InvalidSettlement.into());
+        }
+        if p1 <= pot {
+            check_settlement(pot, p1, pot - p1).unwrap();
+        }
+    }
+
+    #[test]
+    fn split_pot_remainder_goes_first(total in any::<u64>(), weights in any::<[u64; 3]>()) {
+        let (parts, to_treasury) = split_pot(total, weights, Remainder::First);
+        prop_assert_eq!(to_treasury, 0);
+        prop_assert_eq!(parts.iter().map(|&p| p as u128).sum::<u128>(), total as u128);
+        let sum: u128 = weights.iter().map(|&w| w as u128).sum();
+        for i in 1..3 {
+            let exact = if sum == 0 { 0 } else { total as u128 * weights[i] as u128 / sum };
+            prop_assert_eq!(parts[i] as u128, exact);
+        }
+    }
+
+    #[test]
+    fn split_pot_remainder_goes_to_treasury(total in any::<u64>(), weights in any::<[u64; 3]>()) {
+        let (parts, to_treasury) = split_pot(total, weights, Remainder::Treasury);
+        let (first, _) = split_pot(total, weights, Remainder::First);
+        prop_assert_eq!(
+            parts.iter().map(|&p| p as u128).sum::<u128>() + to_treasury as u128,
+            total as u128
+        );
+        prop_assert!(to_treasury < 3);
+        prop_assert_eq!(parts[0] + to_treasury, first[0]);
+        prop_assert_eq!(&parts[1..], &first[1..]);
+    }
+
+    #[test]
+    fn payout_shares_sum_to_amount(
+        amount in any::<u64>(),
+        cuts in prop::collection::btree_set(1..BPS_DENOMINATOR as u16, 0..4),
+    ) {
+        // Точки разреза делят BPS_DENOMINATOR на 1..=4 положительные доли.
+        let mut bounds: Vec<u16> = cuts.into_iter().collect();
+        bounds.push(BPS_DENOMINATOR as u16);
+        let splits: Vec<PayoutSplit> = bounds
+            .iter()
+            .scan(0, |start, &end| {
+                let bps = end - *start;
+                *start = end;
+                Some(PayoutSplit { recipient: Pubkey::new_unique(), bps })
+            })
+            .collect();
+        validate_payout_splits(&splits).unwrap();
+
+        let shares = payout_shares(amount, &splits);
+        prop_assert_eq!(shares.iter().map(|&s| s as u128).sum::<u128>(), amount as u128);
+        for (split, &share) in splits.iter().zip(&shares).skip(1) {
+            prop_assert_eq!(
+                share as u128,
+                amount as u128 * split.bps as u128 / BPS_DENOMINATOR as u128
+            );
+        }
+    }
+
+    #[test]
+    fn insurance_cut_rounds_down(move_fee in any::<u64>(), bps in 0..=MAX_INSURANCE_FEE_BPS) {
+        let cut = insurance_cut(move_fee, bps);
+        prop_assert!(cut <= move_fee);
+        let scaled = cut as u128 * BPS_DENOMINATOR as u128;
+        let exact = move_fee as u128 * bps as u128;
+        prop_assert!(scaled <= exact && exact < scaled + BPS_DENOMINATOR as u128);
+    }
+
+    #[test]
+    fn pot_move_fee_rounds_down_to_at_least_one(
+        pot in any::<u64>(),
+        bps in 0..=BPS_DENOMINATOR as u16,
+    ) {
+        let fee = pot_move_fee(pot, bps);
+        if bps == 0 {
+            prop_assert_eq!(fee, 0);
+        } else {
+            prop_assert!(fee >= 1 && fee <= pot.max(1));
+            let scaled = fee as u128 * BPS_DENOMINATOR as u128;
+            let exact = pot as u128 * bps as u128;
+            prop_assert!(
+                (fee == 1 && exact < BPS_DENOMINATOR as u128)
+                    || (scaled <= exact && exact < scaled + BPS_DENOMINATOR as u128)
+            );
+        }
+    }
+
+    #[test]
+    fn pot_move_fees_grow_and_refunds_stay_exact(
+        stakes in [0..=MAX_AMOUNT, 0..=MAX_AMOUNT],
+        bps in 1..=100u16,
+        moves in 1..200u64,
+        insurance_fee_bps in 0..=MAX_INSURANCE_FEE_BPS,
+    ) {
+        let mut state = game(GameStatus::Active);
+        state.move_fee_bps = bps;
+        let mut paid = stakes;
+        state.add_deposit(1, stakes[0]).unwrap();
+        state.add_deposit(2, stakes[1]).unwrap();
+        let mut last_fee = 0;
+        for _ in 0..moves {
+            // Комиссия считается от банка перед ходом и растёт вместе с ним.
+            let fee = state.base_move_fee();
+            prop_assert_eq!(fee, pot_move_fee(state.pot_lamports, bps));
+            prop_assert!(fee >= last_fee);
+            last_fee = fee;
+            let net = fee - insurance_cut(fee, insurance_fee_bps);
+            book_move_fee(&mut state, None, None, 0, net).unwrap();
+            paid[state.current_turn as usize - 1] += net;
+            state.current_turn = 3 - state.current_turn;
+        }
+        prop_assert_eq!(state.pot_lamports, paid[0] + paid[1]);
+        prop_assert_eq!(state.player1_deposit + state.player1_fees_paid, paid[0]);
+        prop_assert_eq!(state.player2_deposit + state.player2_fees_paid, paid[1]);
+        refunds_sum_to_pot(&state)?;
+    }
+
+    #[test]
+    fn team_shares_sum_to_payout(
+        contributions in any::<[u32; 4]>(),
+        received in any::<[u64; 2]>(),
+    ) {
+        let team = TeamState {
+            game: Pubkey::new_unique(),
+            teammates: [Pubkey::new_unique(); 2],
+            contributions: contributions.map(u64::from),
+            received,
+            claimed: [false; 4],
+            bump: 0,
+        };
+        for side in 0..2 {
+            let (main, mate) = (team.share_of(side * 2), team.share_of(side * 2 + 1));
+            prop_assert_eq!(main as u128 + mate as u128, received[side] as u128);
+            // Остаток — основному игроку стороны.
+            let sum = contributions[side * 2] as u128 + contributions[side * 2 + 1] as u128;
+            if sum > 0 {
+                prop_assert_eq!(
+                    mate as u128,
+                    received[side] as u128 * contributions[side * 2 + 1] as u128 / sum
+                );
+            }
+        }
+    }
+}
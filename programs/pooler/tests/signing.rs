@@ -0,0 +1,209 @@
+//! Подписанные оффчейн сообщения (модуль signing): раскладка байтов,
+//! проверки nonce и срока, повтор принятого сообщения в relayed_move.
+//!
+//! Ed25519-программа вне валидатора не выполняется: инструкция собирается с
+//! нулевыми подписями, а программа проверяет только её содержимое — ключи и
+//! сообщение, которые рантайм сверил бы с подписями.
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey};
+use anchor_lang::AnchorSerialize;
+use common::{config, game, parse, signed_instructions, Account, SLOT};
+use pooler::signing::{SignedAction, SignedMessage};
+use pooler::{ErrorCode, GameState, GameStatus, RelayedMove, RelayedMovePayload};
+use solana_sha256_hasher::hash;
+
+#[test]
+fn signed_message_layout_is_fixed() {
+    let game = Pubkey::new_unique();
+    let payload = b"payload";
+    let message = SignedMessage::new(
+        game,
+        SignedAction::RelayedMove,
+        0x0102_0304_0506_0708,
+        0x1112_1314_1516_1718,
+        payload,
+    );
+    let bytes = message.to_bytes().unwrap();
+
+    assert_eq!(bytes.len(), SignedMessage::LEN);
+    assert_eq!(SignedMessage::LEN, 113);
+    assert_eq!(&bytes[0..32], pooler::ID.as_ref());
+    assert_eq!(&bytes[32..64], game.as_ref());
+    assert_eq!(bytes[64], 0);
+    assert_eq!(bytes[65..73], 0x0102_0304_0506_0708u64.to_le_bytes());
+    assert_eq!(bytes[73..81], 0x1112_1314_1516_1718u64.to_le_bytes());
+    assert_eq!(&bytes[81..113], hash(payload).as_ref());
+    assert_eq!(message.try_to_vec().unwrap(), bytes);
+}
+
+#[test]
+fn signed_message_checks_program_game_nonce_and_expiry() {
+    let key = Pubkey::new_unique();
+    let mut state = game(GameStatus::Active);
+    state.action_nonce = 5;
+    let message = SignedMessage::new(key, SignedAction::RelayedMove, 5, SLOT, &[]);
+
+    message.check(&state, &key, SLOT).unwrap();
+    assert_eq!(
+        message.check(&state, &key, SLOT + 1).unwrap_err(),
+        ErrorCode::SignedMessageExpired.into()
+    );
+    assert_eq!(
+        message
+            .check(&state, &Pubkey::new_unique(), SLOT)
+            .unwrap_err(),
+        ErrorCode::InvalidSignedMessage.into()
+    );
+    let foreign = SignedMessage {
+        program_id: Pubkey::new_unique(),
+        ..message
+    };
+    assert_eq!(
+        foreign.check(&state, &key, SLOT).unwrap_err(),
+        ErrorCode::InvalidSignedMessage.into()
+    );
+    state.action_nonce = 6;
+    assert_eq!(
+        message.check(&state, &key, SLOT).unwrap_err(),
+        ErrorCode::SignedMessageNonceMismatch.into()
+    );
+}
+
+fn relay_game() -> GameState {
+    let mut state = game(GameStatus::Active);
+    state
+        .set_board_points(&pooler::start_board(pooler::RuleVariant::Standard, 0).unwrap())
+        .unwrap();
+    state
+}
+
+fn payload(state: &GameState) -> RelayedMovePayload {
+    RelayedMovePayload {
+        board_points: state.board_points().unwrap(),
+        dice: [3, 1],
+        annotation_hash: [0; 32],
+        expected_move_index: state.move_index,
+    }
+}
+
+/// relayed_move над state с сообщением nonce/expiry_slot, подписанным
+/// ходящим; возвращает состояние игры после обработчика.
+fn relay(
+    key: Pubkey,
+    state: &GameState,
+    nonce: u64,
+    expiry_slot: u64,
+) -> anchor_lang::Result<GameState> {
+    let mover = state.player_key(state.current_turn);
+    relay_signed(key, state, nonce, expiry_slot, &[mover])
+}
+
+/// То же, что relay, но сообщение подписывают signers.
+fn relay_signed(
+    key: Pubkey,
+    state: &GameState,
+    nonce: u64,
+    expiry_slot: u64,
+    signers: &[Pubkey],
+) -> anchor_lang::Result<GameState> {
+    let payload = payload(state);
+    let message = SignedMessage::new(
+        key,
+        SignedAction::RelayedMove,
+        nonce,
+        expiry_slot,
+        &payload.try_to_vec().unwrap(),
+    );
+    let mut accounts = vec![
+        Account::owned(key, state),
+        Account::wallet(Pubkey::new_unique(), true),
+        config(),
+    ];
+    // relay_budget, time_control, fee_route, fee_recipient.
+    accounts.extend((0..4).map(|_| Account::none()));
+    accounts.push(signed_instructions(signers, &message));
+    // insurance_pool, event_buffer, game_history, history_commitment,
+    // decay_sink.
+    accounts.extend((0..5).map(|_| Account::none()));
+    let (mut parsed, bumps) = parse::<RelayedMove>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::relayed_move(ctx, payload, nonce, expiry_slot, [0; 64])?;
+    Ok((*parsed.game).clone())
+}
+
+#[test]
+fn relayed_move_consumes_the_nonce() {
+    let key = Pubkey::new_unique();
+    let state = relay_game();
+    let after = relay(key, &state, 0, SLOT).unwrap();
+    assert_eq!(after.action_nonce, 1);
+    assert_eq!(after.move_index, 1);
+
+    // Следующее сообщение подписывается уже с новым nonce.
+    let after = relay(key, &after, 1, SLOT).unwrap();
+    assert_eq!(after.action_nonce, 2);
+}
+
+#[test]
+fn mover_signature_alone_is_enough() {
+    let key = Pubkey::new_unique();
+    let state = relay_game();
+    // Ход player1 без подписи соперника, как и в make_move.
+    let after = relay_signed(key, &state, 0, SLOT, &[state.player1]).unwrap();
+    assert_eq!((after.move_index, after.current_turn), (1, 2));
+
+    // Подпись одного соперника ход не разрешает.
+    assert_eq!(
+        relay_signed(key, &after, 1, SLOT, &[after.player1])
+            .err()
+            .unwrap(),
+        ErrorCode::MissingPlayerSignature.into()
+    );
+    let after = relay_signed(key, &after, 1, SLOT, &[after.player2]).unwrap();
+    assert_eq!((after.move_index, after.current_turn), (2, 1));
+}
+
+#[test]
+fn replayed_message_is_rejected() {
+    let key = Pubkey::new_unique();
+    let state = relay_game();
+    let mut after = relay(key, &state, 0, SLOT).unwrap();
+    // Даже если move_index совпал бы снова, nonce уже израсходован.
+    after.move_index = state.move_index;
+    after.current_turn = state.current_turn;
+    assert_eq!(
+        relay(key, &after, 0, SLOT).err().unwrap(),
+        ErrorCode::SignedMessageNonceMismatch.into()
+    );
+}
+
+#[test]
+fn expired_message_is_rejected() {
+    let key = Pubkey::new_unique();
+    assert_eq!(
+        relay(key, &relay_game(), 0, SLOT - 1).err().unwrap(),
+        ErrorCode::SignedMessageExpired.into()
+    );
+}
+
+#[test]
+fn message_for_another_game_is_rejected() {
+    // Подписи над сообщением другой игры: сообщение этой игры не подписано.
+    let state = relay_game();
+    let other = SignedMessage::new(
+        Pubkey::new_unique(),
+        SignedAction::RelayedMove,
+        0,
+        SLOT,
+        &payload(&state).try_to_vec().unwrap(),
+    );
+    let key = Pubkey::new_unique();
+    let ours = SignedMessage { game: key, ..other };
+    assert_ne!(other.to_bytes().unwrap(), ours.to_bytes().unwrap());
+    assert_eq!(
+        ours.check(&state, &Pubkey::new_unique(), SLOT).unwrap_err(),
+        ErrorCode::InvalidSignedMessage.into()
+    );
+}
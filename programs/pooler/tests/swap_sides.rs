@@ -0,0 +1,171 @@
+//! swap_sides: до первого хода игроки меняются местами вместе со своими
+//! депозитами и счётчиками, а доска и очередь хода остаются за сторонами.
+//! Пари зрителей после обмена идут на того же игрока.
+//!
+//! Обработчики вызываются напрямую; партия до выплаты после обмена
+//! проверяется в LiteSVM (tests/lifecycle.rs).
+
+mod common;
+
+use anchor_lang::prelude::{Context, Pubkey};
+use common::{config, game, install_sysvars, parse, pda, Account};
+use pooler::{ErrorCode, GameState, GameStatus, SettleSideBet, SideBet, SideBetStatus, SwapSides};
+
+fn active() -> GameState {
+    let mut state = game(GameStatus::Active);
+    let mut board = [0; 24];
+    board[0] = 2;
+    board[23] = -2;
+    state.set_board_points(&board).unwrap();
+    state.pot_lamports = 3_000;
+    state.player1_deposit = 1_000;
+    state.player2_deposit = 2_000;
+    state.player1_session_key = Pubkey::new_unique();
+    state.player1_session_expiry = 50;
+    state.p2_auto_take_up_to = 2;
+    state.p2_take_reserve = 2_000;
+    state.p1_timeout_strikes = 1;
+    state
+}
+
+fn swap(state: &GameState, player1: Pubkey, player2: Pubkey) -> anchor_lang::Result<GameState> {
+    install_sysvars();
+    let game_key = Pubkey::new_unique();
+    let mut accounts = [
+        Account::owned(game_key, state),
+        Account::wallet(player1, true),
+        Account::wallet(player2, true),
+        Account::wallet(pda(&[b"chouette", game_key.as_ref()]).0, false),
+    ];
+    let (mut parsed, bumps) = parse::<SwapSides>(&mut accounts)?;
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::swap_sides(ctx)?;
+    Ok((*parsed.game).clone())
+}
+
+#[test]
+fn swap_exchanges_players_but_not_the_board() {
+    let state = active();
+    let swapped = swap(&state, state.player1, state.player2).unwrap();
+
+    assert_eq!(swapped.player1, state.player2);
+    assert_eq!(swapped.player2, state.player1);
+    assert_eq!(swapped.player1_deposit, 2_000);
+    assert_eq!(swapped.player2_deposit, 1_000);
+    assert_eq!(swapped.player2_session_key, state.player1_session_key);
+    assert_eq!(swapped.player2_session_expiry, 50);
+    assert_eq!(swapped.player1_session_key, Pubkey::default());
+    assert_eq!(swapped.p1_auto_take_up_to, 2);
+    assert_eq!(swapped.p1_take_reserve, 2_000);
+    assert_eq!(swapped.p2_take_reserve, 0);
+    assert_eq!(swapped.p2_timeout_strikes, 1);
+    assert!(swapped.sides_swapped);
+
+    // Доска, очередь и банк принадлежат сторонам и не меняются.
+    assert_eq!(
+        swapped.board_points().unwrap(),
+        state.board_points().unwrap()
+    );
+    assert_eq!(swapped.current_turn, 1);
+    assert_eq!(swapped.pot_lamports, state.pot_lamports);
+
+    // Повторный обмен возвращает исходную раскладку.
+    let back = swap(&swapped, swapped.player1, swapped.player2).unwrap();
+    assert_eq!(back.player1, state.player1);
+    assert_eq!(back.player1_deposit, 1_000);
+    assert!(!back.sides_swapped);
+}
+
+#[test]
+fn swap_needs_both_players_before_the_first_move() {
+    let state = active();
+    assert_eq!(
+        swap(&state, state.player1, Pubkey::new_unique()).err(),
+        Some(ErrorCode::InvalidPlayer.into())
+    );
+
+    let mut moved = active();
+    moved.move_index = 1;
+    assert_eq!(
+        swap(&moved, moved.player1, moved.player2).err(),
+        Some(ErrorCode::SwapWindowClosed.into())
+    );
+    let mut doubled = active();
+    doubled.double_pending = true;
+    assert_eq!(
+        swap(&doubled, doubled.player1, doubled.player2).err(),
+        Some(ErrorCode::SwapWindowClosed.into())
+    );
+
+    let mut bot = active();
+    bot.bot_game = true;
+    assert_eq!(
+        swap(&bot, bot.player1, bot.player2).err(),
+        Some(ErrorCode::SwapNotAllowed.into())
+    );
+
+    let waiting = game(GameStatus::WaitingForPlayer2);
+    assert_eq!(
+        swap(&waiting, waiting.player1, waiting.player2).err(),
+        Some(ErrorCode::GameNotActive.into())
+    );
+}
+
+/// Расчёт пари на создателя игры (индекс 1 до обмена) после обмена
+/// сторонами; true — выиграл bettor.
+fn bet_on_creator_wins(winner_is_creator: bool) -> bool {
+    let state = active();
+    let creator = state.player1;
+    let mut finished = swap(&state, state.player1, state.player2).unwrap();
+    finished.status = GameStatus::Finished;
+    finished.winner = if winner_is_creator {
+        creator
+    } else {
+        finished.player1
+    };
+
+    let game_key = Pubkey::new_unique();
+    let (bettor, counterparty) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (side_bet, bump) = pda(&[
+        b"side_bet",
+        game_key.as_ref(),
+        bettor.as_ref(),
+        &1u64.to_le_bytes(),
+    ]);
+    let amount = 1_000;
+    let mut side_bet = Account::owned(
+        side_bet,
+        &SideBet {
+            game: game_key,
+            bettor,
+            counterparty,
+            bet_id: 1,
+            predicted_winner: creator,
+            predicted_player: 1,
+            amount,
+            status: SideBetStatus::Matched,
+            bump,
+        },
+    );
+    side_bet.lamports += 2 * amount;
+    let mut accounts = [
+        Account::owned(game_key, &finished),
+        side_bet,
+        Account::wallet(bettor, false),
+        Account::wallet(counterparty, false),
+        Account::wallet(Pubkey::new_unique(), true),
+        config(),
+    ];
+    let before = accounts[3].lamports;
+    let (mut parsed, bumps) = parse::<SettleSideBet>(&mut accounts).unwrap();
+    let ctx = Context::new(&pooler::ID, &mut parsed, &[], bumps);
+    pooler::backgammon::settle_side_bet(ctx).unwrap();
+    drop(parsed);
+    accounts[3].lamports == before
+}
+
+#[test]
+fn side_bets_follow_the_player_across_a_swap() {
+    assert!(bet_on_creator_wins(true));
+    assert!(!bet_on_creator_wins(false));
+}
@@ -0,0 +1,117 @@
+//! Швейцарская система: в турнире на 8 игроков и 3 тура pair_round ни при
+//! каком исходе партий не сводит игроков повторно, и в каждом туре каждый
+//! игрок получает ровно одного соперника.
+
+mod common;
+
+use anchor_lang::prelude::{Account as AnchorAccount, AnchorDeserialize, Context, Pubkey, Signer};
+use common::{game, infos, Account};
+use pooler::{
+    GameState, GameStatus, ReportResult, ReportResultBumps, Tournament, TournamentMode,
+    TournamentOrganizer, TournamentOrganizerBumps, TournamentStatus, SWISS_BYE,
+};
+
+const PLAYERS: usize = 8;
+const ROUNDS: u8 = 3;
+
+fn swiss_tournament(organizer: Pubkey) -> Tournament {
+    let mut tournament = Tournament::deserialize(&mut &[0u8; Tournament::MAX_SIZE][..]).unwrap();
+    tournament.organizer = organizer;
+    tournament.mode = TournamentMode::Swiss;
+    tournament.status = TournamentStatus::Running;
+    tournament.max_players = PLAYERS as u8;
+    tournament.player_count = PLAYERS as u8;
+    tournament.swiss_rounds = ROUNDS;
+    for player in &mut tournament.players[..PLAYERS] {
+        *player = Pubkey::new_unique();
+    }
+    tournament
+}
+
+/// Проводит турнир; исход партии тура round между a < b задаёт бит
+/// outcomes (1 — побеждает b). Возвращает соперников каждого игрока по турам.
+fn play(outcomes: u32) -> Vec<Vec<u8>> {
+    let organizer = Pubkey::new_unique();
+    let tournament = swiss_tournament(organizer);
+    let tournament_key = Pubkey::new_unique();
+    let mut accounts = [
+        Account::owned(tournament_key, &tournament),
+        Account::wallet(organizer, true),
+        Account::owned(Pubkey::new_unique(), &game(GameStatus::Finished)),
+    ];
+    let infos = infos(&mut accounts);
+    let mut tournament = AnchorAccount::<Tournament>::try_from(&infos[0]).unwrap();
+    let organizer = Signer::try_from(&infos[1]).unwrap();
+    let mut game = AnchorAccount::<GameState>::try_from(&infos[2]).unwrap();
+    game.tournament = tournament_key;
+
+    let mut met = vec![Vec::new(); PLAYERS];
+    let mut bit = 0;
+    for _ in 0..ROUNDS {
+        let mut ctx_accounts = TournamentOrganizer {
+            tournament: tournament.clone(),
+            organizer: organizer.clone(),
+        };
+        let ctx = Context::new(
+            &pooler::ID,
+            &mut ctx_accounts,
+            &[],
+            TournamentOrganizerBumps::default(),
+        );
+        pooler::backgammon::pair_round(ctx).unwrap();
+        tournament = ctx_accounts.tournament;
+
+        for (a, opponents) in met.iter_mut().enumerate() {
+            let b = tournament.pairings[a];
+            assert_ne!(b, SWISS_BYE, "player {a} got a bye in an even field");
+            assert_eq!(tournament.pairings[b as usize] as usize, a);
+            opponents.push(b);
+            if a > b as usize {
+                continue;
+            }
+            game.player1 = tournament.players[a];
+            game.player2 = tournament.players[b as usize];
+            game.winner = if outcomes & (1 << bit) == 0 {
+                game.player1
+            } else {
+                game.player2
+            };
+            game.tournament_round = tournament.round;
+            bit += 1;
+
+            let mut ctx_accounts = ReportResult {
+                tournament: tournament.clone(),
+                game: game.clone(),
+                organizer: organizer.clone(),
+            };
+            let ctx = Context::new(
+                &pooler::ID,
+                &mut ctx_accounts,
+                &[],
+                ReportResultBumps::default(),
+            );
+            pooler::backgammon::report_swiss_result(ctx).unwrap();
+            tournament = ctx_accounts.tournament;
+        }
+    }
+    assert_eq!(tournament.round, ROUNDS);
+    met
+}
+
+#[test]
+fn no_rematches_for_any_results_in_8_players_3_rounds() {
+    // 4 партии в туре, 3 тура — 12 партий; перебираются все исходы.
+    for outcomes in 0..1u32 << (PLAYERS / 2 * ROUNDS as usize) {
+        for (player, opponents) in play(outcomes).iter().enumerate() {
+            let mut distinct = opponents.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            assert_eq!(
+                distinct.len(),
+                ROUNDS as usize,
+                "player {player} met {opponents:?} with outcomes {outcomes:#b}"
+            );
+            assert!(!opponents.contains(&(player as u8)));
+        }
+    }
+}
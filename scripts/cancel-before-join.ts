@@ -3,6 +3,12 @@ import { Program, Wallet } from "@coral-xyz/anchor";
 import { Keypair, SystemProgram } from "@solana/web3.js";
 import * as fs from "fs";
 import { Backgammon } from "../target/types/backgammon";
+import {
+  defaultGameOptions,
+  eventCpiAccounts,
+  initGameAccounts,
+  pda,
+} from "./common";
 
 function loadKeypair(path: string): Keypair {
   const secret = JSON.parse(fs.readFileSync(path, "utf8"));
@@ -55,13 +61,10 @@ async function main() {
       gameId,
       stakeLamports,
       moveFeeLamports,
-      player2.publicKey
+      player2.publicKey,
+      defaultGameOptions(program)
     )
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
+    .accountsStrict(initGameAccounts(program, game.publicKey, player1.publicKey))
     .signers([player1, game])
     .rpc();
 
@@ -80,10 +83,14 @@ async function main() {
   // cancel_before_join
   await program.methods
     .cancelBeforeJoin()
-    .accounts({
+    .accountsStrict({
       game: game.publicKey,
       player1: player1.publicKey,
       systemProgram: SystemProgram.programId,
+      player1OpenGames: pda(program, "open_games", player1.publicKey.toBuffer()),
+      player1Splits: pda(program, "payout_splits", player1.publicKey.toBuffer()),
+      eventBuffer: null,
+      ...eventCpiAccounts(program),
     } as any)
     .signers([player1])
     .rpc();
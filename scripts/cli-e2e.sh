@@ -0,0 +1,43 @@
+#!/usr/bin/env bash
+# Сквозной прогон партии через backgammon-cli на локальном валидаторе:
+# create -> join -> 2 хода -> finish с чужим победителем (ожидаем ошибку
+# InvalidWinner и ненулевой код) -> finish (победа player1) -> show.
+#
+# Нужны запущенный solana-test-validator с задеплоенной программой,
+# созданный config (init_config) и ключи keys/player{1,2}.
+set -euo pipefail
+cd "$(dirname "$0")/.."
+
+URL=${URL:-http://127.0.0.1:8899}
+P1=keys/player1/player1.json
+P2=keys/player2/player2.json
+
+cargo build -q -p backgammon-cli
+CLI="target/debug/backgammon-cli --url $URL"
+
+P1_PK=$(solana-keygen pubkey "$P1")
+P2_PK=$(solana-keygen pubkey "$P2")
+
+GAME=$($CLI --keypair "$P1" create --stake 500000000 --move-fee 10000000 --opponent "$P2_PK" \
+  | sed -n 's/^game: //p')
+echo "game: $GAME"
+
+$CLI --keypair "$P2" join "$GAME"
+
+$CLI --keypair "$P1" move "$GAME" \
+  --board "1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0" --dice 3,5
+$CLI --keypair "$P2" move "$GAME" \
+  --board "1,2,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0" --dice 2,6
+
+# Заведомо не игрок этой партии.
+STRANGER=11111111111111111111111111111111
+if OUT=$($CLI --keypair "$P1" finish "$GAME" --winner "$STRANGER" --opponent-keypair "$P2" 2>&1); then
+  echo "finish with a stranger as winner unexpectedly succeeded" >&2
+  exit 1
+fi
+echo "$OUT" | grep -q "InvalidWinner" || { echo "unexpected error: $OUT" >&2; exit 1; }
+
+$CLI --keypair "$P1" finish "$GAME" --winner "$P1_PK" --opponent-keypair "$P2"
+
+$CLI --keypair "$P1" show "$GAME" | tee /dev/stderr | grep -q "status: *Finished"
+echo "cli e2e OK"
@@ -0,0 +1,210 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { Backgammon } from "../target/types/backgammon";
+
+// Типы IDL в том объёме, который нужен defaultValue. Program уже перевёл имена
+// в camelCase (инструкции, поля, типы и варианты enum).
+type IdlTypeJson =
+  | string
+  | { option: IdlTypeJson }
+  | { coption: IdlTypeJson }
+  | { vec: IdlTypeJson }
+  | { array: [IdlTypeJson, number] }
+  | { defined: { name: string } };
+
+type IdlJson = {
+  instructions: { name: string; args: { name: string; type: IdlTypeJson }[] }[];
+  types: {
+    name: string;
+    type:
+      | { kind: "struct"; fields?: { name: string; type: IdlTypeJson }[] }
+      | { kind: "enum"; variants: { name: string }[] };
+  }[];
+};
+
+/** PDA программы по seeds; строки кодируются как utf8. */
+export function pda(program: Program<Backgammon>, ...seeds: (string | Buffer)[]): PublicKey {
+  const [address] = PublicKey.findProgramAddressSync(
+    seeds.map((seed) => (typeof seed === "string" ? Buffer.from(seed) : seed)),
+    program.programId
+  );
+  return address;
+}
+
+function defaultValue(idl: IdlJson, type: IdlTypeJson): unknown {
+  if (typeof type === "string") {
+    switch (type) {
+      case "bool":
+        return false;
+      case "u64":
+      case "i64":
+      case "u128":
+      case "i128":
+        return new anchor.BN(0);
+      case "pubkey":
+        return PublicKey.default;
+      case "bytes":
+        return Buffer.alloc(0);
+      case "string":
+        return "";
+      default:
+        return 0;
+    }
+  }
+  if ("option" in type || "coption" in type) {
+    return null;
+  }
+  if ("vec" in type) {
+    return [];
+  }
+  if ("array" in type) {
+    const [inner, len] = type.array;
+    return Array.from({ length: len }, () => defaultValue(idl, inner));
+  }
+
+  const typeName = type.defined.name;
+  const typeDef = idl.types.find((t) => t.name === typeName);
+  if (!typeDef) {
+    throw new Error(`Type ${typeName} not found in IDL`);
+  }
+  if (typeDef.type.kind === "enum") {
+    return { [typeDef.type.variants[0].name]: {} };
+  }
+  const value: Record<string, unknown> = {};
+  for (const field of typeDef.type.fields ?? []) {
+    value[field.name] = defaultValue(idl, field.type);
+  }
+  return value;
+}
+
+/**
+ * GameOptions по умолчанию, собранные по IDL как #[derive(Default)] в
+ * программе: Casual-игра без дополнительных правил.
+ */
+export function defaultGameOptions(program: Program<Backgammon>): any {
+  const idl = program.idl as unknown as IdlJson;
+  const initGame = idl.instructions.find((ix) => ix.name === "initGame");
+  const options = initGame?.args.find((arg) => arg.name === "options");
+  if (!options) {
+    throw new Error("initGame options not found in IDL");
+  }
+  return defaultValue(idl, options.type);
+}
+
+/**
+ * Аккаунты init_game для Casual-игры без FeeRoute, арбитра, истории и с
+ * game_id != 0 (null = необязательный аккаунт не передаётся).
+ */
+export function initGameAccounts(
+  program: Program<Backgammon>,
+  game: PublicKey,
+  player1: PublicKey
+): any {
+  return {
+    game,
+    player1,
+    systemProgram: SystemProgram.programId,
+    config: pda(program, "config"),
+    player1Ban: pda(program, "ban", player1.toBuffer()),
+    openGames: pda(program, "open_games", player1.toBuffer()),
+    timeControl: null,
+    createCooldown: pda(program, "create_cooldown", player1.toBuffer()),
+    feeRoute: null,
+    whitelistedArbiter: null,
+    gameCounter: null,
+    gameHistory: null,
+    historyCommitment: null,
+  };
+}
+
+/** Аккаунты join_game для игры из init_game выше (без размещения банка). */
+export function joinGameAccounts(
+  program: Program<Backgammon>,
+  game: PublicKey,
+  player1: PublicKey,
+  player2: PublicKey
+): any {
+  return {
+    game,
+    player2,
+    systemProgram: SystemProgram.programId,
+    config: pda(program, "config"),
+    player1OpenGames: pda(program, "open_games", player1.toBuffer()),
+    player2Ban: pda(program, "ban", player2.toBuffer()),
+    player2Stats: pda(program, "stats", player2.toBuffer()),
+    clientTelemetry: null,
+    eventBuffer: null,
+    lobby: null,
+    potStake: null,
+    potVault: null,
+    poolTokens: null,
+    poolMint: null,
+    stakePool: null,
+    stakePoolWithdrawAuthority: null,
+    reserveStake: null,
+    managerFeeAccount: null,
+    stakePoolProgram: null,
+    tokenProgram: null,
+  };
+}
+
+/** Аккаунты event-cpi: PDA [b"__event_authority"] и сама программа. */
+export function eventCpiAccounts(program: Program<Backgammon>): any {
+  return {
+    eventAuthority: pda(program, "__event_authority"),
+    program: program.programId,
+  };
+}
+
+/**
+ * Казна из Config (null, если не задана). Возвраты отправляют ей остаток от
+ * деления банка, и тогда аккаунт treasury обязателен.
+ */
+export async function configTreasury(program: Program<Backgammon>): Promise<PublicKey | null> {
+  const config = await program.account.config.fetch(pda(program, "config"));
+  return config.treasury.equals(PublicKey.default) ? null : config.treasury;
+}
+
+/**
+ * make_move от стороны current_turn: подписывает только ходящий, соперник
+ * передаётся без подписи. Страховой пул передаётся, если он создан.
+ */
+export async function makeMove(
+  program: Program<Backgammon>,
+  game: PublicKey,
+  player1: Keypair,
+  player2: Keypair,
+  newBoardPoints: number[],
+  newDice: number[]
+): Promise<void> {
+  const gameAccount = await program.account.gameState.fetch(game);
+  const [mover, opponent] =
+    gameAccount.currentTurn === 1 ? [player1, player2] : [player2, player1];
+  const insurancePool = pda(program, "insurance_pool");
+  const insurancePoolExists =
+    (await program.provider.connection.getAccountInfo(insurancePool)) !== null;
+
+  await program.methods
+    .makeMove(newBoardPoints, newDice, new Array<number>(32).fill(0), null)
+    .accountsStrict({
+      game,
+      mover: mover.publicKey,
+      opponent: opponent.publicKey,
+      systemProgram: SystemProgram.programId,
+      botVault: null,
+      config: pda(program, "config"),
+      team: null,
+      timeControl: null,
+      feeRoute: null,
+      feeRecipient: null,
+      insurancePool: insurancePoolExists ? insurancePool : null,
+      clientTelemetry: null,
+      eventBuffer: null,
+      gameHistory: null,
+      historyCommitment: null,
+      decaySink: null,
+    } as any)
+    .signers([mover])
+    .rpc();
+}
@@ -3,6 +3,15 @@ import { Program, Wallet } from "@coral-xyz/anchor";
 import { Keypair, SystemProgram } from "@solana/web3.js";
 import * as fs from "fs";
 import { Backgammon } from "../target/types/backgammon";
+import {
+  configTreasury,
+  defaultGameOptions,
+  eventCpiAccounts,
+  initGameAccounts,
+  joinGameAccounts,
+  makeMove,
+  pda,
+} from "./common";
 
 function loadKeypair(path: string): Keypair {
   const secret = JSON.parse(fs.readFileSync(path, "utf8"));
@@ -60,13 +69,10 @@ async function main() {
       gameId,
       stakeLamports,
       moveFeeLamports,
-      player2.publicKey
+      player2.publicKey,
+      defaultGameOptions(program)
     )
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
+    .accountsStrict(initGameAccounts(program, game.publicKey, player1.publicKey))
     .signers([player1, game])
     .rpc();
 
@@ -87,12 +93,10 @@ async function main() {
 
   // ---------------- join_game ----------------
   await program.methods
-    .joinGame()
-    .accounts({
-      game: game.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
+    .joinGame(Buffer.alloc(0), null)
+    .accountsStrict(
+      joinGameAccounts(program, game.publicKey, player1.publicKey, player2.publicKey)
+    )
     .signers([player2])
     .rpc();
 
@@ -114,16 +118,7 @@ async function main() {
   boardAfterMove1[0] = 1;
   const diceAfterMove1 = [3, 5];
 
-  await program.methods
-    .makeMove(boardAfterMove1, diceAfterMove1)
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
-    .signers([player1, player2])
-    .rpc();
+  await makeMove(program, game.publicKey, player1, player2, boardAfterMove1, diceAfterMove1);
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
   console.log("After make_move #1:", {
@@ -144,16 +139,7 @@ async function main() {
   boardAfterMove2[1] = 2;
   const diceAfterMove2 = [2, 6];
 
-  await program.methods
-    .makeMove(boardAfterMove2, diceAfterMove2)
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
-    .signers([player1, player2])
-    .rpc();
+  await makeMove(program, game.publicKey, player1, player2, boardAfterMove2, diceAfterMove2);
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
   console.log("After make_move #2:", {
@@ -173,16 +159,14 @@ async function main() {
   const boardAfterMove3 = [...boardAfterMove2];
   boardAfterMove3[2] = 3;
 
-  await program.methods
-    .makeMove(boardAfterMove3, diceAfterMove2 /* условно переиспользуем */)
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
-    .signers([player1, player2])
-    .rpc();
+  await makeMove(
+    program,
+    game.publicKey,
+    player1,
+    player2,
+    boardAfterMove3,
+    diceAfterMove2 /* условно переиспользуем */
+  );
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
   console.log("After make_move #3:", {
@@ -215,13 +199,25 @@ async function main() {
     currentSlot - gameAccount.lastActivitySlot.toNumber()
   );
 
+  // Остаток от деления банка уходит в казну, если она задана в Config
+  const treasury = await configTreasury(program);
+
   await program.methods
     .forceRefund()
-    .accounts({
+    .accountsStrict({
       game: game.publicKey,
       player1: player1.publicKey,
       player2: player2.publicKey,
       systemProgram: SystemProgram.programId,
+      botVault: null,
+      team: null,
+      timeControl: null,
+      player1Splits: pda(program, "payout_splits", player1.publicKey.toBuffer()),
+      player2Splits: pda(program, "payout_splits", player2.publicKey.toBuffer()),
+      eventBuffer: null,
+      config: pda(program, "config"),
+      treasury,
+      ...eventCpiAccounts(program),
     } as any)
     .signers([player1, player2]) // инициировать может второй, но оба подписывают
     .rpc();
@@ -3,6 +3,15 @@ import { Program, Wallet } from "@coral-xyz/anchor";
 import { Keypair, SystemProgram } from "@solana/web3.js";
 import * as fs from "fs";
 import { Backgammon } from "../target/types/backgammon";
+import {
+  configTreasury,
+  defaultGameOptions,
+  eventCpiAccounts,
+  initGameAccounts,
+  joinGameAccounts,
+  makeMove,
+  pda,
+} from "./common";
 
 function loadKeypair(path: string): Keypair {
   const secret = JSON.parse(fs.readFileSync(path, "utf8"));
@@ -64,13 +73,10 @@ async function main() {
       gameId,
       stakeLamports,
       moveFeeLamports,
-      player2.publicKey
+      player2.publicKey,
+      defaultGameOptions(program)
     )
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
+    .accountsStrict(initGameAccounts(program, game.publicKey, player1.publicKey))
     .signers([player1, game])
     .rpc();
 
@@ -91,12 +97,10 @@ async function main() {
 
   // ---------------- join_game ----------------
   await program.methods
-    .joinGame()
-    .accounts({
-      game: game.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
+    .joinGame(Buffer.alloc(0), null)
+    .accountsStrict(
+      joinGameAccounts(program, game.publicKey, player1.publicKey, player2.publicKey)
+    )
     .signers([player2])
     .rpc();
 
@@ -118,16 +122,7 @@ async function main() {
   boardAfterMove1[0] = 1;
   const diceAfterMove1 = [3, 5];
 
-  await program.methods
-    .makeMove(boardAfterMove1, diceAfterMove1)
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
-    .signers([player1, player2])
-    .rpc();
+  await makeMove(program, game.publicKey, player1, player2, boardAfterMove1, diceAfterMove1);
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
   console.log("After make_move #1:", {
@@ -148,16 +143,7 @@ async function main() {
   boardAfterMove2[1] = 2;
   const diceAfterMove2 = [2, 6];
 
-  await program.methods
-    .makeMove(boardAfterMove2, diceAfterMove2)
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
-    .signers([player1, player2])
-    .rpc();
+  await makeMove(program, game.publicKey, player1, player2, boardAfterMove2, diceAfterMove2);
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
   console.log("After make_move #2:", {
@@ -177,16 +163,14 @@ async function main() {
   const boardAfterMove3 = [...boardAfterMove2];
   boardAfterMove3[2] = 3;
 
-  await program.methods
-    .makeMove(boardAfterMove3, diceAfterMove2 /* условно переиспользуем */)
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
-    .signers([player1, player2])
-    .rpc();
+  await makeMove(
+    program,
+    game.publicKey,
+    player1,
+    player2,
+    boardAfterMove3,
+    diceAfterMove2 /* условно переиспользуем */
+  );
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
   console.log("After make_move #3:", {
@@ -202,16 +186,29 @@ async function main() {
     player2: p2_balance / anchor.web3.LAMPORTS_PER_SOL,
   });
 
-  // ---------------- manual_refund (оба игрока согласны отменить игру) ----------------
+  // ---------------- manual_refund (игру отменяет один игрок, requester) ----------------
+  // Остаток от деления банка уходит в казну, если она задана в Config
+  const treasury = await configTreasury(program);
+
   await program.methods
     .manualRefund()
-    .accounts({
+    .accountsStrict({
       game: game.publicKey,
       player1: player1.publicKey,
       player2: player2.publicKey,
+      requester: player1.publicKey,
       systemProgram: SystemProgram.programId,
+      botVault: null,
+      escrowAgent: null,
+      team: null,
+      player1Splits: pda(program, "payout_splits", player1.publicKey.toBuffer()),
+      player2Splits: pda(program, "payout_splits", player2.publicKey.toBuffer()),
+      eventBuffer: null,
+      config: pda(program, "config"),
+      treasury,
+      ...eventCpiAccounts(program),
     } as any)
-    .signers([player1, player2])
+    .signers([player1])
     .rpc();
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
@@ -3,6 +3,14 @@ import { Program, Wallet } from "@coral-xyz/anchor";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import * as fs from "fs";
 import { Backgammon } from "../target/types/backgammon";
+import {
+  defaultGameOptions,
+  eventCpiAccounts,
+  initGameAccounts,
+  joinGameAccounts,
+  makeMove,
+  pda,
+} from "./common";
 
 function loadKeypair(path: string): Keypair {
   const secret = JSON.parse(fs.readFileSync(path, "utf8"));
@@ -60,12 +68,14 @@ async function main() {
 
   // ---------------- init_game ----------------
   await program.methods
-    .initGame(gameId, stakeLamports, moveFeeLamports, player2.publicKey)
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
+    .initGame(
+      gameId,
+      stakeLamports,
+      moveFeeLamports,
+      player2.publicKey,
+      defaultGameOptions(program)
+    )
+    .accountsStrict(initGameAccounts(program, game.publicKey, player1.publicKey))
     .signers([player1, game])
     .rpc();
 
@@ -86,14 +96,12 @@ async function main() {
 
   // ---------------- join_game ----------------
   await program.methods
-      .joinGame()
-      .accounts({
-        game: game.publicKey,
-        player2: player2.publicKey,
-        systemProgram: SystemProgram.programId,
-      } as any)
-      .signers([player2])
-      .rpc();
+    .joinGame(Buffer.alloc(0), null)
+    .accountsStrict(
+      joinGameAccounts(program, game.publicKey, player1.publicKey, player2.publicKey)
+    )
+    .signers([player2])
+    .rpc();
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
   console.log("After join_game:", {
@@ -113,16 +121,7 @@ async function main() {
   boardAfterMove1[0] = 1; // условный ход
   const diceAfterMove1 = [3, 5];
 
-  await program.methods
-    .makeMove(boardAfterMove1, diceAfterMove1)
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
-    .signers([player1, player2])
-    .rpc();
+  await makeMove(program, game.publicKey, player1, player2, boardAfterMove1, diceAfterMove1);
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
   console.log("After make_move #1:", {
@@ -143,16 +142,7 @@ async function main() {
   boardAfterMove2[1] = 2; // условный ход
   const diceAfterMove2 = [2, 6];
 
-  await program.methods
-    .makeMove(boardAfterMove2, diceAfterMove2)
-    .accounts({
-      game: game.publicKey,
-      player1: player1.publicKey,
-      player2: player2.publicKey,
-      systemProgram: SystemProgram.programId,
-    } as any)
-    .signers([player1, player2])
-    .rpc();
+  await makeMove(program, game.publicKey, player1, player2, boardAfterMove2, diceAfterMove2);
 
   gameAccount = await program.account.gameState.fetch(game.publicKey);
   console.log("After make_move #2:", {
@@ -170,12 +160,26 @@ async function main() {
 
   // ---------------- finish_game (выиграл player1) ----------------
   await program.methods
-    .finishGame(player1.publicKey)
-    .accounts({
+    .finishGame(player1.publicKey, 1) // 1 = обычная победа (без марса)
+    .accountsStrict({
       game: game.publicKey,
       player1: player1.publicKey,
       player2: player2.publicKey,
-    })
+      systemProgram: SystemProgram.programId,
+      config: pda(program, "config"),
+      season: null,
+      player1Stats: null,
+      player2Stats: null,
+      botVault: null,
+      escrowAgent: null,
+      team: null,
+      player1Splits: pda(program, "payout_splits", player1.publicKey.toBuffer()),
+      player2Splits: pda(program, "payout_splits", player2.publicKey.toBuffer()),
+      eventBuffer: null,
+      feeVault: null,
+      incinerator: null,
+      ...eventCpiAccounts(program),
+    } as any)
     .signers([player1, player2])
     .rpc();
 
@@ -0,0 +1,65 @@
+//! Запросы getProgramAccounts с memcmp-фильтрами: открытые лобби и активные
+//! игры игрока на локальном валидаторе.
+//!
+//! Запуск: `cargo run -p backgammon-client --example query_games [playerPubkey]`
+
+use std::error::Error;
+
+use anchor_lang::AccountDeserialize;
+use backgammon_client::{filters, GameState, Pubkey, PROGRAM_ID};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_rpc_client_api::filter::RpcFilterType;
+
+fn query(
+    client: &RpcClient,
+    filters: Vec<RpcFilterType>,
+) -> Result<Vec<(Pubkey, GameState)>, Box<dyn Error>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    // Аккаунты старых раскладок до migrate_game_state не разбираются — пропускаем.
+    Ok(client
+        .get_program_accounts_with_config(&PROGRAM_ID, config)?
+        .into_iter()
+        .filter_map(|(address, account)| {
+            let game = GameState::try_deserialize(&mut &account.data[..]).ok()?;
+            Some((address, game))
+        })
+        .collect())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let client = RpcClient::new("http://127.0.0.1:8899".to_string());
+
+    let open = query(&client, filters::open_games())?;
+    println!("Open games: {}", open.len());
+    for (address, game) in &open {
+        println!(
+            "   {address} game_id={} stake={}",
+            game.game_id, game.stake_lamports
+        );
+    }
+
+    let Some(player) = std::env::args().nth(1) else {
+        return Ok(());
+    };
+    let player: Pubkey = player.parse()?;
+
+    // memcmp-фильтры объединяются только по «и»: player1 и player2 — два запроса.
+    let sides = ["player1", "player2"];
+    for (side, filters) in sides.into_iter().zip(filters::active_games_of(&player)) {
+        let games = query(&client, filters)?;
+        println!("Active games where {player} is {side}: {}", games.len());
+        for (address, game) in &games {
+            println!("   {address} game_id={}", game.game_id);
+        }
+    }
+    Ok(())
+}
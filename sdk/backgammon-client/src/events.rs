@@ -0,0 +1,143 @@
+//! Разбор событий программы из логов транзакции и из данных self-CPI.
+//!
+//! События `emit!` попадают в лог строкой `Program data: <base64>` внутри
+//! кадра вызова программы. [`parse_events`] следит за кадрами
+//! `Program <id> invoke [n]` … `success`/`failed`, поэтому строки других
+//! программ (system, token, программы, вызвавшей нашу через CPI) не
+//! принимаются за наши, даже если их данные похожи на событие.
+//!
+//! События `emit_cpi!` (GameFinished, GameCancelled, GameRefunded) в лог не
+//! пишутся: это внутренняя инструкция программы самой себе. Её данные
+//! (inner instructions из `getTransaction`) разбирает [`parse_cpi_event`].
+//!
+//! ErrorContext пишется перед отказом инструкции, поэтому встречается и в
+//! кадрах, закончившихся `failed`.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use crate::PROGRAM_ID;
+
+macro_rules! game_events {
+    ($($name:ident),* $(,)?) => {
+        /// Событие программы.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum GameEvent {
+            $($name(pooler::$name),)*
+        }
+
+        impl GameEvent {
+            /// Событие по данным `дискриминатор || borsh`; None — не наше событие.
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                $(
+                    if let Some(mut body) = data.strip_prefix(pooler::$name::DISCRIMINATOR) {
+                        return pooler::$name::deserialize(&mut body).ok().map(GameEvent::$name);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+game_events! {
+    GameInitialized,
+    PotDecayEnabled,
+    PlayerJoined,
+    GameFinished,
+    GameCancelled,
+    GameRefunded,
+    GameFlagged,
+    InsuranceCompensated,
+    GameEscheated,
+    PotStaked,
+    PotUnstaked,
+    GameLogReplayed,
+    MoveCapSettled,
+    WinClaimed,
+    NegotiatedSettlement,
+    EvidenceSubmitted,
+    MoveMade,
+    PotTipped,
+    AutoDoubleSkipped,
+    ErrorContext,
+    ProtocolFeeCollected,
+    ProtocolFeesWithdrawn,
+    GamesPruned,
+    AdminProposed,
+    AdminChanged,
+    ResolutionProposed,
+    ResolutionAppealed,
+    CounterOffered,
+    CounterAccepted,
+    CounterWithdrawn,
+    SidesSwapped,
+    HistoryEntryVerified,
+}
+
+/// Строка лога, выведенная самой программой (не вложенным вызовом).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgramLine<'a> {
+    /// `Program log: …` (msg!), без префикса.
+    Log(&'a str),
+    /// `Program data: …` (emit!), base64 без префикса.
+    Data(&'a str),
+}
+
+/// Строки логов, выведенные программой program_id, по порядку.
+///
+/// Логи нескольких транзакций можно склеивать: каждая начинается с
+/// `invoke [1]` и заканчивается его `success`/`failed`. Строки вне кадров и
+/// `Log truncated` пропускаются.
+pub fn program_lines<'a, S: AsRef<str>>(
+    program_id: &Pubkey,
+    logs: &'a [S],
+) -> impl Iterator<Item = ProgramLine<'a>> + 'a {
+    let id = program_id.to_string();
+    let mut frames: Vec<&'a str> = Vec::new();
+    logs.iter().filter_map(move |line| {
+        let rest = line.as_ref().strip_prefix("Program ")?;
+        let ours = frames.last() == Some(&id.as_str());
+        if let Some(message) = rest.strip_prefix("log: ") {
+            return ours.then_some(ProgramLine::Log(message));
+        }
+        if let Some(data) = rest.strip_prefix("data: ") {
+            return ours.then_some(ProgramLine::Data(data));
+        }
+        let (program, status) = rest.split_once(' ')?;
+        if status.starts_with("invoke [") {
+            frames.push(program);
+        } else if status == "success" || status.starts_with("failed") {
+            // Кадр закрывается своим же id; при обрезанных логах снимаем
+            // лишние кадры до него.
+            if let Some(at) = frames.iter().rposition(|&p| p == program) {
+                frames.truncate(at);
+            }
+        }
+        None
+    })
+}
+
+/// Событие из base64 строки `Program data:` (см. [`ProgramLine::Data`]).
+pub fn parse_data(data: &str) -> Option<GameEvent> {
+    GameEvent::decode(&BASE64.decode(data).ok()?)
+}
+
+/// События программы из логов транзакции (или нескольких транзакций подряд).
+pub fn parse_events<S: AsRef<str>>(logs: &[S]) -> Vec<GameEvent> {
+    program_lines(&PROGRAM_ID, logs)
+        .filter_map(|line| match line {
+            ProgramLine::Data(data) => parse_data(data),
+            ProgramLine::Log(_) => None,
+        })
+        .collect()
+}
+
+/// Событие `emit_cpi!` из данных внутренней инструкции, вызванной программой
+/// у самой себя (program id внутренней инструкции должен быть PROGRAM_ID).
+pub fn parse_cpi_event(data: &[u8]) -> Option<GameEvent> {
+    GameEvent::decode(data.strip_prefix(EVENT_IX_TAG_LE)?)
+}
@@ -0,0 +1,169 @@
+//! Загрузка и разбор аккаунтов программы.
+//!
+//! SDK не зависит от конкретного RPC-клиента: достаточно реализовать
+//! [`AccountSource`] поверх `solana_client::nonblocking::rpc_client::RpcClient`
+//! (`get_account_with_commitment`) или bankrun `BanksClient` (`get_account`),
+//! а для сводок через симуляцию — [`Simulator`] (`simulate_transaction`,
+//! поле `return_data`).
+
+use std::fmt;
+use std::future::Future;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize};
+use pooler::{Config, GameHistory, GameState, GameSummary, MoveRecord, MoveVerdict};
+
+use crate::{ix, pda};
+
+/// Источник сырых данных аккаунтов. `Ok(None)` — аккаунта нет.
+pub trait AccountSource {
+    type Error;
+
+    fn account_data(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = Result<Option<Vec<u8>>, Self::Error>>;
+}
+
+/// Симуляция транзакции из одной инструкции; плательщика выбирает
+/// реализация. Возвращает return data: program id и данные, `Ok(None)` —
+/// программа ничего не вернула.
+pub trait Simulator {
+    type Error;
+
+    fn return_data(
+        &self,
+        instruction: &Instruction,
+    ) -> impl Future<Output = Result<Option<(Pubkey, Vec<u8>)>, Self::Error>>;
+}
+
+#[derive(Debug)]
+pub enum FetchError<E> {
+    /// Ошибка источника данных.
+    Source(E),
+    /// Аккаунт не существует.
+    NotFound(Pubkey),
+    /// Данные не разбираются как ожидаемый аккаунт (чужой дискриминатор и т.п.).
+    Decode(Pubkey, anchor_lang::error::Error),
+}
+
+impl<E: fmt::Display> fmt::Display for FetchError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Source(err) => write!(f, "account source error: {}", err),
+            FetchError::NotFound(address) => write!(f, "account {} not found", address),
+            FetchError::Decode(address, err) => {
+                write!(f, "failed to decode account {}: {}", address, err)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for FetchError<E> {}
+
+/// Разбирает данные аккаунта с проверкой дискриминатора.
+pub fn decode<T: AccountDeserialize>(mut data: &[u8]) -> anchor_lang::Result<T> {
+    T::try_deserialize(&mut data)
+}
+
+pub async fn fetch_account<T: AccountDeserialize, S: AccountSource>(
+    source: &S,
+    address: &Pubkey,
+) -> Result<T, FetchError<S::Error>> {
+    let data = source
+        .account_data(address)
+        .await
+        .map_err(FetchError::Source)?
+        .ok_or(FetchError::NotFound(*address))?;
+    decode(&data).map_err(|err| FetchError::Decode(*address, err))
+}
+
+pub async fn fetch_game<S: AccountSource>(
+    source: &S,
+    game: &Pubkey,
+) -> Result<GameState, FetchError<S::Error>> {
+    fetch_account(source, game).await
+}
+
+pub async fn fetch_config<S: AccountSource>(source: &S) -> Result<Config, FetchError<S::Error>> {
+    fetch_account(source, &pda::config()).await
+}
+
+/// Все записанные ходы игры по порядку: чанки GameHistory читаются от
+/// текущего (`GameState::current_history_chunk`) назад по `prev_chunk`.
+/// Игра без истории — `NotFound` для чанка 0.
+pub async fn fetch_game_history<S: AccountSource>(
+    source: &S,
+    game: &Pubkey,
+) -> Result<Vec<MoveRecord>, FetchError<S::Error>> {
+    let state = fetch_game(source, game).await?;
+    if !state.move_history {
+        return Err(FetchError::NotFound(pda::game_history(game, 0)));
+    }
+
+    let mut chunks = Vec::new();
+    let mut address = pda::game_history(game, state.current_history_chunk);
+    loop {
+        let chunk: GameHistory = fetch_account(source, &address).await?;
+        if chunk.game != *game {
+            return Err(FetchError::Decode(
+                address,
+                ErrorCode::ConstraintHasOne.into(),
+            ));
+        }
+        let first = chunk.chunk_index == 0;
+        address = chunk.prev_chunk;
+        chunks.push(chunk);
+        if first {
+            break;
+        }
+    }
+    Ok(chunks
+        .into_iter()
+        .rev()
+        .flat_map(|chunk| chunk.moves)
+        .collect())
+}
+
+/// Сводка игры одной симуляцией get_game_summary, без чтения GameState.
+pub async fn simulate_game_summary<S: Simulator>(
+    simulator: &S,
+    game: &Pubkey,
+) -> Result<GameSummary, FetchError<S::Error>> {
+    simulate(simulator, game, &ix::get_game_summary_ix(game)).await
+}
+
+/// Вердикт validate_move: принял бы make_move эту доску при
+/// expected_move_index (см. [`MoveVerdict::reason`]).
+pub async fn simulate_validate_move<S: Simulator>(
+    simulator: &S,
+    game: &Pubkey,
+    new_board_points: [i8; 24],
+    expected_move_index: u64,
+) -> Result<MoveVerdict, FetchError<S::Error>> {
+    let ix = ix::validate_move_ix(game, new_board_points, expected_move_index);
+    simulate(simulator, game, &ix).await
+}
+
+/// Return data программы из симуляции instruction по игре game.
+async fn simulate<T: AnchorDeserialize, S: Simulator>(
+    simulator: &S,
+    game: &Pubkey,
+    instruction: &Instruction,
+) -> Result<T, FetchError<S::Error>> {
+    let (program_id, data) = simulator
+        .return_data(instruction)
+        .await
+        .map_err(FetchError::Source)?
+        .ok_or(FetchError::NotFound(*game))?;
+    if program_id != pooler::ID {
+        return Err(FetchError::Decode(
+            *game,
+            ErrorCode::InvalidProgramId.into(),
+        ));
+    }
+    T::deserialize(&mut &data[..])
+        .map_err(|_| FetchError::Decode(*game, ErrorCode::AccountDidNotDeserialize.into()))
+}
@@ -0,0 +1,55 @@
+//! Фильтры `getProgramAccounts` для аккаунтов игр. Смещения берутся из
+//! [`pooler::layout`], поэтому фильтры не расходятся с раскладкой `GameState`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use pooler::layout;
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+
+use crate::{GameState, GameStatus};
+
+fn memcmp(offset: usize, bytes: &[u8]) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, bytes.to_vec()))
+}
+
+/// Отсекает аккаунты других типов программы.
+pub fn game_state_filter() -> RpcFilterType {
+    memcmp(layout::DISCRIMINATOR, GameState::DISCRIMINATOR)
+}
+
+pub fn status_filter(status: GameStatus) -> RpcFilterType {
+    memcmp(layout::STATUS, &[status as u8])
+}
+
+pub fn game_id_filter(game_id: u64) -> RpcFilterType {
+    memcmp(layout::GAME_ID, &game_id.to_le_bytes())
+}
+
+pub fn winner_filter(winner: &Pubkey) -> RpcFilterType {
+    memcmp(layout::WINNER, winner.as_ref())
+}
+
+/// Игры со статусом `status`, где `player` — player1 или player2.
+/// memcmp-фильтры объединяются только по «и», поэтому это два запроса.
+pub fn games_by_player(player: &Pubkey, status: GameStatus) -> [Vec<RpcFilterType>; 2] {
+    [layout::PLAYER1, layout::PLAYER2].map(|offset| {
+        vec![
+            game_state_filter(),
+            memcmp(offset, player.as_ref()),
+            status_filter(status),
+        ]
+    })
+}
+
+/// Все активные игры игрока.
+pub fn active_games_of(player: &Pubkey) -> [Vec<RpcFilterType>; 2] {
+    games_by_player(player, GameStatus::Active)
+}
+
+/// Открытые лобби (ждут второго игрока).
+pub fn open_games() -> Vec<RpcFilterType> {
+    vec![
+        game_state_filter(),
+        status_filter(GameStatus::WaitingForPlayer2),
+    ]
+}
@@ -0,0 +1,148 @@
+//! История партии из логов её транзакций и запись в формате `.mat`;
+//! доказательства ходов для Merkle-коммитмента истории.
+//!
+//! Ходы берутся из события `MoveMade`, действия с кубом — из строк `msg!`
+//! инструкций offer_double / take_double / auto_take / drop_double,
+//! множитель результата — из лога finish_game; учитываются только строки
+//! самой программы (см. [`events::program_lines`]). Логи подаются в порядке
+//! транзакций: `getSignaturesForAddress` по адресу игры от старых к новым,
+//! затем `getTransaction` каждой.
+
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use pooler::{GameState, GameStatus, HistoryHasher, MoveRecord};
+
+use crate::events::{self, GameEvent, ProgramLine};
+use crate::PROGRAM_ID;
+
+pub use backgammon_core::{Action, MerkleProof, Outcome, Transcript, TranscriptError};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum HistoryError {
+    /// Доска игры ведётся не со стартовой позиции (board_from_start = false),
+    /// ходы не переигрываются.
+    NotFromStart,
+    /// В логах ходы только до found, а в игре move_index = expected.
+    MissingMoves { found: u64, expected: u64 },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::NotFromStart => {
+                write!(f, "game board is not tracked from the starting position")
+            }
+            HistoryError::MissingMoves { found, expected } => write!(
+                f,
+                "logs contain moves up to {}, game is at move {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+/// Запись партии game по её состоянию и логам транзакций.
+///
+/// Ходы сверх move_index игры (неподтверждённый или отклонённый ход
+/// двухфазного режима) не попадают в запись. Итог пишется только для
+/// Finished; очки — куб × множитель finish_game, при отказе от удвоения —
+/// куб до удвоения.
+pub fn transcript<S: AsRef<str>>(
+    game: &Pubkey,
+    state: &GameState,
+    logs: &[S],
+) -> Result<Transcript, HistoryError> {
+    if !state.board_from_start {
+        return Err(HistoryError::NotFromStart);
+    }
+
+    let mut actions = Vec::new();
+    let mut last_move = 0u64;
+    let mut cube = 1u32;
+    let mut multiplier = 1u32;
+    for line in events::program_lines(&PROGRAM_ID, logs) {
+        let message = match line {
+            ProgramLine::Log(message) => message,
+            ProgramLine::Data(data) => {
+                if let Some(GameEvent::MoveMade(event)) = events::parse_data(data) {
+                    if event.game == *game
+                        && event.move_index == last_move + 1
+                        && event.move_index <= state.move_index
+                    {
+                        actions.push(Action::Move {
+                            dice: event.dice,
+                            board: event.board_points,
+                        });
+                        last_move = event.move_index;
+                    }
+                }
+                continue;
+            }
+        };
+        if message.starts_with("offer_double: completed") {
+            actions.push(Action::Double { cube: cube * 2 });
+        } else if message.starts_with("take_double: completed")
+            || message.starts_with("auto_take: completed")
+        {
+            actions.push(Action::Take);
+            cube *= 2;
+        } else if message.starts_with("drop_double: completed") {
+            actions.push(Action::Drop);
+        } else if let Some(rest) = message.strip_prefix("finish_game: multiplier=") {
+            multiplier = rest
+                .split(',')
+                .next()
+                .and_then(|m| m.parse().ok())
+                .unwrap_or(1);
+        }
+    }
+    if last_move != state.move_index {
+        return Err(HistoryError::MissingMoves {
+            found: last_move,
+            expected: state.move_index,
+        });
+    }
+
+    let winner = [state.player1, state.player2]
+        .iter()
+        .position(|p| *p == state.winner && *p != Pubkey::default());
+    let outcome = match winner {
+        Some(side) if state.status == GameStatus::Finished => Some(Outcome {
+            winner: side as u8 + 1,
+            points: if actions.last() == Some(&Action::Drop) {
+                cube
+            } else {
+                state.cube_value as u32 * multiplier
+            },
+        }),
+        _ => None,
+    };
+
+    Ok(Transcript {
+        variant: state.rule_variant.core(),
+        handicap: state.handicap,
+        players: [state.player1.to_string(), state.player2.to_string()],
+        actions,
+        outcome,
+    })
+}
+
+/// Корень Merkle-дерева по всем записям ходов партии по порядку — то же
+/// значение, что `HistoryCommitment::history_root` после последнего из них.
+/// None — записей больше, чем помещается в дерево.
+pub fn history_root(records: &[MoveRecord]) -> Option<[u8; 32]> {
+    let leaves: Vec<_> = records.iter().map(MoveRecord::leaf_hash).collect();
+    backgammon_core::merkle_root::<HistoryHasher>(&leaves)
+}
+
+/// Доказательство записи хода `move_index` (с 1) для `verify_history_entry`
+/// по всем записям ходов партии по порядку (например, из
+/// [`crate::fetch::fetch_game_history`]). None — такого хода нет.
+pub fn history_proof(records: &[MoveRecord], move_index: u64) -> Option<MerkleProof> {
+    let index = usize::try_from(move_index.checked_sub(1)?).ok()?;
+    let leaves: Vec<_> = records.iter().map(MoveRecord::leaf_hash).collect();
+    backgammon_core::merkle_proof::<HistoryHasher>(&leaves, index)
+}
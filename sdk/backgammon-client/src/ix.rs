@@ -0,0 +1,785 @@
+//! Сборка инструкций программы.
+//!
+//! Билдеры заполняют обязательные аккаунты и те необязательные, которые
+//! следуют из состояния игры (контроль времени, FeeRoute, бот, команда,
+//! эскроу-агент, event CPI). Остальные необязательные аккаунты (сезон,
+//! статистика, event_buffer, лобби) передаются как `None`; когда они нужны,
+//! соберите `pooler::accounts::*` сами и вызовите [`build`].
+//!
+//! PDA сплитов выплат передаётся всегда; если получатель их зарегистрировал,
+//! допишите получателей в инструкцию через [`with_split_recipients`].
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use pooler::{
+    accounts, instruction, ClientInfo, Config, ConfigUpdate, GameOptions, GamePreset, GameState,
+    MoveRecord, PayoutSplits, StakePoolAccounts,
+};
+
+use crate::pda;
+
+/// Инструкция программы из контекста и аргументов, сгенерированных Anchor.
+pub fn build(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: pooler::ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+/// Дописывает получателей зарегистрированных сплитов выплат (в порядке
+/// записей) в конец инструкции выплаты. При выплате обоим игрокам сначала
+/// идут получатели первого.
+pub fn with_split_recipients(mut ix: Instruction, splits: &PayoutSplits) -> Instruction {
+    ix.accounts.extend(
+        splits
+            .splits
+            .iter()
+            .map(|split| AccountMeta::new(split.recipient, false)),
+    );
+    ix
+}
+
+fn open_games(state: &GameState) -> Option<Pubkey> {
+    state
+        .open_games_counted
+        .then(|| pda::open_games(&state.player1))
+}
+
+fn escrow_agent(state: &GameState) -> Option<Pubkey> {
+    (state.escrow_agent != Pubkey::default()).then_some(state.escrow_agent)
+}
+
+fn bot_vault(state: &GameState) -> Option<Pubkey> {
+    state.bot_game.then(pda::bot_vault)
+}
+
+fn team(game: &Pubkey, state: &GameState) -> Option<Pubkey> {
+    state.team_game.then(|| pda::team(game))
+}
+
+fn time_control(game: &Pubkey, preset: GamePreset) -> Option<Pubkey> {
+    (preset != GamePreset::Casual).then(|| pda::time_control(game))
+}
+
+fn fee_vault(config: &Config) -> Option<Pubkey> {
+    (config.protocol_fee_bps > 0).then(pda::fee_vault)
+}
+
+fn incinerator(config: &Config) -> Option<Pubkey> {
+    (config.protocol_fee_bps > 0 && config.burn_bps > 0).then_some(pooler::INCINERATOR)
+}
+
+fn treasury(config: &Config) -> Option<Pubkey> {
+    (config.treasury != Pubkey::default()).then_some(config.treasury)
+}
+
+pub fn init_config_ix(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitConfig {
+            config: pda::config(),
+            season: pda::season(0),
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::InitConfig {},
+    )
+}
+
+/// Изменение конфига; подписывает `admin`.
+pub fn update_config_ix(admin: &Pubkey, update: ConfigUpdate) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config(),
+            admin: *admin,
+        },
+        instruction::UpdateConfig { update },
+    )
+}
+
+/// Добавление арбитра в белый список; подписывает `admin`.
+pub fn add_arbiter_ix(admin: &Pubkey, arbiter: &Pubkey) -> Instruction {
+    build(
+        accounts::AddArbiter {
+            config: pda::config(),
+            whitelisted_arbiter: pda::whitelisted_arbiter(arbiter),
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::AddArbiter { arbiter: *arbiter },
+    )
+}
+
+/// Исключение арбитра из белого списка; подписывает `admin`.
+pub fn remove_arbiter_ix(admin: &Pubkey, arbiter: &Pubkey) -> Instruction {
+    build(
+        accounts::RemoveArbiter {
+            config: pda::config(),
+            whitelisted_arbiter: pda::whitelisted_arbiter(arbiter),
+            admin: *admin,
+        },
+        instruction::RemoveArbiter {},
+    )
+}
+
+/// Предложение нового администратора; подписывает текущий `admin`.
+pub fn propose_admin_ix(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::ProposeAdmin {
+            config: pda::config(),
+            admin: *admin,
+        },
+        instruction::ProposeAdmin {
+            new_admin: *new_admin,
+        },
+    )
+}
+
+/// Принятие прав администратора; подписывает предложенный `new_admin`.
+pub fn accept_admin_ix(new_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAdmin {
+            config: pda::config(),
+            new_admin: *new_admin,
+        },
+        instruction::AcceptAdmin {},
+    )
+}
+
+pub fn init_fee_vault_ix(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitFeeVault {
+            config: pda::config(),
+            fee_vault: pda::fee_vault(),
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::InitFeeVault {},
+    )
+}
+
+/// Вывод amount комиссии протокола на `treasury` (= `Config::treasury`).
+pub fn withdraw_protocol_fees_ix(admin: &Pubkey, treasury: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::WithdrawProtocolFees {
+            config: pda::config(),
+            fee_vault: pda::fee_vault(),
+            treasury: *treasury,
+            admin: *admin,
+        },
+        instruction::WithdrawProtocolFees { amount },
+    )
+}
+
+/// `games` — пары (аккаунт игры, её player1), не больше
+/// [`pooler::MAX_PRUNE_BATCH`].
+pub fn prune_games_ix(caller: &Pubkey, games: &[(Pubkey, Pubkey)]) -> Instruction {
+    let mut ix = build(
+        accounts::PruneGames {
+            config: pda::config(),
+            caller: *caller,
+        },
+        instruction::PruneGames {},
+    );
+    ix.accounts.extend(games.iter().flat_map(|(game, payer)| {
+        [
+            AccountMeta::new(*game, false),
+            AccountMeta::new(*payer, false),
+        ]
+    }));
+    ix
+}
+
+/// `game` — новый keypair-аккаунт игры, подписывает вместе с `player1`.
+/// С `game_id = 0` id выдаёт счётчик создателя (PDA `game_counter`), с
+/// `options.move_history` создаётся чанк 0 PDA `game_history`, с
+/// `options.history_commitment` — PDA `history_commitment`.
+pub fn init_game_ix(
+    game: &Pubkey,
+    player1: &Pubkey,
+    game_id: u64,
+    stake_lamports: u64,
+    move_fee_lamports: u64,
+    player2: &Pubkey,
+    options: GameOptions,
+) -> Instruction {
+    build(
+        accounts::InitGame {
+            game: *game,
+            player1: *player1,
+            system_program: system_program::ID,
+            config: pda::config(),
+            player1_ban: Some(pda::ban(player1)),
+            open_games: pda::open_games(player1),
+            time_control: time_control(game, options.preset),
+            create_cooldown: pda::create_cooldown(player1),
+            fee_route: (options.fee_recipient != Pubkey::default()).then(|| pda::fee_route(game)),
+            whitelisted_arbiter: (options.arbiter != Pubkey::default())
+                .then(|| pda::whitelisted_arbiter(&options.arbiter)),
+            game_counter: (game_id == 0).then(|| pda::game_counter(player1)),
+            game_history: options.move_history.then(|| pda::game_history(game, 0)),
+            history_commitment: options
+                .history_commitment
+                .then(|| pda::history_commitment(game)),
+        },
+        instruction::InitGame {
+            game_id,
+            stake_lamports,
+            move_fee_lamports,
+            player2_pubkey: *player2,
+            options,
+        },
+    )
+}
+
+/// С `client` создаётся или обновляется PDA `client_telemetry`. Для игры с
+/// `options.stake_pot` используйте [`join_game_staked_ix`].
+pub fn join_game_ix(
+    game: &Pubkey,
+    state: &GameState,
+    player2: &Pubkey,
+    password: Vec<u8>,
+    client: Option<ClientInfo>,
+) -> Instruction {
+    join_game_with(game, state, player2, password, client, None)
+}
+
+/// join_game игры с `options.stake_pot`: банк вносится в пул `pool`
+/// (`config.stake_pool`), `player2` платит ренту PotStake и счёта токенов.
+pub fn join_game_staked_ix(
+    game: &Pubkey,
+    state: &GameState,
+    player2: &Pubkey,
+    password: Vec<u8>,
+    client: Option<ClientInfo>,
+    pool: &StakePoolAccounts,
+) -> Instruction {
+    join_game_with(game, state, player2, password, client, Some(pool))
+}
+
+fn join_game_with(
+    game: &Pubkey,
+    state: &GameState,
+    player2: &Pubkey,
+    password: Vec<u8>,
+    client: Option<ClientInfo>,
+    pool: Option<&StakePoolAccounts>,
+) -> Instruction {
+    build(
+        accounts::JoinGame {
+            game: *game,
+            player2: *player2,
+            system_program: system_program::ID,
+            config: pda::config(),
+            player1_open_games: open_games(state),
+            player2_ban: Some(pda::ban(player2)),
+            player2_stats: Some(pda::stats(player2)),
+            client_telemetry: client.map(|_| pda::client_telemetry(game)),
+            event_buffer: None,
+            lobby: None,
+            pot_stake: pool.map(|_| pda::pot_stake(game)),
+            pot_vault: pool.map(|_| pda::pot_vault(game)),
+            pool_tokens: pool.map(|_| pda::pot_stake_tokens(game)),
+            pool_mint: pool.map(|pool| pool.pool_mint),
+            stake_pool: pool.map(|pool| pool.stake_pool),
+            stake_pool_withdraw_authority: pool.map(|pool| pool.withdraw_authority),
+            reserve_stake: pool.map(|pool| pool.reserve_stake),
+            manager_fee_account: pool.map(|pool| pool.manager_fee_account),
+            stake_pool_program: pool.map(|_| pooler::SPL_STAKE_POOL_PROGRAM_ID),
+            token_program: pool.map(|_| anchor_spl::token::ID),
+        },
+        instruction::JoinGame { password, client },
+    )
+}
+
+/// Встречное предложение приглашённого `player2` (подписывает и платит
+/// ренту CounterProposal).
+pub fn counter_offer_ix(
+    game: &Pubkey,
+    player2: &Pubkey,
+    new_stake_lamports: u64,
+    new_move_fee_lamports: u64,
+) -> Instruction {
+    build(
+        accounts::CounterOffer {
+            game: *game,
+            proposal: pda::counter_proposal(game),
+            player2: *player2,
+            system_program: system_program::ID,
+            config: pda::config(),
+        },
+        instruction::CounterOffer {
+            new_stake_lamports,
+            new_move_fee_lamports,
+        },
+    )
+}
+
+/// Принятие встречного предложения; подписывает `state.player1`, рента
+/// CounterProposal возвращается `state.player2`.
+pub fn accept_counter_ix(game: &Pubkey, state: &GameState) -> Instruction {
+    build(
+        accounts::AcceptCounter {
+            game: *game,
+            proposal: pda::counter_proposal(game),
+            proposer: state.player2,
+            player1: state.player1,
+            system_program: system_program::ID,
+            config: pda::config(),
+            lobby: None,
+        },
+        instruction::AcceptCounter {},
+    )
+}
+
+/// Снятие встречного предложения `proposer`; подписывает `signer` — сам
+/// автор или player1.
+pub fn withdraw_counter_ix(game: &Pubkey, proposer: &Pubkey, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::WithdrawCounter {
+            game: *game,
+            proposal: pda::counter_proposal(game),
+            proposer: *proposer,
+            signer: *signer,
+        },
+        instruction::WithdrawCounter {},
+    )
+}
+
+/// Обмен сторонами до первого хода; подписывают оба игрока.
+pub fn swap_sides_ix(game: &Pubkey, state: &GameState) -> Instruction {
+    build(
+        accounts::SwapSides {
+            game: *game,
+            player1: state.player1,
+            player2: state.player2,
+            chouette: pda::chouette(game),
+        },
+        instruction::SwapSides {},
+    )
+}
+
+/// Ход стороны `state.current_turn`. Подписывает только ходящий (основной
+/// ключ стороны); сессионный ключ или напарник подставляются вместо
+/// `accounts[1]`. `fee_recipient` — `FeeRoute::fee_recipient`, нужен, только
+/// если `state.fee_routed`; куда уходит срез тающего банка, берётся из `config`.
+pub fn make_move_ix(
+    game: &Pubkey,
+    state: &GameState,
+    config: &Config,
+    new_board_points: [i8; 24],
+    new_dice: [u8; 2],
+    annotation_hash: [u8; 32],
+    fee_recipient: Option<Pubkey>,
+) -> Instruction {
+    let side = state.current_turn;
+    build(
+        accounts::MakeMove {
+            game: *game,
+            mover: state.player_key(side),
+            opponent: state.player_key(3 - side),
+            system_program: system_program::ID,
+            bot_vault: bot_vault(state),
+            config: pda::config(),
+            team: team(game, state),
+            time_control: time_control(game, state.preset),
+            fee_route: state.fee_routed.then(|| pda::fee_route(game)),
+            fee_recipient: if state.fee_routed {
+                fee_recipient
+            } else {
+                None
+            },
+            insurance_pool: (config.insurance_fee_bps > 0).then(pda::insurance_pool),
+            client_telemetry: None,
+            event_buffer: None,
+            game_history: state
+                .move_history
+                .then(|| pda::game_history(game, state.current_history_chunk)),
+            history_commitment: state
+                .history_commitment
+                .then(|| pda::history_commitment(game)),
+            decay_sink: (state.decay_bps > 0).then(|| config.decay_destination()),
+        },
+        instruction::MakeMove {
+            new_board_points,
+            new_dice,
+            annotation_hash,
+            client: None,
+        },
+    )
+}
+
+/// `config` нужен, чтобы при комиссии протокола передать FeeVault и, если
+/// часть комиссии сжигается, incinerator, а при config.rating_enforced —
+/// текущий сезон и статистику обоих игроков.
+pub fn finish_game_ix(
+    game: &Pubkey,
+    state: &GameState,
+    config: &Config,
+    winner: &Pubkey,
+    result_multiplier: u8,
+) -> Instruction {
+    build(
+        accounts::FinishGame {
+            game: *game,
+            player1: state.player1,
+            player2: state.player2,
+            system_program: system_program::ID,
+            config: pda::config(),
+            season: config
+                .rating_enforced
+                .then(|| pda::season(config.current_season)),
+            player1_stats: config.rating_enforced.then(|| pda::stats(&state.player1)),
+            player2_stats: config.rating_enforced.then(|| pda::stats(&state.player2)),
+            bot_vault: bot_vault(state),
+            escrow_agent: escrow_agent(state),
+            team: team(game, state),
+            player1_splits: pda::payout_splits(&state.player1),
+            player2_splits: pda::payout_splits(&state.player2),
+            event_buffer: None,
+            fee_vault: fee_vault(config),
+            incinerator: incinerator(config),
+            event_authority: pda::event_authority(),
+            program: pooler::ID,
+        },
+        instruction::FinishGame {
+            winner: *winner,
+            result_multiplier,
+        },
+    )
+}
+
+/// Предложение удвоения; подписывает игрок на ходу, доплата уходит в банк.
+pub fn offer_double_ix(game: &Pubkey, player: &Pubkey) -> Instruction {
+    build(
+        accounts::OfferDouble {
+            game: *game,
+            player: *player,
+            system_program: system_program::ID,
+            config: pda::config(),
+        },
+        instruction::OfferDouble {},
+    )
+}
+
+/// Отказ от удвоения; подписывает принимающий, банк уходит удвоившему.
+/// `config` — как в [`finish_game_ix`].
+pub fn drop_double_ix(
+    game: &Pubkey,
+    state: &GameState,
+    config: &Config,
+    player: &Pubkey,
+) -> Instruction {
+    let doubler = if state.current_turn == 1 {
+        state.player1
+    } else {
+        state.player2
+    };
+    build(
+        accounts::DropDouble {
+            game: *game,
+            player: *player,
+            doubler,
+            config: pda::config(),
+            season: config
+                .rating_enforced
+                .then(|| pda::season(config.current_season)),
+            player1_stats: config.rating_enforced.then(|| pda::stats(&state.player1)),
+            player2_stats: config.rating_enforced.then(|| pda::stats(&state.player2)),
+            escrow_agent: escrow_agent(state),
+            event_buffer: None,
+            fee_vault: fee_vault(config),
+            incinerator: incinerator(config),
+            doubler_splits: pda::payout_splits(&doubler),
+            event_authority: pda::event_authority(),
+            program: pooler::ID,
+        },
+        instruction::DropDouble {},
+    )
+}
+
+/// Заявка на победу с итоговой доской; подписывает заявитель, он же вносит
+/// залог.
+pub fn claim_win_ix(game: &Pubkey, claimant: &Pubkey, final_board: [i8; 24]) -> Instruction {
+    build(
+        accounts::ClaimWin {
+            game: *game,
+            win_claim: pda::win_claim(game),
+            claimant: *claimant,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimWin { final_board },
+    )
+}
+
+/// Завершение заявки; подписывает кто угодно (`payer`). `claimant` —
+/// заявитель из WinClaim, `config` — как в [`finish_game_ix`]. Если игра уже
+/// выиграна другим игроком, передаётся его кошелёк для залога.
+pub fn finalize_claim_ix(
+    game: &Pubkey,
+    state: &GameState,
+    config: &Config,
+    claimant: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::FinalizeClaim {
+            game: *game,
+            win_claim: pda::win_claim(game),
+            claimant: *claimant,
+            config: pda::config(),
+            season: config
+                .rating_enforced
+                .then(|| pda::season(config.current_season)),
+            player1_stats: config.rating_enforced.then(|| pda::stats(&state.player1)),
+            player2_stats: config.rating_enforced.then(|| pda::stats(&state.player2)),
+            bot_vault: bot_vault(state),
+            team: team(game, state),
+            payer: *payer,
+            event_buffer: None,
+            fee_vault: fee_vault(config),
+            incinerator: incinerator(config),
+            winner: (state.winner != Pubkey::default() && state.winner != *claimant)
+                .then_some(state.winner),
+            claimant_splits: pda::payout_splits(claimant),
+            event_authority: pda::event_authority(),
+            program: pooler::ID,
+        },
+        instruction::FinalizeClaim {},
+    )
+}
+
+/// Решение спора; подписывает `arbiter` — арбитр игры или конфига, после
+/// апелляции — админ. Доказательства передаются, если они есть.
+pub fn arbiter_resolve_ix(
+    game: &Pubkey,
+    state: &GameState,
+    arbiter: &Pubkey,
+    winner: &Pubkey,
+    evidence: bool,
+) -> Instruction {
+    build(
+        accounts::ArbiterResolve {
+            game: *game,
+            config: pda::config(),
+            arbiter: *arbiter,
+            evidence: evidence.then(|| pda::evidence(game)),
+            whitelisted_arbiter: (state.arbiter != Pubkey::default())
+                .then(|| pda::whitelisted_arbiter(&state.arbiter)),
+        },
+        instruction::ArbiterResolve { winner: *winner },
+    )
+}
+
+/// Апелляция `player` на решение арбитра до `state.appeal_deadline_slot`.
+pub fn appeal_ix(game: &Pubkey, player: &Pubkey) -> Instruction {
+    build(
+        accounts::Appeal {
+            game: *game,
+            player: *player,
+        },
+        instruction::Appeal {},
+    )
+}
+
+/// Выплата по решению арбитра после окна апелляции; подписывает кто угодно
+/// (плательщик комиссии). Заявка на победу передаётся, если она есть;
+/// `config` — как в [`finish_game_ix`].
+pub fn execute_resolution_ix(game: &Pubkey, state: &GameState, config: &Config) -> Instruction {
+    build(
+        accounts::ExecuteResolution {
+            game: *game,
+            config: pda::config(),
+            season: config
+                .rating_enforced
+                .then(|| pda::season(config.current_season)),
+            player1_stats: config.rating_enforced.then(|| pda::stats(&state.player1)),
+            player2_stats: config.rating_enforced.then(|| pda::stats(&state.player2)),
+            player1: state.player1,
+            player2: state.player2,
+            bot_vault: bot_vault(state),
+            team: team(game, state),
+            win_claim: state.win_claim_pending.then(|| pda::win_claim(game)),
+            event_buffer: None,
+            fee_vault: fee_vault(config),
+            incinerator: incinerator(config),
+            player1_splits: pda::payout_splits(&state.player1),
+            player2_splits: pda::payout_splits(&state.player2),
+            event_authority: pda::event_authority(),
+            program: pooler::ID,
+        },
+        instruction::ExecuteResolution {},
+    )
+}
+
+pub fn cancel_before_join_ix(game: &Pubkey, state: &GameState) -> Instruction {
+    build(
+        accounts::CancelBeforeJoin {
+            game: *game,
+            player1: state.player1,
+            system_program: system_program::ID,
+            player1_open_games: open_games(state),
+            player1_splits: pda::payout_splits(&state.player1),
+            event_buffer: None,
+            event_authority: pda::event_authority(),
+            program: pooler::ID,
+        },
+        instruction::CancelBeforeJoin {},
+    )
+}
+
+pub fn force_refund_ix(game: &Pubkey, state: &GameState, config: &Config) -> Instruction {
+    build(
+        accounts::ForceRefund {
+            game: *game,
+            player1: state.player1,
+            player2: state.player2,
+            system_program: system_program::ID,
+            bot_vault: bot_vault(state),
+            team: team(game, state),
+            time_control: time_control(game, state.preset),
+            player1_splits: pda::payout_splits(&state.player1),
+            player2_splits: pda::payout_splits(&state.player2),
+            event_buffer: None,
+            config: pda::config(),
+            treasury: treasury(config),
+            event_authority: pda::event_authority(),
+            program: pooler::ID,
+        },
+        instruction::ForceRefund {},
+    )
+}
+
+/// `requester` — игрок, подписывающий свою половину взаимной отмены.
+pub fn manual_refund_ix(
+    game: &Pubkey,
+    state: &GameState,
+    config: &Config,
+    requester: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ManualRefundOneSigner {
+            game: *game,
+            player1: state.player1,
+            player2: state.player2,
+            requester: *requester,
+            system_program: system_program::ID,
+            bot_vault: bot_vault(state),
+            escrow_agent: escrow_agent(state),
+            team: team(game, state),
+            player1_splits: pda::payout_splits(&state.player1),
+            player2_splits: pda::payout_splits(&state.player2),
+            event_buffer: None,
+            config: pda::config(),
+            treasury: treasury(config),
+            event_authority: pda::event_authority(),
+            program: pooler::ID,
+        },
+        instruction::ManualRefund {},
+    )
+}
+
+/// Новый чанк истории ходов после заполненного текущего; подписывает и
+/// платит ренту `player` — один из игроков.
+pub fn rotate_history_chunk_ix(game: &Pubkey, state: &GameState, player: &Pubkey) -> Instruction {
+    let chunk = state.current_history_chunk;
+    build(
+        accounts::RotateHistoryChunk {
+            game: *game,
+            current_chunk: pda::game_history(game, chunk),
+            next_chunk: pda::game_history(game, chunk.wrapping_add(1)),
+            player: *player,
+            system_program: system_program::ID,
+        },
+        instruction::RotateHistoryChunk {},
+    )
+}
+
+/// Закрытие последнего чанка истории ходов завершённой игры; подписывает
+/// кто угодно (плательщик комиссии). `payer` — `GameHistory::payer` чанка
+/// `state.current_history_chunk`, получает его ренту.
+pub fn close_game_history_ix(game: &Pubkey, state: &GameState, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseGameHistory {
+            game: *game,
+            game_history: pda::game_history(game, state.current_history_chunk),
+            payer: *payer,
+        },
+        instruction::CloseGameHistory {},
+    )
+}
+
+/// Проверка записи хода по Merkle-корню игры; подписывает кто угодно
+/// (плательщик комиссии). `proof` — [`crate::history::history_proof`].
+pub fn verify_history_entry_ix(
+    game: &Pubkey,
+    entry: MoveRecord,
+    proof: [[u8; 32]; 16],
+) -> Instruction {
+    build(
+        accounts::VerifyHistoryEntry {
+            game: *game,
+            history_commitment: pda::history_commitment(game),
+        },
+        instruction::VerifyHistoryEntry { entry, proof },
+    )
+}
+
+/// Закрытие Merkle-коммитмента ходов завершённой игры; подписывает кто
+/// угодно (плательщик комиссии). `payer` — `HistoryCommitment::payer`,
+/// получает ренту.
+pub fn close_history_commitment_ix(game: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseHistoryCommitment {
+            game: *game,
+            history_commitment: pda::history_commitment(game),
+            payer: *payer,
+        },
+        instruction::CloseHistoryCommitment {},
+    )
+}
+
+/// Перенос аккаунта игры версии 1 или 2 на текущую раскладку; `payer` доплачивает
+/// ренту за новые байты.
+pub fn migrate_game_state_ix(game: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateGameState {
+            game: *game,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateGameState {},
+    )
+}
+
+/// validate_move для simulateTransaction: вердикт make_move для доски без
+/// подписей и комиссий.
+pub fn validate_move_ix(
+    game: &Pubkey,
+    new_board_points: [i8; 24],
+    expected_move_index: u64,
+) -> Instruction {
+    build(
+        accounts::ValidateMove {
+            game: *game,
+            config: pda::config(),
+        },
+        instruction::ValidateMove {
+            new_board_points,
+            expected_move_index,
+        },
+    )
+}
+
+/// get_game_summary для simulateTransaction: состояние игры знать не нужно,
+/// time_control передаётся всегда (программа читает его, только если он есть).
+pub fn get_game_summary_ix(game: &Pubkey) -> Instruction {
+    build(
+        accounts::GetGameSummary {
+            game: *game,
+            time_control: pda::time_control(game),
+        },
+        instruction::GetGameSummary {},
+    )
+}
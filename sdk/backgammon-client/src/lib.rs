@@ -0,0 +1,33 @@
+//! Оффчейн-SDK программы `backgammon`.
+//!
+//! - [`pda`] — адреса всех PDA программы;
+//! - [`ix`] — сборка `Instruction` для основных инструкций без ручного
+//!   перечисления аккаунтов;
+//! - [`fetch`] — загрузка и разбор аккаунтов поверх любого источника данных
+//!   (`solana-client`, bankrun/`BanksClient`), см. [`fetch::AccountSource`],
+//!   сводка игры и пробный ход одной симуляцией
+//!   ([`fetch::simulate_game_summary`], [`fetch::simulate_validate_move`]);
+//! - [`events`] — типизированные события программы из логов транзакции
+//!   и из данных self-CPI (`emit_cpi!`);
+//! - [`filters`] — фильтры `getProgramAccounts` для поиска игр по игроку,
+//!   статусу, номеру и победителю;
+//! - [`history`] — запись партии в формате `.mat` по логам её транзакций,
+//!   корень и доказательства Merkle-коммитмента ходов.
+//!
+//! Типы аргументов и аккаунтов берутся из самой программы (`pooler`), поэтому
+//! SDK не может разойтись с её интерфейсом.
+
+pub mod events;
+pub mod fetch;
+pub mod filters;
+pub mod history;
+pub mod ix;
+pub mod pda;
+
+pub use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+pub use anchor_lang::solana_program::pubkey::Pubkey;
+pub use pooler::layout;
+pub use pooler::{
+    ClientInfo, Config, FeeRoute, GameOptions, GamePreset, GameState, GameStatus, GameSummary,
+    MoveVerdict, ID as PROGRAM_ID,
+};
@@ -0,0 +1,122 @@
+//! Адреса PDA программы. Сиды совпадают с `seeds = [...]` в контекстах `pooler`.
+
+use anchor_lang::prelude::Pubkey;
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &pooler::ID).0
+}
+
+pub fn config() -> Pubkey {
+    find(&[b"config"])
+}
+
+pub fn season(index: u32) -> Pubkey {
+    find(&[b"season", &index.to_le_bytes()])
+}
+
+pub fn open_games(player: &Pubkey) -> Pubkey {
+    find(&[b"open_games", player.as_ref()])
+}
+
+pub fn create_cooldown(player: &Pubkey) -> Pubkey {
+    find(&[b"create_cooldown", player.as_ref()])
+}
+
+pub fn game_counter(player: &Pubkey) -> Pubkey {
+    find(&[b"counter", player.as_ref()])
+}
+
+pub fn ban(player: &Pubkey) -> Pubkey {
+    find(&[b"ban", player.as_ref()])
+}
+
+pub fn whitelisted_arbiter(arbiter: &Pubkey) -> Pubkey {
+    find(&[b"arbiter", arbiter.as_ref()])
+}
+
+pub fn stats(player: &Pubkey) -> Pubkey {
+    find(&[b"stats", player.as_ref()])
+}
+
+pub fn payout_splits(player: &Pubkey) -> Pubkey {
+    find(&[b"payout_splits", player.as_ref()])
+}
+
+pub fn bot_vault() -> Pubkey {
+    find(&[b"bot_vault"])
+}
+
+pub fn insurance_pool() -> Pubkey {
+    find(&[b"insurance_pool"])
+}
+
+pub fn fee_vault() -> Pubkey {
+    find(&[b"fee_vault"])
+}
+
+pub fn lobby(shard: u8) -> Pubkey {
+    find(&[b"lobby", &[shard]])
+}
+
+pub fn time_control(game: &Pubkey) -> Pubkey {
+    find(&[b"time_control", game.as_ref()])
+}
+
+pub fn fee_route(game: &Pubkey) -> Pubkey {
+    find(&[b"fee_route", game.as_ref()])
+}
+
+pub fn team(game: &Pubkey) -> Pubkey {
+    find(&[b"team", game.as_ref()])
+}
+
+pub fn pot_stake(game: &Pubkey) -> Pubkey {
+    find(&[b"pot_stake", game.as_ref()])
+}
+
+pub fn pot_vault(game: &Pubkey) -> Pubkey {
+    find(&[b"pot_vault", game.as_ref()])
+}
+
+pub fn pot_stake_tokens(game: &Pubkey) -> Pubkey {
+    find(&[b"pot_stake_tokens", game.as_ref()])
+}
+
+pub fn client_telemetry(game: &Pubkey) -> Pubkey {
+    find(&[b"client_telemetry", game.as_ref()])
+}
+
+pub fn evidence(game: &Pubkey) -> Pubkey {
+    find(&[b"evidence", game.as_ref()])
+}
+
+pub fn win_claim(game: &Pubkey) -> Pubkey {
+    find(&[b"win_claim", game.as_ref()])
+}
+
+pub fn counter_proposal(game: &Pubkey) -> Pubkey {
+    find(&[b"counter_proposal", game.as_ref()])
+}
+
+pub fn chouette(game: &Pubkey) -> Pubkey {
+    find(&[b"chouette", game.as_ref()])
+}
+
+pub fn event_buffer(game: &Pubkey) -> Pubkey {
+    find(&[b"event_buffer", game.as_ref()])
+}
+
+/// Чанк `chunk` истории ходов игры.
+pub fn game_history(game: &Pubkey, chunk: u8) -> Pubkey {
+    find(&[b"game_history", game.as_ref(), &[chunk]])
+}
+
+/// Merkle-коммитмент истории ходов игры.
+pub fn history_commitment(game: &Pubkey) -> Pubkey {
+    find(&[b"history_commitment", game.as_ref()])
+}
+
+/// PDA, от имени которого `emit_cpi!` вызывает программу.
+pub fn event_authority() -> Pubkey {
+    find(&[b"__event_authority"])
+}
@@ -0,0 +1,190 @@
+//! Фикстуры — ответы getTransaction в формате локального валидатора.
+
+use backgammon_client::events::{self, parse_cpi_event, parse_events, GameEvent, ProgramLine};
+use backgammon_client::{Pubkey, PROGRAM_ID};
+use serde_json::Value;
+
+fn transaction(name: &str) -> Value {
+    let text = match name {
+        "make_move" => include_str!("fixtures/make_move.json"),
+        "cpi_create_game" => include_str!("fixtures/cpi_create_game.json"),
+        "finish_game" => include_str!("fixtures/finish_game.json"),
+        _ => unreachable!(),
+    };
+    serde_json::from_str(text).unwrap()
+}
+
+fn logs(name: &str) -> Vec<String> {
+    transaction(name)["meta"]["logMessages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|line| line.as_str().unwrap().to_string())
+        .collect()
+}
+
+fn program_logs(logs: &[String]) -> Vec<&str> {
+    events::program_lines(&PROGRAM_ID, logs)
+        .filter_map(|line| match line {
+            ProgramLine::Log(message) => Some(message),
+            ProgramLine::Data(_) => None,
+        })
+        .collect()
+}
+
+#[test]
+fn top_level_move_emits_move_made() {
+    let logs = logs("make_move");
+    let events = parse_events(&logs);
+    let [GameEvent::MoveMade(event)] = events.as_slice() else {
+        panic!("unexpected events {:?}", events);
+    };
+    assert_eq!(event.move_index, 1);
+    assert_eq!(event.dice, [3, 1]);
+    assert_eq!(event.fee_lamports, 10_000_000);
+    assert_eq!(event.board_hash, pooler::board_hash(&event.board_points));
+    assert_eq!(event.client, None);
+
+    let messages = program_logs(&logs);
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0], "Instruction: MakeMove");
+    assert!(messages[1].starts_with("make_move: game_id=1, move_index=0"));
+    assert!(messages[2].starts_with("make_move: completed, new_move_index=1"));
+}
+
+#[test]
+fn nested_cpi_only_reads_frames_of_this_program() {
+    let logs = logs("cpi_create_game");
+    // Вызывающая программа и token-программа пишут данные, похожие на
+    // MoveMade; они должны быть пропущены.
+    let spoofed = logs
+        .iter()
+        .filter(|line| line.starts_with("Program data: "))
+        .filter_map(|line| events::parse_data(&line["Program data: ".len()..]))
+        .filter(|event| matches!(event, GameEvent::MoveMade(_)))
+        .count();
+    assert_eq!(spoofed, 2);
+
+    let events = parse_events(&logs);
+    let [GameEvent::GameInitialized(event)] = events.as_slice() else {
+        panic!("unexpected events {:?}", events);
+    };
+    assert_eq!(event.game_id, 42);
+    assert_eq!(event.stake_lamports, 500_000_000);
+    assert!(!event.practice);
+
+    assert_eq!(
+        program_logs(&logs),
+        [
+            "Instruction: InitGame",
+            "init_game: game_id=42, stake=500000000, move_fee=10000000",
+        ]
+    );
+}
+
+#[test]
+fn cpi_events_are_read_from_inner_instructions() {
+    let tx = transaction("finish_game");
+    let logs = logs("finish_game");
+    // emit_cpi! не пишет в лог: только самовызов без строк.
+    assert!(parse_events(&logs).is_empty());
+    assert!(program_logs(&logs).contains(&"finish_game: multiplier=2, loser pays bonus=500000000"));
+
+    let keys: Vec<Pubkey> = tx["transaction"]["message"]["accountKeys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|key| key.as_str().unwrap().parse().unwrap())
+        .collect();
+    let events: Vec<GameEvent> = tx["meta"]["innerInstructions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|group| group["instructions"].as_array().unwrap())
+        .filter(|ix| keys[ix["programIdIndex"].as_u64().unwrap() as usize] == PROGRAM_ID)
+        .filter_map(|ix| {
+            let data = bs58::decode(ix["data"].as_str().unwrap())
+                .into_vec()
+                .unwrap();
+            parse_cpi_event(&data)
+        })
+        .collect();
+    let [GameEvent::GameFinished(event)] = events.as_slice() else {
+        panic!("unexpected events {:?}", events);
+    };
+    assert_eq!(event.reason, pooler::FinishReason::Win);
+    assert_eq!(event.pot_paid, 1_020_000_000);
+
+    // Данные события без тега самовызова — не CPI-событие.
+    let plain = bs58::decode(
+        tx["meta"]["innerInstructions"][0]["instructions"][1]["data"]
+            .as_str()
+            .unwrap(),
+    )
+    .into_vec()
+    .unwrap();
+    assert!(parse_cpi_event(&plain[8..]).is_none());
+}
+
+#[test]
+fn logs_of_several_transactions_can_be_concatenated() {
+    let mut all = logs("cpi_create_game");
+    all.extend(logs("finish_game"));
+    all.extend(logs("make_move"));
+    let kinds: Vec<&str> = parse_events(&all)
+        .iter()
+        .map(|event| match event {
+            GameEvent::GameInitialized(_) => "init",
+            GameEvent::MoveMade(_) => "move",
+            _ => "other",
+        })
+        .collect();
+    assert_eq!(kinds, ["init", "move"]);
+}
+
+#[test]
+fn truncated_logs_do_not_break_following_transactions() {
+    let mut truncated = logs("cpi_create_game");
+    let cut = truncated
+        .iter()
+        .position(|line| line.contains(" consumed 61877 "))
+        .unwrap();
+    truncated.truncate(cut);
+    truncated.push("Log truncated".to_string());
+    truncated.extend(logs("make_move"));
+
+    let events = parse_events(&truncated);
+    assert!(matches!(
+        events.as_slice(),
+        [GameEvent::GameInitialized(_), GameEvent::MoveMade(_)]
+    ));
+}
+
+#[test]
+fn failed_frame_keeps_error_context() {
+    use anchor_lang::{AnchorSerialize, Discriminator};
+    use base64::Engine;
+    use pooler::{ContextValue, ErrorCode, ErrorContext};
+
+    let context = ErrorContext {
+        error_code: ErrorCode::TimeoutNotReached.into(),
+        expected: ContextValue::Slot(1_600),
+        actual: ContextValue::Slot(1_000),
+    };
+    let mut data = ErrorContext::DISCRIMINATOR.to_vec();
+    context.serialize(&mut data).unwrap();
+    let logs = [
+        format!("Program {} invoke [1]", PROGRAM_ID),
+        "Program log: Instruction: ForceRefund".to_string(),
+        format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(&data)
+        ),
+        "Program log: AnchorError occurred. Error Code: TimeoutNotReached.".to_string(),
+        format!(
+            "Program {} failed: custom program error: {:#x}",
+            PROGRAM_ID, context.error_code
+        ),
+    ];
+    assert_eq!(parse_events(&logs), [GameEvent::ErrorContext(context)]);
+}
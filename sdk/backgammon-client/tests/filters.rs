@@ -0,0 +1,66 @@
+//! Фильтры getProgramAccounts против сериализованного аккаунта игры:
+//! каждый memcmp совпадает со своей игрой и не совпадает с чужой.
+
+use anchor_lang::{AccountSerialize, AnchorDeserialize};
+use backgammon_client::filters;
+use backgammon_client::{GameState, GameStatus, Pubkey};
+use solana_rpc_client_api::filter::RpcFilterType;
+
+fn game(status: GameStatus) -> GameState {
+    let mut state = GameState::deserialize(&mut &[0u8; 640][..]).unwrap();
+    state.player1 = Pubkey::new_unique();
+    state.player2 = Pubkey::new_unique();
+    state.winner = Pubkey::new_unique();
+    state.game_id = 42;
+    state.status = status;
+    state
+}
+
+fn data(state: &GameState) -> Vec<u8> {
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn matches(filters: &[RpcFilterType], data: &[u8]) -> bool {
+    filters.iter().all(|filter| match filter {
+        RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(data),
+        other => panic!("unexpected filter {other:?}"),
+    })
+}
+
+#[test]
+fn filters_select_games_by_field() {
+    let state = game(GameStatus::Active);
+    let account = data(&state);
+    let other = data(&game(GameStatus::Active));
+
+    assert!(matches(&[filters::game_state_filter()], &account));
+    assert!(matches(&[filters::game_id_filter(42)], &account));
+    assert!(!matches(&[filters::game_id_filter(43)], &account));
+    assert!(matches(&[filters::winner_filter(&state.winner)], &account));
+    assert!(!matches(&[filters::winner_filter(&state.winner)], &other));
+    assert!(!matches(&filters::open_games(), &account));
+
+    let [as_player1, as_player2] = filters::active_games_of(&state.player1);
+    assert!(matches(&as_player1, &account));
+    assert!(!matches(&as_player2, &account));
+    assert!(!matches(&as_player1, &other));
+    let [as_player1, as_player2] = filters::active_games_of(&state.player2);
+    assert!(!matches(&as_player1, &account));
+    assert!(matches(&as_player2, &account));
+}
+
+#[test]
+fn open_games_match_only_waiting_lobbies() {
+    let lobby = data(&game(GameStatus::WaitingForPlayer2));
+    assert!(matches(&filters::open_games(), &lobby));
+    for status in [
+        GameStatus::Active,
+        GameStatus::Finished,
+        GameStatus::Disputed,
+        GameStatus::Cancelled,
+    ] {
+        assert!(!matches(&filters::open_games(), &data(&game(status))));
+    }
+}
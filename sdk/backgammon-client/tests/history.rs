@@ -0,0 +1,137 @@
+//! Сборка истории ходов из цепочки чанков GameHistory.
+//!
+//! Валидатора в тестах нет: заглушка отдаёт заранее записанные аккаунты.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use anchor_lang::{AccountSerialize, AnchorDeserialize};
+use backgammon_client::fetch::{self, AccountSource, FetchError};
+use backgammon_client::{pda, GameState, GameStatus, Pubkey};
+use pooler::{GameHistory, MoveRecord};
+
+struct Validator {
+    accounts: HashMap<Pubkey, Vec<u8>>,
+}
+
+impl AccountSource for Validator {
+    type Error = String;
+
+    fn account_data(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = Result<Option<Vec<u8>>, String>> {
+        let data = self.accounts.get(address).cloned();
+        async move { Ok(data) }
+    }
+}
+
+impl Validator {
+    fn insert<T: AccountSerialize>(&mut self, address: Pubkey, account: &T) {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        self.accounts.insert(address, data);
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("stub futures are always ready"),
+    }
+}
+
+fn record(move_index: u64) -> MoveRecord {
+    MoveRecord {
+        move_index,
+        mover: Pubkey::new_unique(),
+        board: [0; 15],
+        dice: [3, 1],
+        slot: move_index,
+        fee_lamports: 0,
+    }
+}
+
+/// Игра с chunks чанками истории по per_chunk ходов в каждом.
+fn validator(game: &Pubkey, chunks: u8, per_chunk: u64) -> Validator {
+    let mut state = GameState::deserialize(&mut &[0u8; 640][..]).unwrap();
+    state.status = GameStatus::Active;
+    state.move_history = true;
+    state.current_history_chunk = chunks - 1;
+    let mut validator = Validator {
+        accounts: HashMap::new(),
+    };
+    validator.insert(*game, &state);
+
+    let mut prev_chunk = Pubkey::default();
+    for chunk_index in 0..chunks {
+        let first = chunk_index as u64 * per_chunk + 1;
+        let history = GameHistory {
+            game: *game,
+            payer: Pubkey::new_unique(),
+            chunk_index,
+            prev_chunk,
+            moves: (first..first + per_chunk).map(record).collect(),
+            bump: 0,
+        };
+        let address = pda::game_history(game, chunk_index);
+        validator.insert(address, &history);
+        prev_chunk = address;
+    }
+    validator
+}
+
+#[test]
+fn chunks_are_concatenated_in_move_order() {
+    let game = Pubkey::new_unique();
+    let chained = validator(&game, 3, 4);
+    let moves = block_on(fetch::fetch_game_history(&chained, &game)).unwrap();
+    let indices: Vec<u64> = moves.iter().map(|m| m.move_index).collect();
+    assert_eq!(indices, (1..=12).collect::<Vec<_>>());
+
+    // Одного чанка достаточно.
+    let single = validator(&game, 1, 4);
+    let moves = block_on(fetch::fetch_game_history(&single, &game)).unwrap();
+    assert_eq!(moves.len(), 4);
+}
+
+#[test]
+fn broken_chains_are_reported() {
+    let game = Pubkey::new_unique();
+
+    // Пропавший промежуточный чанк.
+    let mut missing = validator(&game, 3, 2);
+    let middle = pda::game_history(&game, 1);
+    missing.accounts.remove(&middle);
+    assert!(matches!(
+        block_on(fetch::fetch_game_history(&missing, &game)),
+        Err(FetchError::NotFound(address)) if address == middle
+    ));
+
+    // Чанк чужой игры в цепочке.
+    let mut foreign = validator(&game, 2, 2);
+    let first = pda::game_history(&game, 0);
+    let mut history: GameHistory = fetch::decode(&foreign.accounts[&first]).unwrap();
+    history.game = Pubkey::new_unique();
+    foreign.insert(first, &history);
+    assert!(matches!(
+        block_on(fetch::fetch_game_history(&foreign, &game)),
+        Err(FetchError::Decode(address, _)) if address == first
+    ));
+
+    // Игра без истории.
+    let mut plain = validator(&game, 1, 0);
+    let mut state: GameState = fetch::decode(&plain.accounts[&game]).unwrap();
+    state.move_history = false;
+    plain.insert(game, &state);
+    assert!(matches!(
+        block_on(fetch::fetch_game_history(&plain, &game)),
+        Err(FetchError::NotFound(_))
+    ));
+}
@@ -0,0 +1,95 @@
+//! make_move: подписывает только ходящий, ключ соперника лишь сверяется.
+
+use anchor_lang::AnchorDeserialize;
+use backgammon_client::{ix, Config, GameState, GameStatus, Pubkey};
+use pooler::{ErrorCode, RuleVariant, TeamState};
+
+fn active_game() -> GameState {
+    let mut state = GameState::deserialize(&mut &[0u8; 640][..]).unwrap();
+    state.player1 = Pubkey::new_unique();
+    state.player2 = Pubkey::new_unique();
+    state.status = GameStatus::Active;
+    state.current_turn = 1;
+    state.version = pooler::GAME_STATE_VERSION;
+    state
+        .set_board_points(&pooler::start_board(RuleVariant::Standard, 0).unwrap())
+        .unwrap();
+    state
+}
+
+fn not_players_turn<T: std::fmt::Debug>(result: anchor_lang::Result<T>) {
+    assert_eq!(result.unwrap_err(), ErrorCode::NotPlayersTurn.into());
+}
+
+#[test]
+fn player_on_turn_moves_alone() {
+    let state = active_game();
+    assert_eq!(state.mover_member(&state.player1, None, 0).unwrap(), 0);
+
+    let config = Config::deserialize(&mut &[0u8; 1024][..]).unwrap();
+    let game = Pubkey::new_unique();
+    let ix = ix::make_move_ix(
+        &game,
+        &state,
+        &config,
+        state.board_points().unwrap(),
+        [3, 1],
+        [0; 32],
+        None,
+    );
+    let signers: Vec<_> = ix.accounts.iter().filter(|a| a.is_signer).collect();
+    assert_eq!(signers.len(), 1);
+    assert_eq!(signers[0].pubkey, state.player1);
+    assert_eq!(ix.accounts[2].pubkey, state.player2);
+    assert!(!ix.accounts[2].is_signer && !ix.accounts[2].is_writable);
+}
+
+#[test]
+fn player_off_turn_is_rejected() {
+    let mut state = active_game();
+    not_players_turn(state.mover_member(&state.player2, None, 0));
+
+    state.current_turn = 2;
+    assert_eq!(state.mover_member(&state.player2, None, 0).unwrap(), 2);
+    not_players_turn(state.mover_member(&state.player1, None, 0));
+}
+
+#[test]
+fn non_player_is_rejected() {
+    let state = active_game();
+    not_players_turn(state.mover_member(&Pubkey::new_unique(), None, 0));
+
+    let mut no_turn = state.clone();
+    no_turn.current_turn = 0;
+    assert_eq!(
+        no_turn.mover_member(&state.player1, None, 0).unwrap_err(),
+        ErrorCode::InvalidCurrentTurn.into()
+    );
+}
+
+#[test]
+fn session_key_and_teammate_sign_for_their_side() {
+    let mut state = active_game();
+    let session = Pubkey::new_unique();
+    state.player1_session_key = session;
+    state.player1_session_expiry = 100;
+    assert_eq!(state.mover_member(&session, None, 100).unwrap(), 0);
+    not_players_turn(state.mover_member(&session, None, 101));
+
+    let teammate = Pubkey::new_unique();
+    let team = TeamState {
+        game: Pubkey::new_unique(),
+        teammates: [teammate, Pubkey::default()],
+        contributions: [0; 4],
+        received: [0; 2],
+        claimed: [false; 4],
+        bump: 0,
+    };
+    not_players_turn(state.mover_member(&teammate, Some(&team), 0));
+    state.team_game = true;
+    assert_eq!(state.mover_member(&teammate, Some(&team), 0).unwrap(), 1);
+
+    // Напарник соперника за чужую сторону не ходит.
+    state.current_turn = 2;
+    not_players_turn(state.mover_member(&teammate, Some(&team), 0));
+}
@@ -0,0 +1,153 @@
+//! Сводка из симуляции get_game_summary против сырого аккаунта игры.
+//!
+//! Валидатора в тестах нет: заглушка отвечает на симуляцию так же, как
+//! обработчик get_game_summary (GameState::summary на своём слоте).
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use anchor_lang::{
+    AccountDeserialize, AccountSerialize, AnchorDeserialize, AnchorSerialize, InstructionData,
+};
+use backgammon_client::fetch::{self, AccountSource, FetchError, Simulator};
+use backgammon_client::{ix, pda, GameState, GameStatus, Instruction, Pubkey, PROGRAM_ID};
+
+struct Validator {
+    accounts: HashMap<Pubkey, Vec<u8>>,
+    slot: Cell<u64>,
+}
+
+impl AccountSource for Validator {
+    type Error = String;
+
+    fn account_data(
+        &self,
+        address: &Pubkey,
+    ) -> impl Future<Output = Result<Option<Vec<u8>>, String>> {
+        let data = self.accounts.get(address).cloned();
+        async move { Ok(data) }
+    }
+}
+
+impl Simulator for Validator {
+    type Error = String;
+
+    fn return_data(
+        &self,
+        instruction: &Instruction,
+    ) -> impl Future<Output = Result<Option<(Pubkey, Vec<u8>)>, String>> {
+        let result = (|| {
+            assert_eq!(instruction.program_id, PROGRAM_ID);
+            assert_eq!(
+                instruction.data,
+                pooler::instruction::GetGameSummary {}.data()
+            );
+            let game = instruction.accounts[0].pubkey;
+            let data = self.accounts.get(&game).ok_or("AccountNotInitialized")?;
+            let state = GameState::try_deserialize(&mut &data[..]).map_err(|e| e.to_string())?;
+            let timeout = pooler::CASUAL_TIME_CONTROL.refund_timeout_slots;
+            let summary = state.summary(self.slot.get(), timeout);
+            Ok(Some((PROGRAM_ID, summary.try_to_vec().unwrap())))
+        })();
+        async move { result }
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("stub futures are always ready"),
+    }
+}
+
+fn active_game() -> GameState {
+    let mut state = GameState::deserialize(&mut &[0u8; 640][..]).unwrap();
+    state.player1 = Pubkey::new_unique();
+    state.player2 = Pubkey::new_unique();
+    state.status = GameStatus::Active;
+    state.current_turn = 2;
+    state.move_index = 7;
+    state.stake_lamports = 500_000_000;
+    state.player1_deposit = 500_000_000;
+    state.player2_deposit = 500_000_000;
+    state.player1_fees_paid = 40_000_000;
+    state.player2_fees_paid = 30_000_000;
+    state.pot_lamports = 1_070_000_000;
+    state.last_activity_slot = 1_000;
+    state
+}
+
+fn validator(game: &Pubkey, state: &GameState, slot: u64) -> Validator {
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    Validator {
+        accounts: HashMap::from([(*game, data)]),
+        slot: Cell::new(slot),
+    }
+}
+
+#[test]
+fn summary_instruction_is_read_only() {
+    let game = Pubkey::new_unique();
+    let ix = ix::get_game_summary_ix(&game);
+    assert_eq!(ix.accounts.len(), 2);
+    assert_eq!(ix.accounts[0].pubkey, game);
+    assert_eq!(ix.accounts[1].pubkey, pda::time_control(&game));
+    assert!(ix.accounts.iter().all(|a| !a.is_writable && !a.is_signer));
+}
+
+#[test]
+fn simulated_summary_matches_raw_account() {
+    let game = Pubkey::new_unique();
+    let validator = validator(&game, &active_game(), 1_250);
+
+    let raw = block_on(fetch::fetch_game(&validator, &game)).unwrap();
+    let summary = block_on(fetch::simulate_game_summary(&validator, &game)).unwrap();
+    assert_eq!(summary.status, raw.status);
+    assert_eq!(summary.current_turn, raw.current_turn);
+    assert_eq!(summary.move_index, raw.move_index);
+    assert_eq!(summary.pot_lamports, raw.pot_lamports);
+    assert_eq!(summary.player1_deposit, raw.player1_deposit);
+    assert_eq!(summary.player2_deposit, raw.player2_deposit);
+    assert_eq!(summary.player1_fees_paid, raw.player1_fees_paid);
+    assert_eq!(summary.player2_fees_paid, raw.player2_fees_paid);
+    assert_eq!(summary.slots_since_activity, 1_250 - raw.last_activity_slot);
+    assert!(!summary.force_refund_available);
+}
+
+#[test]
+fn force_refund_becomes_available_after_timeout() {
+    let game = Pubkey::new_unique();
+    let mut state = active_game();
+    let timeout = pooler::CASUAL_TIME_CONTROL.refund_timeout_slots;
+    let validator = validator(&game, &state, state.last_activity_slot + timeout - 1);
+    let summary = |v: &Validator| block_on(fetch::simulate_game_summary(v, &game)).unwrap();
+    assert!(!summary(&validator).force_refund_available);
+
+    validator.slot.set(state.last_activity_slot + timeout);
+    assert!(summary(&validator).force_refund_available);
+
+    state.status = GameStatus::Finished;
+    let finished = self::validator(&game, &state, state.last_activity_slot + timeout);
+    assert!(!summary(&finished).force_refund_available);
+}
+
+#[test]
+fn missing_game_is_reported() {
+    let validator = Validator {
+        accounts: HashMap::new(),
+        slot: Cell::new(0),
+    };
+    let err = block_on(fetch::simulate_game_summary(
+        &validator,
+        &Pubkey::new_unique(),
+    ));
+    assert!(matches!(err, Err(FetchError::Source(_))));
+}
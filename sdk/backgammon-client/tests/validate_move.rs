@@ -0,0 +1,210 @@
+//! validate_move: вердикт make_move без списаний и изменений состояния.
+//!
+//! Валидатора в тестах нет: заглушка отвечает на симуляцию так же, как
+//! обработчик validate_move (GameState::validate_move по аккаунтам).
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorDeserialize, AnchorSerialize};
+use backgammon_client::fetch::{self, Simulator};
+use backgammon_client::{ix, pda, Config, GameState, GameStatus, Instruction, MoveVerdict, Pubkey};
+use pooler::{ErrorCode, RuleVariant};
+
+struct Validator {
+    accounts: HashMap<Pubkey, Vec<u8>>,
+}
+
+impl Simulator for Validator {
+    type Error = String;
+
+    fn return_data(
+        &self,
+        instruction: &Instruction,
+    ) -> impl Future<Output = Result<Option<(Pubkey, Vec<u8>)>, String>> {
+        let result = (|| {
+            let args = pooler::instruction::ValidateMove::deserialize(&mut &instruction.data[8..])
+                .map_err(|e| e.to_string())?;
+            let [game, config] = [0, 1].map(|i| &self.accounts[&instruction.accounts[i].pubkey]);
+            let game = GameState::try_deserialize(&mut &game[..]).map_err(|e| e.to_string())?;
+            let config = Config::try_deserialize(&mut &config[..]).map_err(|e| e.to_string())?;
+            let verdict = game.validate_move(
+                config.paused,
+                &args.new_board_points,
+                args.expected_move_index,
+            );
+            Ok(Some((pooler::ID, verdict.try_to_vec().unwrap())))
+        })();
+        async move { result }
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("stub futures are always ready"),
+    }
+}
+
+fn start() -> [i8; 24] {
+    pooler::start_board(RuleVariant::Standard, 0).unwrap()
+}
+
+/// Доска после 31: 8/5 6/5.
+fn after_31() -> [i8; 24] {
+    let mut board = start();
+    board[7] -= 1;
+    board[5] -= 1;
+    board[4] += 2;
+    board
+}
+
+fn active_game() -> GameState {
+    let mut state = GameState::deserialize(&mut &[0u8; 640][..]).unwrap();
+    state.player1 = Pubkey::new_unique();
+    state.player2 = Pubkey::new_unique();
+    state.status = GameStatus::Active;
+    state.current_turn = 1;
+    state.version = pooler::GAME_STATE_VERSION;
+    state.move_index = 4;
+    state.set_board_points(&start()).unwrap();
+    state
+}
+
+fn serialized<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn rejected(code: ErrorCode) -> MoveVerdict {
+    MoveVerdict {
+        accepted: false,
+        reason: anchor_lang::error::ERROR_CODE_OFFSET + code as u32,
+    }
+}
+
+#[test]
+fn legal_move_is_accepted() {
+    let state = active_game();
+    let verdict = state.validate_move(false, &after_31(), 4);
+    assert_eq!(
+        verdict,
+        MoveVerdict {
+            accepted: true,
+            reason: 0
+        }
+    );
+}
+
+#[test]
+fn each_rejection_class_reports_its_error() {
+    let verdict = |state: &GameState, paused: bool, board: [i8; 24], index: u64| {
+        state.validate_move(paused, &board, index)
+    };
+    let base = active_game();
+
+    assert_eq!(
+        verdict(&base, true, after_31(), 4),
+        rejected(ErrorCode::ProgramPaused)
+    );
+
+    let mut finished = base.clone();
+    finished.status = GameStatus::Finished;
+    assert_eq!(
+        verdict(&finished, false, after_31(), 4),
+        rejected(ErrorCode::GameNotActive)
+    );
+
+    let mut no_turn = base.clone();
+    no_turn.current_turn = 0;
+    assert_eq!(
+        verdict(&no_turn, false, after_31(), 4),
+        rejected(ErrorCode::InvalidCurrentTurn)
+    );
+
+    let mut overfull = start();
+    overfull[5] += 1;
+    assert_eq!(
+        verdict(&base, false, overfull, 4),
+        rejected(ErrorCode::InvalidBoard)
+    );
+
+    let mut doubled = base.clone();
+    doubled.double_pending = true;
+    assert_eq!(
+        verdict(&doubled, false, after_31(), 4),
+        rejected(ErrorCode::DoublePending)
+    );
+
+    let mut pending = base.clone();
+    pending.move_pending = true;
+    assert_eq!(
+        verdict(&pending, false, after_31(), 4),
+        rejected(ErrorCode::MovePending)
+    );
+
+    let mut claimed = base.clone();
+    claimed.win_claim_pending = true;
+    assert_eq!(
+        verdict(&claimed, false, after_31(), 4),
+        rejected(ErrorCode::WinClaimPending)
+    );
+
+    let mut capped = base.clone();
+    capped.max_moves = 4;
+    assert_eq!(
+        verdict(&capped, false, after_31(), 4),
+        rejected(ErrorCode::MoveCapReached)
+    );
+
+    assert_eq!(
+        verdict(&base, false, after_31(), 3),
+        rejected(ErrorCode::MoveIndexMismatch)
+    );
+}
+
+#[test]
+fn validate_move_instruction_is_read_only() {
+    let game = Pubkey::new_unique();
+    let ix = ix::validate_move_ix(&game, after_31(), 4);
+    assert_eq!(ix.accounts.len(), 2);
+    assert_eq!(ix.accounts[0].pubkey, game);
+    assert_eq!(ix.accounts[1].pubkey, pda::config());
+    assert!(ix.accounts.iter().all(|a| !a.is_writable && !a.is_signer));
+}
+
+#[test]
+fn simulation_leaves_accounts_byte_identical() {
+    let game = Pubkey::new_unique();
+    let state = active_game();
+    let config = Config::deserialize(&mut &[0u8; 1024][..]).unwrap();
+    let before = serialized(&state);
+    let validator = Validator {
+        accounts: HashMap::from([(game, before.clone()), (pda::config(), serialized(&config))]),
+    };
+
+    let accepted = block_on(fetch::simulate_validate_move(
+        &validator,
+        &game,
+        after_31(),
+        4,
+    ));
+    assert!(accepted.unwrap().accepted);
+    let stale = block_on(fetch::simulate_validate_move(
+        &validator,
+        &game,
+        after_31(),
+        5,
+    ));
+    assert_eq!(stale.unwrap(), rejected(ErrorCode::MoveIndexMismatch));
+
+    assert_eq!(validator.accounts[&game], before);
+    assert_eq!(serialized(&state), before);
+}
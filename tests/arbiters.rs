@@ -0,0 +1,219 @@
+//! Арбитры в LiteSVM: init_game отклоняет арбитра вне белого списка,
+//! арбитр из списка решает спор, а исключённый после init_game — уже нет.
+//! Решение исполняется execute_resolution только после окна апелляции;
+//! обжалованный спор решает админ.
+//!
+//! Спор выставляется прямой записью состояния игры (Harness::set_game):
+//! путь до Disputed через reject_move здесь не проверяется.
+
+use backgammon_client::{ix, GameOptions, GameStatus, Pubkey};
+use backgammon_tests::{program_error, Game, Harness, PLAYER_LAMPORTS};
+use pooler::{ConfigUpdate, ErrorCode, DEFAULT_APPEAL_WINDOW_SLOTS, EVIDENCE_WINDOW_SLOTS};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 100_000_000;
+
+fn create(h: &mut Harness, arbiter: &Pubkey) -> Result<Game, TransactionError> {
+    let options = GameOptions {
+        arbiter: *arbiter,
+        ..GameOptions::default()
+    };
+    let game = h.create_game(STAKE, 0, options)?;
+    h.join(&game).unwrap();
+
+    // Спор начат сейчас; окно доказательств истекает через EVIDENCE_WINDOW_SLOTS.
+    let mut state = h.game(&game.key);
+    state.status = GameStatus::Disputed;
+    state.last_activity_slot = h.slot();
+    h.set_game(&game.key, &state);
+    Ok(game)
+}
+
+fn resolve(
+    h: &mut Harness,
+    game: &Game,
+    arbiter: &Keypair,
+    winner: &Pubkey,
+) -> Result<(), TransactionError> {
+    let state = h.game(&game.key);
+    h.send(
+        ix::arbiter_resolve_ix(&game.key, &state, &arbiter.pubkey(), winner, false),
+        &[arbiter],
+    )
+}
+
+fn execute(h: &mut Harness, game: &Game) -> Result<(), TransactionError> {
+    let (state, config) = (h.game(&game.key), h.config());
+    h.send(ix::execute_resolution_ix(&game.key, &state, &config), &[])
+}
+
+fn appeal(h: &mut Harness, game: &Game, player: &Keypair) -> Result<(), TransactionError> {
+    h.send(ix::appeal_ix(&game.key, &player.pubkey()), &[player])
+}
+
+/// Конфиг с белым списком, арбитр из него и спорная игра с этим арбитром
+/// после окна доказательств.
+fn disputed_game() -> (Harness, Keypair, Game) {
+    let mut h = whitelisted_harness();
+    let admin = h.admin.insecure_clone();
+    let arbiter = h.player();
+    h.send(
+        ix::add_arbiter_ix(&admin.pubkey(), &arbiter.pubkey()),
+        &[&admin],
+    )
+    .unwrap();
+    let game = create(&mut h, &arbiter.pubkey()).unwrap();
+    h.warp(EVIDENCE_WINDOW_SLOTS);
+    (h, arbiter, game)
+}
+
+fn whitelisted_harness() -> Harness {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let update = ConfigUpdate {
+        require_whitelisted_arbiters: Some(true),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+    h
+}
+
+#[test]
+fn init_game_rejects_arbiter_off_the_whitelist() {
+    let mut h = whitelisted_harness();
+    let arbiter = Pubkey::new_unique();
+    assert_eq!(
+        create(&mut h, &arbiter).err(),
+        Some(program_error(ErrorCode::ArbiterNotWhitelisted))
+    );
+
+    let admin = h.admin.insecure_clone();
+    h.send(ix::add_arbiter_ix(&admin.pubkey(), &arbiter), &[&admin])
+        .unwrap();
+    let game = create(&mut h, &arbiter).unwrap();
+    assert_eq!(h.game(&game.key).arbiter, arbiter);
+}
+
+#[test]
+fn whitelisted_arbiter_resolves_dispute() {
+    let (mut h, arbiter, game) = disputed_game();
+    let admin = h.admin.insecure_clone();
+    let winner = game.player1.pubkey();
+
+    // Арбитр конфига не решает спор игры, где назначен свой арбитр.
+    assert_eq!(
+        resolve(&mut h, &game, &admin, &winner),
+        Err(program_error(ErrorCode::InvalidArbiter))
+    );
+
+    // Решение только записывается: банк ждёт окна апелляции.
+    let before = h.balance(&winner);
+    resolve(&mut h, &game, &arbiter, &winner).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Disputed);
+    assert_eq!(state.resolution_winner, 1);
+    assert_eq!(
+        state.appeal_deadline_slot,
+        h.slot() + DEFAULT_APPEAL_WINDOW_SLOTS
+    );
+    assert_eq!(h.balance(&winner), before);
+
+    // Без апелляции решение исполняет кто угодно после окна.
+    h.warp(DEFAULT_APPEAL_WINDOW_SLOTS + 1);
+    execute(&mut h, &game).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.winner, winner);
+    assert_eq!(state.resolution_winner, 0);
+    assert_eq!(h.balance(&winner), before + 2 * STAKE);
+    assert_eq!(h.balance(&arbiter.pubkey()), PLAYER_LAMPORTS);
+}
+
+#[test]
+fn execution_waits_for_the_appeal_window() {
+    let (mut h, arbiter, game) = disputed_game();
+    assert_eq!(
+        execute(&mut h, &game),
+        Err(program_error(ErrorCode::NoPendingResolution))
+    );
+
+    resolve(&mut h, &game, &arbiter, &game.player1.pubkey()).unwrap();
+    assert_eq!(
+        execute(&mut h, &game),
+        Err(program_error(ErrorCode::AppealWindowOpen))
+    );
+    // Последний слот окна ещё принадлежит апелляции.
+    h.warp(DEFAULT_APPEAL_WINDOW_SLOTS);
+    assert_eq!(
+        execute(&mut h, &game),
+        Err(program_error(ErrorCode::AppealWindowOpen))
+    );
+    assert_eq!(h.game(&game.key).status, GameStatus::Disputed);
+
+    h.warp(1);
+    assert_eq!(
+        appeal(&mut h, &game, &game.player2),
+        Err(program_error(ErrorCode::AppealWindowClosed))
+    );
+    execute(&mut h, &game).unwrap();
+    assert_eq!(h.game(&game.key).status, GameStatus::Finished);
+}
+
+#[test]
+fn appeal_blocks_payout_until_admin_decides() {
+    let (mut h, arbiter, game) = disputed_game();
+    let admin = h.admin.insecure_clone();
+    let (player1, player2) = (game.player1.pubkey(), game.player2.pubkey());
+
+    resolve(&mut h, &game, &arbiter, &player1).unwrap();
+    let before = h.balance(&player1);
+    appeal(&mut h, &game, &game.player2).unwrap();
+    let state = h.game(&game.key);
+    assert!(state.resolution_appealed);
+    assert_eq!(state.resolution_winner, 0);
+
+    // Обжалованное решение не исполняется и после окна.
+    h.warp(DEFAULT_APPEAL_WINDOW_SLOTS + 1);
+    assert_eq!(
+        execute(&mut h, &game),
+        Err(program_error(ErrorCode::NoPendingResolution))
+    );
+    assert_eq!(h.balance(&player1), before);
+
+    // Дальше спор решает только админ, и его решение не обжалуется.
+    assert_eq!(
+        resolve(&mut h, &game, &arbiter, &player1),
+        Err(program_error(ErrorCode::InvalidArbiter))
+    );
+    resolve(&mut h, &game, &admin, &player2).unwrap();
+    assert_eq!(
+        appeal(&mut h, &game, &game.player1),
+        Err(program_error(ErrorCode::AlreadyAppealed))
+    );
+    let before = h.balance(&player2);
+    h.warp(1);
+    execute(&mut h, &game).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.winner, player2);
+    assert_eq!(h.balance(&player2), before + 2 * STAKE);
+}
+
+#[test]
+fn removed_arbiter_cannot_resolve() {
+    let (mut h, arbiter, game) = disputed_game();
+    let admin = h.admin.insecure_clone();
+    h.send(
+        ix::remove_arbiter_ix(&admin.pubkey(), &arbiter.pubkey()),
+        &[&admin],
+    )
+    .unwrap();
+    assert_eq!(
+        resolve(&mut h, &game, &arbiter, &game.player1.pubkey()),
+        Err(program_error(ErrorCode::ArbiterNotWhitelisted))
+    );
+    assert_eq!(h.game(&game.key).status, GameStatus::Disputed);
+}
@@ -0,0 +1,118 @@
+//! Автоудвоения в LiteSVM: каждая ничья на стартовом броске поворачивает
+//! куб (он остаётся в центре) и берёт доплату с обоих игроков; после
+//! auto_double_limit или когда доплату не покрыть, ничья остаётся простым
+//! переброском с событием AutoDoubleSkipped.
+
+use backgammon_client::events::GameEvent;
+use backgammon_client::{ix, pda, GameOptions, GameState};
+use backgammon_tests::{options, program_error, Game, Harness};
+use pooler::{accounts, instruction, AutoDoubleSkipReason, AutoDoubleSkipped, ErrorCode};
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 1_000_000_000;
+
+fn auto_doubles(auto_doubles: bool, auto_double_limit: u8) -> GameOptions {
+    GameOptions {
+        auto_doubles,
+        auto_double_limit,
+        ..options()
+    }
+}
+
+/// Ничья на стартовом броске; возвращает события.
+fn tie(h: &mut Harness, game: &Game) -> Result<Vec<GameEvent>, TransactionError> {
+    h.send_with_events(
+        ix::build(
+            accounts::RecordOpeningTie {
+                game: game.key,
+                player1: game.player1.pubkey(),
+                player2: game.player2.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+                config: pda::config(),
+            },
+            instruction::RecordOpeningTie {},
+        ),
+        &[&game.player1, &game.player2],
+    )
+}
+
+/// Причина из события AutoDoubleSkipped, если автоудвоение пропущено.
+fn skipped(events: &[GameEvent]) -> Option<AutoDoubleSkipReason> {
+    events.iter().find_map(|event| match event {
+        GameEvent::AutoDoubleSkipped(AutoDoubleSkipped { reason, .. }) => Some(*reason),
+        _ => None,
+    })
+}
+
+/// Куб в центре, каждый внёс stake × cube_value.
+fn assert_cube(state: &GameState, cube_value: u8) {
+    assert_eq!(state.cube_value, cube_value);
+    assert_eq!(state.cube_owner, 0);
+    let each = STAKE * cube_value as u64;
+    assert_eq!(state.player1_deposit, each);
+    assert_eq!(state.player2_deposit, each);
+    assert_eq!(state.pot_lamports, 2 * each);
+}
+
+#[test]
+fn ties_turn_the_cube_until_a_player_cannot_cover() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, auto_doubles(true, 0));
+    let (p1_before, p2_before) = (
+        h.balance(&game.player1.pubkey()),
+        h.balance(&game.player2.pubkey()),
+    );
+
+    for (done, cube_value) in [(1, 2), (2, 4), (3, 8)] {
+        let events = tie(&mut h, &game).unwrap();
+        assert_eq!(skipped(&events), None);
+        let state = h.game(&game.key);
+        assert_cube(&state, cube_value);
+        assert_eq!(state.auto_doubles_done, done);
+    }
+    assert_eq!(h.balance(&game.player1.pubkey()), p1_before - 7 * STAKE);
+    assert_eq!(h.balance(&game.player2.pubkey()), p2_before - 7 * STAKE);
+
+    // Доплата до 16 больше оставшихся у игроков средств: простой переброс.
+    let events = tie(&mut h, &game).unwrap();
+    assert_eq!(
+        skipped(&events),
+        Some(AutoDoubleSkipReason::InsufficientFunds)
+    );
+    let state = h.game(&game.key);
+    assert_cube(&state, 8);
+    assert_eq!(state.auto_doubles_done, 3);
+}
+
+#[test]
+fn limit_caps_the_automatic_doubles() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, auto_doubles(true, 1));
+
+    tie(&mut h, &game).unwrap();
+    assert_cube(&h.game(&game.key), 2);
+
+    let events = tie(&mut h, &game).unwrap();
+    assert_eq!(skipped(&events), Some(AutoDoubleSkipReason::Limit));
+    let state = h.game(&game.key);
+    assert_cube(&state, 2);
+    assert_eq!(state.auto_doubles_done, 1);
+}
+
+#[test]
+fn ties_need_the_option_and_an_unplayed_game() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, auto_doubles(false, 0));
+    assert_eq!(
+        tie(&mut h, &game).unwrap_err(),
+        program_error(ErrorCode::AutoDoublesDisabled)
+    );
+
+    let game = h.active_game(STAKE, 0, auto_doubles(true, 0));
+    h.make_move(&game).unwrap();
+    assert_eq!(
+        tie(&mut h, &game).unwrap_err(),
+        program_error(ErrorCode::OpeningRollOver)
+    );
+}
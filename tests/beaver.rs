@@ -0,0 +1,141 @@
+//! Бобры и еноты в LiteSVM: цепочка удвоение → бобр → енот доводит куб до 8,
+//! и каждый игрок вносит в банк ровно 8 ставок; любой ход закрывает окно
+//! для енота, а без options.beavers_allowed бобр отклоняется.
+
+use backgammon_client::{ix, pda, GameOptions};
+use backgammon_tests::{options, program_error, Game, Harness};
+use pooler::{accounts, instruction, ErrorCode};
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+
+fn beavers(beavers_allowed: bool) -> GameOptions {
+    GameOptions {
+        beavers_allowed,
+        ..options()
+    }
+}
+
+fn beaver(h: &mut Harness, game: &Game) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::BeaverDouble {
+                game: game.key,
+                player1: game.player1.pubkey(),
+                player2: game.player2.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+                config: pda::config(),
+            },
+            instruction::BeaverDouble {},
+        ),
+        &[&game.player1, &game.player2],
+    )
+}
+
+fn raccoon(h: &mut Harness, game: &Game) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::RaccoonDouble {
+                game: game.key,
+                player1: game.player1.pubkey(),
+                player2: game.player2.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+                config: pda::config(),
+            },
+            instruction::RaccoonDouble {},
+        ),
+        &[&game.player1, &game.player2],
+    )
+}
+
+#[test]
+fn double_beaver_raccoon_reaches_8x() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, beavers(true));
+    let (p1_before, p2_before) = (
+        h.balance(&game.player1.pubkey()),
+        h.balance(&game.player2.pubkey()),
+    );
+
+    h.send(
+        ix::offer_double_ix(&game.key, &game.player1.pubkey()),
+        &[&game.player1],
+    )
+    .unwrap();
+    // Енот возможен только после бобра.
+    assert_eq!(
+        raccoon(&mut h, &game),
+        Err(program_error(ErrorCode::NoRaccoonWindow))
+    );
+
+    // Бобр: принявший сразу переудваивает до 4 и оставляет куб себе.
+    beaver(&mut h, &game).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!((state.cube_value, state.cube_owner), (4, 2));
+    assert!(!state.double_pending);
+    assert!(state.raccoon_pending);
+    assert_eq!(state.player1_deposit, 4 * STAKE);
+    assert_eq!(state.player2_deposit, 4 * STAKE);
+
+    // Енот: удвоивший отвечает удвоением до 8, куб остаётся у бобра.
+    raccoon(&mut h, &game).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!((state.cube_value, state.cube_owner), (8, 2));
+    assert!(!state.raccoon_pending);
+    assert_eq!(state.player1_deposit, 8 * STAKE);
+    assert_eq!(state.player2_deposit, 8 * STAKE);
+    assert_eq!(state.pot_lamports, 16 * STAKE);
+    assert_eq!(h.balance(&game.player1.pubkey()), p1_before - 7 * STAKE);
+    assert_eq!(h.balance(&game.player2.pubkey()), p2_before - 7 * STAKE);
+    assert_eq!(
+        raccoon(&mut h, &game),
+        Err(program_error(ErrorCode::NoRaccoonWindow))
+    );
+
+    // Победитель забирает банк восьмикратной игры.
+    let config = h.config();
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &game.player2.pubkey(), 1),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+    assert_eq!(h.balance(&game.player2.pubkey()), p2_before + 9 * STAKE);
+    assert_eq!(h.balance(&game.player1.pubkey()), p1_before - 7 * STAKE);
+}
+
+#[test]
+fn a_move_closes_the_raccoon_window() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, beavers(true));
+    h.send(
+        ix::offer_double_ix(&game.key, &game.player1.pubkey()),
+        &[&game.player1],
+    )
+    .unwrap();
+    beaver(&mut h, &game).unwrap();
+
+    h.make_move(&game).unwrap();
+    assert!(!h.game(&game.key).raccoon_pending);
+    assert_eq!(
+        raccoon(&mut h, &game),
+        Err(program_error(ErrorCode::NoRaccoonWindow))
+    );
+    assert_eq!(h.game(&game.key).cube_value, 4);
+}
+
+#[test]
+fn beaver_needs_the_option() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, beavers(false));
+    h.send(
+        ix::offer_double_ix(&game.key, &game.player1.pubkey()),
+        &[&game.player1],
+    )
+    .unwrap();
+    assert_eq!(
+        beaver(&mut h, &game),
+        Err(program_error(ErrorCode::BeaversNotAllowed))
+    );
+    assert!(h.game(&game.key).double_pending);
+}
@@ -0,0 +1,268 @@
+//! Шуэт в LiteSVM: коробка (player1) играет доску против капитана (player2),
+//! три члена команды держат с коробкой свои ставки. Коробка удваивает двоих:
+//! один принимает, второй отказывается и сразу отдаёт коробке обе ставки,
+//! у третьего куб не тронут. settle_chouette по исходу одной доски платит
+//! каждому оставшемуся члену по его кубу.
+
+use backgammon_client::{ix, pda, AccountMeta, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{
+    accounts, instruction, start_board, ChouetteMemberStatus, ChouetteState, ErrorCode, RuleVariant,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 50_000_000;
+const MEMBER_STAKE: u64 = 10_000_000;
+
+struct Chouette {
+    game: Pubkey,
+    key: Pubkey,
+    box_player: Keypair,
+    captain: Keypair,
+    members: Vec<Keypair>,
+}
+
+impl Chouette {
+    fn state(&self, h: &Harness) -> ChouetteState {
+        h.account(&self.key)
+    }
+}
+
+fn system_program() -> Pubkey {
+    anchor_lang::system_program::ID
+}
+
+/// Игра коробки с капитаном и шуэт с members членами команды.
+fn chouette(h: &mut Harness, members: usize) -> Chouette {
+    let (box_player, captain) = (h.player(), h.player());
+    let game = Keypair::new();
+    let options = GameOptions {
+        initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+        ..GameOptions::default()
+    };
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &box_player.pubkey(),
+            1,
+            STAKE,
+            0,
+            &captain.pubkey(),
+            options,
+        ),
+        &[&game, &box_player],
+    )
+    .unwrap();
+    let state = h.game(&game.pubkey());
+    h.send(
+        ix::join_game_ix(&game.pubkey(), &state, &captain.pubkey(), Vec::new(), None),
+        &[&captain],
+    )
+    .unwrap();
+
+    let key = pda::chouette(&game.pubkey());
+    h.send(
+        ix::build(
+            accounts::InitChouette {
+                game: game.pubkey(),
+                chouette: key,
+                box_player: box_player.pubkey(),
+                system_program: system_program(),
+                config: pda::config(),
+            },
+            instruction::InitChouette {
+                stake_lamports: MEMBER_STAKE,
+            },
+        ),
+        &[&box_player],
+    )
+    .unwrap();
+
+    let members: Vec<_> = (0..members).map(|_| h.player()).collect();
+    for member in &members {
+        h.send(
+            ix::build(
+                accounts::JoinChouette {
+                    game: game.pubkey(),
+                    chouette: key,
+                    box_player: box_player.pubkey(),
+                    member: member.pubkey(),
+                    system_program: system_program(),
+                    config: pda::config(),
+                },
+                instruction::JoinChouette {},
+            ),
+            &[&box_player, member],
+        )
+        .unwrap();
+    }
+    Chouette {
+        game: game.pubkey(),
+        key,
+        box_player,
+        captain,
+        members,
+    }
+}
+
+fn offer(h: &mut Harness, c: &Chouette, member_index: u8) {
+    h.send(
+        ix::build(
+            accounts::ChouetteOfferDouble {
+                chouette: c.key,
+                box_player: c.box_player.pubkey(),
+                system_program: system_program(),
+            },
+            instruction::ChouetteOfferDouble { member_index },
+        ),
+        &[&c.box_player],
+    )
+    .unwrap();
+}
+
+fn take(h: &mut Harness, c: &Chouette, member_index: u8) -> Result<(), TransactionError> {
+    let member = &c.members[member_index as usize];
+    h.send(
+        ix::build(
+            accounts::ChouetteRespond {
+                chouette: c.key,
+                member: member.pubkey(),
+                system_program: system_program(),
+            },
+            instruction::ChouetteTake { member_index },
+        ),
+        &[member],
+    )
+}
+
+fn drop_double(h: &mut Harness, c: &Chouette, member_index: u8) {
+    let member = &c.members[member_index as usize];
+    h.send(
+        ix::build(
+            accounts::ChouetteDrop {
+                chouette: c.key,
+                member: member.pubkey(),
+                box_player: c.box_player.pubkey(),
+            },
+            instruction::ChouetteDrop { member_index },
+        ),
+        &[member],
+    )
+    .unwrap();
+}
+
+/// Завершает доску победой winner и рассчитывает шуэт; в remaining_accounts
+/// идут активные члены команды по порядку.
+fn finish_and_settle(h: &mut Harness, c: &Chouette, winner: &Pubkey, active: &[&Keypair]) {
+    let (state, config) = (h.game(&c.game), h.config());
+    h.send(
+        ix::finish_game_ix(&c.game, &state, &config, winner, 1),
+        &[&c.box_player, &c.captain],
+    )
+    .unwrap();
+
+    let crank = h.player();
+    let mut settle = ix::build(
+        accounts::SettleChouette {
+            game: c.game,
+            chouette: c.key,
+            box_player: c.box_player.pubkey(),
+            payer: crank.pubkey(),
+            config: pda::config(),
+        },
+        instruction::SettleChouette {},
+    );
+    settle.accounts.extend(
+        active
+            .iter()
+            .map(|member| AccountMeta::new(member.pubkey(), false)),
+    );
+    h.send(settle, &[&crank]).unwrap();
+}
+
+/// Три члена команды: 0 принимает удвоение, 1 отказывается, 2 без удвоения.
+fn doubled(h: &mut Harness) -> Chouette {
+    let c = chouette(h, 3);
+    offer(h, &c, 0);
+    offer(h, &c, 1);
+    take(h, &c, 0).unwrap();
+
+    let box_before = h.balance(&c.box_player.pubkey());
+    drop_double(h, &c, 1);
+    // Отказавшийся теряет ставку, коробка забирает и свою доплату.
+    assert_eq!(
+        h.balance(&c.box_player.pubkey()),
+        box_before + 3 * MEMBER_STAKE
+    );
+    assert_eq!(
+        take(h, &c, 1),
+        Err(program_error(ErrorCode::InvalidChouetteMember))
+    );
+
+    let state = c.state(h);
+    let [taken, dropped, untouched, empty] = state.members;
+    assert_eq!(taken.status, ChouetteMemberStatus::Active);
+    assert_eq!(taken.cube_value, 2);
+    assert_eq!(
+        (taken.member_deposit, taken.box_deposit),
+        (2 * MEMBER_STAKE, 2 * MEMBER_STAKE)
+    );
+    assert_eq!(dropped.status, ChouetteMemberStatus::Dropped);
+    assert_eq!((dropped.member_deposit, dropped.box_deposit), (0, 0));
+    assert_eq!(untouched.cube_value, 1);
+    assert_eq!(empty.status, ChouetteMemberStatus::Empty);
+    assert_eq!(state.member_count, 3);
+    c
+}
+
+#[test]
+fn team_win_pays_each_member_by_their_cube() {
+    let mut h = Harness::new();
+    let c = doubled(&mut h);
+    let before: Vec<_> = c.members.iter().map(|m| h.balance(&m.pubkey())).collect();
+    let box_before = h.balance(&c.box_player.pubkey());
+
+    let captain = c.captain.pubkey();
+    finish_and_settle(&mut h, &c, &captain, &[&c.members[0], &c.members[2]]);
+
+    // Принявший забирает банк двойного куба, нетронутый — одинарного.
+    assert_eq!(
+        h.balance(&c.members[0].pubkey()),
+        before[0] + 4 * MEMBER_STAKE
+    );
+    assert_eq!(h.balance(&c.members[1].pubkey()), before[1]);
+    assert_eq!(
+        h.balance(&c.members[2].pubkey()),
+        before[2] + 2 * MEMBER_STAKE
+    );
+    assert_eq!(h.balance(&c.box_player.pubkey()), box_before);
+    let state = c.state(&h);
+    assert!(state.settled);
+    assert!(state
+        .members
+        .iter()
+        .all(|m| m.member_deposit == 0 && m.box_deposit == 0));
+}
+
+#[test]
+fn box_win_collects_every_remaining_stake() {
+    let mut h = Harness::new();
+    let c = doubled(&mut h);
+    let before: Vec<_> = c.members.iter().map(|m| h.balance(&m.pubkey())).collect();
+    let box_before = h.balance(&c.box_player.pubkey());
+
+    let box_player = c.box_player.pubkey();
+    finish_and_settle(&mut h, &c, &box_player, &[&c.members[0], &c.members[2]]);
+
+    // Банк доски (2 × STAKE) и ставки шуэта: 4 у принявшего, 2 у нетронутого.
+    assert_eq!(
+        h.balance(&c.box_player.pubkey()),
+        box_before + 2 * STAKE + 6 * MEMBER_STAKE
+    );
+    for (member, before) in c.members.iter().zip(before) {
+        assert_eq!(h.balance(&member.pubkey()), before);
+    }
+    assert!(c.state(&h).settled);
+}
@@ -0,0 +1,193 @@
+//! Merkle-коммитмент истории ходов в LiteSVM: init_game с
+//! options.history_commitment создаёт HistoryCommitment за счёт player1,
+//! каждый ход сдвигает history_root, и корень совпадает с построенным
+//! оффчейн по записям ходов. verify_history_entry доказывает настоящую запись
+//! событием HistoryEntryVerified и отклоняет подделанную; после завершения
+//! close_history_commitment возвращает ренту player1.
+
+use anchor_lang::Space;
+use backgammon_client::events::{parse_events, GameEvent};
+use backgammon_client::{history, ix, pda, GameOptions, GameState, GameStatus};
+use backgammon_tests::{program_error, Game, Harness, PLAYER_LAMPORTS};
+use pooler::{
+    pack_board, start_board, CreateCooldown, ErrorCode, HistoryCommitment, MoveRecord, OpenGames,
+    ReplayPosition, RuleVariant,
+};
+use solana_signer::Signer;
+
+const STAKE: u64 = 100_000_000;
+const MOVE_FEE: u64 = 1_000_000;
+const MOVES: u64 = 12;
+
+fn create(h: &mut Harness) -> Game {
+    let options = GameOptions {
+        initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+        history_commitment: true,
+        ..GameOptions::default()
+    };
+    let game = h.create_game(STAKE, MOVE_FEE, options).unwrap();
+
+    // Ренту коммитмента платит player1 вместе с рентой игры.
+    let rent = h.rent(8 + GameState::MAX_SIZE)
+        + h.rent(8 + OpenGames::MAX_SIZE)
+        + h.rent(8 + CreateCooldown::MAX_SIZE)
+        + h.rent(8 + HistoryCommitment::INIT_SPACE);
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        PLAYER_LAMPORTS - STAKE - rent
+    );
+    let commitment: HistoryCommitment = h.account(&pda::history_commitment(&game.key));
+    assert_eq!(commitment.game, game.key);
+    assert_eq!(commitment.payer, game.player1.pubkey());
+    assert_eq!(commitment.leaf_count, 0);
+    assert_eq!(Some(commitment.history_root), history::history_root(&[]));
+    assert!(h.game(&game.key).history_commitment);
+
+    h.join(&game).unwrap();
+    game
+}
+
+/// MOVES законных ходов; возвращает их записи, собранные клиентом: доска,
+/// кубики, слот и комиссия каждого хода.
+fn play(h: &mut Harness, game: &Game) -> Vec<MoveRecord> {
+    let config = h.config();
+    let mut position: backgammon_core::Position = ReplayPosition::start(RuleVariant::Standard, 0)
+        .unwrap()
+        .into();
+    let mut records = Vec::new();
+    let mut roots = Vec::new();
+    for i in 0..MOVES {
+        let dice = [(i % 6) as u8 + 1, ((i * 5 + 2) % 6) as u8 + 1];
+        let state = h.game(&game.key);
+        let side = state.current_turn;
+        position = backgammon_core::legal_moves(&position, side, dice)[0];
+        let mover = if side == 1 {
+            &game.player1
+        } else {
+            &game.player2
+        };
+
+        h.warp(1);
+        records.push(MoveRecord {
+            move_index: i + 1,
+            mover: mover.pubkey(),
+            board: pack_board(&position.board).unwrap(),
+            dice,
+            slot: h.slot(),
+            fee_lamports: MOVE_FEE,
+        });
+        h.send(
+            ix::make_move_ix(
+                &game.key,
+                &state,
+                &config,
+                position.board,
+                dice,
+                [0; 32],
+                None,
+            ),
+            &[mover],
+        )
+        .unwrap();
+
+        // Каждый ход сдвигает корень.
+        let commitment: HistoryCommitment = h.account(&pda::history_commitment(&game.key));
+        assert_eq!(commitment.leaf_count, i + 1);
+        assert!(!roots.contains(&commitment.history_root));
+        roots.push(commitment.history_root);
+    }
+    records
+}
+
+#[test]
+fn committed_moves_prove_and_forgeries_fail() {
+    let mut h = Harness::new();
+    let game = create(&mut h);
+    let records = play(&mut h, &game);
+
+    let commitment: HistoryCommitment = h.account(&pda::history_commitment(&game.key));
+    assert_eq!(
+        Some(commitment.history_root),
+        history::history_root(&records)
+    );
+
+    // Любая записанная запись доказывается; доказательство попадает в событие.
+    let verifier = h.player();
+    for record in &records {
+        let proof = history::history_proof(&records, record.move_index).unwrap();
+        let (result, logs) = h.send_with_logs(
+            ix::verify_history_entry_ix(&game.key, *record, proof),
+            &[&verifier],
+        );
+        result.unwrap();
+        let verified = parse_events(&logs)
+            .into_iter()
+            .find_map(|event| match event {
+                GameEvent::HistoryEntryVerified(verified) => Some(verified),
+                _ => None,
+            })
+            .expect("HistoryEntryVerified");
+        assert_eq!(verified.game, game.key);
+        assert_eq!(verified.history_root, commitment.history_root);
+        assert_eq!(verified.entry, *record);
+    }
+
+    // Подделанный лист с настоящим доказательством не проходит.
+    let proof = history::history_proof(&records, 5).unwrap();
+    let mut forged = records[4];
+    forged.board = records[5].board;
+    assert_eq!(
+        h.send(
+            ix::verify_history_entry_ix(&game.key, forged, proof),
+            &[&verifier]
+        ),
+        Err(program_error(ErrorCode::HistoryProofInvalid))
+    );
+    let mut forged = records[4];
+    forged.fee_lamports = 0;
+    assert_eq!(
+        h.send(
+            ix::verify_history_entry_ix(&game.key, forged, proof),
+            &[&verifier]
+        ),
+        Err(program_error(ErrorCode::HistoryProofInvalid))
+    );
+}
+
+#[test]
+fn close_returns_the_rent_after_the_game() {
+    let mut h = Harness::new();
+    let game = create(&mut h);
+    let commitment = pda::history_commitment(&game.key);
+
+    // Закрыть коммитмент можно только после завершения игры.
+    let closer = h.player();
+    assert_eq!(
+        h.send(
+            ix::close_history_commitment_ix(&game.key, &game.player1.pubkey()),
+            &[&closer]
+        ),
+        Err(program_error(ErrorCode::GameNotFinished))
+    );
+
+    play(&mut h, &game);
+    let (state, config) = (h.game(&game.key), h.config());
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &game.player1.pubkey(), 1),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+    assert_eq!(h.game(&game.key).status, GameStatus::Finished);
+
+    let rent = h.balance(&commitment);
+    assert_eq!(rent, h.rent(8 + HistoryCommitment::INIT_SPACE));
+    let player1_before = h.balance(&game.player1.pubkey());
+    h.send(
+        ix::close_history_commitment_ix(&game.key, &game.player1.pubkey()),
+        &[&closer],
+    )
+    .unwrap();
+    assert_eq!(h.balance(&commitment), 0);
+    assert_eq!(h.balance(&game.player1.pubkey()), player1_before + rent);
+    assert!(!h.game(&game.key).history_commitment);
+}
@@ -0,0 +1,208 @@
+//! Расход вычислительных единиц (CU) основными инструкциями и пороги,
+//! превышение которых валит прогон: регрессия по CU видна в PR, а не на
+//! mainnet.
+//!
+//! Замеры печатаются таблицей (`cargo test --manifest-path tests/Cargo.toml
+//! --test compute_units -- --nocapture`), чтобы сравнивать их при ревью
+//! фич, которые утяжеляют make_move. Порог меняется только вместе с
+//! объяснением в PR.
+//!
+//! Пороги проверяются на сборке по умолчанию (с verbose-logs) — это верхняя
+//! оценка. Если рядом собрана релизная программа (QUIET_PROGRAM_SO), тот же
+//! сценарий прогоняется и на ней, а в таблице появляется экономия от
+//! отключённых логов.
+
+use std::path::Path;
+
+use backgammon_client::{ix, GameOptions, Pubkey};
+use backgammon_tests::{Harness, PROGRAM_SO, QUIET_PROGRAM_SO};
+use pooler::{start_board, RuleVariant};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const STAKE: u64 = 100_000_000;
+const MOVE_FEE: u64 = 1_000_000;
+/// Ходов до позднего замера make_move и до finish_game.
+const LATE_MOVES: u64 = 40;
+
+/// Пороги CU по инструкциям.
+const INIT_GAME_BUDGET: u64 = 80_000;
+const JOIN_GAME_BUDGET: u64 = 50_000;
+const MAKE_MOVE_BUDGET: u64 = 40_000;
+const FINISH_GAME_BUDGET: u64 = 60_000;
+
+struct Measurement {
+    instruction: &'static str,
+    conditions: &'static str,
+    units: u64,
+    budget: u64,
+}
+
+struct Bench {
+    h: Harness,
+    rows: Vec<Measurement>,
+}
+
+impl Bench {
+    fn record(
+        &mut self,
+        instruction: &'static str,
+        conditions: &'static str,
+        units: u64,
+        budget: u64,
+    ) {
+        self.rows.push(Measurement {
+            instruction,
+            conditions,
+            units,
+            budget,
+        });
+    }
+
+    /// Создаёт игру со стандартной доской и записывает init_game.
+    fn init_game(&mut self, conditions: &'static str) -> (Pubkey, Keypair, Keypair) {
+        let (player1, player2) = (self.h.player(), self.h.player());
+        let game = Keypair::new();
+        let options = GameOptions {
+            initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+            ..GameOptions::default()
+        };
+        let units = self
+            .h
+            .send_metered(
+                ix::init_game_ix(
+                    &game.pubkey(),
+                    &player1.pubkey(),
+                    1,
+                    STAKE,
+                    MOVE_FEE,
+                    &player2.pubkey(),
+                    options,
+                ),
+                &[&game, &player1],
+            )
+            .unwrap();
+        self.record("init_game", conditions, units, INIT_GAME_BUDGET);
+        (game.pubkey(), player1, player2)
+    }
+
+    fn join_game(&mut self, game: &Pubkey, player2: &Keypair) {
+        let state = self.h.game(game);
+        let units = self
+            .h
+            .send_metered(
+                ix::join_game_ix(game, &state, &player2.pubkey(), Vec::new(), None),
+                &[player2],
+            )
+            .unwrap();
+        self.record("join_game", "без напарников", units, JOIN_GAME_BUDGET);
+    }
+
+    /// Ход того, чья очередь; возвращает израсходованные CU.
+    fn make_move(&mut self, game: &Pubkey, players: [&Keypair; 2]) -> u64 {
+        let config = self.h.config();
+        let state = self.h.game(game);
+        let mover = players[state.current_turn as usize - 1];
+        let mut annotation = [0; 32];
+        annotation[..8].copy_from_slice(&state.move_index.to_le_bytes());
+        self.h
+            .send_metered(
+                ix::make_move_ix(
+                    game,
+                    &state,
+                    &config,
+                    state.board_points().unwrap(),
+                    [3, 1],
+                    annotation,
+                    None,
+                ),
+                &[mover],
+            )
+            .unwrap()
+    }
+}
+
+/// Сценарий на программе program_so: init_game первой игры игрока,
+/// join_game, LATE_MOVES ходов, finish_game.
+fn measure(program_so: &str) -> Vec<Measurement> {
+    let mut bench = Bench {
+        h: Harness::with_program(program_so),
+        rows: Vec::new(),
+    };
+
+    let (game, player1, player2) = bench.init_game("первая игра: создаёт PDA игрока");
+    bench.join_game(&game, &player2);
+
+    let players = [&player1, &player2];
+    let units = bench.make_move(&game, players);
+    bench.record("make_move", "первый ход", units, MAKE_MOVE_BUDGET);
+    let mut worst = units;
+    for _ in 1..LATE_MOVES - 1 {
+        worst = worst.max(bench.make_move(&game, players));
+    }
+    let units = bench.make_move(&game, players);
+    bench.record("make_move", "40-й ход", units, MAKE_MOVE_BUDGET);
+    bench.record(
+        "make_move",
+        "максимум за 40 ходов",
+        worst.max(units),
+        MAKE_MOVE_BUDGET,
+    );
+
+    let state = bench.h.game(&game);
+    let config = bench.h.config();
+    let units = bench
+        .h
+        .send_metered(
+            ix::finish_game_ix(&game, &state, &config, &player1.pubkey(), 1),
+            &[&player1, &player2],
+        )
+        .unwrap();
+    bench.record("finish_game", "после 40 ходов", units, FINISH_GAME_BUDGET);
+    bench.rows
+}
+
+/// Таблица замеров; с quiet — ещё CU релизной сборки и экономия.
+fn print(rows: &[Measurement], quiet: Option<&[Measurement]>) {
+    println!(
+        "{:<12} {:<32} {:>8} {:>8} {:>8} {:>8}",
+        "instruction", "conditions", "CU", "budget", "quiet", "saved"
+    );
+    for (i, row) in rows.iter().enumerate() {
+        let (quiet, saved) = match quiet {
+            Some(quiet) => (
+                quiet[i].units.to_string(),
+                (row.units as i64 - quiet[i].units as i64).to_string(),
+            ),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        println!(
+            "{:<12} {:<32} {:>8} {:>8} {:>8} {:>8}",
+            row.instruction, row.conditions, row.units, row.budget, quiet, saved
+        );
+    }
+    if quiet.is_none() {
+        println!("no {QUIET_PROGRAM_SO}: build with --no-default-features to see the savings");
+    }
+}
+
+#[test]
+fn instructions_stay_within_compute_budget() {
+    let rows = measure(PROGRAM_SO);
+    let quiet = Path::new(QUIET_PROGRAM_SO)
+        .exists()
+        .then(|| measure(QUIET_PROGRAM_SO));
+    print(&rows, quiet.as_deref());
+
+    let over: Vec<String> = rows
+        .iter()
+        .filter(|row| row.units >= row.budget)
+        .map(|row| {
+            format!(
+                "{} ({}): {} CU, budget {}",
+                row.instruction, row.conditions, row.units, row.budget
+            )
+        })
+        .collect();
+    assert!(over.is_empty(), "over compute budget: {over:?}");
+}
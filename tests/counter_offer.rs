@@ -0,0 +1,161 @@
+//! Встречное предложение ставки в LiteSVM: player2 предлагает другие
+//! условия, player1 принимает их с доплатой или частичным возвратом
+//! депозита, и join_game идёт по новой ставке. Без accept_counter вход
+//! идёт по исходным условиям.
+//!
+//! Балансы сверяются до lamport; ренту CounterProposal платит и получает
+//! обратно player2.
+
+use anchor_lang::Space;
+use backgammon_client::{ix, pda, GameOptions, GameStatus};
+use backgammon_tests::{program_error, Game, Harness, PLAYER_LAMPORTS};
+use pooler::{CounterProposal, ErrorCode};
+use solana_signer::Signer;
+
+const STAKE: u64 = 100_000_000;
+const MOVE_FEE: u64 = 1_000_000;
+
+fn counter(h: &mut Harness, game: &Game, stake: u64, move_fee: u64) {
+    h.send(
+        ix::counter_offer_ix(&game.key, &game.player2.pubkey(), stake, move_fee),
+        &[&game.player2],
+    )
+    .unwrap();
+    let state = h.game(&game.key);
+    assert!(state.counter_pending);
+    let proposal: CounterProposal = h.account(&pda::counter_proposal(&game.key));
+    assert_eq!(proposal.proposer, game.player2.pubkey());
+    assert_eq!(proposal.stake_lamports, stake);
+    assert_eq!(proposal.move_fee_lamports, move_fee);
+}
+
+/// Принимает предложение и проверяет, что player1 доплатил (или получил)
+/// ровно разницу ставок, а рента PDA вернулась player2.
+fn accept(h: &mut Harness, game: &Game, stake: u64, move_fee: u64) {
+    let proposal_rent = h.rent(8 + CounterProposal::INIT_SPACE);
+    let player1_before = h.balance(&game.player1.pubkey());
+    let player2_before = h.balance(&game.player2.pubkey());
+    let state = h.game(&game.key);
+    h.send(ix::accept_counter_ix(&game.key, &state), &[&game.player1])
+        .unwrap();
+
+    assert_eq!(
+        h.balance(&game.player1.pubkey()) as i128 - player1_before as i128,
+        STAKE as i128 - stake as i128
+    );
+    assert_eq!(
+        h.balance(&game.player2.pubkey()),
+        player2_before + proposal_rent
+    );
+    assert_eq!(h.balance(&pda::counter_proposal(&game.key)), 0);
+
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::WaitingForPlayer2);
+    assert!(!state.counter_pending);
+    assert_eq!(state.stake_lamports, stake);
+    assert_eq!(state.move_fee_lamports, move_fee);
+    assert_eq!(state.pot_lamports, stake);
+    assert_eq!(state.player1_deposit, stake);
+    assert_eq!(
+        h.balance(&game.key),
+        h.rent(8 + pooler::GameState::MAX_SIZE) + stake
+    );
+}
+
+fn join(h: &mut Harness, game: &Game) {
+    let state = h.game(&game.key);
+    h.send(
+        ix::join_game_ix(&game.key, &state, &game.player2.pubkey(), Vec::new(), None),
+        &[&game.player2],
+    )
+    .unwrap();
+}
+
+#[test]
+fn accepted_decrease_refunds_player1() {
+    let mut h = Harness::new();
+    let game = h
+        .create_game(STAKE, MOVE_FEE, GameOptions::default())
+        .unwrap();
+    let (stake, move_fee) = (STAKE / 4, MOVE_FEE / 2);
+    counter(&mut h, &game, stake, move_fee);
+    accept(&mut h, &game, stake, move_fee);
+
+    join(&mut h, &game);
+    assert_eq!(h.balance(&game.player2.pubkey()), PLAYER_LAMPORTS - stake);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Active);
+    assert_eq!(state.pot_lamports, 2 * stake);
+}
+
+#[test]
+fn accepted_increase_takes_extra_transfer() {
+    let mut h = Harness::new();
+    let game = h
+        .create_game(STAKE, MOVE_FEE, GameOptions::default())
+        .unwrap();
+    let (stake, move_fee) = (3 * STAKE, 2 * MOVE_FEE);
+    counter(&mut h, &game, stake, move_fee);
+    accept(&mut h, &game, stake, move_fee);
+
+    join(&mut h, &game);
+    assert_eq!(h.balance(&game.player2.pubkey()), PLAYER_LAMPORTS - stake);
+    assert_eq!(h.game(&game.key).pot_lamports, 2 * stake);
+}
+
+#[test]
+fn unaccepted_counter_keeps_original_terms() {
+    let mut h = Harness::new();
+    let game = h
+        .create_game(STAKE, MOVE_FEE, GameOptions::default())
+        .unwrap();
+
+    // Чужой ключ не торгуется, комиссия не выше ставки.
+    let stranger = h.player();
+    assert!(h
+        .send(
+            ix::counter_offer_ix(&game.key, &stranger.pubkey(), STAKE, 0),
+            &[&stranger],
+        )
+        .is_err());
+    assert_eq!(
+        h.send(
+            ix::counter_offer_ix(&game.key, &game.player2.pubkey(), STAKE, STAKE + 1),
+            &[&game.player2],
+        ),
+        Err(program_error(ErrorCode::MoveFeeExceedsStake))
+    );
+
+    counter(&mut h, &game, 2 * STAKE, MOVE_FEE);
+    // Второе предложение не создаётся, пока первое не снято.
+    assert!(h
+        .send(
+            ix::counter_offer_ix(&game.key, &game.player2.pubkey(), STAKE / 2, 0),
+            &[&game.player2],
+        )
+        .is_err());
+
+    // Вход без accept_counter — по исходной ставке.
+    join(&mut h, &game);
+    let proposal_rent = h.rent(8 + CounterProposal::INIT_SPACE);
+    assert_eq!(
+        h.balance(&game.player2.pubkey()),
+        PLAYER_LAMPORTS - STAKE - proposal_rent
+    );
+    let state = h.game(&game.key);
+    assert_eq!(state.stake_lamports, STAKE);
+    assert_eq!(state.pot_lamports, 2 * STAKE);
+    assert_eq!(
+        h.send(ix::accept_counter_ix(&game.key, &state), &[&game.player1]),
+        Err(program_error(ErrorCode::GameNotWaitingForPlayer2))
+    );
+
+    // Висящее предложение снимается и после входа; рента — автору.
+    h.send(
+        ix::withdraw_counter_ix(&game.key, &game.player2.pubkey(), &game.player1.pubkey()),
+        &[&game.player1],
+    )
+    .unwrap();
+    assert_eq!(h.balance(&game.player2.pubkey()), PLAYER_LAMPORTS - STAKE);
+    assert!(!h.game(&game.key).counter_pending);
+}
@@ -0,0 +1,85 @@
+//! Интервал между играми создателя в LiteSVM: вторая игра сразу после первой
+//! отклоняется с CreateRateLimited, после config.min_create_gap_slots
+//! создаётся; админ интервала не ждёт.
+
+use backgammon_client::{ix, pda, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{ConfigUpdate, CreateCooldown, ErrorCode};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+const GAP: u64 = 50;
+
+fn harness() -> Harness {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let update = ConfigUpdate {
+        min_create_gap_slots: Some(GAP),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+    // Нулевой last_created_slot означает «игр ещё не было».
+    h.warp(1);
+    h
+}
+
+fn create(h: &mut Harness, player1: &Keypair, game_id: u64) -> Result<(), TransactionError> {
+    let game = Keypair::new();
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            game_id,
+            STAKE,
+            0,
+            &Pubkey::new_unique(),
+            GameOptions::default(),
+        ),
+        &[&game, player1],
+    )
+}
+
+fn last_created_slot(h: &Harness, player: &Pubkey) -> u64 {
+    h.account::<CreateCooldown>(&pda::create_cooldown(player))
+        .last_created_slot
+}
+
+#[test]
+fn second_game_waits_for_the_gap() {
+    let mut h = harness();
+    let player1 = h.player();
+
+    create(&mut h, &player1, 1).unwrap();
+    let first = last_created_slot(&h, &player1.pubkey());
+    assert_eq!(first, h.slot());
+
+    assert_eq!(
+        create(&mut h, &player1, 2),
+        Err(program_error(ErrorCode::CreateRateLimited))
+    );
+    h.warp(GAP - 1);
+    assert_eq!(
+        create(&mut h, &player1, 2),
+        Err(program_error(ErrorCode::CreateRateLimited))
+    );
+    assert_eq!(last_created_slot(&h, &player1.pubkey()), first);
+
+    h.warp(1);
+    create(&mut h, &player1, 2).unwrap();
+    assert_eq!(last_created_slot(&h, &player1.pubkey()), first + GAP);
+
+    // Интервал у каждого кошелька свой.
+    let other = h.player();
+    create(&mut h, &other, 1).unwrap();
+}
+
+#[test]
+fn admin_is_exempt() {
+    let mut h = harness();
+    let admin = h.admin.insecure_clone();
+    create(&mut h, &admin, 1).unwrap();
+    create(&mut h, &admin, 2).unwrap();
+}
@@ -0,0 +1,156 @@
+//! Куб удвоения в LiteSVM: принятое удвоение удваивает банк и передаёт куб
+//! принявшему, отказ отдаёт удвоившему банк до удвоения, владелец куба
+//! переудваивает, а игрок без доступа к кубу удвоить не может.
+//!
+//! Комиссия за ход нулевая, так что банк всегда равен сумме вкладов.
+
+use backgammon_client::{ix, pda, GameState};
+use backgammon_tests::{options, program_error, Game, Harness};
+use pooler::{accounts, instruction, ErrorCode, FinishReason, GameStatus};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+
+fn offer(h: &mut Harness, game: &Game, player: &Keypair) -> Result<(), TransactionError> {
+    h.send(ix::offer_double_ix(&game.key, &player.pubkey()), &[player])
+}
+
+fn take(h: &mut Harness, game: &Game, player: &Keypair) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::TakeDouble {
+                game: game.key,
+                player: player.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+                config: pda::config(),
+            },
+            instruction::TakeDouble {},
+        ),
+        &[player],
+    )
+}
+
+/// Банк покрывает куб: каждый внёс stake × cube_value.
+fn assert_escrow(state: &GameState) {
+    let each = STAKE * state.cube_value as u64;
+    assert_eq!(state.player1_deposit, each);
+    assert_eq!(state.player2_deposit, each);
+    assert_eq!(state.pot_lamports, 2 * each);
+}
+
+#[test]
+fn take_doubles_the_pot_and_passes_the_cube() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, options());
+    let (p1_before, p2_before) = (
+        h.balance(&game.player1.pubkey()),
+        h.balance(&game.player2.pubkey()),
+    );
+
+    offer(&mut h, &game, &game.player1).unwrap();
+    let state = h.game(&game.key);
+    assert!(state.double_pending);
+    assert_eq!(state.cube_value, 1);
+    assert_eq!(state.pot_lamports, 3 * STAKE);
+    assert_eq!(h.balance(&game.player1.pubkey()), p1_before - STAKE);
+    // Второе предложение до ответа не принимается, принимает только соперник.
+    assert_eq!(
+        offer(&mut h, &game, &game.player1),
+        Err(program_error(ErrorCode::DoublePending))
+    );
+    assert_eq!(
+        take(&mut h, &game, &game.player1),
+        Err(program_error(ErrorCode::InvalidPlayer))
+    );
+
+    take(&mut h, &game, &game.player2).unwrap();
+    let state = h.game(&game.key);
+    assert!(!state.double_pending);
+    assert_eq!((state.cube_value, state.cube_owner), (2, 2));
+    assert_escrow(&state);
+    assert_eq!(h.balance(&game.player2.pubkey()), p2_before - STAKE);
+
+    // Расчёт идёт по банку с учётом куба.
+    let config = h.config();
+    let winner_before = h.balance(&game.player1.pubkey());
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &game.player1.pubkey(), 1),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+    assert_eq!(h.balance(&game.player1.pubkey()), winner_before + 4 * STAKE);
+    assert_eq!(h.game(&game.key).pot_lamports, 0);
+}
+
+#[test]
+fn drop_resigns_at_the_pre_double_value() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, options());
+    let (p1_before, p2_before) = (
+        h.balance(&game.player1.pubkey()),
+        h.balance(&game.player2.pubkey()),
+    );
+
+    offer(&mut h, &game, &game.player1).unwrap();
+    let (state, config) = (h.game(&game.key), h.config());
+    h.send(
+        ix::drop_double_ix(&game.key, &state, &config, &game.player2.pubkey()),
+        &[&game.player2],
+    )
+    .unwrap();
+
+    // Удвоивший получает назад доплату и выигрывает ставку соперника.
+    assert_eq!(h.balance(&game.player1.pubkey()), p1_before + STAKE);
+    assert_eq!(h.balance(&game.player2.pubkey()), p2_before);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.finish_reason, FinishReason::DoubleDropped);
+    assert_eq!(state.winner, game.player1.pubkey());
+    assert_eq!(state.cube_value, 1);
+    assert_eq!(state.pot_lamports, 0);
+}
+
+#[test]
+fn cube_owner_redoubles() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, options());
+    offer(&mut h, &game, &game.player1).unwrap();
+    take(&mut h, &game, &game.player2).unwrap();
+
+    // Куб у player2: player1 на ходу, но доступа к кубу у него нет.
+    assert_eq!(
+        offer(&mut h, &game, &game.player1),
+        Err(program_error(ErrorCode::NoCubeAccess))
+    );
+    h.make_move(&game).unwrap();
+    assert_eq!(h.game(&game.key).current_turn, 2);
+
+    offer(&mut h, &game, &game.player2).unwrap();
+    assert_eq!(h.game(&game.key).pot_lamports, 6 * STAKE);
+    take(&mut h, &game, &game.player1).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!((state.cube_value, state.cube_owner), (4, 1));
+    assert_escrow(&state);
+
+    // Теперь куб у player1, и player2 на ходу снова удвоить не может.
+    assert_eq!(
+        offer(&mut h, &game, &game.player2),
+        Err(program_error(ErrorCode::NoCubeAccess))
+    );
+}
+
+#[test]
+fn only_the_player_on_roll_may_double() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, options());
+    assert_eq!(
+        offer(&mut h, &game, &game.player2),
+        Err(program_error(ErrorCode::NotPlayersTurn))
+    );
+    assert_eq!(
+        take(&mut h, &game, &game.player2),
+        Err(program_error(ErrorCode::NoDoublePending))
+    );
+}
@@ -0,0 +1,195 @@
+//! Тающий банк в LiteSVM: init_game с options.decay_bps объявляет режим
+//! отдельным событием PotDecayEnabled, каждый ход срезает долю банка на
+//! incinerator (после update_config с decay_to_treasury — в treasury), а
+//! manual_refund делит игрокам остаток банка. Ходящий за срез не платит.
+
+use backgammon_client::events::{parse_events, GameEvent};
+use backgammon_client::{ix, GameOptions, GameState, Pubkey};
+use backgammon_tests::{options, program_error, Game, Harness, PLAYER_LAMPORTS};
+use pooler::{
+    pot_decay, ConfigUpdate, CreateCooldown, ErrorCode, FinishReason, OpenGames, INCINERATOR,
+    MAX_DECAY_BPS,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const STAKE: u64 = 100_000_000;
+/// 3% банка за ход.
+const DECAY_BPS: u16 = 300;
+const MOVES: u64 = 10;
+
+fn decaying(decay_bps: u16) -> GameOptions {
+    GameOptions {
+        decay_bps,
+        ..options()
+    }
+}
+
+fn create(h: &mut Harness) -> Game {
+    let (player1, player2) = (h.player(), h.player());
+    let game = Keypair::new();
+    let (result, logs) = h.send_with_logs(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            1,
+            STAKE,
+            0,
+            &player2.pubkey(),
+            decaying(DECAY_BPS),
+        ),
+        &[&game, &player1],
+    );
+    result.unwrap();
+
+    // Режим виден сразу при создании, до первого хода.
+    let enabled = parse_events(&logs)
+        .into_iter()
+        .find_map(|event| match event {
+            GameEvent::PotDecayEnabled(enabled) => Some(enabled),
+            _ => None,
+        })
+        .expect("PotDecayEnabled");
+    assert_eq!(enabled.game, game.pubkey());
+    assert_eq!(enabled.decay_bps, DECAY_BPS);
+    assert_eq!(h.game(&game.pubkey()).decay_bps, DECAY_BPS);
+    Game {
+        key: game.pubkey(),
+        player1,
+        player2,
+    }
+}
+
+/// MOVES ходов; каждый срез уходит на sink. Возвращает срезы по порядку.
+fn play(h: &mut Harness, game: &Game, sink: &Pubkey) -> Vec<u64> {
+    assert_eq!(h.config().decay_destination(), *sink);
+    let mut decays = Vec::new();
+    for _ in 0..MOVES {
+        let state = h.game(&game.key);
+        let mover = game.side(state.current_turn).pubkey();
+        let (mover_before, sink_before) = (h.balance(&mover), h.balance(sink));
+        h.make_move(game).unwrap();
+
+        // Доля банка перед ходом, округлённая вниз; ходящий ничего не платит.
+        let decay = state.pot_lamports * DECAY_BPS as u64 / 10_000;
+        assert_eq!(decay, pot_decay(state.pot_lamports, DECAY_BPS));
+        assert_eq!(h.balance(&mover), mover_before);
+        assert_eq!(h.balance(sink), sink_before + decay);
+        let after = h.game(&game.key);
+        assert_eq!(after.pot_lamports, state.pot_lamports - decay);
+        assert_eq!(after.decayed_lamports, state.decayed_lamports + decay);
+        decays.push(decay);
+    }
+    decays
+}
+
+#[test]
+fn pot_burns_every_move_and_refunds_split_the_rest() {
+    let mut h = Harness::new();
+    let game = create(&mut h);
+    h.join(&game).unwrap();
+    let decays = play(&mut h, &game, &INCINERATOR);
+
+    // 3% от 2 * STAKE, дальше от уменьшающегося банка.
+    assert_eq!(decays[0], 6_000_000);
+    assert_eq!(decays[1], 5_820_000);
+    assert!(
+        decays.windows(2).all(|pair| pair[1] < pair[0]),
+        "{:?}",
+        decays
+    );
+    let state = h.game(&game.key);
+    let decayed: u64 = decays.iter().sum();
+    assert_eq!(state.decayed_lamports, decayed);
+    assert_eq!(state.pot_lamports, 2 * STAKE - decayed);
+    assert_eq!(state.pot_lamports, 147_484_827);
+
+    h.send(
+        ix::manual_refund_ix(&game.key, &state, &h.config(), &game.player2.pubkey()),
+        &[&game.player2],
+    )
+    .unwrap();
+
+    // Равные ставки — срез пополам; игроки получают ровно остаток банка.
+    let game_rent = h.rent(8 + GameState::MAX_SIZE);
+    let pda_rent = h.rent(8 + OpenGames::MAX_SIZE) + h.rent(8 + CreateCooldown::MAX_SIZE);
+    let share = decayed / 2;
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        PLAYER_LAMPORTS - game_rent - pda_rent - (decayed - share)
+    );
+    assert_eq!(h.balance(&game.player2.pubkey()), PLAYER_LAMPORTS - share);
+    assert_eq!(h.balance(&game.key), game_rent);
+    assert_eq!(h.game(&game.key).finish_reason, FinishReason::ManualRefund);
+}
+
+#[test]
+fn decay_can_go_to_the_treasury() {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let treasury = h.player();
+    let update = ConfigUpdate {
+        treasury: Some(treasury.pubkey()),
+        decay_to_treasury: Some(true),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+
+    let game = create(&mut h);
+    h.join(&game).unwrap();
+    let incinerated = h.balance(&INCINERATOR);
+    let decays = play(&mut h, &game, &treasury.pubkey());
+    assert_eq!(
+        h.balance(&treasury.pubkey()),
+        PLAYER_LAMPORTS + decays.iter().sum::<u64>()
+    );
+    assert_eq!(h.balance(&INCINERATOR), incinerated);
+}
+
+#[test]
+fn decay_excludes_move_fees() {
+    let mut h = Harness::new();
+    let (player1, player2) = (h.player(), h.player());
+    let game = Keypair::new();
+    assert_eq!(
+        h.send(
+            ix::init_game_ix(
+                &game.pubkey(),
+                &player1.pubkey(),
+                1,
+                STAKE,
+                1_000,
+                &player2.pubkey(),
+                decaying(DECAY_BPS),
+            ),
+            &[&game, &player1]
+        ),
+        Err(program_error(ErrorCode::ConflictingMoveFee))
+    );
+    assert_eq!(
+        h.send(
+            ix::init_game_ix(
+                &game.pubkey(),
+                &player1.pubkey(),
+                1,
+                STAKE,
+                0,
+                &player2.pubkey(),
+                decaying(MAX_DECAY_BPS + 1),
+            ),
+            &[&game, &player1]
+        ),
+        Err(program_error(ErrorCode::InvalidDecayBps))
+    );
+
+    // Встречное предложение не может вернуть фиксированную комиссию.
+    let game = create(&mut h);
+    assert_eq!(
+        h.send(
+            ix::counter_offer_ix(&game.key, &game.player2.pubkey(), STAKE / 2, 1_000),
+            &[&game.player2]
+        ),
+        Err(program_error(ErrorCode::ConflictingMoveFee))
+    );
+}
@@ -0,0 +1,192 @@
+//! События полного цикла игры в LiteSVM, разобранные SDK: GameInitialized,
+//! PlayerJoined и MoveMade из логов, GameFinished, GameCancelled и
+//! GameRefunded из внутренних инструкций emit_cpi!. Каждая транзакция
+//! порождает ровно ожидаемые события с полями, совпадающими с состоянием.
+
+use backgammon_client::events::GameEvent;
+use backgammon_client::{ix, GameOptions};
+use backgammon_tests::{Game, Harness};
+use pooler::{
+    start_board, FinishReason, GameCancelled, GameFinished, GameInitialized, GameRefunded,
+    PlayerJoined, RefundReason, RuleVariant,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const STAKE: u64 = 100_000_000;
+const MOVE_FEE: u64 = 1_000_000;
+const MOVES: u64 = 4;
+
+fn create(h: &mut Harness) -> Game {
+    let (player1, player2) = (h.player(), h.player());
+    let game = Keypair::new();
+    let options = GameOptions {
+        initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+        ..GameOptions::default()
+    };
+    let events = h
+        .send_with_events(
+            ix::init_game_ix(
+                &game.pubkey(),
+                &player1.pubkey(),
+                7,
+                STAKE,
+                MOVE_FEE,
+                &player2.pubkey(),
+                options,
+            ),
+            &[&game, &player1],
+        )
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![GameEvent::GameInitialized(GameInitialized {
+            game: game.pubkey(),
+            game_id: 7,
+            player1: player1.pubkey(),
+            player2: player2.pubkey(),
+            stake_lamports: STAKE,
+            move_fee_lamports: MOVE_FEE,
+            practice: false,
+        })]
+    );
+    Game {
+        key: game.pubkey(),
+        player1,
+        player2,
+    }
+}
+
+fn join(h: &mut Harness, game: &Game) {
+    let state = h.game(&game.key);
+    let events = h
+        .send_with_events(
+            ix::join_game_ix(&game.key, &state, &game.player2.pubkey(), Vec::new(), None),
+            &[&game.player2],
+        )
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![GameEvent::PlayerJoined(PlayerJoined {
+            game: game.key,
+            player: game.player2.pubkey(),
+            stake_lamports: STAKE,
+            pot_lamports: 2 * STAKE,
+            bot: false,
+        })]
+    );
+}
+
+/// MOVES ходов; каждый порождает одно MoveMade с номером после хода.
+fn play(h: &mut Harness, game: &Game) {
+    let config = h.config();
+    for _ in 0..MOVES {
+        let state = h.game(&game.key);
+        let mover = if state.current_turn == 1 {
+            &game.player1
+        } else {
+            &game.player2
+        };
+        let mut annotation = [0; 32];
+        annotation[..8].copy_from_slice(&state.move_index.to_le_bytes());
+        let events = h
+            .send_with_events(
+                ix::make_move_ix(
+                    &game.key,
+                    &state,
+                    &config,
+                    state.board_points().unwrap(),
+                    [3, 1],
+                    annotation,
+                    None,
+                ),
+                &[mover],
+            )
+            .unwrap();
+        let [GameEvent::MoveMade(made)] = &events[..] else {
+            panic!("expected one MoveMade, got {events:?}");
+        };
+        assert_eq!(made.game, game.key);
+        assert_eq!(made.player, mover.pubkey());
+        assert_eq!(made.move_index, state.move_index + 1);
+        assert_eq!(made.board_points, state.board_points().unwrap());
+        assert_eq!(made.dice, [3, 1]);
+        assert_eq!(made.fee_lamports, MOVE_FEE);
+        assert_eq!(made.client, None);
+    }
+}
+
+#[test]
+fn finished_game_emits_every_event() {
+    let mut h = Harness::new();
+    let game = create(&mut h);
+    join(&mut h, &game);
+    play(&mut h, &game);
+
+    let state = h.game(&game.key);
+    let config = h.config();
+    let events = h
+        .send_with_events(
+            ix::finish_game_ix(&game.key, &state, &config, &game.player2.pubkey(), 1),
+            &[&game.player1, &game.player2],
+        )
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![GameEvent::GameFinished(GameFinished {
+            game: game.key,
+            winner: game.player2.pubkey(),
+            pot_paid: 2 * STAKE + MOVES * MOVE_FEE,
+            fee_lamports: MOVES * MOVE_FEE,
+            reason: FinishReason::Win,
+        })]
+    );
+}
+
+#[test]
+fn cancelled_game_emits_game_cancelled() {
+    let mut h = Harness::new();
+    let game = create(&mut h);
+    let state = h.game(&game.key);
+    let events = h
+        .send_with_events(
+            ix::cancel_before_join_ix(&game.key, &state),
+            &[&game.player1],
+        )
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![GameEvent::GameCancelled(GameCancelled {
+            game: game.key,
+            player1: game.player1.pubkey(),
+            refunded: STAKE,
+        })]
+    );
+}
+
+#[test]
+fn refunded_game_emits_game_refunded() {
+    let mut h = Harness::new();
+    let game = create(&mut h);
+    join(&mut h, &game);
+    play(&mut h, &game);
+
+    let state = h.game(&game.key);
+    let events = h
+        .send_with_events(
+            ix::manual_refund_ix(&game.key, &state, &h.config(), &game.player2.pubkey()),
+            &[&game.player2],
+        )
+        .unwrap();
+    let fees = MOVES / 2 * MOVE_FEE;
+    assert_eq!(
+        events,
+        vec![GameEvent::GameRefunded(GameRefunded {
+            game: game.key,
+            player1_amount: STAKE + fees,
+            player2_amount: STAKE + fees,
+            reason: RefundReason::Manual,
+            finish_reason: FinishReason::ManualRefund,
+        })]
+    );
+}
@@ -0,0 +1,100 @@
+//! finalize_from_board в LiteSVM: по терминальной доске банк получает
+//! победитель, а инструкцию подписывает посторонний плательщик (крэнк);
+//! нетерминальная доска отклоняется с BoardNotTerminal.
+
+use backgammon_client::{ix, pda};
+use backgammon_tests::{options, program_error, Game, Harness};
+use pooler::{accounts, instruction, ErrorCode, FinishReason, GameStatus};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 50_000_000;
+
+fn finalize(h: &mut Harness, game: &Game, crank: &Keypair) -> Result<(), TransactionError> {
+    let state = h.game(&game.key);
+    h.send(
+        ix::build(
+            accounts::FinalizeFromBoard {
+                game: game.key,
+                player1: state.player1,
+                player2: state.player2,
+                config: pda::config(),
+                season: None,
+                player1_stats: None,
+                player2_stats: None,
+                bot_vault: None,
+                team: None,
+                payer: crank.pubkey(),
+                event_buffer: None,
+                fee_vault: None,
+                incinerator: None,
+                player1_splits: pda::payout_splits(&state.player1),
+                player2_splits: pda::payout_splits(&state.player2),
+                event_authority: pda::event_authority(),
+                program: pooler::ID,
+            },
+            instruction::FinalizeFromBoard {},
+        ),
+        &[crank],
+    )
+}
+
+/// Доводит доску до конца партии в пользу winner_side и рассчитывает её.
+fn finalize_won_by(winner_side: u8) {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, options());
+    let mut state = h.game(&game.key);
+    assert!(state.board_from_start);
+    // У победителя на доске не осталось шашек.
+    let mut board = [0; 24];
+    if winner_side == 1 {
+        board[0] = -2;
+    } else {
+        board[23] = 2;
+    }
+    state.set_board_points(&board).unwrap();
+    h.set_game(&game.key, &state);
+
+    let (winner, loser) = if winner_side == 1 {
+        (&game.player1, &game.player2)
+    } else {
+        (&game.player2, &game.player1)
+    };
+    let (winner_before, loser_before) = (h.balance(&winner.pubkey()), h.balance(&loser.pubkey()));
+    let crank = h.player();
+    finalize(&mut h, &game, &crank).unwrap();
+
+    assert_eq!(
+        h.balance(&winner.pubkey()),
+        winner_before + state.pot_lamports
+    );
+    assert_eq!(h.balance(&loser.pubkey()), loser_before);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.finish_reason, FinishReason::Board);
+    assert_eq!(state.winner, winner.pubkey());
+    assert_eq!(state.pot_lamports, 0);
+}
+
+#[test]
+fn terminal_board_pays_player1() {
+    finalize_won_by(1);
+}
+
+#[test]
+fn terminal_board_pays_player2() {
+    finalize_won_by(2);
+}
+
+#[test]
+fn non_terminal_board_is_rejected() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, options());
+    let crank = h.player();
+    assert_eq!(
+        finalize(&mut h, &game, &crank),
+        Err(program_error(ErrorCode::BoardNotTerminal))
+    );
+    assert_eq!(h.game(&game.key).status, GameStatus::Active);
+}
@@ -0,0 +1,90 @@
+//! Бесплатное окно в LiteSVM: игра с options.free_moves не платит за первые
+//! ходы, даже если config.free_moves больше, а первый ход после окна стоит
+//! ровно move_fee_lamports и поднимает банк.
+
+use backgammon_client::{ix, GameOptions, Pubkey};
+use backgammon_tests::Harness;
+use pooler::ConfigUpdate;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const STAKE: u64 = 100_000_000;
+const MOVE_FEE: u64 = 1_000_000;
+const FREE_MOVES: u16 = 3;
+
+#[test]
+fn first_paid_move_after_the_game_window_charges_the_base_fee() {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let update = ConfigUpdate {
+        free_moves: Some(10),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+
+    let (player1, player2) = (h.player(), h.player());
+    let game = Keypair::new();
+    let options = GameOptions {
+        free_moves: Some(FREE_MOVES),
+        ..GameOptions::default()
+    };
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            1,
+            STAKE,
+            MOVE_FEE,
+            &player2.pubkey(),
+            options,
+        ),
+        &[&game, &player1],
+    )
+    .unwrap();
+    let key: Pubkey = game.pubkey();
+    let state = h.game(&key);
+    assert!(state.own_free_moves);
+    assert_eq!(state.free_moves, FREE_MOVES);
+    h.send(
+        ix::join_game_ix(&key, &state, &player2.pubkey(), Vec::new(), None),
+        &[&player2],
+    )
+    .unwrap();
+
+    let config = h.config();
+    let mut fees = Vec::new();
+    for _ in 0..FREE_MOVES + 2 {
+        let state = h.game(&key);
+        let mover = if state.current_turn == 1 {
+            &player1
+        } else {
+            &player2
+        };
+        let before = h.balance(&mover.pubkey());
+        let mut annotation = [0; 32];
+        annotation[..8].copy_from_slice(&state.move_index.to_le_bytes());
+        h.send(
+            ix::make_move_ix(
+                &key,
+                &state,
+                &config,
+                state.board_points().unwrap(),
+                [3, 1],
+                annotation,
+                None,
+            ),
+            &[mover],
+        )
+        .unwrap();
+        let fee = before - h.balance(&mover.pubkey());
+        assert_eq!(h.game(&key).pot_lamports, state.pot_lamports + fee);
+        fees.push(fee);
+    }
+    assert_eq!(fees, [0, 0, 0, MOVE_FEE, MOVE_FEE]);
+    let state = h.game(&key);
+    assert_eq!(
+        state.player1_fees_paid + state.player2_fees_paid,
+        2 * MOVE_FEE
+    );
+}
@@ -0,0 +1,113 @@
+//! Автоматические game_id в LiteSVM: init_game с game_id = 0 берёт id из
+//! счётчика создателя (PDA GameCounter), id разных игр не совпадают, а явные
+//! id не заходят в пространство выданных.
+//!
+//! Игры создаются подряд, как пачка параллельных транзакций одного клиента:
+//! счётчик — записываемый аккаунт, и рантайм всё равно выполняет их по одной.
+
+use backgammon_client::events::{parse_events, GameEvent};
+use backgammon_client::{ix, pda, AccountMeta, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{ErrorCode, GameCounter, AUTO_GAME_ID_FLAG};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+use std::collections::HashSet;
+
+const STAKE: u64 = 100_000_000;
+const GAMES: u64 = 5;
+
+/// init_game с game_id; возвращает ключ игры и id из события GameInitialized.
+fn create(
+    h: &mut Harness,
+    player1: &Keypair,
+    game_id: u64,
+) -> Result<(Pubkey, u64), TransactionError> {
+    let game = Keypair::new();
+    let (result, logs) = h.send_with_logs(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            game_id,
+            STAKE,
+            0,
+            &Pubkey::new_unique(),
+            GameOptions::default(),
+        ),
+        &[&game, player1],
+    );
+    result?;
+    let event_id = parse_events(&logs)
+        .into_iter()
+        .find_map(|event| match event {
+            GameEvent::GameInitialized(init) => Some(init.game_id),
+            _ => None,
+        })
+        .expect("GameInitialized");
+    assert_eq!(h.game(&game.pubkey()).game_id, event_id);
+    Ok((game.pubkey(), event_id))
+}
+
+#[test]
+fn auto_ids_are_unique_per_creator() {
+    let mut h = Harness::new();
+    let (alice, bob) = (h.player(), h.player());
+
+    let mut ids = HashSet::new();
+    for expected in 0..GAMES {
+        let (_, id) = create(&mut h, &alice, 0).unwrap();
+        assert_eq!(id, AUTO_GAME_ID_FLAG | expected);
+        assert!(ids.insert(id));
+    }
+    let counter: GameCounter = h.account(&pda::game_counter(&alice.pubkey()));
+    assert_eq!(counter.next_id, GAMES);
+
+    // У другого создателя свой счётчик.
+    let (_, id) = create(&mut h, &bob, 0).unwrap();
+    assert_eq!(id, AUTO_GAME_ID_FLAG);
+    let counter: GameCounter = h.account(&pda::game_counter(&bob.pubkey()));
+    assert_eq!(counter.next_id, 1);
+}
+
+#[test]
+fn explicit_ids_stay_out_of_the_auto_range() {
+    let mut h = Harness::new();
+    let alice = h.player();
+
+    // Явный id по-прежнему работает и счётчик не трогает.
+    let (_, id) = create(&mut h, &alice, 7).unwrap();
+    assert_eq!(id, 7);
+    assert_eq!(h.balance(&pda::game_counter(&alice.pubkey())), 0);
+
+    // Id из пространства счётчика задать явно нельзя.
+    let (_, auto) = create(&mut h, &alice, 0).unwrap();
+    assert_eq!(
+        create(&mut h, &alice, auto).err(),
+        Some(program_error(ErrorCode::ReservedGameId))
+    );
+    assert_eq!(
+        create(&mut h, &alice, AUTO_GAME_ID_FLAG | 1).err(),
+        Some(program_error(ErrorCode::ReservedGameId))
+    );
+    assert_eq!(create(&mut h, &alice, 0).unwrap().1, AUTO_GAME_ID_FLAG | 1);
+
+    // Без аккаунта счётчика game_id = 0 отклоняется.
+    let game = Keypair::new();
+    let mut init = ix::init_game_ix(
+        &game.pubkey(),
+        &alice.pubkey(),
+        0,
+        STAKE,
+        0,
+        &Pubkey::new_unique(),
+        GameOptions::default(),
+    );
+    let counter = pda::game_counter(&alice.pubkey());
+    for meta in init.accounts.iter_mut().filter(|m| m.pubkey == counter) {
+        *meta = AccountMeta::new_readonly(pooler::ID, false);
+    }
+    assert_eq!(
+        h.send(init, &[&game, &alice]),
+        Err(program_error(ErrorCode::MissingGameCounter))
+    );
+}
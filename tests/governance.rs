@@ -0,0 +1,148 @@
+//! Администратор — PDA программы управления: права передаются через
+//! propose_admin / accept_admin, админское действие выполняется через CPI
+//! из cpi_caller (макет мультисига или SPL Governance).
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use backgammon_client::{ix, pda, Instruction, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{ConfigUpdate, ErrorCode};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+/// PDA управления в cpi_caller для authority.
+fn governance(authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"governance", authority.as_ref()], &cpi_caller::ID).0
+}
+
+fn govern(authority: &Pubkey, args: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: cpi_caller::ID,
+        accounts: cpi_caller::accounts::Govern {
+            governance: governance(authority),
+            authority: *authority,
+            config: pda::config(),
+            backgammon_program: pooler::ID,
+        }
+        .to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+fn pause_update(paused: bool) -> ConfigUpdate {
+    ConfigUpdate {
+        paused: Some(paused),
+        ..ConfigUpdate::default()
+    }
+}
+
+#[test]
+fn handover_to_keypair() {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let new_admin = h.player();
+
+    h.send(
+        ix::propose_admin_ix(&admin.pubkey(), &new_admin.pubkey()),
+        &[&admin],
+    )
+    .unwrap();
+    h.send(ix::accept_admin_ix(&new_admin.pubkey()), &[&new_admin])
+        .unwrap();
+    let config = h.config();
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, Pubkey::default());
+
+    h.send(
+        ix::update_config_ix(&new_admin.pubkey(), pause_update(true)),
+        &[&new_admin],
+    )
+    .unwrap();
+    assert!(h.config().paused);
+    assert!(h
+        .send(
+            ix::update_config_ix(&admin.pubkey(), pause_update(false)),
+            &[&admin]
+        )
+        .is_err());
+}
+
+#[test]
+fn unaccepted_proposal_keeps_admin() {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    // Адрес, которым никто не управляет: права на него не перейдут.
+    let typo = Pubkey::new_unique();
+    h.send(ix::propose_admin_ix(&admin.pubkey(), &typo), &[&admin])
+        .unwrap();
+    assert_eq!(h.config().pending_admin, typo);
+
+    let stranger = h.player();
+    assert_eq!(
+        h.send(ix::accept_admin_ix(&stranger.pubkey()), &[&stranger])
+            .unwrap_err(),
+        program_error(ErrorCode::NotPendingAdmin)
+    );
+    let config = h.config();
+    assert_eq!(config.admin, admin.pubkey());
+
+    // Админ по-прежнему управляет конфигом и может исправить предложение.
+    h.send(
+        ix::update_config_ix(&admin.pubkey(), pause_update(true)),
+        &[&admin],
+    )
+    .unwrap();
+    h.send(
+        ix::propose_admin_ix(&admin.pubkey(), &Pubkey::default()),
+        &[&admin],
+    )
+    .unwrap();
+    assert_eq!(
+        h.send(ix::accept_admin_ix(&stranger.pubkey()), &[&stranger])
+            .unwrap_err(),
+        program_error(ErrorCode::NoPendingAdmin)
+    );
+}
+
+#[test]
+fn governance_pda_administers_through_cpi() {
+    let mut h = Harness::new();
+    h.add_cpi_caller();
+    let admin = h.admin.insecure_clone();
+    let authority: Keypair = h.player();
+    let governance = governance(&authority.pubkey());
+
+    h.send(
+        ix::propose_admin_ix(&admin.pubkey(), &governance),
+        &[&admin],
+    )
+    .unwrap();
+    h.send(
+        govern(
+            &authority.pubkey(),
+            cpi_caller::instruction::GovernAcceptAdmin {},
+        ),
+        &[&authority],
+    )
+    .unwrap();
+    assert_eq!(h.config().admin, governance);
+
+    h.send(
+        govern(
+            &authority.pubkey(),
+            cpi_caller::instruction::GovernUpdateConfig {
+                update: pause_update(true),
+            },
+        ),
+        &[&authority],
+    )
+    .unwrap();
+    assert!(h.config().paused);
+
+    // Прежний ключ администратора больше ничего не меняет.
+    assert!(h
+        .send(
+            ix::update_config_ix(&admin.pubkey(), pause_update(false)),
+            &[&admin]
+        )
+        .is_err());
+}
@@ -0,0 +1,314 @@
+//! История ходов в LiteSVM: init_game с options.move_history создаёт чанк 0
+//! GameHistory за счёт player1, законные ходы с комиссиями записываются в
+//! него, и переигрывание истории даёт итоговую доску игры. Заполненный чанк
+//! останавливает ходы, пока игрок не создаст следующий rotate_history_chunk.
+//! После завершения close_game_history возвращает ренту плательщикам чанков.
+
+use anchor_lang::Space;
+use backgammon_client::{ix, pda, AccountMeta, GameOptions, GameState, GameStatus, Pubkey};
+use backgammon_tests::{program_error, Game, Harness, PLAYER_LAMPORTS};
+use pooler::{
+    board_hash, start_board, unpack_board, CreateCooldown, ErrorCode, GameHistory, MoveRecord,
+    OpenGames, ReplayPosition, RuleVariant, MAX_HISTORY_MOVES,
+};
+use solana_signer::Signer;
+
+const STAKE: u64 = 100_000_000;
+const MOVE_FEE: u64 = 1_000_000;
+const MOVES: u64 = 20;
+
+/// Позиция по правилам после сыгранных ходов и состояние ГПСЧ кубиков.
+struct Playback {
+    position: backgammon_core::Position,
+    seed: u64,
+}
+
+fn create(h: &mut Harness) -> (Game, Playback) {
+    let options = GameOptions {
+        initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+        move_history: true,
+        ..GameOptions::default()
+    };
+    let game = h.create_game(STAKE, MOVE_FEE, options).unwrap();
+
+    // Ренту истории платит player1 вместе с рентой игры.
+    let rent = h.rent(8 + GameState::MAX_SIZE)
+        + h.rent(8 + OpenGames::MAX_SIZE)
+        + h.rent(8 + CreateCooldown::MAX_SIZE)
+        + h.rent(8 + GameHistory::INIT_SPACE);
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        PLAYER_LAMPORTS - STAKE - rent
+    );
+    let history: GameHistory = h.account(&pda::game_history(&game.key, 0));
+    assert_eq!(history.game, game.key);
+    assert_eq!(history.payer, game.player1.pubkey());
+    assert_eq!(history.chunk_index, 0);
+    assert_eq!(history.prev_chunk, Pubkey::default());
+    assert!(history.moves.is_empty());
+    assert!(h.game(&game.key).move_history);
+
+    h.join(&game).unwrap();
+    let playback = Playback {
+        position: ReplayPosition::start(RuleVariant::Standard, 0)
+            .unwrap()
+            .into(),
+        seed: 11,
+    };
+    (game, playback)
+}
+
+/// count законных ходов с кубиками из простого ГПСЧ; возвращает слоты ходов.
+/// Из законных ходов берётся тот, что дальше всего отбрасывает соперника
+/// (бой), — так партия не кончается раньше нужного числа ходов.
+fn play(h: &mut Harness, game: &Game, playback: &mut Playback, count: u64) -> Vec<u64> {
+    let config = h.config();
+    let mut slots = Vec::new();
+    for _ in 0..count {
+        playback.seed = playback
+            .seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        let dice = [
+            (playback.seed >> 33) as u8 % 6 + 1,
+            (playback.seed >> 45) as u8 % 6 + 1,
+        ];
+        let state = h.game(&game.key);
+        let side = state.current_turn;
+        playback.position = backgammon_core::legal_moves(&playback.position, side, dice)
+            .into_iter()
+            .max_by_key(|position| position.pip_count(3 - side))
+            .unwrap();
+
+        h.warp(1);
+        slots.push(h.slot());
+        let mut annotation = [0; 32];
+        annotation[..8].copy_from_slice(&state.move_index.to_le_bytes());
+        h.send(
+            ix::make_move_ix(
+                &game.key,
+                &state,
+                &config,
+                playback.position.board,
+                dice,
+                annotation,
+                None,
+            ),
+            &[game.side(state.current_turn)],
+        )
+        .unwrap();
+    }
+    slots
+}
+
+/// Переигрывает записи по правилам со стартовой позиции: каждая — законный
+/// ход своей стороны со своим слотом и комиссией; итог совпадает с доской игры.
+fn replay(state: &GameState, moves: &[MoveRecord], slots: &[u64]) {
+    assert_eq!(moves.len(), slots.len());
+    let mut position = ReplayPosition::start(RuleVariant::Standard, 0).unwrap();
+    for (i, record) in moves.iter().enumerate() {
+        let side = 1 + (i % 2) as u8;
+        assert_eq!(record.move_index, i as u64 + 1);
+        assert_eq!(record.mover, state.player_key(side));
+        assert_eq!(record.slot, slots[i]);
+        assert_eq!(record.fee_lamports, MOVE_FEE);
+        let board = unpack_board(&record.board).unwrap();
+        position = position
+            .play_move(side, record.dice, &board)
+            .unwrap_or_else(|| panic!("move {} is not legal", record.move_index));
+    }
+    assert_eq!(
+        board_hash(&position.board),
+        board_hash(&state.board_points().unwrap())
+    );
+}
+
+#[test]
+fn history_replays_a_twenty_move_game() {
+    let mut h = Harness::new();
+    let (game, mut playback) = create(&mut h);
+    let slots = play(&mut h, &game, &mut playback, MOVES);
+
+    let state = h.game(&game.key);
+    let history: GameHistory = h.account(&pda::game_history(&game.key, 0));
+    assert_eq!(state.move_index, MOVES);
+    assert_eq!(state.current_history_chunk, 0);
+    replay(&state, &history.moves, &slots);
+}
+
+#[test]
+fn full_chunk_rotates_into_the_next() {
+    let mut h = Harness::new();
+    let (game, mut playback) = create(&mut h);
+    let first = pda::game_history(&game.key, 0);
+
+    // Пока чанк не заполнен, новый не создаётся.
+    let state = h.game(&game.key);
+    assert_eq!(
+        h.send(
+            ix::rotate_history_chunk_ix(&game.key, &state, &game.player2.pubkey()),
+            &[&game.player2]
+        ),
+        Err(program_error(ErrorCode::HistoryNotFull))
+    );
+
+    let mut slots = play(&mut h, &game, &mut playback, MAX_HISTORY_MOVES as u64);
+    let history: GameHistory = h.account(&first);
+    assert!(history.is_full());
+
+    // Ход в заполненный чанк не проходит и ничего не теряет.
+    let (state, config) = (h.game(&game.key), h.config());
+    assert_eq!(
+        h.send(
+            ix::make_move_ix(
+                &game.key,
+                &state,
+                &config,
+                state.board_points().unwrap(),
+                [3, 1],
+                [0; 32],
+                None,
+            ),
+            &[game.side(state.current_turn)]
+        ),
+        Err(program_error(ErrorCode::HistoryFull))
+    );
+    assert_eq!(h.game(&game.key).move_index, MAX_HISTORY_MOVES as u64);
+
+    // Следующий чанк создаёт только игрок; ренту платит он же.
+    let outsider = h.player();
+    assert_eq!(
+        h.send(
+            ix::rotate_history_chunk_ix(&game.key, &state, &outsider.pubkey()),
+            &[&outsider]
+        ),
+        Err(program_error(ErrorCode::InvalidPlayer))
+    );
+    let chunk_rent = h.rent(8 + GameHistory::INIT_SPACE);
+    let player2_before = h.balance(&game.player2.pubkey());
+    h.send(
+        ix::rotate_history_chunk_ix(&game.key, &state, &game.player2.pubkey()),
+        &[&game.player2],
+    )
+    .unwrap();
+    assert_eq!(
+        h.balance(&game.player2.pubkey()),
+        player2_before - chunk_rent
+    );
+    let second = pda::game_history(&game.key, 1);
+    let next: GameHistory = h.account(&second);
+    assert_eq!(next.game, game.key);
+    assert_eq!(next.payer, game.player2.pubkey());
+    assert_eq!(next.chunk_index, 1);
+    assert_eq!(next.prev_chunk, first);
+    assert_eq!(h.game(&game.key).current_history_chunk, 1);
+
+    slots.extend(play(&mut h, &game, &mut playback, MOVES));
+    let state = h.game(&game.key);
+    assert_eq!(state.move_index, MAX_HISTORY_MOVES as u64 + MOVES);
+    let (history, next): (GameHistory, GameHistory) = (h.account(&first), h.account(&second));
+    assert_eq!(next.moves.len() as u64, MOVES);
+    let moves: Vec<MoveRecord> = history.moves.into_iter().chain(next.moves).collect();
+    replay(&state, &moves, &slots);
+
+    // Чанки закрываются с последнего, рента — их плательщикам.
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &game.player1.pubkey(), 1),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+    let closer = h.player();
+    let state = h.game(&game.key);
+    let player2_before = h.balance(&game.player2.pubkey());
+    h.send(
+        ix::close_game_history_ix(&game.key, &state, &game.player2.pubkey()),
+        &[&closer],
+    )
+    .unwrap();
+    assert_eq!(h.balance(&second), 0);
+    assert_eq!(
+        h.balance(&game.player2.pubkey()),
+        player2_before + chunk_rent
+    );
+    let state = h.game(&game.key);
+    assert_eq!(state.current_history_chunk, 0);
+    assert!(state.move_history);
+
+    let player1_before = h.balance(&game.player1.pubkey());
+    h.send(
+        ix::close_game_history_ix(&game.key, &state, &game.player1.pubkey()),
+        &[&closer],
+    )
+    .unwrap();
+    assert_eq!(h.balance(&first), 0);
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        player1_before + chunk_rent
+    );
+    assert!(!h.game(&game.key).move_history);
+}
+
+#[test]
+fn moves_need_the_history_and_close_returns_its_rent() {
+    let mut h = Harness::new();
+    let (game, mut playback) = create(&mut h);
+
+    // Ход без истории не проходит.
+    let (state, config) = (h.game(&game.key), h.config());
+    let mut make_move = ix::make_move_ix(
+        &game.key,
+        &state,
+        &config,
+        state.board_points().unwrap(),
+        [3, 1],
+        [0; 32],
+        None,
+    );
+    let history = pda::game_history(&game.key, 0);
+    for meta in make_move
+        .accounts
+        .iter_mut()
+        .filter(|m| m.pubkey == history)
+    {
+        *meta = AccountMeta::new_readonly(pooler::ID, false);
+    }
+    assert_eq!(
+        h.send(make_move, &[&game.player1]),
+        Err(program_error(ErrorCode::MissingGameHistory))
+    );
+
+    // Закрыть историю можно только после завершения игры.
+    let closer = h.player();
+    assert_eq!(
+        h.send(
+            ix::close_game_history_ix(&game.key, &state, &game.player1.pubkey()),
+            &[&closer]
+        ),
+        Err(program_error(ErrorCode::GameNotFinished))
+    );
+
+    play(&mut h, &game, &mut playback, MOVES);
+    let (state, config) = (h.game(&game.key), h.config());
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &game.player1.pubkey(), 1),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+
+    let history_rent = h.balance(&history);
+    assert_eq!(history_rent, h.rent(8 + GameHistory::INIT_SPACE));
+    let player1_before = h.balance(&game.player1.pubkey());
+    h.send(
+        ix::close_game_history_ix(&game.key, &state, &game.player1.pubkey()),
+        &[&closer],
+    )
+    .unwrap();
+    assert_eq!(h.balance(&history), 0);
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        player1_before + history_rent
+    );
+    assert!(!h.game(&game.key).move_history);
+}
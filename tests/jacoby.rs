@@ -0,0 +1,85 @@
+//! Правило Джекоби в LiteSVM: марс при неповёрнутом кубе под Джекоби
+//! оплачивается как обычная победа, без Джекоби проигравший доплачивает
+//! ставку, а после принятого удвоения марс считается и под Джекоби.
+
+use backgammon_client::{ix, pda, GameOptions};
+use backgammon_tests::{options, Game, Harness};
+use pooler::{accounts, instruction};
+use solana_signer::Signer;
+
+const STAKE: u64 = 10_000_000;
+/// Множитель марса в finish_game.
+const GAMMON: u8 = 2;
+
+fn jacoby(jacoby: bool) -> GameOptions {
+    GameOptions {
+        jacoby,
+        ..options()
+    }
+}
+
+/// player1 выигрывает марсом; возвращает изменение балансов обоих игроков.
+fn gammon_for_player1(h: &mut Harness, game: &Game) -> (i128, i128) {
+    let (p1_before, p2_before) = (
+        h.balance(&game.player1.pubkey()),
+        h.balance(&game.player2.pubkey()),
+    );
+    let (state, config) = (h.game(&game.key), h.config());
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &game.player1.pubkey(), GAMMON),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+    (
+        h.balance(&game.player1.pubkey()) as i128 - p1_before as i128,
+        h.balance(&game.player2.pubkey()) as i128 - p2_before as i128,
+    )
+}
+
+#[test]
+fn jacoby_gammon_with_an_untouched_cube_pays_single() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, jacoby(true));
+    assert_eq!(gammon_for_player1(&mut h, &game), (2 * STAKE as i128, 0));
+}
+
+#[test]
+fn gammon_without_jacoby_pays_double() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, jacoby(false));
+    assert_eq!(
+        gammon_for_player1(&mut h, &game),
+        (3 * STAKE as i128, -(STAKE as i128))
+    );
+}
+
+#[test]
+fn jacoby_gammon_counts_once_the_cube_is_turned() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, jacoby(true));
+    h.send(
+        ix::offer_double_ix(&game.key, &game.player1.pubkey()),
+        &[&game.player1],
+    )
+    .unwrap();
+    h.send(
+        ix::build(
+            accounts::TakeDouble {
+                game: game.key,
+                player: game.player2.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+                config: pda::config(),
+            },
+            instruction::TakeDouble {},
+        ),
+        &[&game.player2],
+    )
+    .unwrap();
+    assert_eq!(h.game(&game.key).cube_value, 2);
+
+    // Банк 4 ставки и надбавка stake × cube за марс.
+    assert_eq!(
+        gammon_for_player1(&mut h, &game),
+        (6 * STAKE as i128, -2 * STAKE as i128)
+    );
+}
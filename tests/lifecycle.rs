@@ -0,0 +1,603 @@
+//! Жизненный цикл игры в LiteSVM: создание, вход, дюжина ходов с
+//! комиссиями, победа каждой стороны, отмена до входа, ручной возврат и
+//! force_refund после перевода часов за тайм-аут, комиссия протокола с
+//! двух игр и её вывод в казну, сжигание доли комиссии, комиссия при отказе
+//! от удвоения и по неоспоренной заявке на победу, закрытие старых игр через
+//! prune_games, обмен сторонами до первого хода.
+//!
+//! Балансы сверяются до lamport, рента аккаунтов считается по их размеру.
+
+use anchor_lang::Space;
+use backgammon_client::events::{parse_events, GameEvent};
+use backgammon_client::{ix, pda, GameOptions, GameState, GameStatus, Pubkey};
+use backgammon_tests::{program_error, Game, Harness, PLAYER_LAMPORTS};
+use pooler::{
+    burn_share, protocol_fee, prune_reward, start_board, ConfigUpdate, ContextValue,
+    CreateCooldown, ErrorCode, ErrorContext, FeeVault, FinishReason, GamesPruned, OpenGames,
+    RuleVariant, WinClaim, CLAIM_CONTEST_WINDOW_SLOTS, DEFAULT_PRUNE_AFTER_SLOTS,
+    FORCE_REFUND_TIMEOUT_SLOTS, INCINERATOR, WIN_CLAIM_BOND_LAMPORTS,
+};
+use solana_signer::Signer;
+
+const STAKE: u64 = 100_000_000;
+const MOVE_FEE: u64 = 1_000_000;
+const MOVES: u64 = 12;
+
+/// Рента аккаунта игры и двух PDA, которые создаёт первая игра игрока.
+fn init_rent(h: &Harness) -> (u64, u64) {
+    let game = h.rent(8 + GameState::MAX_SIZE);
+    let pdas = h.rent(8 + OpenGames::MAX_SIZE) + h.rent(8 + CreateCooldown::MAX_SIZE);
+    (game, pdas)
+}
+
+fn create(h: &mut Harness) -> Game {
+    let options = GameOptions {
+        initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+        ..GameOptions::default()
+    };
+    let game = h.create_game(STAKE, MOVE_FEE, options).unwrap();
+
+    let (game_rent, pda_rent) = init_rent(h);
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        PLAYER_LAMPORTS - STAKE - game_rent - pda_rent
+    );
+    assert_eq!(h.balance(&game.key), game_rent + STAKE);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::WaitingForPlayer2);
+    assert_eq!(state.pot_lamports, STAKE);
+    assert_eq!(state.player1_deposit, STAKE);
+    game
+}
+
+fn join(h: &mut Harness, game: &Game) {
+    h.join(game).unwrap();
+
+    assert_eq!(h.balance(&game.player2.pubkey()), PLAYER_LAMPORTS - STAKE);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Active);
+    assert_eq!(state.pot_lamports, 2 * STAKE);
+    assert_eq!(state.player2_deposit, STAKE);
+}
+
+/// moves ходов по очереди; каждый платит MOVE_FEE в банк.
+fn play(h: &mut Harness, game: &Game, moves: u64) {
+    let config = h.config();
+    for _ in 0..moves {
+        let state = h.game(&game.key);
+        let mover = if state.current_turn == 1 {
+            &game.player1
+        } else {
+            &game.player2
+        };
+        let before = h.balance(&mover.pubkey());
+        let mut annotation = [0; 32];
+        annotation[..8].copy_from_slice(&state.move_index.to_le_bytes());
+        h.send(
+            ix::make_move_ix(
+                &game.key,
+                &state,
+                &config,
+                state.board_points().unwrap(),
+                [3, 1],
+                annotation,
+                None,
+            ),
+            &[mover],
+        )
+        .unwrap();
+
+        assert_eq!(h.balance(&mover.pubkey()), before - MOVE_FEE);
+        let after = h.game(&game.key);
+        assert_eq!(after.move_index, state.move_index + 1);
+        assert_eq!(after.current_turn, 3 - state.current_turn);
+        assert_eq!(after.pot_lamports, state.pot_lamports + MOVE_FEE);
+    }
+}
+
+/// Игра после create, join и MOVES ходов; сверяет банк и вклады.
+fn played_game(h: &mut Harness) -> Game {
+    let game = create(h);
+    join(h, &game);
+    play(h, &game, MOVES);
+
+    let state = h.game(&game.key);
+    let fees = MOVES / 2 * MOVE_FEE;
+    assert_eq!(state.move_index, MOVES);
+    assert_eq!(state.pot_lamports, 2 * STAKE + MOVES * MOVE_FEE);
+    assert_eq!(state.player1_fees_paid, fees);
+    assert_eq!(state.player2_fees_paid, fees);
+    let (game_rent, _) = init_rent(h);
+    assert_eq!(h.balance(&game.key), game_rent + state.pot_lamports);
+    game
+}
+
+fn finish_to(winner_side: u8) {
+    let mut h = Harness::new();
+    let game = played_game(&mut h);
+    let state = h.game(&game.key);
+    let (winner, loser) = if winner_side == 1 {
+        (&game.player1, &game.player2)
+    } else {
+        (&game.player2, &game.player1)
+    };
+    let (winner_before, loser_before) = (h.balance(&winner.pubkey()), h.balance(&loser.pubkey()));
+
+    let config = h.config();
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &winner.pubkey(), 1),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+
+    assert_eq!(
+        h.balance(&winner.pubkey()),
+        winner_before + state.pot_lamports
+    );
+    assert_eq!(h.balance(&loser.pubkey()), loser_before);
+    let (game_rent, _) = init_rent(&h);
+    assert_eq!(h.balance(&game.key), game_rent);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.finish_reason, FinishReason::Win);
+    assert_eq!(state.winner, winner.pubkey());
+    assert_eq!(state.pot_lamports, 0);
+}
+
+#[test]
+fn finish_pays_the_pot_to_player1() {
+    finish_to(1);
+}
+
+#[test]
+fn finish_pays_the_pot_to_player2() {
+    finish_to(2);
+}
+
+/// Создатель сел не за ту сторону: после swap_sides он играет за player2,
+/// первым ходит бывший player2, а банк получает кошелёк победителя.
+#[test]
+fn swap_sides_before_the_first_move() {
+    let mut h = Harness::new();
+    let game = create(&mut h);
+    join(&mut h, &game);
+    let state = h.game(&game.key);
+    h.send(
+        ix::swap_sides_ix(&game.key, &state),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+
+    let Game {
+        key,
+        player1: creator,
+        player2: opponent,
+    } = game;
+    let state = h.game(&key);
+    assert_eq!(state.player1, opponent.pubkey());
+    assert_eq!(state.player2, creator.pubkey());
+    assert_eq!(state.current_turn, 1);
+    assert_eq!(state.pot_lamports, 2 * STAKE);
+    let game = Game {
+        key,
+        player1: opponent,
+        player2: creator,
+    };
+
+    // play сверяет, что комиссию платит ходящий кошелёк новой стороны.
+    play(&mut h, &game, MOVES);
+    let state = h.game(&game.key);
+    assert_eq!(state.player1_fees_paid, MOVES / 2 * MOVE_FEE);
+    assert_eq!(
+        h.send(
+            ix::swap_sides_ix(&game.key, &state),
+            &[&game.player1, &game.player2],
+        ),
+        Err(program_error(ErrorCode::SwapWindowClosed))
+    );
+
+    // Побеждает создатель, теперь player2.
+    let winner = game.player2.pubkey();
+    let (winner_before, loser_before) = (h.balance(&winner), h.balance(&game.player1.pubkey()));
+    let config = h.config();
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &winner, 1),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+    assert_eq!(h.balance(&winner), winner_before + state.pot_lamports);
+    assert_eq!(h.balance(&game.player1.pubkey()), loser_before);
+    assert_eq!(h.game(&game.key).winner, winner);
+}
+
+#[test]
+fn cancel_before_join_returns_the_stake() {
+    let mut h = Harness::new();
+    let game = create(&mut h);
+    let state = h.game(&game.key);
+
+    h.send(
+        ix::cancel_before_join_ix(&game.key, &state),
+        &[&game.player1],
+    )
+    .unwrap();
+
+    // Ставка вернулась, рента аккаунта игры и PDA осталась на них.
+    let (game_rent, pda_rent) = init_rent(&h);
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        PLAYER_LAMPORTS - game_rent - pda_rent
+    );
+    assert_eq!(h.balance(&game.key), game_rent);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Cancelled);
+    assert_eq!(state.pot_lamports, 0);
+}
+
+#[test]
+fn manual_refund_returns_deposits_and_fees() {
+    let mut h = Harness::new();
+    let game = played_game(&mut h);
+    let state = h.game(&game.key);
+
+    h.send(
+        ix::manual_refund_ix(&game.key, &state, &h.config(), &game.player2.pubkey()),
+        &[&game.player2],
+    )
+    .unwrap();
+
+    // Каждый получает назад ставку и свои комиссии за ходы: балансы как
+    // до игры, за вычетом ренты, которую платил создатель.
+    let (game_rent, pda_rent) = init_rent(&h);
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        PLAYER_LAMPORTS - game_rent - pda_rent
+    );
+    assert_eq!(h.balance(&game.player2.pubkey()), PLAYER_LAMPORTS);
+    assert_eq!(h.balance(&game.key), game_rent);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.finish_reason, FinishReason::ManualRefund);
+}
+
+#[test]
+fn force_refund_after_timeout() {
+    let mut h = Harness::new();
+    let game = played_game(&mut h);
+    let state = h.game(&game.key);
+    let refund = |h: &mut Harness| {
+        let state = h.game(&game.key);
+        h.send(
+            ix::force_refund_ix(&game.key, &state, &h.config()),
+            &[&game.player1, &game.player2],
+        )
+    };
+
+    // До тайм-аута возврат недоступен; в логе — слот, с которого можно.
+    let first_slot = state.last_activity_slot + FORCE_REFUND_TIMEOUT_SLOTS;
+    h.warp(first_slot - 1 - h.slot());
+    let (result, logs) = h.send_with_logs(
+        ix::force_refund_ix(&game.key, &state, &h.config()),
+        &[&game.player1, &game.player2],
+    );
+    assert_eq!(
+        result.unwrap_err(),
+        program_error(ErrorCode::TimeoutNotReached)
+    );
+    assert_eq!(
+        parse_events(&logs),
+        [GameEvent::ErrorContext(ErrorContext {
+            error_code: ErrorCode::TimeoutNotReached.into(),
+            expected: ContextValue::Slot(first_slot),
+            actual: ContextValue::Slot(first_slot - 1),
+        })]
+    );
+
+    h.warp(1);
+    refund(&mut h).unwrap();
+
+    let (game_rent, pda_rent) = init_rent(&h);
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        PLAYER_LAMPORTS - game_rent - pda_rent
+    );
+    assert_eq!(h.balance(&game.player2.pubkey()), PLAYER_LAMPORTS);
+    assert_eq!(h.balance(&game.key), game_rent);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.finish_reason, FinishReason::TimeoutRefund);
+}
+
+/// Счётчики FeeVault сходятся с балансом хранилища сверх ренты.
+fn fee_vault(h: &Harness) -> FeeVault {
+    let vault: FeeVault = h.account(&pda::fee_vault());
+    let rent = h.rent(8 + FeeVault::INIT_SPACE);
+    assert_eq!(
+        vault.collected - vault.withdrawn - vault.burned,
+        h.balance(&pda::fee_vault()) - rent
+    );
+    vault
+}
+
+#[test]
+fn protocol_fees_accumulate_and_are_withdrawn_in_parts() {
+    const FEE_BPS: u16 = 250;
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let treasury = Pubkey::new_unique();
+    h.send(ix::init_fee_vault_ix(&admin.pubkey()), &[&admin])
+        .unwrap();
+    let update = ConfigUpdate {
+        treasury: Some(treasury),
+        protocol_fee_bps: Some(FEE_BPS),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+
+    let mut collected = 0;
+    for winner_side in [1, 2] {
+        let game = played_game(&mut h);
+        let state = h.game(&game.key);
+        let winner = if winner_side == 1 {
+            &game.player1
+        } else {
+            &game.player2
+        };
+        let before = h.balance(&winner.pubkey());
+        let config = h.config();
+        h.send(
+            ix::finish_game_ix(&game.key, &state, &config, &winner.pubkey(), 1),
+            &[&game.player1, &game.player2],
+        )
+        .unwrap();
+
+        let fee = protocol_fee(state.pot_lamports, FEE_BPS);
+        assert!(fee > 0);
+        assert_eq!(
+            h.balance(&winner.pubkey()),
+            before + state.pot_lamports - fee
+        );
+        collected += fee;
+        assert_eq!(fee_vault(&h).collected, collected);
+    }
+
+    let withdraw = |h: &mut Harness, amount| {
+        h.send(
+            ix::withdraw_protocol_fees_ix(&admin.pubkey(), &treasury, amount),
+            &[&admin],
+        )
+    };
+    let part = collected / 2;
+    withdraw(&mut h, part).unwrap();
+    assert_eq!(h.balance(&treasury), part);
+    assert_eq!(fee_vault(&h).withdrawn, part);
+
+    assert_eq!(
+        withdraw(&mut h, collected - part + 1).unwrap_err(),
+        program_error(ErrorCode::InsufficientProtocolFees)
+    );
+    assert_eq!(fee_vault(&h).withdrawn, part);
+
+    withdraw(&mut h, collected - part).unwrap();
+    assert_eq!(h.balance(&treasury), collected);
+    let vault = fee_vault(&h);
+    assert_eq!(vault.collected, vault.withdrawn);
+}
+
+#[test]
+fn burned_fee_share_goes_to_incinerator() {
+    const FEE_BPS: u16 = 250;
+    const BURN_BPS: u16 = 4_000;
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let treasury = Pubkey::new_unique();
+    h.send(ix::init_fee_vault_ix(&admin.pubkey()), &[&admin])
+        .unwrap();
+    let update = ConfigUpdate {
+        treasury: Some(treasury),
+        protocol_fee_bps: Some(FEE_BPS),
+        burn_bps: Some(BURN_BPS),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+
+    let game = played_game(&mut h);
+    let state = h.game(&game.key);
+    let (winner, config) = (game.player1.pubkey(), h.config());
+    let before = h.balance(&winner);
+    let incinerated = h.balance(&INCINERATOR);
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &winner, 1),
+        &[&game.player1, &game.player2],
+    )
+    .unwrap();
+
+    let fee = protocol_fee(state.pot_lamports, FEE_BPS);
+    let burned = burn_share(fee, BURN_BPS);
+    assert_eq!(burned, fee * 4 / 10);
+    assert!(burned > 0);
+    assert_eq!(h.balance(&winner), before + state.pot_lamports - fee);
+    assert_eq!(h.balance(&INCINERATOR), incinerated + burned);
+    let vault = fee_vault(&h);
+    assert_eq!((vault.collected, vault.burned), (fee, burned));
+    assert_eq!(vault.available(), fee - burned);
+
+    // Сожжённая часть не выводится.
+    let withdraw = |h: &mut Harness, amount| {
+        h.send(
+            ix::withdraw_protocol_fees_ix(&admin.pubkey(), &treasury, amount),
+            &[&admin],
+        )
+    };
+    assert_eq!(
+        withdraw(&mut h, fee - burned + 1).unwrap_err(),
+        program_error(ErrorCode::InsufficientProtocolFees)
+    );
+    withdraw(&mut h, fee - burned).unwrap();
+    assert_eq!(h.balance(&treasury), fee - burned);
+    assert_eq!(fee_vault(&h).available(), 0);
+}
+
+/// FeeVault и комиссия протокола fee_bps, из которой burn_bps сжигается.
+fn enable_protocol_fee(h: &mut Harness, fee_bps: u16, burn_bps: u16) {
+    let admin = h.admin.insecure_clone();
+    h.send(ix::init_fee_vault_ix(&admin.pubkey()), &[&admin])
+        .unwrap();
+    let update = ConfigUpdate {
+        protocol_fee_bps: Some(fee_bps),
+        burn_bps: Some(burn_bps),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+}
+
+#[test]
+fn dropped_double_pays_the_doubler_net_of_the_fee() {
+    const FEE_BPS: u16 = 300;
+    const BURN_BPS: u16 = 2_500;
+    let mut h = Harness::new();
+    enable_protocol_fee(&mut h, FEE_BPS, BURN_BPS);
+    let game = played_game(&mut h);
+    let state = h.game(&game.key);
+    let (doubler, taker) = if state.current_turn == 1 {
+        (&game.player1, &game.player2)
+    } else {
+        (&game.player2, &game.player1)
+    };
+    h.send(
+        ix::offer_double_ix(&game.key, &doubler.pubkey()),
+        &[doubler],
+    )
+    .unwrap();
+
+    let (state, config) = (h.game(&game.key), h.config());
+    assert!(state.double_pending);
+    let before = h.balance(&doubler.pubkey());
+    let incinerated = h.balance(&INCINERATOR);
+    h.send(
+        ix::drop_double_ix(&game.key, &state, &config, &taker.pubkey()),
+        &[taker],
+    )
+    .unwrap();
+
+    let fee = protocol_fee(state.pot_lamports, FEE_BPS);
+    let burned = burn_share(fee, BURN_BPS);
+    assert!(burned > 0 && burned < fee);
+    assert_eq!(
+        h.balance(&doubler.pubkey()),
+        before + state.pot_lamports - fee
+    );
+    assert_eq!(h.balance(&INCINERATOR), incinerated + burned);
+    let vault = fee_vault(&h);
+    assert_eq!((vault.collected, vault.burned), (fee, burned));
+    let (game_rent, _) = init_rent(&h);
+    assert_eq!(h.balance(&game.key), game_rent);
+    let state = h.game(&game.key);
+    assert_eq!(state.finish_reason, FinishReason::DoubleDropped);
+    assert_eq!(state.winner, doubler.pubkey());
+}
+
+#[test]
+fn unchallenged_claim_pays_the_claimant_net_of_the_fee() {
+    const FEE_BPS: u16 = 250;
+    let mut h = Harness::new();
+    enable_protocol_fee(&mut h, FEE_BPS, 0);
+    let game = played_game(&mut h);
+    let claimant = &game.player2;
+    let final_board = h.game(&game.key).board_points().unwrap();
+    h.send(
+        ix::claim_win_ix(&game.key, &claimant.pubkey(), final_board),
+        &[claimant],
+    )
+    .unwrap();
+    h.warp(CLAIM_CONTEST_WINDOW_SLOTS + 1);
+
+    let (state, config) = (h.game(&game.key), h.config());
+    let before = h.balance(&claimant.pubkey());
+    let payer = h.player();
+    h.send(
+        ix::finalize_claim_ix(
+            &game.key,
+            &state,
+            &config,
+            &claimant.pubkey(),
+            &payer.pubkey(),
+        ),
+        &[&payer],
+    )
+    .unwrap();
+
+    // Банк за вычетом комиссии, плюс залог и рента заявки.
+    let fee = protocol_fee(state.pot_lamports, FEE_BPS);
+    assert!(fee > 0);
+    let claim_rent = h.rent(8 + WinClaim::MAX_SIZE);
+    assert_eq!(
+        h.balance(&claimant.pubkey()),
+        before + state.pot_lamports - fee + WIN_CLAIM_BOND_LAMPORTS + claim_rent
+    );
+    let vault = fee_vault(&h);
+    assert_eq!((vault.collected, vault.burned), (fee, 0));
+    let state = h.game(&game.key);
+    assert_eq!(state.finish_reason, FinishReason::Claim);
+    assert_eq!(state.winner, claimant.pubkey());
+}
+
+#[test]
+fn prune_games_closes_only_expired_games() {
+    const REWARD_BPS: u16 = 1_000;
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let update = ConfigUpdate {
+        prune_reward_bps: Some(REWARD_BPS),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+
+    let finish = |h: &mut Harness, game: &Game| {
+        let (state, config) = (h.game(&game.key), h.config());
+        h.send(
+            ix::finish_game_ix(&game.key, &state, &config, &game.player1.pubkey(), 1),
+            &[&game.player1, &game.player2],
+        )
+        .unwrap();
+    };
+    let (old, recent, active) = (
+        played_game(&mut h),
+        played_game(&mut h),
+        played_game(&mut h),
+    );
+    finish(&mut h, &old);
+    h.warp(DEFAULT_PRUNE_AFTER_SLOTS / 2);
+    finish(&mut h, &recent);
+    h.warp(DEFAULT_PRUNE_AFTER_SLOTS - DEFAULT_PRUNE_AFTER_SLOTS / 2);
+
+    let caller = h.player();
+    let (game_rent, _) = init_rent(&h);
+    let player1_before = h.balance(&old.player1.pubkey());
+    let pairs = [&old, &recent, &active].map(|game| (game.key, game.player1.pubkey()));
+    let (result, logs) = h.send_with_logs(ix::prune_games_ix(&caller.pubkey(), &pairs), &[&caller]);
+    result.unwrap();
+
+    let reward = prune_reward(game_rent, REWARD_BPS);
+    assert_eq!(h.balance(&old.key), 0);
+    assert_eq!(h.balance(&caller.pubkey()), PLAYER_LAMPORTS + reward);
+    assert_eq!(
+        h.balance(&old.player1.pubkey()),
+        player1_before + game_rent - reward
+    );
+    assert_eq!(h.game(&recent.key).status, GameStatus::Finished);
+    assert_eq!(h.game(&active.key).status, GameStatus::Active);
+    assert!(
+        parse_events(&logs).contains(&GameEvent::GamesPruned(GamesPruned {
+            caller: caller.pubkey(),
+            pruned: 1,
+            skipped: 2,
+            caller_reward: reward,
+            payer_refund: game_rent - reward,
+        }))
+    );
+}
@@ -0,0 +1,191 @@
+//! Лобби открытых игр в LiteSVM: list_game добавляет запись в шард,
+//! join_game с переданным шардом её снимает, prune_lobby убирает запись
+//! игры, отменённой напрямую через cancel_before_join, и оставляет
+//! ожидающие; переполненный шард отклоняет новую запись с LobbyFull.
+
+use backgammon_client::{ix, pda, AccountMeta, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Game, Harness};
+use pooler::{
+    accounts, instruction, ErrorCode, GameStatus, Lobby, LobbyEntry, RuleVariant, DEFAULT_RATING,
+    LOBBY_SHARDS, LOBBY_SHARD_CAPACITY,
+};
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+const SHARD: u8 = 3;
+
+fn init_shard(h: &mut Harness, shard: u8) -> Result<(), TransactionError> {
+    let payer = h.player();
+    h.send(
+        ix::build(
+            accounts::InitLobbyShard {
+                lobby: pda::lobby(shard),
+                payer: payer.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+            },
+            instruction::InitLobbyShard { shard },
+        ),
+        &[&payer],
+    )
+}
+
+fn create(h: &mut Harness) -> Game {
+    h.create_game(STAKE, 0, GameOptions::default()).unwrap()
+}
+
+fn list(h: &mut Harness, game: &Game) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::ListGame {
+                game: game.key,
+                player1: game.player1.pubkey(),
+                lobby: pda::lobby(SHARD),
+                player1_stats: pda::stats(&game.player1.pubkey()),
+            },
+            instruction::ListGame {},
+        ),
+        &[&game.player1],
+    )
+}
+
+/// join_game с шардом лобби (SDK передаёт его как None).
+fn join(h: &mut Harness, game: &Game) {
+    let state = h.game(&game.key);
+    let player2 = game.player2.pubkey();
+    h.send(
+        ix::build(
+            accounts::JoinGame {
+                game: game.key,
+                player2,
+                system_program: anchor_lang::system_program::ID,
+                config: pda::config(),
+                player1_open_games: state
+                    .open_games_counted
+                    .then(|| pda::open_games(&state.player1)),
+                player2_ban: Some(pda::ban(&player2)),
+                player2_stats: Some(pda::stats(&player2)),
+                client_telemetry: None,
+                event_buffer: None,
+                lobby: Some(pda::lobby(SHARD)),
+                pot_stake: None,
+                pot_vault: None,
+                pool_tokens: None,
+                pool_mint: None,
+                stake_pool: None,
+                stake_pool_withdraw_authority: None,
+                reserve_stake: None,
+                manager_fee_account: None,
+                stake_pool_program: None,
+                token_program: None,
+            },
+            instruction::JoinGame {
+                password: Vec::new(),
+                client: None,
+            },
+        ),
+        &[&game.player2],
+    )
+    .unwrap();
+}
+
+fn prune(h: &mut Harness, games: &[Pubkey]) {
+    let mut prune = ix::build(
+        accounts::PruneLobby {
+            lobby: pda::lobby(SHARD),
+        },
+        instruction::PruneLobby {},
+    );
+    prune.accounts.extend(
+        games
+            .iter()
+            .map(|game| AccountMeta::new_readonly(*game, false)),
+    );
+    // Очистка без разрешений: подписывает только плательщик харнесса.
+    h.send(prune, &[]).unwrap();
+}
+
+fn listed(h: &Harness) -> Vec<Pubkey> {
+    h.account::<Lobby>(&pda::lobby(SHARD))
+        .entries
+        .iter()
+        .map(|entry| entry.game)
+        .collect()
+}
+
+#[test]
+fn list_then_join_removes_the_entry() {
+    let mut h = Harness::new();
+    init_shard(&mut h, SHARD).unwrap();
+    let (first, second) = (create(&mut h), create(&mut h));
+    list(&mut h, &first).unwrap();
+    list(&mut h, &second).unwrap();
+
+    let lobby = h.account::<Lobby>(&pda::lobby(SHARD));
+    assert_eq!(lobby.shard, SHARD);
+    assert_eq!(
+        lobby.entries[0],
+        LobbyEntry {
+            game: first.key,
+            stake_lamports: STAKE,
+            rule_variant: RuleVariant::Standard,
+            creator_rating: DEFAULT_RATING,
+        }
+    );
+    assert_eq!(listed(&h), vec![first.key, second.key]);
+    assert_eq!(
+        list(&mut h, &first),
+        Err(program_error(ErrorCode::GameAlreadyListed))
+    );
+
+    join(&mut h, &first);
+    assert_eq!(h.game(&first.key).status, GameStatus::Active);
+    assert_eq!(listed(&h), vec![second.key]);
+    // Активную игру опубликовать уже нельзя.
+    assert_eq!(
+        list(&mut h, &first),
+        Err(program_error(ErrorCode::GameNotWaitingForPlayer2))
+    );
+}
+
+#[test]
+fn prune_removes_a_game_cancelled_directly() {
+    let mut h = Harness::new();
+    init_shard(&mut h, SHARD).unwrap();
+    let (cancelled, waiting) = (create(&mut h), create(&mut h));
+    list(&mut h, &cancelled).unwrap();
+    list(&mut h, &waiting).unwrap();
+
+    // Отмена без шарда лобби: запись остаётся устаревшей.
+    let state = h.game(&cancelled.key);
+    h.send(
+        ix::cancel_before_join_ix(&cancelled.key, &state),
+        &[&cancelled.player1],
+    )
+    .unwrap();
+    assert_eq!(h.game(&cancelled.key).status, GameStatus::Cancelled);
+    assert_eq!(listed(&h), vec![cancelled.key, waiting.key]);
+
+    prune(&mut h, &[cancelled.key, waiting.key]);
+    assert_eq!(listed(&h), vec![waiting.key]);
+}
+
+#[test]
+fn full_shard_rejects_a_new_entry() {
+    let mut h = Harness::new();
+    assert_eq!(
+        init_shard(&mut h, LOBBY_SHARDS),
+        Err(program_error(ErrorCode::InvalidLobbyShard))
+    );
+    init_shard(&mut h, SHARD).unwrap();
+    for _ in 0..LOBBY_SHARD_CAPACITY {
+        let game = create(&mut h);
+        list(&mut h, &game).unwrap();
+    }
+    let game = create(&mut h);
+    assert_eq!(
+        list(&mut h, &game),
+        Err(program_error(ErrorCode::LobbyFull))
+    );
+    assert_eq!(listed(&h).len(), LOBBY_SHARD_CAPACITY as usize);
+}
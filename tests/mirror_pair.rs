@@ -0,0 +1,101 @@
+//! Зеркальная пара в LiteSVM: init_mirror_pair принимает только игры с
+//! общим ненулевым сидом кубиков, помечает обе игры mirror_paired, и
+//! собрать из тех же игр вторую пару (с другим PDA) уже нельзя.
+
+use anchor_lang::system_program;
+use backgammon_client::{ix, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{accounts, instruction, ErrorCode};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const PAIR_STAKE: u64 = 10_000_000;
+
+/// Тренировочная игра без ставки player1 против player2 с сидом seed.
+fn mirror_game(h: &mut Harness, player1: &Keypair, player2: &Keypair, seed: [u8; 32]) -> Pubkey {
+    let game = Keypair::new();
+    let options = GameOptions {
+        practice: true,
+        dice_seed: seed,
+        ..GameOptions::default()
+    };
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            1,
+            0,
+            0,
+            &player2.pubkey(),
+            options,
+        ),
+        &[&game, player1],
+    )
+    .unwrap();
+    let state = h.game(&game.pubkey());
+    h.send(
+        ix::join_game_ix(&game.pubkey(), &state, &player2.pubkey(), Vec::new(), None),
+        &[player2],
+    )
+    .unwrap();
+    game.pubkey()
+}
+
+fn init_pair(
+    h: &mut Harness,
+    game_a: &Pubkey,
+    game_b: &Pubkey,
+    player_a: &Keypair,
+    player_b: &Keypair,
+) -> Result<(), TransactionError> {
+    let (pair, _) =
+        Pubkey::find_program_address(&[b"mirror", game_a.as_ref(), game_b.as_ref()], &pooler::ID);
+    h.send(
+        ix::build(
+            accounts::InitMirrorPair {
+                game_a: *game_a,
+                game_b: *game_b,
+                pair,
+                player_a: player_a.pubkey(),
+                player_b: player_b.pubkey(),
+                system_program: system_program::ID,
+                config: backgammon_client::pda::config(),
+            },
+            instruction::InitMirrorPair {
+                stake_lamports: PAIR_STAKE,
+            },
+        ),
+        &[player_a, player_b],
+    )
+}
+
+#[test]
+fn games_with_different_seeds_are_not_paired() {
+    let mut h = Harness::new();
+    let (a, b) = (h.player(), h.player());
+    let game_a = mirror_game(&mut h, &a, &b, [1; 32]);
+    let game_b = mirror_game(&mut h, &b, &a, [2; 32]);
+    assert_eq!(
+        init_pair(&mut h, &game_a, &game_b, &a, &b).unwrap_err(),
+        program_error(ErrorCode::MirrorDiceSeedMismatch)
+    );
+}
+
+#[test]
+fn games_join_only_one_pair() {
+    let mut h = Harness::new();
+    let (a, b) = (h.player(), h.player());
+    let game_a = mirror_game(&mut h, &a, &b, [1; 32]);
+    let game_b = mirror_game(&mut h, &b, &a, [1; 32]);
+
+    init_pair(&mut h, &game_a, &game_b, &a, &b).unwrap();
+    assert!(h.game(&game_a).mirror_paired);
+    assert!(h.game(&game_b).mirror_paired);
+
+    // Те же игры в обратном порядке дали бы другой PDA пары.
+    assert_eq!(
+        init_pair(&mut h, &game_b, &game_a, &b, &a).unwrap_err(),
+        program_error(ErrorCode::GameAlreadyPaired)
+    );
+}
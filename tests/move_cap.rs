@@ -0,0 +1,92 @@
+//! Предел ходов в LiteSVM: игра с options.max_moves доходит до предела,
+//! следующий ход отклоняется с MoveCapReached, а settle_by_move_cap
+//! возвращает каждому игроку его ставку и комиссии и закрывает игру с
+//! причиной MoveCapRefund.
+
+use backgammon_client::{ix, pda, GameOptions};
+use backgammon_tests::{options, program_error, Game, Harness};
+use pooler::{accounts, instruction, ErrorCode, FinishReason, GameStatus, Remainder};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 20_000_000;
+const MOVE_FEE: u64 = 1_000_000;
+const MAX_MOVES: u16 = 4;
+
+fn capped() -> GameOptions {
+    GameOptions {
+        max_moves: MAX_MOVES,
+        ..options()
+    }
+}
+
+fn settle(h: &mut Harness, game: &Game, requester: &Keypair) -> Result<(), TransactionError> {
+    let state = h.game(&game.key);
+    h.send(
+        ix::build(
+            accounts::SettleByMoveCap {
+                game: game.key,
+                player1: state.player1,
+                player2: state.player2,
+                requester: requester.pubkey(),
+                bot_vault: None,
+                escrow_agent: None,
+                team: None,
+                player1_splits: pda::payout_splits(&state.player1),
+                player2_splits: pda::payout_splits(&state.player2),
+                event_buffer: None,
+                config: pda::config(),
+                treasury: None,
+                event_authority: pda::event_authority(),
+                program: pooler::ID,
+            },
+            instruction::SettleByMoveCap {},
+        ),
+        &[requester],
+    )
+}
+
+#[test]
+fn game_at_the_cap_settles_by_contributions() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, MOVE_FEE, capped());
+
+    for _ in 0..MAX_MOVES {
+        assert_eq!(
+            settle(&mut h, &game, &game.player1),
+            Err(program_error(ErrorCode::MoveCapNotReached))
+        );
+        h.make_move(&game).unwrap();
+    }
+    assert_eq!(
+        h.make_move(&game),
+        Err(program_error(ErrorCode::MoveCapReached))
+    );
+
+    let state = h.game(&game.key);
+    assert_eq!(state.move_index, MAX_MOVES as u64);
+    let (total_p1, total_p2, _) = state.refund_totals(Remainder::First).unwrap();
+    assert_eq!(total_p1, STAKE + state.player1_fees_paid);
+    assert_eq!(total_p2, STAKE + state.player2_fees_paid);
+    assert_eq!(total_p1 + total_p2, state.pot_lamports);
+
+    let (p1_before, p2_before) = (
+        h.balance(&game.player1.pubkey()),
+        h.balance(&game.player2.pubkey()),
+    );
+    // Посторонний рассчитать игру не может.
+    let stranger = h.player();
+    assert_eq!(
+        settle(&mut h, &game, &stranger),
+        Err(program_error(ErrorCode::InvalidPlayer))
+    );
+    settle(&mut h, &game, &game.player2).unwrap();
+
+    assert_eq!(h.balance(&game.player1.pubkey()), p1_before + total_p1);
+    assert_eq!(h.balance(&game.player2.pubkey()), p2_before + total_p2);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.finish_reason, FinishReason::MoveCapRefund);
+    assert_eq!(state.pot_lamports, 0);
+}
@@ -0,0 +1,98 @@
+//! Лимит открытых лобби в LiteSVM: создатель открывает config.max_open_games
+//! игр, следующая отклоняется, после отмены одной место освобождается и игра
+//! создаётся снова. Игра, созданная до OpenGames, отменяется без счётчика и
+//! его не уменьшает.
+
+use backgammon_client::{ix, pda, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{ConfigUpdate, ErrorCode, GameStatus, OpenGames};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+const MAX_OPEN_GAMES: u16 = 2;
+
+fn init_game(
+    h: &mut Harness,
+    player1: &Keypair,
+    game_id: u64,
+) -> (Keypair, Result<(), TransactionError>) {
+    let game = Keypair::new();
+    let result = h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            game_id,
+            STAKE,
+            0,
+            &Pubkey::new_unique(),
+            GameOptions::default(),
+        ),
+        &[&game, player1],
+    );
+    (game, result)
+}
+
+fn open_games(h: &Harness, player: &Pubkey) -> u16 {
+    h.account::<OpenGames>(&pda::open_games(player)).open_games
+}
+
+fn cancel(h: &mut Harness, game: &Pubkey, player1: &Keypair) {
+    let state = h.game(game);
+    h.send(ix::cancel_before_join_ix(game, &state), &[player1])
+        .unwrap();
+    assert_eq!(h.game(game).status, GameStatus::Cancelled);
+}
+
+#[test]
+fn cancelling_frees_a_slot_under_the_limit() {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let update = ConfigUpdate {
+        max_open_games: Some(MAX_OPEN_GAMES),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+
+    let player1 = h.player();
+    let mut games = Vec::new();
+    for game_id in 1..=MAX_OPEN_GAMES as u64 {
+        let (game, result) = init_game(&mut h, &player1, game_id);
+        result.unwrap();
+        assert!(h.game(&game.pubkey()).open_games_counted);
+        games.push(game);
+    }
+    assert_eq!(open_games(&h, &player1.pubkey()), MAX_OPEN_GAMES);
+
+    let (_, rejected) = init_game(&mut h, &player1, 10);
+    assert_eq!(rejected, Err(program_error(ErrorCode::TooManyOpenGames)));
+    assert_eq!(open_games(&h, &player1.pubkey()), MAX_OPEN_GAMES);
+
+    cancel(&mut h, &games[0].pubkey(), &player1);
+    assert_eq!(open_games(&h, &player1.pubkey()), MAX_OPEN_GAMES - 1);
+    assert!(!h.game(&games[0].pubkey()).open_games_counted);
+
+    let (_, result) = init_game(&mut h, &player1, 11);
+    result.unwrap();
+    assert_eq!(open_games(&h, &player1.pubkey()), MAX_OPEN_GAMES);
+}
+
+#[test]
+fn legacy_game_cancels_without_the_counter() {
+    let mut h = Harness::new();
+    let player1 = h.player();
+    let (legacy, result) = init_game(&mut h, &player1, 1);
+    result.unwrap();
+    let (_, result) = init_game(&mut h, &player1, 2);
+    result.unwrap();
+    assert_eq!(open_games(&h, &player1.pubkey()), 2);
+
+    // Игра до OpenGames: флага нет, счётчик её не учитывал.
+    let mut state = h.game(&legacy.pubkey());
+    state.open_games_counted = false;
+    h.set_game(&legacy.pubkey(), &state);
+    cancel(&mut h, &legacy.pubkey(), &player1);
+    assert_eq!(open_games(&h, &player1.pubkey()), 2);
+}
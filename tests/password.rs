@@ -0,0 +1,83 @@
+//! Пароль на вход в LiteSVM: игра с options.password_hash пускает игрока
+//! с верным паролем, отклоняет неверный с WrongPassword и слишком длинный с
+//! PasswordTooLong; в игру без пароля переданный пароль не мешает войти.
+
+use backgammon_client::{ix, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{ErrorCode, GameStatus, MAX_PASSWORD_LEN};
+use solana_keypair::Keypair;
+use solana_sha256_hasher::hash;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+const PASSWORD: &[u8] = b"stream-42";
+
+/// Открытая игра с паролем (None — без пароля).
+fn game(h: &mut Harness, password: Option<&[u8]>) -> Pubkey {
+    let player1 = h.player();
+    let game = Keypair::new();
+    let options = GameOptions {
+        open_lobby: true,
+        password_hash: password.map_or([0; 32], |p| hash(p).to_bytes()),
+        ..GameOptions::default()
+    };
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            1,
+            STAKE,
+            0,
+            &Pubkey::default(),
+            options,
+        ),
+        &[&game, &player1],
+    )
+    .unwrap();
+    game.pubkey()
+}
+
+fn join(h: &mut Harness, game: &Pubkey, password: &[u8]) -> Result<(), TransactionError> {
+    let player2 = h.player();
+    let state = h.game(game);
+    h.send(
+        ix::join_game_ix(game, &state, &player2.pubkey(), password.to_vec(), None),
+        &[&player2],
+    )
+}
+
+#[test]
+fn right_password_joins() {
+    let mut h = Harness::new();
+    let game = game(&mut h, Some(PASSWORD));
+    join(&mut h, &game, PASSWORD).unwrap();
+    assert_eq!(h.game(&game).status, GameStatus::Active);
+}
+
+#[test]
+fn wrong_password_is_rejected() {
+    let mut h = Harness::new();
+    let game = game(&mut h, Some(PASSWORD));
+    assert_eq!(
+        join(&mut h, &game, b"stream-43"),
+        Err(program_error(ErrorCode::WrongPassword))
+    );
+    assert_eq!(
+        join(&mut h, &game, b""),
+        Err(program_error(ErrorCode::WrongPassword))
+    );
+    assert_eq!(
+        join(&mut h, &game, &[b'x'; MAX_PASSWORD_LEN as usize + 1]),
+        Err(program_error(ErrorCode::PasswordTooLong))
+    );
+    assert_eq!(h.game(&game).status, GameStatus::WaitingForPlayer2);
+}
+
+#[test]
+fn passwordless_game_ignores_the_password() {
+    let mut h = Harness::new();
+    let game = game(&mut h, None);
+    join(&mut h, &game, b"anything").unwrap();
+    assert_eq!(h.game(&game).status, GameStatus::Active);
+}
@@ -0,0 +1,109 @@
+//! Аварийная пауза в LiteSVM: администратор включает Config.paused посреди
+//! партии. Новая активность (init_game, join_game, make_move) и выплата
+//! победителю (finish_game) останавливаются с ProgramPaused, а возвраты
+//! (manual_refund, force_refund, cancel_before_join) по-прежнему выводят
+//! средства игроков. После снятия паузы игра продолжается.
+
+use backgammon_client::{ix, GameStatus};
+use backgammon_tests::{options, program_error, Game, Harness};
+use pooler::{ConfigUpdate, ErrorCode, FORCE_REFUND_TIMEOUT_SLOTS};
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+
+fn finish(h: &mut Harness, game: &Game) -> Result<(), TransactionError> {
+    let (state, config) = (h.game(&game.key), h.config());
+    h.send(
+        ix::finish_game_ix(&game.key, &state, &config, &game.player1.pubkey(), 1),
+        &[&game.player1, &game.player2],
+    )
+}
+
+fn set_paused(h: &mut Harness, paused: bool) {
+    let admin = h.admin.insecure_clone();
+    let update = ConfigUpdate {
+        paused: Some(paused),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+    assert_eq!(h.config().paused, paused);
+}
+
+/// Активная партия с одним сделанным ходом и ожидающее соперника лобби.
+fn mid_game(h: &mut Harness) -> (Game, Game) {
+    let active = h.active_game(STAKE, 0, options());
+    h.make_move(&active).unwrap();
+    let waiting = h.create_game(STAKE, 0, options()).unwrap();
+    (active, waiting)
+}
+
+#[test]
+fn pause_stops_new_activity_and_payouts() {
+    let mut h = Harness::new();
+    let (active, waiting) = mid_game(&mut h);
+
+    // Включить паузу может только администратор.
+    let stranger = h.player();
+    let update = ConfigUpdate {
+        paused: Some(true),
+        ..ConfigUpdate::default()
+    };
+    assert!(h
+        .send(
+            ix::update_config_ix(&stranger.pubkey(), update),
+            &[&stranger]
+        )
+        .is_err());
+    set_paused(&mut h, true);
+
+    let paused = Err(program_error(ErrorCode::ProgramPaused));
+    assert_eq!(h.create_game(STAKE, 0, options()).map(drop), paused);
+    assert_eq!(h.join(&waiting), paused);
+    assert_eq!(h.make_move(&active), paused);
+    assert_eq!(finish(&mut h, &active), paused);
+    assert_eq!(h.game(&active.key).move_index, 1);
+
+    set_paused(&mut h, false);
+    h.make_move(&active).unwrap();
+    h.join(&waiting).unwrap();
+    finish(&mut h, &active).unwrap();
+    assert_eq!(h.game(&active.key).status, GameStatus::Finished);
+}
+
+#[test]
+fn refunds_still_work_while_paused() {
+    let mut h = Harness::new();
+    let (active, waiting) = mid_game(&mut h);
+    let timed_out = h.active_game(STAKE, 0, options());
+    set_paused(&mut h, true);
+
+    let before = h.balance(&active.player1.pubkey());
+    let state = h.game(&active.key);
+    h.send(
+        ix::manual_refund_ix(&active.key, &state, &h.config(), &active.player2.pubkey()),
+        &[&active.player2],
+    )
+    .unwrap();
+    assert_eq!(h.game(&active.key).status, GameStatus::Finished);
+    assert_eq!(h.balance(&active.player1.pubkey()), before + STAKE);
+
+    let state = h.game(&waiting.key);
+    h.send(
+        ix::cancel_before_join_ix(&waiting.key, &state),
+        &[&waiting.player1],
+    )
+    .unwrap();
+    assert_eq!(h.game(&waiting.key).status, GameStatus::Cancelled);
+
+    let state = h.game(&timed_out.key);
+    let first_slot = state.last_activity_slot + FORCE_REFUND_TIMEOUT_SLOTS;
+    h.warp(first_slot - h.slot());
+    h.send(
+        ix::force_refund_ix(&timed_out.key, &state, &h.config()),
+        &[&timed_out.player1, &timed_out.player2],
+    )
+    .unwrap();
+    assert_eq!(h.game(&timed_out.key).status, GameStatus::Finished);
+}
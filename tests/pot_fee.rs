@@ -0,0 +1,125 @@
+//! Комиссия за ход от банка в LiteSVM: игра с options.move_fee_bps списывает
+//! с ходящего долю банка перед ходом, поэтому каждая следующая комиссия
+//! больше предыдущей. Вклады игроков записывают ровно списанные суммы, и
+//! manual_refund возвращает всё до lamport. Фиксированная комиссия вместе с
+//! move_fee_bps не принимается ни в init_game, ни во встречном предложении.
+
+use backgammon_client::{ix, GameOptions, GameState};
+use backgammon_tests::{options, program_error, Game, Harness, PLAYER_LAMPORTS};
+use pooler::{pot_move_fee, CreateCooldown, ErrorCode, FinishReason, OpenGames};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const STAKE: u64 = 100_000_000;
+/// 1% банка.
+const MOVE_FEE_BPS: u16 = 100;
+const MOVES: u64 = 12;
+
+fn pot_fee() -> GameOptions {
+    GameOptions {
+        move_fee_bps: MOVE_FEE_BPS,
+        ..options()
+    }
+}
+
+fn create(h: &mut Harness) -> Game {
+    let game = h.create_game(STAKE, 0, pot_fee()).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.move_fee_bps, MOVE_FEE_BPS);
+    assert_eq!(state.move_fee_lamports, 0);
+    game
+}
+
+/// MOVES ходов; возвращает списанные комиссии по порядку.
+fn play(h: &mut Harness, game: &Game) -> Vec<u64> {
+    let mut fees = Vec::new();
+    for _ in 0..MOVES {
+        let state = h.game(&game.key);
+        let mover = game.side(state.current_turn).pubkey();
+        let before = h.balance(&mover);
+        h.make_move(game).unwrap();
+
+        // Доля банка перед ходом, округлённая вниз.
+        let fee = state.pot_lamports * MOVE_FEE_BPS as u64 / 10_000;
+        assert_eq!(fee, pot_move_fee(state.pot_lamports, MOVE_FEE_BPS));
+        assert_eq!(h.balance(&mover), before - fee);
+        let after = h.game(&game.key);
+        assert_eq!(after.pot_lamports, state.pot_lamports + fee);
+        fees.push(fee);
+    }
+    fees
+}
+
+fn fees_paid(fees: &[u64], side: usize) -> u64 {
+    fees.iter().skip(side - 1).step_by(2).sum()
+}
+
+#[test]
+fn fee_grows_with_the_pot_and_refunds_exactly() {
+    let mut h = Harness::new();
+    let game = create(&mut h);
+    h.join(&game).unwrap();
+    let fees = play(&mut h, &game);
+
+    // Первый ход платит 1% от двух ставок, дальше банк растёт на каждую
+    // комиссию, а с ним и комиссия.
+    assert_eq!(fees[0], 2 * STAKE / 100);
+    assert!(fees.windows(2).all(|pair| pair[1] > pair[0]), "{:?}", fees);
+    let state = h.game(&game.key);
+    assert_eq!(state.player1_fees_paid, fees_paid(&fees, 1));
+    assert_eq!(state.player2_fees_paid, fees_paid(&fees, 2));
+    assert_eq!(state.pot_lamports, 2 * STAKE + fees.iter().sum::<u64>());
+
+    h.send(
+        ix::manual_refund_ix(&game.key, &state, &h.config(), &game.player2.pubkey()),
+        &[&game.player2],
+    )
+    .unwrap();
+
+    // Возвращаются ставки и ровно списанные комиссии.
+    let game_rent = h.rent(8 + GameState::MAX_SIZE);
+    let pda_rent = h.rent(8 + OpenGames::MAX_SIZE) + h.rent(8 + CreateCooldown::MAX_SIZE);
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        PLAYER_LAMPORTS - game_rent - pda_rent
+    );
+    assert_eq!(h.balance(&game.player2.pubkey()), PLAYER_LAMPORTS);
+    assert_eq!(h.balance(&game.key), game_rent);
+    assert_eq!(h.game(&game.key).finish_reason, FinishReason::ManualRefund);
+}
+
+#[test]
+fn flat_fee_and_bps_are_mutually_exclusive() {
+    let mut h = Harness::new();
+    let (player1, player2) = (h.player(), h.player());
+    let game = Keypair::new();
+    assert_eq!(
+        h.send(
+            ix::init_game_ix(
+                &game.pubkey(),
+                &player1.pubkey(),
+                1,
+                STAKE,
+                1_000,
+                &player2.pubkey(),
+                pot_fee(),
+            ),
+            &[&game, &player1]
+        ),
+        Err(program_error(ErrorCode::ConflictingMoveFee))
+    );
+
+    let game = create(&mut h);
+    assert_eq!(
+        h.send(
+            ix::counter_offer_ix(&game.key, &game.player2.pubkey(), STAKE / 2, 1_000),
+            &[&game.player2]
+        ),
+        Err(program_error(ErrorCode::ConflictingMoveFee))
+    );
+    h.send(
+        ix::counter_offer_ix(&game.key, &game.player2.pubkey(), STAKE / 2, 0),
+        &[&game.player2],
+    )
+    .unwrap();
+}
@@ -0,0 +1,365 @@
+//! Банк в SPL Stake Pool в LiteSVM: join_game игры с options.stake_pot
+//! вносит банк в локально развёрнутый пул, пока банк в пуле, выплаты и
+//! возвраты отклоняются с
+//! PotStaked, а claim_pot_unstake в следующей эпохе возвращает банк с
+//! доходом пула, который при возврате делится пропорционально вкладам.
+//!
+//! Программа пула — дамп SPL Stake Pool с mainnet:
+//! `solana program dump -um SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy
+//! tests/fixtures/spl_stake_pool.so`.
+
+use anchor_lang::prelude::Clock;
+use anchor_lang::system_program;
+use backgammon_client::{ix, pda, GameOptions, GameStatus, Instruction, Pubkey};
+use backgammon_tests::{program_error, Game, Harness};
+use pooler::{
+    accounts, instruction, ConfigUpdate, ErrorCode, StakePoolAccounts, SPL_STAKE_POOL_PROGRAM_ID,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_stake_interface::{self as stake, state::StakeStateV2};
+use solana_system_interface::instruction as system_instruction;
+use solana_transaction::Transaction;
+use spl_stake_pool::find_withdraw_authority_program_address;
+use spl_stake_pool::solana_program::borsh1::{
+    get_instance_packed_len, get_packed_len, try_from_slice_unchecked,
+};
+use spl_stake_pool::solana_program::program_pack::Pack;
+use spl_stake_pool::solana_program::sysvar;
+use spl_stake_pool::state::{Fee, StakePool, ValidatorList};
+
+/// Дамп программы SPL Stake Pool.
+const STAKE_POOL_SO: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/spl_stake_pool.so");
+
+const STAKE: u64 = 1_000_000_000;
+const POT: u64 = 2 * STAKE;
+/// Стартовый резерв пула сверх ренты (токены пула — менеджеру).
+const SEED: u64 = 2_000_000_000;
+/// Доход пула за эпоху, пока банк в нём.
+const YIELD: u64 = 40_000_000;
+
+/// Аккаунты локального пула.
+struct Pool {
+    address: Pubkey,
+    withdraw_authority: Pubkey,
+    validator_list: Pubkey,
+    reserve_stake: Pubkey,
+    pool_mint: Pubkey,
+    manager_fee_account: Pubkey,
+}
+
+/// Транзакция из нескольких инструкций; комиссию платит h.payer.
+fn send_all(h: &mut Harness, ixs: &[Instruction], signers: &[&Keypair]) {
+    let mut all: Vec<&Keypair> = vec![&h.payer];
+    all.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&h.payer.pubkey()),
+        &all,
+        h.svm.latest_blockhash(),
+    );
+    h.svm.send_transaction(tx).unwrap();
+    h.svm.expire_blockhash();
+}
+
+/// Пул без валидаторов и без комиссий: весь SOL лежит в резерве, поэтому
+/// доход — это lamports, доложенные в резерв.
+fn create_pool(h: &mut Harness) -> Pool {
+    h.svm
+        .add_program_from_file(SPL_STAKE_POOL_PROGRAM_ID, STAKE_POOL_SO)
+        .unwrap_or_else(|err| panic!("{STAKE_POOL_SO}: {err}; dump the program first"));
+    let manager = h.player();
+    let (stake_pool, validator_list, reserve, mint, fee_account) = (
+        Keypair::new(),
+        Keypair::new(),
+        Keypair::new(),
+        Keypair::new(),
+        Keypair::new(),
+    );
+    let (withdraw_authority, _) =
+        find_withdraw_authority_program_address(&SPL_STAKE_POOL_PROGRAM_ID, &stake_pool.pubkey());
+    let payer = h.payer.pubkey();
+
+    let mut ixs = stake::instruction::create_account(
+        &payer,
+        &reserve.pubkey(),
+        &stake::state::Authorized {
+            staker: withdraw_authority,
+            withdrawer: withdraw_authority,
+        },
+        &stake::state::Lockup::default(),
+        h.rent(StakeStateV2::size_of()) + SEED,
+    );
+    ixs.extend([
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            h.rent(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint(
+            &spl_token::ID,
+            &mint.pubkey(),
+            &withdraw_authority,
+            None,
+            9,
+        )
+        .unwrap(),
+        system_instruction::create_account(
+            &payer,
+            &fee_account.pubkey(),
+            h.rent(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_account(
+            &spl_token::ID,
+            &fee_account.pubkey(),
+            &mint.pubkey(),
+            &manager.pubkey(),
+        )
+        .unwrap(),
+    ]);
+    send_all(h, &ixs, &[&reserve, &mint, &fee_account]);
+
+    let pool_len = get_packed_len::<StakePool>();
+    let list_len = get_instance_packed_len(&ValidatorList::new(1)).unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &payer,
+            &stake_pool.pubkey(),
+            h.rent(pool_len),
+            pool_len as u64,
+            &SPL_STAKE_POOL_PROGRAM_ID,
+        ),
+        system_instruction::create_account(
+            &payer,
+            &validator_list.pubkey(),
+            h.rent(list_len),
+            list_len as u64,
+            &SPL_STAKE_POOL_PROGRAM_ID,
+        ),
+        spl_stake_pool::instruction::initialize(
+            &SPL_STAKE_POOL_PROGRAM_ID,
+            &stake_pool.pubkey(),
+            &manager.pubkey(),
+            &manager.pubkey(),
+            &withdraw_authority,
+            &validator_list.pubkey(),
+            &reserve.pubkey(),
+            &mint.pubkey(),
+            &fee_account.pubkey(),
+            &spl_token::ID,
+            None,
+            Fee::default(),
+            Fee::default(),
+            Fee::default(),
+            0,
+            1,
+        ),
+    ];
+    send_all(h, &ixs, &[&stake_pool, &validator_list, &manager]);
+
+    let admin = h.admin.insecure_clone();
+    let update = ConfigUpdate {
+        stake_pool: Some(stake_pool.pubkey()),
+        ..ConfigUpdate::default()
+    };
+    h.send(ix::update_config_ix(&admin.pubkey(), update), &[&admin])
+        .unwrap();
+
+    Pool {
+        address: stake_pool.pubkey(),
+        withdraw_authority,
+        validator_list: validator_list.pubkey(),
+        reserve_stake: reserve.pubkey(),
+        pool_mint: mint.pubkey(),
+        manager_fee_account: fee_account.pubkey(),
+    }
+}
+
+/// Новая эпоха: пул принимает вывод только после UpdateStakePoolBalance
+/// в текущей эпохе.
+fn next_epoch(h: &mut Harness, pool: &Pool) {
+    let mut clock = h.svm.get_sysvar::<Clock>();
+    clock.epoch += 1;
+    h.svm.set_sysvar(&clock);
+
+    let state: StakePool =
+        try_from_slice_unchecked(&h.svm.get_account(&pool.address).unwrap().data).unwrap();
+    let list: ValidatorList =
+        try_from_slice_unchecked(&h.svm.get_account(&pool.validator_list).unwrap().data).unwrap();
+    let (list_ixs, final_ixs) = spl_stake_pool::instruction::update_stake_pool(
+        &SPL_STAKE_POOL_PROGRAM_ID,
+        &state,
+        &list,
+        &pool.address,
+        false,
+    );
+    assert!(list_ixs.is_empty());
+    send_all(h, &final_ixs, &[]);
+}
+
+impl Pool {
+    fn accounts(&self) -> StakePoolAccounts {
+        StakePoolAccounts {
+            stake_pool: self.address,
+            withdraw_authority: self.withdraw_authority,
+            reserve_stake: self.reserve_stake,
+            manager_fee_account: self.manager_fee_account,
+            pool_mint: self.pool_mint,
+        }
+    }
+}
+
+fn request_pot_unstake_ix(game: &Game) -> Instruction {
+    ix::build(
+        accounts::RequestPotUnstake {
+            game: game.key,
+            pot_stake: pda::pot_stake(&game.key),
+            config: pda::config(),
+            requester: game.player1.pubkey(),
+        },
+        instruction::RequestPotUnstake {},
+    )
+}
+
+fn claim_pot_unstake_ix(game: &Game, pool: &Pool) -> Instruction {
+    ix::build(
+        accounts::ClaimPotUnstake {
+            game: game.key,
+            pot_stake: pda::pot_stake(&game.key),
+            pot_vault: pda::pot_vault(&game.key),
+            pool_tokens: pda::pot_stake_tokens(&game.key),
+            pool_mint: pool.pool_mint,
+            stake_pool: pool.address,
+            stake_pool_withdraw_authority: pool.withdraw_authority,
+            reserve_stake: pool.reserve_stake,
+            manager_fee_account: pool.manager_fee_account,
+            funder: game.player2.pubkey(),
+            clock: sysvar::clock::ID,
+            stake_history: sysvar::stake_history::ID,
+            stake_program: stake::program::ID,
+            stake_pool_program: SPL_STAKE_POOL_PROGRAM_ID,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimPotUnstake {},
+    )
+}
+
+/// Игра с options.stake_pot: player2 входит, и банк уходит в пул.
+fn staked_game(h: &mut Harness, pool: &Pool) -> Game {
+    let options = GameOptions {
+        stake_pot: true,
+        ..GameOptions::default()
+    };
+    let game = h.create_game(STAKE, 0, options).unwrap();
+    let state = h.game(&game.key);
+    let join = ix::join_game_staked_ix(
+        &game.key,
+        &state,
+        &game.player2.pubkey(),
+        Vec::new(),
+        None,
+        &pool.accounts(),
+    );
+    h.send(join, &[&game.player2]).unwrap();
+    game
+}
+
+#[test]
+fn staking_needs_the_pool_at_create_and_join() {
+    let mut h = Harness::new();
+    let options = GameOptions {
+        stake_pot: true,
+        ..GameOptions::default()
+    };
+    assert_eq!(
+        h.create_game(STAKE, 0, options).err(),
+        Some(program_error(ErrorCode::StakePoolDisabled))
+    );
+
+    create_pool(&mut h);
+    let game = h.create_game(STAKE, 0, options).unwrap();
+    assert_eq!(
+        h.join(&game),
+        Err(program_error(ErrorCode::MissingStakePoolAccounts))
+    );
+    // Без options.stake_pot банк остаётся на аккаунте игры.
+    let plain = h.active_game(STAKE, 0, GameOptions::default());
+    assert_eq!(h.game(&plain.key).staked_lamports, 0);
+    assert!(h.svm.get_account(&pda::pot_stake(&plain.key)).is_none());
+}
+
+#[test]
+fn staked_pot_blocks_payouts_and_returns_with_yield() {
+    let mut h = Harness::new();
+    let pool = create_pool(&mut h);
+    let game = staked_game(&mut h, &pool);
+
+    let mut state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Active);
+    assert_eq!(state.pot_lamports, POT);
+    assert_eq!(state.staked_lamports, POT);
+    assert_eq!(h.balance(&pda::pot_vault(&game.key)), 0);
+    // player1 внёс втрое больше player2 (банк тот же).
+    state.player1_deposit = POT * 3 / 4;
+    state.player2_deposit = POT / 4;
+    h.set_game(&game.key, &state);
+
+    // Пока банк в пуле, ни возврат, ни выплата не проходят.
+    let refund = ix::manual_refund_ix(&game.key, &state, &h.config(), &game.player1.pubkey());
+    assert_eq!(
+        h.send(refund, &[&game.player1]),
+        Err(program_error(ErrorCode::PotStaked))
+    );
+    let forced = ix::force_refund_ix(&game.key, &state, &h.config());
+    assert_eq!(
+        h.send(forced, &[&game.player1, &game.player2]),
+        Err(program_error(ErrorCode::PotStaked))
+    );
+
+    // Доход пула за эпоху.
+    let reward = system_instruction::transfer(&h.payer.pubkey(), &pool.reserve_stake, YIELD);
+    send_all(&mut h, &[reward], &[]);
+    h.send(request_pot_unstake_ix(&game), &[&game.player1])
+        .unwrap();
+    assert_eq!(
+        h.send(claim_pot_unstake_ix(&game, &pool), &[]),
+        Err(program_error(ErrorCode::UnstakeEpochNotReached))
+    );
+    next_epoch(&mut h, &pool);
+    h.send(claim_pot_unstake_ix(&game, &pool), &[]).unwrap();
+
+    // Курс пула 1:1 при внесении; доход делится на все токены пула.
+    let returned = (POT as u128 * (SEED + POT + YIELD) as u128 / (SEED + POT) as u128) as u64;
+    let gain = returned - POT;
+    assert_eq!(gain, YIELD / 2);
+    let state = h.game(&game.key);
+    assert_eq!(state.pot_lamports, returned);
+    assert_eq!(state.staked_lamports, 0);
+    assert_eq!(state.player1_deposit, POT * 3 / 4 + gain * 3 / 4);
+    assert_eq!(state.player2_deposit, POT / 4 + gain / 4);
+    assert_eq!(state.tips_lamports, 0);
+
+    // Возврат делит доход по вкладам, а не пополам.
+    let before = (
+        h.balance(&game.player1.pubkey()),
+        h.balance(&game.player2.pubkey()),
+    );
+    let refund = ix::manual_refund_ix(&game.key, &state, &h.config(), &game.player1.pubkey());
+    h.send(refund, &[&game.player1]).unwrap();
+    assert_eq!(
+        h.balance(&game.player1.pubkey()),
+        before.0 + (POT + gain) * 3 / 4
+    );
+    assert_eq!(
+        h.balance(&game.player2.pubkey()),
+        before.1 + (POT + gain) / 4
+    );
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.pot_lamports, 0);
+}
@@ -0,0 +1,135 @@
+//! Порог рейтинга на вход в LiteSVM: игра с options.min_opponent_rating
+//! пускает игрока с достаточным рейтингом, отклоняет слабого с RatingTooLow,
+//! считает игрока без PlayerStats имеющим DEFAULT_RATING и не принимает
+//! чужой аккаунт статистики вместо PDA самого входящего.
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_lang::solana_program::instruction::error::InstructionError;
+use backgammon_client::{ix, pda, AccountMeta, GameOptions, Instruction, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{accounts, instruction, ErrorCode, GameStatus, PlayerStats, DEFAULT_RATING};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+const MIN_RATING: u32 = 1500;
+
+/// Открытая игра с порогом min_rating; возвращает её адрес.
+fn gated_game(h: &mut Harness, min_rating: u32) -> Pubkey {
+    let player1 = h.player();
+    let game = Keypair::new();
+    let options = GameOptions {
+        open_lobby: true,
+        min_opponent_rating: min_rating,
+        ..GameOptions::default()
+    };
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            1,
+            STAKE,
+            0,
+            &Pubkey::default(),
+            options,
+        ),
+        &[&game, &player1],
+    )
+    .unwrap();
+    game.pubkey()
+}
+
+/// Игрок с PlayerStats и заданным рейтингом.
+fn rated_player(h: &mut Harness, rating: u32) -> Keypair {
+    let player = h.player();
+    let stats = pda::stats(&player.pubkey());
+    h.send(
+        ix::build(
+            accounts::InitPlayerStats {
+                stats,
+                config: pda::config(),
+                player: player.pubkey(),
+                payer: player.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+            },
+            instruction::InitPlayerStats {},
+        ),
+        &[&player],
+    )
+    .unwrap();
+    let mut state: PlayerStats = h.account(&stats);
+    assert_eq!(state.rating, DEFAULT_RATING);
+    state.rating = rating;
+    h.set_account(&stats, &state);
+    player
+}
+
+fn join_ix(h: &Harness, game: &Pubkey, player2: &Keypair) -> Instruction {
+    let state = h.game(game);
+    ix::join_game_ix(game, &state, &player2.pubkey(), Vec::new(), None)
+}
+
+fn join(h: &mut Harness, game: &Pubkey, player2: &Keypair) -> Result<(), TransactionError> {
+    let join = join_ix(h, game, player2);
+    h.send(join, &[player2])
+}
+
+#[test]
+fn qualified_joiner_gets_in() {
+    let mut h = Harness::new();
+    let game = gated_game(&mut h, MIN_RATING);
+    let strong = rated_player(&mut h, MIN_RATING);
+    join(&mut h, &game, &strong).unwrap();
+    assert_eq!(h.game(&game).status, GameStatus::Active);
+}
+
+#[test]
+fn unqualified_joiner_is_rejected() {
+    let mut h = Harness::new();
+    let game = gated_game(&mut h, MIN_RATING);
+    let weak = rated_player(&mut h, MIN_RATING - 1);
+    assert_eq!(
+        join(&mut h, &game, &weak),
+        Err(program_error(ErrorCode::RatingTooLow))
+    );
+    assert_eq!(h.game(&game).status, GameStatus::WaitingForPlayer2);
+}
+
+#[test]
+fn fresh_player_counts_as_default_rating() {
+    let mut h = Harness::new();
+    let fresh = h.player();
+    let game = gated_game(&mut h, DEFAULT_RATING + 1);
+    assert_eq!(
+        join(&mut h, &game, &fresh),
+        Err(program_error(ErrorCode::RatingTooLow))
+    );
+
+    let game = gated_game(&mut h, DEFAULT_RATING);
+    join(&mut h, &game, &fresh).unwrap();
+}
+
+#[test]
+fn someone_elses_stats_do_not_pass_the_gate() {
+    let mut h = Harness::new();
+    let game = gated_game(&mut h, MIN_RATING);
+    let strong = rated_player(&mut h, MIN_RATING + 500);
+    let weak = h.player();
+
+    let mut join = join_ix(&h, &game, &weak);
+    let own_stats = pda::stats(&weak.pubkey());
+    let meta = join
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == own_stats)
+        .unwrap();
+    *meta = AccountMeta::new_readonly(pda::stats(&strong.pubkey()), false);
+    assert_eq!(
+        h.send(join, &[&weak]),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AnchorErrorCode::ConstraintSeeds as u32)
+        ))
+    );
+}
@@ -0,0 +1,281 @@
+//! Отказ от админских прав в LiteSVM: после renounce_admin каждая админская
+//! инструкция отклоняется с ConfigLocked, конфиг остаётся байт в байт
+//! прежним, и ни update_config, ни повторный renounce_admin, ни передача
+//! прав через accept_admin снять блокировку не могут.
+//!
+//! Всё, что нужно инструкциям для разбора аккаунтов (арбитр, бан, FeeVault,
+//! InsurancePool, помеченная игра), создаётся до отказа, так что отказ
+//! приходит именно от блокировки конфига; init_fee_vault и
+//! init_insurance_pool проверяются на отдельном харнессе, где хранилищ нет.
+
+use anchor_lang::AccountSerialize;
+use backgammon_client::{ix, pda, GameOptions, GameStatus, Instruction, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{accounts, instruction, ConfigUpdate, ErrorCode};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+
+const STAKE: u64 = 10_000_000;
+
+fn system_program() -> Pubkey {
+    anchor_lang::system_program::ID
+}
+
+fn config_bytes(h: &Harness) -> Vec<u8> {
+    let mut data = Vec::new();
+    h.config().try_serialize(&mut data).unwrap();
+    data
+}
+
+fn renounce_ix(admin: &Pubkey) -> Instruction {
+    ix::build(
+        accounts::UpdateConfig {
+            config: pda::config(),
+            admin: *admin,
+        },
+        instruction::RenounceAdmin {},
+    )
+}
+
+/// Активная игра; с flagged = true банк рассогласован и integrity_check
+/// создаёт для неё GameFlag.
+fn game(h: &mut Harness, flagged: bool) -> (Pubkey, Keypair, Keypair) {
+    let (player1, player2) = (h.player(), h.player());
+    let game = Keypair::new();
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            1,
+            STAKE,
+            0,
+            &player2.pubkey(),
+            GameOptions::default(),
+        ),
+        &[&game, &player1],
+    )
+    .unwrap();
+    let state = h.game(&game.pubkey());
+    h.send(
+        ix::join_game_ix(&game.pubkey(), &state, &player2.pubkey(), Vec::new(), None),
+        &[&player2],
+    )
+    .unwrap();
+    if flagged {
+        let mut state = h.game(&game.pubkey());
+        state.pot_lamports += 1;
+        h.set_game(&game.pubkey(), &state);
+        let crank = h.player();
+        h.send(
+            ix::build(
+                accounts::IntegrityCheck {
+                    game: game.pubkey(),
+                    game_flag: game_flag(&game.pubkey()),
+                    payer: crank.pubkey(),
+                    system_program: system_program(),
+                },
+                instruction::IntegrityCheck {},
+            ),
+            &[&crank],
+        )
+        .unwrap();
+    }
+    (game.pubkey(), player1, player2)
+}
+
+fn init_insurance_pool_ix(admin: &Pubkey) -> Instruction {
+    ix::build(
+        accounts::InitInsurancePool {
+            config: pda::config(),
+            insurance_pool: pda::insurance_pool(),
+            admin: *admin,
+            system_program: system_program(),
+        },
+        instruction::InitInsurancePool {},
+    )
+}
+
+fn game_flag(game: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"game_flag", game.as_ref()], &pooler::ID).0
+}
+
+#[test]
+fn renounced_admin_loses_every_instruction() {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let a = admin.pubkey();
+    let (arbiter, banned, successor, treasury) = (h.player(), h.player(), h.player(), h.player());
+
+    // Состояние, которое админские инструкции ожидают найти.
+    let setup = [
+        ix::add_arbiter_ix(&a, &arbiter.pubkey()),
+        ix::init_fee_vault_ix(&a),
+        ix::build(
+            accounts::AddBan {
+                config: pda::config(),
+                ban: pda::ban(&banned.pubkey()),
+                admin: a,
+                system_program: system_program(),
+            },
+            instruction::AddBan {
+                player: banned.pubkey(),
+            },
+        ),
+        init_insurance_pool_ix(&a),
+        ix::update_config_ix(
+            &a,
+            ConfigUpdate {
+                treasury: Some(treasury.pubkey()),
+                ..ConfigUpdate::default()
+            },
+        ),
+        ix::propose_admin_ix(&a, &successor.pubkey()),
+    ];
+    for setup in setup {
+        h.send(setup, &[&admin]).unwrap();
+    }
+    let (flagged, player1, _) = game(&mut h, true);
+    let (disputed, disputant, _) = game(&mut h, false);
+    let mut state = h.game(&disputed);
+    state.status = GameStatus::Disputed;
+    state.resolution_appealed = true;
+    h.set_game(&disputed, &state);
+
+    h.send(renounce_ix(&a), &[&admin]).unwrap();
+    let config = h.config();
+    assert!(config.locked);
+    assert_eq!(config.pending_admin, Pubkey::default());
+    let frozen = config_bytes(&h);
+
+    let flagged_state = h.game(&flagged);
+    let rejected = [
+        renounce_ix(&a),
+        ix::update_config_ix(
+            &a,
+            ConfigUpdate {
+                paused: Some(true),
+                ..ConfigUpdate::default()
+            },
+        ),
+        ix::update_config_ix(&a, ConfigUpdate::default()),
+        ix::propose_admin_ix(&a, &successor.pubkey()),
+        ix::add_arbiter_ix(&a, &Pubkey::new_unique()),
+        ix::remove_arbiter_ix(&a, &arbiter.pubkey()),
+        ix::withdraw_protocol_fees_ix(&a, &a, 1),
+        ix::build(
+            accounts::AddBan {
+                config: pda::config(),
+                ban: pda::ban(&player1.pubkey()),
+                admin: a,
+                system_program: system_program(),
+            },
+            instruction::AddBan {
+                player: player1.pubkey(),
+            },
+        ),
+        ix::build(
+            accounts::RemoveBan {
+                config: pda::config(),
+                ban: pda::ban(&banned.pubkey()),
+                admin: a,
+            },
+            instruction::RemoveBan {},
+        ),
+        ix::build(
+            accounts::WithdrawBotVault {
+                config: pda::config(),
+                bot_vault: pda::bot_vault(),
+                admin: a,
+                system_program: system_program(),
+            },
+            instruction::WithdrawBotVault { amount: 1 },
+        ),
+        ix::build(
+            accounts::CloseSeason {
+                config: pda::config(),
+                season: pda::season(0),
+                next_season: pda::season(1),
+                admin: a,
+                system_program: system_program(),
+            },
+            instruction::CloseSeason {},
+        ),
+        ix::build(
+            accounts::Compensate {
+                config: pda::config(),
+                game: flagged,
+                game_flag: game_flag(&flagged),
+                insurance_pool: pda::insurance_pool(),
+                recipient: player1.pubkey(),
+                admin: a,
+            },
+            instruction::Compensate {
+                player: player1.pubkey(),
+                amount: 1,
+            },
+        ),
+        ix::build(
+            accounts::Escheat {
+                config: pda::config(),
+                game: flagged,
+                team: None,
+                treasury: treasury.pubkey(),
+                player1: flagged_state.player1,
+                player2: flagged_state.player2,
+                admin: a,
+            },
+            instruction::Escheat {},
+        ),
+    ];
+    for (i, rejected) in rejected.into_iter().enumerate() {
+        assert_eq!(
+            h.send(rejected, &[&admin]),
+            Err(program_error(ErrorCode::ConfigLocked)),
+            "rejected[{i}]"
+        );
+    }
+
+    // Обжалованный спор решал бы админ; теперь его закрывают только возвраты.
+    let state = h.game(&disputed);
+    assert_eq!(
+        h.send(
+            ix::arbiter_resolve_ix(&disputed, &state, &a, &state.player1, false),
+            &[&admin],
+        ),
+        Err(program_error(ErrorCode::ConfigLocked))
+    );
+    h.send(
+        ix::manual_refund_ix(&disputed, &state, &h.config(), &disputant.pubkey()),
+        &[&disputant],
+    )
+    .unwrap();
+    assert_eq!(h.game(&disputed).status, GameStatus::Finished);
+
+    // Предложенный до отказа преемник права не получает.
+    assert_eq!(
+        h.send(ix::accept_admin_ix(&successor.pubkey()), &[&successor]),
+        Err(program_error(ErrorCode::ConfigLocked))
+    );
+    assert_eq!(config_bytes(&h), frozen);
+    let config = h.config();
+    assert!(config.locked);
+    assert_eq!(config.admin, a);
+}
+
+/// Хранилища, не созданные до отказа, создать уже нельзя. Отдельный харнесс:
+/// init уже существующего аккаунта упал бы раньше проверки блокировки.
+#[test]
+fn renounced_admin_cannot_create_vaults() {
+    let mut h = Harness::new();
+    let admin = h.admin.insecure_clone();
+    let a = admin.pubkey();
+    h.send(renounce_ix(&a), &[&admin]).unwrap();
+    for rejected in [ix::init_fee_vault_ix(&a), init_insurance_pool_ix(&a)] {
+        assert_eq!(
+            h.send(rejected, &[&admin]),
+            Err(program_error(ErrorCode::ConfigLocked))
+        );
+    }
+    assert_eq!(h.balance(&pda::fee_vault()), 0);
+    assert_eq!(h.balance(&pda::insurance_pool()), 0);
+}
@@ -0,0 +1,124 @@
+//! Повторное приглашение в LiteSVM: после join_deadline_slot прежний
+//! приглашённый войти не может, reopen_invite продлевает срок и меняет
+//! приглашённого, и новый игрок входит; до срока, после входа и после
+//! отмены игру переоткрыть нельзя.
+
+use backgammon_client::{ix, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Game, Harness};
+use pooler::{accounts, instruction, ErrorCode, GameStatus};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+const DEADLINE: u64 = 10;
+
+fn invite(h: &mut Harness) -> Game {
+    let options = GameOptions {
+        join_deadline_slot: h.slot() + DEADLINE,
+        ..GameOptions::default()
+    };
+    h.create_game(STAKE, 0, options).unwrap()
+}
+
+fn reopen(
+    h: &mut Harness,
+    game: &Game,
+    new_player2: Pubkey,
+    new_deadline_slot: u64,
+) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::ReopenInvite {
+                game: game.key,
+                player1: game.player1.pubkey(),
+            },
+            instruction::ReopenInvite {
+                new_player2,
+                new_deadline_slot,
+            },
+        ),
+        &[&game.player1],
+    )
+}
+
+fn join(h: &mut Harness, game: &Pubkey, player2: &Keypair) -> Result<(), TransactionError> {
+    let state = h.game(game);
+    h.send(
+        ix::join_game_ix(game, &state, &player2.pubkey(), Vec::new(), None),
+        &[player2],
+    )
+}
+
+#[test]
+fn expired_invite_reopens_for_a_new_player() {
+    let mut h = Harness::new();
+    let game = invite(&mut h);
+    let newcomer = h.player();
+
+    let early = h.slot() + 100;
+    assert_eq!(
+        reopen(&mut h, &game, newcomer.pubkey(), early),
+        Err(program_error(ErrorCode::JoinDeadlineNotPassed))
+    );
+    h.warp(DEADLINE + 1);
+    assert_eq!(
+        join(&mut h, &game.key, &game.player2),
+        Err(program_error(ErrorCode::JoinDeadlinePassed))
+    );
+    // Новый срок должен быть в будущем.
+    let now = h.slot();
+    assert_eq!(
+        reopen(&mut h, &game, newcomer.pubkey(), now),
+        Err(program_error(ErrorCode::InvalidJoinDeadline))
+    );
+
+    let deadline = h.slot() + DEADLINE;
+    reopen(&mut h, &game, newcomer.pubkey(), deadline).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::WaitingForPlayer2);
+    assert_eq!(state.player2, newcomer.pubkey());
+    assert_eq!(state.join_deadline_slot, deadline);
+    assert_eq!(state.pot_lamports, STAKE);
+
+    // Прежнего приглашённого больше не ждут.
+    assert!(join(&mut h, &game.key, &game.player2).is_err());
+    join(&mut h, &game.key, &newcomer).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Active);
+    assert_eq!(state.pot_lamports, 2 * STAKE);
+}
+
+#[test]
+fn reopen_keeps_the_invitee_when_none_is_given() {
+    let mut h = Harness::new();
+    let game = invite(&mut h);
+    h.warp(DEADLINE + 1);
+    let deadline = h.slot() + DEADLINE;
+    reopen(&mut h, &game, Pubkey::default(), deadline).unwrap();
+    assert_eq!(h.game(&game.key).player2, game.player2.pubkey());
+    join(&mut h, &game.key, &game.player2).unwrap();
+}
+
+#[test]
+fn joined_or_cancelled_games_do_not_reopen() {
+    let mut h = Harness::new();
+    let joined = invite(&mut h);
+    join(&mut h, &joined.key, &joined.player2).unwrap();
+    let cancelled = invite(&mut h);
+    let state = h.game(&cancelled.key);
+    h.send(
+        ix::cancel_before_join_ix(&cancelled.key, &state),
+        &[&cancelled.player1],
+    )
+    .unwrap();
+
+    h.warp(DEADLINE + 1);
+    let deadline = h.slot() + DEADLINE;
+    for game in [&joined, &cancelled] {
+        assert_eq!(
+            reopen(&mut h, game, Pubkey::new_unique(), deadline),
+            Err(program_error(ErrorCode::GameNotWaitingForPlayer2))
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Сессионные ключи в LiteSVM: горячий ключ, выданный игроком через
+//! authorize_session_key, подписывает make_move за его ходы, но не за ходы
+//! соперника и не finish_game; после expiry_slot и после revoke_session_key
+//! ключ больше не действует.
+
+use backgammon_client::{ix, AccountMeta, Instruction, Pubkey};
+use backgammon_tests::{options, program_error, Game, Harness};
+use pooler::{accounts, instruction, ErrorCode, GameStatus};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+const MOVE_FEE: u64 = 100_000;
+const LIFETIME: u64 = 10;
+
+fn session_ix(
+    game: &Game,
+    player: &Keypair,
+    data: impl anchor_lang::InstructionData,
+) -> Instruction {
+    ix::build(
+        accounts::SessionKey {
+            game: game.key,
+            player: player.pubkey(),
+        },
+        data,
+    )
+}
+
+/// player1 выдаёт сессионный ключ до слота expiry_slot включительно.
+fn authorize(
+    h: &mut Harness,
+    game: &Game,
+    key: &Pubkey,
+    expiry_slot: u64,
+) -> Result<(), TransactionError> {
+    let authorize = session_ix(
+        game,
+        &game.player1,
+        instruction::AuthorizeSessionKey {
+            key: *key,
+            expiry_slot,
+        },
+    );
+    h.send(authorize, &[&game.player1])
+}
+
+/// Заменяет подпись основного ключа player на подпись signer.
+fn signed_by(mut ix: Instruction, player: &Pubkey, signer: &Pubkey) -> Instruction {
+    let meta = ix
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == *player && meta.is_signer)
+        .unwrap();
+    *meta = AccountMeta::new(*signer, true);
+    ix
+}
+
+/// Ход текущей стороны, подписанный signer вместо основного ключа.
+fn move_with(h: &mut Harness, game: &Game, signer: &Keypair) -> Result<(), TransactionError> {
+    let state = h.game(&game.key);
+    let make_move = signed_by(
+        h.move_ix(game),
+        &state.player_key(state.current_turn),
+        &signer.pubkey(),
+    );
+    h.send(make_move, &[signer])
+}
+
+#[test]
+fn session_key_moves_only_for_its_player() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, MOVE_FEE, options());
+    let session = h.player();
+    let expiry = h.slot() + LIFETIME;
+    authorize(&mut h, &game, &session.pubkey(), expiry).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.player1_session_key, session.pubkey());
+    assert_eq!(state.player1_session_expiry, expiry);
+    assert_eq!(state.current_turn, 1);
+
+    // Комиссию за ход платит подписавший его сессионный ключ.
+    let before = h.balance(&session.pubkey());
+    move_with(&mut h, &game, &session).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.move_index, 1);
+    assert_eq!(state.player1_fees_paid, MOVE_FEE);
+    assert_eq!(h.balance(&session.pubkey()), before - MOVE_FEE);
+
+    // Ход соперника ключ player1 подписать не может.
+    assert_eq!(state.current_turn, 2);
+    assert_eq!(
+        move_with(&mut h, &game, &session),
+        Err(program_error(ErrorCode::NotPlayersTurn))
+    );
+    move_with(&mut h, &game, &game.player2).unwrap();
+
+    // Банк двигает только основной ключ.
+    let (state, config) = (h.game(&game.key), h.config());
+    let finish = ix::finish_game_ix(&game.key, &state, &config, &game.player1.pubkey(), 1);
+    let finish = signed_by(finish, &game.player1.pubkey(), &session.pubkey());
+    assert_eq!(
+        h.send(finish, &[&session, &game.player2]),
+        Err(program_error(ErrorCode::InvalidPlayer1))
+    );
+    assert_eq!(h.game(&game.key).status, GameStatus::Active);
+}
+
+#[test]
+fn expired_session_key_is_rejected() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, MOVE_FEE, options());
+    let session = h.player();
+    let now = h.slot();
+    assert_eq!(
+        authorize(&mut h, &game, &session.pubkey(), now),
+        Err(program_error(ErrorCode::InvalidSessionKey))
+    );
+    authorize(&mut h, &game, &session.pubkey(), now + LIFETIME).unwrap();
+
+    // Слот expiry_slot ещё входит в срок действия.
+    h.warp(LIFETIME);
+    move_with(&mut h, &game, &session).unwrap();
+    move_with(&mut h, &game, &game.player2).unwrap();
+    h.warp(1);
+    assert_eq!(
+        move_with(&mut h, &game, &session),
+        Err(program_error(ErrorCode::NotPlayersTurn))
+    );
+    move_with(&mut h, &game, &game.player1).unwrap();
+}
+
+#[test]
+fn revoked_session_key_is_rejected() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, MOVE_FEE, options());
+    let session = h.player();
+    let expiry = h.slot() + LIFETIME;
+    authorize(&mut h, &game, &session.pubkey(), expiry).unwrap();
+
+    let stranger = h.player();
+    assert_eq!(
+        h.send(
+            session_ix(&game, &stranger, instruction::RevokeSessionKey {}),
+            &[&stranger],
+        ),
+        Err(program_error(ErrorCode::InvalidPlayer))
+    );
+    h.send(
+        session_ix(&game, &game.player1, instruction::RevokeSessionKey {}),
+        &[&game.player1],
+    )
+    .unwrap();
+    assert_eq!(h.game(&game.key).player1_session_key, Pubkey::default());
+    assert_eq!(
+        move_with(&mut h, &game, &session),
+        Err(program_error(ErrorCode::NotPlayersTurn))
+    );
+}
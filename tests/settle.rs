@@ -0,0 +1,121 @@
+//! Договорное завершение в LiteSVM: settle_game за подписью обоих игроков
+//! делит банк в согласованных долях (FinishReason::Negotiated, событие
+//! NegotiatedSettlement), доли, не дающие в сумме ровно банк, отклоняются с
+//! InvalidSettlement, а соглашение закрывает и открытый спор.
+
+use backgammon_client::events::GameEvent;
+use backgammon_client::{ix, pda, GameOptions, GameStatus, Instruction};
+use backgammon_tests::{program_error, Game, Harness};
+use pooler::{accounts, instruction, ErrorCode, FinishReason, NegotiatedSettlement};
+use solana_signer::Signer;
+
+const STAKE: u64 = 10_000_000;
+const POT: u64 = 2 * STAKE;
+
+fn settle_ix(h: &Harness, game: &Game, p1_amount: u64, p2_amount: u64) -> Instruction {
+    let state = h.game(&game.key);
+    ix::build(
+        accounts::SettleGame {
+            game: game.key,
+            player1: state.player1,
+            player2: state.player2,
+            config: pda::config(),
+            bot_vault: None,
+            escrow_agent: None,
+            team: None,
+            event_buffer: None,
+            fee_vault: None,
+            incinerator: None,
+            player1_splits: pda::payout_splits(&state.player1),
+            player2_splits: pda::payout_splits(&state.player2),
+            event_authority: pda::event_authority(),
+            program: pooler::ID,
+        },
+        instruction::SettleGame {
+            p1_amount,
+            p2_amount,
+        },
+    )
+}
+
+#[test]
+fn agreed_split_pays_exactly() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, GameOptions::default());
+    let (p1, p2) = (POT * 7 / 10, POT * 3 / 10);
+    let before = (
+        h.balance(&game.player1.pubkey()),
+        h.balance(&game.player2.pubkey()),
+    );
+
+    let settle = settle_ix(&h, &game, p1, p2);
+    let events = h
+        .send_with_events(settle, &[&game.player1, &game.player2])
+        .unwrap();
+    assert!(
+        events.contains(&GameEvent::NegotiatedSettlement(NegotiatedSettlement {
+            game: game.key,
+            player1_amount: p1,
+            player2_amount: p2,
+        }))
+    );
+    assert_eq!(h.balance(&game.player1.pubkey()), before.0 + p1);
+    assert_eq!(h.balance(&game.player2.pubkey()), before.1 + p2);
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.finish_reason, FinishReason::Negotiated);
+    assert_eq!(state.pot_lamports, 0);
+}
+
+#[test]
+fn split_off_by_one_is_rejected() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, GameOptions::default());
+    for (p1, p2) in [
+        (POT / 2, POT / 2 - 1),
+        (POT / 2 + 1, POT / 2),
+        (POT + 1, 0),
+        (POT + 1, u64::MAX - POT),
+    ] {
+        let settle = settle_ix(&h, &game, p1, p2);
+        assert_eq!(
+            h.send(settle, &[&game.player1, &game.player2]),
+            Err(program_error(ErrorCode::InvalidSettlement)),
+            "{p1} + {p2}"
+        );
+    }
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Active);
+    assert_eq!(state.pot_lamports, POT);
+
+    // Весь банк одному — тоже договорённость.
+    let settle = settle_ix(&h, &game, 0, POT);
+    h.send(settle, &[&game.player1, &game.player2]).unwrap();
+}
+
+#[test]
+fn settlement_clears_a_dispute() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, GameOptions::default());
+    let mut state = h.game(&game.key);
+    state.status = GameStatus::Disputed;
+    state.last_activity_slot = h.slot();
+    h.set_game(&game.key, &state);
+
+    // Пока решение арбитра ждёт апелляции, банк не делится.
+    state.resolution_winner = 1;
+    h.set_game(&game.key, &state);
+    let settle = settle_ix(&h, &game, STAKE, STAKE);
+    assert_eq!(
+        h.send(settle, &[&game.player1, &game.player2]),
+        Err(program_error(ErrorCode::ResolutionPending))
+    );
+
+    state.resolution_winner = 0;
+    h.set_game(&game.key, &state);
+    let settle = settle_ix(&h, &game, STAKE, STAKE);
+    h.send(settle, &[&game.player1, &game.player2]).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.finish_reason, FinishReason::Negotiated);
+}
@@ -0,0 +1,233 @@
+//! Пари зрителей в LiteSVM: сведённое пари после завершения игры забирает
+//! угадавшая сторона, при возврате банка игрокам каждая сторона получает
+//! свою ставку обратно, а несведённое пари автор забирает сам вместе с
+//! арендой.
+//!
+//! Комиссии транзакций платит отдельный кошелёк харнесса, поэтому балансы
+//! сторон сверяются точно.
+
+use backgammon_client::{ix, pda, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{
+    accounts, instruction, start_board, ErrorCode, GameStatus, RuleVariant, SideBet, SideBetStatus,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+const AMOUNT: u64 = 5_000_000;
+const BET_ID: u64 = 7;
+
+struct Bet {
+    game: Pubkey,
+    player1: Keypair,
+    player2: Keypair,
+    key: Pubkey,
+    bettor: Keypair,
+    counterparty: Keypair,
+}
+
+fn system_program() -> Pubkey {
+    anchor_lang::system_program::ID
+}
+
+/// Активная игра и открытое (ещё не сведённое) пари на победу player1.
+fn open_bet(h: &mut Harness) -> Bet {
+    let (player1, player2) = (h.player(), h.player());
+    let game = Keypair::new();
+    let options = GameOptions {
+        initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+        ..GameOptions::default()
+    };
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            1,
+            STAKE,
+            0,
+            &player2.pubkey(),
+            options,
+        ),
+        &[&game, &player1],
+    )
+    .unwrap();
+    let state = h.game(&game.pubkey());
+    h.send(
+        ix::join_game_ix(&game.pubkey(), &state, &player2.pubkey(), Vec::new(), None),
+        &[&player2],
+    )
+    .unwrap();
+
+    let (bettor, counterparty) = (h.player(), h.player());
+    let (key, _) = Pubkey::find_program_address(
+        &[
+            b"side_bet",
+            game.pubkey().as_ref(),
+            bettor.pubkey().as_ref(),
+            &BET_ID.to_le_bytes(),
+        ],
+        &pooler::ID,
+    );
+    h.send(
+        ix::build(
+            accounts::OpenSideBet {
+                game: game.pubkey(),
+                side_bet: key,
+                bettor: bettor.pubkey(),
+                system_program: system_program(),
+                config: pda::config(),
+            },
+            instruction::OpenSideBet {
+                bet_id: BET_ID,
+                predicted_winner: player1.pubkey(),
+                amount: AMOUNT,
+            },
+        ),
+        &[&bettor],
+    )
+    .unwrap();
+    Bet {
+        game: game.pubkey(),
+        player1,
+        player2,
+        key,
+        bettor,
+        counterparty,
+    }
+}
+
+fn match_bet(h: &mut Harness, bet: &Bet) {
+    h.send(
+        ix::build(
+            accounts::MatchSideBet {
+                game: bet.game,
+                side_bet: bet.key,
+                counterparty: bet.counterparty.pubkey(),
+                system_program: system_program(),
+            },
+            instruction::MatchSideBet {},
+        ),
+        &[&bet.counterparty],
+    )
+    .unwrap();
+}
+
+fn settle(h: &mut Harness, bet: &Bet) -> Result<(), TransactionError> {
+    let crank = h.player();
+    h.send(
+        ix::build(
+            accounts::SettleSideBet {
+                game: bet.game,
+                side_bet: bet.key,
+                bettor: bet.bettor.pubkey(),
+                counterparty: bet.counterparty.pubkey(),
+                payer: crank.pubkey(),
+                config: pda::config(),
+            },
+            instruction::SettleSideBet {},
+        ),
+        &[&crank],
+    )
+}
+
+fn reclaim(h: &mut Harness, bet: &Bet) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::ReclaimSideBet {
+                side_bet: bet.key,
+                bettor: bet.bettor.pubkey(),
+            },
+            instruction::ReclaimSideBet {},
+        ),
+        &[&bet.bettor],
+    )
+}
+
+/// Балансы автора и контрагента до открытия пари: автор уже внёс ставку и
+/// аренду, контрагент ещё ничего.
+fn stakes_before(h: &Harness, bet: &Bet) -> (u64, u64) {
+    let rent = h.rent(8 + SideBet::MAX_SIZE);
+    (
+        h.balance(&bet.bettor.pubkey()) + AMOUNT + rent,
+        h.balance(&bet.counterparty.pubkey()),
+    )
+}
+
+#[test]
+fn won_bet_pays_the_whole_pool() {
+    let mut h = Harness::new();
+    let bet = open_bet(&mut h);
+    let (bettor_before, counterparty_before) = stakes_before(&h, &bet);
+    match_bet(&mut h, &bet);
+    let state: SideBet = h.account(&bet.key);
+    assert_eq!(state.status, SideBetStatus::Matched);
+    assert_eq!(state.counterparty, bet.counterparty.pubkey());
+    assert_eq!(h.game(&bet.game).unsettled_side_bets, 1);
+
+    assert_eq!(
+        reclaim(&mut h, &bet),
+        Err(program_error(ErrorCode::SideBetNotOpen))
+    );
+    assert_eq!(
+        settle(&mut h, &bet),
+        Err(program_error(ErrorCode::GameNotFinished))
+    );
+
+    // Сведённое пари не мешает игрокам завершить игру.
+    let (state, config) = (h.game(&bet.game), h.config());
+    h.send(
+        ix::finish_game_ix(&bet.game, &state, &config, &bet.player1.pubkey(), 1),
+        &[&bet.player1, &bet.player2],
+    )
+    .unwrap();
+    settle(&mut h, &bet).unwrap();
+
+    assert_eq!(h.balance(&bet.bettor.pubkey()), bettor_before + AMOUNT);
+    assert_eq!(
+        h.balance(&bet.counterparty.pubkey()),
+        counterparty_before - AMOUNT
+    );
+    assert_eq!(h.balance(&bet.key), 0);
+    assert_eq!(h.game(&bet.game).unsettled_side_bets, 0);
+}
+
+#[test]
+fn refunded_game_returns_both_stakes() {
+    let mut h = Harness::new();
+    let bet = open_bet(&mut h);
+    let (bettor_before, counterparty_before) = stakes_before(&h, &bet);
+    match_bet(&mut h, &bet);
+
+    let state = h.game(&bet.game);
+    h.send(
+        ix::manual_refund_ix(&bet.game, &state, &h.config(), &bet.player2.pubkey()),
+        &[&bet.player2],
+    )
+    .unwrap();
+    let state = h.game(&bet.game);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.winner, Pubkey::default());
+    settle(&mut h, &bet).unwrap();
+
+    assert_eq!(h.balance(&bet.bettor.pubkey()), bettor_before);
+    assert_eq!(h.balance(&bet.counterparty.pubkey()), counterparty_before);
+    assert_eq!(h.balance(&bet.key), 0);
+}
+
+#[test]
+fn unmatched_bet_is_reclaimed_with_rent() {
+    let mut h = Harness::new();
+    let bet = open_bet(&mut h);
+    let (bettor_before, _) = stakes_before(&h, &bet);
+    assert_eq!(
+        settle(&mut h, &bet),
+        Err(program_error(ErrorCode::SideBetNotMatched))
+    );
+
+    reclaim(&mut h, &bet).unwrap();
+    assert_eq!(h.balance(&bet.bettor.pubkey()), bettor_before);
+    assert_eq!(h.balance(&bet.key), 0);
+    assert_eq!(h.game(&bet.game).unsettled_side_bets, 0);
+}
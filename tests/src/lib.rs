@@ -0,0 +1,308 @@
+//! Программа backgammon в LiteSVM: собранный pooler.so, инициализированный
+//! Config, игроки с балансом, отправка инструкций и чтение аккаунтов.
+//!
+//! Комиссию за транзакции платит отдельный ключ (`Harness::payer`), поэтому
+//! балансы игроков меняются только на ставки, комиссии за ходы, ренту и
+//! выплаты — их можно сверять до lamport.
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::prelude::Clock;
+use anchor_lang::solana_program::instruction::error::InstructionError;
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use backgammon_client::events::{parse_cpi_event, parse_events, GameEvent};
+use backgammon_client::{fetch, ix, pda, Config, GameOptions, GameState, Instruction, Pubkey};
+use litesvm::types::TransactionResult;
+use litesvm::LiteSVM;
+use pooler::{start_board, ErrorCode, RuleVariant};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+
+/// Программа из `anchor build`.
+pub const PROGRAM_SO: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy/pooler.so");
+
+/// Та же программа без фичи verbose-logs (релизная сборка):
+/// `cargo build-sbf --manifest-path programs/pooler/Cargo.toml
+/// --no-default-features --sbf-out-dir target/deploy-quiet`.
+pub const QUIET_PROGRAM_SO: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../target/deploy-quiet/pooler.so"
+);
+
+/// Программа-посредник cpi_caller из `anchor build` (CPI от имени PDA).
+pub const CPI_CALLER_SO: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../target/deploy/cpi_caller.so"
+);
+
+/// Стартовый баланс каждого игрока.
+pub const PLAYER_LAMPORTS: u64 = 10_000_000_000;
+
+/// Опции игры со стартовой доской классического варианта: доска ведётся с
+/// init_game, и make_move может повторять её как ход. Тесты дописывают свои
+/// поля через `GameOptions { .., ..options() }`.
+pub fn options() -> GameOptions {
+    GameOptions {
+        initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+        ..GameOptions::default()
+    }
+}
+
+/// Игра двух новых игроков: player1 создал её, player2 приглашён.
+pub struct Game {
+    pub key: Pubkey,
+    pub player1: Keypair,
+    pub player2: Keypair,
+}
+
+impl Game {
+    /// Кошелёк игрока с номером стороны turn.
+    pub fn side(&self, turn: u8) -> &Keypair {
+        if turn == 1 {
+            &self.player1
+        } else {
+            &self.player2
+        }
+    }
+}
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    /// Платит комиссии всех транзакций.
+    pub payer: Keypair,
+    pub admin: Keypair,
+}
+
+impl Harness {
+    /// SVM с загруженной программой и созданным Config.
+    pub fn new() -> Self {
+        Self::with_program(PROGRAM_SO)
+    }
+
+    /// То же, что new, но с программой из program_so.
+    pub fn with_program(program_so: &str) -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(pooler::ID, program_so)
+            .unwrap_or_else(|err| panic!("{program_so}: {err}; build the program first"));
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), PLAYER_LAMPORTS).unwrap();
+        let mut harness = Harness {
+            svm,
+            payer,
+            admin: Keypair::new(),
+        };
+        let admin = harness.player();
+        harness
+            .send(ix::init_config_ix(&admin.pubkey()), &[&admin])
+            .unwrap();
+        harness.admin = admin;
+        harness
+    }
+
+    /// Загружает cpi_caller рядом с backgammon.
+    pub fn add_cpi_caller(&mut self) {
+        self.svm
+            .add_program_from_file(cpi_caller::ID, CPI_CALLER_SO)
+            .unwrap_or_else(|err| panic!("{CPI_CALLER_SO}: {err}; build the program first"));
+    }
+
+    /// Новый игрок с балансом PLAYER_LAMPORTS.
+    pub fn player(&mut self) -> Keypair {
+        let player = Keypair::new();
+        self.svm.airdrop(&player.pubkey(), PLAYER_LAMPORTS).unwrap();
+        player
+    }
+
+    /// Лобби: новый player1 создаёт игру №1 со ставкой stake и комиссией
+    /// за ход move_fee, приглашая нового player2.
+    pub fn create_game(
+        &mut self,
+        stake: u64,
+        move_fee: u64,
+        options: GameOptions,
+    ) -> Result<Game, TransactionError> {
+        let (player1, player2) = (self.player(), self.player());
+        let game = Keypair::new();
+        self.send(
+            ix::init_game_ix(
+                &game.pubkey(),
+                &player1.pubkey(),
+                1,
+                stake,
+                move_fee,
+                &player2.pubkey(),
+                options,
+            ),
+            &[&game, &player1],
+        )?;
+        Ok(Game {
+            key: game.pubkey(),
+            player1,
+            player2,
+        })
+    }
+
+    /// Вход приглашённого player2 в игру.
+    pub fn join(&mut self, game: &Game) -> Result<(), TransactionError> {
+        let state = self.game(&game.key);
+        self.send(
+            ix::join_game_ix(&game.key, &state, &game.player2.pubkey(), Vec::new(), None),
+            &[&game.player2],
+        )
+    }
+
+    /// То же, что create_game, но player2 сразу входит: игра Active.
+    pub fn active_game(&mut self, stake: u64, move_fee: u64, options: GameOptions) -> Game {
+        let game = self.create_game(stake, move_fee, options).unwrap();
+        self.join(&game).unwrap();
+        game
+    }
+
+    /// make_move очередного игрока: доска прежняя, кубики [3, 1].
+    pub fn move_ix(&self, game: &Game) -> Instruction {
+        let (state, config) = (self.game(&game.key), self.config());
+        ix::make_move_ix(
+            &game.key,
+            &state,
+            &config,
+            state.board_points().unwrap(),
+            [3, 1],
+            [0; 32],
+            None,
+        )
+    }
+
+    /// Ход очередного игрока (move_ix), подписанный им самим.
+    pub fn make_move(&mut self, game: &Game) -> Result<(), TransactionError> {
+        let mover = game.side(self.game(&game.key).current_turn);
+        let ix = self.move_ix(game);
+        self.send(ix, &[mover])
+    }
+
+    /// Отправляет инструкцию; signers — все подписанты, кроме плательщика.
+    /// Блокхэш после каждой транзакции меняется, чтобы одинаковые
+    /// инструкции (например, два одинаковых хода) не считались повтором.
+    pub fn send(&mut self, ix: Instruction, signers: &[&Keypair]) -> Result<(), TransactionError> {
+        self.send_metered(ix, signers).map(drop)
+    }
+
+    /// То же, что send, но возвращает израсходованные вычислительные единицы
+    /// (CU) транзакции из одной инструкции.
+    pub fn send_metered(
+        &mut self,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<u64, TransactionError> {
+        self.submit(ix, signers)
+            .map(|meta| meta.compute_units_consumed)
+            .map_err(|failed| failed.err)
+    }
+
+    /// То же, что send, но возвращает и логи транзакции — в том числе
+    /// упавшей (в них остаются события, записанные до ошибки).
+    pub fn send_with_logs(
+        &mut self,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) -> (Result<(), TransactionError>, Vec<String>) {
+        match self.submit(ix, signers) {
+            Ok(meta) => (Ok(()), meta.logs),
+            Err(failed) => (Err(failed.err), failed.meta.logs),
+        }
+    }
+
+    /// То же, что send, но возвращает события программы: сначала записанные
+    /// emit! в логи, затем emit_cpi! из внутренних инструкций (их в логах нет).
+    pub fn send_with_events(
+        &mut self,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<Vec<GameEvent>, TransactionError> {
+        let meta = self.submit(ix, signers).map_err(|failed| failed.err)?;
+        let mut events = parse_events(&meta.logs);
+        events.extend(
+            meta.inner_instructions
+                .iter()
+                .flatten()
+                .filter_map(|inner| parse_cpi_event(&inner.instruction.data)),
+        );
+        Ok(events)
+    }
+
+    fn submit(&mut self, ix: Instruction, signers: &[&Keypair]) -> TransactionResult {
+        let mut all: Vec<&Keypair> = vec![&self.payer];
+        all.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.payer.pubkey()),
+            &all,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        self.svm.expire_blockhash();
+        result
+    }
+
+    pub fn balance(&self, address: &Pubkey) -> u64 {
+        self.svm.get_balance(address).unwrap_or(0)
+    }
+
+    /// Рента аккаунта с data_len байтами данных.
+    pub fn rent(&self, data_len: usize) -> u64 {
+        self.svm.minimum_balance_for_rent_exemption(data_len)
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self
+            .svm
+            .get_account(address)
+            .unwrap_or_else(|| panic!("account {address} does not exist"));
+        fetch::decode(&account.data).unwrap()
+    }
+
+    pub fn game(&self, address: &Pubkey) -> GameState {
+        self.account(address)
+    }
+
+    /// Перезаписывает состояние игры в обход программы — чтобы поставить
+    /// её в положение, до которого долго доходить инструкциями (спор и т.п.).
+    pub fn set_game(&mut self, address: &Pubkey, state: &GameState) {
+        self.set_account(address, state);
+    }
+
+    /// То же, что set_game, для любого существующего аккаунта программы
+    /// (например, рейтинг в PlayerStats).
+    pub fn set_account<T: AccountSerialize>(&mut self, address: &Pubkey, state: &T) {
+        let mut account = self.svm.get_account(address).unwrap();
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        self.svm.set_account(*address, account).unwrap();
+    }
+
+    pub fn config(&self) -> Config {
+        self.account(&pda::config())
+    }
+
+    pub fn slot(&self) -> u64 {
+        self.svm.get_sysvar::<Clock>().slot
+    }
+
+    /// Переводит часы на slots слотов вперёд.
+    pub fn warp(&mut self, slots: u64) {
+        let slot = self.slot() + slots;
+        self.svm.warp_to_slot(slot);
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ошибка программы в первой инструкции транзакции.
+pub fn program_error(code: ErrorCode) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(ERROR_CODE_OFFSET + code as u32))
+}
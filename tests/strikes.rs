@@ -0,0 +1,108 @@
+//! Страйки в LiteSVM: когда срок хода истёк, соперник вызывает
+//! record_strike — первые два страйка лишь засчитываются нарушителю и
+//! обновляют срок, третий (DEFAULT_STRIKE_LIMIT) отдаёт банк заявителю.
+//!
+//! Игры Casual: срок хода — MOVE_TIMEOUT_SLOTS, без запаса времени.
+
+use backgammon_client::{ix, pda, GameOptions, GameStatus};
+use backgammon_tests::{options, program_error, Game, Harness};
+use pooler::{
+    accounts, instruction, ErrorCode, FinishReason, DEFAULT_STRIKE_LIMIT, MOVE_TIMEOUT_SLOTS,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+
+/// Первым ходит player1. strike_limit = 0 — по умолчанию.
+fn strike_limit(strike_limit: u8) -> GameOptions {
+    GameOptions {
+        strike_limit,
+        ..options()
+    }
+}
+
+fn strike(h: &mut Harness, game: &Game, claimant: &Keypair) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::RecordStrike {
+                game: game.key,
+                claimant: claimant.pubkey(),
+                config: pda::config(),
+                bot_vault: None,
+                team: None,
+                time_control: None,
+                event_buffer: None,
+                season: None,
+                player1_stats: None,
+                player2_stats: None,
+                fee_vault: None,
+                incinerator: None,
+                claimant_splits: pda::payout_splits(&claimant.pubkey()),
+                event_authority: pda::event_authority(),
+                program: pooler::ID,
+            },
+            instruction::RecordStrike {},
+        ),
+        &[claimant],
+    )
+}
+
+#[test]
+fn third_strike_forfeits() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, strike_limit(0));
+    assert_eq!(
+        strike(&mut h, &game, &game.player2),
+        Err(program_error(ErrorCode::TimeoutNotReached))
+    );
+
+    for strikes in 1..DEFAULT_STRIKE_LIMIT {
+        h.warp(MOVE_TIMEOUT_SLOTS);
+        // Нарушитель не может засчитать страйк сам себе.
+        assert_eq!(
+            strike(&mut h, &game, &game.player1),
+            Err(program_error(ErrorCode::InvalidPlayer))
+        );
+        strike(&mut h, &game, &game.player2).unwrap();
+        let state = h.game(&game.key);
+        assert_eq!(state.status, GameStatus::Active);
+        assert_eq!(state.p1_timeout_strikes, strikes);
+        assert_eq!(state.p2_timeout_strikes, 0);
+        assert_eq!(state.last_activity_slot, h.slot());
+        assert_eq!(state.pot_lamports, 2 * STAKE);
+
+        // Страйк обновил срок: следующий — только после нового тайм-аута.
+        assert_eq!(
+            strike(&mut h, &game, &game.player2),
+            Err(program_error(ErrorCode::TimeoutNotReached))
+        );
+    }
+
+    h.warp(MOVE_TIMEOUT_SLOTS);
+    let before = h.balance(&game.player2.pubkey());
+    strike(&mut h, &game, &game.player2).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.finish_reason, FinishReason::Strikes);
+    assert_eq!(state.winner, game.player2.pubkey());
+    assert_eq!(state.p1_timeout_strikes, DEFAULT_STRIKE_LIMIT);
+    assert_eq!(state.pot_lamports, 0);
+    assert_eq!(h.balance(&game.player2.pubkey()), before + 2 * STAKE);
+    assert_eq!(
+        strike(&mut h, &game, &game.player2),
+        Err(program_error(ErrorCode::GameNotActive))
+    );
+}
+
+#[test]
+fn strike_limit_from_options() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, strike_limit(1));
+    h.warp(MOVE_TIMEOUT_SLOTS);
+    strike(&mut h, &game, &game.player2).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Finished);
+    assert_eq!(state.winner, game.player2.pubkey());
+}
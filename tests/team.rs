@@ -0,0 +1,194 @@
+//! Консультационная игра 2 на 2 в LiteSVM: напарники вносят неравные доли
+//! ставки через add_teammate, ходы за сторону подписывает любой из двух её
+//! ключей, а после force_refund claim_team_share возвращает каждому из
+//! четырёх кошельков ровно его вклад — долю ставки и оплаченные им комиссии.
+
+use backgammon_client::{ix, pda, AccountMeta, GameOptions, GameStatus, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{
+    accounts, instruction, start_board, ErrorCode, RuleVariant, TeamState,
+    FORCE_REFUND_TIMEOUT_SLOTS,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 40_000_000;
+const MOVE_FEE: u64 = 1_000_000;
+/// Доли напарников: четверть ставки у первой стороны, половина у второй.
+const MATE1_SHARE: u64 = STAKE / 4;
+const MATE2_SHARE: u64 = STAKE / 2;
+
+struct Teams {
+    game: Pubkey,
+    /// player1, его напарник, player2, его напарник — в порядке member.
+    members: [Keypair; 4],
+}
+
+fn add_teammate(h: &mut Harness, game: &Pubkey, player: &Keypair, mate: &Keypair, share: u64) {
+    h.send(
+        ix::build(
+            accounts::AddTeammate {
+                game: *game,
+                team: pda::team(game),
+                player: player.pubkey(),
+                teammate: mate.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+            },
+            instruction::AddTeammate {
+                share_lamports: share,
+            },
+        ),
+        &[player, mate],
+    )
+    .unwrap();
+}
+
+fn teams(h: &mut Harness) -> Teams {
+    let members = [h.player(), h.player(), h.player(), h.player()];
+    let [player1, mate1, player2, mate2] = &members;
+    let game = Keypair::new();
+    let options = GameOptions {
+        initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+        ..GameOptions::default()
+    };
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            1,
+            STAKE,
+            MOVE_FEE,
+            &player2.pubkey(),
+            options,
+        ),
+        &[&game, player1],
+    )
+    .unwrap();
+    let state = h.game(&game.pubkey());
+    h.send(
+        ix::join_game_ix(&game.pubkey(), &state, &player2.pubkey(), Vec::new(), None),
+        &[player2],
+    )
+    .unwrap();
+    add_teammate(h, &game.pubkey(), player1, mate1, MATE1_SHARE);
+    add_teammate(h, &game.pubkey(), player2, mate2, MATE2_SHARE);
+    Teams {
+        game: game.pubkey(),
+        members,
+    }
+}
+
+/// Ход текущей стороны, подписанный signer вместо основного ключа.
+fn move_by(h: &mut Harness, teams: &Teams, signer: &Keypair) -> Result<(), TransactionError> {
+    let (state, config) = (h.game(&teams.game), h.config());
+    let mut make_move = ix::make_move_ix(
+        &teams.game,
+        &state,
+        &config,
+        state.board_points().unwrap(),
+        [3, 1],
+        [0; 32],
+        None,
+    );
+    let main = state.player_key(state.current_turn);
+    let meta = make_move
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == main && meta.is_signer)
+        .unwrap();
+    *meta = AccountMeta::new(signer.pubkey(), true);
+    h.send(make_move, &[signer])
+}
+
+fn claim(h: &mut Harness, teams: &Teams, member: u8) -> Result<(), TransactionError> {
+    let crank = h.player();
+    h.send(
+        ix::build(
+            accounts::ClaimTeamShare {
+                game: teams.game,
+                team: pda::team(&teams.game),
+                recipient: teams.members[member as usize].pubkey(),
+                payer: crank.pubkey(),
+                config: pda::config(),
+            },
+            instruction::ClaimTeamShare { member },
+        ),
+        &[&crank],
+    )
+}
+
+#[test]
+fn either_teammate_moves_for_their_side() {
+    let mut h = Harness::new();
+    let teams = teams(&mut h);
+    let [player1, mate1, player2, mate2] = &teams.members;
+
+    // Напарник соперника за чужую сторону не ходит.
+    assert_eq!(
+        move_by(&mut h, &teams, mate2),
+        Err(program_error(ErrorCode::NotPlayersTurn))
+    );
+    move_by(&mut h, &teams, mate1).unwrap();
+    move_by(&mut h, &teams, player2).unwrap();
+    move_by(&mut h, &teams, player1).unwrap();
+    move_by(&mut h, &teams, mate2).unwrap();
+    assert_eq!(h.game(&teams.game).move_index, 4);
+}
+
+#[test]
+fn force_refund_returns_each_wallet_its_contribution() {
+    let mut h = Harness::new();
+    let teams = teams(&mut h);
+    let [player1, mate1, player2, mate2] = &teams.members;
+
+    // Комиссии платят по-разному: mate1 — три хода, player1 — ни одного.
+    for signer in [mate1, mate2, mate1, player2, mate1] {
+        move_by(&mut h, &teams, signer).unwrap();
+    }
+    let team: TeamState = h.account(&pda::team(&teams.game));
+    let expected = [
+        STAKE - MATE1_SHARE,
+        MATE1_SHARE + 3 * MOVE_FEE,
+        STAKE - MATE2_SHARE + MOVE_FEE,
+        MATE2_SHARE + MOVE_FEE,
+    ];
+    assert_eq!(team.contributions, expected);
+    let state = h.game(&teams.game);
+    assert_eq!(state.pot_lamports, expected.iter().sum::<u64>());
+
+    let first_slot = state.last_activity_slot + FORCE_REFUND_TIMEOUT_SLOTS;
+    h.warp(first_slot - h.slot());
+    let before: Vec<_> = teams
+        .members
+        .iter()
+        .map(|m| h.balance(&m.pubkey()))
+        .collect();
+    h.send(
+        ix::force_refund_ix(&teams.game, &state, &h.config()),
+        &[player1, player2],
+    )
+    .unwrap();
+    assert_eq!(h.game(&teams.game).status, GameStatus::Finished);
+    // Возврат сторон ждёт раздела в TeamState.
+    let team: TeamState = h.account(&pda::team(&teams.game));
+    assert_eq!(
+        team.received,
+        [expected[0] + expected[1], expected[2] + expected[3]]
+    );
+
+    for member in 0..4u8 {
+        claim(&mut h, &teams, member).unwrap();
+    }
+    for (i, member) in teams.members.iter().enumerate() {
+        assert_eq!(
+            h.balance(&member.pubkey()),
+            before[i] + expected[i],
+            "member {i}"
+        );
+    }
+    assert_eq!(
+        claim(&mut h, &teams, 1),
+        Err(program_error(ErrorCode::TeamShareClaimed))
+    );
+}
@@ -0,0 +1,252 @@
+//! Турнир на выбывание в LiteSVM: четыре игрока платят взнос, организатор
+//! запускает сетку, партии каждого тура играются обычными играми со ссылкой
+//! на турнир и тур, report_result берёт победителя из GameState.winner, а
+//! после финала призовой фонд получает чемпион.
+
+use backgammon_client::{ix, pda, GameOptions, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{
+    accounts, instruction, start_board, ErrorCode, RuleVariant, Tournament, TournamentMode,
+    TournamentStatus,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const ENTRY_FEE: u64 = 25_000_000;
+const STAKE: u64 = 1_000_000;
+const PLAYERS: u8 = 4;
+
+struct Bracket {
+    key: Pubkey,
+    organizer: Keypair,
+    players: Vec<Keypair>,
+}
+
+impl Bracket {
+    fn state(&self, h: &Harness) -> Tournament {
+        h.account(&self.key)
+    }
+
+    fn keypair(&self, player: &Pubkey) -> &Keypair {
+        self.players.iter().find(|p| p.pubkey() == *player).unwrap()
+    }
+}
+
+fn system_program() -> Pubkey {
+    anchor_lang::system_program::ID
+}
+
+fn organizer_ix(
+    h: &mut Harness,
+    bracket: &Bracket,
+    data: impl anchor_lang::InstructionData,
+) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::TournamentOrganizer {
+                tournament: bracket.key,
+                organizer: bracket.organizer.pubkey(),
+            },
+            data,
+        ),
+        &[&bracket.organizer],
+    )
+}
+
+/// Турнир с PLAYERS записавшимися игроками, ещё не запущенный.
+fn registered(h: &mut Harness) -> Bracket {
+    let organizer = h.player();
+    let tournament_id = 1u64;
+    let (key, _) = Pubkey::find_program_address(
+        &[
+            b"tournament",
+            organizer.pubkey().as_ref(),
+            &tournament_id.to_le_bytes(),
+        ],
+        &pooler::ID,
+    );
+    h.send(
+        ix::build(
+            accounts::CreateTournament {
+                tournament: key,
+                organizer: organizer.pubkey(),
+                system_program: system_program(),
+                config: pda::config(),
+            },
+            instruction::CreateTournament {
+                tournament_id,
+                entry_fee: ENTRY_FEE,
+                max_players: PLAYERS,
+                mode: TournamentMode::Knockout,
+                swiss_rounds: 0,
+            },
+        ),
+        &[&organizer],
+    )
+    .unwrap();
+
+    let players: Vec<_> = (0..PLAYERS).map(|_| h.player()).collect();
+    for player in &players {
+        h.send(
+            ix::build(
+                accounts::JoinTournament {
+                    tournament: key,
+                    player: player.pubkey(),
+                    system_program: system_program(),
+                    config: pda::config(),
+                },
+                instruction::JoinTournament {},
+            ),
+            &[player],
+        )
+        .unwrap();
+    }
+    Bracket {
+        key,
+        organizer,
+        players,
+    }
+}
+
+/// Партия тура round между a и b, выигранная winner; возвращает игру.
+fn play_match(
+    h: &mut Harness,
+    bracket: &Bracket,
+    round: u8,
+    a: &Pubkey,
+    b: &Pubkey,
+    winner: &Pubkey,
+) -> Pubkey {
+    let (player1, player2) = (bracket.keypair(a), bracket.keypair(b));
+    let game = Keypair::new();
+    let options = GameOptions {
+        tournament: bracket.key,
+        tournament_round: round,
+        initial_board: start_board(RuleVariant::Standard, 0).unwrap(),
+        ..GameOptions::default()
+    };
+    h.send(
+        ix::init_game_ix(&game.pubkey(), a, u64::from(round), STAKE, 0, b, options),
+        &[&game, player1],
+    )
+    .unwrap();
+    let state = h.game(&game.pubkey());
+    h.send(
+        ix::join_game_ix(&game.pubkey(), &state, b, Vec::new(), None),
+        &[player2],
+    )
+    .unwrap();
+    let (state, config) = (h.game(&game.pubkey()), h.config());
+    h.send(
+        ix::finish_game_ix(&game.pubkey(), &state, &config, winner, 1),
+        &[player1, player2],
+    )
+    .unwrap();
+    game.pubkey()
+}
+
+fn report(h: &mut Harness, bracket: &Bracket, game: &Pubkey) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::ReportResult {
+                tournament: bracket.key,
+                game: *game,
+                organizer: bracket.organizer.pubkey(),
+            },
+            instruction::ReportResult {},
+        ),
+        &[&bracket.organizer],
+    )
+}
+
+#[test]
+fn four_player_bracket_end_to_end() {
+    let mut h = Harness::new();
+    let bracket = registered(&mut h);
+    let state = bracket.state(&h);
+    assert_eq!(state.player_count, PLAYERS);
+    assert_eq!(state.prize_pool, PLAYERS as u64 * ENTRY_FEE);
+
+    organizer_ix(&mut h, &bracket, instruction::StartTournament {}).unwrap();
+    let state = bracket.state(&h);
+    assert_eq!(state.status, TournamentStatus::Running);
+    assert_eq!((state.round, state.bracket_size), (1, PLAYERS));
+    let seeds: Vec<Pubkey> = bracket.players.iter().map(Keypair::pubkey).collect();
+    assert_eq!(&state.bracket[..PLAYERS as usize], &seeds[..]);
+
+    // Полуфиналы: в первой паре побеждает второй посев, во второй — первый.
+    let semi_a = play_match(&mut h, &bracket, 1, &seeds[0], &seeds[1], &seeds[1]);
+    let semi_b = play_match(&mut h, &bracket, 1, &seeds[2], &seeds[3], &seeds[2]);
+    report(&mut h, &bracket, &semi_a).unwrap();
+    assert_eq!(
+        organizer_ix(&mut h, &bracket, instruction::AdvanceRound {}),
+        Err(program_error(ErrorCode::RoundNotComplete))
+    );
+    assert_eq!(
+        report(&mut h, &bracket, &semi_a),
+        Err(program_error(ErrorCode::ResultAlreadyReported))
+    );
+    report(&mut h, &bracket, &semi_b).unwrap();
+    assert_eq!(&bracket.state(&h).round_winners[..2], &[seeds[1], seeds[2]]);
+
+    organizer_ix(&mut h, &bracket, instruction::AdvanceRound {}).unwrap();
+    let state = bracket.state(&h);
+    assert_eq!((state.round, state.bracket_size), (2, 2));
+    assert_eq!(&state.bracket[..2], &[seeds[1], seeds[2]]);
+
+    // Игра прошлого тура в финал не засчитывается.
+    assert_eq!(
+        report(&mut h, &bracket, &semi_b),
+        Err(program_error(ErrorCode::GameNotInTournament))
+    );
+    let fin = play_match(&mut h, &bracket, 2, &seeds[1], &seeds[2], &seeds[2]);
+    report(&mut h, &bracket, &fin).unwrap();
+    organizer_ix(&mut h, &bracket, instruction::AdvanceRound {}).unwrap();
+    let state = bracket.state(&h);
+    assert_eq!(state.status, TournamentStatus::Finished);
+    assert_eq!(state.champion, seeds[2]);
+
+    let before = h.balance(&seeds[2]);
+    h.send(
+        ix::build(
+            accounts::SettleTournament {
+                tournament: bracket.key,
+                champion: seeds[2],
+                config: pda::config(),
+            },
+            instruction::SettleTournament {},
+        ),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(h.balance(&seeds[2]), before + PLAYERS as u64 * ENTRY_FEE);
+    assert_eq!(bracket.state(&h).prize_pool, 0);
+}
+
+#[test]
+fn full_bracket_closes_registration() {
+    let mut h = Harness::new();
+    let bracket = registered(&mut h);
+    let late = h.player();
+    assert_eq!(
+        h.send(
+            ix::build(
+                accounts::JoinTournament {
+                    tournament: bracket.key,
+                    player: late.pubkey(),
+                    system_program: system_program(),
+                    config: pda::config(),
+                },
+                instruction::JoinTournament {},
+            ),
+            &[&late],
+        ),
+        Err(program_error(ErrorCode::TournamentFull))
+    );
+    organizer_ix(&mut h, &bracket, instruction::StartTournament {}).unwrap();
+    assert_eq!(
+        organizer_ix(&mut h, &bracket, instruction::StartTournament {}),
+        Err(program_error(ErrorCode::TournamentNotInRegistration))
+    );
+}
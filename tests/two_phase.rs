@@ -0,0 +1,144 @@
+//! Двухфазные ходы в LiteSVM: в игре с two_phase_moves make_move лишь
+//! записывает pending_board, не передавая очередь. Соперник подтверждает ход
+//! (ack_move) или оспаривает его (reject_move — игра в Disputed), а ход без
+//! ответа после pending_deadline_slot подтверждает кто угодно через
+//! finalize_pending.
+
+use backgammon_client::{ix, GameOptions, GameStatus};
+use backgammon_tests::{options, program_error, Game, Harness};
+use pooler::{accounts, instruction, ErrorCode};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+
+fn two_phase() -> GameOptions {
+    GameOptions {
+        two_phase_moves: true,
+        ..options()
+    }
+}
+
+/// Ход player1: одна шашка сдвигается на соседний пункт. Возвращает новую доску.
+fn propose(h: &mut Harness, game: &Game) -> Result<[i8; 24], TransactionError> {
+    let (state, config) = (h.game(&game.key), h.config());
+    let mut board = state.board_points().unwrap();
+    let from = board.iter().position(|&p| p > 1).unwrap();
+    assert!(board[from + 1] >= 0);
+    board[from] -= 1;
+    board[from + 1] += 1;
+    h.send(
+        ix::make_move_ix(&game.key, &state, &config, board, [3, 1], [0; 32], None),
+        &[&game.player1],
+    )?;
+    Ok(board)
+}
+
+fn respond(
+    h: &mut Harness,
+    game: &Game,
+    player: &Keypair,
+    data: impl anchor_lang::InstructionData,
+) -> Result<(), TransactionError> {
+    h.send(
+        ix::build(
+            accounts::RespondMove {
+                game: game.key,
+                player: player.pubkey(),
+            },
+            data,
+        ),
+        &[player],
+    )
+}
+
+fn finalize(h: &mut Harness, game: &Game) -> Result<(), TransactionError> {
+    let crank = h.player();
+    h.send(
+        ix::build(
+            accounts::FinalizePending {
+                game: game.key,
+                payer: crank.pubkey(),
+            },
+            instruction::FinalizePending {},
+        ),
+        &[&crank],
+    )
+}
+
+#[test]
+fn acknowledged_move_becomes_official() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, two_phase());
+    let official = h.game(&game.key).board_points().unwrap();
+    let board = propose(&mut h, &game).unwrap();
+
+    // Ход ждёт ответа: доска и очередь прежние.
+    let state = h.game(&game.key);
+    assert!(state.move_pending);
+    assert_eq!(state.pending_board_points().unwrap(), board);
+    assert_eq!(state.pending_dice, [3, 1]);
+    assert_eq!(state.board_points().unwrap(), official);
+    assert_eq!((state.move_index, state.current_turn), (0, 1));
+    assert_eq!(
+        propose(&mut h, &game).map(drop),
+        Err(program_error(ErrorCode::MovePending))
+    );
+    assert_eq!(
+        respond(&mut h, &game, &game.player1, instruction::AckMove {}),
+        Err(program_error(ErrorCode::InvalidPlayer))
+    );
+
+    respond(&mut h, &game, &game.player2, instruction::AckMove {}).unwrap();
+    let state = h.game(&game.key);
+    assert!(!state.move_pending);
+    assert_eq!(state.board_points().unwrap(), board);
+    assert_eq!(state.dice, [3, 1]);
+    assert_eq!((state.move_index, state.current_turn), (1, 2));
+    assert_eq!(
+        respond(&mut h, &game, &game.player2, instruction::AckMove {}),
+        Err(program_error(ErrorCode::NoMovePending))
+    );
+}
+
+#[test]
+fn rejected_move_opens_a_dispute() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, two_phase());
+    let official = h.game(&game.key).board_points().unwrap();
+    propose(&mut h, &game).unwrap();
+
+    respond(&mut h, &game, &game.player2, instruction::RejectMove {}).unwrap();
+    let state = h.game(&game.key);
+    assert_eq!(state.status, GameStatus::Disputed);
+    assert_eq!(state.board_points().unwrap(), official);
+    assert_eq!((state.move_index, state.current_turn), (0, 1));
+    assert_eq!(
+        finalize(&mut h, &game),
+        Err(program_error(ErrorCode::GameNotActive))
+    );
+}
+
+#[test]
+fn unanswered_move_finalizes_after_the_deadline() {
+    let mut h = Harness::new();
+    let game = h.active_game(STAKE, 0, two_phase());
+    let board = propose(&mut h, &game).unwrap();
+    let deadline = h.game(&game.key).pending_deadline_slot;
+    assert!(deadline > h.slot());
+
+    // Слот pending_deadline_slot ещё за соперником.
+    h.warp(deadline - h.slot());
+    assert_eq!(
+        finalize(&mut h, &game),
+        Err(program_error(ErrorCode::TimeoutNotReached))
+    );
+    h.warp(1);
+    finalize(&mut h, &game).unwrap();
+    let state = h.game(&game.key);
+    assert!(!state.move_pending);
+    assert_eq!(state.board_points().unwrap(), board);
+    assert_eq!((state.move_index, state.current_turn), (1, 2));
+    assert_eq!(state.last_activity_slot, h.slot());
+}
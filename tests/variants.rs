@@ -0,0 +1,80 @@
+//! Варианты правил в LiteSVM: init_game принимает для каждого варианта его
+//! стартовую расстановку и сохраняет её вместе с rule_variant, а игру
+//! хайпергаммона с доской обычных нард (15 шашек вместо 3) отклоняет.
+
+use backgammon_client::{ix, GameOptions, GameStatus, Pubkey};
+use backgammon_tests::{program_error, Harness};
+use pooler::{start_board, ErrorCode, RuleVariant};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+const STAKE: u64 = 10_000_000;
+
+fn create(
+    h: &mut Harness,
+    rule_variant: RuleVariant,
+    initial_board: [i8; 24],
+) -> Result<(Pubkey, Keypair, Keypair), TransactionError> {
+    let (player1, player2) = (h.player(), h.player());
+    let game = Keypair::new();
+    let options = GameOptions {
+        rule_variant,
+        initial_board,
+        ..GameOptions::default()
+    };
+    h.send(
+        ix::init_game_ix(
+            &game.pubkey(),
+            &player1.pubkey(),
+            1,
+            STAKE,
+            0,
+            &player2.pubkey(),
+            options,
+        ),
+        &[&game, &player1],
+    )?;
+    Ok((game.pubkey(), player1, player2))
+}
+
+#[test]
+fn each_variant_starts_from_its_board() {
+    let mut h = Harness::new();
+    for variant in [
+        RuleVariant::Standard,
+        RuleVariant::Nackgammon,
+        RuleVariant::Hypergammon,
+    ] {
+        let board = start_board(variant, 0).unwrap();
+        let (game, player1, player2) = create(&mut h, variant, board).unwrap();
+        let state = h.game(&game);
+        assert_eq!(state.rule_variant, variant);
+        assert_eq!(state.board_points().unwrap(), board);
+
+        h.send(
+            ix::join_game_ix(&game, &state, &player2.pubkey(), Vec::new(), None),
+            &[&player2],
+        )
+        .unwrap();
+        let (state, config) = (h.game(&game), h.config());
+        assert_eq!(state.status, GameStatus::Active);
+        h.send(
+            ix::make_move_ix(&game, &state, &config, board, [3, 1], [0; 32], None),
+            &[&player1],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn hypergammon_rejects_a_standard_board() {
+    let mut h = Harness::new();
+    let standard = start_board(RuleVariant::Standard, 0).unwrap();
+    assert_eq!(
+        create(&mut h, RuleVariant::Hypergammon, standard).map(drop),
+        Err(program_error(ErrorCode::InvalidInitialBoard))
+    );
+    // Та же доска годится для обычной игры.
+    create(&mut h, RuleVariant::Standard, standard).unwrap();
+}
@@ -0,0 +1,136 @@
+//! Глобальные инварианты, которые проверяются после каждого шага.
+//!
+//! Снимок берётся до и после транзакции по одному и тому же набору адресов —
+//! всем аккаунтам её инструкции, кроме плательщика комиссии транзакции.
+//! Нарушение — паника: для фаззера это найденный сбой.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::Rent;
+use backgammon_client::{fetch, GameState, GameStatus, Pubkey};
+use pooler::FinishReason;
+
+/// Rent в SVM фаззера — значение по умолчанию.
+fn rent_min(data_len: usize) -> u64 {
+    Rent::default().minimum_balance(data_len)
+}
+
+/// Аккаунт игры в снимке: разобранное состояние и размер данных для ренты.
+#[derive(Clone)]
+pub struct GameSnapshot {
+    pub state: GameState,
+    pub data_len: usize,
+    pub lamports: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct Snapshot {
+    pub lamports: BTreeMap<Pubkey, u64>,
+    pub games: BTreeMap<Pubkey, GameSnapshot>,
+}
+
+impl Snapshot {
+    /// Записывает аккаунт; GameState распознаётся по владельцу и дискриминатору.
+    pub fn record(&mut self, address: Pubkey, lamports: u64, owner: &Pubkey, data: &[u8]) {
+        self.lamports.insert(address, lamports);
+        if *owner != pooler::ID {
+            return;
+        }
+        if let Ok(state) = fetch::decode::<GameState>(data) {
+            self.games.insert(
+                address,
+                GameSnapshot {
+                    state,
+                    data_len: data.len(),
+                    lamports,
+                },
+            );
+        }
+    }
+
+    fn total_lamports(&self) -> u128 {
+        self.lamports.values().map(|&l| l as u128).sum()
+    }
+}
+
+/// Переход статуса допустим: завершённая или отменённая игра больше не
+/// меняет статус, из спора выход только в Finished.
+pub fn transition_allowed(from: GameStatus, to: GameStatus) -> bool {
+    use GameStatus::*;
+    from == to
+        || matches!(
+            (from, to),
+            (WaitingForPlayer2, Active | Cancelled | Finished)
+                | (Active, Finished | Disputed)
+                | (Disputed, Finished)
+        )
+}
+
+/// Банк открытой игры складывается из взносов: депозиты, комиссии за ходы и
+/// чаевые. При FeeRoute комиссии уходят мимо банка и в сумму не входят.
+fn check_pot(address: &Pubkey, game: &GameSnapshot) {
+    let state = &game.state;
+    if state.status.is_over() {
+        assert_eq!(
+            state.pot_lamports, 0,
+            "{address}: pot left in a closed game"
+        );
+        assert_ne!(
+            state.finish_reason,
+            FinishReason::NotFinished,
+            "{address}: closed game without a finish reason"
+        );
+        return;
+    }
+    let mut contributions =
+        state.player1_deposit as u128 + state.player2_deposit as u128 + state.tips_lamports as u128;
+    if !state.fee_routed {
+        contributions += state.player1_fees_paid as u128 + state.player2_fees_paid as u128;
+    }
+    assert_eq!(
+        state.pot_lamports as u128, contributions,
+        "{address}: pot_lamports does not match contributions"
+    );
+    let held = rent_min(game.data_len) as u128
+        + state.pot_lamports as u128
+        + state.p1_take_reserve as u128
+        + state.p2_take_reserve as u128;
+    assert!(
+        game.lamports as u128 >= held,
+        "{address}: game holds {} lamports, owes {held}",
+        game.lamports
+    );
+}
+
+/// Проверяет шаг: lamports не создаются и не исчезают, отклонённая
+/// транзакция ничего не меняет, банк каждой игры сходится со взносами,
+/// статусы меняются только по допустимым рёбрам.
+pub fn check_step(label: &str, before: &Snapshot, after: &Snapshot, succeeded: bool) {
+    assert_eq!(
+        before.total_lamports(),
+        after.total_lamports(),
+        "{label}: lamports minted or destroyed"
+    );
+    if !succeeded {
+        assert_eq!(
+            before.lamports, after.lamports,
+            "{label}: rejected transaction moved lamports"
+        );
+    }
+    for (address, game) in &after.games {
+        check_pot(address, game);
+        let to = game.state.status;
+        match before.games.get(address) {
+            Some(prev) => assert!(
+                transition_allowed(prev.state.status, to),
+                "{label}: {address} went {:?} -> {to:?}",
+                prev.state.status
+            ),
+            None => assert_eq!(
+                to,
+                GameStatus::WaitingForPlayer2,
+                "{label}: {address} created in {to:?}"
+            ),
+        }
+    }
+}
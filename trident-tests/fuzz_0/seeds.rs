@@ -0,0 +1,22 @@
+//! Известные трудные случаи, которые фаззер подмешивает к случайным
+//! значениям: нулевая ставка, предельные суммы, один аккаунт на двух местах.
+
+/// Суммы на границах: ноль, один lamport, рента пустого аккаунта и края u64.
+pub const AMOUNTS: [u64; 6] = [0, 1, 890_880, u64::MAX / 2, u64::MAX - 1, u64::MAX];
+
+/// Доля шагов (из 100), в которых сумма берётся из AMOUNTS.
+pub const EDGE_AMOUNT_PCT: u64 = 50;
+
+/// Доля шагов (из 100), в которых один и тот же аккаунт подставляется на
+/// место двух разных (игрок на месте соперника, player1 на месте player2).
+pub const ALIAS_PCT: u64 = 20;
+
+/// Множители результата для finish_game: допустимые 1..=3 и соседние.
+pub const RESULT_MULTIPLIERS: [u8; 5] = [0, 1, 2, 3, u8::MAX];
+
+/// Баланс, который получает каждый игрок: хватает на любые ставки,
+/// кроме самых больших из AMOUNTS.
+pub const PLAYER_LAMPORTS: u64 = 1_000_000_000_000;
+
+/// Сколько игроков участвует в фаззинге.
+pub const PLAYERS: usize = 4;
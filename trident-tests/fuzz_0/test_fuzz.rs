@@ -0,0 +1,258 @@
+//! Фаззинг набора инструкций: случайные, но типово корректные
+//! последовательности init_game / join_game / make_move / finish_game /
+//! cancel_before_join / force_refund / manual_refund со случайными суммами,
+//! досками и подменой аккаунтов.
+//!
+//! Инструкции собираются билдерами SDK (`backgammon_client::ix`), после
+//! каждой транзакции проверяются инварианты из [`invariants`]. Запуск:
+//! `anchor build`, затем `trident fuzz run fuzz_0` в каталоге trident-tests.
+
+mod invariants;
+mod seeds;
+
+use backgammon_client::{fetch, ix, pda, Config, GameOptions, GameState, Instruction, Pubkey};
+use invariants::{check_step, Snapshot};
+use trident_fuzz::fuzzing::*;
+
+#[derive(FuzzTestMethods)]
+struct FuzzTest {
+    trident: Trident,
+    fuzz_accounts: AccountAddresses,
+    players: Vec<Pubkey>,
+    games: Vec<Pubkey>,
+    config: Option<Config>,
+}
+
+#[derive(Default)]
+struct AccountAddresses {
+    player: AddressStorage,
+}
+
+#[flow_executor]
+impl FuzzTest {
+    fn new() -> Self {
+        Self {
+            trident: Trident::default(),
+            fuzz_accounts: AccountAddresses::default(),
+            players: Vec::new(),
+            games: Vec::new(),
+            config: None,
+        }
+    }
+
+    #[init]
+    fn start(&mut self) {
+        self.players.clear();
+        self.games.clear();
+        for _ in 0..seeds::PLAYERS {
+            let player = self.fuzz_accounts.player.insert(&mut self.trident, None);
+            self.trident.airdrop(&player, seeds::PLAYER_LAMPORTS);
+            self.players.push(player);
+        }
+        let admin = self.players[0];
+        // Config создаётся один раз за SVM; повторный init_config отклоняется.
+        self.step(ix::init_config_ix(&admin), "init_config");
+        self.config = self.account::<Config>(&pda::config());
+    }
+
+    #[flow]
+    fn init_game(&mut self) {
+        let player1 = self.player();
+        let player2 = if self.chance(seeds::ALIAS_PCT) {
+            player1
+        } else {
+            self.player()
+        };
+        let game = self.trident.random_pubkey();
+        let game_id = self.trident.random_from_range(0..u64::MAX);
+        let stake = self.amount();
+        let move_fee = self.amount();
+        let ix = ix::init_game_ix(
+            &game,
+            &player1,
+            game_id,
+            stake,
+            move_fee,
+            &player2,
+            GameOptions::default(),
+        );
+        if self.step(ix, "init_game") {
+            self.games.push(game);
+        }
+    }
+
+    #[flow]
+    fn join_game(&mut self) {
+        let Some((game, state)) = self.game() else {
+            return;
+        };
+        let player2 = if self.chance(seeds::ALIAS_PCT) {
+            state.player1
+        } else {
+            self.player()
+        };
+        self.step(
+            ix::join_game_ix(&game, &state, &player2, Vec::new(), None),
+            "join_game",
+        );
+    }
+
+    #[flow]
+    fn make_move(&mut self) {
+        let (Some((game, state)), Some(config)) = (self.game(), self.config.clone()) else {
+            return;
+        };
+        let board = if self.chance(50) {
+            state.board_points().unwrap_or_default()
+        } else {
+            std::array::from_fn(|_| self.trident.random_from_range(i8::MIN..i8::MAX))
+        };
+        let dice = [
+            self.trident.random_from_range(0..8u8),
+            self.trident.random_from_range(0..8u8),
+        ];
+        let mut ix = ix::make_move_ix(&game, &state, &config, board, dice, [0; 32], None);
+        if self.chance(seeds::ALIAS_PCT) {
+            // Ходящий на месте соперника или чужой ключ на месте ходящего.
+            ix.accounts[2].pubkey = ix.accounts[1].pubkey;
+            ix.accounts[1].pubkey = self.player();
+        }
+        self.step(ix, "make_move");
+    }
+
+    #[flow]
+    fn finish_game(&mut self) {
+        let (Some((game, state)), Some(config)) = (self.game(), self.config.clone()) else {
+            return;
+        };
+        let winner = match self.trident.random_from_range(0..3u8) {
+            0 => state.player1,
+            1 => state.player2,
+            _ => self.player(),
+        };
+        let multiplier = seeds::RESULT_MULTIPLIERS[self
+            .trident
+            .random_from_range(0..seeds::RESULT_MULTIPLIERS.len())];
+        let mut ix = ix::finish_game_ix(&game, &state, &config, &winner, multiplier);
+        if self.chance(seeds::ALIAS_PCT) {
+            ix.accounts[2].pubkey = ix.accounts[1].pubkey;
+        }
+        self.step(ix, "finish_game");
+    }
+
+    #[flow]
+    fn cancel_before_join(&mut self) {
+        let Some((game, state)) = self.game() else {
+            return;
+        };
+        self.step(
+            ix::cancel_before_join_ix(&game, &state),
+            "cancel_before_join",
+        );
+    }
+
+    #[flow]
+    fn force_refund(&mut self) {
+        let Some((game, state)) = self.game() else {
+            return;
+        };
+        let mut ix = ix::force_refund_ix(&game, &state);
+        if self.chance(seeds::ALIAS_PCT) {
+            ix.accounts[1].pubkey = ix.accounts[2].pubkey;
+        }
+        self.step(ix, "force_refund");
+    }
+
+    #[flow]
+    fn manual_refund(&mut self) {
+        let Some((game, state)) = self.game() else {
+            return;
+        };
+        let requester = match self.trident.random_from_range(0..3u8) {
+            0 => state.player1,
+            1 => state.player2,
+            _ => self.player(),
+        };
+        self.step(
+            ix::manual_refund_ix(&game, &state, &requester),
+            "manual_refund",
+        );
+    }
+
+    #[end]
+    fn end(&mut self) {}
+}
+
+impl FuzzTest {
+    fn chance(&mut self, pct: u64) -> bool {
+        self.trident.random_from_range(0..100u64) < pct
+    }
+
+    fn player(&mut self) -> Pubkey {
+        self.players[self.trident.random_from_range(0..self.players.len())]
+    }
+
+    /// Сумма для ставки или комиссии: граничная из seeds::AMOUNTS или случайная.
+    fn amount(&mut self) -> u64 {
+        if self.chance(seeds::EDGE_AMOUNT_PCT) {
+            seeds::AMOUNTS[self.trident.random_from_range(0..seeds::AMOUNTS.len())]
+        } else {
+            self.trident.random_from_range(0..seeds::PLAYER_LAMPORTS)
+        }
+    }
+
+    fn account<T: anchor_lang::AccountDeserialize>(&mut self, address: &Pubkey) -> Option<T> {
+        let account = self.trident.get_account(address);
+        fetch::decode(account.data()).ok()
+    }
+
+    /// Случайная из созданных игр вместе с её текущим состоянием.
+    fn game(&mut self) -> Option<(Pubkey, GameState)> {
+        if self.games.is_empty() {
+            return None;
+        }
+        let game = self.games[self.trident.random_from_range(0..self.games.len())];
+        Some((game, self.account(&game)?))
+    }
+
+    fn snapshot(&mut self, addresses: &[Pubkey]) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        for address in addresses {
+            let account = self.trident.get_account(address);
+            snapshot.record(
+                *address,
+                account.lamports(),
+                account.owner(),
+                account.data(),
+            );
+        }
+        snapshot
+    }
+
+    /// Выполняет инструкцию и проверяет инварианты; true, если транзакция
+    /// прошла.
+    fn step(&mut self, ix: Instruction, label: &str) -> bool {
+        let payer = self.trident.payer().pubkey();
+        let mut addresses: Vec<Pubkey> = ix
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey)
+            .filter(|address| *address != payer)
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+
+        let before = self.snapshot(&addresses);
+        let succeeded = self
+            .trident
+            .process_transaction(&[ix], Some(label))
+            .is_success();
+        let after = self.snapshot(&addresses);
+        check_step(label, &before, &after, succeeded);
+        succeeded
+    }
+}
+
+fn main() {
+    FuzzTest::fuzz(1000, 50);
+}